@@ -0,0 +1,211 @@
+//! Analytics exporter.
+//!
+//! Exports verified blocks, their transactions and account updates as newline-delimited
+//! JSON or Parquet files partitioned by the day of block verification, so they can be
+//! loaded into BigQuery, Spark and similar tools:
+//!
+//! ```text
+//! <output>/blocks/date=2021-02-18/0000000001.parquet
+//! <output>/transactions/date=2021-02-18/0000000001.parquet
+//! <output>/account_updates/date=2021-02-18/0000000001.parquet
+//! ```
+//!
+//! The number of the last exported block is stored in the `cursor` file of the output
+//! directory, so every run continues from where the previous one stopped. In the `watch`
+//! mode the exporter keeps running and exports new blocks as they get verified.
+
+use std::{collections::BTreeMap, fs, path::PathBuf, time::Duration};
+
+use chrono::NaiveDate;
+use structopt::StructOpt;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::BlockNumber;
+
+use crate::{
+    records::{AccountUpdateRecord, BlockRecord, Record, TransactionRecord},
+    writer::{Format, PartitionedWriter},
+};
+
+mod records;
+mod writer;
+
+const CURSOR_FILE: &str = "cursor";
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "zkSync analytics exporter",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+struct Opt {
+    /// Directory to write the exported files to
+    #[structopt(long)]
+    output: PathBuf,
+
+    /// Format of the exported files: `ndjson` or `parquet`
+    #[structopt(long, default_value = "ndjson")]
+    format: Format,
+
+    /// Maximum amount of blocks exported into a single set of files
+    #[structopt(long, default_value = "100")]
+    batch_size: u32,
+
+    /// Keep running and export blocks as they get verified
+    #[structopt(long)]
+    watch: bool,
+
+    /// Interval between the checks for the newly verified blocks in the `watch` mode, in seconds
+    #[structopt(long, default_value = "60")]
+    poll_interval: u64,
+}
+
+/// Records of a batch of blocks grouped by the day of block verification.
+#[derive(Debug, Default)]
+struct Partition {
+    blocks: Vec<BlockRecord>,
+    transactions: Vec<TransactionRecord>,
+    account_updates: Vec<AccountUpdateRecord>,
+}
+
+fn load_cursor(opt: &Opt) -> anyhow::Result<BlockNumber> {
+    let path = opt.output.join(CURSOR_FILE);
+    if !path.exists() {
+        return Ok(BlockNumber(0));
+    }
+    let cursor = fs::read_to_string(path)?.trim().parse()?;
+    Ok(BlockNumber(cursor))
+}
+
+fn save_cursor(opt: &Opt, block: BlockNumber) -> anyhow::Result<()> {
+    let path = opt.output.join(CURSOR_FILE);
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, block.to_string())?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+async fn load_partitions(
+    storage: &mut StorageProcessor<'_>,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> anyhow::Result<BTreeMap<NaiveDate, Partition>> {
+    let mut partitions: BTreeMap<NaiveDate, Partition> = BTreeMap::new();
+
+    for block_number in *from_block..=*to_block {
+        let block_number = BlockNumber(block_number);
+        let details = storage
+            .chain()
+            .block_schema()
+            .load_block_range(block_number, 1)
+            .await?
+            .into_iter()
+            .find(|details| details.block_number == i64::from(*block_number))
+            .ok_or_else(|| anyhow::anyhow!("Block {} is not found", block_number))?;
+        let date = details
+            .verified_at
+            .ok_or_else(|| anyhow::anyhow!("Block {} is not verified", block_number))?
+            .date()
+            .naive_utc();
+
+        let transactions = storage
+            .chain()
+            .block_schema()
+            .get_block_transactions(block_number)
+            .await?;
+        let account_updates = storage
+            .chain()
+            .state_schema()
+            .load_state_diff_for_block(block_number)
+            .await?;
+
+        let partition = partitions.entry(date).or_default();
+        partition.blocks.push(details.into());
+        // Transactions are loaded in the reverse order.
+        partition
+            .transactions
+            .extend(transactions.into_iter().rev().map(TransactionRecord::from));
+        partition
+            .account_updates
+            .extend(
+                account_updates
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (account_id, update))| {
+                        AccountUpdateRecord::new(block_number, idx, *account_id, update)
+                    }),
+            );
+    }
+
+    Ok(partitions)
+}
+
+fn write_table<R: Record>(
+    writer: &PartitionedWriter,
+    date: NaiveDate,
+    first_block: BlockNumber,
+    records: &[R],
+) -> anyhow::Result<()> {
+    writer.write(date, *first_block, records)?;
+    vlog::debug!(
+        "Exported {} records into the `{}` table for {}",
+        records.len(),
+        R::TABLE,
+        date
+    );
+    Ok(())
+}
+
+/// Exports all the verified blocks after the cursor. Returns the number of the last exported block.
+async fn export(pool: &ConnectionPool, opt: &Opt) -> anyhow::Result<BlockNumber> {
+    let writer = PartitionedWriter::new(&opt.output, opt.format);
+    let mut last_exported = load_cursor(opt)?;
+
+    let mut storage = pool.access_storage().await?;
+    let last_verified = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await?;
+
+    while last_exported < last_verified {
+        let from_block = last_exported + 1;
+        let to_block = BlockNumber(std::cmp::min(
+            *last_exported + opt.batch_size,
+            *last_verified,
+        ));
+
+        let partitions = load_partitions(&mut storage, from_block, to_block).await?;
+        for (date, partition) in partitions {
+            write_table(&writer, date, from_block, &partition.blocks)?;
+            write_table(&writer, date, from_block, &partition.transactions)?;
+            write_table(&writer, date, from_block, &partition.account_updates)?;
+        }
+
+        save_cursor(opt, to_block)?;
+        vlog::info!("Exported blocks {}..={}", from_block, to_block);
+        last_exported = to_block;
+    }
+
+    Ok(last_exported)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    vlog::init();
+    let opt = Opt::from_args();
+    anyhow::ensure!(opt.batch_size > 0, "Batch size must be positive");
+    fs::create_dir_all(&opt.output)?;
+
+    let pool = ConnectionPool::new(Some(1));
+    loop {
+        let last_exported = export(&pool, &opt).await?;
+        if !opt.watch {
+            vlog::info!(
+                "All the verified blocks up to {} are exported",
+                last_exported
+            );
+            return Ok(());
+        }
+        tokio::time::delay_for(Duration::from_secs(opt.poll_interval)).await;
+    }
+}
@@ -0,0 +1,227 @@
+//! Flat records exported for every table.
+//!
+//! All the records have a fixed set of scalar columns, so the same data can be written
+//! both as newline-delimited JSON and as Parquet. Nested values (operations and account
+//! updates) are stored as JSON-encoded strings.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use zksync_storage::chain::block::records::{BlockDetails, BlockTransactionItem};
+use zksync_types::{AccountId, AccountUpdate, BlockNumber};
+
+/// Values of a single column for a batch of records.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Int64(Vec<Option<i64>>),
+    Bool(Vec<Option<bool>>),
+    Utf8(Vec<Option<String>>),
+}
+
+/// Record type written into its own table.
+pub trait Record: Serialize {
+    /// Name of the table, used as a directory name.
+    const TABLE: &'static str;
+    /// Parquet message type describing the columns in the order returned by `columns`.
+    const PARQUET_SCHEMA: &'static str;
+
+    /// Splits the records into columns.
+    fn columns(records: &[Self]) -> Vec<Column>
+    where
+        Self: Sized;
+}
+
+fn timestamp(time: &DateTime<Utc>) -> i64 {
+    time.timestamp_millis()
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BlockRecord {
+    pub block_number: i64,
+    pub new_state_root: String,
+    pub block_size: i64,
+    pub commit_tx_hash: Option<String>,
+    pub verify_tx_hash: Option<String>,
+    pub committed_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl From<BlockDetails> for BlockRecord {
+    fn from(details: BlockDetails) -> Self {
+        Self {
+            block_number: details.block_number,
+            new_state_root: hex_string(&details.new_state_root),
+            block_size: details.block_size,
+            commit_tx_hash: details.commit_tx_hash.as_deref().map(hex_string),
+            verify_tx_hash: details.verify_tx_hash.as_deref().map(hex_string),
+            committed_at: details.committed_at,
+            verified_at: details.verified_at,
+        }
+    }
+}
+
+impl Record for BlockRecord {
+    const TABLE: &'static str = "blocks";
+    const PARQUET_SCHEMA: &'static str = "
+        message block {
+            OPTIONAL INT64 block_number;
+            OPTIONAL BYTE_ARRAY new_state_root (UTF8);
+            OPTIONAL INT64 block_size;
+            OPTIONAL BYTE_ARRAY commit_tx_hash (UTF8);
+            OPTIONAL BYTE_ARRAY verify_tx_hash (UTF8);
+            OPTIONAL INT64 committed_at (TIMESTAMP_MILLIS);
+            OPTIONAL INT64 verified_at (TIMESTAMP_MILLIS);
+        }
+    ";
+
+    fn columns(records: &[Self]) -> Vec<Column> {
+        vec![
+            Column::Int64(records.iter().map(|r| Some(r.block_number)).collect()),
+            Column::Utf8(
+                records
+                    .iter()
+                    .map(|r| Some(r.new_state_root.clone()))
+                    .collect(),
+            ),
+            Column::Int64(records.iter().map(|r| Some(r.block_size)).collect()),
+            Column::Utf8(records.iter().map(|r| r.commit_tx_hash.clone()).collect()),
+            Column::Utf8(records.iter().map(|r| r.verify_tx_hash.clone()).collect()),
+            Column::Int64(
+                records
+                    .iter()
+                    .map(|r| Some(timestamp(&r.committed_at)))
+                    .collect(),
+            ),
+            Column::Int64(
+                records
+                    .iter()
+                    .map(|r| r.verified_at.as_ref().map(timestamp))
+                    .collect(),
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TransactionRecord {
+    pub tx_hash: String,
+    pub block_number: i64,
+    pub tx_type: Option<String>,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// JSON-encoded transaction or priority operation.
+    pub op: String,
+}
+
+impl From<BlockTransactionItem> for TransactionRecord {
+    fn from(item: BlockTransactionItem) -> Self {
+        Self {
+            tx_type: item.op["type"].as_str().map(String::from),
+            op: item.op.to_string(),
+            tx_hash: item.tx_hash,
+            block_number: item.block_number,
+            success: item.success,
+            fail_reason: item.fail_reason,
+            created_at: item.created_at,
+        }
+    }
+}
+
+impl Record for TransactionRecord {
+    const TABLE: &'static str = "transactions";
+    const PARQUET_SCHEMA: &'static str = "
+        message transaction {
+            OPTIONAL BYTE_ARRAY tx_hash (UTF8);
+            OPTIONAL INT64 block_number;
+            OPTIONAL BYTE_ARRAY tx_type (UTF8);
+            OPTIONAL BOOLEAN success;
+            OPTIONAL BYTE_ARRAY fail_reason (UTF8);
+            OPTIONAL INT64 created_at (TIMESTAMP_MILLIS);
+            OPTIONAL BYTE_ARRAY op (UTF8);
+        }
+    ";
+
+    fn columns(records: &[Self]) -> Vec<Column> {
+        vec![
+            Column::Utf8(records.iter().map(|r| Some(r.tx_hash.clone())).collect()),
+            Column::Int64(records.iter().map(|r| Some(r.block_number)).collect()),
+            Column::Utf8(records.iter().map(|r| r.tx_type.clone()).collect()),
+            Column::Bool(records.iter().map(|r| r.success).collect()),
+            Column::Utf8(records.iter().map(|r| r.fail_reason.clone()).collect()),
+            Column::Int64(
+                records
+                    .iter()
+                    .map(|r| Some(timestamp(&r.created_at)))
+                    .collect(),
+            ),
+            Column::Utf8(records.iter().map(|r| Some(r.op.clone())).collect()),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AccountUpdateRecord {
+    pub block_number: i64,
+    pub account_id: i64,
+    /// Position of the update among the updates of the block.
+    pub update_index: i64,
+    pub update_type: String,
+    /// JSON-encoded `AccountUpdate`.
+    pub update: String,
+}
+
+impl AccountUpdateRecord {
+    pub fn new(
+        block_number: BlockNumber,
+        update_index: usize,
+        account_id: AccountId,
+        update: &AccountUpdate,
+    ) -> Self {
+        let update_type = match update {
+            AccountUpdate::Create { .. } => "Create",
+            AccountUpdate::Delete { .. } => "Delete",
+            AccountUpdate::UpdateBalance { .. } => "UpdateBalance",
+            AccountUpdate::ChangePubKeyHash { .. } => "ChangePubKeyHash",
+        };
+
+        Self {
+            block_number: i64::from(*block_number),
+            account_id: i64::from(*account_id),
+            update_index: update_index as i64,
+            update_type: update_type.to_string(),
+            update: serde_json::to_string(update).expect("AccountUpdate is serializable"),
+        }
+    }
+}
+
+impl Record for AccountUpdateRecord {
+    const TABLE: &'static str = "account_updates";
+    const PARQUET_SCHEMA: &'static str = "
+        message account_update {
+            OPTIONAL INT64 block_number;
+            OPTIONAL INT64 account_id;
+            OPTIONAL INT64 update_index;
+            OPTIONAL BYTE_ARRAY update_type (UTF8);
+            OPTIONAL BYTE_ARRAY update (UTF8);
+        }
+    ";
+
+    fn columns(records: &[Self]) -> Vec<Column> {
+        vec![
+            Column::Int64(records.iter().map(|r| Some(r.block_number)).collect()),
+            Column::Int64(records.iter().map(|r| Some(r.account_id)).collect()),
+            Column::Int64(records.iter().map(|r| Some(r.update_index)).collect()),
+            Column::Utf8(
+                records
+                    .iter()
+                    .map(|r| Some(r.update_type.clone()))
+                    .collect(),
+            ),
+            Column::Utf8(records.iter().map(|r| Some(r.update.clone())).collect()),
+        ]
+    }
+}
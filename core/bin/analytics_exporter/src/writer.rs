@@ -0,0 +1,238 @@
+//! Writing the exported records into the day-partitioned files.
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use chrono::NaiveDate;
+use parquet::{
+    column::writer::{ColumnWriter, ColumnWriterImpl},
+    data_type::{ByteArray, DataType},
+    file::{
+        properties::WriterProperties,
+        writer::{FileWriter, RowGroupWriter, SerializedFileWriter},
+    },
+    schema::parser::parse_message_type,
+};
+
+use crate::records::{Column, Record};
+
+/// Format of the exported files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Newline-delimited JSON, one record per line.
+    Ndjson,
+    /// Apache Parquet, one row group per file.
+    Parquet,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Ndjson => "ndjson",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ndjson" | "json" => Ok(Self::Ndjson),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(anyhow::anyhow!("Unknown export format: {}", other)),
+        }
+    }
+}
+
+/// Writes the records into the `<table>/date=<YYYY-MM-DD>/` directories under the output root.
+#[derive(Debug, Clone)]
+pub struct PartitionedWriter {
+    root: PathBuf,
+    format: Format,
+}
+
+impl PartitionedWriter {
+    pub fn new(root: impl Into<PathBuf>, format: Format) -> Self {
+        Self {
+            root: root.into(),
+            format,
+        }
+    }
+
+    /// Path of the file for the batch starting with the given block.
+    ///
+    /// File name depends only on the first block of the batch, so a batch that was
+    /// exported but not recorded in the cursor overwrites its own files when re-exported.
+    pub fn file_path<R: Record>(&self, date: NaiveDate, first_block: u32) -> PathBuf {
+        self.root
+            .join(R::TABLE)
+            .join(format!("date={}", date.format("%Y-%m-%d")))
+            .join(format!("{:010}.{}", first_block, self.format.extension()))
+    }
+
+    /// Writes the records of a single partition. Nothing is written for an empty set of records.
+    pub fn write<R: Record>(
+        &self,
+        date: NaiveDate,
+        first_block: u32,
+        records: &[R],
+    ) -> anyhow::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.file_path::<R>(date, first_block);
+        fs::create_dir_all(path.parent().expect("Partition file always has a parent"))?;
+
+        // Write into a temporary file first, so the readers never observe partially written files.
+        let tmp_path = path.with_extension("tmp");
+        match self.format {
+            Format::Ndjson => write_ndjson(&tmp_path, records)?,
+            Format::Parquet => write_parquet(&tmp_path, records)?,
+        }
+        fs::rename(tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+fn write_ndjson<R: Record>(path: &Path, records: &[R]) -> anyhow::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for record in records {
+        serde_json::to_writer(&mut file, record)?;
+        file.write_all(b"\n")?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+fn write_column<T: DataType>(
+    writer: &mut ColumnWriterImpl<T>,
+    values: Vec<Option<T::T>>,
+) -> anyhow::Result<()> {
+    // All the columns are declared as optional, so the definition level marks
+    // whether the value is present.
+    let def_levels: Vec<i16> = values.iter().map(|value| value.is_some() as i16).collect();
+    let values: Vec<T::T> = values.into_iter().flatten().collect();
+    writer.write_batch(&values, Some(&def_levels), None)?;
+    Ok(())
+}
+
+fn write_parquet<R: Record>(path: &Path, records: &[R]) -> anyhow::Result<()> {
+    let schema = Arc::new(parse_message_type(R::PARQUET_SCHEMA)?);
+    let props = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, props)?;
+
+    let mut row_group = writer.next_row_group()?;
+    let mut columns = R::columns(records).into_iter();
+    while let Some(mut column_writer) = row_group.next_column()? {
+        let column = columns
+            .next()
+            .expect("Parquet schema and record columns mismatch");
+        match (&mut column_writer, column) {
+            (ColumnWriter::Int64ColumnWriter(writer), Column::Int64(values)) => {
+                write_column(writer, values)?
+            }
+            (ColumnWriter::BoolColumnWriter(writer), Column::Bool(values)) => {
+                write_column(writer, values)?
+            }
+            (ColumnWriter::ByteArrayColumnWriter(writer), Column::Utf8(values)) => write_column(
+                writer,
+                values
+                    .into_iter()
+                    .map(|value| value.map(|s| ByteArray::from(s.into_bytes())))
+                    .collect(),
+            )?,
+            _ => anyhow::bail!("Column type mismatch in the `{}` table", R::TABLE),
+        }
+        row_group.close_column(column_writer)?;
+    }
+    writer.close_row_group(row_group)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::records::AccountUpdateRecord;
+    use zksync_types::{AccountId, AccountUpdate, Address, BlockNumber, Nonce};
+
+    fn records() -> Vec<AccountUpdateRecord> {
+        (0..3)
+            .map(|idx| {
+                AccountUpdateRecord::new(
+                    BlockNumber(5),
+                    idx,
+                    AccountId(idx as u32),
+                    &AccountUpdate::Create {
+                        address: Address::repeat_byte(idx as u8),
+                        nonce: Nonce(0),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Checks that records are written into the day partitions as newline-delimited JSON.
+    #[test]
+    fn ndjson_partitions() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let writer = PartitionedWriter::new(dir.path(), Format::Ndjson);
+        let date = NaiveDate::from_ymd(2021, 2, 18);
+
+        writer.write(date, 5, &records())?;
+        // Empty partitions are skipped.
+        writer.write::<AccountUpdateRecord>(date.succ(), 5, &[])?;
+
+        let path = dir
+            .path()
+            .join("account_updates/date=2021-02-18/0000000005.ndjson");
+        assert_eq!(writer.file_path::<AccountUpdateRecord>(date, 5), path);
+
+        let content = fs::read_to_string(path)?;
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2]["account_id"], 2);
+        assert_eq!(lines[2]["update_type"], "Create");
+
+        assert!(!dir.path().join("account_updates/date=2021-02-19").exists());
+        Ok(())
+    }
+
+    /// Checks that the records can be read back from the Parquet file.
+    #[test]
+    fn parquet_roundtrip() -> anyhow::Result<()> {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = tempfile::tempdir()?;
+        let writer = PartitionedWriter::new(dir.path(), Format::Parquet);
+        let date = NaiveDate::from_ymd(2021, 2, 18);
+        writer.write(date, 5, &records())?;
+
+        let reader = SerializedFileReader::new(File::open(
+            writer.file_path::<AccountUpdateRecord>(date, 5),
+        )?)?;
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 3);
+        assert_eq!(
+            reader
+                .metadata()
+                .file_metadata()
+                .schema_descr()
+                .num_columns(),
+            5
+        );
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 // Built-in deps
 use std::str::FromStr;
 // Workspace deps
+use zksync_cold_storage::ColdStorage;
 use zksync_crypto::proof::EncodedProofPlonk;
 use zksync_storage::{data_restore::records::NewBlockEvent, StorageProcessor};
 use zksync_types::{
+    ethereum::NewTokenEvent,
     AccountId, Action, BlockNumber, Operation, Token, TokenGenesisListItem, TokenId,
     {block::Block, AccountUpdate, AccountUpdates, ZkSyncOp},
 };
@@ -13,7 +15,7 @@ use crate::storage_interactor::StoredTreeState;
 use crate::{
     data_restore_driver::StorageUpdateState,
     events::BlockEvent,
-    events_state::{EventsState, NewTokenEvent},
+    events_state::EventsState,
     rollup_ops::RollupOpsBlock,
     storage_interactor::{
         block_event_into_stored_block_event, stored_block_event_into_block_event,
@@ -32,17 +34,42 @@ impl From<&NewTokenEvent> for zksync_storage::data_restore::records::NewTokenEve
 
 pub struct DatabaseStorageInteractor<'a> {
     storage: StorageProcessor<'a>,
+    cold_storage: Option<ColdStorage>,
 }
 
 impl<'a> DatabaseStorageInteractor<'a> {
     pub fn new(storage: StorageProcessor<'a>) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            cold_storage: None,
+        }
+    }
+
+    /// Sets the cold storage the proofs of the restored blocks are loaded from.
+    pub fn with_cold_storage(mut self, cold_storage: ColdStorage) -> Self {
+        self.cold_storage = Some(cold_storage);
+        self
     }
 
     pub fn storage(&mut self) -> &mut StorageProcessor<'a> {
         &mut self.storage
     }
 
+    /// Returns the proof of the block archived in the cold storage. Proofs are not published
+    /// on L1, so the blocks without the archived proof are restored with the default one.
+    async fn restored_proof(&self, block_number: BlockNumber) -> EncodedProofPlonk {
+        let cold_storage = match &self.cold_storage {
+            Some(cold_storage) => cold_storage,
+            None => return EncodedProofPlonk::default(),
+        };
+
+        cold_storage
+            .restore_proof(block_number)
+            .await
+            .expect("Cant load the proof from the cold storage")
+            .unwrap_or_default()
+    }
+
     /// Returns last watched ethereum block number from storage
     pub async fn get_last_watched_block_number_from_storage(&mut self) -> u64 {
         let last_watched_block_number_string = self
@@ -77,6 +104,7 @@ impl StorageInteractor for DatabaseStorageInteractor<'_> {
     }
 
     async fn update_tree_state(&mut self, block: Block, accounts_updated: AccountUpdates) {
+        let proof = self.restored_proof(block.block_number).await;
         let mut transaction = self
             .storage
             .start_transaction()
@@ -91,7 +119,7 @@ impl StorageInteractor for DatabaseStorageInteractor<'_> {
 
         let verify_op = Operation {
             action: Action::Verify {
-                proof: Box::new(EncodedProofPlonk::default()),
+                proof: Box::new(proof),
             },
             block: block.clone(),
             id: None,
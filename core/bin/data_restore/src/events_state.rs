@@ -8,29 +8,7 @@ use web3::{Transport, Web3};
 // Workspace deps
 use crate::eth_tx_helpers::get_block_number_from_ethereum_transaction;
 use crate::events::{BlockEvent, EventType};
-use zksync_types::{Address, TokenId};
-
-#[derive(Debug)]
-pub struct NewTokenEvent {
-    pub address: Address,
-    pub id: TokenId,
-}
-
-impl TryFrom<Log> for NewTokenEvent {
-    type Error = anyhow::Error;
-
-    fn try_from(event: Log) -> Result<NewTokenEvent, anyhow::Error> {
-        if event.topics.len() != 3 {
-            return Err(format_err!("Failed to parse NewTokenEvent: {:#?}", event));
-        }
-        Ok(NewTokenEvent {
-            address: Address::from_slice(&event.topics[1].as_fixed_bytes()[12..]),
-            id: TokenId(
-                U256::from_big_endian(&event.topics[2].as_fixed_bytes()[..]).as_u32() as u16,
-            ),
-        })
-    }
-}
+use zksync_types::ethereum::NewTokenEvent;
 
 /// Rollup contract events states description
 #[derive(Debug, Clone)]
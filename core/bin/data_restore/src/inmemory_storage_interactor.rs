@@ -5,14 +5,14 @@ use web3::types::Address;
 
 use zksync_types::block::Block;
 use zksync_types::{
-    Account, AccountId, AccountMap, AccountUpdate, AccountUpdates, Action, BlockNumber,
-    EncodedProofPlonk, Operation, Token, TokenGenesisListItem, TokenId,
+    ethereum::NewTokenEvent, Account, AccountId, AccountMap, AccountUpdate, AccountUpdates, Action,
+    BlockNumber, EncodedProofPlonk, Operation, Token, TokenGenesisListItem, TokenId,
 };
 
 use crate::{
     data_restore_driver::StorageUpdateState,
     events::{BlockEvent, EventType},
-    events_state::{EventsState, NewTokenEvent},
+    events_state::EventsState,
     rollup_ops::RollupOpsBlock,
     storage_interactor::StorageInteractor,
     storage_interactor::StoredTreeState,
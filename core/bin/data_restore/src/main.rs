@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use serde::Deserialize;
 use structopt::StructOpt;
 use web3::transports::Http;
+use zksync_cold_storage::{ColdStorage, FileObjectStore};
 use zksync_config::configs::{ChainConfig, ContractsConfig as EnvContractsConfig, ETHClientConfig};
 use zksync_crypto::convert::FeConvert;
 use zksync_storage::ConnectionPool;
@@ -35,6 +38,10 @@ struct Opt {
     #[structopt(long)]
     final_hash: Option<String>,
 
+    /// Directory of the cold storage to restore the proofs of the blocks from
+    #[structopt(long)]
+    cold_storage: Option<String>,
+
     /// Sets the web3 API to be used to interact with the Ethereum blockchain
     #[structopt(long = "web3", name = "web3")]
     web3_url: Option<String>,
@@ -69,7 +76,7 @@ impl ContractsConfig {
             governance_addr: contracts_opts.governance_addr,
             genesis_tx_hash: contracts_opts.genesis_tx_hash,
             contract_addr: contracts_opts.contract_addr,
-            available_block_chunk_sizes: chain_opts.state_keeper.block_chunk_sizes,
+            available_block_chunk_sizes: chain_opts.block_chunk_sizes(),
         }
     }
 }
@@ -113,6 +120,10 @@ async fn main() {
     );
 
     let mut interactor = DatabaseStorageInteractor::new(storage);
+    if let Some(path) = opt.cold_storage {
+        let cold_storage = ColdStorage::new(Arc::new(FileObjectStore::new(path)));
+        interactor = interactor.with_cold_storage(cold_storage);
+    }
     // If genesis is argument is present - there will be fetching contracts creation transactions to get first eth block and genesis acc address
     if opt.genesis {
         // We have to load pre-defined tokens into the database before restoring state,
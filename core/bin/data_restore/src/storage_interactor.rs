@@ -6,14 +6,14 @@ use zksync_storage::data_restore::records::{
     NewBlockEvent, StoredBlockEvent, StoredRollupOpsBlock,
 };
 use zksync_types::{
-    block::Block, AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber,
-    TokenGenesisListItem, TokenId,
+    block::Block, ethereum::NewTokenEvent, AccountId, AccountMap, AccountUpdate, AccountUpdates,
+    BlockNumber, TokenGenesisListItem, TokenId,
 };
 
 use crate::{
     data_restore_driver::StorageUpdateState,
     events::{BlockEvent, EventType},
-    events_state::{EventsState, NewTokenEvent},
+    events_state::EventsState,
     rollup_ops::RollupOpsBlock,
 };
 
@@ -55,6 +55,7 @@ fn create_withdraw_operations(
         success: false,
         op: Some(withdraw_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
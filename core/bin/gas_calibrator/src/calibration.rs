@@ -0,0 +1,161 @@
+//! Utilities for comparing the estimated gas costs with the actual gas usage
+//! and fitting the gas cost constants to the observed data.
+
+/// Gas usage data collected for a single Ethereum transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasSample {
+    /// Number of the zkSync block processed by the transaction.
+    pub block_number: u32,
+    /// Estimated cost of processing the block operations (without the base cost).
+    pub estimated_ops_cost: u64,
+    /// Amount of gas actually used by the transaction.
+    pub gas_used: u64,
+}
+
+/// Result of fitting the `gas_used = base_cost + ops_scale * estimated_ops_cost`
+/// linear model to the collected samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    /// Fitted cost of processing an empty block.
+    pub base_cost: u64,
+    /// Factor by which the current operation costs should be multiplied.
+    pub ops_scale: f64,
+}
+
+impl Calibration {
+    /// Fits the model using the least squares method.
+    ///
+    /// Returns `None` if there are no samples.
+    pub fn fit(samples: &[GasSample]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let n = samples.len() as f64;
+        let mean_x = samples
+            .iter()
+            .map(|sample| sample.estimated_ops_cost as f64)
+            .sum::<f64>()
+            / n;
+        let mean_y = samples
+            .iter()
+            .map(|sample| sample.gas_used as f64)
+            .sum::<f64>()
+            / n;
+
+        let (covariance, variance) =
+            samples
+                .iter()
+                .fold((0f64, 0f64), |(covariance, variance), sample| {
+                    let dx = sample.estimated_ops_cost as f64 - mean_x;
+                    let dy = sample.gas_used as f64 - mean_y;
+                    (covariance + dx * dy, variance + dx * dx)
+                });
+
+        // If all the blocks have the same operations cost (e.g. all of them are empty),
+        // there is no way to determine the scale, so we keep the current operation costs.
+        let ops_scale = if variance > 0f64 {
+            (covariance / variance).max(0f64)
+        } else {
+            1f64
+        };
+        let base_cost = (mean_y - ops_scale * mean_x).max(0f64);
+
+        Some(Self {
+            base_cost: base_cost.ceil() as u64,
+            ops_scale,
+        })
+    }
+
+    /// Returns the suggested value of the operation cost constant.
+    pub fn scale_op_cost(&self, current_cost: u64) -> u64 {
+        (current_cost as f64 * self.ops_scale).ceil() as u64
+    }
+}
+
+/// Statistics of the relative error of the current gas estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimationError {
+    /// Mean relative error, positive values mean overestimation.
+    pub mean: f64,
+    /// The worst underestimation (or the least overestimation) observed.
+    pub min: f64,
+    /// The worst overestimation observed.
+    pub max: f64,
+}
+
+impl EstimationError {
+    /// Calculates the estimation error for the given base cost.
+    ///
+    /// Returns `None` if there are no samples.
+    pub fn calculate(base_cost: u64, samples: &[GasSample]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let errors: Vec<_> = samples
+            .iter()
+            .map(|sample| {
+                let estimated = (base_cost + sample.estimated_ops_cost) as f64;
+                let actual = sample.gas_used as f64;
+                (estimated - actual) / actual
+            })
+            .collect();
+
+        Some(Self {
+            mean: errors.iter().sum::<f64>() / errors.len() as f64,
+            min: errors.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: errors.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(estimated_ops_cost: u64, gas_used: u64) -> GasSample {
+        GasSample {
+            block_number: 1,
+            estimated_ops_cost,
+            gas_used,
+        }
+    }
+
+    #[test]
+    fn fit_linear_model() {
+        // gas_used = 100_000 + 2 * estimated_ops_cost
+        let samples = [
+            sample(1_000, 102_000),
+            sample(5_000, 110_000),
+            sample(20_000, 140_000),
+        ];
+
+        let calibration = Calibration::fit(&samples).unwrap();
+        assert_eq!(calibration.base_cost, 100_000);
+        assert!((calibration.ops_scale - 2f64).abs() < 1e-9);
+        assert_eq!(calibration.scale_op_cost(334), 668);
+    }
+
+    #[test]
+    fn fit_constant_ops_cost() {
+        // Scale can't be determined, so it stays the same.
+        let samples = [sample(0, 100_000), sample(0, 120_000)];
+
+        let calibration = Calibration::fit(&samples).unwrap();
+        assert_eq!(calibration.base_cost, 110_000);
+        assert!((calibration.ops_scale - 1f64).abs() < 1e-9);
+
+        assert!(Calibration::fit(&[]).is_none());
+    }
+
+    #[test]
+    fn estimation_error() {
+        let samples = [sample(0, 100_000), sample(0, 200_000)];
+
+        let error = EstimationError::calculate(150_000, &samples).unwrap();
+        assert!((error.max - 0.5).abs() < 1e-9);
+        assert!((error.min + 0.25).abs() < 1e-9);
+        assert!((error.mean - 0.125).abs() < 1e-9);
+    }
+}
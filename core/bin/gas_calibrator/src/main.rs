@@ -0,0 +1,188 @@
+//! Gas calibration tool.
+//!
+//! Replays the Ethereum transactions sent for the latest committed blocks, compares
+//! the estimated gas costs with the actual gas usage and suggests updated values for
+//! the `CommitCost` and `VerifyCost` constants. Since the fee ticker operation costs
+//! are derived from these constants, the suggested values apply to it as well.
+
+use structopt::StructOpt;
+use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    gas_counter::{CommitCost, VerifyCost},
+    BlockNumber, ZkSyncOp, H256, U256,
+};
+
+use crate::calibration::{Calibration, EstimationError, GasSample};
+
+mod calibration;
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "zkSync gas calibrator",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+struct Opt {
+    /// Amount of the latest committed blocks to analyze
+    #[structopt(long, default_value = "100")]
+    blocks: u32,
+}
+
+/// Returns the amount of gas used by the Ethereum transaction, if it was executed.
+async fn gas_used(
+    eth_gateway: &EthereumGateway,
+    tx_hash: Option<Vec<u8>>,
+) -> anyhow::Result<Option<u64>> {
+    let tx_hash = match tx_hash {
+        Some(tx_hash) => H256::from_slice(&tx_hash),
+        None => return Ok(None),
+    };
+
+    let gas_used = eth_gateway
+        .tx_receipt(tx_hash)
+        .await?
+        .and_then(|receipt| receipt.gas_used);
+    Ok(gas_used.map(|gas| gas.as_u64()))
+}
+
+fn ops_cost(ops: &[ZkSyncOp], op_cost: fn(&ZkSyncOp) -> U256) -> u64 {
+    ops.iter()
+        .map(op_cost)
+        .fold(U256::zero(), |acc, cost| acc + cost)
+        .as_u64()
+}
+
+fn report(method: &str, base_cost: u64, op_costs: &[(&str, u64)], samples: &[GasSample]) {
+    println!("`{}` ({} transactions analyzed)", method, samples.len());
+
+    let (calibration, error) = match (
+        Calibration::fit(samples),
+        EstimationError::calculate(base_cost, samples),
+    ) {
+        (Some(calibration), Some(error)) => (calibration, error),
+        _ => {
+            println!("  No executed transactions found\n");
+            return;
+        }
+    };
+
+    println!(
+        "  Current estimation error: mean {:+.2}%, min {:+.2}%, max {:+.2}%",
+        error.mean * 100f64,
+        error.min * 100f64,
+        error.max * 100f64
+    );
+    if let Some(worst) = samples.iter().min_by_key(|sample| {
+        // Block with the worst underestimation.
+        (base_cost + sample.estimated_ops_cost) as i128 - sample.gas_used as i128
+    }) {
+        println!(
+            "  Worst estimated block: #{} (estimated {}, used {})",
+            worst.block_number,
+            base_cost + worst.estimated_ops_cost,
+            worst.gas_used
+        );
+    }
+    println!("  Operation costs scale: {:.3}", calibration.ops_scale);
+
+    println!("  Suggested constants:");
+    println!(
+        "    pub const BASE_COST: u64 = {}; // currently {}",
+        calibration.base_cost, base_cost
+    );
+    for (name, cost) in op_costs {
+        println!(
+            "    pub const {}: u64 = {}; // currently {}",
+            name,
+            calibration.scale_op_cost(*cost),
+            cost
+        );
+    }
+    println!();
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    vlog::init();
+
+    let opt = Opt::from_args();
+    let config = ZkSyncConfig::from_env();
+    let connection_pool = ConnectionPool::new(Some(1));
+    let eth_gateway = EthereumGateway::from_config(&config);
+
+    let mut storage = connection_pool.access_storage().await?;
+    let last_block = storage
+        .chain()
+        .block_schema()
+        .get_last_committed_block()
+        .await?;
+    let blocks = storage
+        .chain()
+        .block_schema()
+        .load_block_range(last_block, opt.blocks)
+        .await?;
+
+    let mut commit_samples = Vec::new();
+    let mut verify_samples = Vec::new();
+    for block in blocks {
+        let block_number = BlockNumber(block.block_number as u32);
+        let ops = storage
+            .chain()
+            .block_schema()
+            .get_block_operations(block_number)
+            .await?;
+
+        if let Some(gas_used) = gas_used(&eth_gateway, block.commit_tx_hash).await? {
+            commit_samples.push(GasSample {
+                block_number: *block_number,
+                estimated_ops_cost: ops_cost(&ops, CommitCost::op_cost),
+                gas_used,
+            });
+        }
+        if let Some(gas_used) = gas_used(&eth_gateway, block.verify_tx_hash).await? {
+            verify_samples.push(GasSample {
+                block_number: *block_number,
+                estimated_ops_cost: ops_cost(&ops, VerifyCost::op_cost),
+                gas_used,
+            });
+        }
+    }
+
+    report(
+        "commitBlock",
+        CommitCost::BASE_COST,
+        &[
+            ("DEPOSIT_COST", CommitCost::DEPOSIT_COST),
+            (
+                "CHANGE_PUBKEY_COST_OFFCHAIN",
+                CommitCost::CHANGE_PUBKEY_COST_OFFCHAIN,
+            ),
+            (
+                "CHANGE_PUBKEY_COST_ONCHAIN",
+                CommitCost::CHANGE_PUBKEY_COST_ONCHAIN,
+            ),
+            ("TRANSFER_COST", CommitCost::TRANSFER_COST),
+            ("TRANSFER_TO_NEW_COST", CommitCost::TRANSFER_TO_NEW_COST),
+            ("FULL_EXIT_COST", CommitCost::FULL_EXIT_COST),
+            ("WITHDRAW_COST", CommitCost::WITHDRAW_COST),
+        ],
+        &commit_samples,
+    );
+    report(
+        "verifyBlock",
+        VerifyCost::BASE_COST,
+        &[
+            ("DEPOSIT_COST", VerifyCost::DEPOSIT_COST),
+            ("CHANGE_PUBKEY_COST", VerifyCost::CHANGE_PUBKEY_COST),
+            ("TRANSFER_COST", VerifyCost::TRANSFER_COST),
+            ("TRANSFER_TO_NEW_COST", VerifyCost::TRANSFER_TO_NEW_COST),
+            ("FULL_EXIT_COST", VerifyCost::FULL_EXIT_COST),
+            ("WITHDRAW_COST", VerifyCost::WITHDRAW_COST),
+        ],
+        &verify_samples,
+    );
+
+    Ok(())
+}
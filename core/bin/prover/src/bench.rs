@@ -0,0 +1,117 @@
+//! Prover benchmark: measures the time and memory required to prove blocks
+//! of the different sizes on the current hardware.
+
+// Built-in deps
+use std::fs;
+use std::time::Instant;
+// External deps
+use num::BigUint;
+use serde::Serialize;
+// Workspace deps
+use zksync_circuit::witness::{deposit::DepositWitness, utils::WitnessBuilder, Witness};
+use zksync_crypto::circuit::{account::CircuitAccount, CircuitAccountTree};
+use zksync_prover_utils::{
+    prover_data::ProverData, PlonkVerificationKey, SetupForStepByStepProver,
+};
+use zksync_types::{
+    operations::DepositOp, Account, AccountId, Address, BlockNumber, Deposit, TokenId,
+};
+
+/// Results of the benchmark for a single block size.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSizeBenchmark {
+    /// Amount of chunks in the block.
+    pub block_chunks: usize,
+    /// Time spent on the circuit transpilation and setup preparation.
+    pub setup_time_ms: u128,
+    /// Time spent on the proof generation and verification.
+    pub proving_time_ms: u128,
+    /// Peak resident memory of the process while proving the block, if it can be measured.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// Creates the witness for a block of the given size filled with deposits to the new accounts.
+pub fn synthetic_prover_data(block_chunks: usize) -> ProverData {
+    let mut circuit_account_tree =
+        CircuitAccountTree::new(zksync_crypto::params::account_tree_depth());
+    let fee_account_id = AccountId(0);
+    let fee_account = Account::default_with_address(&Address::default());
+    circuit_account_tree.insert(*fee_account_id, CircuitAccount::from(fee_account));
+
+    let mut witness_accum =
+        WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, BlockNumber(1));
+
+    let deposits_count = block_chunks / DepositOp::CHUNKS;
+    for idx in 1..=deposits_count {
+        let address = Address::from_low_u64_be(idx as u64);
+        let deposit_op = DepositOp {
+            priority_op: Deposit {
+                from: address,
+                token: TokenId(0),
+                amount: BigUint::from(1u32),
+                to: address,
+            },
+            account_id: AccountId(idx as u32),
+        };
+
+        let deposit_witness =
+            DepositWitness::apply_tx(&mut witness_accum.account_tree, &deposit_op);
+        let deposit_operations = deposit_witness.calculate_operations(());
+        let pub_data_from_witness = deposit_witness.get_pubdata();
+        witness_accum.add_operation_with_pubdata(deposit_operations, pub_data_from_witness);
+    }
+    witness_accum.extend_pubdata_with_noops(block_chunks);
+    witness_accum.collect_fees(&[]);
+    witness_accum.calculate_pubdata_commitment();
+
+    witness_accum.into()
+}
+
+/// Generates and verifies the proof for the synthetic block of the given size.
+pub fn bench_block_size(
+    block_chunks: usize,
+    download_setup_from_network: bool,
+) -> Result<BlockSizeBenchmark, anyhow::Error> {
+    let circuit = synthetic_prover_data(block_chunks).into_circuit(1);
+    reset_peak_memory();
+
+    let start = Instant::now();
+    let setup = SetupForStepByStepProver::prepare_setup_for_step_by_step_prover(
+        circuit.clone(),
+        download_setup_from_network,
+    )?;
+    let setup_time = start.elapsed();
+
+    let vk = PlonkVerificationKey::read_verification_key_for_main_circuit(block_chunks)?;
+    let start = Instant::now();
+    setup.gen_step_by_step_proof_using_prepared_setup(circuit, &vk)?;
+    let proving_time = start.elapsed();
+
+    Ok(BlockSizeBenchmark {
+        block_chunks,
+        setup_time_ms: setup_time.as_millis(),
+        proving_time_ms: proving_time.as_millis(),
+        peak_memory_bytes: peak_memory(),
+    })
+}
+
+/// Resets the peak resident memory counter of the process (Linux only).
+fn reset_peak_memory() {
+    // Writing "5" to `clear_refs` resets the `VmHWM` value, see `man 5 proc`.
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// Returns the peak resident memory of the process (Linux only).
+fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find(|line| line.starts_with("VmHWM:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
@@ -88,7 +88,7 @@ impl<C: ApiClient> ProverImpl<C> for DummyProver<C> {
         vlog::info!("starting to compute proof for block {}", block,);
 
         self.api_client
-            .publish(block, EncodedProofPlonk::default())
+            .publish(job_id, block, EncodedProofPlonk::default())
             .map_err(|e| BabyProverError::Api(format!("failed to publish proof: {}", e)))?;
 
         vlog::info!("finished and published proof for block {}", block);
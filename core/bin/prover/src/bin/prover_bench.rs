@@ -0,0 +1,56 @@
+//! Measures the proving time and memory for every supported block size
+//! and prints the report in JSON format.
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+use zksync_config::configs::ChainConfig;
+use zksync_prover::bench::bench_block_size;
+use zksync_utils::parse_env;
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "zkSync prover benchmark",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+struct Opt {
+    /// Block sizes (in chunks) to benchmark, all the supported block sizes are used by default
+    #[structopt(long, use_delimiter = true)]
+    block_sizes: Vec<usize>,
+    /// File to write the report to, the report is printed to stdout if not set
+    #[structopt(long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    vlog::init();
+
+    let opt = Opt::from_args();
+    let block_sizes = if opt.block_sizes.is_empty() {
+        ChainConfig::from_env().circuit.supported_block_chunks_sizes
+    } else {
+        opt.block_sizes
+    };
+    let download_setup_from_network = parse_env("MISC_PROVER_DOWNLOAD_SETUP");
+
+    let mut report = Vec::with_capacity(block_sizes.len());
+    for block_chunks in block_sizes {
+        vlog::info!("Benchmarking block size {}", block_chunks);
+        let result = bench_block_size(block_chunks, download_setup_from_network)?;
+        vlog::info!(
+            "Block size {}: setup {} ms, proving {} ms",
+            block_chunks,
+            result.setup_time_ms,
+            result.proving_time_ms
+        );
+        report.push(result);
+    }
+
+    let report = serde_json::to_string_pretty(&report)?;
+    match opt.output {
+        Some(path) => std::fs::write(path, report)?,
+        None => println!("{}", report),
+    }
+
+    Ok(())
+}
@@ -10,7 +10,8 @@ use std::{
 // External deps
 use structopt::StructOpt;
 // Workspace deps
-use zksync_config::configs::ProverConfig as EnvProverConfig;
+use zksync_config::configs::{ChainConfig, ProverConfig as EnvProverConfig};
+use zksync_prover_utils::{api::ProverVersion, key_management::provision_keys};
 use zksync_utils::{get_env, parse_env};
 // Local deps
 use crate::{client, start, ApiClient, ProverConfig, ProverImpl, ShutdownRequest};
@@ -19,7 +20,17 @@ fn api_client_from_env(worker_name: &str) -> client::ApiClient {
     let server_api_url = parse_env("API_PROVER_URL");
     let request_timout = Duration::from_secs(parse_env::<u64>("PROVER_PROVER_REQUEST_TIMEOUT"));
     let secret = get_env("API_PROVER_SECRET_AUTH");
-    client::ApiClient::new(&server_api_url, worker_name, request_timout, &secret)
+    let version = ProverVersion::from_verification_keys(
+        &ChainConfig::from_env().circuit.supported_block_chunks_sizes,
+    )
+    .expect("Failed to load the verification keys to determine the prover version");
+    client::ApiClient::new(
+        &server_api_url,
+        worker_name,
+        request_timout,
+        &secret,
+        version,
+    )
 }
 
 #[derive(StructOpt)]
@@ -39,12 +50,22 @@ pub fn main_for_prover_impl<P: ProverImpl<client::ApiClient> + 'static + Send +
     let worker_name = opt.worker_name;
 
     // used env
-    let heartbeat_interval = EnvProverConfig::from_env().prover.heartbeat_interval();
+    let env_prover_config = EnvProverConfig::from_env();
+    let heartbeat_interval = env_prover_config.prover.heartbeat_interval();
     let prover_config = <P as ProverImpl<client::ApiClient>>::Config::from_env();
     let api_client = api_client_from_env(&worker_name);
     let prover = P::create_from_config(prover_config, api_client.clone(), heartbeat_interval);
 
     vlog::init();
+
+    let chain_config = ChainConfig::from_env();
+    provision_keys(
+        &env_prover_config.keys,
+        &chain_config.circuit,
+        &chain_config.block_chunk_sizes(),
+    )
+    .expect("Failed to provision the keys");
+
     const ABSENT_PROVER_ID: i32 = -1;
 
     vlog::info!("creating prover, worker name: {}", worker_name);
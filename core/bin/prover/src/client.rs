@@ -13,7 +13,10 @@ use crate::client;
 use zksync_circuit::circuit::ZkSyncCircuit;
 use zksync_crypto::proof::EncodedProofPlonk;
 use zksync_crypto::Engine;
-use zksync_prover_utils::api::{BlockToProveRes, ProverReq, PublishReq, WorkingOnReq};
+use zksync_prover_utils::api::{
+    BlockToProveRes, JobFailedReq, ProofChunkReq, ProofChunkRes, ProverReq, ProverVersion,
+    WorkingOnReq, PROOF_CHUNK_SIZE,
+};
 use zksync_prover_utils::prover_data::ProverData;
 
 #[derive(Debug, Clone)]
@@ -21,10 +24,12 @@ pub struct ApiClient {
     register_url: Url,
     block_to_prove_url: Url,
     working_on_url: Url,
+    job_failed_url: Url,
     prover_data_url: Url,
-    publish_url: Url,
+    publish_chunk_url: Url,
     stopped_url: Url,
     worker: String,
+    version: ProverVersion,
     // Client keeps connection pool inside, so it is recommended to reuse it (see docstring for reqwest::Client)
     http_client: reqwest::blocking::Client,
     // A generator that create the authentication token upon request to any endpoint
@@ -40,6 +45,7 @@ impl ApiClient {
         worker: &str,
         req_server_timeout: time::Duration,
         secret: &str,
+        version: ProverVersion,
     ) -> Self {
         if worker.is_empty() {
             panic!("worker name cannot be empty")
@@ -54,10 +60,12 @@ impl ApiClient {
             register_url: base_url.join("/register").unwrap(),
             block_to_prove_url: base_url.join("/block_to_prove").unwrap(),
             working_on_url: base_url.join("/working_on").unwrap(),
+            job_failed_url: base_url.join("/job_failed").unwrap(),
             prover_data_url: base_url.join("/prover_data").unwrap(),
-            publish_url: base_url.join("/publish").unwrap(),
+            publish_chunk_url: base_url.join("/publish_chunk").unwrap(),
             stopped_url: base_url.join("/stopped").unwrap(),
             worker: worker.to_string(),
+            version,
             http_client,
             auth_token_generator,
         }
@@ -105,58 +113,71 @@ impl ApiClient {
         }
     }
 
+    fn prover_req(&self, block_size: usize) -> ProverReq {
+        ProverReq {
+            name: self.worker.clone(),
+            block_size,
+            version: Some(self.version.clone()),
+        }
+    }
+
     pub fn register_prover(&self, block_size: usize) -> anyhow::Result<i32> {
-        let op = || -> Result<i32, anyhow::Error> {
+        let op = || -> Result<Result<i32, String>, anyhow::Error> {
             info!("Registering prover...");
             let res = self
                 .http_client
                 .post(self.register_url.as_str())
                 .bearer_auth(&self.get_encoded_token()?)
-                .json(&client::ProverReq {
-                    name: self.worker.clone(),
-                    block_size,
-                })
+                .json(&self.prover_req(block_size))
                 .send();
 
             let res = res.map_err(|e| format_err!("register request failed: {}", e))?;
+            if res.status() == reqwest::StatusCode::CONFLICT {
+                // Prover version is incompatible with the server, there is no point in retrying.
+                return Ok(Err(res.text().unwrap_or_default()));
+            }
             let text = res
                 .text()
                 .map_err(|e| format_err!("failed to read register response: {}", e))?;
 
-            Ok(i32::from_str(&text)
-                .map_err(|e| format_err!("failed to parse register prover id: {}", e))?)
+            Ok(Ok(i32::from_str(&text).map_err(|e| {
+                format_err!("failed to parse register prover id: {}", e)
+            })?))
         };
 
-        Ok(self.with_retries(&op)?)
+        self.with_retries(&op)?
+            .map_err(|rejection| format_err!("prover was rejected by the server: {}", rejection))
     }
 }
 
 impl crate::ApiClient for ApiClient {
     fn block_to_prove(&self, block_size: usize) -> Result<Option<(i64, i32)>, anyhow::Error> {
-        let op = || -> Result<Option<(i64, i32)>, anyhow::Error> {
+        let op = || -> Result<Result<Option<(i64, i32)>, String>, anyhow::Error> {
             trace!("sending block_to_prove");
             let res = self
                 .http_client
                 .get(self.block_to_prove_url.as_str())
                 .bearer_auth(&self.get_encoded_token()?)
-                .json(&client::ProverReq {
-                    name: self.worker.clone(),
-                    block_size,
-                })
+                .json(&self.prover_req(block_size))
                 .send()
                 .map_err(|e| format_err!("block to prove request failed: {}", e))?;
+            if res.status() == reqwest::StatusCode::CONFLICT {
+                // Server was upgraded to the incompatible version, there is no point in retrying.
+                return Ok(Err(res.text().unwrap_or_default()));
+            }
             let text = res
                 .text()
                 .map_err(|e| format_err!("failed to read block to prove response: {}", e))?;
             let res: client::BlockToProveRes = serde_json::from_str(&text)
                 .map_err(|e| format_err!("failed to parse block to prove response: {}", e))?;
             if res.block != 0 {
-                return Ok(Some((res.block, res.prover_run_id)));
+                return Ok(Ok(Some((res.block, res.prover_run_id))));
             }
-            Ok(None)
+            Ok(Ok(None))
         };
 
-        Ok(self.with_retries(&op)?)
+        self.with_retries(&op)?
+            .map_err(|rejection| format_err!("prover was rejected by the server: {}", rejection))
     }
 
     fn working_on(&self, job_id: i32) -> Result<(), anyhow::Error> {
@@ -178,6 +199,27 @@ impl crate::ApiClient for ApiClient {
         }
     }
 
+    fn job_failed(&self, job_id: i32, block: i64, error: &str) -> Result<(), anyhow::Error> {
+        trace!("sending job_failed {} for block {}", job_id, block);
+
+        let res = self
+            .http_client
+            .post(self.job_failed_url.as_str())
+            .bearer_auth(&self.get_encoded_token()?)
+            .json(&JobFailedReq {
+                prover_run_id: job_id,
+                block: block as u32,
+                error: error.to_string(),
+            })
+            .send()
+            .map_err(|e| format_err!("failed to send job failed request: {}", e))?;
+        if res.status() != reqwest::StatusCode::OK {
+            bail!("job failed request failed with status: {}", res.status())
+        } else {
+            Ok(())
+        }
+    }
+
     fn prover_data(&self, block: i64) -> Result<ZkSyncCircuit<'_, Engine>, anyhow::Error> {
         let op = || -> Result<ProverData, anyhow::Error> {
             trace!("sending prover_data");
@@ -200,44 +242,69 @@ impl crate::ApiClient for ApiClient {
         Ok(prover_data.into_circuit(block))
     }
 
-    fn publish(&self, block: i64, proof: EncodedProofPlonk) -> Result<(), anyhow::Error> {
-        let op = move || -> Result<(), anyhow::Error> {
-            trace!("Trying publish proof {}", block);
-            let proof = proof.clone();
-            let res = self
-                .http_client
-                .post(self.publish_url.as_str())
-                .bearer_auth(&self.get_encoded_token()?)
-                .json(&client::PublishReq {
-                    block: block as u32,
-                    proof,
-                })
-                .send()
-                .map_err(|e| format_err!("failed to send publish request: {}", e))?;
-            let status = res.status();
-            if status != reqwest::StatusCode::OK {
-                match res.text() {
-                    Ok(message) => {
-                        if message == "duplicate key" {
-                            warn!("proof for block {} already exists", block);
-                        } else {
+    fn publish(
+        &self,
+        job_id: i32,
+        block: i64,
+        proof: EncodedProofPlonk,
+    ) -> Result<(), anyhow::Error> {
+        let proof = serde_json::to_vec(&proof)?;
+        let total_size = proof.len() as u64;
+
+        // The proof is uploaded in chunks, so after a network failure the upload is continued
+        // from the last chunk received by the server instead of starting from scratch.
+        let mut offset = 0;
+        while offset < total_size {
+            let op = || -> Result<u64, anyhow::Error> {
+                trace!("Trying publish proof {} from offset {}", block, offset);
+                let chunk_end = std::cmp::min(offset as usize + PROOF_CHUNK_SIZE, proof.len());
+                let res = self
+                    .http_client
+                    .post(self.publish_chunk_url.as_str())
+                    .bearer_auth(&self.get_encoded_token()?)
+                    .query(&ProofChunkReq {
+                        prover_run_id: job_id,
+                        block: block as u32,
+                        total_size,
+                        offset,
+                    })
+                    .body(proof[offset as usize..chunk_end].to_vec())
+                    .send()
+                    .map_err(|e| format_err!("failed to send publish request: {}", e))?;
+                let status = res.status();
+                if status != reqwest::StatusCode::OK {
+                    match res.text() {
+                        Ok(message) => {
+                            if message == "duplicate key" {
+                                warn!("proof for block {} already exists", block);
+                                return Ok(total_size);
+                            }
                             bail!(
                                 "publish request failed with status: {} and message: {}",
                                 status,
                                 message
                             );
                         }
-                    }
-                    Err(_) => {
-                        bail!("publish request failed with status: {}", status);
-                    }
-                };
-            }
+                        Err(_) => {
+                            bail!("publish request failed with status: {}", status);
+                        }
+                    };
+                }
 
-            Ok(())
-        };
+                let res: ProofChunkRes = res
+                    .json()
+                    .map_err(|e| format_err!("failed to parse publish response: {}", e))?;
+                Ok(res.uploaded)
+            };
 
-        Ok(self.with_retries(&op)?)
+            offset = self.with_retries(&op)?;
+            info!(
+                "Uploaded {}/{} bytes of the proof for block {}",
+                offset, total_size, block
+            );
+        }
+
+        Ok(())
     }
 
     fn prover_stopped(&self, prover_run_id: i32) -> Result<(), anyhow::Error> {
@@ -1,4 +1,5 @@
 pub mod auth_utils;
+pub mod bench;
 pub mod cli_utils;
 pub mod client;
 pub mod plonk_step_by_step_prover;
@@ -85,11 +86,14 @@ pub trait ProverImpl<C: ApiClient> {
 pub trait ApiClient: Debug {
     fn block_to_prove(&self, block_size: usize) -> Result<Option<(i64, i32)>, anyhow::Error>;
     fn working_on(&self, job_id: i32) -> Result<(), anyhow::Error>;
+    /// Reports that the proof for the block of the job could not be created or verified,
+    /// so the server can collect the debugging data for the block.
+    fn job_failed(&self, job_id: i32, block: i64, error: &str) -> Result<(), anyhow::Error>;
     fn prover_data(
         &self,
         block: i64,
     ) -> Result<zksync_circuit::circuit::ZkSyncCircuit<'_, Engine>, anyhow::Error>;
-    fn publish(&self, block: i64, p: EncodedProofPlonk) -> Result<(), anyhow::Error>;
+    fn publish(&self, job_id: i32, block: i64, p: EncodedProofPlonk) -> Result<(), anyhow::Error>;
     fn prover_stopped(&self, prover_run_id: i32) -> Result<(), anyhow::Error>;
 }
 
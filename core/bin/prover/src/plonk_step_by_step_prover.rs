@@ -1,8 +1,9 @@
 use crate::{ApiClient, BabyProverError, ProverConfig, ProverImpl};
 use std::sync::{mpsc, Mutex};
 use std::time::Duration;
+use zksync_config::configs::ChainConfig;
 use zksync_prover_utils::{PlonkVerificationKey, SetupForStepByStepProver};
-use zksync_utils::{get_env, parse_env};
+use zksync_utils::parse_env;
 
 /// We prepare some data before making proof for each block size, so we cache it in case next block
 /// would be of our size
@@ -26,10 +27,7 @@ pub struct PlonkStepByStepProverConfig {
 impl ProverConfig for PlonkStepByStepProverConfig {
     fn from_env() -> Self {
         Self {
-            block_sizes: get_env("CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES")
-                .split(',')
-                .map(|p| p.parse().unwrap())
-                .collect(),
+            block_sizes: ChainConfig::from_env().block_chunk_sizes(),
             download_setup_from_network: parse_env("MISC_PROVER_DOWNLOAD_SETUP"),
         }
     }
@@ -154,6 +152,13 @@ impl<C: ApiClient> ProverImpl<C> for PlonkStepByStepProver<C> {
             .setup
             .gen_step_by_step_proof_using_prepared_setup(instance, &vk)
             .map_err(|e| {
+                if let Err(err) = self.api_client.job_failed(job_id, block, &e.to_string()) {
+                    vlog::warn!(
+                        "failed to report proving failure for block {}: {}",
+                        block,
+                        err
+                    );
+                }
                 BabyProverError::Internal(format!(
                     "Failed to create verified proof for block: {}, size: {}, err: {}",
                     block, block_size, e
@@ -163,7 +168,7 @@ impl<C: ApiClient> ProverImpl<C> for PlonkStepByStepProver<C> {
         *self.prepared_computations.lock().unwrap() = Some(precomp);
 
         self.api_client
-            .publish(block, verified_proof)
+            .publish(job_id, block, verified_proof)
             .map_err(|e| BabyProverError::Api(format!("failed to publish proof: {}", e)))?;
 
         vlog::info!("finished and published proof for block {}", block);
@@ -199,6 +199,10 @@ impl<F: Fn() -> Option<ProverData>> zksync_prover::ApiClient for MockApiClient<F
         Ok(())
     }
 
+    fn job_failed(&self, _job: i32, _block: i64, _error: &str) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
     fn prover_data(&self, block: i64) -> Result<ZkSyncCircuit<'_, Engine>, anyhow::Error> {
         let block_to_prove = self.block_to_prove.lock().unwrap();
         if (*block_to_prove).is_some() {
@@ -210,7 +214,12 @@ impl<F: Fn() -> Option<ProverData>> zksync_prover::ApiClient for MockApiClient<F
         Err(anyhow::format_err!("mock not configured"))
     }
 
-    fn publish(&self, _block: i64, p: EncodedProofPlonk) -> Result<(), anyhow::Error> {
+    fn publish(
+        &self,
+        _job_id: i32,
+        _block: i64,
+        p: EncodedProofPlonk,
+    ) -> Result<(), anyhow::Error> {
         // No more blocks to prove. We're only testing single rounds.
         let mut block_to_prove = self.block_to_prove.lock().unwrap();
         *block_to_prove = None;
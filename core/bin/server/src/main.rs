@@ -1,6 +1,7 @@
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
-use std::cell::RefCell;
+use futures::{channel::mpsc, executor::block_on, future, SinkExt, StreamExt};
+use std::{cell::RefCell, path::PathBuf, str::FromStr};
 use structopt::StructOpt;
+use tokio::task::JoinHandle;
 use zksync_api::run_api;
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_sender::run_eth_sender;
@@ -16,12 +17,58 @@ pub enum ServerCommand {
     Launch,
 }
 
+/// Component of the server. Each of them can also be launched separately using its own binary
+/// (`zksync_core`, `zksync_api`, `zksync_eth_sender` and `zksync_witness_generator`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Component {
+    Core,
+    Api,
+    EthSender,
+    WitnessGenerator,
+}
+
+impl FromStr for Component {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "core" => Ok(Self::Core),
+            "api" => Ok(Self::Api),
+            "eth_sender" => Ok(Self::EthSender),
+            "witness_generator" => Ok(Self::WitnessGenerator),
+            other => Err(format!("Unknown server component: {}", other)),
+        }
+    }
+}
+
 #[derive(StructOpt)]
 #[structopt(name = "zkSync operator node", author = "Matter Labs")]
 struct Opt {
     /// Generate genesis block for the first contract deployment
     #[structopt(long)]
     genesis: bool,
+    /// Genesis specification file with the pre-funded accounts, tokens and governance parameters.
+    /// Only used along with `--genesis`.
+    #[structopt(long, parse(from_os_str))]
+    genesis_spec: Option<PathBuf>,
+    /// Comma-separated list of the components to launch.
+    #[structopt(
+        long,
+        use_delimiter = true,
+        default_value = "core,api,eth_sender,witness_generator",
+        possible_values = &["core", "api", "eth_sender", "witness_generator"]
+    )]
+    components: Vec<Component>,
+}
+
+/// Waits for the task of the component, never resolves if the component is not launched.
+async fn wait_for_component(task_handle: Option<JoinHandle<()>>) {
+    match task_handle {
+        Some(task_handle) => {
+            task_handle.await.ok();
+        }
+        None => future::pending().await,
+    }
 }
 
 #[tokio::main]
@@ -37,14 +84,30 @@ async fn main() -> anyhow::Result<()> {
 
     if let ServerCommand::Genesis = server_mode {
         vlog::info!("Performing the server genesis initialization",);
-        genesis_init(&config).await;
+        genesis_init(&config, opt.genesis_spec.as_deref()).await;
         return Ok(());
     }
 
     // It's a `ServerCommand::Launch`, perform the usual routine.
-    vlog::info!("Running the zkSync server");
+    let components = opt.components;
+    vlog::info!(
+        "Running the zkSync server with components: {:?}",
+        components
+    );
 
-    let connection_pool = ConnectionPool::new(None);
+    let slow_query_threshold = config.db.slow_query_threshold();
+    // Every subsystem has its own connection pool, so e.g. a burst of API requests
+    // can't starve the committer of connections.
+    let core_pool =
+        ConnectionPool::dedicated("core", config.db.core_pool_size, slow_query_threshold);
+    let api_pool = ConnectionPool::dedicated("api", config.db.api_pool_size, slow_query_threshold);
+    let eth_sender_pool = ConnectionPool::dedicated(
+        "eth_sender",
+        config.db.eth_sender_pool_size,
+        slow_query_threshold,
+    );
+    let prover_pool =
+        ConnectionPool::dedicated("prover", config.db.prover_pool_size, slow_query_threshold);
 
     // Handle Ctrl+C
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
@@ -57,42 +120,65 @@ async fn main() -> anyhow::Result<()> {
         .expect("Error setting Ctrl+C handler");
     }
 
-    // Run prometheus data exporter.
-    let (prometheus_task_handle, counter_task_handle) =
-        run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, true);
+    // Run prometheus data exporter. Operations are counted by the Core.
+    let (prometheus_task_handle, counter_task_handle) = run_prometheus_exporter(
+        api_pool.clone(),
+        &config.api.prometheus,
+        components.contains(&Component::Core),
+    );
 
     // Run core actors.
-    vlog::info!("Starting the Core actors");
-    let core_task_handles = run_core(connection_pool.clone(), stop_signal_sender.clone(), &config)
-        .await
-        .expect("Unable to start Core actors");
+    let core_task_handles = if components.contains(&Component::Core) {
+        vlog::info!("Starting the Core actors");
+        run_core(core_pool, stop_signal_sender.clone(), &config)
+            .await
+            .expect("Unable to start Core actors")
+    } else {
+        Vec::new()
+    };
 
     // Run API actors.
-    vlog::info!("Starting the API server actors");
-    let api_task_handle = run_api(connection_pool.clone(), stop_signal_sender.clone(), &config);
+    let api_task_handle = if components.contains(&Component::Api) {
+        vlog::info!("Starting the API server actors");
+        Some(run_api(api_pool, stop_signal_sender.clone(), &config))
+    } else {
+        None
+    };
 
     // Run Ethereum sender actors.
-    vlog::info!("Starting the Ethereum sender actors");
-    let eth_sender_task_handle = run_eth_sender(connection_pool.clone(), config.clone());
+    let eth_sender_task_handle = if components.contains(&Component::EthSender) {
+        vlog::info!("Starting the Ethereum sender actors");
+        Some(run_eth_sender(eth_sender_pool, config.clone()).await?)
+    } else {
+        None
+    };
 
     // Run prover server & witness generator.
-    vlog::info!("Starting the Prover server actors");
-    run_prover_server(connection_pool, stop_signal_sender, config);
+    if components.contains(&Component::WitnessGenerator) {
+        vlog::info!("Starting the Prover server actors");
+        run_prover_server(prover_pool, stop_signal_sender, config);
+    }
 
     tokio::select! {
-        _ = async { wait_for_tasks(core_task_handles).await } => {
+        _ = async {
+            if core_task_handles.is_empty() {
+                future::pending().await
+            } else {
+                wait_for_tasks(core_task_handles).await
+            }
+        } => {
             // We don't need to do anything here, since Core actors will panic upon future resolving.
         },
-        _ = async { api_task_handle.await } => {
+        _ = async { wait_for_component(api_task_handle).await } => {
             panic!("API server actors aren't supposed to finish their execution")
         },
-        _ = async { eth_sender_task_handle.await } => {
+        _ = async { wait_for_component(eth_sender_task_handle).await } => {
             panic!("Ethereum Sender actors aren't supposed to finish their execution")
         },
         _ = async { prometheus_task_handle.await } => {
             panic!("Prometheus exporter actors aren't supposed to finish their execution")
         },
-        _ = async { counter_task_handle.unwrap().await } => {
+        _ = async { wait_for_component(counter_task_handle).await } => {
             panic!("Operation counting actor is not supposed to finish its execution")
         },
         _ = async { stop_signal_receiver.next().await } => {
@@ -13,12 +13,27 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 use futures::channel::mpsc;
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
+use num::BigUint;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 // Local uses
-use zksync_storage::ConnectionPool;
-use zksync_types::{tokens, Address, TokenId};
-use zksync_utils::panic_notify::ThreadPanicNotify;
+use zksync_storage::{
+    runtime_flags::{RuntimeFlag, RuntimeFlagState},
+    ConnectionPool,
+};
+use zksync_types::{
+    tokens::{self, TokenListingFeeStatus, TokenTxRestriction},
+    tx::TxHash,
+    Address, BlockNumber, TokenId, H256,
+};
+use zksync_utils::{panic_notify::ThreadPanicNotify, BigUintSerdeAsRadix10Str};
+
+use crate::{
+    api_server::runtime_flags::{MaintenanceInfo, RuntimeFlagsCache},
+    core_api_client::{CoreApiClient, CoreApiError},
+    utils::token_db_cache::TokenDBCache,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct PayloadAuthToken {
@@ -32,6 +47,7 @@ struct PayloadAuthToken {
 struct AppState {
     secret_auth: String,
     connection_pool: ConnectionPool,
+    core_api_client: CoreApiClient,
 }
 
 impl AppState {
@@ -55,6 +71,69 @@ struct AddTokenRequest {
     pub symbol: String,
     /// Token precision (e.g. 18 for "ETH" so "1.0" ETH = 10e18 as U256 number)
     pub decimals: u8,
+    /// ID of the pending listing fee paid for this token, if the token is listed
+    /// via the permissionless listing flow.
+    #[serde(default)]
+    pub listing_fee_id: Option<i64>,
+}
+
+/// Fee paid in the L1 for the permissionless token listing.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct AddListingFeeRequest {
+    /// Address of the token requested to be listed.
+    pub token_address: Address,
+    /// Address of the account that paid the fee.
+    pub payer: Address,
+    /// Paid amount in wei.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Hash of the L1 transaction containing the payment.
+    pub eth_tx_hash: H256,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct ListingFeesQuery {
+    /// Only the fees with this status are returned, if provided.
+    pub status: Option<TokenListingFeeStatus>,
+}
+
+/// Runtime flag switched by the operator.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+struct RuntimeFlagRequest {
+    pub enabled: bool,
+}
+
+/// Emergency pause switched by the operator.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct PauseRequest {
+    pub paused: bool,
+    /// Reason returned to the users whose transactions are rejected while the server is paused.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Transactions to evict from the mempool: either a single transaction
+/// or all the transactions of an account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct EvictTxsRequest {
+    #[serde(default)]
+    pub tx_hash: Option<TxHash>,
+    #[serde(default)]
+    pub account: Option<Address>,
+    /// Reason of the eviction, stored in the audit log.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct EvictTxsResponse {
+    /// Hashes of the removed transactions, including the other transactions of the affected batches.
+    pub evicted: Vec<TxHash>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct AuditLogQuery {
+    pub limit: Option<u32>,
 }
 
 struct AuthTokenValidator<'a> {
@@ -75,6 +154,14 @@ impl<'a> AuthTokenValidator<'a> {
         Ok(())
     }
 
+    /// Returns the subject of the token, i.e. the operator performing the request.
+    fn token_subject(&self, token: &str) -> Result<String, JwtError> {
+        let token_data =
+            decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+
+        Ok(token_data.claims.sub)
+    }
+
     async fn validator(
         &self,
         req: ServiceRequest,
@@ -89,11 +176,28 @@ impl<'a> AuthTokenValidator<'a> {
     }
 }
 
+fn storage_error(e: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("failed to process the request in the storage: {}", e);
+    actix_web::error::ErrorInternalServerError("storage layer error")
+}
+
+fn core_api_error(e: CoreApiError) -> actix_web::Error {
+    vlog::warn!("failed to process the request in the Core: {}", e);
+    match e {
+        CoreApiError::Unavailable => actix_web::error::ErrorServiceUnavailable("core unavailable"),
+        CoreApiError::Request(_) | CoreApiError::Grpc(_) => {
+            actix_web::error::ErrorInternalServerError("core api error")
+        }
+    }
+}
+
 async fn add_token(
     data: web::Data<AppState>,
     token_request: web::Json<AddTokenRequest>,
 ) -> actix_web::Result<HttpResponse> {
     let mut storage = data.access_storage().await?;
+    let mut transaction = storage.start_transaction().await.map_err(storage_error)?;
+    let storage = &mut transaction;
 
     // if id is None then set it to next available ID from server.
     let id = match token_request.id {
@@ -123,9 +227,457 @@ async fn add_token(
             actix_web::error::ErrorInternalServerError("storage layer error")
         })?;
 
+    if let Some(listing_fee_id) = token_request.listing_fee_id {
+        let fee = storage
+            .tokens_schema()
+            .resolve_listing_fee(listing_fee_id, TokenListingFeeStatus::Listed, Some(id))
+            .await
+            .map_err(storage_error)?
+            .ok_or_else(|| {
+                actix_web::error::ErrorBadRequest("listing fee not found or already resolved")
+            })?;
+        // The transaction is not committed, so the token and the fee remain untouched.
+        if fee.token_address != token.address {
+            return Err(actix_web::error::ErrorBadRequest(
+                "listing fee is paid for another token",
+            ));
+        }
+    }
+    transaction.commit().await.map_err(storage_error)?;
+    // Token with the same ID could be cached with the previous data.
+    TokenDBCache::invalidate_all();
+
     Ok(HttpResponse::Ok().json(token))
 }
 
+async fn add_listing_fee(
+    data: web::Data<AppState>,
+    fee_request: web::Json<AddListingFeeRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let fee_request = fee_request.into_inner();
+    let fee = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .store_listing_fee(
+            fee_request.token_address,
+            fee_request.payer,
+            fee_request.amount,
+            fee_request.eth_tx_hash,
+        )
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| actix_web::error::ErrorConflict("listing fee is recorded already"))?;
+
+    Ok(HttpResponse::Ok().json(fee))
+}
+
+async fn listing_fees(
+    data: web::Data<AppState>,
+    query: web::Query<ListingFeesQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let fees = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .load_listing_fees(query.status)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(fees))
+}
+
+async fn refund_listing_fee(
+    data: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> actix_web::Result<HttpResponse> {
+    let fee = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .resolve_listing_fee(*id, TokenListingFeeStatus::Refunded, None)
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| {
+            actix_web::error::ErrorBadRequest("listing fee not found or already resolved")
+        })?;
+
+    Ok(HttpResponse::Ok().json(fee))
+}
+
+async fn tx_restrictions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let restrictions = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .load_tx_restrictions()
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(restrictions))
+}
+
+async fn set_tx_restriction(
+    data: web::Data<AppState>,
+    restriction: web::Json<TokenTxRestriction>,
+) -> actix_web::Result<HttpResponse> {
+    let restriction = restriction.into_inner();
+    let mut storage = data.access_storage().await?;
+
+    let token = storage
+        .tokens_schema()
+        .get_token(restriction.token_id.into())
+        .await
+        .map_err(storage_error)?;
+    if token.is_none() {
+        return Err(actix_web::error::ErrorBadRequest("token not found"));
+    }
+
+    storage
+        .tokens_schema()
+        .store_tx_restriction(&restriction)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(restriction))
+}
+
+async fn remove_tx_restriction(
+    data: web::Data<AppState>,
+    token_id: web::Path<u16>,
+) -> actix_web::Result<HttpResponse> {
+    let removed = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .remove_tx_restriction(TokenId(*token_id))
+        .await
+        .map_err(storage_error)?;
+    if !removed {
+        return Err(actix_web::error::ErrorBadRequest(
+            "token has no restrictions",
+        ));
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn fee_disabled_tokens(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let tokens = data
+        .access_storage()
+        .await?
+        .tokens_schema()
+        .load_fee_disabled_tokens()
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+async fn disable_token_fees(
+    data: web::Data<AppState>,
+    credentials: BearerAuth,
+    token_id: web::Path<u16>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(*token_id);
+    let actor = AuthTokenValidator::new(&data.secret_auth)
+        .token_subject(credentials.token())
+        .map_err(actix_web::error::ErrorUnauthorized)?;
+    let mut storage = data.access_storage().await?;
+
+    let token = storage
+        .tokens_schema()
+        .get_token(token_id.into())
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("token not found"))?;
+
+    let mut transaction = storage.start_transaction().await.map_err(storage_error)?;
+    let disabled = transaction
+        .tokens_schema()
+        .store_fee_disabled_token(token_id)
+        .await
+        .map_err(storage_error)?;
+    if !disabled {
+        return Err(actix_web::error::ErrorBadRequest(
+            "fees are already disabled for the token",
+        ));
+    }
+    transaction
+        .admin_audit_schema()
+        .record(
+            "disable_token_fees",
+            &actor,
+            json!({ "token_id": token_id }),
+        )
+        .await
+        .map_err(storage_error)?;
+    transaction.commit().await.map_err(storage_error)?;
+    TokenDBCache::invalidate_all();
+    vlog::info!("Fees in {} are disabled by the operator", token.symbol);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn enable_token_fees(
+    data: web::Data<AppState>,
+    credentials: BearerAuth,
+    token_id: web::Path<u16>,
+) -> actix_web::Result<HttpResponse> {
+    let token_id = TokenId(*token_id);
+    let actor = AuthTokenValidator::new(&data.secret_auth)
+        .token_subject(credentials.token())
+        .map_err(actix_web::error::ErrorUnauthorized)?;
+    let mut storage = data.access_storage().await?;
+
+    let mut transaction = storage.start_transaction().await.map_err(storage_error)?;
+    let enabled = transaction
+        .tokens_schema()
+        .remove_fee_disabled_token(token_id)
+        .await
+        .map_err(storage_error)?;
+    if !enabled {
+        return Err(actix_web::error::ErrorBadRequest(
+            "fees are not disabled for the token",
+        ));
+    }
+    transaction
+        .admin_audit_schema()
+        .record("enable_token_fees", &actor, json!({ "token_id": token_id }))
+        .await
+        .map_err(storage_error)?;
+    transaction.commit().await.map_err(storage_error)?;
+    TokenDBCache::invalidate_all();
+    vlog::info!("Fees in token {} are enabled by the operator", token_id);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn fast_processing(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let enabled = data
+        .access_storage()
+        .await?
+        .runtime_flags_schema()
+        .is_enabled(RuntimeFlag::FastProcessing)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(RuntimeFlagRequest { enabled }))
+}
+
+async fn set_fast_processing(
+    data: web::Data<AppState>,
+    request: web::Json<RuntimeFlagRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    data.access_storage()
+        .await?
+        .runtime_flags_schema()
+        .set_enabled(RuntimeFlag::FastProcessing, request.enabled)
+        .await
+        .map_err(storage_error)?;
+    RuntimeFlagsCache::invalidate_all();
+    vlog::info!(
+        "Fast processing of withdrawals is {} by the operator",
+        if request.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    Ok(HttpResponse::Ok().json(request))
+}
+
+async fn pause(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let paused = storage
+        .runtime_flags_schema()
+        .is_enabled(RuntimeFlag::Paused)
+        .await
+        .map_err(storage_error)?;
+    let reason = storage
+        .runtime_flags_schema()
+        .reason(RuntimeFlag::Paused)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(PauseRequest { paused, reason }))
+}
+
+async fn set_pause(
+    data: web::Data<AppState>,
+    request: web::Json<PauseRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    data.access_storage()
+        .await?
+        .runtime_flags_schema()
+        .set_enabled_with_reason(
+            RuntimeFlag::Paused,
+            request.paused,
+            request.reason.as_deref(),
+        )
+        .await
+        .map_err(storage_error)?;
+    RuntimeFlagsCache::invalidate_all();
+    if request.paused {
+        vlog::warn!(
+            "Server is paused by the operator, reason: {}",
+            request.reason.as_deref().unwrap_or("not provided")
+        );
+    } else {
+        vlog::info!("Server is resumed by the operator");
+    }
+
+    Ok(HttpResponse::Ok().json(request))
+}
+
+async fn maintenance(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+    let maintenance = MaintenanceInfo::load(&mut storage)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(maintenance))
+}
+
+async fn set_maintenance(
+    data: web::Data<AppState>,
+    credentials: BearerAuth,
+    request: web::Json<MaintenanceInfo>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    let actor = AuthTokenValidator::new(&data.secret_auth)
+        .token_subject(credentials.token())
+        .map_err(actix_web::error::ErrorUnauthorized)?;
+    if request.enabled && matches!(request.until, Some(until) if until <= chrono::Utc::now()) {
+        return Err(actix_web::error::ErrorBadRequest(
+            "expected end of the maintenance has already passed",
+        ));
+    }
+    let state = RuntimeFlagState {
+        enabled: request.enabled,
+        reason: request.message.clone(),
+        until: request.until,
+    };
+
+    let mut storage = data.access_storage().await?;
+    let mut transaction = storage.start_transaction().await.map_err(storage_error)?;
+    transaction
+        .runtime_flags_schema()
+        .set_state(RuntimeFlag::Maintenance, &state)
+        .await
+        .map_err(storage_error)?;
+    // Operators drain the servers before the upgrades by enabling the maintenance,
+    // so the switches are recorded to know who started and finished it.
+    transaction
+        .admin_audit_schema()
+        .record("set_maintenance", &actor, json!(request))
+        .await
+        .map_err(storage_error)?;
+    transaction.commit().await.map_err(storage_error)?;
+    RuntimeFlagsCache::invalidate_all();
+    if request.enabled {
+        vlog::warn!(
+            "Maintenance is started by the operator, expected end: {}",
+            request
+                .until
+                .map(|until| until.to_rfc3339())
+                .unwrap_or_else(|| "not provided".to_string())
+        );
+    } else {
+        vlog::info!("Maintenance is finished by the operator");
+    }
+
+    Ok(HttpResponse::Ok().json(request))
+}
+
+async fn seal_block(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    data.core_api_client
+        .seal_block()
+        .await
+        .map_err(core_api_error)?;
+    vlog::info!("Sealing of the pending block is requested by the operator");
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn evict_txs(
+    data: web::Data<AppState>,
+    credentials: BearerAuth,
+    request: web::Json<EvictTxsRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let request = request.into_inner();
+    let actor = AuthTokenValidator::new(&data.secret_auth)
+        .token_subject(credentials.token())
+        .map_err(actix_web::error::ErrorUnauthorized)?;
+
+    let evicted = match (request.tx_hash, request.account) {
+        (Some(tx_hash), None) => data.core_api_client.evict_tx(tx_hash).await,
+        (None, Some(account)) => data.core_api_client.evict_account_txs(account).await,
+        _ => {
+            return Err(actix_web::error::ErrorBadRequest(
+                "either tx_hash or account should be specified",
+            ))
+        }
+    }
+    .map_err(core_api_error)?;
+
+    data.access_storage()
+        .await?
+        .admin_audit_schema()
+        .record(
+            "evict_txs",
+            &actor,
+            json!({
+                "tx_hash": request.tx_hash,
+                "account": request.account,
+                "reason": request.reason,
+                "evicted": evicted,
+            }),
+        )
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(EvictTxsResponse { evicted }))
+}
+
+async fn audit_log(
+    data: web::Data<AppState>,
+    query: web::Query<AuditLogQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let entries = data
+        .access_storage()
+        .await?
+        .admin_audit_schema()
+        .load_latest(query.limit.unwrap_or(100))
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+/// Returns the debug bundle collected for the block the prover failed to prove.
+/// The bundle is `null` until it's built by the `witness_debug_bundle` tool.
+async fn witness_debug_bundle(
+    data: web::Data<AppState>,
+    block: web::Path<u32>,
+) -> actix_web::Result<HttpResponse> {
+    let bundle = data
+        .access_storage()
+        .await?
+        .prover_schema()
+        .load_debug_bundle(BlockNumber(*block))
+        .await
+        .map_err(storage_error)?
+        .ok_or_else(|| {
+            actix_web::error::ErrorNotFound("no proving failure reported for the block")
+        })?;
+
+    Ok(HttpResponse::Ok().json(bundle))
+}
+
 async fn run_server(app_state: AppState, bind_to: SocketAddr) {
     HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -143,6 +695,40 @@ async fn run_server(app_state: AppState, bind_to: SocketAddr) {
             .wrap(auth)
             .app_data(web::Data::new(app_state.clone()))
             .route("/tokens", web::post().to(add_token))
+            .route("/token_listing_fees", web::post().to(add_listing_fee))
+            .route("/token_listing_fees", web::get().to(listing_fees))
+            .route(
+                "/token_listing_fees/{id}/refund",
+                web::post().to(refund_listing_fee),
+            )
+            .route("/token_tx_restrictions", web::get().to(tx_restrictions))
+            .route("/token_tx_restrictions", web::post().to(set_tx_restriction))
+            .route(
+                "/token_tx_restrictions/{token_id}",
+                web::delete().to(remove_tx_restriction),
+            )
+            .route("/fee_disabled_tokens", web::get().to(fee_disabled_tokens))
+            .route(
+                "/fee_disabled_tokens/{token_id}",
+                web::post().to(disable_token_fees),
+            )
+            .route(
+                "/fee_disabled_tokens/{token_id}",
+                web::delete().to(enable_token_fees),
+            )
+            .route("/fast_processing", web::get().to(fast_processing))
+            .route("/fast_processing", web::post().to(set_fast_processing))
+            .route("/pause", web::get().to(pause))
+            .route("/pause", web::post().to(set_pause))
+            .route("/maintenance", web::get().to(maintenance))
+            .route("/maintenance", web::post().to(set_maintenance))
+            .route("/seal_block", web::post().to(seal_block))
+            .route("/mempool/evict", web::post().to(evict_txs))
+            .route("/audit_log", web::get().to(audit_log))
+            .route(
+                "/witness_debug_bundles/{block}",
+                web::get().to(witness_debug_bundle),
+            )
     })
     .workers(1)
     .bind(&bind_to)
@@ -156,6 +742,7 @@ pub fn start_admin_server(
     bind_to: SocketAddr,
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    core_api_client: CoreApiClient,
     panic_notify: mpsc::Sender<bool>,
 ) {
     thread::Builder::new()
@@ -165,6 +752,7 @@ pub fn start_admin_server(
             actix_rt::System::new("api-server").block_on(async move {
                 let app_state = AppState {
                     connection_pool,
+                    core_api_client,
                     secret_auth,
                 };
 
@@ -0,0 +1,189 @@
+//! Authentication of the trusted integrators by the API keys.
+//!
+//! Privileged submission options (e.g. the fast processing of withdrawals) can be restricted
+//! to the integrators with an API key, and every key has its own hourly quota of the
+//! privileged requests. The keys are passed in the `X-API-Key` header of the REST API
+//! requests, or as a parameter of the JSON RPC methods.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// External uses
+use actix_web::{dev::ServiceRequest, HttpMessage, HttpRequest};
+
+// Workspace uses
+use zksync_api_client::rest::v1::API_KEY_HEADER;
+use zksync_config::configs::api::ApiAuth;
+
+// Local uses
+use super::rest::v1::Error as ApiError;
+
+/// Period of the API key quotas.
+const QUOTA_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug)]
+struct QuotaWindow {
+    started_at: Instant,
+    used: u32,
+}
+
+/// Integrator authenticated by the API key.
+#[derive(Debug)]
+pub struct ApiClient {
+    /// Name of the integrator, the key itself is never logged.
+    pub name: String,
+    /// Maximum number of the privileged requests per hour, zero means no limit.
+    hourly_quota: u32,
+    window: Mutex<QuotaWindow>,
+}
+
+impl ApiClient {
+    fn new(name: String, hourly_quota: u32) -> Self {
+        Self {
+            name,
+            hourly_quota,
+            window: Mutex::new(QuotaWindow {
+                started_at: Instant::now(),
+                used: 0,
+            }),
+        }
+    }
+
+    /// Takes a privileged request from the hourly quota of the key. If the quota is exhausted,
+    /// returns the time after which it's renewed, rounded up to whole seconds.
+    pub fn take_quota(&self) -> Result<(), Duration> {
+        self.take_quota_at(Instant::now())
+    }
+
+    fn take_quota_at(&self, now: Instant) -> Result<(), Duration> {
+        if self.hourly_quota == 0 {
+            return Ok(());
+        }
+
+        let mut window = self.window.lock().unwrap();
+        if now.saturating_duration_since(window.started_at) >= QUOTA_WINDOW {
+            *window = QuotaWindow {
+                started_at: now,
+                used: 0,
+            };
+        }
+
+        if window.used < self.hourly_quota {
+            window.used += 1;
+            Ok(())
+        } else {
+            let remaining = QUOTA_WINDOW - now.saturating_duration_since(window.started_at);
+            let round_up = remaining.subsec_nanos() > 0;
+            Err(Duration::from_secs(remaining.as_secs() + round_up as u64))
+        }
+    }
+}
+
+/// API keys of the trusted integrators, shared by all the API servers.
+#[derive(Debug, Clone)]
+pub struct ApiKeys {
+    clients: Arc<HashMap<String, Arc<ApiClient>>>,
+    /// Whether the privileged submission options require an API key.
+    pub required: bool,
+}
+
+impl ApiKeys {
+    pub fn from_config(config: &ApiAuth) -> Self {
+        let clients = config
+            .api_keys()
+            .iter()
+            .map(|api_key| {
+                let client = ApiClient::new(api_key.name.clone(), api_key.hourly_quota);
+                (api_key.key.clone(), Arc::new(client))
+            })
+            .collect();
+
+        Self {
+            clients: Arc::new(clients),
+            required: config.require_api_key,
+        }
+    }
+
+    /// Returns the integrator the API key belongs to, or `None` if the key is unknown.
+    pub fn authenticate(&self, api_key: &str) -> Option<Arc<ApiClient>> {
+        let client = self.clients.get(api_key).cloned();
+        if client.is_none() {
+            metrics::counter!("api.auth.invalid_api_keys", 1);
+        }
+        client
+    }
+
+    /// Authenticates the REST API request by the API key from the header, and stores the
+    /// integrator in the request extensions. Requests without the key are anonymous,
+    /// while the requests with an unknown key are rejected.
+    pub fn authenticate_rest_request(&self, req: &ServiceRequest) -> Result<(), ApiError> {
+        let api_key = match req.headers().get(API_KEY_HEADER) {
+            Some(api_key) => api_key,
+            None => return Ok(()),
+        };
+
+        let client = api_key
+            .to_str()
+            .ok()
+            .and_then(|api_key| self.authenticate(api_key))
+            .ok_or_else(|| ApiError::unauthorized("Invalid API key"))?;
+        req.extensions_mut().insert(client);
+        Ok(())
+    }
+}
+
+/// Returns the integrator authenticated by the middleware, if any.
+pub fn rest_api_client(req: &HttpRequest) -> Option<Arc<ApiClient>> {
+    req.extensions().get::<Arc<ApiClient>>().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_config::configs::api::ApiKey;
+
+    #[test]
+    fn api_keys_authentication() {
+        let api_keys = ApiKeys::from_config(&ApiAuth {
+            require_api_key: true,
+            api_keys: Some(vec![ApiKey {
+                name: "wallet".into(),
+                key: "sample-key".into(),
+                hourly_quota: 0,
+            }]),
+        });
+
+        let client = api_keys.authenticate("sample-key").unwrap();
+        assert_eq!(client.name, "wallet");
+        assert!(api_keys.authenticate("wallet").is_none());
+        assert!(api_keys.authenticate("").is_none());
+    }
+
+    #[test]
+    fn quota_is_renewed_hourly() {
+        let client = ApiClient::new("wallet".into(), 2);
+        let now = client.window.lock().unwrap().started_at;
+
+        assert_eq!(client.take_quota_at(now), Ok(()));
+        assert_eq!(client.take_quota_at(now), Ok(()));
+        assert_eq!(
+            client.take_quota_at(now + Duration::from_secs(600)),
+            Err(Duration::from_secs(3000))
+        );
+
+        let next_hour = now + QUOTA_WINDOW;
+        assert_eq!(client.take_quota_at(next_hour), Ok(()));
+        assert_eq!(client.take_quota_at(next_hour), Ok(()));
+        assert!(client.take_quota_at(next_hour).is_err());
+
+        // Zero quota means no limit.
+        let client = ApiClient::new("exchange".into(), 0);
+        for _ in 0..100 {
+            assert_eq!(client.take_quota_at(now), Ok(()));
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use super::ExecutedOps;
+use crate::api_server::rpc_server::types::{BlockNotification, BlockStage};
+use crate::api_server::runtime_flags::MaintenanceInfo;
 use futures::{channel::mpsc, SinkExt};
 use std::time::{Duration, Instant};
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, StorageActionType};
 use zksync_types::{
-    block::ExecutedOperations, block::PendingBlock, ActionType, BlockNumber, Operation,
+    block::{Block, ExecutedOperations, PendingBlock},
+    ActionType, BlockNumber, Operation, H256,
 };
 
 /// Simple awaiter for the database futures, which will add a log entry upon DB failure
@@ -21,7 +24,7 @@ macro_rules! await_db {
 }
 
 /// Event fetcher is an actor which polls the database from time to time in order to see
-/// whether new blocks were committed or verified.
+/// whether new blocks were sealed, committed or verified.
 ///
 /// Once tha new data is available, it is sent to the `OperationNotifier`, which broadcasts it
 /// to the subscribers.
@@ -31,11 +34,16 @@ pub struct EventFetcher {
     db_pool: ConnectionPool,
 
     last_committed_block: BlockNumber,
+    /// Last block with the commit transaction confirmed on L1.
+    last_committed_confirmed_block: BlockNumber,
     last_verified_block: BlockNumber,
     pending_block: Option<PendingBlock>,
+    maintenance: MaintenanceInfo,
 
     operations_sender: mpsc::Sender<Operation>,
     txs_sender: mpsc::Sender<ExecutedOps>,
+    maintenance_sender: mpsc::Sender<MaintenanceInfo>,
+    blocks_sender: mpsc::Sender<BlockNotification>,
 }
 
 impl EventFetcher {
@@ -44,24 +52,33 @@ impl EventFetcher {
         miniblock_interval: Duration,
         operations_sender: mpsc::Sender<Operation>,
         txs_sender: mpsc::Sender<ExecutedOps>,
+        maintenance_sender: mpsc::Sender<MaintenanceInfo>,
+        blocks_sender: mpsc::Sender<BlockNotification>,
     ) -> anyhow::Result<Self> {
         let mut fetcher = EventFetcher {
             miniblock_interval,
             db_pool,
 
             last_committed_block: BlockNumber(0),
+            last_committed_confirmed_block: BlockNumber(0),
             last_verified_block: BlockNumber(0),
             pending_block: None,
+            maintenance: MaintenanceInfo::default(),
 
             operations_sender,
             txs_sender,
+            maintenance_sender,
+            blocks_sender,
         };
 
         let pending_block = fetcher.load_pending_block().await?;
         let last_committed_block = fetcher.last_committed_block().await?;
+        let last_committed_confirmed_block = fetcher.last_committed_confirmed_block().await?;
         let last_verified_block = fetcher.last_verified_block().await?;
+        fetcher.maintenance = fetcher.load_maintenance().await?;
 
         fetcher.last_committed_block = last_committed_block;
+        fetcher.last_committed_confirmed_block = last_committed_confirmed_block;
         fetcher.last_verified_block = last_verified_block;
         if let Some(block) = pending_block {
             // We only want to set this field if the pending block is actually the latest block (ahead of last committed one).
@@ -79,7 +96,19 @@ impl EventFetcher {
         loop {
             interval.tick().await;
 
-            // 1. Update last verified block.
+            // 1. Update last block committed on L1.
+            let last_committed_confirmed_block =
+                await_db!(self.last_committed_confirmed_block(), continue);
+            if last_committed_confirmed_block > self.last_committed_confirmed_block {
+                self.send_committed_blocks(
+                    self.last_committed_confirmed_block,
+                    last_committed_confirmed_block,
+                )
+                .await;
+                self.last_committed_confirmed_block = last_committed_confirmed_block;
+            }
+
+            // 2. Update last verified block.
             let last_verified_block = await_db!(self.last_verified_block(), continue);
             if last_verified_block > self.last_verified_block {
                 self.send_operations(
@@ -91,7 +120,7 @@ impl EventFetcher {
                 self.last_verified_block = last_verified_block;
             }
 
-            // 2. Update last committed block.
+            // 3. Update last committed block.
             let last_committed_block = await_db!(self.last_committed_block(), continue);
             if last_committed_block > self.last_committed_block {
                 self.send_operations(
@@ -103,7 +132,7 @@ impl EventFetcher {
                 self.last_committed_block = last_committed_block;
             }
 
-            // 3. Update pending block (it may contain new executed txs).
+            // 4. Update pending block (it may contain new executed txs).
             let pending_block = await_db!(self.load_pending_block(), continue);
             if let Some(pending_block) = pending_block {
                 // We're only interested in the pending blocks **newer** than the last committed blocks;
@@ -111,6 +140,16 @@ impl EventFetcher {
                     self.txs_sender.send(executed_ops).await.unwrap_or_default();
                 }
             }
+
+            // 5. Check whether the maintenance status was changed.
+            let maintenance = await_db!(self.load_maintenance(), continue);
+            if maintenance != self.maintenance {
+                self.maintenance_sender
+                    .send(maintenance.clone())
+                    .await
+                    .unwrap_or_default();
+                self.maintenance = maintenance;
+            }
         }
     }
 
@@ -177,6 +216,11 @@ impl EventFetcher {
                 self.load_operation(BlockNumber(block_idx), action),
                 continue
             );
+            let stage = match action {
+                ActionType::COMMIT => BlockStage::Sealed,
+                ActionType::VERIFY => BlockStage::Verified,
+            };
+            self.send_block_notification(&operation.block, stage).await;
             self.operations_sender
                 .send(operation)
                 .await
@@ -185,6 +229,67 @@ impl EventFetcher {
         metrics::histogram!("api.event_fetcher.send_operations", start.elapsed());
     }
 
+    /// Notifies the block subscribers about the blocks with the commit transaction confirmed on L1.
+    async fn send_committed_blocks(
+        &mut self,
+        current_last_block: BlockNumber,
+        new_last_block: BlockNumber,
+    ) {
+        for block_idx in (*current_last_block + 1)..=*new_last_block {
+            let operation = await_db!(
+                self.load_operation(BlockNumber(block_idx), ActionType::COMMIT),
+                continue
+            );
+            self.send_block_notification(&operation.block, BlockStage::Committed)
+                .await;
+        }
+    }
+
+    async fn send_block_notification(&mut self, block: &Block, stage: BlockStage) {
+        let notification = await_db!(self.load_block_notification(block, stage), return);
+        self.blocks_sender
+            .send(notification)
+            .await
+            .unwrap_or_default();
+    }
+
+    async fn load_block_notification(
+        &mut self,
+        block: &Block,
+        stage: BlockStage,
+    ) -> anyhow::Result<BlockNotification> {
+        let mut notification = BlockNotification {
+            block_number: block.block_number,
+            stage,
+            new_state_root: block.new_root_hash,
+            commit_tx_hash: None,
+            verify_tx_hash: None,
+        };
+        if stage == BlockStage::Sealed {
+            // Sealed block is not sent to L1 yet.
+            return Ok(notification);
+        }
+
+        let mut storage = self.db_pool.access_storage().await?;
+        let stages = storage
+            .chain()
+            .block_schema()
+            .load_block_stages(block.block_number)
+            .await?;
+        for stage in stages {
+            let tx_hash = stage.final_hash.map(|hash| H256::from_slice(&hash));
+            match stage.action_type {
+                StorageActionType::COMMIT => {
+                    notification.commit_tx_hash = tx_hash.or(notification.commit_tx_hash)
+                }
+                StorageActionType::VERIFY => {
+                    notification.verify_tx_hash = tx_hash.or(notification.verify_tx_hash)
+                }
+            }
+        }
+        Ok(notification)
+    }
+
     async fn load_pending_block(&mut self) -> anyhow::Result<Option<PendingBlock>> {
         let start = Instant::now();
         let mut storage = self
@@ -216,6 +321,27 @@ impl EventFetcher {
         Ok(last_block)
     }
 
+    async fn last_committed_confirmed_block(&mut self) -> anyhow::Result<BlockNumber> {
+        let start = Instant::now();
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .expect("Can't get access to the storage");
+
+        let last_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_confirmed_block()
+            .await?;
+
+        metrics::histogram!(
+            "api.event_fetcher.last_committed_confirmed_block",
+            start.elapsed()
+        );
+        Ok(last_block)
+    }
+
     async fn last_verified_block(&mut self) -> anyhow::Result<BlockNumber> {
         let start = Instant::now();
         let mut storage = self
@@ -234,6 +360,12 @@ impl EventFetcher {
         Ok(last_block)
     }
 
+    async fn load_maintenance(&mut self) -> anyhow::Result<MaintenanceInfo> {
+        let mut storage = self.db_pool.access_storage().await?;
+        let maintenance = MaintenanceInfo::load(&mut storage).await?;
+        Ok(maintenance)
+    }
+
     async fn load_operation(
         &mut self,
         block_number: BlockNumber,
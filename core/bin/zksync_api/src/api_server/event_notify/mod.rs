@@ -1,4 +1,8 @@
-use super::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp};
+use super::rpc_server::types::{
+    BlockNotification, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    TxStatusNotification,
+};
+use super::runtime_flags::MaintenanceInfo;
 use futures::{channel::mpsc, select, stream::StreamExt};
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -41,11 +45,23 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    Maintenance {
+        subscriber: Subscriber<MaintenanceInfo>,
+    },
+    TransactionStatus {
+        hash: TxHash,
+        subscriber: Subscriber<TxStatusNotification>,
+    },
+    Blocks {
+        subscriber: Subscriber<BlockNotification>,
+    },
 }
 
 pub enum EventNotifierRequest {
     Sub(EventSubscribeRequest),
     Unsub(SubscriptionId),
+    /// Transactions were accepted by the API server and sent to the mempool.
+    TxsReceived(Vec<TxHash>),
 }
 
 #[derive(Debug)]
@@ -62,6 +78,8 @@ pub fn start_sub_notifier(
 ) -> tokio::task::JoinHandle<()> {
     let (new_block_sender, mut new_block_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
     let (new_txs_sender, mut new_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (maintenance_sender, mut maintenance_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (blocks_sender, mut blocks_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
 
     let mut notifier = OperationNotifier::new(api_requests_caches_size, db_pool.clone());
 
@@ -71,6 +89,8 @@ pub fn start_sub_notifier(
             miniblock_interval,
             new_block_sender,
             new_txs_sender,
+            maintenance_sender,
+            blocks_sender,
         )
         .await
         .expect("Unable to create event fetcher");
@@ -94,6 +114,16 @@ pub fn start_sub_notifier(
                             .unwrap_or_default();
                     }
                 },
+                maintenance = maintenance_receiver.next() => {
+                    if let Some(maintenance) = maintenance {
+                        notifier.handle_maintenance_update(maintenance);
+                    }
+                },
+                block_notification = blocks_receiver.next() => {
+                    if let Some(block_notification) = block_notification {
+                        notifier.handle_block_notification(block_notification);
+                    }
+                },
                 new_sub = subscription_stream.next() => {
                     if let Some(new_sub) = new_sub {
                         notifier.handle_notify_req(new_sub)
@@ -1,6 +1,9 @@
 use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    BlockInfo, BlockNotification, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    TxStatusNotification, TxStatusStage,
 };
+use crate::api_server::runtime_flags::MaintenanceInfo;
+use futures::{compat::Future01CompatExt, FutureExt};
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::Instant;
 use zksync_storage::ConnectionPool;
@@ -11,16 +14,26 @@ use zksync_types::{
 };
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{SubStorage, TxStatusSubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps, SubscriptionSender,
 };
 
+const MAINTENANCE_SUB_PREFIX: &str = "mtsub";
+const BLOCKS_SUB_PREFIX: &str = "blsub";
+
 pub struct OperationNotifier {
     state: NotifierState,
 
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    tx_status_subs: TxStatusSubStorage,
+    /// Unlike other subscriptions, maintenance subscribers are notified about every status change
+    /// until they unsubscribe.
+    maintenance_subs: Vec<SubscriptionSender<MaintenanceInfo>>,
+    /// Block subscribers are notified about every stage of every block until they unsubscribe.
+    block_subs: Vec<SubscriptionSender<BlockNotification>>,
 }
 
 impl OperationNotifier {
@@ -30,6 +43,9 @@ impl OperationNotifier {
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            tx_status_subs: TxStatusSubStorage::new(),
+            maintenance_subs: Vec::new(),
+            block_subs: Vec::new(),
         }
     }
 
@@ -61,11 +77,22 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::Maintenance { subscriber } => {
+                    self.add_maintenance_sub(subscriber).await
+                }
+                EventSubscribeRequest::TransactionStatus { hash, subscriber } => {
+                    self.add_tx_status_sub(hash, subscriber).await
+                }
+                EventSubscribeRequest::Blocks { subscriber } => self.add_blocks_sub(subscriber),
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
                 .handle_unsub(sub_id)
                 .map_err(|e| anyhow::format_err!("Failed to remove sub: {}", e)),
+            EventNotifierRequest::TxsReceived(hashes) => {
+                self.handle_txs_received(hashes);
+                Ok(())
+            }
         }
     }
 
@@ -73,10 +100,15 @@ impl OperationNotifier {
     pub async fn handle_new_block(&mut self, op: Operation) -> Result<(), anyhow::Error> {
         let start = Instant::now();
         let action = op.action.get_type();
+        let stage = match action {
+            ActionType::COMMIT => TxStatusStage::Committed,
+            ActionType::VERIFY => TxStatusStage::Verified,
+        };
 
         self.handle_executed_operations(
             op.block.block_transactions.clone(),
             action,
+            stage,
             op.block.block_number,
         )?;
 
@@ -114,6 +146,7 @@ impl OperationNotifier {
         &mut self,
         ops: Vec<ExecutedOperations>,
         action: ActionType,
+        stage: TxStatusStage,
         block_number: BlockNumber,
     ) -> Result<(), anyhow::Error> {
         let start = Instant::now();
@@ -121,6 +154,19 @@ impl OperationNotifier {
             match tx {
                 ExecutedOperations::Tx(tx) => {
                     let hash = tx.signed_tx.hash();
+                    if self.tx_status_subs.subscriber_exists(&hash) {
+                        self.tx_status_subs.notify(TxStatusNotification {
+                            tx_hash: hash,
+                            stage,
+                            success: Some(tx.success),
+                            fail_reason: tx.fail_reason.clone(),
+                            block: Some(BlockInfo {
+                                block_number: i64::from(*block_number),
+                                committed: stage >= TxStatusStage::Committed,
+                                verified: stage == TxStatusStage::Verified,
+                            }),
+                        });
+                    }
                     let resp = TransactionInfoResp {
                         executed: true,
                         success: Some(tx.success),
@@ -159,14 +205,45 @@ impl OperationNotifier {
         self.handle_executed_operations(
             exec_batch.operations,
             ActionType::COMMIT,
+            TxStatusStage::Executed,
             exec_batch.block_number,
         )
     }
 
+    /// Notifies the transaction status subscribers about the transactions accepted by the server.
+    fn handle_txs_received(&mut self, hashes: Vec<TxHash>) {
+        for hash in hashes {
+            self.tx_status_subs.notify(TxStatusNotification {
+                tx_hash: hash,
+                stage: TxStatusStage::Received,
+                success: None,
+                fail_reason: None,
+                block: None,
+            });
+        }
+    }
+
+    /// Notifies the maintenance subscribers about the changed maintenance status.
+    pub fn handle_maintenance_update(&mut self, maintenance: MaintenanceInfo) {
+        for sub in &self.maintenance_subs {
+            tokio::spawn(sub.sink.notify(Ok(maintenance.clone())).compat().map(drop));
+        }
+    }
+
+    /// Notifies the block subscribers about the block which has reached the next stage.
+    pub fn handle_block_notification(&mut self, notification: BlockNotification) {
+        for sub in &self.block_subs {
+            tokio::spawn(sub.sink.notify(Ok(notification.clone())).compat().map(drop));
+        }
+    }
+
     /// Removes provided subscription from the list.
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
+        self.maintenance_subs.retain(|sub| sub.id != sub_id);
+        self.block_subs.retain(|sub| sub.id != sub_id);
+        self.tx_status_subs.remove(sub_id.clone())?;
         self.account_subs.remove(sub_id)?;
         Ok(())
     }
@@ -265,6 +342,63 @@ impl OperationNotifier {
         Ok(())
     }
 
+    /// Add transaction status subscription. The subscriber is notified about the current
+    /// status of the transaction right away, if the transaction is already known.
+    async fn add_tx_status_sub(
+        &mut self,
+        hash: TxHash,
+        sub: Subscriber<TxStatusNotification>,
+    ) -> Result<(), anyhow::Error> {
+        let start = Instant::now();
+        let sub_id = self.tx_status_subs.generate_sub_id(hash);
+
+        let current_status = if let Some(receipt) = self.state.get_tx_receipt(&hash).await? {
+            // There may be no block, if transaction was executed in the pending block only.
+            let block_info = self
+                .state
+                .get_block_info(BlockNumber(receipt.block_number as u32))
+                .await?
+                .filter(|block_info| block_info.block_number == receipt.block_number);
+            let stage = if receipt.verified {
+                TxStatusStage::Verified
+            } else if block_info.is_some() {
+                TxStatusStage::Committed
+            } else {
+                TxStatusStage::Executed
+            };
+            Some(TxStatusNotification {
+                tx_hash: hash,
+                stage,
+                success: Some(receipt.success),
+                fail_reason: receipt.fail_reason,
+                block: Some(block_info.unwrap_or(BlockInfo {
+                    block_number: receipt.block_number,
+                    committed: false,
+                    verified: false,
+                })),
+            })
+        } else {
+            let mut storage = self.state.db_pool.access_storage().await?;
+            let received = storage.chain().mempool_schema().contains_tx(hash).await?;
+            if received {
+                Some(TxStatusNotification {
+                    tx_hash: hash,
+                    stage: TxStatusStage::Received,
+                    success: None,
+                    fail_reason: None,
+                    block: None,
+                })
+            } else {
+                None
+            }
+        };
+
+        self.tx_status_subs
+            .insert_new(sub_id, sub, hash, current_status)?;
+        metrics::histogram!("api.notifier.add_tx_status_sub", start.elapsed());
+        Ok(())
+    }
+
     /// Add account info subscription.
     async fn add_account_update_sub(
         &mut self,
@@ -282,4 +416,43 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add maintenance status subscription. The current status is sent to the subscriber right away.
+    async fn add_maintenance_sub(
+        &mut self,
+        sub: Subscriber<MaintenanceInfo>,
+    ) -> Result<(), anyhow::Error> {
+        let sub_id = SubscriptionId::String(format!(
+            "{}/{}",
+            MAINTENANCE_SUB_PREFIX,
+            zksync_crypto::rand::random::<u64>()
+        ));
+
+        let mut storage = self.state.db_pool.access_storage().await?;
+        let maintenance = MaintenanceInfo::load(&mut storage).await?;
+
+        let sink = sub
+            .assign_id(sub_id.clone())
+            .map_err(|_| anyhow::format_err!("SubIdAssign"))?;
+        tokio::spawn(sink.notify(Ok(maintenance)).compat().map(drop));
+        self.maintenance_subs
+            .push(SubscriptionSender { id: sub_id, sink });
+        Ok(())
+    }
+
+    /// Add block lifecycle subscription.
+    fn add_blocks_sub(&mut self, sub: Subscriber<BlockNotification>) -> Result<(), anyhow::Error> {
+        let sub_id = SubscriptionId::String(format!(
+            "{}/{}",
+            BLOCKS_SUB_PREFIX,
+            zksync_crypto::rand::random::<u64>()
+        ));
+
+        let sink = sub
+            .assign_id(sub_id.clone())
+            .map_err(|_| anyhow::format_err!("SubIdAssign"))?;
+        self.block_subs
+            .push(SubscriptionSender { id: sub_id, sink });
+        Ok(())
+    }
 }
@@ -1,7 +1,12 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
+use crate::api_server::rpc_server::types::{TxStatusNotification, TxStatusStage};
 use futures::{compat::Future01CompatExt, FutureExt};
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
+use std::{
+    cmp::Ord,
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
 use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
 
 use jsonrpc_pubsub::{
@@ -13,6 +18,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const TX_STATUS_SUB_PREFIX: &str = "txstsub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -157,3 +163,121 @@ where
         Ok(())
     }
 }
+
+/// Subscription to the status of the transaction.
+#[derive(Debug)]
+struct TxStatusSub {
+    sender: SubscriptionSender<TxStatusNotification>,
+    /// The last stage the subscriber was notified about.
+    stage: Option<TxStatusStage>,
+}
+
+/// Storage of the `tx_status` subscriptions. Unlike `SubStorage`, subscribers are notified
+/// about every stage of the transaction until it's verified or failed.
+#[derive(Debug, Default)]
+pub struct TxStatusSubStorage {
+    storage: HashMap<TxHash, Vec<TxStatusSub>>,
+}
+
+impl TxStatusSubStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn generate_sub_id(&self, hash: TxHash) -> SubscriptionId {
+        SubscriptionId::String(format!(
+            "{}/{}/{}",
+            TX_STATUS_SUB_PREFIX,
+            hash.to_string(),
+            zksync_crypto::rand::random::<u64>()
+        ))
+    }
+
+    /// Adds the subscription, notifying it about the current status of the transaction if it's known.
+    pub fn insert_new(
+        &mut self,
+        sub_id: SubscriptionId,
+        sub: Subscriber<TxStatusNotification>,
+        hash: TxHash,
+        current_status: Option<TxStatusNotification>,
+    ) -> anyhow::Result<()> {
+        let subs = self.storage.entry(hash).or_default();
+        if subs.len() >= MAX_LISTENERS_PER_ENTITY {
+            return Ok(());
+        }
+
+        let sink = sub
+            .assign_id(sub_id.clone())
+            .map_err(|_| anyhow::format_err!("SubIdAssign"))?;
+        let stage = current_status.as_ref().map(|status| status.stage);
+        if let Some(status) = current_status {
+            let is_final = status.is_final();
+            tokio::spawn(sink.notify(Ok(status)).compat().map(drop));
+            if is_final {
+                if subs.is_empty() {
+                    self.storage.remove(&hash);
+                }
+                return Ok(());
+            }
+        }
+        subs.push(TxStatusSub {
+            sender: SubscriptionSender { id: sub_id, sink },
+            stage,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, sub_id: SubscriptionId) -> anyhow::Result<()> {
+        let str_sub_id = match &sub_id {
+            SubscriptionId::String(str_sub_id) => str_sub_id,
+            _ => anyhow::bail!("SubscriptionId should be String"),
+        };
+        let mut id_split = str_sub_id.split('/');
+        if id_split.next() != Some(TX_STATUS_SUB_PREFIX) {
+            // Not our type, do nothing.
+            return Ok(());
+        }
+        let hash: TxHash = id_split
+            .next()
+            .and_then(|hash| hash.parse().ok())
+            .ok_or_else(|| anyhow::format_err!("Incorrect id: {:?}", str_sub_id))?;
+
+        if let Some(subs) = self.storage.get_mut(&hash) {
+            subs.retain(|sub| sub.sender.id != sub_id);
+            if subs.is_empty() {
+                self.storage.remove(&hash);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn subscriber_exists(&self, hash: &TxHash) -> bool {
+        self.storage.contains_key(hash)
+    }
+
+    /// Notifies the subscribers that haven't seen this stage of the transaction yet.
+    /// Subscriptions are removed once the final status is sent.
+    pub fn notify(&mut self, status: TxStatusNotification) {
+        let subs = match self.storage.get_mut(&status.tx_hash) {
+            Some(subs) => subs,
+            None => return,
+        };
+        for sub in subs.iter_mut() {
+            if sub.stage.map(|stage| stage < status.stage).unwrap_or(true) {
+                tokio::spawn(
+                    sub.sender
+                        .sink
+                        .notify(Ok(status.clone()))
+                        .compat()
+                        .map(drop),
+                );
+                sub.stage = Some(status.stage);
+            }
+        }
+        if status.is_final() {
+            self.storage.remove(&status.tx_hash);
+        }
+    }
+}
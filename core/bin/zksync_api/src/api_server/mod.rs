@@ -9,39 +9,67 @@ pub use rest::v1;
 
 // External uses
 use futures::channel::mpsc;
+use vlog::Traced;
 // Workspace uses
 use zksync_config::ZkSyncConfig;
 use zksync_storage::ConnectionPool;
 // Local uses
+use crate::core_api_client::CoreApiClient;
 use crate::fee_ticker::TickerRequest;
 use crate::signature_checker;
+use api_keys::ApiKeys;
+use event_notify::start_sub_notifier;
+use rate_limit::RateLimits;
 
 mod admin_server;
+mod api_keys;
 mod event_notify;
 mod helpers;
+mod proxy;
+mod rate_limit;
 mod rest;
 pub mod rpc_server;
 mod rpc_subscriptions;
+mod runtime_flags;
+mod tls;
 mod tx_sender;
 
 /// Amount of threads used by each server to serve requests.
 const THREADS_PER_SERVER: usize = 128;
+/// Capacity of the channel of the subscription requests and the received transactions notifications.
+const EVENT_NOTIFIER_CHANNEL_CAPACITY: usize = 2048;
 
 #[allow(clippy::too_many_arguments)]
 pub fn start_api_server(
     connection_pool: ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
-    ticker_request_sender: mpsc::Sender<TickerRequest>,
+    ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
     config: &ZkSyncConfig,
 ) {
     let (sign_check_sender, sign_check_receiver) = mpsc::channel(32768);
 
     signature_checker::start_sign_checker_detached(
         config.clone(),
+        connection_pool.clone(),
         sign_check_receiver,
         panic_notify.clone(),
     );
 
+    // Notifier of the subscribers is shared by all the servers, so the servers accepting
+    // the transactions can notify the subscribers that the transactions were received.
+    let (event_sub_sender, event_sub_receiver) = mpsc::channel(EVENT_NOTIFIER_CHANNEL_CAPACITY);
+    start_sub_notifier(
+        connection_pool.clone(),
+        event_sub_receiver,
+        config.api.common.caches_size,
+        config.chain.state_keeper.miniblock_iteration_interval(),
+    );
+
+    // Limiters are shared by all the servers, so the clients can't bypass the limits
+    // by spreading the requests between the servers.
+    let rate_limits = RateLimits::from_config(&config.api.rate_limit);
+    let api_keys = ApiKeys::from_config(&config.api.auth);
+
     rest::start_server_thread_detached(
         connection_pool.clone(),
         config.api.rest.bind_addr(),
@@ -49,6 +77,9 @@ pub fn start_api_server(
         panic_notify.clone(),
         ticker_request_sender.clone(),
         sign_check_sender.clone(),
+        event_sub_sender.clone(),
+        rate_limits.clone(),
+        api_keys.clone(),
         config.clone(),
     );
 
@@ -56,6 +87,9 @@ pub fn start_api_server(
         connection_pool.clone(),
         sign_check_sender.clone(),
         ticker_request_sender.clone(),
+        event_sub_sender.clone(),
+        rate_limits.clone(),
+        api_keys.clone(),
         panic_notify.clone(),
         config,
     );
@@ -64,6 +98,7 @@ pub fn start_api_server(
         config.api.admin.bind_addr(),
         config.api.admin.secret_auth.clone(),
         connection_pool.clone(),
+        CoreApiClient::from_config(&config.api.private),
         panic_notify.clone(),
     );
 
@@ -71,6 +106,9 @@ pub fn start_api_server(
         connection_pool,
         sign_check_sender,
         ticker_request_sender,
+        event_sub_sender,
+        rate_limits,
+        api_keys,
         panic_notify,
         config,
     );
@@ -0,0 +1,390 @@
+//! Proxy in front of the JSON RPC servers.
+//!
+//! JSON RPC servers neither support TLS nor provide the peer address of the request, so they
+//! listen on the loopback interface, and the connections to their public addresses are accepted
+//! by the proxy. The proxy terminates TLS if it's enabled and passes the client address to the
+//! upstream server in the `X-Forwarded-For` header of every request, so the requests are rate
+//! limited per client.
+//!
+//! The header sent by the client is replaced, unless the API servers are configured to trust
+//! the proxies in front of them (`use_forwarded_ip`): then the header set by such a proxy is kept.
+//!
+//! Requests of the keep-alive connection are framed by their `Content-Length`. The rest of
+//! the connection is forwarded as is after the WebSocket upgrade or the request with a chunked
+//! body, so the connection is closed after such a request.
+
+// Built-in uses
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+// External uses
+use anyhow::{ensure, format_err, Context};
+use futures::{future, pin_mut};
+use openssl::ssl::SslAcceptor;
+use tokio::{
+    io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    time::{timeout_at, Instant},
+};
+
+/// Time given to the client to complete the TLS handshake and send the first request head.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximum size of the request head read by the proxy.
+const MAX_REQUEST_HEAD_SIZE: usize = 16 * 1024;
+
+/// Address of the server behind the proxy, the port is chosen by the OS.
+pub fn upstream_bind_addr() -> SocketAddr {
+    SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)
+}
+
+/// Starts the proxy accepting the connections on the `bind_addr` and forwarding them to the
+/// `upstream` server. The TLS connections are accepted if the `acceptor` is set.
+/// Must be called from the context of Tokio runtime.
+pub fn start_proxy(
+    bind_addr: SocketAddr,
+    acceptor: Option<SslAcceptor>,
+    upstream: SocketAddr,
+    trust_forwarded: bool,
+) {
+    // The address is bound right away, so the errors are reported on the server start.
+    let listener = std::net::TcpListener::bind(bind_addr)
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .expect("Unable to bind the proxy listener");
+
+    tokio::spawn(async move {
+        let mut listener =
+            TcpListener::from_std(listener).expect("Unable to register the proxy listener");
+        loop {
+            let (stream, peer_addr) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(err) => {
+                    vlog::warn!("Unable to accept the connection on {}: {}", bind_addr, err);
+                    continue;
+                }
+            };
+
+            let acceptor = acceptor.clone();
+            let client = Client {
+                ip: peer_addr.ip(),
+                trust_forwarded,
+            };
+            tokio::spawn(async move {
+                if let Err(err) = accept_connection(acceptor, stream, client, upstream).await {
+                    vlog::debug!("Connection from {} failed: {}", peer_addr, err);
+                }
+            });
+        }
+    });
+}
+
+/// Peer of the proxied connection.
+#[derive(Debug, Clone, Copy)]
+struct Client {
+    ip: IpAddr,
+    trust_forwarded: bool,
+}
+
+/// Framing of the request body.
+#[derive(Debug, PartialEq)]
+enum RequestBody {
+    /// Body of the known length, the next request follows it.
+    Length(usize),
+    /// The rest of the connection is forwarded as is.
+    Raw,
+}
+
+async fn accept_connection(
+    acceptor: Option<SslAcceptor>,
+    stream: TcpStream,
+    client: Client,
+    upstream: SocketAddr,
+) -> anyhow::Result<()> {
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+    match acceptor {
+        Some(acceptor) => {
+            let stream = timeout_at(deadline, tokio_openssl::accept(&acceptor, stream))
+                .await
+                .map_err(|_| format_err!("TLS handshake timed out"))?
+                .map_err(|err| format_err!("TLS handshake failed: {}", err))?;
+            forward_connection(stream, client, upstream, deadline).await
+        }
+        None => forward_connection(stream, client, upstream, deadline).await,
+    }
+}
+
+async fn forward_connection(
+    mut stream: impl AsyncRead + AsyncWrite + Unpin,
+    client: Client,
+    upstream: SocketAddr,
+    deadline: Instant,
+) -> anyhow::Result<()> {
+    let first_request = timeout_at(deadline, read_request_head(&mut stream, Vec::new()))
+        .await
+        .map_err(|_| format_err!("Request head timed out"))??;
+    let first_request = match first_request {
+        Some(request) => request,
+        None => return Ok(()),
+    };
+
+    let upstream = TcpStream::connect(upstream).await?;
+    let (client_reader, client_writer) = io::split(stream);
+    let (upstream_reader, upstream_writer) = io::split(upstream);
+
+    let requests = forward_requests(client_reader, upstream_writer, client, first_request);
+    let responses = pipe(upstream_reader, client_writer);
+    pin_mut!(requests, responses);
+    match future::select(requests, responses).await {
+        // The client has sent all the requests, the rest of the responses are still forwarded.
+        future::Either::Left((result, responses)) => {
+            result?;
+            responses.await?;
+        }
+        // The upstream server has closed the connection, nothing can be forwarded to it anymore.
+        future::Either::Right((result, _)) => result?,
+    }
+    Ok(())
+}
+
+/// Forwards the requests of the client rewriting their heads, until the client closes
+/// the connection.
+async fn forward_requests(
+    mut reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    client: Client,
+    first_request: (String, Vec<u8>),
+) -> anyhow::Result<()> {
+    let (mut head, mut buffer) = first_request;
+    loop {
+        let (head_rewritten, body) = rewrite_request_head(&head, client);
+        writer.write_all(head_rewritten.as_bytes()).await?;
+
+        let mut body_len = match body {
+            RequestBody::Length(len) => len,
+            RequestBody::Raw => {
+                writer.write_all(&buffer).await?;
+                return pipe(reader, writer).await.map_err(From::from);
+            }
+        };
+        if buffer.len() >= body_len {
+            let rest = buffer.split_off(body_len);
+            writer.write_all(&buffer).await?;
+            buffer = rest;
+        } else {
+            writer.write_all(&buffer).await?;
+            body_len -= buffer.len();
+            buffer.clear();
+            let copied = io::copy(&mut (&mut reader).take(body_len as u64), &mut writer).await?;
+            ensure!(
+                copied == body_len as u64,
+                "Connection is closed before the request body"
+            );
+        }
+
+        match read_request_head(&mut reader, buffer).await? {
+            Some((next_head, rest)) => {
+                head = next_head;
+                buffer = rest;
+            }
+            None => {
+                writer.shutdown().await?;
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Reads the head of the next request, `buffer` is the data received after the previous request.
+/// Returns the head along with the data received after it, or `None` if the connection is closed
+/// before the next request.
+async fn read_request_head(
+    stream: &mut (impl AsyncRead + Unpin),
+    mut buffer: Vec<u8>,
+) -> anyhow::Result<Option<(String, Vec<u8>)>> {
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            let rest = buffer.split_off(end + 4);
+            let head = String::from_utf8(buffer).context("Request head is not valid UTF-8")?;
+            return Ok(Some((head, rest)));
+        }
+        ensure!(
+            buffer.len() <= MAX_REQUEST_HEAD_SIZE,
+            "Request head is too large"
+        );
+
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            ensure!(
+                buffer.is_empty(),
+                "Connection is closed before the request head"
+            );
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Sets the `X-Forwarded-For` header of the request to the client address, unless the header
+/// set by the trusted proxy is kept. Returns the rewritten head along with the framing
+/// of the request body.
+fn rewrite_request_head(head: &str, client: Client) -> (String, RequestBody) {
+    let mut lines = head.trim_end().split("\r\n");
+    let mut rewritten = format!("{}\r\n", lines.next().unwrap_or_default());
+    let mut headers = Vec::new();
+    let mut content_length = Some(0);
+    let (mut chunked, mut upgrade, mut forwarded) = (false, false, false);
+    for line in lines {
+        let (name, value) = match line.find(':') {
+            Some(idx) => (line[..idx].trim(), line[idx + 1..].trim()),
+            None => (line, ""),
+        };
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "x-forwarded-for" | "forwarded" if !client.trust_forwarded => continue,
+            "x-forwarded-for" => forwarded = true,
+            "content-length" => content_length = value.parse().ok(),
+            "transfer-encoding" => chunked = true,
+            "connection" => upgrade = value.to_ascii_lowercase().contains("upgrade"),
+            _ => {}
+        }
+        headers.push((name, line));
+    }
+
+    // The connection is closed after the request with the body of unknown length, since the next
+    // requests can't be told apart from its body.
+    let close = (chunked || content_length.is_none()) && !upgrade;
+    for (name, line) in headers {
+        if name == "connection" && close {
+            continue;
+        }
+        rewritten.push_str(line);
+        rewritten.push_str("\r\n");
+    }
+    if close {
+        rewritten.push_str("Connection: close\r\n");
+    }
+    if !forwarded {
+        rewritten.push_str(&format!("X-Forwarded-For: {}\r\n", client.ip));
+    }
+    rewritten.push_str("\r\n");
+
+    let body = match content_length {
+        Some(len) if !chunked && !upgrade => RequestBody::Length(len),
+        _ => RequestBody::Raw,
+    };
+    (rewritten, body)
+}
+
+/// Copies the data until the reader is closed, then closes the writer.
+async fn pipe(
+    mut reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+) -> io::Result<()> {
+    io::copy(&mut reader, &mut writer).await?;
+    writer.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(trust_forwarded: bool) -> Client {
+        Client {
+            ip: "1.2.3.4".parse().unwrap(),
+            trust_forwarded,
+        }
+    }
+
+    #[test]
+    fn request_head_is_rewritten() {
+        let head = "POST / HTTP/1.1\r\nHost: api\r\nX-Forwarded-For: 5.6.7.8\r\n\
+                    Connection: keep-alive\r\nContent-Length: 2\r\n\r\n";
+        assert_eq!(
+            rewrite_request_head(head, client(false)),
+            (
+                "POST / HTTP/1.1\r\nHost: api\r\nConnection: keep-alive\r\nContent-Length: 2\r\n\
+                 X-Forwarded-For: 1.2.3.4\r\n\r\n"
+                    .to_owned(),
+                RequestBody::Length(2)
+            )
+        );
+
+        // The header set by the trusted proxy is kept.
+        let (rewritten, _) = rewrite_request_head(head, client(true));
+        assert_eq!(rewritten, head);
+        let head = "GET / HTTP/1.1\r\nHost: api\r\n\r\n";
+        assert_eq!(
+            rewrite_request_head(head, client(true)),
+            (
+                "GET / HTTP/1.1\r\nHost: api\r\nX-Forwarded-For: 1.2.3.4\r\n\r\n".to_owned(),
+                RequestBody::Length(0)
+            )
+        );
+
+        // Upgraded connections are kept open, the chunked requests close the connection.
+        let head = "GET / HTTP/1.1\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n";
+        assert_eq!(
+            rewrite_request_head(head, client(false)),
+            (
+                "GET / HTTP/1.1\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+                 X-Forwarded-For: 1.2.3.4\r\n\r\n"
+                    .to_owned(),
+                RequestBody::Raw
+            )
+        );
+        let head =
+            "POST / HTTP/1.1\r\nConnection: keep-alive\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert_eq!(
+            rewrite_request_head(head, client(false)),
+            (
+                "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\
+                 X-Forwarded-For: 1.2.3.4\r\n\r\n"
+                    .to_owned(),
+                RequestBody::Raw
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn request_head_is_read() {
+        let data = b"GET / HTTP/1.1\r\nHost: api\r\n\r\n{}".to_vec();
+        let (head, rest) = read_request_head(&mut data.as_slice(), Vec::new())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(head, "GET / HTTP/1.1\r\nHost: api\r\n\r\n");
+        assert_eq!(rest, b"{}");
+
+        let data = b"GET / HTTP/1.1\r\n".to_vec();
+        assert!(read_request_head(&mut data.as_slice(), Vec::new())
+            .await
+            .is_err());
+        let data = Vec::new();
+        assert!(read_request_head(&mut data.as_slice(), Vec::new())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn keep_alive_requests_are_rewritten() {
+        let data = b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}\
+                     GET / HTTP/1.1\r\nX-Forwarded-For: 5.6.7.8\r\n\r\n"
+            .to_vec();
+        let mut forwarded = Vec::new();
+        let first_request = read_request_head(&mut data.as_slice(), Vec::new())
+            .await
+            .unwrap()
+            .unwrap();
+        forward_requests(&data[0..0], &mut forwarded, client(false), first_request)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(forwarded).unwrap(),
+            "POST / HTTP/1.1\r\nContent-Length: 2\r\nX-Forwarded-For: 1.2.3.4\r\n\r\n{}\
+             GET / HTTP/1.1\r\nX-Forwarded-For: 1.2.3.4\r\n\r\n"
+        );
+    }
+}
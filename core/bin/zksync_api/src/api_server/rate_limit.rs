@@ -0,0 +1,354 @@
+//! Rate limiting of the public API servers.
+//!
+//! Requests to the REST and HTTP JSON RPC APIs and the WebSocket handshakes are limited per client
+//! IP address and rejected with `429 Too Many Requests`. Messages of the WebSocket sessions are
+//! limited per session with their own rate and rejected with the JSON RPC error. Transactions
+//! submitted through any of the APIs are limited per account address by the `TxSender`.
+
+// Built-in uses
+use std::{net::IpAddr, sync::Arc, time::Duration};
+
+// External uses
+use actix_web::{dev::ServiceRequest, http::StatusCode};
+use jsonrpc_core::{
+    futures::future::{self as future01, Either},
+    Call, Failure, Id, Middleware, Output, Request, Response as JsonRpcResponse, Version,
+};
+use jsonrpc_http_server::{hyper, RequestMiddleware, RequestMiddlewareAction};
+use jsonrpc_pubsub::Session;
+use jsonrpc_ws_server::{ws, MiddlewareAction};
+
+// Workspace uses
+use zksync_config::configs::api::RateLimit;
+use zksync_types::Address;
+
+// Local uses
+use super::{rest::v1::Error as ApiError, rpc_server::error::rate_limit_exceeded};
+use crate::utils::rate_limiter::RateLimiter;
+
+/// Header with the addresses of the client and the proxies the request went through.
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Returns the client IP address added to the `X-Forwarded-For` header by the proxy.
+///
+/// The client can send the header with arbitrary addresses, so only the last one,
+/// appended by the proxy, can be trusted.
+fn forwarded_ip(header: &str) -> Option<IpAddr> {
+    header.rsplit(',').next()?.trim().parse().ok()
+}
+
+/// Rate limiters shared by all the API servers.
+#[derive(Debug, Clone)]
+pub struct RateLimits {
+    /// Limiter of the requests from the same IP address.
+    ip: Option<RateLimiter<IpAddr>>,
+    /// Limiter of the messages of the same WebSocket session.
+    ws_session: Option<RateLimiter<usize>>,
+    /// Limiter of the transactions submitted by the same account.
+    pub address: Option<RateLimiter<Address>>,
+    use_forwarded_ip: bool,
+}
+
+impl RateLimits {
+    pub fn from_config(config: &RateLimit) -> Self {
+        Self {
+            ip: RateLimiter::new(config.per_ip_rps, config.per_ip_burst),
+            ws_session: RateLimiter::new(config.per_ws_session_rps, config.per_ws_session_burst),
+            address: RateLimiter::new(config.per_address_rps, config.per_address_burst),
+            use_forwarded_ip: config.use_forwarded_ip,
+        }
+    }
+
+    /// Returns the limits of the server behind the JSON RPC proxy, which passes the client address
+    /// in the `X-Forwarded-For` header (see the `proxy` module).
+    pub fn behind_proxy(mut self) -> Self {
+        self.use_forwarded_ip = true;
+        self
+    }
+
+    /// Checks the rate of the requests from the client IP address. Returns the time after which
+    /// the request can be repeated if the limit is exceeded.
+    ///
+    /// If the forwarded address is trusted but the header is missing or malformed, the requests
+    /// are limited by the peer address. The requests with no known address at all (the JSON RPC
+    /// servers don't expose the peer address) are not limited.
+    fn check_ip(
+        &self,
+        peer_ip: Option<IpAddr>,
+        forwarded_for: Option<&str>,
+    ) -> Result<(), Duration> {
+        let limiter = match &self.ip {
+            Some(limiter) => limiter,
+            None => return Ok(()),
+        };
+
+        let ip = if self.use_forwarded_ip {
+            forwarded_for.and_then(forwarded_ip).or(peer_ip)
+        } else {
+            peer_ip
+        };
+        match ip {
+            Some(ip) => limiter.check(ip),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the rate of the requests to the REST API from the client IP address.
+    pub fn check_rest_request(&self, req: &ServiceRequest) -> Result<(), ApiError> {
+        let forwarded_for = req
+            .headers()
+            .get(X_FORWARDED_FOR)
+            .and_then(|value| value.to_str().ok());
+        let peer_ip = req.peer_addr().map(|addr| addr.ip());
+
+        self.check_ip(peer_ip, forwarded_for)
+            .map_err(|retry_after| {
+                metrics::counter!("api.rate_limit.rejected_requests", 1, "server" => "rest");
+                ApiError::too_many_requests("Too many requests").retry_after(Some(retry_after))
+            })
+    }
+
+    /// Returns the limiter of the messages of the WebSocket sessions.
+    pub fn ws_session_limiter(&self) -> WsSessionLimiter {
+        WsSessionLimiter(self.ws_session.clone())
+    }
+}
+
+/// The HTTP JSON RPC server doesn't provide the peer address of the request, so it must be
+/// behind the proxy passing the address in the `X-Forwarded-For` header.
+impl RequestMiddleware for RateLimits {
+    fn on_request(&self, request: hyper::Request<hyper::Body>) -> RequestMiddlewareAction {
+        let forwarded_for = request
+            .headers()
+            .get(X_FORWARDED_FOR)
+            .and_then(|value| value.to_str().ok());
+
+        let retry_after = match self.check_ip(None, forwarded_for) {
+            Ok(()) => return request.into(),
+            Err(retry_after) => retry_after,
+        };
+        metrics::counter!("api.rate_limit.rejected_requests", 1, "server" => "json_rpc");
+
+        // The request id is unknown, so `null` is used as the JSON RPC spec requires.
+        let body = JsonRpcResponse::Single(Output::Failure(Failure {
+            jsonrpc: Some(Version::V2),
+            error: rate_limit_exceeded(retry_after),
+            id: Id::Null,
+        }));
+        let response = hyper::Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS.as_u16())
+            .header("content-type", "application/json")
+            .header("retry-after", retry_after.as_secs().to_string())
+            .body(hyper::Body::from(
+                serde_json::to_vec(&body).expect("Unable to serialize the JSON RPC response"),
+            ))
+            .expect("Unable to build the HTTP response");
+
+        RequestMiddlewareAction::Respond {
+            should_validate_hosts: false,
+            response: Box::new(future01::ok(response)),
+        }
+    }
+}
+
+/// WebSocket handshakes are limited per client IP address, which is passed by the proxy
+/// in the `X-Forwarded-For` header, as the HTTP JSON RPC requests are.
+impl jsonrpc_ws_server::RequestMiddleware for RateLimits {
+    fn process(&self, request: &ws::Request) -> MiddlewareAction {
+        let forwarded_for = request
+            .header(X_FORWARDED_FOR)
+            .and_then(|value| std::str::from_utf8(value).ok());
+
+        let retry_after = match self.check_ip(None, forwarded_for) {
+            Ok(()) => return MiddlewareAction::Proceed,
+            Err(retry_after) => retry_after,
+        };
+        metrics::counter!("api.rate_limit.rejected_requests", 1, "server" => "ws");
+
+        let mut response = ws::Response::new(
+            StatusCode::TOO_MANY_REQUESTS.as_u16(),
+            "Too Many Requests",
+            Vec::new(),
+        );
+        response.headers_mut().push((
+            "Retry-After".to_owned(),
+            retry_after.as_secs().to_string().into_bytes(),
+        ));
+        MiddlewareAction::Respond {
+            response,
+            validate_origin: false,
+            validate_hosts: false,
+        }
+    }
+}
+
+/// Limiter of the messages of the WebSocket sessions. The session is identified by the address
+/// of its metadata, which lives as long as the session.
+#[derive(Debug, Clone)]
+pub struct WsSessionLimiter(Option<RateLimiter<usize>>);
+
+impl WsSessionLimiter {
+    fn check(&self, session: &Arc<Session>) -> Result<(), Duration> {
+        match &self.0 {
+            Some(limiter) => limiter.check(Arc::as_ptr(session) as usize),
+            None => Ok(()),
+        }
+    }
+
+    fn reject_call(call: &Call, retry_after: Duration) -> Option<Output> {
+        match call {
+            Call::MethodCall(call) => Some(Output::Failure(Failure {
+                jsonrpc: call.jsonrpc,
+                error: rate_limit_exceeded(retry_after),
+                id: call.id.clone(),
+            })),
+            // Notifications are dropped without a response.
+            Call::Notification(_) => None,
+            // The request id is unknown, so `null` is used as the JSON RPC spec requires.
+            Call::Invalid { id } => Some(Output::Failure(Failure {
+                jsonrpc: Some(Version::V2),
+                error: rate_limit_exceeded(retry_after),
+                id: id.clone(),
+            })),
+        }
+    }
+}
+
+impl Middleware<Arc<Session>> for WsSessionLimiter {
+    type Future = future01::FutureResult<Option<JsonRpcResponse>, ()>;
+    type CallFuture = future01::FutureResult<Option<Output>, ()>;
+
+    fn on_request<F, X>(
+        &self,
+        request: Request,
+        meta: Arc<Session>,
+        next: F,
+    ) -> Either<Self::Future, X>
+    where
+        F: Fn(Request, Arc<Session>) -> X + Send + Sync,
+        X: future01::Future<Item = Option<JsonRpcResponse>, Error = ()> + Send + 'static,
+    {
+        let retry_after = match self.check(&meta) {
+            Ok(()) => return Either::B(next(request, meta)),
+            Err(retry_after) => retry_after,
+        };
+        metrics::counter!("api.rate_limit.rejected_requests", 1, "server" => "ws_session");
+
+        // The whole batch is counted as a single message.
+        let response = match request {
+            Request::Single(call) => {
+                Self::reject_call(&call, retry_after).map(JsonRpcResponse::Single)
+            }
+            Request::Batch(calls) => {
+                let outputs: Vec<_> = calls
+                    .iter()
+                    .filter_map(|call| Self::reject_call(call, retry_after))
+                    .collect();
+                Some(outputs)
+                    .filter(|outputs| !outputs.is_empty())
+                    .map(JsonRpcResponse::Batch)
+            }
+        };
+        Either::A(future01::ok(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_core::{futures::sync::mpsc, MetaIoHandler, Value};
+
+    use super::*;
+
+    fn rate_limits(use_forwarded_ip: bool) -> RateLimits {
+        RateLimits::from_config(&RateLimit {
+            per_ip_rps: 1,
+            per_ip_burst: 1,
+            per_ws_session_rps: 1,
+            per_ws_session_burst: 1,
+            per_address_rps: 0,
+            per_address_burst: 0,
+            use_forwarded_ip,
+        })
+    }
+
+    #[test]
+    fn forwarded_ip_is_last_address() {
+        assert_eq!(
+            forwarded_ip("1.1.1.1, 2.2.2.2"),
+            Some("2.2.2.2".parse().unwrap())
+        );
+        assert_eq!(forwarded_ip("::1"), Some("::1".parse().unwrap()));
+        assert_eq!(forwarded_ip("1.1.1.1, unknown"), None);
+        assert_eq!(forwarded_ip(""), None);
+    }
+
+    #[test]
+    fn client_ip_selection() {
+        let peer_ip = Some("1.1.1.1".parse().unwrap());
+
+        // Forwarded addresses are ignored unless the proxy is trusted.
+        let limits = rate_limits(false);
+        assert!(limits.check_ip(peer_ip, Some("2.2.2.2")).is_ok());
+        assert!(limits.check_ip(peer_ip, Some("3.3.3.3")).is_err());
+
+        let limits = rate_limits(true);
+        assert!(limits.check_ip(peer_ip, Some("2.2.2.2")).is_ok());
+        assert!(limits.check_ip(peer_ip, Some("3.3.3.3")).is_ok());
+        assert!(limits.check_ip(peer_ip, Some("3.3.3.3")).is_err());
+        // The peer address is used if the forwarded one is missing or malformed.
+        assert!(limits.check_ip(peer_ip, None).is_ok());
+        assert!(limits.check_ip(peer_ip, Some("unknown")).is_err());
+        // Requests of unknown origin are not limited.
+        assert!(limits.check_ip(None, None).is_ok());
+        assert!(limits.check_ip(None, None).is_ok());
+    }
+
+    #[test]
+    fn ws_session_limit_is_separate() {
+        let limits = RateLimits::from_config(&RateLimit {
+            per_ip_rps: 1,
+            per_ip_burst: 1,
+            per_ws_session_rps: 0,
+            per_ws_session_burst: 0,
+            per_address_rps: 0,
+            per_address_burst: 0,
+            use_forwarded_ip: false,
+        });
+        assert!(limits.ip.is_some());
+        assert!(limits.ws_session_limiter().0.is_none());
+    }
+
+    #[test]
+    fn ws_sessions_are_limited() {
+        let mut io = MetaIoHandler::with_middleware(rate_limits(false).ws_session_limiter());
+        io.add_method("ping", |_| Ok(Value::from("pong")));
+        let session = || {
+            let (sender, _) = mpsc::channel(1);
+            Arc::new(Session::new(sender))
+        };
+        let request = r#"{"jsonrpc":"2.0","method":"ping","id":1}"#;
+
+        let first_session = session();
+        let response = io.handle_request_sync(request, first_session.clone());
+        assert_eq!(
+            response.as_deref(),
+            Some(r#"{"jsonrpc":"2.0","result":"pong","id":1}"#)
+        );
+        let response = io
+            .handle_request_sync(request, first_session.clone())
+            .unwrap();
+        assert!(response.contains("Too many requests"), "{}", response);
+
+        // Sessions are limited independently.
+        let response = io.handle_request_sync(request, session()).unwrap();
+        assert!(response.contains("pong"), "{}", response);
+    }
+
+    #[test]
+    fn proxy_is_trusted() {
+        // The address set by the JSON RPC proxy is used even if the other proxies are not trusted.
+        let limits = rate_limits(false).behind_proxy();
+        assert!(limits.check_ip(None, Some("2.2.2.2")).is_ok());
+        assert!(limits.check_ip(None, Some("2.2.2.2")).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! GraphQL API implementation.
+//!
+//! Exposes the same accounts, transactions, blocks and tokens as the REST API, but lets the client
+//! select the fields and fetch the nested entities (e.g. account together with its history and
+//! the metadata of the tokens) in a single request.
+
+// Built-in uses
+use std::{collections::HashMap, sync::Arc};
+
+// External uses
+use actix_web::{web, Scope};
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    Context, EmptyMutation, EmptySubscription, Object, Result, Schema,
+};
+use async_graphql_actix_web::{Request, Response};
+
+// Workspace uses
+use zksync_storage::{
+    chain::{
+        block::records::{BlockDetails, BlockTransactionItem},
+        operations_ext::records::TransactionsHistoryItem,
+    },
+    ConnectionPool, QueryResult,
+};
+use zksync_types::{Address, BlockNumber, TokenId, TokenLike};
+
+// Local uses
+use super::v1::{accounts::AccountQuery, MAX_LIMIT};
+use crate::{api_server::helpers::try_parse_hash, utils::token_db_cache::TokenDBCache};
+
+use self::types::{Account, Block, Token, Transaction};
+
+mod types;
+
+/// Maximum nesting of the query fields, so a single request can't force the server
+/// to resolve the unbounded graph of the entities.
+const MAX_QUERY_DEPTH: usize = 8;
+/// Maximum complexity of the query: every field costs 1, and the lists multiply the cost
+/// of their items by the page size.
+const MAX_QUERY_COMPLEXITY: usize = 5000;
+/// Estimated amount of the transactions in a block used to calculate the query complexity.
+const BLOCK_TRANSACTIONS_COMPLEXITY: usize = 50;
+
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+fn check_limit(limit: u32) -> Result<()> {
+    if limit > MAX_LIMIT {
+        return Err(format!("Limit should not exceed {}", MAX_LIMIT).into());
+    }
+    Ok(())
+}
+
+/// Shared data between the GraphQL resolvers.
+#[derive(Clone)]
+struct GraphqlData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl GraphqlData {
+    async fn token(&self, token_like: TokenLike) -> QueryResult<Option<zksync_types::Token>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        self.tokens.get_token(&mut storage, token_like).await
+    }
+
+    async fn block(&self, block_number: BlockNumber) -> QueryResult<Option<BlockDetails>> {
+        let mut storage = self.pool.access_storage().await?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .load_block_range(block_number, 1)
+            .await?
+            .into_iter()
+            .next();
+
+        // Check if this is exactly the requested block.
+        Ok(block.filter(|block| block.block_number == *block_number as i64))
+    }
+
+    async fn account_transactions(
+        &self,
+        address: Address,
+        offset: u64,
+        limit: u32,
+    ) -> QueryResult<Vec<TransactionsHistoryItem>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history(&address, offset, limit as u64)
+            .await
+    }
+}
+
+/// Loads the transactions of all the blocks requested by the query at once.
+struct BlockTransactionsLoader {
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Loader<BlockNumber> for BlockTransactionsLoader {
+    type Value = Vec<BlockTransactionItem>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[BlockNumber],
+    ) -> Result<HashMap<BlockNumber, Self::Value>, Self::Error> {
+        let mut storage = self.pool.access_storage().await?;
+        let items = storage
+            .chain()
+            .block_schema()
+            .get_blocks_transactions(keys)
+            .await?;
+
+        let mut transactions: HashMap<_, Vec<_>> = HashMap::new();
+        for item in items {
+            transactions
+                .entry(BlockNumber(item.block_number as u32))
+                .or_default()
+                .push(item);
+        }
+        Ok(transactions)
+    }
+}
+
+/// Loads the tokens of all the balances requested by the query using a single connection.
+struct TokensLoader {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+#[async_trait::async_trait]
+impl Loader<TokenId> for TokensLoader {
+    type Value = zksync_types::Token;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, keys: &[TokenId]) -> Result<HashMap<TokenId, Self::Value>, Self::Error> {
+        let mut storage = self.pool.access_storage().await?;
+        let mut tokens = HashMap::new();
+        for &token_id in keys {
+            if let Some(token) = self.tokens.get_token(&mut storage, token_id).await? {
+                tokens.insert(token_id, token);
+            }
+        }
+        Ok(tokens)
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Account with the given ID or address, absent if the account is not committed yet.
+    async fn account(&self, ctx: &Context<'_>, query: String) -> Result<Option<Account>> {
+        let query: AccountQuery = query.parse()?;
+
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let mut storage = data.pool.access_storage().await?;
+        let account_id = match query {
+            AccountQuery::Id(id) => Some(id),
+            AccountQuery::Address(address) => {
+                storage
+                    .chain()
+                    .account_schema()
+                    .account_id_by_address(address)
+                    .await?
+            }
+        };
+        let account_id = match account_id {
+            Some(account_id) => account_id,
+            None => return Ok(None),
+        };
+
+        let state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_id(account_id)
+            .await?;
+        Ok(state.committed.map(|(id, committed)| {
+            Account::new(id, committed, state.verified.map(|(_id, account)| account))
+        }))
+    }
+
+    /// Executed transaction or priority operation with the given hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Transaction>> {
+        let tx_hash = try_parse_hash(&hash)?;
+
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let mut storage = data.pool.access_storage().await?;
+        let tx = storage
+            .chain()
+            .operations_ext_schema()
+            .get_tx_by_hash(tx_hash.as_bytes())
+            .await?;
+        Ok(tx.map(|tx| Transaction::from_tx_by_hash(hash, tx)))
+    }
+
+    async fn block(&self, ctx: &Context<'_>, number: u32) -> Result<Option<Block>> {
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let block = data.block(BlockNumber(number)).await?;
+        Ok(block.map(Block))
+    }
+
+    /// Committed blocks in descending order, starting from the given block or the last one.
+    #[graphql(complexity = "limit as usize * child_complexity")]
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        before: Option<u32>,
+        #[graphql(default = 20)] limit: u32,
+    ) -> Result<Vec<Block>> {
+        check_limit(limit)?;
+
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let max_block = BlockNumber(before.unwrap_or(u32::MAX));
+        let mut storage = data.pool.access_storage().await?;
+        let blocks = storage
+            .chain()
+            .block_schema()
+            .load_block_range(max_block, limit)
+            .await?;
+        Ok(blocks.into_iter().map(Block).collect())
+    }
+
+    /// Token with the given ID, address or symbol.
+    async fn token(&self, ctx: &Context<'_>, token: String) -> Result<Option<Token>> {
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let token = data.token(TokenLike::parse(&token)).await?;
+        Ok(token.map(Token::from))
+    }
+
+    async fn tokens(&self, ctx: &Context<'_>) -> Result<Vec<Token>> {
+        let data = ctx.data_unchecked::<GraphqlData>();
+        let mut storage = data.pool.access_storage().await?;
+        let tokens = storage.tokens_schema().load_tokens().await?;
+
+        // Provide tokens in a predictable order.
+        let mut tokens: Vec<_> = tokens.into_iter().map(|(_k, v)| v).collect();
+        tokens.sort_unstable_by_key(|token| token.id);
+
+        Ok(tokens.into_iter().map(Token::from).collect())
+    }
+}
+
+pub fn schema(pool: ConnectionPool, tokens: TokenDBCache) -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(BlockTransactionsLoader {
+            pool: pool.clone(),
+        }))
+        .data(DataLoader::new(TokensLoader {
+            pool: pool.clone(),
+            tokens: tokens.clone(),
+        }))
+        .data(GraphqlData { pool, tokens })
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+async fn graphql(schema: web::Data<ApiSchema>, request: Request) -> Response {
+    schema.execute(request.into_inner()).await.into()
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    web::scope("/api/graphql")
+        .data(schema(pool, tokens))
+        .route("", web::post().to(graphql))
+        .route("", web::get().to(graphql))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        super::v1::test_utils::{TestServerConfig, COMMITTED_BLOCKS_COUNT},
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_graphql_queries() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let schema = schema(cfg.pool.clone(), TokenDBCache::new());
+
+        // Nested entities are resolved in a single request.
+        let response = schema
+            .execute("{ blocks(limit: 2) { number transactions { hash blockNumber } } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let blocks = serde_json::to_value(&response.data)?["blocks"].clone();
+        let blocks = blocks.as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["number"], json!(*COMMITTED_BLOCKS_COUNT));
+        for block in blocks {
+            for tx in block["transactions"].as_array().unwrap() {
+                assert_eq!(tx["blockNumber"], block["number"]);
+            }
+        }
+
+        let response = schema.execute("{ tokens { id symbol } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            serde_json::to_value(&response.data)?["tokens"][0],
+            json!({ "id": 0, "symbol": "ETH" })
+        );
+
+        // Missing entities are returned as `null`.
+        let response = schema.execute("{ block(number: 100000) { number } }").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            serde_json::to_value(&response.data)?,
+            json!({ "block": null })
+        );
+
+        // Too large pages are rejected.
+        let response = schema
+            .execute(format!(
+                "{{ blocks(limit: {}) {{ number }} }}",
+                MAX_LIMIT + 1
+            ))
+            .await;
+        assert!(!response.errors.is_empty());
+
+        // Too complex queries are rejected.
+        let response = schema
+            .execute(format!(
+                "{{ blocks(limit: {}) {{ transactions {{ hash txType op createdAt }} }} }}",
+                MAX_LIMIT
+            ))
+            .await;
+        assert!(!response.errors.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,305 @@
+//! Entities of the GraphQL API.
+//!
+//! Nested entities (e.g. the transactions of the block or the token of the balance) are resolved
+//! lazily, so the database is queried only for the fields selected by the client. Entities nested
+//! in the lists are loaded in batches, and the entities don't refer back to their parents, so the
+//! query can't fan out over the same relation repeatedly.
+
+// Built-in uses
+
+// External uses
+use async_graphql::{dataloader::DataLoader, Context, Json, Object, Result, SimpleObject};
+use num::BigUint;
+use serde_json::Value;
+
+// Workspace uses
+use zksync_storage::chain::{
+    block::records::{BlockDetails, BlockTransactionItem},
+    operations_ext::records::{TransactionsHistoryItem, TxByHashResponse},
+};
+use zksync_types::{Account as ZkSyncAccount, AccountId, Address, BlockNumber, TokenId};
+
+// Local uses
+use super::{
+    check_limit, BlockTransactionsLoader, GraphqlData, TokensLoader, BLOCK_TRANSACTIONS_COMPLEXITY,
+};
+
+fn data<'a>(ctx: &Context<'a>) -> &'a GraphqlData {
+    ctx.data_unchecked::<GraphqlData>()
+}
+
+/// Token registered in the zkSync network.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Token {
+    pub id: u16,
+    /// Address of the ERC20 contract, zero address for ETH.
+    pub address: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl From<zksync_types::Token> for Token {
+    fn from(token: zksync_types::Token) -> Self {
+        Self {
+            id: *token.id,
+            address: format!("{:?}", token.address),
+            symbol: token.symbol,
+            decimals: token.decimals,
+        }
+    }
+}
+
+/// Non-zero balance of the account.
+#[derive(Debug, Clone)]
+pub struct Balance {
+    token_id: TokenId,
+    amount: BigUint,
+}
+
+#[Object]
+impl Balance {
+    async fn token(&self, ctx: &Context<'_>) -> Result<Option<Token>> {
+        let token = ctx
+            .data_unchecked::<DataLoader<TokensLoader>>()
+            .load_one(self.token_id)
+            .await?;
+        Ok(token.map(Token::from))
+    }
+
+    /// Amount in the minimal units of the token.
+    async fn amount(&self) -> String {
+        self.amount.to_string()
+    }
+}
+
+/// State of the account in a certain block.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AccountState {
+    pub nonce: u32,
+    pub pub_key_hash: String,
+    pub balances: Vec<Balance>,
+}
+
+impl From<&ZkSyncAccount> for AccountState {
+    fn from(account: &ZkSyncAccount) -> Self {
+        let mut balances: Vec<_> = account
+            .get_nonzero_balances()
+            .into_iter()
+            .map(|(token_id, amount)| Balance {
+                token_id,
+                amount: amount.0,
+            })
+            .collect();
+        // Provide balances in a predictable order.
+        balances.sort_unstable_by_key(|balance| balance.token_id);
+
+        Self {
+            nonce: *account.nonce,
+            pub_key_hash: account.pub_key_hash.to_hex(),
+            balances,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    id: AccountId,
+    address: Address,
+    committed: ZkSyncAccount,
+    verified: Option<ZkSyncAccount>,
+}
+
+impl Account {
+    pub fn new(id: AccountId, committed: ZkSyncAccount, verified: Option<ZkSyncAccount>) -> Self {
+        Self {
+            id,
+            address: committed.address,
+            committed,
+            verified,
+        }
+    }
+}
+
+#[Object]
+impl Account {
+    async fn id(&self) -> u32 {
+        *self.id
+    }
+
+    async fn address(&self) -> String {
+        format!("{:?}", self.address)
+    }
+
+    /// State of the account in the last committed block.
+    async fn committed(&self) -> AccountState {
+        AccountState::from(&self.committed)
+    }
+
+    /// State of the account in the last verified block, absent if the account is not verified yet.
+    async fn verified(&self) -> Option<AccountState> {
+        self.verified.as_ref().map(AccountState::from)
+    }
+
+    /// Executed transactions and priority operations of the account, the newest go first.
+    #[graphql(complexity = "limit as usize * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] offset: u64,
+        #[graphql(default = 20)] limit: u32,
+    ) -> Result<Vec<Transaction>> {
+        check_limit(limit)?;
+
+        let items = data(ctx)
+            .account_transactions(self.address, offset, limit)
+            .await?;
+        Ok(items.into_iter().map(Transaction::from).collect())
+    }
+}
+
+/// Executed transaction or priority operation.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    hash: Option<String>,
+    block_number: Option<BlockNumber>,
+    op: Value,
+    success: Option<bool>,
+    fail_reason: Option<String>,
+    created_at: String,
+}
+
+impl Transaction {
+    pub fn from_tx_by_hash(hash: String, tx: TxByHashResponse) -> Self {
+        Self {
+            hash: Some(hash),
+            block_number: Some(BlockNumber(tx.block_number as u32)),
+            op: tx.tx,
+            success: Some(tx.fail_reason.is_none()),
+            fail_reason: tx.fail_reason,
+            created_at: tx.created_at,
+        }
+    }
+}
+
+impl From<TransactionsHistoryItem> for Transaction {
+    fn from(item: TransactionsHistoryItem) -> Self {
+        // Identifier of the history entry has the `<block_number>,<block_index>` format.
+        let block_number = item
+            .tx_id
+            .split(',')
+            .next()
+            .and_then(|block_number| block_number.parse().ok())
+            .map(BlockNumber);
+
+        Self {
+            hash: item.hash,
+            block_number,
+            op: item.tx,
+            success: item.success,
+            fail_reason: item.fail_reason,
+            created_at: item.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<BlockTransactionItem> for Transaction {
+    fn from(item: BlockTransactionItem) -> Self {
+        Self {
+            hash: Some(item.tx_hash),
+            block_number: Some(BlockNumber(item.block_number as u32)),
+            op: item.op,
+            success: item.success,
+            fail_reason: item.fail_reason,
+            created_at: item.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// Hash of the transaction, or the Ethereum transaction hash for the priority operations.
+    async fn hash(&self) -> Option<String> {
+        self.hash.clone()
+    }
+
+    async fn tx_type(&self) -> Option<String> {
+        self.op
+            .get("type")
+            .and_then(Value::as_str)
+            .map(String::from)
+    }
+
+    async fn block_number(&self) -> Option<u32> {
+        self.block_number.map(|block_number| *block_number)
+    }
+
+    async fn success(&self) -> Option<bool> {
+        self.success
+    }
+
+    async fn fail_reason(&self) -> Option<String> {
+        self.fail_reason.clone()
+    }
+
+    async fn created_at(&self) -> String {
+        self.created_at.clone()
+    }
+
+    /// Transaction itself in the same format as in the REST API.
+    async fn op(&self) -> Json<Value> {
+        Json(self.op.clone())
+    }
+}
+
+/// Committed block.
+#[derive(Debug, Clone)]
+pub struct Block(pub BlockDetails);
+
+#[Object]
+impl Block {
+    async fn number(&self) -> u32 {
+        self.0.block_number as u32
+    }
+
+    async fn new_state_root(&self) -> String {
+        format!("sync-bl:{}", hex::encode(&self.0.new_state_root))
+    }
+
+    async fn size(&self) -> u64 {
+        self.0.block_size as u64
+    }
+
+    async fn commit_tx_hash(&self) -> Option<String> {
+        self.0
+            .commit_tx_hash
+            .as_ref()
+            .map(|hash| format!("0x{}", hex::encode(hash)))
+    }
+
+    async fn verify_tx_hash(&self) -> Option<String> {
+        self.0
+            .verify_tx_hash
+            .as_ref()
+            .map(|hash| format!("0x{}", hex::encode(hash)))
+    }
+
+    async fn committed_at(&self) -> String {
+        self.0.committed_at.to_rfc3339()
+    }
+
+    async fn verified_at(&self) -> Option<String> {
+        self.0
+            .verified_at
+            .map(|verified_at| verified_at.to_rfc3339())
+    }
+
+    #[graphql(complexity = "BLOCK_TRANSACTIONS_COMPLEXITY * child_complexity")]
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        let items = ctx
+            .data_unchecked::<DataLoader<BlockTransactionsLoader>>()
+            .load_one(BlockNumber(self.0.block_number as u32))
+            .await?
+            .unwrap_or_default();
+        Ok(items.into_iter().map(Transaction::from).collect())
+    }
+}
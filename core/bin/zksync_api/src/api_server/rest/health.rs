@@ -0,0 +1,241 @@
+//! Health checks of the API server, used by the orchestrators (e.g. the Kubernetes probes)
+//! to decide whether the server can receive the traffic or has to be restarted.
+//!
+//! - `/livez` reports that the server is running and able to respond.
+//! - `/readyz` checks the dependencies required to serve the requests: the database,
+//!   the Core private server and the fee ticker.
+//! - `/healthz` additionally checks that the `eth_sender` isn't lagging behind the committed blocks.
+//!
+//! Every endpoint responds with the report of the performed checks, and with the
+//! `503 Service Unavailable` status if any of them has failed.
+
+// Built-in uses
+use std::{collections::BTreeMap, time::Duration};
+
+// External uses
+use actix_web::{http::StatusCode, web, HttpResponse};
+use anyhow::{ensure, format_err};
+use futures::{
+    channel::{mpsc, oneshot},
+    Future, SinkExt,
+};
+use serde::Serialize;
+use vlog::Traced;
+
+// Workspace uses
+use zksync_config::configs::api::Health;
+use zksync_storage::ConnectionPool;
+use zksync_types::{ActionType, TokenId, TokenLike};
+
+// Local uses
+use crate::{
+    api_server::tx_sender::TxSender, core_api_client::CoreApiClient, fee_ticker::TickerRequest,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthReport {
+    status: Status,
+    checks: BTreeMap<&'static str, CheckResult>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<(&'static str, CheckResult)>) -> Self {
+        let status = if checks.iter().all(|(_, check)| check.status == Status::Ok) {
+            Status::Ok
+        } else {
+            Status::Failed
+        };
+
+        Self {
+            status,
+            checks: checks.into_iter().collect(),
+        }
+    }
+
+    fn into_response(self) -> HttpResponse {
+        let status = match self.status {
+            Status::Ok => StatusCode::OK,
+            Status::Failed => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        HttpResponse::build(status).json(self)
+    }
+}
+
+/// Shared data between the health check endpoints.
+#[derive(Debug, Clone)]
+pub struct ApiHealthData {
+    pool: ConnectionPool,
+    core_api_client: CoreApiClient,
+    ticker_requests: mpsc::Sender<Traced<TickerRequest>>,
+    config: Health,
+}
+
+impl ApiHealthData {
+    pub fn new(tx_sender: &TxSender, config: &Health) -> Self {
+        Self {
+            pool: tx_sender.pool.clone(),
+            core_api_client: tx_sender.core_api_client.clone(),
+            ticker_requests: tx_sender.ticker_requests.clone(),
+            config: config.clone(),
+        }
+    }
+
+    async fn check_database(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        Ok(())
+    }
+
+    async fn check_core_api(&self) -> anyhow::Result<()> {
+        self.core_api_client.ping().await?;
+        Ok(())
+    }
+
+    async fn check_fee_ticker(&self) -> anyhow::Result<()> {
+        // Fee token check is the cheapest request handled by the ticker itself.
+        let (sender, receiver) = oneshot::channel();
+        self.ticker_requests
+            .clone()
+            .send(Traced::new(TickerRequest::IsTokenAllowed {
+                token: TokenLike::Id(TokenId(0)),
+                response: sender,
+            }))
+            .await?;
+        receiver.await??;
+        Ok(())
+    }
+
+    async fn check_eth_sender(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let committed = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        let confirmed = storage
+            .chain()
+            .operations_schema()
+            .get_last_block_by_action(ActionType::COMMIT, Some(true))
+            .await?;
+
+        let lag = committed.0.saturating_sub(confirmed.0);
+        ensure!(
+            lag <= self.config.max_eth_sender_lag_blocks,
+            "{} committed blocks are not confirmed on Ethereum",
+            lag
+        );
+        Ok(())
+    }
+}
+
+/// Runs the check, the check that didn't complete in time is considered failed.
+async fn run_check(
+    name: &'static str,
+    timeout: Duration,
+    check: impl Future<Output = anyhow::Result<()>>,
+) -> (&'static str, CheckResult) {
+    let result = tokio::time::timeout(timeout, check)
+        .await
+        .unwrap_or_else(|_| Err(format_err!("Check timed out after {:?}", timeout)));
+
+    let result = match result {
+        Ok(()) => CheckResult {
+            status: Status::Ok,
+            error: None,
+        },
+        Err(err) => {
+            vlog::warn!("Health check `{}` has failed: {}", name, err);
+            metrics::counter!("api.health.failed_checks", 1, "check" => name);
+            CheckResult {
+                status: Status::Failed,
+                error: Some(err.to_string()),
+            }
+        }
+    };
+    (name, result)
+}
+
+// Server implementation
+
+async fn livez() -> HttpResponse {
+    HealthReport::new(Vec::new()).into_response()
+}
+
+async fn readyz(data: web::Data<ApiHealthData>) -> HttpResponse {
+    let timeout = data.config.check_timeout();
+    let (database, core_api, fee_ticker) = futures::join!(
+        run_check("database", timeout, data.check_database()),
+        run_check("core_api", timeout, data.check_core_api()),
+        run_check("fee_ticker", timeout, data.check_fee_ticker()),
+    );
+
+    HealthReport::new(vec![database, core_api, fee_ticker]).into_response()
+}
+
+async fn healthz(data: web::Data<ApiHealthData>) -> HttpResponse {
+    let timeout = data.config.check_timeout();
+    let (database, core_api, fee_ticker, eth_sender) = futures::join!(
+        run_check("database", timeout, data.check_database()),
+        run_check("core_api", timeout, data.check_core_api()),
+        run_check("fee_ticker", timeout, data.check_fee_ticker()),
+        run_check("eth_sender", timeout, data.check_eth_sender()),
+    );
+
+    HealthReport::new(vec![database, core_api, fee_ticker, eth_sender]).into_response()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig, data: ApiHealthData) {
+    cfg.data(data)
+        .route("/livez", web::get().to(livez))
+        .route("/readyz", web::get().to(readyz))
+        .route("/healthz", web::get().to(healthz));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn failed_checks_are_reported() {
+        let timeout = Duration::from_millis(10);
+        let ok = run_check("ok", timeout, async { Ok(()) }).await;
+        let failed = run_check("failed", timeout, async { Err(format_err!("Unavailable")) }).await;
+        let stuck = run_check("stuck", timeout, futures::future::pending()).await;
+        assert_eq!(stuck.1.status, Status::Failed);
+
+        let report = HealthReport::new(vec![ok, failed, stuck]);
+        assert_eq!(report.status, Status::Failed);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "status": "failed",
+                "checks": {
+                    "failed": { "status": "failed", "error": "Unavailable" },
+                    "ok": { "status": "ok" },
+                    "stuck": { "status": "failed", "error": "Check timed out after 10ms" },
+                },
+            })
+        );
+
+        let report = HealthReport::new(Vec::new());
+        assert_eq!(report.status, Status::Ok);
+    }
+}
@@ -1,7 +1,11 @@
 use actix_cors::Cors;
-use actix_web::{web, App, HttpResponse, HttpServer};
-use futures::channel::mpsc;
+use actix_web::{dev::Service, middleware::Compress, web, App, HttpResponse, HttpServer};
+use futures::{
+    channel::mpsc,
+    future::{self, Either},
+};
 use std::net::SocketAddr;
+use vlog::Traced;
 use zksync_storage::ConnectionPool;
 use zksync_types::H160;
 
@@ -10,44 +14,87 @@ use zksync_utils::panic_notify::ThreadPanicNotify;
 use self::v01::api_decl::ApiV01;
 use crate::{fee_ticker::TickerRequest, signature_checker::VerifyTxSignatureRequest};
 
-use super::tx_sender::TxSender;
+use super::{
+    api_keys::ApiKeys, event_notify::EventNotifierRequest, rate_limit::RateLimits, tls,
+    tx_sender::TxSender,
+};
 use zksync_config::ZkSyncConfig;
 
+mod graphql;
+mod health;
 mod helpers;
 mod v01;
 pub mod v1;
+mod v2;
 
 async fn start_server(
     api_v01: ApiV01,
-    fee_ticker: mpsc::Sender<TickerRequest>,
-    sign_verifier: mpsc::Sender<VerifyTxSignatureRequest>,
+    fee_ticker: mpsc::Sender<Traced<TickerRequest>>,
+    sign_verifier: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+    event_notifier: mpsc::Sender<EventNotifierRequest>,
+    rate_limits: RateLimits,
+    api_keys: ApiKeys,
     bind_to: SocketAddr,
 ) {
-    HttpServer::new(move || {
+    let tls = tls::acceptor_builder(&api_v01.config.api.tls)
+        .expect("Invalid TLS configuration of the API servers");
+
+    let server = HttpServer::new(move || {
         let api_v01 = api_v01.clone();
+        let rate_limits = rate_limits.clone();
+        let api_keys = api_keys.clone();
 
-        let api_v1_scope = {
-            let tx_sender = TxSender::new(
-                api_v01.connection_pool.clone(),
-                sign_verifier.clone(),
-                fee_ticker.clone(),
-                &api_v01.config,
-            );
-            v1::api_scope(tx_sender, &api_v01.config)
-        };
+        let tx_sender = TxSender::new(
+            api_v01.connection_pool.clone(),
+            sign_verifier.clone(),
+            fee_ticker.clone(),
+            &api_v01.config,
+        )
+        .with_event_notifier(event_notifier.clone())
+        .with_rate_limits(&rate_limits);
+        let api_graphql_scope =
+            graphql::api_scope(tx_sender.pool.clone(), tx_sender.tokens.clone());
+        let api_v2_scope = v2::api_scope(tx_sender.clone(), &api_v01.config);
+        let health_data = health::ApiHealthData::new(&tx_sender, &api_v01.config.api.health);
+        let api_v1_scope = v1::api_scope(tx_sender, &api_v01.config);
 
         App::new()
+            // Integrators are authenticated after the rate limits are checked, so the clients
+            // can't guess the API keys at an unlimited rate.
+            .wrap_fn(
+                move |req, srv| match api_keys.authenticate_rest_request(&req) {
+                    Ok(()) => Either::Left(srv.call(req)),
+                    Err(err) => Either::Right(future::ok(req.error_response(err))),
+                },
+            )
+            // Rejected requests are wrapped by the CORS middleware too, so the browsers
+            // can read the responses.
+            .wrap_fn(move |req, srv| match rate_limits.check_rest_request(&req) {
+                Ok(()) => Either::Left(srv.call(req)),
+                Err(err) => Either::Right(future::ok(req.error_response(err))),
+            })
             .wrap(Cors::new().send_wildcard().max_age(3600).finish())
+            // Responses are compressed with the best encoding accepted by the client (brotli, gzip
+            // or deflate), the payloads of the account history, block transactions and tokens
+            // list endpoints are often hundreds of kilobytes.
+            .wrap(Compress::default())
             .service(api_v01.into_scope())
             .service(api_v1_scope)
+            .service(api_v2_scope)
+            .service(api_graphql_scope)
+            .configure(|cfg| health::configure(cfg, health_data))
             // Endpoint needed for js isReachable
             .route(
                 "/favicon.ico",
                 web::get().to(|| HttpResponse::Ok().finish()),
             )
     })
-    .workers(super::THREADS_PER_SERVER)
-    .bind(bind_to)
+    .workers(super::THREADS_PER_SERVER);
+
+    match tls {
+        Some(tls) => server.bind_openssl(bind_to, tls),
+        None => server.bind(bind_to),
+    }
     .unwrap()
     .shutdown_timeout(1)
     .run()
@@ -62,8 +109,11 @@ pub(super) fn start_server_thread_detached(
     listen_addr: SocketAddr,
     contract_address: H160,
     panic_notify: mpsc::Sender<bool>,
-    fee_ticker: mpsc::Sender<TickerRequest>,
-    sign_verifier: mpsc::Sender<VerifyTxSignatureRequest>,
+    fee_ticker: mpsc::Sender<Traced<TickerRequest>>,
+    sign_verifier: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+    event_notifier: mpsc::Sender<EventNotifierRequest>,
+    rate_limits: RateLimits,
+    api_keys: ApiKeys,
     config: ZkSyncConfig,
 ) {
     std::thread::Builder::new()
@@ -75,7 +125,16 @@ pub(super) fn start_server_thread_detached(
                 let api_v01 = ApiV01::new(connection_pool, contract_address, config.clone());
                 api_v01.spawn_network_status_updater(panic_notify);
 
-                start_server(api_v01, fee_ticker, sign_verifier, listen_addr).await;
+                start_server(
+                    api_v01,
+                    fee_ticker,
+                    sign_verifier,
+                    event_notifier,
+                    rate_limits,
+                    api_keys,
+                    listen_addr,
+                )
+                .await;
             });
         })
         .expect("Api server thread");
@@ -40,7 +40,7 @@ impl ApiV01 {
         contract_address: H160,
         config: ZkSyncConfig,
     ) -> Self {
-        let api_client = CoreApiClient::new(config.api.private.url.clone());
+        let api_client = CoreApiClient::from_config(&config.api.private);
         Self {
             caches: Caches::new(config.api.common.caches_size),
             connection_pool,
@@ -275,6 +275,6 @@ impl ApiV01 {
         &self,
         eth_tx_hash: H256,
     ) -> Result<Option<(EthBlockId, PriorityOp)>, anyhow::Error> {
-        self.api_client.get_unconfirmed_op(eth_tx_hash).await
+        Ok(self.api_client.get_unconfirmed_op(eth_tx_hash).await?)
     }
 }
@@ -69,8 +69,17 @@ impl ApiV01 {
             .load_tokens_by_market_volume(liquidity_volume)
             .await
             .map_err(Self::db_error)?;
+        let fee_disabled = storage
+            .tokens_schema()
+            .load_fee_disabled_tokens()
+            .await
+            .map_err(Self::db_error)?;
 
-        let mut tokens = tokens.values().cloned().collect::<Vec<_>>();
+        let mut tokens = tokens
+            .values()
+            .filter(|token| !fee_disabled.contains(&token.id))
+            .cloned()
+            .collect::<Vec<_>>();
         tokens.sort_by_key(|t| t.id);
 
         metrics::histogram!("api.v01.tokens_acceptable_for_fees", start.elapsed());
@@ -8,6 +8,8 @@ use zksync_storage::ConnectionPool;
 use zksync_types::BlockNumber;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+use crate::api_server::runtime_flags::MaintenanceInfo;
+
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
 pub struct NetworkStatus {
     pub next_block_at_max: Option<u64>,
@@ -15,6 +17,9 @@ pub struct NetworkStatus {
     pub last_verified: BlockNumber,
     pub total_transactions: u32,
     pub outstanding_txs: u32,
+    /// Scheduled maintenance, during which new transactions are not accepted.
+    #[serde(default)]
+    pub maintenance: MaintenanceInfo,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -86,12 +91,17 @@ impl SharedNetworkStatus {
                             .await
                             .unwrap_or(0);
 
+                        let maintenance = MaintenanceInfo::load(&mut transaction)
+                            .await
+                            .unwrap_or_default();
+
                         let status = NetworkStatus {
                             next_block_at_max: None,
                             last_committed,
                             last_verified,
                             total_transactions,
                             outstanding_txs,
+                            maintenance,
                         };
 
                         transaction.commit().await.unwrap_or_default();
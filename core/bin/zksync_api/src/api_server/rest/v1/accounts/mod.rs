@@ -10,27 +10,36 @@ use actix_web::{
 
 // Workspace uses
 use zksync_storage::{ConnectionPool, QueryResult, StorageProcessor};
-use zksync_types::{AccountId, Address, BlockNumber, TokenId};
+use zksync_types::{
+    AccountId, AccountUpdateJournalEntry, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+};
 
 // Local uses
-use crate::{core_api_client::CoreApiClient, utils::token_db_cache::TokenDBCache};
+use crate::{
+    core_api_client::CoreApiClient,
+    utils::{
+        ens_resolver::{is_ens_name, EnsError, EnsResolver},
+        token_db_cache::TokenDBCache,
+    },
+};
 
-use super::{ApiError, JsonResult};
+use super::{ApiError, JsonResult, MAX_LIMIT};
 use zksync_config::ZkSyncConfig;
 
 use self::types::{
     convert::{
         depositing_balances_from_pending_ops, op_receipt_from_response,
-        pending_account_op_receipt_from_priority_op, search_direction_as_storage,
-        tx_receipt_from_response, validate_receipts_query,
+        pending_account_op_receipt_from_priority_op, pending_account_tx_from_tx,
+        search_direction_as_storage, tx_receipt_from_response, validate_receipts_query,
     },
-    AccountReceiptsQuery, SearchDirection,
+    AccountReceiptsQuery, AccountUpdatesQuery, SearchDirection,
 };
 // Public uses
 pub use self::types::{
-    convert::account_state_from_storage, AccountInfo, AccountOpReceipt, AccountQuery,
-    AccountReceipts, AccountState, AccountTxReceipt, DepositingBalances, DepositingFunds,
-    PendingAccountOpReceipt, TxLocation,
+    convert::account_state_from_storage, AccountInfo, AccountNonces, AccountOpReceipt,
+    AccountQuery, AccountReceipts, AccountState, AccountTxReceipt, DepositingBalances,
+    DepositingFunds, PendingAccountOpReceipt, PendingAccountTx, SigningKeyAuthType,
+    SigningKeyStatus, TxLocation,
 };
 
 #[cfg(test)]
@@ -41,12 +50,62 @@ fn unable_to_find_token(token_id: TokenId) -> anyhow::Error {
     anyhow::anyhow!("Unable to find token with ID {}", *token_id)
 }
 
-// Additional parser because actix-web doesn't understand enums in path extractor.
-fn parse_account_query(query: String) -> Result<AccountQuery, ApiError> {
-    query.parse().map_err(|err| {
-        ApiError::bad_request("Must be specified either an account ID or an account address.")
-            .detail(format!("An error occurred: {}", err))
-    })
+fn ens_error(error: EnsError) -> ApiError {
+    match error {
+        EnsError::Resolution(err) => ApiError::internal(err),
+        err => ApiError::bad_request("Unable to resolve the ENS name.").detail(err.to_string()),
+    }
+}
+
+/// Loads the committed and verified states of the account along with its ongoing deposits,
+/// returns `None` if the account hasn't been committed yet.
+pub(crate) async fn load_account_info(
+    storage: &mut StorageProcessor<'_>,
+    tokens: &TokenDBCache,
+    core_api_client: &CoreApiClient,
+    confirmations_for_eth_event: BlockNumber,
+    account_id: AccountId,
+) -> QueryResult<Option<AccountInfo>> {
+    let account_state = storage
+        .chain()
+        .account_schema()
+        .account_state_by_id(account_id)
+        .await?;
+
+    let (account_id, account) = if let Some(state) = account_state.committed {
+        state
+    } else {
+        // This account has not been committed.
+        return Ok(None);
+    };
+
+    let committed = account_state_from_storage(storage, tokens, &account).await?;
+    let verified = match account_state.verified {
+        Some((_id, account)) => account_state_from_storage(storage, tokens, &account).await?,
+        None => AccountState::default(),
+    };
+
+    let depositing = {
+        let ongoing_ops = core_api_client
+            .get_unconfirmed_deposits(account.address)
+            .await?;
+
+        depositing_balances_from_pending_ops(
+            storage,
+            tokens,
+            ongoing_ops,
+            confirmations_for_eth_event,
+        )
+        .await?
+    };
+
+    Ok(Some(AccountInfo {
+        address: account.address,
+        id: account_id,
+        committed,
+        verified,
+        depositing,
+    }))
 }
 
 /// Shared data between `api/v1/accounts` endpoints.
@@ -55,6 +114,7 @@ struct ApiAccountsData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     core_api_client: CoreApiClient,
+    ens_resolver: EnsResolver,
     confirmations_for_eth_event: BlockNumber,
 }
 
@@ -63,12 +123,14 @@ impl ApiAccountsData {
         pool: ConnectionPool,
         tokens: TokenDBCache,
         core_api_client: CoreApiClient,
+        ens_resolver: EnsResolver,
         confirmations_for_eth_event: BlockNumber,
     ) -> Self {
         Self {
             pool,
             tokens,
             core_api_client,
+            ens_resolver,
             confirmations_for_eth_event,
         }
     }
@@ -77,8 +139,24 @@ impl ApiAccountsData {
         self.pool.access_storage().await.map_err(From::from)
     }
 
+    // Additional parser because actix-web doesn't understand enums in path extractor.
+    // ENS names are resolved to the addresses they point to.
+    async fn parse_account_query(&self, query: String) -> Result<AccountQuery, ApiError> {
+        if is_ens_name(&query) {
+            let address = self.ens_resolver.resolve(&query).await.map_err(ens_error)?;
+            return Ok(AccountQuery::Address(address));
+        }
+
+        query.parse().map_err(|err| {
+            ApiError::bad_request(
+                "Must be specified either an account ID, an account address or an ENS name.",
+            )
+            .detail(format!("An error occurred: {}", err))
+        })
+    }
+
     async fn find_account_address(&self, query: String) -> Result<Address, ApiError> {
-        let query = parse_account_query(query)?;
+        let query = self.parse_account_query(query).await?;
         self.account_address(query)
             .await
             .map_err(ApiError::internal)?
@@ -126,51 +204,14 @@ impl ApiAccountsData {
             return Ok(None);
         };
 
-        let account_state = storage
-            .chain()
-            .account_schema()
-            .account_state_by_id(account_id)
-            .await?;
-
-        let (account_id, account) = if let Some(state) = account_state.committed {
-            state
-        } else {
-            // This account has not been committed.
-            return Ok(None);
-        };
-
-        let committed = account_state_from_storage(&mut storage, &self.tokens, &account).await?;
-        let verified = match account_state.verified {
-            Some((_id, account)) => {
-                account_state_from_storage(&mut storage, &self.tokens, &account).await?
-            }
-            None => AccountState::default(),
-        };
-
-        let depositing = {
-            let ongoing_ops = self
-                .core_api_client
-                .get_unconfirmed_deposits(account.address)
-                .await?;
-
-            depositing_balances_from_pending_ops(
-                &mut storage,
-                &self.tokens,
-                ongoing_ops,
-                self.confirmations_for_eth_event,
-            )
-            .await?
-        };
-
-        let info = AccountInfo {
-            address: account.address,
-            id: account_id,
-            committed,
-            verified,
-            depositing,
-        };
-
-        Ok(Some(info))
+        load_account_info(
+            &mut storage,
+            &self.tokens,
+            &self.core_api_client,
+            self.confirmations_for_eth_event,
+            account_id,
+        )
+        .await
     }
 
     async fn tx_receipts(
@@ -221,6 +262,62 @@ impl ApiAccountsData {
         Ok(items.into_iter().map(op_receipt_from_response).collect())
     }
 
+    async fn signing_key_status(
+        &self,
+        query: AccountQuery,
+    ) -> QueryResult<Option<SigningKeyStatus>> {
+        let mut storage = self.access_storage().await?;
+        let account = match Self::account_id(&mut storage, query).await? {
+            Some(account_id) => {
+                storage
+                    .chain()
+                    .account_schema()
+                    .last_committed_state_for_account(account_id)
+                    .await?
+            }
+            None => None,
+        };
+
+        let (address, nonce, pub_key_hash) = match (account, query) {
+            (Some(account), _) => (account.address, account.nonce, account.pub_key_hash),
+            // The signing key may be authorized onchain even before the account is created.
+            (None, AccountQuery::Address(address)) => (address, Nonce(0), PubKeyHash::default()),
+            (None, AccountQuery::Id(_)) => return Ok(None),
+        };
+
+        let is_set = pub_key_hash != PubKeyHash::default();
+        let auth_type = if is_set {
+            storage
+                .chain()
+                .operations_ext_schema()
+                .last_change_pubkey_tx(&address)
+                .await?
+                .map(|tx| {
+                    if tx.eth_signature.is_some() {
+                        SigningKeyAuthType::EthereumSignature
+                    } else {
+                        SigningKeyAuthType::Onchain
+                    }
+                })
+        } else {
+            None
+        };
+        // Authorization facts are indexed by the Ethereum watcher, so the contract isn't queried.
+        let onchain_auth_pending = storage
+            .chain()
+            .account_schema()
+            .load_auth_fact(address, nonce)
+            .await?
+            .is_some();
+
+        Ok(Some(SigningKeyStatus {
+            is_set,
+            pub_key_hash: if is_set { Some(pub_key_hash) } else { None },
+            auth_type,
+            onchain_auth_pending,
+        }))
+    }
+
     async fn pending_op_receipts(
         &self,
         address: Address,
@@ -234,6 +331,49 @@ impl ApiAccountsData {
 
         Ok(receipts)
     }
+
+    async fn account_nonces(&self, address: Address) -> QueryResult<AccountNonces> {
+        let (committed, last_executed) = {
+            let mut storage = self.access_storage().await?;
+            let committed = storage
+                .chain()
+                .account_schema()
+                .account_state_by_address(address)
+                .await?
+                .committed
+                .map(|(_id, account)| account.nonce)
+                .unwrap_or_default();
+            let last_executed = storage
+                .chain()
+                .operations_ext_schema()
+                .last_executed_nonce(&address)
+                .await?;
+            (committed, last_executed)
+        };
+        let queued_txs = self.core_api_client.get_queued_txs(address).await?;
+
+        // Transactions of the pending block are not committed yet, so the nonce of the last
+        // executed transaction may be ahead of the committed one.
+        let next = queued_txs
+            .iter()
+            .map(|tx| tx.nonce())
+            .chain(last_executed)
+            .map(|nonce| nonce + 1)
+            .chain(std::iter::once(committed))
+            .max()
+            .unwrap_or(committed);
+
+        Ok(AccountNonces { committed, next })
+    }
+
+    async fn pending_txs(&self, address: Address) -> QueryResult<Vec<PendingAccountTx>> {
+        let queued_txs = self.core_api_client.get_queued_txs(address).await?;
+
+        Ok(queued_txs
+            .into_iter()
+            .map(pending_account_tx_from_tx)
+            .collect())
+    }
 }
 
 // Server implementation
@@ -242,7 +382,7 @@ async fn account_info(
     data: web::Data<ApiAccountsData>,
     web::Path(query): web::Path<String>,
 ) -> JsonResult<Option<AccountInfo>> {
-    let query = parse_account_query(query)?;
+    let query = data.parse_account_query(query).await?;
 
     data.account_info(query)
         .await
@@ -296,26 +436,100 @@ async fn account_pending_receipts(
     Ok(Json(receipts))
 }
 
+async fn account_nonces(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account_query): web::Path<String>,
+) -> JsonResult<AccountNonces> {
+    let address = data.find_account_address(account_query).await?;
+
+    let nonces = data
+        .account_nonces(address)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(nonces))
+}
+
+async fn account_pending_txs(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account_query): web::Path<String>,
+) -> JsonResult<Vec<PendingAccountTx>> {
+    let address = data.find_account_address(account_query).await?;
+
+    let txs = data
+        .pending_txs(address)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(txs))
+}
+
+async fn account_signing_key(
+    data: web::Data<ApiAccountsData>,
+    web::Path(query): web::Path<String>,
+) -> JsonResult<Option<SigningKeyStatus>> {
+    let query = data.parse_account_query(query).await?;
+
+    data.signing_key_status(query)
+        .await
+        .map(Json)
+        .map_err(ApiError::internal)
+}
+
+async fn account_updates(
+    data: web::Data<ApiAccountsData>,
+    web::Query(query): web::Query<AccountUpdatesQuery>,
+) -> JsonResult<Vec<AccountUpdateJournalEntry>> {
+    if query.limit == 0 || query.limit > MAX_LIMIT {
+        return Err(ApiError::bad_request("Incorrect limit")
+            .detail(format!("Limit should be between {} and {}", 1, MAX_LIMIT)));
+    }
+
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let updates = storage
+        .chain()
+        .state_schema()
+        .load_account_updates_journal(query.from, query.limit)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(updates))
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     config: &ZkSyncConfig,
     tokens: TokenDBCache,
     core_api_client: CoreApiClient,
+    ens_resolver: EnsResolver,
 ) -> Scope {
     let data = ApiAccountsData::new(
         pool,
         tokens,
         core_api_client,
+        ens_resolver,
         BlockNumber(config.eth_watch.confirmations_for_eth_event as u32),
     );
 
     web::scope("accounts")
         .data(data)
+        // Must be registered before `{id}`, otherwise it will be treated as an account query.
+        .route("updates", web::get().to(account_updates))
         .route("{id}", web::get().to(account_info))
         .route(
             "{id}/transactions/receipts",
             web::get().to(account_tx_receipts),
         )
+        .route("{id}/nonces", web::get().to(account_nonces))
+        .route(
+            "{id}/transactions/pending",
+            web::get().to(account_pending_txs),
+        )
         .route(
             "{id}/operations/receipts",
             web::get().to(account_op_receipts),
@@ -324,4 +538,5 @@ pub fn api_scope(
             "{id}/operations/pending",
             web::get().to(account_pending_receipts),
         )
+        .route("{id}/signing_key", web::get().to(account_signing_key))
 }
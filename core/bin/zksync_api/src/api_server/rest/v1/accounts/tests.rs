@@ -14,12 +14,15 @@ use zksync_storage::{
     chain::operations_ext::records::{AccountOpReceiptResponse, AccountTxReceiptResponse},
     ConnectionPool, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, ExecutedOperations, H256};
+use zksync_types::{
+    tx::{TxFailureCode, TxHash},
+    AccountId, Address, BlockNumber, ExecutedOperations, Nonce, PubKeyHash, H256,
+};
 
 // Local uses
 use crate::{
     api_server::v1::{
-        test_utils::{dummy_deposit_op, TestServerConfig},
+        test_utils::{dummy_deposit_op, TestServerConfig, TestTransactions},
         transactions::Receipt,
         Client,
     },
@@ -44,6 +47,7 @@ fn create_pending_ops_handle() -> PendingOpsHandle {
 fn get_unconfirmed_ops_loopback(
     ops_handle: PendingOpsHandle,
     deposits_handle: PendingOpsHandle,
+    queued_txs_handle: PendingOpsHandle,
 ) -> (CoreApiClient, actix_web::test::TestServer) {
     async fn get_ops(
         data: web::Data<PendingOpsHandle>,
@@ -55,6 +59,7 @@ fn get_unconfirmed_ops_loopback(
     let server = actix_web::test::start(move || {
         let ops_handle = ops_handle.clone();
         let deposits_handle = deposits_handle.clone();
+        let queued_txs_handle = queued_txs_handle.clone();
         App::new()
             .service(
                 web::scope("unconfirmed_ops")
@@ -66,10 +71,15 @@ fn get_unconfirmed_ops_loopback(
                     .data(deposits_handle)
                     .route("{address}", web::get().to(get_ops)),
             )
+            .service(
+                web::scope("queued_txs")
+                    .data(queued_txs_handle)
+                    .route("{address}", web::get().to(get_ops)),
+            )
     });
 
     let url = server.url("").trim_end_matches('/').to_owned();
-    (CoreApiClient::new(url), server)
+    (CoreApiClient::new(url, "secret".to_owned()), server)
 }
 
 struct TestServer {
@@ -78,6 +88,7 @@ struct TestServer {
     pool: ConnectionPool,
     pending_ops: PendingOpsHandle,
     pending_deposits: PendingOpsHandle,
+    queued_txs: PendingOpsHandle,
 }
 
 impl TestServer {
@@ -87,8 +98,12 @@ impl TestServer {
 
         let pending_ops = create_pending_ops_handle();
         let pending_deposits = create_pending_ops_handle();
-        let (core_client, core_server) =
-            get_unconfirmed_ops_loopback(pending_ops.clone(), pending_deposits.clone());
+        let queued_txs = create_pending_ops_handle();
+        let (core_client, core_server) = get_unconfirmed_ops_loopback(
+            pending_ops.clone(),
+            pending_deposits.clone(),
+            queued_txs.clone(),
+        );
 
         let pool = cfg.pool.clone();
 
@@ -98,6 +113,7 @@ impl TestServer {
                 &cfg.config,
                 TokenDBCache::new(),
                 core_client.clone(),
+                cfg.ens_resolver(),
             )
         });
 
@@ -109,6 +125,7 @@ impl TestServer {
                 pool,
                 pending_ops,
                 pending_deposits,
+                queued_txs,
             },
         ))
     }
@@ -142,11 +159,15 @@ impl TestServer {
     ignore = "Use `zk test rust-api` command to perform this test"
 )]
 async fn unconfirmed_deposits_loopback() -> anyhow::Result<()> {
-    let (client, server) =
-        get_unconfirmed_ops_loopback(create_pending_ops_handle(), create_pending_ops_handle());
+    let (client, server) = get_unconfirmed_ops_loopback(
+        create_pending_ops_handle(),
+        create_pending_ops_handle(),
+        create_pending_ops_handle(),
+    );
 
     client.get_unconfirmed_deposits(Address::default()).await?;
     client.get_unconfirmed_ops(Address::default()).await?;
+    client.get_queued_txs(Address::default()).await?;
 
     server.stop().await;
     Ok(())
@@ -206,7 +227,8 @@ async fn accounts_scope() -> anyhow::Result<()> {
     assert_eq!(
         receipts[0].receipt,
         Receipt::Rejected {
-            reason: Some("Unknown token".to_string())
+            reason: Some("Unknown token".to_string()),
+            code: Some(TxFailureCode::UnsupportedToken),
         }
     );
     assert_eq!(receipts[2].index, Some(3));
@@ -346,6 +368,61 @@ async fn accounts_scope() -> anyhow::Result<()> {
     assert_eq!(pending_receipts[1].eth_block, 5);
     assert_eq!(pending_receipts[1].hash, [1u8; 32].into());
 
+    // Get account transactions waiting in the mempool.
+    let TestTransactions { txs, .. } = TestServerConfig::gen_zk_txs(100);
+    let tx = txs[0].0.clone();
+    *server.queued_txs.lock().await = serde_json::to_value(vec![tx.clone()])?;
+    let pending_txs = client.account_pending_txs(address).await?;
+
+    assert_eq!(pending_txs.len(), 1);
+    assert_eq!(pending_txs[0].tx_hash, tx.hash());
+    assert_eq!(pending_txs[0].nonce, tx.nonce());
+    assert_eq!(pending_txs[0].fee.0, tx.get_fee_info().unwrap().3);
+    assert_eq!(pending_txs[0].tx, tx);
+
+    // Next nonce takes the queued transactions into account.
+    let nonces = client.account_nonces(address).await?;
+    let account_info = client.account_info(address).await?.unwrap();
+    assert_eq!(nonces.committed, account_info.committed.nonce);
+    assert!(nonces.next > tx.nonce());
+    assert!(nonces.next >= nonces.committed);
+
+    // Accounts absent in the state have no signing key status.
+    assert_eq!(client.account_signing_key(AccountId(1000)).await?, None);
+
+    // Onchain authorization of the account which doesn't exist yet is taken from the
+    // facts indexed by the Ethereum watcher.
+    let new_address = Address::repeat_byte(0x42);
+    let status = client.account_signing_key(new_address).await?.unwrap();
+    assert!(!status.is_set);
+    assert!(!status.onchain_auth_pending);
+    server
+        .pool
+        .access_storage()
+        .await?
+        .chain()
+        .account_schema()
+        .store_auth_fact(new_address, Nonce(0), &PubKeyHash::default())
+        .await?;
+    let status = client.account_signing_key(new_address).await?.unwrap();
+    assert!(!status.is_set);
+    assert!(status.onchain_auth_pending);
+
+    // Get the journal of the committed account updates.
+    let updates = client.account_updates(0, MAX_LIMIT).await?;
+    assert!(!updates.is_empty());
+    for pair in updates.windows(2) {
+        assert!(pair[0].sequence_number < pair[1].sequence_number);
+    }
+    // The journal can be streamed from any sequence number.
+    let from = updates[updates.len() / 2].sequence_number;
+    assert_eq!(
+        client.account_updates(from, MAX_LIMIT).await?,
+        &updates[updates.len() / 2..]
+    );
+    // Incorrect limit should be rejected.
+    assert!(client.account_updates(0, MAX_LIMIT + 1).await.is_err());
+
     server.stop().await;
     Ok(())
 }
@@ -363,13 +440,16 @@ fn account_tx_response_to_receipt() {
                 block_number: 1,
                 success: true,
                 fail_reason: None,
+                fail_code: None,
                 commit_tx_hash: None,
                 verify_tx_hash: None,
                 tx_hash: empty_hash(),
+                memo: None,
             },
             AccountTxReceipt {
                 index: Some(1),
                 hash: TxHash::default(),
+                memo: None,
                 receipt: Receipt::Executed,
             },
         ),
@@ -379,13 +459,16 @@ fn account_tx_response_to_receipt() {
                 block_number: 1,
                 success: true,
                 fail_reason: None,
+                fail_code: None,
                 commit_tx_hash: None,
                 verify_tx_hash: None,
                 tx_hash: empty_hash(),
+                memo: None,
             },
             AccountTxReceipt {
                 index: None,
                 hash: TxHash::default(),
+                memo: None,
                 receipt: Receipt::Executed,
             },
         ),
@@ -395,15 +478,19 @@ fn account_tx_response_to_receipt() {
                 block_number: 1,
                 success: false,
                 fail_reason: Some("Oops".to_string()),
+                fail_code: Some("nonceMismatch".to_string()),
                 commit_tx_hash: None,
                 verify_tx_hash: None,
                 tx_hash: empty_hash(),
+                memo: None,
             },
             AccountTxReceipt {
                 index: Some(1),
                 hash: TxHash::default(),
+                memo: None,
                 receipt: Receipt::Rejected {
                     reason: Some("Oops".to_string()),
+                    code: Some(TxFailureCode::NonceMismatch),
                 },
             },
         ),
@@ -413,13 +500,16 @@ fn account_tx_response_to_receipt() {
                 block_number: 1,
                 success: true,
                 fail_reason: None,
+                fail_code: None,
                 commit_tx_hash: Some(empty_hash()),
                 verify_tx_hash: None,
                 tx_hash: empty_hash(),
+                memo: None,
             },
             AccountTxReceipt {
                 index: Some(1),
                 hash: TxHash::default(),
+                memo: None,
                 receipt: Receipt::Committed {
                     block: BlockNumber(1),
                 },
@@ -431,13 +521,16 @@ fn account_tx_response_to_receipt() {
                 block_number: 1,
                 success: true,
                 fail_reason: None,
+                fail_code: None,
                 commit_tx_hash: Some(empty_hash()),
                 verify_tx_hash: Some(empty_hash()),
                 tx_hash: empty_hash(),
+                memo: Some("order #42".to_string()),
             },
             AccountTxReceipt {
                 index: Some(1),
                 hash: TxHash::default(),
+                memo: Some("order #42".to_string()),
                 receipt: Receipt::Verified {
                     block: BlockNumber(1),
                 },
@@ -3,11 +3,15 @@
 // Built-in uses
 use std::collections::BTreeMap;
 
+// External uses
+use num::{BigUint, Zero};
+
 // Workspace uses
 pub use zksync_api_client::rest::v1::accounts::{
-    AccountInfo, AccountOpReceipt, AccountQuery, AccountReceipts, AccountReceiptsQuery,
-    AccountState, AccountTxReceipt, DepositingBalances, DepositingFunds, PendingAccountOpReceipt,
-    SearchDirection, TxLocation,
+    AccountInfo, AccountNonces, AccountOpReceipt, AccountQuery, AccountReceipts,
+    AccountReceiptsQuery, AccountState, AccountTxReceipt, AccountUpdatesQuery, DepositingBalances,
+    DepositingFunds, PendingAccountOpReceipt, PendingAccountTx, SearchDirection,
+    SigningKeyAuthType, SigningKeyStatus, TxLocation,
 };
 use zksync_storage::{
     chain::operations_ext::{
@@ -16,7 +20,9 @@ use zksync_storage::{
     },
     QueryResult, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, Account, BlockNumber, PriorityOp, ZkSyncPriorityOp, H256};
+use zksync_types::{
+    tx::TxHash, Account, BlockNumber, PriorityOp, TokenId, ZkSyncPriorityOp, ZkSyncTx, H256,
+};
 
 // Local uses
 use crate::{api_server::v1::MAX_LIMIT, utils::token_db_cache::TokenDBCache};
@@ -140,9 +146,8 @@ pub(super) mod convert {
             return AccountTxReceipt {
                 index,
                 hash,
-                receipt: Receipt::Rejected {
-                    reason: inner.fail_reason,
-                },
+                receipt: Receipt::rejected(inner.fail_reason, inner.fail_code.as_deref()),
+                memo: inner.memo,
             };
         }
 
@@ -163,6 +168,7 @@ pub(super) mod convert {
             index,
             receipt,
             hash,
+            memo: inner.memo,
         }
     }
 
@@ -191,6 +197,25 @@ pub(super) mod convert {
         }
     }
 
+    pub fn pending_account_tx_from_tx(tx: ZkSyncTx) -> PendingAccountTx {
+        let (fee_token, fee) = match &tx {
+            ZkSyncTx::Transfer(tx) => (tx.token, tx.fee.clone()),
+            ZkSyncTx::Withdraw(tx) => (tx.token, tx.fee.clone()),
+            ZkSyncTx::ForcedExit(tx) => (tx.token, tx.fee.clone()),
+            ZkSyncTx::ChangePubKey(tx) => (tx.fee_token, tx.fee.clone()),
+            // `Close` transactions are not accepted to the mempool.
+            ZkSyncTx::Close(_) => (TokenId(0), BigUint::zero()),
+        };
+
+        PendingAccountTx {
+            tx_hash: tx.hash(),
+            nonce: tx.nonce(),
+            fee_token,
+            fee: fee.into(),
+            tx,
+        }
+    }
+
     pub fn pending_account_op_receipt_from_priority_op(op: PriorityOp) -> PendingAccountOpReceipt {
         PendingAccountOpReceipt {
             eth_block: op.eth_block,
@@ -9,11 +9,12 @@ use actix_web::{
 };
 
 // Workspace uses
-pub use zksync_api_client::rest::v1::{BlockInfo, TransactionInfo};
-use zksync_config::ZkSyncConfig;
-use zksync_crypto::{convert::FeConvert, Fr};
-use zksync_storage::{chain::block::records, ConnectionPool, QueryResult};
-use zksync_types::{tx::TxHash, BlockNumber};
+pub use zksync_api_client::rest::v1::{BlockInfo, BlockMetadata, BlockStageInfo, TransactionInfo};
+use zksync_cold_storage::ColdStorage;
+use zksync_config::{ColdStorageConfig, ZkSyncConfig};
+use zksync_crypto::{convert::FeConvert, proof::EncodedProofPlonk, Fr};
+use zksync_storage::{chain::block::records, ConnectionPool, QueryResult, StorageActionType};
+use zksync_types::{tx::TxHash, BlockNumber, H256, U256};
 
 // Local uses
 use super::{Error as ApiError, JsonResult, Pagination, PaginationQuery};
@@ -23,14 +24,16 @@ use crate::{api_server::helpers::try_parse_tx_hash, utils::shared_lru_cache::Asy
 #[derive(Debug, Clone)]
 struct ApiBlocksData {
     pool: ConnectionPool,
+    cold_storage: ColdStorage,
     /// Verified blocks cache.
     verified_blocks: AsyncLruCache<BlockNumber, records::BlockDetails>,
 }
 
 impl ApiBlocksData {
-    fn new(pool: ConnectionPool, capacity: usize) -> Self {
+    fn new(pool: ConnectionPool, cold_storage: ColdStorage, capacity: usize) -> Self {
         Self {
             pool,
+            cold_storage,
             verified_blocks: AsyncLruCache::new(capacity),
         }
     }
@@ -85,6 +88,51 @@ impl ApiBlocksData {
             .await
     }
 
+    /// Returns the processing timeline of the block with the specified number.
+    async fn block_metadata(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<BlockMetadata>> {
+        let stages = {
+            let mut storage = self.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .load_block_stages(block_number)
+                .await?
+        };
+
+        let mut commit = None;
+        let mut verify = None;
+        for stage in stages {
+            let action_type = stage.action_type.clone();
+            let info = convert::block_stage_info(stage);
+            match action_type {
+                StorageActionType::COMMIT => commit = Some(info),
+                StorageActionType::VERIFY => verify = Some(info),
+            }
+        }
+
+        // Block without a commit operation is not created yet.
+        Ok(commit.map(|commit| BlockMetadata {
+            block_number,
+            created_at: commit.created_at,
+            commit,
+            verify,
+        }))
+    }
+
+    /// Returns the proof of the block, either from the database or from the cold storage.
+    async fn block_proof(
+        &self,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<Option<EncodedProofPlonk>> {
+        let mut storage = self.pool.access_storage().await?;
+        self.cold_storage
+            .load_proof(&mut storage, block_number)
+            .await
+    }
+
     /// Return transactions stored in the block with the specified number.
     async fn block_transactions(
         &self,
@@ -99,7 +147,7 @@ impl ApiBlocksData {
     }
 }
 
-pub(super) mod convert {
+pub(crate) mod convert {
     use zksync_api_client::rest::v1::PaginationQueryError;
 
     use super::*;
@@ -135,6 +183,16 @@ pub(super) mod convert {
             }
     }
 
+    pub fn block_stage_info(stage: records::BlockStageDetails) -> BlockStageInfo {
+        BlockStageInfo {
+            created_at: stage.created_at,
+            eth_op_id: stage.eth_op_id,
+            tx_hash: stage.final_hash.map(|bytes| H256::from_slice(&bytes)),
+            gas_used: stage.gas_used.map(|gas_used| U256::from(gas_used as u64)),
+            confirmed_at: stage.confirmed_at,
+        }
+    }
+
     pub fn transaction_info_from_transaction_item(
         inner: records::BlockTransactionItem,
     ) -> TransactionInfo {
@@ -174,6 +232,30 @@ async fn block_by_id(
     ))
 }
 
+async fn block_metadata(
+    data: web::Data<ApiBlocksData>,
+    web::Path(block_number): web::Path<BlockNumber>,
+) -> JsonResult<Option<BlockMetadata>> {
+    let metadata = data
+        .block_metadata(block_number)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(metadata))
+}
+
+async fn block_proof(
+    data: web::Data<ApiBlocksData>,
+    web::Path(block_number): web::Path<BlockNumber>,
+) -> JsonResult<Option<EncodedProofPlonk>> {
+    let proof = data
+        .block_proof(block_number)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(proof))
+}
+
 async fn block_transactions(
     data: web::Data<ApiBlocksData>,
     web::Path(block_number): web::Path<BlockNumber>,
@@ -221,12 +303,18 @@ async fn blocks_range(
 }
 
 pub fn api_scope(config: &ZkSyncConfig, pool: ConnectionPool) -> Scope {
-    let data = ApiBlocksData::new(pool, config.api.common.caches_size);
+    let data = ApiBlocksData::new(
+        pool,
+        ColdStorage::from_config(&ColdStorageConfig::from_env()),
+        config.api.common.caches_size,
+    );
 
     web::scope("blocks")
         .data(data)
         .route("", web::get().to(blocks_range))
         .route("{id}", web::get().to(block_by_id))
+        .route("{id}/metadata", web::get().to(block_metadata))
+        .route("{id}/proof", web::get().to(block_proof))
         .route("{id}/transactions", web::get().to(block_transactions))
 }
 
@@ -305,6 +393,31 @@ mod tests {
         );
         assert_eq!(client.block_transactions(BlockNumber(6)).await?, vec![]);
 
+        // Metadata requests part.
+        let metadata = client
+            .block_metadata(BlockNumber(1))
+            .await?
+            .expect("Block metadata should exist");
+        assert_eq!(metadata.block_number, BlockNumber(1));
+        assert_eq!(metadata.created_at, metadata.commit.created_at);
+        assert_eq!(
+            metadata.commit.tx_hash.map(|hash| hash.as_bytes().to_vec()),
+            blocks[7].commit_tx_hash.map(|hash| hash.as_ref().to_vec())
+        );
+        assert!(client
+            .block_metadata(BlockNumber(u32::MAX))
+            .await?
+            .is_none());
+
+        // Proof requests part.
+        let proof = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage.prover_schema().load_proof(BlockNumber(1)).await?
+        };
+        assert!(proof.is_some());
+        assert_eq!(client.block_proof(BlockNumber(1)).await?, proof);
+        assert!(client.block_proof(BlockNumber(u32::MAX)).await?.is_none());
+
         server.stop().await;
         Ok(())
     }
@@ -6,9 +6,9 @@
 use actix_web::{web, Scope};
 
 // Workspace uses
-use zksync_api_client::rest::v1::Contracts;
+use zksync_api_client::rest::v1::{Contracts, NetworkConfig, NetworkContracts};
 use zksync_config::ZkSyncConfig;
-use zksync_types::{network::Network, Address};
+use zksync_types::{network::Network, tx::PackedEthSignature, Address};
 
 // Local uses
 use super::Json;
@@ -19,6 +19,8 @@ struct ApiConfigData {
     contract_address: Address,
     deposit_confirmations: u64,
     network: Network,
+    soft_confirmation_signer: Option<Address>,
+    network_config: NetworkConfig,
 }
 
 impl ApiConfigData {
@@ -27,12 +29,37 @@ impl ApiConfigData {
             contract_address: config.contracts.contract_addr,
             deposit_confirmations: config.eth_watch.confirmations_for_eth_event,
             network: config.chain.eth.network,
+            soft_confirmation_signer: config.api.common.soft_confirmation_private_key.map(
+                |private_key| {
+                    PackedEthSignature::address_from_private_key(&private_key)
+                        .expect("Incorrect soft confirmation private key")
+                },
+            ),
+            network_config: NetworkConfig {
+                network: config.chain.eth.network,
+                chain_id: config.eth_client.chain_id,
+                contracts: NetworkContracts {
+                    main: config.contracts.contract_addr,
+                    governance: config.contracts.governance_addr,
+                    verifier: config.contracts.verifier_addr,
+                },
+                block_chunk_sizes: config.chain.block_chunk_sizes(),
+                enforce_pubkey_change_fee: config.api.common.enforce_pubkey_change_fee,
+                forced_exit_minimum_account_age_secs: config
+                    .api
+                    .common
+                    .forced_exit_minimum_account_age_secs,
+            },
         }
     }
 }
 
 // Server implementation
 
+async fn network_config(data: web::Data<ApiConfigData>) -> Json<NetworkConfig> {
+    Json(data.network_config.clone())
+}
+
 async fn contracts(data: web::Data<ApiConfigData>) -> Json<Contracts> {
     Json(Contracts {
         contract: data.contract_address,
@@ -47,13 +74,22 @@ async fn network(data: web::Data<ApiConfigData>) -> Json<Network> {
     Json(data.network)
 }
 
+async fn soft_confirmation_signer(data: web::Data<ApiConfigData>) -> Json<Option<Address>> {
+    Json(data.soft_confirmation_signer)
+}
+
 pub fn api_scope(config: &ZkSyncConfig) -> Scope {
     let data = ApiConfigData::new(config);
 
     web::scope("config")
         .data(data)
+        .route("", web::get().to(network_config))
         .route("contracts", web::get().to(contracts))
         .route("network", web::get().to(network))
+        .route(
+            "soft_confirmation_signer",
+            web::get().to(soft_confirmation_signer),
+        )
         .route(
             "deposit_confirmations",
             web::get().to(deposit_confirmations),
@@ -88,6 +124,26 @@ mod tests {
                 contract: cfg.config.contracts.contract_addr
             },
         );
+        assert_eq!(
+            client.soft_confirmation_signer().await?,
+            ApiConfigData::new(&cfg.config).soft_confirmation_signer
+        );
+
+        let network_config = client.network_config().await?;
+        assert_eq!(
+            network_config,
+            ApiConfigData::new(&cfg.config).network_config
+        );
+        assert_eq!(
+            network_config.contracts.main,
+            cfg.config.contracts.contract_addr
+        );
+
+        // Chain ID is always taken from the config, test networks have no preset.
+        let mut config = cfg.config.clone();
+        config.chain.eth.network = Network::Test;
+        config.eth_client.chain_id = 27;
+        assert_eq!(ApiConfigData::new(&config).network_config.chain_id, 27);
 
         server.stop().await;
 
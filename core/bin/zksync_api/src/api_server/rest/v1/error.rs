@@ -1,12 +1,19 @@
 // Built-in uses
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
 
 // External uses
 use actix_web::{dev::Body, http::HeaderValue, HttpResponse, ResponseError};
-use reqwest::{header::CONTENT_TYPE, StatusCode};
+use reqwest::{
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    StatusCode,
+};
+use serde_json::Value;
 
 // Workspace uses
-pub use zksync_api_client::rest::v1::ErrorBody;
+pub use zksync_api_client::rest::v1::{ErrorBody, ErrorCode};
 
 // Local uses
 
@@ -22,17 +29,32 @@ pub struct Error {
 impl Error {
     /// Creates a new Error with the BAD_REQUEST (400) status code.
     pub fn bad_request(title: impl Display) -> Self {
-        Self::with_code(StatusCode::BAD_REQUEST, title)
+        Self::with_code(StatusCode::BAD_REQUEST, title).code(ErrorCode::InvalidRequest)
+    }
+
+    /// Creates a new Error with the UNAUTHORIZED (401) status code.
+    pub fn unauthorized(title: impl Display) -> Self {
+        Self::with_code(StatusCode::UNAUTHORIZED, title).code(ErrorCode::Unauthorized)
     }
 
     /// Creates a new Error with the INTERNAL_SERVER_ERROR (500) status code.
     pub fn internal(title: impl Display) -> Self {
-        Self::with_code(StatusCode::INTERNAL_SERVER_ERROR, title)
+        Self::with_code(StatusCode::INTERNAL_SERVER_ERROR, title).code(ErrorCode::Internal)
     }
 
     /// Creates a new Error with the NOT_IMPLEMENTED (501) status code.
     pub fn not_implemented(title: impl Display) -> Self {
-        Self::with_code(StatusCode::NOT_IMPLEMENTED, title)
+        Self::with_code(StatusCode::NOT_IMPLEMENTED, title).code(ErrorCode::NotImplemented)
+    }
+
+    /// Creates a new Error with the TOO_MANY_REQUESTS (429) status code.
+    pub fn too_many_requests(title: impl Display) -> Self {
+        Self::with_code(StatusCode::TOO_MANY_REQUESTS, title).code(ErrorCode::RateLimitExceeded)
+    }
+
+    /// Creates a new Error with the SERVICE_UNAVAILABLE (503) status code.
+    pub fn service_unavailable(title: impl Display) -> Self {
+        Self::with_code(StatusCode::SERVICE_UNAVAILABLE, title).code(ErrorCode::ServiceUnavailable)
     }
 
     fn with_code(http_code: StatusCode, title: impl Display) -> Self {
@@ -58,10 +80,32 @@ impl Error {
     }
 
     /// Sets error specific code.
-    pub fn code(mut self, code: u64) -> Self {
+    pub fn code(mut self, code: ErrorCode) -> Self {
+        self.body.code = Some(code.as_u64());
+        self.body.code_name = code.name().to_owned();
+        self.retry_after(code.retry_after())
+    }
+
+    /// Overrides the numeric error code set by [`code`](Self::code), used by the endpoints
+    /// which keep their legacy numeric codes.
+    pub fn numeric_code(mut self, code: u64) -> Self {
         self.body.code = Some(code);
         self
     }
+
+    /// Sets structured details of the error.
+    pub fn data(mut self, data: Option<Value>) -> Self {
+        self.body.data = data;
+        self
+    }
+
+    /// Sets the suggested delay before retrying the request, `None` means that
+    /// the request shouldn't be retried as is.
+    pub fn retry_after(mut self, delay: Option<Duration>) -> Self {
+        self.body.retryable = delay.is_some();
+        self.body.retry_after = delay.map(|delay| delay.as_secs());
+        self
+    }
 }
 
 impl Display for Error {
@@ -82,6 +126,10 @@ impl ResponseError for Error {
             Ok(body) => {
                 resp.headers_mut()
                     .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                if let Some(retry_after) = self.body.retry_after {
+                    resp.headers_mut()
+                        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+                }
                 resp.set_body(Body::from_slice(&body))
             }
 
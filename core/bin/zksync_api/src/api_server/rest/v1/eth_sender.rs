@@ -0,0 +1,132 @@
+//! Ethereum sender part of API implementation.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+pub use zksync_api_client::rest::v1::{EthOperationInfo, EthOperationsQuery};
+use zksync_storage::ConnectionPool;
+use zksync_types::ethereum::GasAdjusterStats;
+
+// Local uses
+use super::{Error as ApiError, JsonResult, MAX_LIMIT};
+
+/// Shared data between `api/v1/eth_sender` endpoints.
+#[derive(Debug, Clone)]
+struct ApiEthSenderData {
+    pool: ConnectionPool,
+}
+
+impl ApiEthSenderData {
+    fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Server implementation
+
+async fn gas_adjuster_stats(
+    data: web::Data<ApiEthSenderData>,
+) -> JsonResult<Option<GasAdjusterStats>> {
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let stats = storage
+        .ethereum_schema()
+        .load_gas_adjuster_stats()
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(web::Json(stats))
+}
+
+async fn eth_operations(
+    data: web::Data<ApiEthSenderData>,
+    web::Query(query): web::Query<EthOperationsQuery>,
+) -> JsonResult<Vec<EthOperationInfo>> {
+    if query.limit == 0 || query.limit > MAX_LIMIT {
+        return Err(ApiError::bad_request("Incorrect limit")
+            .detail(format!("Limit should be between {} and {}", 1, MAX_LIMIT)));
+    }
+
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let operations = storage
+        .ethereum_schema()
+        .load_operations_summary(query.before, query.limit)
+        .await
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(EthOperationInfo::new)
+        .collect();
+
+    Ok(web::Json(operations))
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiEthSenderData::new(pool);
+
+    web::scope("eth_sender")
+        .data(data)
+        .route("gas_adjuster", web::get().to(gas_adjuster_stats))
+        .route("operations", web::get().to(eth_operations))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{super::test_utils::TestServerConfig, *};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn eth_sender_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let (client, server) = cfg.start_server(|cfg| api_scope(cfg.pool.clone()));
+
+        let stats = GasAdjusterStats {
+            last_suggested_price: Some(15.into()),
+            samples: vec![10.into(), 20.into()],
+            average_price: None,
+            price_limit: 100.into(),
+            scale_factor: 1.5,
+            updated_at: Utc.timestamp(1_600_000_000, 0),
+        };
+        cfg.pool
+            .access_storage()
+            .await?
+            .ethereum_schema()
+            .update_gas_adjuster_stats(&stats)
+            .await?;
+
+        assert_eq!(client.gas_adjuster_stats().await?, Some(stats));
+
+        // Operations are returned in the descending order.
+        let operations = client.eth_operations(None, MAX_LIMIT).await?;
+        for pair in operations.windows(2) {
+            assert!(pair[0].id > pair[1].id);
+        }
+        // Confirmed operations have a final hash.
+        for op in &operations {
+            assert_eq!(op.confirmed, op.final_hash.is_some());
+        }
+
+        // Incorrect limit should be rejected.
+        assert!(client.eth_operations(None, MAX_LIMIT + 1).await.is_err());
+
+        server.stop().await;
+        Ok(())
+    }
+}
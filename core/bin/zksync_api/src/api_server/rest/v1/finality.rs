@@ -0,0 +1,622 @@
+//! Finality estimation part of API implementation.
+//!
+//! Transaction becomes final once its block is verified on Ethereum. Until then the block
+//! has to be sealed, committed, proven and verified, so the estimate is based on the rates
+//! of these stages observed for the recent blocks and on the current queues of the mempool,
+//! the prover and the Ethereum sender.
+
+// Built-in uses
+use std::{cmp, time::Duration};
+
+// External uses
+use actix_web::{web, Scope};
+use chrono::{DateTime, Utc};
+
+// Workspace uses
+pub use zksync_api_client::rest::v1::{FinalityEstimate, FinalityQuery, NetworkLoad};
+use zksync_config::ZkSyncConfig;
+use zksync_storage::{chain::stats::BlockTimings, ConnectionPool, QueryResult, StorageProcessor};
+use zksync_types::{
+    tokens::TokenTxType, tx::TxHash, BlockNumber, ChangePubKeyOp, ForcedExitOp, TransferOp,
+    WithdrawOp,
+};
+
+// Local uses
+use super::{Error as ApiError, JsonResult};
+
+/// Amount of the recent blocks used to measure the processing rates.
+const TIMINGS_SAMPLE_SIZE: u32 = 20;
+
+/// Returns the amount of the block chunks taken by the transaction of the given type.
+fn tx_chunks(tx_type: TokenTxType) -> u32 {
+    let chunks = match tx_type {
+        TokenTxType::Transfer => TransferOp::CHUNKS,
+        TokenTxType::Withdraw => WithdrawOp::CHUNKS,
+        TokenTxType::ForcedExit => ForcedExitOp::CHUNKS,
+        TokenTxType::ChangePubKey => ChangePubKeyOp::CHUNKS,
+    };
+    chunks as u32
+}
+
+/// Returns the average interval between the events.
+fn mean_interval(mut times: Vec<DateTime<Utc>>) -> Option<Duration> {
+    if times.len() < 2 {
+        return None;
+    }
+    times.sort();
+    let total = (*times.last().unwrap() - times[0]).to_std().ok()?;
+    Some(total / (times.len() - 1) as u32)
+}
+
+/// Returns the average duration between the start and the end of the events.
+fn mean_latency(events: impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)>) -> Option<Duration> {
+    let latencies: Vec<_> = events
+        .map(|(start, end)| (end - start).to_std().unwrap_or_default())
+        .collect();
+    if latencies.is_empty() {
+        return None;
+    }
+    Some(latencies.iter().sum::<Duration>() / latencies.len() as u32)
+}
+
+/// Returns the remaining part of the expected duration of the stage started `elapsed` ago.
+fn remaining(expected: Duration, started_at: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    let elapsed = (now - started_at).to_std().unwrap_or_default();
+    expected.checked_sub(elapsed).unwrap_or_default()
+}
+
+/// Average durations of the block processing stages observed for the recent blocks.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct ProcessingRates {
+    /// Interval between the sealed blocks.
+    block_interval: Option<Duration>,
+    /// Time from sealing the block until its commit is confirmed on Ethereum.
+    commit_latency: Option<Duration>,
+    /// Time from sealing the block until its proof is ready.
+    proof_latency: Option<Duration>,
+    /// Interval between the proofs, i.e. the throughput of the provers.
+    proof_interval: Option<Duration>,
+    /// Time from the proof being ready until the verification is confirmed on Ethereum.
+    verify_latency: Option<Duration>,
+    /// Interval between the operations confirmed on Ethereum.
+    eth_confirmation_interval: Option<Duration>,
+}
+
+impl ProcessingRates {
+    fn new(timings: &[BlockTimings]) -> Self {
+        let confirmations = timings
+            .iter()
+            .flat_map(|block| vec![block.commit_confirmed_at, block.verify_confirmed_at])
+            .flatten()
+            .collect();
+
+        Self {
+            block_interval: mean_interval(timings.iter().map(|block| block.committed_at).collect()),
+            commit_latency: mean_latency(
+                timings
+                    .iter()
+                    .filter_map(|block| Some((block.committed_at, block.commit_confirmed_at?))),
+            ),
+            proof_latency: mean_latency(
+                timings
+                    .iter()
+                    .filter_map(|block| Some((block.committed_at, block.verified_at?))),
+            ),
+            proof_interval: mean_interval(
+                timings
+                    .iter()
+                    .filter_map(|block| block.verified_at)
+                    .collect(),
+            ),
+            verify_latency: mean_latency(
+                timings
+                    .iter()
+                    .filter_map(|block| Some((block.verified_at?, block.verify_confirmed_at?))),
+            ),
+            eth_confirmation_interval: mean_interval(confirmations),
+        }
+    }
+}
+
+/// Block sealing parameters of the server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SealingParams {
+    /// Capacity of the largest block.
+    max_block_chunks: u32,
+    /// Time until the block containing a fast withdrawal is sealed.
+    fast_seal_time: Duration,
+}
+
+impl SealingParams {
+    fn from_config(config: &ZkSyncConfig) -> Self {
+        let state_keeper = &config.chain.state_keeper;
+        Self {
+            max_block_chunks: config
+                .chain
+                .block_chunk_sizes()
+                .into_iter()
+                .max()
+                .unwrap_or_default() as u32,
+            fast_seal_time: state_keeper.miniblock_iteration_interval()
+                * state_keeper.fast_block_miniblock_iterations as u32,
+        }
+    }
+}
+
+/// Snapshot of the network state used to estimate the finality.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NetworkState {
+    rates: ProcessingRates,
+    sealing: SealingParams,
+    /// Amount of the block chunks taken by the transactions in the mempool.
+    mempool_chunks: u32,
+    /// Last block sealed and committed by the server.
+    last_committed_block: BlockNumber,
+    /// Last block the proof is ready for.
+    last_verified_block: BlockNumber,
+    eth_sender_backlog: u32,
+}
+
+impl NetworkState {
+    async fn load(storage: &mut StorageProcessor<'_>, sealing: SealingParams) -> QueryResult<Self> {
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        let timings = storage
+            .chain()
+            .stats_schema()
+            .load_block_timings(last_committed_block, TIMINGS_SAMPLE_SIZE)
+            .await?;
+        let eth_sender_backlog = storage
+            .chain()
+            .stats_schema()
+            .count_unconfirmed_eth_operations()
+            .await?;
+        let last_verified_block = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_block()
+            .await?;
+        // Transactions of the unknown types are not expected in the mempool.
+        let mempool_chunks = storage
+            .chain()
+            .stats_schema()
+            .count_mempool_txs_by_type()
+            .await?
+            .into_iter()
+            .filter_map(|(tx_type, count)| Some(tx_chunks(tx_type.parse().ok()?) * count))
+            .sum();
+
+        Ok(Self {
+            rates: ProcessingRates::new(&timings),
+            sealing,
+            mempool_chunks,
+            last_committed_block,
+            last_verified_block,
+            eth_sender_backlog,
+        })
+    }
+
+    fn load_info(&self) -> NetworkLoad {
+        NetworkLoad {
+            block_interval: self.rates.block_interval.map(|interval| interval.as_secs()),
+            prover_queue: self
+                .last_committed_block
+                .saturating_sub(*self.last_verified_block),
+            mempool_chunks: self.mempool_chunks,
+            eth_sender_backlog: self.eth_sender_backlog,
+        }
+    }
+
+    /// Expected time until the new operation sent to Ethereum is confirmed,
+    /// taking the operations sent before it into account.
+    fn eth_wait(&self, latency: Option<Duration>) -> Option<Duration> {
+        let backlog_wait = self
+            .rates
+            .eth_confirmation_interval
+            .map(|interval| interval * self.eth_sender_backlog)
+            .unwrap_or_default();
+        latency.map(|latency| cmp::max(latency, backlog_wait))
+    }
+
+    /// Expected time until the proof is ready for the block with the given amount of
+    /// blocks waiting for a proof up to and including it.
+    fn proof_wait(&self, proofs_ahead: u32) -> Option<Duration> {
+        let proof_interval = self.rates.proof_interval.unwrap_or_default();
+        Some(self.rates.proof_latency? + proof_interval * proofs_ahead.saturating_sub(1))
+    }
+
+    /// Amount of the blocks to be sealed until the transaction with the given amount of chunks
+    /// is included, up to and including its block. The mempool is processed in the FIFO order,
+    /// so all the transactions waiting in it are included first.
+    fn blocks_ahead(&self, tx_chunks: u32) -> u32 {
+        let max_block_chunks = cmp::max(self.sealing.max_block_chunks, 1);
+        let chunks = self.mempool_chunks + tx_chunks;
+        cmp::max((chunks + max_block_chunks - 1) / max_block_chunks, 1)
+    }
+
+    /// Expected time until the block with the new transaction is sealed.
+    fn seal_wait(&self, blocks_ahead: u32, fast_processing: bool) -> Option<Duration> {
+        let block_interval = self.rates.block_interval?;
+        if fast_processing {
+            // Block with a fast withdrawal is sealed without waiting for it to be filled.
+            let fast_seal_time = cmp::min(self.sealing.fast_seal_time, block_interval);
+            Some(block_interval * (blocks_ahead - 1) + fast_seal_time)
+        } else {
+            Some(block_interval * blocks_ahead)
+        }
+    }
+
+    /// Expected times until the transaction that is not sealed yet is committed and verified.
+    fn pending_tx_wait(&self, query: FinalityQuery) -> (Option<Duration>, Option<Duration>) {
+        let blocks_ahead = self.blocks_ahead(query.tx_type.map(tx_chunks).unwrap_or_default());
+        let seal_wait = self.seal_wait(blocks_ahead, query.fast_processing);
+
+        let time_to_commit = seal_wait
+            .and_then(|seal_wait| Some(seal_wait + self.eth_wait(self.rates.commit_latency)?));
+        let proofs_ahead = self.load_info().prover_queue + blocks_ahead;
+        let proof_ready =
+            seal_wait.and_then(|seal_wait| Some(seal_wait + self.proof_wait(proofs_ahead)?));
+        let time_to_verify = match (time_to_commit, proof_ready) {
+            (Some(time_to_commit), Some(proof_ready)) => self
+                .eth_wait(self.rates.verify_latency)
+                .map(|verify_wait| cmp::max(time_to_commit, proof_ready) + verify_wait),
+            _ => None,
+        };
+        (time_to_commit, time_to_verify)
+    }
+
+    /// Estimates the finality of a transaction of the given type sent right now.
+    fn estimate_new_tx(&self, query: FinalityQuery) -> FinalityEstimate {
+        let (time_to_commit, time_to_verify) = self.pending_tx_wait(query);
+        self.finality_estimate(None, time_to_commit, time_to_verify)
+    }
+
+    /// Estimates the finality of the transaction in the block with the given timeline,
+    /// or in the block that is not sealed yet.
+    ///
+    /// The position of the transaction in the mempool is unknown, so the pending transaction
+    /// is expected to be included after all the transactions in the mempool.
+    fn estimate(
+        &self,
+        block: Option<BlockNumber>,
+        timings: Option<&BlockTimings>,
+        now: DateTime<Utc>,
+    ) -> FinalityEstimate {
+        let (time_to_commit, time_to_verify) = match timings {
+            None => self.pending_tx_wait(FinalityQuery::default()),
+            Some(timings) => {
+                let time_to_commit = match timings.commit_confirmed_at {
+                    Some(_) => Some(Duration::default()),
+                    None => self
+                        .eth_wait(self.rates.commit_latency)
+                        .map(|wait| remaining(wait, timings.committed_at, now)),
+                };
+                let time_to_verify = match (timings.verified_at, timings.verify_confirmed_at) {
+                    (_, Some(_)) => Some(Duration::default()),
+                    (Some(verified_at), None) => self
+                        .eth_wait(self.rates.verify_latency)
+                        .map(|wait| remaining(wait, verified_at, now)),
+                    (None, None) => {
+                        let proofs_ahead =
+                            (timings.block_number as u32).saturating_sub(*self.last_verified_block);
+                        let proof_ready = self
+                            .proof_wait(proofs_ahead)
+                            .map(|wait| remaining(wait, timings.committed_at, now));
+                        match (time_to_commit, proof_ready) {
+                            (Some(time_to_commit), Some(proof_ready)) => {
+                                self.eth_wait(self.rates.verify_latency).map(|verify_wait| {
+                                    cmp::max(time_to_commit, proof_ready) + verify_wait
+                                })
+                            }
+                            _ => None,
+                        }
+                    }
+                };
+                (time_to_commit, time_to_verify)
+            }
+        };
+
+        self.finality_estimate(block, time_to_commit, time_to_verify)
+    }
+
+    fn finality_estimate(
+        &self,
+        block: Option<BlockNumber>,
+        time_to_commit: Option<Duration>,
+        time_to_verify: Option<Duration>,
+    ) -> FinalityEstimate {
+        FinalityEstimate {
+            block,
+            time_to_commit: time_to_commit.map(|time| time.as_secs()),
+            time_to_verify: time_to_verify.map(|time| time.as_secs()),
+            load: self.load_info(),
+        }
+    }
+}
+
+/// Shared data between `api/v1/finality` endpoints.
+#[derive(Debug, Clone)]
+struct ApiFinalityData {
+    pool: ConnectionPool,
+    sealing: SealingParams,
+}
+
+impl ApiFinalityData {
+    fn new(pool: ConnectionPool, sealing: SealingParams) -> Self {
+        Self { pool, sealing }
+    }
+
+    async fn finality_estimate(&self, query: FinalityQuery) -> QueryResult<FinalityEstimate> {
+        let mut storage = self.pool.access_storage().await?;
+        let state = NetworkState::load(&mut storage, self.sealing).await?;
+
+        Ok(state.estimate_new_tx(query))
+    }
+
+    async fn tx_finality_estimate(&self, tx_hash: TxHash) -> QueryResult<Option<FinalityEstimate>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let block = match storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipt(tx_hash.as_ref())
+            .await?
+        {
+            Some(receipt) if !receipt.success => return Ok(None),
+            Some(receipt) => Some(BlockNumber(receipt.block_number as u32)),
+            None => {
+                let tx_in_mempool = storage
+                    .chain()
+                    .mempool_schema()
+                    .contains_tx(tx_hash)
+                    .await?;
+                if !tx_in_mempool {
+                    return Ok(None);
+                }
+                None
+            }
+        };
+
+        let state = NetworkState::load(&mut storage, self.sealing).await?;
+        // Timings are known only for the sealed blocks.
+        let timings = match block {
+            Some(block) => storage
+                .chain()
+                .stats_schema()
+                .load_block_timings(block, 1)
+                .await?
+                .into_iter()
+                .find(|timings| timings.block_number == i64::from(*block)),
+            None => None,
+        };
+
+        Ok(Some(state.estimate(block, timings.as_ref(), Utc::now())))
+    }
+}
+
+// Server implementation
+
+async fn finality_estimate(
+    data: web::Data<ApiFinalityData>,
+    web::Query(query): web::Query<FinalityQuery>,
+) -> JsonResult<FinalityEstimate> {
+    if query.fast_processing && query.tx_type != Some(TokenTxType::Withdraw) {
+        return Err(ApiError::bad_request("Incorrect transaction type")
+            .detail("Fast processing is available only for withdrawals"));
+    }
+
+    let estimate = data
+        .finality_estimate(query)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(web::Json(estimate))
+}
+
+async fn tx_finality_estimate(
+    data: web::Data<ApiFinalityData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> JsonResult<Option<FinalityEstimate>> {
+    let estimate = data
+        .tx_finality_estimate(tx_hash)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(web::Json(estimate))
+}
+
+pub fn api_scope(config: &ZkSyncConfig, pool: ConnectionPool) -> Scope {
+    let data = ApiFinalityData::new(pool, SealingParams::from_config(config));
+
+    web::scope("finality")
+        .data(data)
+        .route("", web::get().to(finality_estimate))
+        .route("{tx_hash}", web::get().to(tx_finality_estimate))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::{super::test_utils::TestServerConfig, *};
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp(1_600_000_000 + secs, 0)
+    }
+
+    /// Blocks sealed every 60 seconds, committed in 30 seconds, proven in 300 seconds
+    /// and verified in 30 seconds.
+    fn timings(blocks: u32, verified_blocks: u32) -> Vec<BlockTimings> {
+        (1..=blocks)
+            .rev()
+            .map(|block| {
+                let committed_at = i64::from(block) * 60;
+                let verified = block <= verified_blocks;
+                BlockTimings {
+                    block_number: i64::from(block),
+                    committed_at: at(committed_at),
+                    commit_confirmed_at: Some(at(committed_at + 30)),
+                    verified_at: if verified {
+                        Some(at(committed_at + 300))
+                    } else {
+                        None
+                    },
+                    verify_confirmed_at: if verified {
+                        Some(at(committed_at + 330))
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn state(timings: &[BlockTimings], eth_sender_backlog: u32) -> NetworkState {
+        NetworkState {
+            rates: ProcessingRates::new(timings),
+            last_committed_block: BlockNumber(timings[0].block_number as u32),
+            last_verified_block: BlockNumber(
+                timings
+                    .iter()
+                    .find(|block| block.verified_at.is_some())
+                    .map(|block| block.block_number as u32)
+                    .unwrap_or(0),
+            ),
+            eth_sender_backlog,
+            sealing: SealingParams {
+                max_block_chunks: 50,
+                fast_seal_time: Duration::from_secs(10),
+            },
+            mempool_chunks: 0,
+        }
+    }
+
+    #[test]
+    fn processing_rates() {
+        let rates = ProcessingRates::new(&timings(10, 5));
+        assert_eq!(rates.block_interval, Some(Duration::from_secs(60)));
+        assert_eq!(rates.commit_latency, Some(Duration::from_secs(30)));
+        assert_eq!(rates.proof_latency, Some(Duration::from_secs(300)));
+        assert_eq!(rates.proof_interval, Some(Duration::from_secs(60)));
+        assert_eq!(rates.verify_latency, Some(Duration::from_secs(30)));
+
+        // Not enough blocks to measure the rates.
+        let rates = ProcessingRates::new(&timings(1, 0));
+        assert_eq!(rates.block_interval, None);
+        assert_eq!(rates.proof_latency, None);
+    }
+
+    #[test]
+    fn estimate_new_tx() {
+        let timings = timings(10, 5);
+        let now = at(10 * 60 + 10);
+
+        let estimate = state(&timings, 0).estimate(None, None, now);
+        assert_eq!(estimate.load.prover_queue, 5);
+        assert_eq!(estimate.load.block_interval, Some(60));
+        // Block interval and the commit latency.
+        assert_eq!(estimate.time_to_commit, Some(90));
+        // Block interval, the proofs of 6 blocks and the verify latency.
+        assert_eq!(estimate.time_to_verify, Some(60 + 300 + 5 * 60 + 30));
+
+        // Ethereum sender backlog delays the confirmations.
+        let congested = state(&timings, 10).estimate(None, None, now);
+        assert!(congested.time_to_commit > estimate.time_to_commit);
+        assert!(congested.time_to_verify > estimate.time_to_verify);
+
+        // There is no estimate without the recent blocks.
+        let estimate = state(&timings[9..], 0).estimate(None, None, now);
+        assert_eq!(estimate.time_to_commit, None);
+        assert_eq!(estimate.time_to_verify, None);
+    }
+
+    #[test]
+    fn estimate_new_tx_by_type() {
+        let timings = timings(10, 5);
+        let mut state = state(&timings, 0);
+        state.mempool_chunks = 96;
+
+        // Withdrawal is included in the third block after the mempool transactions.
+        let estimate = state.estimate_new_tx(FinalityQuery {
+            tx_type: Some(TokenTxType::Withdraw),
+            fast_processing: false,
+        });
+        assert_eq!(estimate.load.mempool_chunks, 96);
+        assert_eq!(estimate.time_to_commit, Some(3 * 60 + 30));
+        // Proofs of the 5 blocks in the prover queue and the 3 new blocks.
+        assert_eq!(estimate.time_to_verify, Some(3 * 60 + 300 + 7 * 60 + 30));
+
+        // Transfer still fits into the second block.
+        let estimate = state.estimate_new_tx(FinalityQuery {
+            tx_type: Some(TokenTxType::Transfer),
+            fast_processing: false,
+        });
+        assert_eq!(estimate.time_to_commit, Some(2 * 60 + 30));
+
+        // Block with the fast withdrawal is sealed right away.
+        let estimate = state.estimate_new_tx(FinalityQuery {
+            tx_type: Some(TokenTxType::Withdraw),
+            fast_processing: true,
+        });
+        assert_eq!(estimate.time_to_commit, Some(2 * 60 + 10 + 30));
+
+        // Transaction of the unknown type is expected to be included after the mempool.
+        let estimate = state.estimate_new_tx(FinalityQuery::default());
+        assert_eq!(estimate.time_to_commit, Some(2 * 60 + 30));
+    }
+
+    #[test]
+    fn estimate_sealed_block() {
+        let timings = timings(10, 5);
+        let state = state(&timings, 0);
+
+        // Final block.
+        let estimate = state.estimate(Some(BlockNumber(5)), Some(&timings[5]), at(700));
+        assert_eq!(estimate.time_to_commit, Some(0));
+        assert_eq!(estimate.time_to_verify, Some(0));
+
+        // Committed block waiting for the proofs of the preceding block.
+        let estimate = state.estimate(Some(BlockNumber(7)), Some(&timings[3]), at(7 * 60 + 100));
+        assert_eq!(estimate.time_to_commit, Some(0));
+        // Proof of 2 blocks since the commit and the verify latency.
+        assert_eq!(estimate.time_to_verify, Some(300 + 60 - 100 + 30));
+
+        // Block waiting for the commit confirmation.
+        let mut block = timings[0].clone();
+        block.commit_confirmed_at = None;
+        let estimate = state.estimate(Some(BlockNumber(10)), Some(&block), at(10 * 60 + 10));
+        assert_eq!(estimate.time_to_commit, Some(20));
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn finality_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let (client, server) = cfg.start_server(|cfg| api_scope(&cfg.config, cfg.pool.clone()));
+
+        let estimate = client.finality_estimate().await?;
+        assert_eq!(estimate.block, None);
+
+        let estimate = client
+            .tx_type_finality_estimate(TokenTxType::Withdraw, true)
+            .await?;
+        assert_eq!(estimate.block, None);
+        // Fast processing is available only for withdrawals.
+        assert!(client
+            .tx_type_finality_estimate(TokenTxType::Transfer, true)
+            .await
+            .is_err());
+
+        // There is no estimate for the unknown transaction.
+        assert_eq!(client.tx_finality_estimate(TxHash::default()).await?, None);
+
+        server.stop().await;
+        Ok(())
+    }
+}
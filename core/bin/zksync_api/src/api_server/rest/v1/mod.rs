@@ -20,15 +20,18 @@ use crate::api_server::tx_sender::TxSender;
 pub use self::error::{Error, ErrorBody};
 
 pub(crate) mod accounts;
-mod blocks;
+pub(super) mod blocks;
 mod config;
 mod error;
-mod operations;
+mod eth_sender;
+mod finality;
+pub(super) mod operations;
 mod search;
+mod spec;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 mod tokens;
-mod transactions;
+pub(super) mod transactions;
 
 type JsonResult<T> = std::result::Result<web::Json<T>, Error>;
 
@@ -39,12 +42,19 @@ pub(crate) fn api_scope(tx_sender: TxSender, zk_config: &ZkSyncConfig) -> Scope
             zk_config,
             tx_sender.tokens.clone(),
             tx_sender.core_api_client.clone(),
+            tx_sender.ens_resolver.clone(),
         ))
         .service(config::api_scope(&zk_config))
         .service(blocks::api_scope(&zk_config, tx_sender.pool.clone()))
+        .service(eth_sender::api_scope(tx_sender.pool.clone()))
+        .service(finality::api_scope(&zk_config, tx_sender.pool.clone()))
         .service(transactions::api_scope(tx_sender.clone()))
-        .service(operations::api_scope(tx_sender.pool.clone()))
+        .service(operations::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.core_api_client.clone(),
+        ))
         .service(search::api_scope(tx_sender.pool.clone()))
+        .route("spec.json", spec::api_route())
         .service(tokens::api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens,
@@ -10,26 +10,50 @@ use actix_web::{
 
 // Workspace uses
 use zksync_api_client::rest::v1::{
-    PriorityOpData, PriorityOpQuery, PriorityOpQueryError, PriorityOpReceipt,
+    PriorityOpData, PriorityOpQuery, PriorityOpQueryError, PriorityOpQueueEntry, PriorityOpReceipt,
 };
 use zksync_storage::{
     chain::operations::records::StoredExecutedPriorityOperation, ConnectionPool, QueryResult,
     StorageProcessor,
 };
-use zksync_types::{BlockNumber, H256};
+use zksync_types::{Address, BlockNumber, H256};
 
 // Local uses
 use super::{transactions::Receipt, Error as ApiError, JsonResult};
+use crate::core_api_client::CoreApiClient;
 
-/// Shared data between `api/v1/operations` endpoints.
+/// Shared data between `api/v1/operations` and `api/v2/operations` endpoints.
 #[derive(Debug, Clone)]
-struct ApiOperationsData {
+pub struct ApiOperationsData {
     pool: ConnectionPool,
+    core_api_client: CoreApiClient,
 }
 
 impl ApiOperationsData {
-    pub fn new(pool: ConnectionPool) -> Self {
-        Self { pool }
+    pub fn new(pool: ConnectionPool, core_api_client: CoreApiClient) -> Self {
+        Self {
+            pool,
+            core_api_client,
+        }
+    }
+
+    pub async fn priority_op_queue(
+        &self,
+        address: Option<Address>,
+    ) -> anyhow::Result<Vec<PriorityOpQueueEntry>> {
+        let ops = self.core_api_client.get_priority_queue(address).await?;
+        Ok(ops.into_iter().map(PriorityOpQueueEntry::from).collect())
+    }
+
+    pub async fn incoming_priority_ops(
+        &self,
+        address: Address,
+    ) -> anyhow::Result<Vec<PriorityOpQueueEntry>> {
+        let ops = self
+            .core_api_client
+            .get_incoming_priority_ops(address)
+            .await?;
+        Ok(ops.into_iter().map(PriorityOpQueueEntry::from).collect())
     }
 
     pub async fn priority_op_data(
@@ -158,11 +182,49 @@ async fn priority_op_data(
     Ok(Json(data))
 }
 
-pub fn api_scope(pool: ConnectionPool) -> Scope {
-    let data = ApiOperationsData::new(pool);
+async fn priority_op_queue(
+    data: web::Data<ApiOperationsData>,
+) -> JsonResult<Vec<PriorityOpQueueEntry>> {
+    let queue = data
+        .priority_op_queue(None)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(queue))
+}
+
+async fn priority_op_queue_for(
+    data: web::Data<ApiOperationsData>,
+    web::Path(address): web::Path<Address>,
+) -> JsonResult<Vec<PriorityOpQueueEntry>> {
+    let queue = data
+        .priority_op_queue(Some(address))
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(queue))
+}
+
+async fn incoming_priority_ops(
+    data: web::Data<ApiOperationsData>,
+    web::Path(address): web::Path<Address>,
+) -> JsonResult<Vec<PriorityOpQueueEntry>> {
+    let queue = data
+        .incoming_priority_ops(address)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(queue))
+}
+
+pub fn api_scope(pool: ConnectionPool, core_api_client: CoreApiClient) -> Scope {
+    let data = ApiOperationsData::new(pool, core_api_client);
 
     web::scope("operations")
         .data(data)
+        .route("queue", web::get().to(priority_op_queue))
+        .route("queue/{address}", web::get().to(priority_op_queue_for))
+        .route(
+            "queue/{address}/incoming",
+            web::get().to(incoming_priority_ops),
+        )
         .route("{id}", web::get().to(priority_op))
         .route("{id}/data", web::get().to(priority_op_data))
 }
@@ -188,7 +250,12 @@ mod tests {
         let cfg = TestServerConfig::default();
         cfg.fill_database().await?;
 
-        let (client, server) = cfg.start_server(|cfg| api_scope(cfg.pool.clone()));
+        let (client, server) = cfg.start_server(|cfg| {
+            api_scope(
+                cfg.pool.clone(),
+                CoreApiClient::from_config(&cfg.config.api.private),
+            )
+        });
 
         // Check verified priority operation.
 
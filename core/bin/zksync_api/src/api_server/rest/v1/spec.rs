@@ -0,0 +1,703 @@
+//! OpenAPI 3.0 specification of the API v1, served at `api/v1/spec.json`.
+//!
+//! The specification covers the endpoints the client SDKs are generated for: transactions
+//! submission, fee requests, account state and block queries. Schemas of the data transfer
+//! objects and the query parameters are checked against the serialized form of the types
+//! used by the handlers in the tests, so they don't get out of sync with the actual types.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, HttpResponse};
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+
+// Workspace uses
+use zksync_api_client::rest::v1::API_KEY_HEADER;
+
+// Local uses
+
+/// Path prefix of the API v1 endpoints.
+const BASE_PATH: &str = "/api/v1";
+
+static SPEC: Lazy<Value> = Lazy::new(spec);
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn nullable_ref(name: &str) -> Value {
+    json!({ "nullable": true, "allOf": [schema_ref(name)] })
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn path_param(name: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": { "type": "string" },
+    })
+}
+
+fn query_param(name: &str, description: &str, schema: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": schema,
+    })
+}
+
+fn header_param(name: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "header",
+        "required": false,
+        "description": description,
+        "schema": { "type": "string" },
+    })
+}
+
+/// Describes the API operation, all the operations share the same error response.
+fn operation(
+    operation_id: &str,
+    summary: &str,
+    params: Vec<Value>,
+    body: Option<Value>,
+    response: Value,
+) -> Value {
+    let mut operation = json!({
+        "operationId": operation_id,
+        "summary": summary,
+        "responses": {
+            "200": {
+                "description": "Successful response.",
+                "content": { "application/json": { "schema": response } },
+            },
+            "default": {
+                "description": "Error response.",
+                "content": { "application/json": { "schema": schema_ref("ErrorBody") } },
+            },
+        },
+    });
+    if !params.is_empty() {
+        operation["parameters"] = Value::Array(params);
+    }
+    if let Some(body) = body {
+        operation["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": body } },
+        });
+    }
+    operation
+}
+
+fn paths() -> Value {
+    let endpoints = vec![
+        (
+            "post",
+            "/transactions/submit",
+            operation(
+                "submitTx",
+                "Submits the transaction to the mempool, returns its hash.",
+                vec![
+                    query_param(
+                        "fastProcessing",
+                        "Whether the withdrawal should be processed without waiting for the block to be filled.",
+                        json!({ "type": "boolean" }),
+                    ),
+                    header_param(
+                        API_KEY_HEADER,
+                        "API key of the trusted integrator, may be required for the fast processing.",
+                    ),
+                ],
+                Some(schema_ref("IncomingTx")),
+                schema_ref("TxHash"),
+            ),
+        ),
+        (
+            "post",
+            "/transactions/submit/batch",
+            operation(
+                "submitTxsBatch",
+                "Submits the batch of transactions to the mempool, returns their hashes.",
+                vec![],
+                Some(schema_ref("IncomingTxBatch")),
+                array_of(schema_ref("TxHash")),
+            ),
+        ),
+        (
+            "post",
+            "/transactions/fee",
+            operation(
+                "getTxFee",
+                "Returns the fee of the transaction.",
+                vec![],
+                Some(schema_ref("IncomingTxForFee")),
+                schema_ref("Fee"),
+            ),
+        ),
+        (
+            "post",
+            "/transactions/fee/batch",
+            operation(
+                "getTxsBatchFee",
+                "Returns the total fee of the transactions batch.",
+                vec![],
+                Some(schema_ref("IncomingTxBatchForFee")),
+                schema_ref("BatchFee"),
+            ),
+        ),
+        (
+            "get",
+            "/accounts/{id}",
+            operation(
+                "accountInfo",
+                "Returns the state of the account, or null if the account doesn't exist.",
+                vec![path_param("id", "Account ID, address or ENS name.")],
+                None,
+                nullable_ref("AccountInfo"),
+            ),
+        ),
+        (
+            "get",
+            "/blocks",
+            operation(
+                "blocksRange",
+                "Returns the range of the blocks in descending order.",
+                vec![
+                    query_param(
+                        "before",
+                        "Return the blocks before the given one (not including it).",
+                        json!({ "type": "integer", "minimum": 0 }),
+                    ),
+                    query_param(
+                        "after",
+                        "Return the blocks after the given one (not including it).",
+                        json!({ "type": "integer", "minimum": 0 }),
+                    ),
+                    query_param(
+                        "limit",
+                        "Maximum amount of the blocks.",
+                        json!({ "type": "integer", "minimum": 1, "maximum": super::MAX_LIMIT }),
+                    ),
+                ],
+                None,
+                array_of(schema_ref("BlockInfo")),
+            ),
+        ),
+        (
+            "get",
+            "/blocks/{id}",
+            operation(
+                "blockById",
+                "Returns the block, or null if the block doesn't exist.",
+                vec![path_param("id", "Block number.")],
+                None,
+                nullable_ref("BlockInfo"),
+            ),
+        ),
+        (
+            "get",
+            "/blocks/{id}/transactions",
+            operation(
+                "blockTransactions",
+                "Returns the transactions of the block.",
+                vec![path_param("id", "Block number.")],
+                None,
+                array_of(schema_ref("TransactionInfo")),
+            ),
+        ),
+        (
+            "get",
+            "/tokens",
+            operation(
+                "tokens",
+                "Returns all the tokens of the network.",
+                vec![],
+                None,
+                array_of(schema_ref("Token")),
+            ),
+        ),
+        (
+            "get",
+            "/tokens/{id}",
+            operation(
+                "tokenById",
+                "Returns the token, or null if the token doesn't exist.",
+                vec![path_param("id", "Token ID, address or symbol.")],
+                None,
+                nullable_ref("Token"),
+            ),
+        ),
+    ];
+
+    let mut paths = Map::new();
+    for (method, path, operation) in endpoints {
+        let path = paths
+            .entry(format!("{}{}", BASE_PATH, path))
+            .or_insert_with(|| json!({}));
+        path[method] = operation;
+    }
+    Value::Object(paths)
+}
+
+fn schemas() -> Value {
+    let big_uint = json!({ "type": "string", "pattern": "^[0-9]+$" });
+    let address = json!({ "type": "string", "pattern": "^0x[0-9a-fA-F]{40}$" });
+    let tx_fee_type = json!({
+        "oneOf": [
+            { "type": "string", "enum": ["Withdraw", "FastWithdraw", "Transfer"] },
+            {
+                "type": "object",
+                "required": ["ChangePubKey"],
+                "properties": {
+                    "ChangePubKey": {
+                        "type": "object",
+                        "required": ["onchainPubkeyAuth"],
+                        "properties": { "onchainPubkeyAuth": { "type": "boolean" } },
+                    },
+                },
+            },
+        ],
+    });
+    let output_fee_type = json!({
+        "oneOf": [
+            { "type": "string", "enum": ["Withdraw", "FastWithdraw", "Transfer", "TransferToNew"] },
+            tx_fee_type["oneOf"][1].clone(),
+        ],
+    });
+
+    let mut schemas = Map::new();
+    let mut add = |name: &str, schema: Value| {
+        schemas.insert(name.to_owned(), schema);
+    };
+    add(
+        "ErrorBody",
+        json!({
+            "type": "object",
+            "required": ["retryable"],
+            "properties": {
+                "type": { "type": "string" },
+                "title": { "type": "string" },
+                "detail": { "type": "string" },
+                "location": { "type": "string" },
+                "code": { "type": "integer", "description": "Stable numeric error code, the transaction submission errors keep their legacy codes." },
+                "codeName": { "type": "string", "description": "Stable string identifier of the error code." },
+                "retryable": { "type": "boolean" },
+                "retryAfter": { "type": "integer", "description": "Suggested delay in seconds before retrying the request." },
+                "data": { "type": "object", "description": "Structured details of the error, depending on the error code." },
+            },
+        }),
+    );
+    add("Address", address);
+    add("BigUint", big_uint);
+    add(
+        "TxHash",
+        json!({ "type": "string", "pattern": "^sync-tx:[0-9a-fA-F]{64}$" }),
+    );
+    add(
+        "TokenLike",
+        json!({
+            "description": "Token ID, address or symbol.",
+            "oneOf": [{ "type": "integer" }, { "type": "string" }],
+        }),
+    );
+    add(
+        "ZkSyncTx",
+        json!({
+            "type": "object",
+            "description": "Signed zkSync transaction, the fields depend on the transaction type.",
+            "required": ["type"],
+            "properties": {
+                "type": { "type": "string", "enum": ["Transfer", "Withdraw", "ChangePubKey", "ForcedExit"] },
+            },
+            "additionalProperties": true,
+        }),
+    );
+    add(
+        "TxEthSignature",
+        json!({
+            "type": "object",
+            "required": ["type", "signature"],
+            "properties": {
+                "type": { "type": "string", "enum": ["EthereumSignature", "EIP1271Signature"] },
+                "signature": { "type": "string" },
+            },
+        }),
+    );
+    add(
+        "IncomingTx",
+        json!({
+            "type": "object",
+            "required": ["tx"],
+            "properties": {
+                "tx": schema_ref("ZkSyncTx"),
+                "signature": nullable_ref("TxEthSignature"),
+            },
+        }),
+    );
+    add(
+        "IncomingTxBatch",
+        json!({
+            "type": "object",
+            "required": ["txs"],
+            "properties": {
+                "txs": array_of(schema_ref("ZkSyncTx")),
+                "signature": nullable_ref("TxEthSignature"),
+            },
+        }),
+    );
+    add("TxFeeType", tx_fee_type);
+    add(
+        "IncomingTxForFee",
+        json!({
+            "type": "object",
+            "required": ["txType", "address", "tokenLike"],
+            "properties": {
+                "txType": schema_ref("TxFeeType"),
+                "address": schema_ref("Address"),
+                "tokenLike": schema_ref("TokenLike"),
+            },
+        }),
+    );
+    add(
+        "IncomingTxBatchForFee",
+        json!({
+            "type": "object",
+            "required": ["txTypes", "addresses", "tokenLike"],
+            "properties": {
+                "txTypes": array_of(schema_ref("TxFeeType")),
+                "addresses": array_of(schema_ref("Address")),
+                "tokenLike": schema_ref("TokenLike"),
+            },
+        }),
+    );
+    add(
+        "Fee",
+        json!({
+            "type": "object",
+            "required": ["feeType", "gasTxAmount", "gasPriceWei", "gasFee", "zkpFee", "totalFee"],
+            "properties": {
+                "feeType": output_fee_type,
+                "gasTxAmount": schema_ref("BigUint"),
+                "gasPriceWei": schema_ref("BigUint"),
+                "gasFee": schema_ref("BigUint"),
+                "zkpFee": schema_ref("BigUint"),
+                "totalFee": schema_ref("BigUint"),
+            },
+        }),
+    );
+    add(
+        "BatchFee",
+        json!({
+            "type": "object",
+            "required": ["totalFee"],
+            "properties": { "totalFee": schema_ref("BigUint") },
+        }),
+    );
+    add(
+        "AccountState",
+        json!({
+            "type": "object",
+            "required": ["balances", "nonce", "pubKeyHash"],
+            "properties": {
+                "balances": {
+                    "type": "object",
+                    "description": "Balances by the token symbols.",
+                    "additionalProperties": schema_ref("BigUint"),
+                },
+                "nonce": { "type": "integer" },
+                "pubKeyHash": { "type": "string", "pattern": "^sync:[0-9a-fA-F]{40}$" },
+            },
+        }),
+    );
+    add(
+        "DepositingFunds",
+        json!({
+            "type": "object",
+            "required": ["amount", "expectedAcceptBlock"],
+            "properties": {
+                "amount": schema_ref("BigUint"),
+                "expectedAcceptBlock": { "type": "integer" },
+            },
+        }),
+    );
+    add(
+        "AccountInfo",
+        json!({
+            "type": "object",
+            "required": ["address", "id", "committed", "verified", "depositing"],
+            "properties": {
+                "address": schema_ref("Address"),
+                "id": { "type": "integer" },
+                "committed": schema_ref("AccountState"),
+                "verified": schema_ref("AccountState"),
+                "depositing": {
+                    "type": "object",
+                    "required": ["balances"],
+                    "properties": {
+                        "balances": {
+                            "type": "object",
+                            "description": "Depositing funds by the token symbols.",
+                            "additionalProperties": schema_ref("DepositingFunds"),
+                        },
+                    },
+                },
+            },
+        }),
+    );
+    add(
+        "BlockInfo",
+        json!({
+            "type": "object",
+            "required": ["blockNumber", "newStateRoot", "blockSize", "commitTxHash", "verifyTxHash", "committedAt", "verifiedAt"],
+            "properties": {
+                "blockNumber": { "type": "integer" },
+                "newStateRoot": { "type": "string" },
+                "blockSize": { "type": "integer" },
+                "commitTxHash": nullable_ref("TxHash"),
+                "verifyTxHash": nullable_ref("TxHash"),
+                "committedAt": { "type": "string", "format": "date-time" },
+                "verifiedAt": { "type": "string", "format": "date-time", "nullable": true },
+            },
+        }),
+    );
+    add(
+        "TransactionInfo",
+        json!({
+            "type": "object",
+            "required": ["txHash", "blockNumber", "op", "success", "failReason", "createdAt"],
+            "properties": {
+                "txHash": schema_ref("TxHash"),
+                "blockNumber": { "type": "integer" },
+                "op": { "type": "object", "additionalProperties": true },
+                "success": { "type": "boolean", "nullable": true },
+                "failReason": { "type": "string", "nullable": true },
+                "createdAt": { "type": "string", "format": "date-time" },
+            },
+        }),
+    );
+    add(
+        "Token",
+        json!({
+            "type": "object",
+            "required": ["id", "address", "symbol", "decimals"],
+            "properties": {
+                "id": { "type": "integer" },
+                "address": schema_ref("Address"),
+                "symbol": { "type": "string" },
+                "decimals": { "type": "integer" },
+            },
+        }),
+    );
+
+    Value::Object(schemas)
+}
+
+/// Builds the OpenAPI specification of the API v1.
+pub fn spec() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "zkSync REST API",
+            "version": "1.0",
+        },
+        "paths": paths(),
+        "components": { "schemas": schemas() },
+    })
+}
+
+async fn spec_json() -> HttpResponse {
+    HttpResponse::Ok().json(&*SPEC)
+}
+
+pub fn api_route() -> actix_web::Route {
+    web::get().to(spec_json)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use num::BigUint;
+    use serde::Serialize;
+
+    use zksync_api_client::rest::v1::{
+        AccountInfo, BlockInfo, ErrorBody, FastProcessingQuery, IncomingTxForFee, PaginationQuery,
+        TransactionInfo,
+    };
+    use zksync_crypto::{ff::Field, Fr};
+    use zksync_types::{
+        AccountId, Address, BatchFee, BlockNumber, Token, TokenId, TokenLike, TxFeeTypes,
+    };
+
+    use super::*;
+
+    fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    refs.push(reference.clone());
+                }
+                map.values().for_each(|value| collect_refs(value, refs));
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    /// Checks that the serialized object has all the required properties of the schema,
+    /// and doesn't have the properties absent in the schema.
+    fn assert_matches_schema(name: &str, object: impl Serialize) {
+        let spec = spec();
+        let schema = &spec["components"]["schemas"][name];
+        let object = serde_json::to_value(object).unwrap();
+        let object = object.as_object().unwrap();
+
+        let properties = schema["properties"].as_object().unwrap();
+        for key in object.keys() {
+            assert!(
+                properties.contains_key(key),
+                "{}: property {} is missing in the schema",
+                name,
+                key
+            );
+        }
+        for key in schema["required"].as_array().unwrap() {
+            assert!(
+                object.contains_key(key.as_str().unwrap()),
+                "{}: required property {} is missing in the object",
+                name,
+                key
+            );
+        }
+    }
+
+    /// Checks that the query parameters of the operation are the same as the fields
+    /// of the query type the handler deserializes them into.
+    fn assert_query_matches(operation_id: &str, query: impl Serialize) {
+        let spec = spec();
+        let operation = spec["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .flat_map(|path| path.as_object().unwrap().values())
+            .find(|operation| operation["operationId"] == operation_id)
+            .unwrap_or_else(|| panic!("Operation {} is missing in the spec", operation_id));
+
+        let mut documented: Vec<_> = operation["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|param| param["in"] == "query")
+            .map(|param| param["name"].as_str().unwrap().to_owned())
+            .collect();
+        documented.sort();
+        // Optional fields are serialized as `null`, so all the fields are present.
+        let query = serde_json::to_value(query).unwrap();
+        let mut fields: Vec<_> = query.as_object().unwrap().keys().cloned().collect();
+        fields.sort();
+
+        assert_eq!(
+            documented, fields,
+            "{}: query parameters mismatch",
+            operation_id
+        );
+    }
+
+    #[test]
+    fn schema_references_are_resolved() {
+        let spec = spec();
+        let mut refs = Vec::new();
+        collect_refs(&spec, &mut refs);
+        assert!(!refs.is_empty());
+
+        for reference in refs {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            assert!(
+                spec["components"]["schemas"].get(name).is_some(),
+                "Unresolved reference {}",
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn schemas_match_types() {
+        assert_matches_schema(
+            "ErrorBody",
+            ErrorBody {
+                title: "title".to_owned(),
+                ..ErrorBody::default()
+            },
+        );
+        assert_matches_schema(
+            "BatchFee",
+            BatchFee {
+                total_fee: BigUint::from(1_u32),
+            },
+        );
+        assert_matches_schema(
+            "IncomingTxForFee",
+            IncomingTxForFee {
+                tx_type: TxFeeTypes::Transfer,
+                address: Address::default(),
+                token_like: TokenLike::Id(TokenId(0)),
+            },
+        );
+        assert_matches_schema(
+            "AccountInfo",
+            AccountInfo {
+                address: Address::default(),
+                id: AccountId(1),
+                committed: Default::default(),
+                verified: Default::default(),
+                depositing: Default::default(),
+            },
+        );
+        assert_matches_schema(
+            "BlockInfo",
+            BlockInfo {
+                block_number: BlockNumber(1),
+                new_state_root: Fr::zero(),
+                block_size: 10,
+                commit_tx_hash: None,
+                verify_tx_hash: None,
+                committed_at: Utc::now(),
+                verified_at: None,
+            },
+        );
+        assert_matches_schema(
+            "TransactionInfo",
+            TransactionInfo {
+                tx_hash: Default::default(),
+                block_number: BlockNumber(1),
+                op: json!({}),
+                success: None,
+                fail_reason: None,
+                created_at: Utc::now(),
+            },
+        );
+        assert_matches_schema(
+            "Token",
+            Token::new(TokenId(0), Address::default(), "ETH", 18),
+        );
+    }
+
+    #[test]
+    fn query_params_match_types() {
+        assert_query_matches(
+            "submitTx",
+            FastProcessingQuery {
+                fast_processing: None,
+            },
+        );
+        assert_query_matches("blocksRange", PaginationQuery::default());
+    }
+}
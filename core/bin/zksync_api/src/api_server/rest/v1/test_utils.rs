@@ -6,12 +6,15 @@ use std::str::FromStr;
 // External uses
 use actix_web::{web, App, Scope};
 use chrono::Utc;
+use futures::{channel::mpsc, StreamExt};
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
+use vlog::Traced;
 
 // Workspace uses
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::{
     chain::operations::records::NewExecutedPriorityOperation,
     test_data::{
@@ -25,13 +28,21 @@ use zksync_types::{
     ethereum::OperationType,
     helpers::{apply_updates, closest_packable_fee_amount, closest_packable_token_amount},
     operations::{ChangePubKeyOp, TransferToNewOp},
+    tx::TxFailureCode,
     AccountId, AccountMap, Action, Address, BlockNumber, Deposit, DepositOp, ExecutedOperations,
     ExecutedPriorityOp, ExecutedTx, FullExit, FullExitOp, Nonce, PriorityOp, Token, TokenId,
-    Transfer, TransferOp, ZkSyncOp, ZkSyncTx, H256,
+    TokenLike, Transfer, TransferOp, ZkSyncOp, ZkSyncTx, H256,
 };
 
 // Local uses
 use super::Client;
+use crate::{
+    api_server::tx_sender::TxSender,
+    core_api_client::CoreApiClient,
+    fee_ticker::TickerRequest,
+    signature_checker::{VerifiedTx, VerifyTxSignatureRequest},
+    utils::ens_resolver::EnsResolver,
+};
 
 /// Serial ID of the verified priority operation.
 pub const VERIFIED_OP_SERIAL_ID: u64 = 10;
@@ -64,6 +75,27 @@ pub struct TestTransactions {
 }
 
 impl TestServerConfig {
+    /// ENS resolver with the test configuration, names are only resolved if the registry is set.
+    pub fn ens_resolver(&self) -> EnsResolver {
+        EnsResolver::new(
+            EthereumGateway::from_config(&self.config),
+            &self.config.api.ens,
+            self.config.api.common.caches_size,
+        )
+    }
+
+    /// Transactions sender with the dummy fee ticker and signature verifier, sending
+    /// the transactions to the configured core server.
+    pub fn tx_sender(&self) -> TxSender {
+        TxSender::with_client(
+            CoreApiClient::from_config(&self.config.api.private),
+            self.pool.clone(),
+            dummy_sign_verifier(),
+            dummy_fee_ticker(),
+            &self.config,
+        )
+    }
+
     pub fn start_server<F>(&self, scope_factory: F) -> (Client, actix_web::test::TestServer)
     where
         F: Fn(&TestServerConfig) -> Scope + Clone + Send + 'static,
@@ -114,6 +146,7 @@ impl TestServerConfig {
                 success: true,
                 op: Some(zksync_op),
                 fail_reason: None,
+                fail_code: None,
                 block_index: Some(1),
                 created_at: chrono::Utc::now(),
                 batch_id: None,
@@ -149,6 +182,7 @@ impl TestServerConfig {
                 success: true,
                 op: Some(zksync_op),
                 fail_reason: None,
+                fail_code: None,
                 block_index: Some(2),
                 created_at: chrono::Utc::now(),
                 batch_id: None,
@@ -184,6 +218,7 @@ impl TestServerConfig {
                 success: false,
                 op: Some(zksync_op),
                 fail_reason: Some("Unknown token".to_string()),
+                fail_code: Some(TxFailureCode::UnsupportedToken),
                 block_index: None,
                 created_at: chrono::Utc::now(),
                 batch_id: None,
@@ -218,6 +253,7 @@ impl TestServerConfig {
                 success: true,
                 op: Some(zksync_op),
                 fail_reason: None,
+                fail_code: None,
                 block_index: Some(3),
                 created_at: chrono::Utc::now(),
                 batch_id: None,
@@ -269,7 +305,7 @@ impl TestServerConfig {
             .tokens_schema()
             .store_token(Token::new(
                 TokenId(1),
-                Address::from_str("38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7").unwrap(),
+                Address::from_str(PHNX_ADDRESS).unwrap(),
                 "PHNX",
                 18,
             ))
@@ -352,7 +388,7 @@ impl TestServerConfig {
                 .await?;
             storage
                 .ethereum_schema()
-                .confirm_eth_tx(&eth_tx_hash)
+                .confirm_eth_tx(&eth_tx_hash, None)
                 .await?;
 
             // Add verification for the block if required.
@@ -391,7 +427,7 @@ impl TestServerConfig {
                     .await?;
                 storage
                     .ethereum_schema()
-                    .confirm_eth_tx(&eth_tx_hash)
+                    .confirm_eth_tx(&eth_tx_hash, None)
                     .await?;
             }
         }
@@ -528,3 +564,60 @@ pub fn dummy_full_exit_op(
         created_at: Utc::now(),
     }
 }
+
+/// Address of the `PHNX` token stored by `TestServerConfig::fill_database`.
+const PHNX_ADDRESS: &str = "38A2fDc11f526Ddd5a607C1F251C065f40fBF2f7";
+
+/// Returns `true` if the query refers to the `PHNX` token stored by
+/// `TestServerConfig::fill_database`.
+pub fn is_phnx(token: &TokenLike) -> bool {
+    match token {
+        TokenLike::Id(id) => **id == 1,
+        TokenLike::Symbol(symbol) => symbol == "PHNX",
+        TokenLike::Address(address) => *address == Address::from_str(PHNX_ADDRESS).unwrap(),
+    }
+}
+
+/// Creates dummy fee ticker which only checks whether the tokens are allowed to pay fees in.
+/// For test purposes, `PHNX` token is not allowed.
+pub fn dummy_fee_ticker() -> mpsc::Sender<Traced<TickerRequest>> {
+    let (sender, mut receiver) = mpsc::channel(10);
+
+    actix_rt::spawn(async move {
+        while let Some(item) = receiver.next().await {
+            match item.into_inner() {
+                TickerRequest::IsTokenAllowed { token, response } => {
+                    response.send(Ok(!is_phnx(&token))).unwrap_or_default();
+                }
+                TickerRequest::GetAllowedTokens { tokens, response } => {
+                    let allowed = tokens
+                        .into_iter()
+                        .filter(|token| !is_phnx(&TokenLike::Id(token.id)))
+                        .map(|token| token.id)
+                        .collect();
+                    response.send(Ok(allowed)).unwrap_or_default();
+                }
+                _ => {}
+            }
+        }
+    });
+
+    sender
+}
+
+/// Creates dummy signature verifier which accepts all the transactions without checking them.
+pub fn dummy_sign_verifier() -> mpsc::Sender<Traced<VerifyTxSignatureRequest>> {
+    let (sender, mut receiver) = mpsc::channel::<Traced<VerifyTxSignatureRequest>>(10);
+
+    actix_rt::spawn(async move {
+        while let Some(item) = receiver.next().await {
+            let item = item.into_inner();
+            let verified = VerifiedTx::unverified(item.tx);
+            item.response
+                .send(Ok(verified))
+                .expect("Unable to send response");
+        }
+    });
+
+    sender
+}
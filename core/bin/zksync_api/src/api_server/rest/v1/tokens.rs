@@ -12,11 +12,15 @@ use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
 };
+use vlog::Traced;
 
 // Workspace uses
-use zksync_api_client::rest::v1::{TokenPriceKind, TokenPriceQuery};
+use zksync_api_client::rest::v1::{
+    AccountBalance, BalancesSnapshotQuery, TokenPriceKind, TokenPriceQuery,
+};
 use zksync_storage::{ConnectionPool, QueryResult};
-use zksync_types::{Token, TokenLike};
+use zksync_types::{AccountId, Address, Token, TokenLike};
+use zksync_utils::big_decimal_to_ratio;
 
 use crate::{
     fee_ticker::{TickerRequest, TokenPriceRequestType},
@@ -24,12 +28,12 @@ use crate::{
 };
 
 // Local uses
-use super::{ApiError, JsonResult};
+use super::{ApiError, JsonResult, MAX_LIMIT};
 
 /// Shared data between `api/v1/tokens` endpoints.
 #[derive(Clone)]
 struct ApiTokensData {
-    fee_ticker: mpsc::Sender<TickerRequest>,
+    fee_ticker: mpsc::Sender<Traced<TickerRequest>>,
     tokens: TokenDBCache,
     pool: ConnectionPool,
 }
@@ -38,7 +42,7 @@ impl ApiTokensData {
     fn new(
         pool: ConnectionPool,
         tokens: TokenDBCache,
-        fee_ticker: mpsc::Sender<TickerRequest>,
+        fee_ticker: mpsc::Sender<Traced<TickerRequest>>,
     ) -> Self {
         Self {
             pool,
@@ -69,11 +73,11 @@ impl ApiTokensData {
         let (price_sender, price_receiver) = oneshot::channel();
         self.fee_ticker
             .clone()
-            .send(TickerRequest::GetTokenPrice {
+            .send(Traced::new(TickerRequest::GetTokenPrice {
                 token,
                 response: price_sender,
                 req_type: TokenPriceRequestType::USDForOneToken,
-            })
+            }))
             .await?;
 
         // Ugly hack to distinguish real error from missing token.
@@ -109,6 +113,64 @@ async fn token_by_id(
     Ok(Json(token))
 }
 
+async fn token_balances(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+    web::Query(query): web::Query<BalancesSnapshotQuery>,
+) -> JsonResult<Vec<AccountBalance>> {
+    if query.limit == 0 || query.limit > MAX_LIMIT {
+        return Err(ApiError::bad_request("Incorrect limit")
+            .detail(format!("Limit should be between {} and {}", 1, MAX_LIMIT)));
+    }
+
+    let token = data
+        .token(TokenLike::parse(&token_like))
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::bad_request("Unknown token"))?;
+
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(ApiError::internal)?;
+    let last_verified_block = storage
+        .chain()
+        .block_schema()
+        .get_last_verified_confirmed_block()
+        .await
+        .map_err(ApiError::internal)?;
+    if query.block > last_verified_block {
+        return Err(
+            ApiError::bad_request("Block is not verified").detail(format!(
+                "The last verified block is {}",
+                *last_verified_block
+            )),
+        );
+    }
+
+    let balances = storage
+        .chain()
+        .account_schema()
+        .load_balances_snapshot(token.id, query.block, query.after, query.limit)
+        .await
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(|entry| {
+            let balance = big_decimal_to_ratio(&entry.balance)
+                .map(|ratio| ratio.to_integer())
+                .map_err(ApiError::internal)?;
+            Ok(AccountBalance {
+                account_id: AccountId(entry.account_id as u32),
+                address: Address::from_slice(&entry.address),
+                balance: balance.into(),
+            })
+        })
+        .collect::<Result<_, ApiError>>()?;
+
+    Ok(Json(balances))
+}
+
 async fn token_price(
     data: web::Data<ApiTokensData>,
     web::Path(token_like): web::Path<String>,
@@ -135,7 +197,7 @@ async fn token_price(
 pub fn api_scope(
     pool: ConnectionPool,
     tokens_db: TokenDBCache,
-    fee_ticker: mpsc::Sender<TickerRequest>,
+    fee_ticker: mpsc::Sender<Traced<TickerRequest>>,
 ) -> Scope {
     let data = ApiTokensData::new(pool, tokens_db, fee_ticker);
 
@@ -144,6 +206,7 @@ pub fn api_scope(
         .route("", web::get().to(tokens))
         .route("{id}", web::get().to(token_by_id))
         .route("{id}/price", web::get().to(token_price))
+        .route("{id}/balances", web::get().to(token_balances))
 }
 
 #[cfg(test)]
@@ -154,13 +217,13 @@ mod tests {
 
     use super::{super::test_utils::TestServerConfig, *};
 
-    fn dummy_fee_ticker(prices: &[(TokenLike, BigDecimal)]) -> mpsc::Sender<TickerRequest> {
+    fn dummy_fee_ticker(prices: &[(TokenLike, BigDecimal)]) -> mpsc::Sender<Traced<TickerRequest>> {
         let (sender, mut receiver) = mpsc::channel(10);
 
         let prices: HashMap<_, _> = prices.iter().cloned().collect();
         actix_rt::spawn(async move {
             while let Some(item) = receiver.next().await {
-                match item {
+                match item.into_inner() {
                     TickerRequest::GetTokenPrice {
                         token,
                         response,
@@ -281,6 +344,39 @@ mod tests {
         );
         assert_eq!(client.token_by_id(&TokenLike::parse("XM")).await?, None);
 
+        // Balances snapshot is available for the verified blocks only.
+        let eth = TokenLike::parse("ETH");
+        let last_verified_block = cfg
+            .pool
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        let balances = client
+            .token_balances(&eth, last_verified_block, None, MAX_LIMIT)
+            .await?;
+        for pair in balances.windows(2) {
+            assert!(pair[0].account_id < pair[1].account_id);
+        }
+        if let Some(first) = balances.first() {
+            assert_eq!(
+                client
+                    .token_balances(&eth, last_verified_block, None, 1)
+                    .await?,
+                vec![first.clone()]
+            );
+        }
+        assert!(client
+            .token_balances(&eth, last_verified_block + 1, None, MAX_LIMIT)
+            .await
+            .is_err());
+        assert!(client
+            .token_balances(&eth, last_verified_block, None, MAX_LIMIT + 1)
+            .await
+            .is_err());
+
         server.stop().await;
         Ok(())
     }
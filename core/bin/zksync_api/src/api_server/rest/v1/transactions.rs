@@ -1,27 +1,41 @@
 //! Transactions part of API implementation.
 
 // Built-in uses
+use std::collections::HashMap;
 
 // External uses
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
 
 // Workspace uses
 pub use zksync_api_client::rest::v1::{
-    FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-    Receipt, TxData,
+    BatchStatus, FastProcessingQuery, ForcedExitEligibility, IncomingTx, IncomingTxBatch,
+    IncomingTxBatchForFee, IncomingTxForFee, Receipt, SoftConfirmation, SoftConfirmationStage,
+    SubmitBatchResponse, TxData, TxStatus, TxStatusesQuery, MAX_TX_STATUSES,
 };
 use zksync_storage::{
     chain::operations_ext::records::TxReceiptResponse, QueryResult, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, BatchFee, BlockNumber, Fee, SignedZkSyncTx};
+use zksync_types::{
+    tx::{PackedEthSignature, TxHash},
+    Address, BatchFee, BlockNumber, Fee, SignedZkSyncTx, TokenLike, H256,
+};
 
 // Local uses
 use super::{ApiError, JsonResult, Pagination, PaginationQuery};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use crate::api_server::{
+    api_keys::rest_api_client,
+    tx_sender::{SubmitError, TxSender},
+};
 
+/// Numeric codes of the transaction submission errors returned by the API v1.
+///
+/// These codes predate the [`ErrorCode`] ones and are kept as is for compatibility,
+/// the error is identified unambiguously by the `codeName` field of the response.
+///
+/// [`ErrorCode`]: zksync_api_client::rest::v1::ErrorCode
 #[derive(Debug, Clone, Copy)]
 pub enum SumbitErrorCode {
     AccountCloseDisabled = 101,
@@ -30,10 +44,21 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    FastProcessingDisabled = 107,
+    TokenTxRestricted = 108,
+    Paused = 109,
 
     Internal = 110,
     CommunicationCoreServer = 111,
     Other = 112,
+    Maintenance = 113,
+    BatchExecutionFailed = 114,
+    CosignatureRejected = 115,
+    CoreServerUnavailable = 116,
+    CosignerUnavailable = 117,
+    RateLimited = 118,
+    ApiKeyRequired = 119,
+    ApiKeyQuotaExceeded = 120,
 }
 
 impl SumbitErrorCode {
@@ -43,9 +68,23 @@ impl SumbitErrorCode {
             SubmitError::InvalidParams(_) => Self::InvalidParams,
             SubmitError::UnsupportedFastProcessing => Self::UnsupportedFastProcessing,
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
-            SubmitError::TxAdd(_) => Self::TxAdd,
+            // Fee errors used to be returned as the `TxAdd` ones.
+            SubmitError::TxAdd(_)
+            | SubmitError::FeeTooLow { .. }
+            | SubmitError::BatchFeeTooLow { .. } => Self::TxAdd,
+            SubmitError::BatchExecutionFailed { .. } => Self::BatchExecutionFailed,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::FastProcessingDisabled => Self::FastProcessingDisabled,
+            SubmitError::TokenTxRestricted { .. } => Self::TokenTxRestricted,
+            SubmitError::Paused(_) => Self::Paused,
+            SubmitError::Maintenance(_) => Self::Maintenance,
+            SubmitError::CosignatureRejected => Self::CosignatureRejected,
             SubmitError::CommunicationCoreServer(_) => Self::CommunicationCoreServer,
+            SubmitError::CoreServerUnavailable => Self::CoreServerUnavailable,
+            SubmitError::CosignerUnavailable(_) => Self::CosignerUnavailable,
+            SubmitError::RateLimited { .. } => Self::RateLimited,
+            SubmitError::ApiKeyRequired => Self::ApiKeyRequired,
+            SubmitError::ApiKeyQuotaExceeded { .. } => Self::ApiKeyQuotaExceeded,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
         }
@@ -58,25 +97,47 @@ impl SumbitErrorCode {
 
 impl From<SubmitError> for ApiError {
     fn from(inner: SubmitError) -> Self {
-        let internal_code = SumbitErrorCode::from_err(&inner).as_code();
-
-        if let SubmitError::Internal(err) = &inner {
-            ApiError::internal(err)
-        } else {
-            ApiError::bad_request(inner)
+        let code = inner.error_code();
+        let legacy_code = SumbitErrorCode::from_err(&inner).as_code();
+        let retry_after = inner.retry_after();
+        let data = inner.details();
+
+        match &inner {
+            SubmitError::Internal(err) => ApiError::internal(err),
+            SubmitError::Paused(_)
+            | SubmitError::CoreServerUnavailable
+            | SubmitError::CosignerUnavailable(_) => ApiError::service_unavailable(inner),
+            SubmitError::RateLimited { .. } | SubmitError::ApiKeyQuotaExceeded { .. } => {
+                ApiError::too_many_requests(inner)
+            }
+            SubmitError::ApiKeyRequired => ApiError::unauthorized(inner),
+            SubmitError::Maintenance(maintenance) => {
+                let error = ApiError::service_unavailable(&inner);
+                match maintenance.until {
+                    Some(until) => error.detail(format!(
+                        "Maintenance is expected to end at {}",
+                        until.to_rfc3339()
+                    )),
+                    None => error,
+                }
+            }
+            _ => ApiError::bad_request(inner),
         }
-        .code(internal_code)
+        .code(code)
+        .numeric_code(legacy_code)
+        .retry_after(retry_after)
+        .data(data)
     }
 }
 
-/// Shared data between `api/v1/transactions` endpoints.
+/// Shared data between `api/v1/transactions` and `api/v2/transactions` endpoints.
 #[derive(Clone)]
-struct ApiTransactionsData {
-    tx_sender: TxSender,
+pub struct ApiTransactionsData {
+    pub tx_sender: TxSender,
 }
 
 impl ApiTransactionsData {
-    fn new(tx_sender: TxSender) -> Self {
+    pub fn new(tx_sender: TxSender) -> Self {
         Self { tx_sender }
     }
 
@@ -91,7 +152,7 @@ impl ApiTransactionsData {
             .await
     }
 
-    async fn tx_status(&self, tx_hash: TxHash) -> QueryResult<Option<Receipt>> {
+    pub async fn tx_status(&self, tx_hash: TxHash) -> QueryResult<Option<Receipt>> {
         let mut storage = self.tx_sender.pool.access_storage().await?;
 
         let tx_receipt = {
@@ -116,9 +177,10 @@ impl ApiTransactionsData {
         let block_number = BlockNumber(tx_receipt.block_number as u32);
         // Check the cases where we don't need to get block details.
         if !tx_receipt.success {
-            return Ok(Some(Receipt::Rejected {
-                reason: tx_receipt.fail_reason,
-            }));
+            return Ok(Some(Receipt::rejected(
+                tx_receipt.fail_reason,
+                tx_receipt.fail_code.as_deref(),
+            )));
         }
 
         if tx_receipt.verified {
@@ -155,7 +217,93 @@ impl ApiTransactionsData {
         Ok(Some(tx_receipt))
     }
 
-    async fn tx_data(&self, tx_hash: TxHash) -> QueryResult<Option<SignedZkSyncTx>> {
+    async fn tx_statuses(&self, tx_hashes: Vec<TxHash>) -> QueryResult<Vec<TxStatus>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let statuses: HashMap<_, _> = storage
+            .chain()
+            .operations_ext_schema()
+            .get_txs_statuses(&tx_hashes)
+            .await?
+            .into_iter()
+            .map(|status| {
+                let tx_hash = TxHash::from_slice(&status.tx_hash).unwrap_or_else(|| {
+                    panic!(
+                        "Database provided an incorrect tx_hash field: {}",
+                        hex::encode(&status.tx_hash)
+                    )
+                });
+                (tx_hash, status)
+            })
+            .collect();
+
+        let statuses = tx_hashes
+            .into_iter()
+            .map(|tx_hash| {
+                let receipt = statuses
+                    .get(&tx_hash)
+                    .and_then(|status| match status.block_number {
+                        None if status.pending => Some(Receipt::Pending),
+                        None => None,
+                        Some(block_number) => {
+                            let block = BlockNumber(block_number as u32);
+                            Some(if status.success != Some(true) {
+                                Receipt::rejected(
+                                    status.fail_reason.clone(),
+                                    status.fail_code.as_deref(),
+                                )
+                            } else if status.verified {
+                                Receipt::Verified { block }
+                            } else if status.committed {
+                                Receipt::Committed { block }
+                            } else {
+                                Receipt::Executed
+                            })
+                        }
+                    });
+
+                TxStatus { tx_hash, receipt }
+            })
+            .collect();
+
+        Ok(statuses)
+    }
+
+    pub async fn batch_status(&self, batch_hash: TxHash) -> QueryResult<Option<BatchStatus>> {
+        let batch = {
+            let mut storage = self.tx_sender.pool.access_storage().await?;
+            storage
+                .chain()
+                .mempool_schema()
+                .get_batch_info(batch_hash)
+                .await?
+        };
+        let batch = match batch {
+            Some(batch) => batch,
+            None => return Ok(None),
+        };
+
+        let tx_hashes = batch
+            .tx_hashes
+            .iter()
+            .map(|tx_hash| {
+                TxHash::from_slice(tx_hash).unwrap_or_else(|| {
+                    panic!(
+                        "Database provided an incorrect tx_hash field: {}",
+                        hex::encode(tx_hash)
+                    )
+                })
+            })
+            .collect();
+
+        Ok(Some(BatchStatus {
+            batch_hash,
+            transactions: self.tx_statuses(tx_hashes).await?,
+            created_at: batch.created_at,
+        }))
+    }
+
+    pub async fn tx_data(&self, tx_hash: TxHash) -> QueryResult<Option<SignedZkSyncTx>> {
         let mut storage = self.tx_sender.pool.access_storage().await?;
 
         let operation = storage
@@ -176,6 +324,66 @@ impl ApiTransactionsData {
             storage.chain().mempool_schema().get_tx(tx_hash).await
         }
     }
+
+    async fn tx_soft_confirmation(
+        &self,
+        tx_hash: TxHash,
+        private_key: &H256,
+    ) -> QueryResult<Option<SoftConfirmation>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let executed_tx = storage
+            .chain()
+            .operations_schema()
+            .get_executed_operation(tx_hash.as_ref())
+            .await?;
+
+        let (stage, timestamp) = if let Some(tx) = executed_tx {
+            // Rejected transactions are not confirmed.
+            if !tx.success {
+                return Ok(None);
+            }
+
+            let stage = SoftConfirmationStage::Included {
+                block: BlockNumber(tx.block_number as u32),
+                block_index: tx.block_index.unwrap_or_default() as u32,
+            };
+            (stage, tx.created_at)
+        } else if let Some(tx) = storage
+            .chain()
+            .mempool_schema()
+            .get_mempool_tx(tx_hash)
+            .await?
+        {
+            let stage = SoftConfirmationStage::Accepted {
+                mempool_seq_no: tx.id as u64,
+            };
+            (stage, tx.created_at)
+        } else {
+            return Ok(None);
+        };
+
+        let domain = self.tx_sender.eip712_domain;
+        let message = SoftConfirmation::message(
+            domain.chain_id,
+            domain.verifying_contract,
+            tx_hash,
+            stage,
+            timestamp,
+        );
+        let signature = PackedEthSignature::sign(private_key, message.as_bytes())?;
+        let signer = PackedEthSignature::address_from_private_key(private_key)?;
+
+        Ok(Some(SoftConfirmation {
+            chain_id: domain.chain_id,
+            contract_address: domain.verifying_contract,
+            tx_hash,
+            stage,
+            timestamp,
+            signer,
+            signature,
+        }))
+    }
 }
 
 // Server implementation
@@ -189,6 +397,27 @@ async fn tx_status(
     Ok(Json(tx_status))
 }
 
+async fn tx_statuses(
+    data: web::Data<ApiTransactionsData>,
+    Json(body): Json<TxStatusesQuery>,
+) -> JsonResult<Vec<TxStatus>> {
+    if body.tx_hashes.is_empty() || body.tx_hashes.len() > MAX_TX_STATUSES {
+        return Err(
+            ApiError::bad_request("Incorrect number of transactions").detail(format!(
+                "Number of transactions should be between {} and {}",
+                1, MAX_TX_STATUSES
+            )),
+        );
+    }
+
+    let statuses = data
+        .tx_statuses(body.tx_hashes)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(statuses))
+}
+
 async fn tx_data(
     data: web::Data<ApiTransactionsData>,
     web::Path(tx_hash): web::Path<TxHash>,
@@ -198,6 +427,23 @@ async fn tx_data(
     Ok(Json(tx_data.map(TxData::from)))
 }
 
+async fn tx_soft_confirmation(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> JsonResult<Option<SoftConfirmation>> {
+    let private_key = data
+        .tx_sender
+        .soft_confirmation_key
+        .ok_or_else(|| ApiError::not_implemented("Soft confirmations are disabled"))?;
+
+    let confirmation = data
+        .tx_soft_confirmation(tx_hash, &private_key)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(confirmation))
+}
+
 async fn tx_receipt_by_id(
     data: web::Data<ApiTransactionsData>,
     web::Path((tx_hash, receipt_id)): web::Path<(TxHash, u32)>,
@@ -235,32 +481,54 @@ async fn tx_receipts(
 }
 
 async fn submit_tx(
+    req: HttpRequest,
     data: web::Data<ApiTransactionsData>,
     Json(body): Json<IncomingTx>,
     web::Query(query): web::Query<FastProcessingQuery>,
 ) -> JsonResult<TxHash> {
+    let api_client = rest_api_client(&req);
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, query.fast_processing)
+        .submit_tx(
+            body.tx,
+            body.signature,
+            query.fast_processing,
+            api_client.as_deref(),
+        )
         .await
         .map_err(ApiError::from)?;
 
     Ok(Json(tx_hash))
 }
 
+async fn batch_status(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(batch_hash): web::Path<TxHash>,
+) -> JsonResult<Option<BatchStatus>> {
+    let batch_status = data
+        .batch_status(batch_hash)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(batch_status))
+}
+
 async fn submit_tx_batch(
     data: web::Data<ApiTransactionsData>,
     Json(body): Json<IncomingTxBatch>,
-) -> JsonResult<Vec<TxHash>> {
+) -> JsonResult<SubmitBatchResponse> {
     let txs = body.txs.into_iter().zip(std::iter::repeat(None)).collect();
 
-    let tx_hashes = data
+    let transaction_hashes = data
         .tx_sender
         .submit_txs_batch(txs, body.signature)
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(tx_hashes))
+    Ok(Json(SubmitBatchResponse {
+        batch_hash: TxHash::batch_hash(&transaction_hashes),
+        transaction_hashes,
+    }))
 }
 
 async fn get_txs_fee_in_wei(
@@ -292,13 +560,47 @@ async fn get_txs_batch_fee_in_wei(
     Ok(Json(fee))
 }
 
+async fn forced_exit_eligibility(
+    data: web::Data<ApiTransactionsData>,
+    web::Path((target, token_like)): web::Path<(Address, String)>,
+) -> JsonResult<ForcedExitEligibility> {
+    let eligibility = data
+        .tx_sender
+        .forced_exit_eligibility(target, TokenLike::parse(&token_like))
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(eligibility))
+}
+
+async fn fast_processing_enabled(data: web::Data<ApiTransactionsData>) -> JsonResult<bool> {
+    let enabled = data
+        .tx_sender
+        .fast_processing_enabled()
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(enabled))
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiTransactionsData::new(tx_sender);
 
     web::scope("transactions")
         .data(data)
+        .route("fast_processing", web::get().to(fast_processing_enabled))
+        .route(
+            "forced_exit/{target}/{token}",
+            web::get().to(forced_exit_eligibility),
+        )
+        .route("statuses", web::post().to(tx_statuses))
+        .route("batches/{batch_hash}", web::get().to(batch_status))
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route(
+            "{tx_hash}/confirmation",
+            web::get().to(tx_soft_confirmation),
+        )
         .route(
             "{tx_hash}/receipts/{receipt_id}",
             web::get().to(tx_receipt_by_id),
@@ -318,7 +620,7 @@ mod tests {
     use futures::{channel::mpsc, StreamExt};
     use num::{BigUint, Zero};
 
-    use zksync_api_client::rest::v1::Client;
+    use zksync_api_client::rest::v1::{Client, ClientError, ErrorCode, ForcedExitIneligibility};
     use zksync_storage::ConnectionPool;
     use zksync_test_account::ZkSyncAccount;
     use zksync_types::{
@@ -330,13 +632,14 @@ mod tests {
     };
 
     use crate::{
-        api_server::helpers::try_parse_tx_hash,
-        core_api_client::CoreApiClient,
+        api_server::helpers::try_parse_tx_hash, core_api_client::CoreApiClient,
         fee_ticker::TickerRequest,
-        signature_checker::{VerifiedTx, VerifyTxSignatureRequest},
     };
+    use vlog::Traced;
 
-    use super::super::test_utils::{TestServerConfig, TestTransactions};
+    use super::super::test_utils::{
+        dummy_sign_verifier, is_phnx, TestServerConfig, TestTransactions,
+    };
     use super::*;
 
     fn submit_txs_loopback() -> (CoreApiClient, actix_web::test::TestServer) {
@@ -358,15 +661,15 @@ mod tests {
 
         let url = server.url("").trim_end_matches('/').to_owned();
 
-        (CoreApiClient::new(url), server)
+        (CoreApiClient::new(url, "secret".to_owned()), server)
     }
 
-    fn dummy_fee_ticker() -> mpsc::Sender<TickerRequest> {
+    fn dummy_fee_ticker() -> mpsc::Sender<Traced<TickerRequest>> {
         let (sender, mut receiver) = mpsc::channel(10);
 
         actix_rt::spawn(async move {
             while let Some(item) = receiver.next().await {
-                match item {
+                match item.into_inner() {
                     TickerRequest::GetTxFee { response, .. } => {
                         let fee = Ok(Fee::new(
                             Withdraw,
@@ -385,12 +688,15 @@ mod tests {
                     }
                     TickerRequest::IsTokenAllowed { token, response } => {
                         // For test purposes, PHNX token is not allowed.
-                        let is_phnx = match token {
-                            TokenLike::Id(id) => *id == 1,
-                            TokenLike::Symbol(sym) => sym == "PHNX",
-                            TokenLike::Address(_) => unreachable!(),
-                        };
-                        response.send(Ok(!is_phnx)).unwrap_or_default();
+                        response.send(Ok(!is_phnx(&token))).unwrap_or_default();
+                    }
+                    TickerRequest::GetAllowedTokens { tokens, response } => {
+                        let allowed = tokens
+                            .into_iter()
+                            .filter(|token| !is_phnx(&TokenLike::Id(token.id)))
+                            .map(|token| token.id)
+                            .collect();
+                        response.send(Ok(allowed)).unwrap_or_default();
                     }
                     TickerRequest::GetBatchTxFee {
                         response,
@@ -410,21 +716,6 @@ mod tests {
         sender
     }
 
-    fn dummy_sign_verifier() -> mpsc::Sender<VerifyTxSignatureRequest> {
-        let (sender, mut receiver) = mpsc::channel::<VerifyTxSignatureRequest>(10);
-
-        actix_rt::spawn(async move {
-            while let Some(item) = receiver.next().await {
-                let verified = VerifiedTx::unverified(item.tx);
-                item.response
-                    .send(Ok(verified))
-                    .expect("Unable to send response");
-            }
-        });
-
-        sender
-    }
-
     struct TestServer {
         core_server: actix_web::test::TestServer,
         api_server: actix_web::test::TestServer,
@@ -481,8 +772,8 @@ mod tests {
             eth_sign_data: None,
         };
 
-        core_client.send_tx(signed_tx.clone()).await??;
-        core_client.send_txs_batch(vec![signed_tx], None).await??;
+        core_client.send_tx(&signed_tx, 1).await??;
+        core_client.send_txs_batch(&[signed_tx], &None, 2).await??;
 
         core_server.stop().await;
         Ok(())
@@ -525,6 +816,19 @@ mod tests {
             )
             .await?;
         assert_ne!(fee.total_fee, BigUint::zero());
+
+        // Forced exit of the unknown account.
+        let eligibility = client
+            .forced_exit_eligibility(Address::random(), &TokenLike::Id(TokenId(0)))
+            .await?;
+        assert_eq!(
+            eligibility,
+            ForcedExitEligibility {
+                eligible: false,
+                reasons: vec![ForcedExitIneligibility::AccountNotFound],
+            }
+        );
+
         // Tx receipt by ID.
         let unknown_tx_hash = TxHash::default();
         assert!(client
@@ -590,9 +894,18 @@ mod tests {
             SignedZkSyncTx::from(client.tx_data(committed_tx_hash).await?.unwrap()).hash(),
             committed_tx_hash
         );
+        let confirmation = client
+            .tx_soft_confirmation(committed_tx_hash)
+            .await?
+            .unwrap();
+        assert!(confirmation.verify());
+        assert!(matches!(
+            confirmation.stage,
+            SoftConfirmationStage::Included { block, .. } if block == BlockNumber(1)
+        ));
 
         // Tx status and data for pending transaction.
-        let tx_hash = {
+        let pending_tx_hash = {
             let mut storage = server.pool.access_storage().await?;
 
             let tx = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.clone();
@@ -608,16 +921,51 @@ mod tests {
 
             tx_hash
         };
-        assert_eq!(client.tx_status(tx_hash).await?, Some(Receipt::Pending));
         assert_eq!(
-            SignedZkSyncTx::from(client.tx_data(tx_hash).await?.unwrap()).hash(),
-            tx_hash
+            client.tx_status(pending_tx_hash).await?,
+            Some(Receipt::Pending)
         );
+        assert_eq!(
+            SignedZkSyncTx::from(client.tx_data(pending_tx_hash).await?.unwrap()).hash(),
+            pending_tx_hash
+        );
+        let confirmation = client.tx_soft_confirmation(pending_tx_hash).await?.unwrap();
+        assert!(confirmation.verify());
+        assert!(matches!(
+            confirmation.stage,
+            SoftConfirmationStage::Accepted { .. }
+        ));
 
         // Tx status for unknown transaction.
         let tx_hash = TestServerConfig::gen_zk_txs(1_u64).txs[1].0.hash();
         assert_eq!(client.tx_status(tx_hash).await?, None);
         assert!(client.tx_data(tx_hash).await?.is_none());
+        assert!(client.tx_soft_confirmation(tx_hash).await?.is_none());
+
+        // Bulk tx status keeps the order of the requested hashes.
+        let statuses = client
+            .tx_statuses(vec![tx_hash, committed_tx_hash, pending_tx_hash])
+            .await?;
+        assert_eq!(
+            statuses,
+            vec![
+                TxStatus {
+                    tx_hash,
+                    receipt: None,
+                },
+                TxStatus {
+                    tx_hash: committed_tx_hash,
+                    receipt: Some(Receipt::Verified {
+                        block: BlockNumber(1)
+                    }),
+                },
+                TxStatus {
+                    tx_hash: pending_tx_hash,
+                    receipt: Some(Receipt::Pending),
+                },
+            ]
+        );
+        assert!(client.tx_statuses(vec![]).await.is_err());
 
         // Submit correct transaction.
         let tx = TestServerConfig::gen_zk_txs(1_00).txs[0].0.clone();
@@ -626,12 +974,17 @@ mod tests {
 
         // Submit transaction without fee.
         let tx = TestServerConfig::gen_zk_txs(0).txs[0].0.clone();
-        assert!(client
-            .submit_tx(tx, None, None)
-            .await
-            .unwrap_err()
-            .to_string()
-            .contains("Transaction fee is too low"));
+        let err = client.submit_tx(tx, None, None).await.unwrap_err();
+        assert!(err.to_string().contains("Transaction fee is too low"));
+        assert_eq!(err.error_code(), Some(ErrorCode::FeeTooLow));
+        // Numeric code of the API v1 is the legacy `TxAdd` one.
+        assert!(matches!(
+            &err,
+            ClientError::BadRequest { body, .. } if body.code == Some(SumbitErrorCode::TxAdd as u64)
+        ));
+        let data = err.data().unwrap();
+        assert_eq!(data["providedFee"], "0");
+        assert!(data["requiredFee"].is_string());
 
         // Submit correct transactions batch.
         let TestTransactions { acc, txs } = TestServerConfig::gen_zk_txs(1_00);
@@ -646,11 +999,45 @@ mod tests {
         let batch_message = crate::api_server::tx_sender::get_batch_sign_message(txs.iter());
         let signature = PackedEthSignature::sign(&acc.eth_private_key, &batch_message).unwrap();
 
+        let response = client
+            .submit_tx_batch(txs, Some(TxEthSignature::EthereumSignature(signature)))
+            .await?;
+        assert_eq!(response.transaction_hashes, tx_hashes);
+        assert_eq!(response.batch_hash, TxHash::batch_hash(&tx_hashes));
+        // Batch wasn't accepted to the mempool, since the core server is mocked.
+        assert_eq!(client.batch_status(response.batch_hash).await?, None);
+
+        // Status of the batch stored in the mempool.
+        let txs = TestServerConfig::gen_zk_txs(1_00)
+            .txs
+            .into_iter()
+            .map(|(tx, _op)| SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+            })
+            .collect::<Vec<_>>();
+        let tx_hashes = txs.iter().map(|tx| tx.hash()).collect::<Vec<_>>();
+        let batch_hash = TxHash::batch_hash(&tx_hashes);
+        {
+            let mut storage = server.pool.access_storage().await?;
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_batch(&txs, None)
+                .await?;
+        }
+
+        let batch_status = client.batch_status(batch_hash).await?.unwrap();
+        assert_eq!(batch_status.batch_hash, batch_hash);
         assert_eq!(
-            client
-                .submit_tx_batch(txs, Some(TxEthSignature::EthereumSignature(signature)))
-                .await?,
+            batch_status.transactions,
             tx_hashes
+                .into_iter()
+                .map(|tx_hash| TxStatus {
+                    tx_hash,
+                    receipt: Some(Receipt::Pending),
+                })
+                .collect::<Vec<_>>()
         );
 
         server.stop().await;
@@ -739,7 +1126,8 @@ mod tests {
         assert_eq!(
             client
                 .submit_tx_batch(good_batch, Some(TxEthSignature::EthereumSignature(eth_sig)))
-                .await?,
+                .await?
+                .transaction_hashes,
             good_batch_hashes
         );
 
@@ -0,0 +1,436 @@
+//! Accounts part of API v2 implementation.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_client::rest::v2::{TxDirection, TxHistoryCursor, TxHistoryItem, TxHistoryQuery};
+use zksync_config::ZkSyncConfig;
+use zksync_storage::{
+    chain::operations_ext::{self, records::TransactionsHistoryPageItem, TxHistoryFilter},
+    ConnectionPool, QueryResult,
+};
+use zksync_types::{AccountId, Address, BlockNumber, TokenLike};
+
+// Local uses
+use super::{
+    super::v1::accounts::{load_account_info, AccountInfo},
+    ok, validate_pagination, Error, JsonResult, Paginated, PaginationQuery,
+};
+use crate::{
+    core_api_client::CoreApiClient,
+    utils::{
+        ens_resolver::{EnsError, EnsResolver},
+        token_db_cache::TokenDBCache,
+    },
+};
+
+/// Shared data between `api/v2/accounts` endpoints.
+#[derive(Clone)]
+struct ApiAccountsData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    ens_resolver: EnsResolver,
+    core_api_client: CoreApiClient,
+    confirmations_for_eth_event: BlockNumber,
+}
+
+impl ApiAccountsData {
+    /// Parses the address of the account, ENS names are resolved to the addresses they point to.
+    async fn account_address(&self, address: &str) -> Result<Address, Error> {
+        self.ens_resolver
+            .parse_address(address)
+            .await
+            .map_err(|err| match err {
+                EnsError::Resolution(err) => Error::internal(err),
+                err => Error::invalid_params(err),
+            })
+    }
+
+    /// Finds the ID of the account given either by its ID, its address or an ENS name,
+    /// returns `None` if there is no such account.
+    async fn account_id(&self, account: &str) -> Result<Option<AccountId>, Error> {
+        if let Ok(id) = account.parse::<u32>() {
+            return Ok(Some(AccountId(id)));
+        }
+
+        let address = self.account_address(account).await?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::internal)?;
+        storage
+            .chain()
+            .account_schema()
+            .account_id_by_address(address)
+            .await
+            .map_err(Error::internal)
+    }
+
+    async fn account_info(&self, account_id: AccountId) -> QueryResult<Option<AccountInfo>> {
+        let mut storage = self.pool.access_storage().await?;
+        load_account_info(
+            &mut storage,
+            &self.tokens,
+            &self.core_api_client,
+            self.confirmations_for_eth_event,
+            account_id,
+        )
+        .await
+    }
+
+    /// Converts the query into the storage filter, resolving the token.
+    async fn history_filter(&self, query: TxHistoryQuery) -> Result<TxHistoryFilter, Error> {
+        if let (Some(from_time), Some(to_time)) = (query.from_time, query.to_time) {
+            if from_time >= to_time {
+                return Err(Error::invalid_params(
+                    "The start of the time range should precede its end",
+                ));
+            }
+        }
+
+        let token = match query.token {
+            Some(token_like) => {
+                let mut storage = self.pool.access_storage().await.map_err(Error::internal)?;
+                let token = self
+                    .tokens
+                    .get_token(&mut storage, TokenLike::parse(&token_like))
+                    .await
+                    .map_err(Error::internal)?
+                    .ok_or_else(|| Error::invalid_params("Unknown token"))?;
+                Some(token.id)
+            }
+            None => None,
+        };
+
+        Ok(TxHistoryFilter {
+            token,
+            tx_type: query.tx_type.map(|tx_type| tx_type.as_str().to_owned()),
+            direction: query.direction.map(|direction| match direction {
+                TxDirection::Outgoing => operations_ext::TxDirection::Outgoing,
+                TxDirection::Incoming => operations_ext::TxDirection::Incoming,
+            }),
+            from_time: query.from_time,
+            to_time: query.to_time,
+        })
+    }
+
+    /// Returns the account transactions from the newest to the oldest ones,
+    /// starting from the transaction following the cursor.
+    async fn transactions_page(
+        &self,
+        address: Address,
+        filter: &TxHistoryFilter,
+        cursor: Option<TxHistoryCursor>,
+        limit: u32,
+    ) -> QueryResult<Vec<TransactionsHistoryPageItem>> {
+        let cursor = cursor.map(|cursor| operations_ext::TxHistoryCursor {
+            block_number: cursor.block_number,
+            block_index: cursor.block_index,
+            hash: cursor.hash,
+        });
+
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_page(&address, filter, cursor.as_ref(), limit as u64)
+            .await
+    }
+}
+
+fn tx_history_cursor(item: &TransactionsHistoryPageItem) -> TxHistoryCursor {
+    let cursor = operations_ext::TxHistoryCursor::from(item);
+    TxHistoryCursor {
+        block_number: cursor.block_number,
+        block_index: cursor.block_index,
+        hash: cursor.hash,
+    }
+}
+
+fn tx_history_item(item: TransactionsHistoryPageItem) -> TxHistoryItem {
+    TxHistoryItem {
+        tx_hash: item.hash,
+        block_number: BlockNumber(item.block_number as u32),
+        block_index: item.block_index.map(|index| index as u32),
+        eth_block: item.eth_block.map(|block| block as u64),
+        priority_op_id: item.pq_id.map(|id| id as u64),
+        tx: item.tx,
+        success: item.success,
+        fail_reason: item.fail_reason,
+        verified: item.verified,
+        created_at: item.created_at,
+    }
+}
+
+// Server implementation
+
+async fn account_info(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account): web::Path<String>,
+) -> JsonResult<Option<AccountInfo>> {
+    let info = match data.account_id(&account).await? {
+        Some(account_id) => data
+            .account_info(account_id)
+            .await
+            .map_err(Error::internal)?,
+        None => None,
+    };
+
+    ok(info)
+}
+
+async fn account_transactions(
+    data: web::Data<ApiAccountsData>,
+    web::Path(address): web::Path<String>,
+    web::Query(query): web::Query<PaginationQuery<TxHistoryCursor>>,
+    web::Query(history_query): web::Query<TxHistoryQuery>,
+) -> JsonResult<Paginated<TxHistoryItem, TxHistoryCursor>> {
+    validate_pagination(&query)?;
+    let address = data.account_address(&address).await?;
+    let filter = data.history_filter(history_query).await?;
+
+    let items = data
+        .transactions_page(address, &filter, query.cursor, query.limit)
+        .await
+        .map_err(Error::internal)?;
+    let page = Paginated::new(items, query.limit, tx_history_cursor);
+
+    ok(Paginated {
+        list: page.list.into_iter().map(tx_history_item).collect(),
+        limit: page.limit,
+        next_cursor: page.next_cursor,
+    })
+}
+
+pub fn api_scope(
+    pool: ConnectionPool,
+    config: &ZkSyncConfig,
+    tokens: TokenDBCache,
+    core_api_client: CoreApiClient,
+    ens_resolver: EnsResolver,
+) -> Scope {
+    let data = ApiAccountsData {
+        pool,
+        tokens,
+        ens_resolver,
+        core_api_client,
+        confirmations_for_eth_event: BlockNumber(
+            config.eth_watch.confirmations_for_eth_event as u32,
+        ),
+    };
+
+    web::scope("accounts")
+        .data(data)
+        .route("{account}", web::get().to(account_info))
+        .route(
+            "{address}/transactions",
+            web::get().to(account_transactions),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use actix_web::{
+        http::StatusCode,
+        test,
+        web::{self, Json},
+        App,
+    };
+    use serde_json::Value;
+    use zksync_types::tx::TxHash;
+    use zksync_utils::remove_prefix;
+
+    use super::{
+        super::{
+            super::v1::test_utils::{dummy_fee_ticker, dummy_sign_verifier, TestServerConfig},
+            api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+    use crate::api_server::tx_sender::TxSender;
+
+    /// Core server which reports no unconfirmed deposits.
+    fn no_deposits_loopback() -> (CoreApiClient, actix_web::test::TestServer) {
+        async fn get_deposits(_path: web::Path<String>) -> Json<Value> {
+            Json(Value::Array(Vec::new()))
+        }
+
+        let server = actix_web::test::start(move || {
+            App::new().route(
+                "unconfirmed_deposits/{address}",
+                web::get().to(get_deposits),
+            )
+        });
+
+        let url = server.url("").trim_end_matches('/').to_owned();
+        (CoreApiClient::new(url, "secret".to_owned()), server)
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_account_info() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let account_id: AccountId = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let block_txs = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            serde_json::from_value(block_txs[0].op["accountId"].clone())?
+        };
+
+        let (core_client, core_server) = no_deposits_loopback();
+        let tx_sender = TxSender::with_client(
+            core_client,
+            cfg.pool.clone(),
+            dummy_sign_verifier(),
+            dummy_fee_ticker(),
+            &cfg.config,
+        );
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(tx_sender, &cfg.config))).await;
+
+        // Account is found both by its ID and by its address.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/accounts/{}", account_id))
+            .to_request();
+        let info: Response<AccountInfo> = test::read_response_json(&mut app, req).await;
+        let info = info.into_result().unwrap().unwrap();
+        assert_eq!(info.id, account_id);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/accounts/{:?}", info.address))
+            .to_request();
+        let info_by_address: Response<AccountInfo> = test::read_response_json(&mut app, req).await;
+        assert_eq!(info_by_address.into_result().unwrap(), Some(info));
+
+        // Unknown account has the `null` result.
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/v2/accounts/{:?}",
+                Address::repeat_byte(0x42)
+            ))
+            .to_request();
+        let info: Response<AccountInfo> = test::read_response_json(&mut app, req).await;
+        assert_eq!(info.into_result().unwrap(), None);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/accounts/not_an_account")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+
+        core_server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_accounts_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        // Take the sender of the first transaction as the account with the history.
+        let address: Address = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let block_txs = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            let tx_hash = TxHash::from_str(&block_txs[0].tx_hash)?;
+            let tx = storage
+                .chain()
+                .operations_ext_schema()
+                .get_tx_by_hash(tx_hash.as_ref())
+                .await?
+                .unwrap();
+            remove_prefix(&tx.from).parse()?
+        };
+        let uri = |query: &str| format!("/api/v2/accounts/{:?}/transactions?{}", address, query);
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        // Walk through the history using the cursors.
+        let mut history = Vec::new();
+        let mut query = "limit=2".to_owned();
+        loop {
+            let req = test::TestRequest::get().uri(&uri(&query)).to_request();
+            let page: Response<Paginated<TxHistoryItem, TxHistoryCursor>> =
+                test::read_response_json(&mut app, req).await;
+            let page = page.into_result().unwrap().unwrap();
+
+            history.extend(page.list);
+            match page.next_cursor {
+                Some(cursor) => query = format!("limit=2&cursor={}", cursor),
+                None => break,
+            }
+        }
+
+        let req = test::TestRequest::get().uri(&uri("limit=100")).to_request();
+        let page: Response<Paginated<TxHistoryItem, TxHistoryCursor>> =
+            test::read_response_json(&mut app, req).await;
+        let page = page.into_result().unwrap().unwrap();
+        assert!(!history.is_empty());
+        assert_eq!(page.list, history);
+        assert_eq!(page.next_cursor, None);
+
+        // Filtered history contains only the matching transactions.
+        let req = test::TestRequest::get()
+            .uri(&uri("txType=Transfer&direction=outgoing"))
+            .to_request();
+        let page: Response<Paginated<TxHistoryItem, TxHistoryCursor>> =
+            test::read_response_json(&mut app, req).await;
+        let transfers = page.into_result().unwrap().unwrap().list;
+        let expected_transfers: Vec<_> = history
+            .into_iter()
+            .filter(|item| {
+                item.tx["type"] == "Transfer" && item.tx["from"] == format!("{:?}", address)
+            })
+            .collect();
+        assert_eq!(transfers, expected_transfers);
+
+        // Incorrect queries are rejected.
+        for query in &[
+            "cursor=1,2",
+            "token=NOT_A_TOKEN",
+            "txType=Swap",
+            "fromTime=2021-03-05T00:00:00Z&toTime=2021-03-04T00:00:00Z",
+        ] {
+            let req = test::TestRequest::get().uri(&uri(query)).to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+            let body: Response<Value> = test::read_body_json(resp).await;
+            let error = body.into_result().unwrap_err();
+            assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+        }
+
+        // ENS names aren't accepted unless the registry is configured.
+        if cfg.config.api.ens.registry_addr.is_none() {
+            let req = test::TestRequest::get()
+                .uri("/api/v2/accounts/unknown.eth/transactions")
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        }
+
+        Ok(())
+    }
+}
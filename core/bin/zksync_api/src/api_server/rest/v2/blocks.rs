@@ -0,0 +1,206 @@
+//! Blocks part of API v2 implementation.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_storage::{chain::block::records, ConnectionPool, QueryResult};
+use zksync_types::BlockNumber;
+
+// Local uses
+use super::{
+    super::v1::blocks::{
+        convert::{block_info_from_details, transaction_info_from_transaction_item},
+        BlockInfo, TransactionInfo,
+    },
+    ok, validate_pagination, Error, JsonResult, Paginated, PaginationQuery,
+};
+
+/// Shared data between `api/v2/blocks` endpoints.
+#[derive(Debug, Clone)]
+struct ApiBlocksData {
+    pool: ConnectionPool,
+}
+
+impl ApiBlocksData {
+    /// Returns the blocks in descending order, starting from the block preceding the cursor.
+    async fn blocks_page(
+        &self,
+        cursor: Option<BlockNumber>,
+        limit: u32,
+    ) -> QueryResult<Vec<records::BlockDetails>> {
+        let max_block = match cursor {
+            Some(BlockNumber(0)) => return Ok(Vec::new()),
+            Some(cursor) => cursor - 1,
+            None => BlockNumber(u32::MAX),
+        };
+
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .load_block_range(max_block, limit)
+            .await
+    }
+
+    async fn block(&self, block_number: BlockNumber) -> QueryResult<Option<records::BlockDetails>> {
+        let mut storage = self.pool.access_storage().await?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .load_block_range(block_number, 1)
+            .await?
+            .into_iter()
+            .next();
+
+        // Check if this is exactly the requested block.
+        Ok(block.filter(|block| block.block_number == *block_number as i64))
+    }
+
+    async fn block_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<records::BlockTransactionItem>> {
+        let mut storage = self.pool.access_storage().await?;
+        storage
+            .chain()
+            .block_schema()
+            .get_block_transactions(block_number)
+            .await
+    }
+}
+
+// Server implementation
+
+async fn blocks(
+    data: web::Data<ApiBlocksData>,
+    web::Query(query): web::Query<PaginationQuery<BlockNumber>>,
+) -> JsonResult<Paginated<BlockInfo, BlockNumber>> {
+    validate_pagination(&query)?;
+
+    let blocks = data
+        .blocks_page(query.cursor, query.limit)
+        .await
+        .map_err(Error::internal)?
+        .into_iter()
+        .map(block_info_from_details)
+        .collect();
+
+    ok(Paginated::new(blocks, query.limit, |block| {
+        block.block_number
+    }))
+}
+
+async fn block_by_number(
+    data: web::Data<ApiBlocksData>,
+    web::Path(block_number): web::Path<BlockNumber>,
+) -> JsonResult<Option<BlockInfo>> {
+    let block = data
+        .block(block_number)
+        .await
+        .map_err(Error::internal)?
+        .map(block_info_from_details);
+
+    ok(block)
+}
+
+async fn block_transactions(
+    data: web::Data<ApiBlocksData>,
+    web::Path(block_number): web::Path<BlockNumber>,
+) -> JsonResult<Vec<TransactionInfo>> {
+    let transactions = data
+        .block_transactions(block_number)
+        .await
+        .map_err(Error::internal)?
+        .into_iter()
+        .map(transaction_info_from_transaction_item)
+        .collect();
+
+    ok(transactions)
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiBlocksData { pool };
+
+    web::scope("blocks")
+        .data(data)
+        .route("", web::get().to(blocks))
+        .route("{block_number}", web::get().to(block_by_number))
+        .route(
+            "{block_number}/transactions",
+            web::get().to(block_transactions),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+
+    use super::{
+        super::{
+            super::v1::test_utils::{TestServerConfig, COMMITTED_BLOCKS_COUNT},
+            api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_blocks_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        // Walk through all the blocks using the cursors.
+        let mut blocks = Vec::new();
+        let mut uri = "/api/v2/blocks?limit=3".to_owned();
+        loop {
+            let req = test::TestRequest::get().uri(&uri).to_request();
+            let page: Response<Paginated<BlockInfo, BlockNumber>> =
+                test::read_response_json(&mut app, req).await;
+            let page = page.into_result().unwrap().unwrap();
+
+            blocks.extend(page.list.into_iter().map(|block| block.block_number));
+            match page.next_cursor {
+                Some(cursor) => uri = format!("/api/v2/blocks?limit=3&cursor={}", cursor),
+                None => break,
+            }
+        }
+        let expected: Vec<_> = (1..=*COMMITTED_BLOCKS_COUNT)
+            .rev()
+            .map(BlockNumber)
+            .collect();
+        assert_eq!(blocks, expected);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/blocks/1")
+            .to_request();
+        let block: Response<BlockInfo> = test::read_response_json(&mut app, req).await;
+        assert_eq!(
+            block.into_result().unwrap().unwrap().block_number,
+            BlockNumber(1)
+        );
+
+        // Errors are returned in the envelope too.
+        for uri in &["/api/v2/blocks?limit=1000", "/api/v2/blocks/not_a_number"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+            let body: Response<Value> = test::read_body_json(resp).await;
+            let error = body.into_result().unwrap_err();
+            assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+        }
+
+        Ok(())
+    }
+}
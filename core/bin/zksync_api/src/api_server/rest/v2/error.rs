@@ -0,0 +1,111 @@
+// Built-in uses
+use std::fmt::{self, Display};
+
+// External uses
+use actix_web::{
+    dev::Body,
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    http::HeaderValue,
+    HttpRequest, HttpResponse, ResponseError,
+};
+use reqwest::{
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    StatusCode,
+};
+
+// Workspace uses
+pub use zksync_api_client::rest::v2::{Error as ErrorBody, ErrorCode, Response};
+
+// Local uses
+
+/// An HTTP error of the API v2, the body is wrapped into the response envelope.
+#[derive(Debug)]
+pub struct Error {
+    /// HTTP error code.
+    pub http_code: StatusCode,
+    pub body: ErrorBody,
+}
+
+impl Error {
+    /// Creates a new error with the HTTP status code corresponding to the error code.
+    pub fn new(code: ErrorCode, message: impl Display) -> Self {
+        let http_code = match code {
+            ErrorCode::InvalidRequest | ErrorCode::InvalidParams => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::RateLimitExceeded | ErrorCode::ApiKeyQuotaExceeded => {
+                StatusCode::TOO_MANY_REQUESTS
+            }
+            ErrorCode::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+            ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::StorageUnavailable
+            | ErrorCode::ServiceUnavailable
+            | ErrorCode::CoreServerUnavailable
+            | ErrorCode::Paused
+            | ErrorCode::Maintenance
+            | ErrorCode::CommunicationCoreServer => StatusCode::SERVICE_UNAVAILABLE,
+            // Errors of the transactions processing.
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+
+        Self {
+            http_code,
+            body: ErrorBody::new(code, message),
+        }
+    }
+
+    /// Creates a new error with the `invalidParams` code.
+    pub fn invalid_params(message: impl Display) -> Self {
+        Self::new(ErrorCode::InvalidParams, message)
+    }
+
+    /// Creates a new error with the `internal` code.
+    pub fn internal(message: impl Display) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// Handler of the query deserialization errors, so they are returned in the envelope as well.
+    pub fn query_error_handler(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+        Self::invalid_params(err).into()
+    }
+
+    /// Handler of the path deserialization errors, so they are returned in the envelope as well.
+    pub fn path_error_handler(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+        Self::invalid_params(err).into()
+    }
+
+    /// Handler of the body deserialization errors, so they are returned in the envelope as well.
+    pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+        Self::invalid_params(err).into()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.body, self.http_code)
+    }
+}
+
+impl ResponseError for Error {
+    fn status_code(&self) -> reqwest::StatusCode {
+        self.http_code
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        let mut resp = HttpResponse::new(self.status_code());
+
+        let response = Response::<()>::error(self.body.clone());
+        match serde_json::to_vec_pretty(&response) {
+            Ok(body) => {
+                resp.headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                if let Some(retry_after) = self.body.retry_after {
+                    resp.headers_mut()
+                        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+                }
+                resp.set_body(Body::from_slice(&body))
+            }
+
+            Err(err) => err.error_response(),
+        }
+    }
+}
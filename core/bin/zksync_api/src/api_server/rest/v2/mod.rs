@@ -0,0 +1,75 @@
+//! Second version of the API.
+//!
+//! Unlike the first version, every response (including the errors) is wrapped into the
+//! `{ result, error }` envelope, errors always carry the stable machine-readable codes,
+//! and the lists are paginated with cursors.
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+
+// Workspace uses
+pub use zksync_api_client::rest::v2::{Paginated, PaginationQuery, DEFAULT_LIMIT};
+use zksync_config::ZkSyncConfig;
+
+// Local uses
+use crate::api_server::tx_sender::TxSender;
+
+// Public uses
+pub use self::error::{Error, ErrorBody, ErrorCode, Response};
+
+mod accounts;
+mod blocks;
+mod error;
+mod operations;
+mod rejected_txs;
+mod search;
+mod stats;
+mod tokens;
+mod transactions;
+mod webhooks;
+
+type JsonResult<T> = std::result::Result<Json<Response<T>>, Error>;
+
+/// Wraps the successful result into the response envelope.
+fn ok<T>(result: T) -> JsonResult<T> {
+    Ok(Json(Response::ok(result)))
+}
+
+/// Checks the pagination query, so the handlers can rely on the limit being correct.
+fn validate_pagination<C>(query: &PaginationQuery<C>) -> Result<(), Error> {
+    query.validate().map_err(Error::invalid_params)
+}
+
+pub(crate) fn api_scope(tx_sender: TxSender, zk_config: &ZkSyncConfig) -> Scope {
+    let pool = tx_sender.pool.clone();
+
+    web::scope("/api/v2")
+        .app_data(web::QueryConfig::default().error_handler(Error::query_error_handler))
+        .app_data(web::PathConfig::default().error_handler(Error::path_error_handler))
+        .app_data(web::JsonConfig::default().error_handler(Error::json_error_handler))
+        .service(accounts::api_scope(
+            pool.clone(),
+            zk_config,
+            tx_sender.tokens.clone(),
+            tx_sender.core_api_client.clone(),
+            tx_sender.ens_resolver.clone(),
+        ))
+        .service(blocks::api_scope(pool.clone()))
+        .service(operations::api_scope(
+            pool.clone(),
+            tx_sender.core_api_client.clone(),
+        ))
+        .service(rejected_txs::api_scope(pool.clone()))
+        .service(search::api_scope(pool.clone()))
+        .service(stats::api_scope(pool.clone()))
+        .service(tokens::api_scope(
+            pool.clone(),
+            tx_sender.tokens.clone(),
+            tx_sender.ticker_requests.clone(),
+        ))
+        .service(webhooks::api_scope(pool))
+        .service(transactions::api_scope(tx_sender))
+}
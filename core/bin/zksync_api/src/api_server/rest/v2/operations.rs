@@ -0,0 +1,127 @@
+//! Operations part of API v2 implementation.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_client::rest::v1::{PriorityOpData, PriorityOpQuery, PriorityOpReceipt};
+use zksync_storage::ConnectionPool;
+
+// Local uses
+use super::{super::v1::operations::ApiOperationsData, ok, Error, JsonResult};
+use crate::core_api_client::CoreApiClient;
+
+/// Parses the priority operation query, either a serial ID or an Ethereum transaction hash.
+fn priority_op_query(path: String) -> Result<PriorityOpQuery, Error> {
+    PriorityOpQuery::from_path(path).map_err(|err| Error::invalid_params(err.detail))
+}
+
+// Server implementation
+
+async fn priority_op(
+    data: web::Data<ApiOperationsData>,
+    web::Path(path): web::Path<String>,
+) -> JsonResult<Option<PriorityOpReceipt>> {
+    let query = priority_op_query(path)?;
+
+    let receipt = data.priority_op(query).await.map_err(Error::internal)?;
+    ok(receipt)
+}
+
+async fn priority_op_data(
+    data: web::Data<ApiOperationsData>,
+    web::Path(path): web::Path<String>,
+) -> JsonResult<Option<PriorityOpData>> {
+    let query = priority_op_query(path)?;
+
+    let data = data
+        .priority_op_data(query)
+        .await
+        .map_err(Error::internal)?;
+    ok(data)
+}
+
+pub fn api_scope(pool: ConnectionPool, core_api_client: CoreApiClient) -> Scope {
+    let data = ApiOperationsData::new(pool, core_api_client);
+
+    web::scope("operations")
+        .data(data)
+        .route("{id}", web::get().to(priority_op))
+        .route("{id}/data", web::get().to(priority_op_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+    use zksync_api_client::rest::v1::Receipt;
+    use zksync_types::BlockNumber;
+
+    use super::{
+        super::{
+            super::v1::test_utils::{TestServerConfig, VERIFIED_OP_SERIAL_ID},
+            api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_operations_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/operations/{}", VERIFIED_OP_SERIAL_ID))
+            .to_request();
+        let receipt: Response<PriorityOpReceipt> = test::read_response_json(&mut app, req).await;
+        assert_eq!(
+            receipt.into_result().unwrap(),
+            Some(PriorityOpReceipt {
+                index: Some(2),
+                status: Receipt::Verified {
+                    block: BlockNumber(2)
+                },
+            })
+        );
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/v2/operations/{}/data",
+                VERIFIED_OP_SERIAL_ID
+            ))
+            .to_request();
+        let data: Response<PriorityOpData> = test::read_response_json(&mut app, req).await;
+        assert_eq!(
+            data.into_result().unwrap().unwrap().serial_id,
+            VERIFIED_OP_SERIAL_ID
+        );
+
+        // Unknown operation has the `null` result.
+        let req = test::TestRequest::get()
+            .uri("/api/v2/operations/1000")
+            .to_request();
+        let receipt: Response<PriorityOpReceipt> = test::read_response_json(&mut app, req).await;
+        assert_eq!(receipt.into_result().unwrap(), None);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/operations/not_an_id")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+//! Rejected transactions part of API v2 implementation.
+//!
+//! Transactions rejected on submission never reach the mempool, so they can't be found
+//! by the other endpoints. Their rejections are recorded by the transaction sender.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_client::rest::v2::{RejectedTx, RejectedTxsQuery};
+use zksync_storage::{rejected_txs::records::StoredRejectedTx, ConnectionPool, QueryResult};
+use zksync_types::{tx::TxHash, Address};
+
+// Local uses
+use super::{
+    ok, validate_pagination, Error, ErrorBody, ErrorCode, JsonResult, Paginated, PaginationQuery,
+};
+
+/// Shared data between `api/v2/rejected_transactions` endpoints.
+#[derive(Clone)]
+struct ApiRejectedTxsData {
+    pool: ConnectionPool,
+}
+
+impl ApiRejectedTxsData {
+    /// Returns the rejected transactions from the newest to the oldest ones,
+    /// starting from the rejection following the cursor.
+    async fn rejected_txs_page(
+        &self,
+        query: RejectedTxsQuery,
+        cursor: Option<u64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredRejectedTx>> {
+        let cursor = cursor.map(|id| id as i64);
+
+        let mut storage = self.pool.access_storage().await?;
+        let mut schema = storage.rejected_txs_schema();
+        match (query.tx_hash, query.address) {
+            (Some(tx_hash), _) => schema.load_by_hash(tx_hash.as_ref(), cursor, limit).await,
+            (None, Some(address)) => schema.load_by_address(address, cursor, limit).await,
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+}
+
+fn rejected_tx(tx: StoredRejectedTx) -> RejectedTx {
+    let code = ErrorCode::from_u64(tx.error_code as u64);
+
+    RejectedTx {
+        id: tx.id as u64,
+        // Hashes are stored by the server itself, so they always have the correct length.
+        tx_hash: TxHash::from_slice(&tx.tx_hash).unwrap(),
+        address: Address::from_slice(&tx.address),
+        tx: tx.tx,
+        error: ErrorBody {
+            code: tx.error_code as u64,
+            code_name: code.map(ErrorCode::name).unwrap_or_default().to_owned(),
+            message: tx.reason,
+            // The submission is over, so there is nothing to retry.
+            retry_after: None,
+            data: None,
+        },
+        rejected_at: tx.created_at,
+    }
+}
+
+// Server implementation
+
+async fn rejected_txs(
+    data: web::Data<ApiRejectedTxsData>,
+    web::Query(query): web::Query<PaginationQuery<u64>>,
+    web::Query(rejected_query): web::Query<RejectedTxsQuery>,
+) -> JsonResult<Paginated<RejectedTx, u64>> {
+    validate_pagination(&query)?;
+    rejected_query.validate().map_err(Error::invalid_params)?;
+
+    let txs = data
+        .rejected_txs_page(rejected_query, query.cursor, query.limit)
+        .await
+        .map_err(Error::internal)?;
+    let page = Paginated::new(txs, query.limit, |tx| tx.id as u64);
+
+    ok(Paginated {
+        list: page.list.into_iter().map(rejected_tx).collect(),
+        limit: page.limit,
+        next_cursor: page.next_cursor,
+    })
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiRejectedTxsData { pool };
+
+    web::scope("rejected_transactions")
+        .data(data)
+        .route("", web::get().to(rejected_txs))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+
+    use super::{
+        super::{super::v1::test_utils::TestServerConfig, api_scope as api_v2_scope, Response},
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_rejected_txs_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let txs: Vec<_> = TestServerConfig::gen_zk_txs(0)
+            .txs
+            .into_iter()
+            .map(|(tx, _)| tx)
+            .collect();
+        let address = txs[0].account();
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .rejected_txs_schema()
+                .record(&txs[..1], 104, "Transaction fee is too low")
+                .await?;
+            storage
+                .rejected_txs_schema()
+                .record(&txs, 106, "Transactions batch summary fee is too low")
+                .await?;
+        }
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/v2/rejected_transactions?txHash={}",
+                txs[0].hash().to_string()
+            ))
+            .to_request();
+        let page: Response<Paginated<RejectedTx, u64>> =
+            test::read_response_json(&mut app, req).await;
+        let rejections = page.into_result().unwrap().unwrap().list;
+        assert_eq!(rejections.len(), 2);
+        assert_eq!(
+            rejections[0].error.error_code(),
+            Some(ErrorCode::BatchFeeTooLow)
+        );
+        assert_eq!(rejections[1].error.error_code(), Some(ErrorCode::FeeTooLow));
+        assert_eq!(rejections[1].error.message, "Transaction fee is too low");
+        assert_eq!(rejections[1].tx_hash, txs[0].hash());
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/v2/rejected_transactions?address={:?}&limit=1",
+                address
+            ))
+            .to_request();
+        let page: Response<Paginated<RejectedTx, u64>> =
+            test::read_response_json(&mut app, req).await;
+        let page = page.into_result().unwrap().unwrap();
+        assert_eq!(page.list.len(), 1);
+        assert_eq!(page.list[0].address, address);
+        assert_eq!(page.next_cursor, Some(page.list[0].id));
+
+        // Exactly one of the filters should be set.
+        let both_filters = format!("txHash={}&address={:?}", txs[0].hash().to_string(), address);
+        for query in &["", both_filters.as_str()] {
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/v2/rejected_transactions?{}", query))
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+            let body: Response<Value> = test::read_body_json(resp).await;
+            let error = body.into_result().unwrap_err();
+            assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+//! Search part of API v2 implementation.
+//!
+//! Resolves the free-form query of the block explorer to the entity it identifies,
+//! so the explorer doesn't have to guess which endpoint to request.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_client::rest::v2::{SearchQuery, SearchResult};
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_types::{tx::TxHash, Address, BlockNumber, H256};
+
+// Local uses
+use super::{ok, Error, JsonResult};
+
+/// Search query recognized by its format.
+#[derive(Debug, Clone, PartialEq)]
+enum ParsedQuery {
+    BlockNumber(BlockNumber),
+    /// Hash of the transaction, the priority operation or the block.
+    Hash(Vec<u8>),
+    Address(Address),
+}
+
+impl ParsedQuery {
+    /// Parses the query, hashes and addresses are accepted with or without the known prefixes.
+    fn parse(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if let Ok(block_number) = query.parse() {
+            return Some(Self::BlockNumber(BlockNumber(block_number)));
+        }
+
+        let hex_query = ["0x", "sync-tx:", "sync-bl:"]
+            .iter()
+            .find_map(|prefix| query.strip_prefix(prefix))
+            .unwrap_or(query);
+        let bytes = hex::decode(hex_query).ok()?;
+        match bytes.len() {
+            20 => Some(Self::Address(Address::from_slice(&bytes))),
+            32 => Some(Self::Hash(bytes)),
+            _ => None,
+        }
+    }
+}
+
+/// Shared data between `api/v2/search` endpoints.
+#[derive(Clone)]
+struct ApiSearchData {
+    pool: ConnectionPool,
+}
+
+impl ApiSearchData {
+    async fn search(&self, query: ParsedQuery) -> QueryResult<Option<SearchResult>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let block_query = match query {
+            ParsedQuery::BlockNumber(block_number) => block_number.to_string(),
+            ParsedQuery::Address(address) => {
+                let account_id = storage
+                    .chain()
+                    .account_schema()
+                    .account_id_by_address(address)
+                    .await?;
+                return Ok(Some(SearchResult::Account {
+                    address,
+                    account_id,
+                }));
+            }
+            ParsedQuery::Hash(hash) => {
+                if let Some(tx) = storage
+                    .chain()
+                    .operations_schema()
+                    .get_executed_operation(&hash)
+                    .await?
+                {
+                    return Ok(Some(SearchResult::Transaction {
+                        tx_hash: TxHash::from_slice(&tx.tx_hash).unwrap(),
+                        block_number: Some(BlockNumber(tx.block_number as u32)),
+                    }));
+                }
+
+                // Hashes of the transactions have a fixed length, so the conversion can't fail.
+                let tx_hash = TxHash::from_slice(&hash).unwrap();
+                if storage
+                    .chain()
+                    .mempool_schema()
+                    .contains_tx(tx_hash)
+                    .await?
+                {
+                    return Ok(Some(SearchResult::Transaction {
+                        tx_hash,
+                        block_number: None,
+                    }));
+                }
+
+                if let Some(op) = storage
+                    .chain()
+                    .operations_schema()
+                    .get_executed_priority_operation_by_hash(&hash)
+                    .await?
+                {
+                    return Ok(Some(SearchResult::PriorityOperation {
+                        eth_hash: H256::from_slice(&op.eth_hash),
+                        serial_id: op.priority_op_serialid as u64,
+                        block_number: BlockNumber(op.block_number as u32),
+                    }));
+                }
+
+                hex::encode(hash)
+            }
+        };
+
+        // Block is searched by its number, its state root hash and the hashes
+        // of its commit and verify Ethereum transactions.
+        let block = storage
+            .chain()
+            .block_schema()
+            .find_block_by_height_or_hash(block_query)
+            .await;
+        Ok(block.map(|block| SearchResult::Block {
+            block_number: BlockNumber(block.block_number as u32),
+        }))
+    }
+}
+
+// Server implementation
+
+async fn search(
+    data: web::Data<ApiSearchData>,
+    web::Query(query): web::Query<SearchQuery>,
+) -> JsonResult<Option<SearchResult>> {
+    let query = ParsedQuery::parse(&query.query).ok_or_else(|| {
+        Error::invalid_params("Query should be a block number, a hash or an account address")
+    })?;
+
+    let result = data.search(query).await.map_err(Error::internal)?;
+    ok(result)
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiSearchData { pool };
+
+    web::scope("search")
+        .data(data)
+        .route("", web::get().to(search))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+
+    use super::{
+        super::{
+            super::v1::test_utils::{TestServerConfig, VERIFIED_OP_SERIAL_ID},
+            api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+
+    #[test]
+    fn parse_query() {
+        let hash = [1_u8; 32];
+        let address = Address::repeat_byte(2);
+
+        assert_eq!(
+            ParsedQuery::parse(" 42 "),
+            Some(ParsedQuery::BlockNumber(BlockNumber(42)))
+        );
+        for query in &[
+            hex::encode(hash),
+            format!("0x{}", hex::encode(hash)),
+            format!("sync-tx:{}", hex::encode(hash)),
+            format!("sync-bl:{}", hex::encode(hash)),
+        ] {
+            assert_eq!(
+                ParsedQuery::parse(query),
+                Some(ParsedQuery::Hash(hash.to_vec()))
+            );
+        }
+        assert_eq!(
+            ParsedQuery::parse(&format!("{:?}", address)),
+            Some(ParsedQuery::Address(address))
+        );
+
+        for query in &["", "-1", "0x1234", "sync-tx:not_a_hash", "PHNX"] {
+            assert_eq!(ParsedQuery::parse(query), None);
+        }
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_search_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let (tx, priority_op) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let block_txs = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            let tx = block_txs
+                .into_iter()
+                .find(|tx| tx.tx_hash.starts_with("sync-tx:"))
+                .unwrap();
+            let priority_op = storage
+                .chain()
+                .operations_schema()
+                .get_executed_priority_operation(VERIFIED_OP_SERIAL_ID as u32)
+                .await?
+                .unwrap();
+            (tx, priority_op)
+        };
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        let eth_hash = H256::from_slice(&priority_op.eth_hash);
+        let cases = vec![
+            (
+                "1".to_owned(),
+                Some(SearchResult::Block {
+                    block_number: BlockNumber(1),
+                }),
+            ),
+            (
+                tx.tx_hash.clone(),
+                Some(SearchResult::Transaction {
+                    tx_hash: tx.tx_hash.parse()?,
+                    block_number: Some(BlockNumber(1)),
+                }),
+            ),
+            (
+                format!("{:?}", eth_hash),
+                Some(SearchResult::PriorityOperation {
+                    eth_hash,
+                    serial_id: VERIFIED_OP_SERIAL_ID,
+                    block_number: BlockNumber(priority_op.block_number as u32),
+                }),
+            ),
+            // Unknown hash isn't resolved to anything.
+            (hex::encode([0xff_u8; 32]), None),
+        ];
+        for (query, expected) in cases {
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/v2/search?query={}", query))
+                .to_request();
+            let result: Response<SearchResult> = test::read_response_json(&mut app, req).await;
+            assert_eq!(result.into_result().unwrap(), expected, "query: {}", query);
+        }
+
+        // Any valid address is resolved to the account.
+        let address = Address::from_slice(&priority_op.to_account);
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/search?query={:?}", address))
+            .to_request();
+        let result: Response<SearchResult> = test::read_response_json(&mut app, req).await;
+        assert!(matches!(
+            result.into_result().unwrap(),
+            Some(SearchResult::Account { address: found, .. }) if found == address
+        ));
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/search?query=PHNX")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+
+        Ok(())
+    }
+}
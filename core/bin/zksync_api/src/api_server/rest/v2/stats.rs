@@ -0,0 +1,181 @@
+//! Network statistics part of API v2 implementation.
+//!
+//! The statistics are precomputed by the stats aggregator of the Core server,
+//! so the endpoint only reads the aggregated tables.
+
+// Built-in uses
+use std::collections::HashMap;
+
+// External uses
+use actix_web::{web, Scope};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+
+// Workspace uses
+use zksync_api_client::rest::v2::{DailyStats, NetworkStats, StatsQuery, TokenFeeStats, TokenTvl};
+use zksync_storage::ConnectionPool;
+use zksync_types::{Token, TokenId};
+use zksync_utils::{big_decimal_to_ratio, BigUintSerdeWrapper};
+
+// Local uses
+use super::{ok, Error, JsonResult};
+
+/// Shared data between `api/v2/stats` endpoints.
+#[derive(Clone)]
+struct ApiStatsData {
+    pool: ConnectionPool,
+}
+
+/// Returns the symbol of the token, the aggregated tokens are always known.
+fn token_symbol(tokens: &HashMap<TokenId, Token>, token_id: i32) -> Result<String, Error> {
+    tokens
+        .get(&TokenId(token_id as u16))
+        .map(|token| token.symbol.clone())
+        .ok_or_else(|| Error::internal(format!("Unknown token {}", token_id)))
+}
+
+fn to_amount(amount: &BigDecimal) -> Result<BigUintSerdeWrapper, Error> {
+    let amount = big_decimal_to_ratio(amount).map_err(Error::internal)?;
+    Ok(amount.to_integer().into())
+}
+
+impl ApiStatsData {
+    async fn network_stats(&self, days: u32) -> Result<NetworkStats, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::internal)?;
+        let tokens = storage
+            .tokens_schema()
+            .load_tokens()
+            .await
+            .map_err(Error::internal)?;
+
+        let since = Utc::today().naive_utc() - Duration::days(days as i64 - 1);
+        let mut stats = storage.chain().stats_schema();
+        let tvl = stats.load_tvl().await.map_err(Error::internal)?;
+        let daily = stats
+            .load_daily_stats(since)
+            .await
+            .map_err(Error::internal)?;
+        let fees = stats
+            .load_daily_fees(since)
+            .await
+            .map_err(Error::internal)?;
+
+        let updated_at = tvl
+            .iter()
+            .map(|token| token.updated_at)
+            .chain(daily.iter().map(|day| day.updated_at))
+            .max();
+
+        let mut daily_fees: HashMap<_, Vec<_>> = HashMap::new();
+        for fee in fees {
+            daily_fees.entry(fee.day).or_default().push(TokenFeeStats {
+                token_id: TokenId(fee.token_id as u16),
+                symbol: token_symbol(&tokens, fee.token_id)?,
+                tx_count: fee.tx_count as u64,
+                average_fee: to_amount(&fee.average_fee)?,
+            });
+        }
+
+        let tvl = tvl
+            .into_iter()
+            .map(|token| {
+                Ok(TokenTvl {
+                    token_id: TokenId(token.token_id as u16),
+                    symbol: token_symbol(&tokens, token.token_id)?,
+                    amount: to_amount(&token.amount)?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let daily = daily
+            .into_iter()
+            .rev()
+            .map(|day| DailyStats {
+                day: day.day,
+                tx_count: day.tx_count as u64,
+                active_accounts: day.active_accounts as u64,
+                fees: daily_fees.remove(&day.day).unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(NetworkStats {
+            tvl,
+            daily,
+            updated_at,
+        })
+    }
+}
+
+// Server implementation
+
+async fn stats(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<StatsQuery>,
+) -> JsonResult<NetworkStats> {
+    query.validate().map_err(Error::invalid_params)?;
+
+    let stats = data.network_stats(query.days).await?;
+    ok(stats)
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiStatsData { pool };
+
+    web::scope("stats")
+        .data(data)
+        .route("", web::get().to(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+
+    use super::{
+        super::{
+            super::v1::test_utils::TestServerConfig, api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage.chain().stats_schema().aggregate_tvl().await?;
+        }
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/stats?days=7")
+            .to_request();
+        let stats: Response<NetworkStats> = test::read_response_json(&mut app, req).await;
+        let stats = stats.into_result().unwrap().unwrap();
+        assert!(stats.daily.len() <= 7);
+        assert!(stats
+            .tvl
+            .windows(2)
+            .all(|tokens| tokens[0].token_id < tokens[1].token_id));
+        assert_eq!(stats.updated_at.is_some(), !stats.tvl.is_empty());
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/stats?days=0")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+
+        Ok(())
+    }
+}
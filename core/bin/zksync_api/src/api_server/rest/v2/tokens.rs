@@ -0,0 +1,264 @@
+//! Tokens part of API v2 implementation.
+
+// Built-in uses
+use std::{collections::HashSet, sync::Arc};
+
+// External uses
+use actix_web::{http::header, web, HttpResponse, Scope};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use tokio::sync::RwLock;
+use vlog::Traced;
+
+// Workspace uses
+use zksync_api_client::rest::v2::TokenInfo;
+use zksync_storage::{tokens::records::ListedToken, ConnectionPool};
+use zksync_types::{TokenId, TokenLike};
+
+// Local uses
+use super::{ok, validate_pagination, Error, JsonResult, Paginated, PaginationQuery, Response};
+use crate::{
+    fee_ticker::TickerRequest,
+    utils::token_db_cache::{TokenDBCache, LISTED_TOKENS_TTL},
+};
+
+/// Shared data between `api/v2/tokens` endpoints.
+#[derive(Clone)]
+struct ApiTokensData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    ticker_requests: mpsc::Sender<Traced<TickerRequest>>,
+    /// Tokens allowed to pay fees in, along with the list of tokens they were computed for.
+    allowed_tokens: Arc<RwLock<Option<(Arc<Vec<ListedToken>>, Arc<HashSet<TokenId>>)>>>,
+}
+
+impl ApiTokensData {
+    async fn listed_tokens(&self) -> Result<Arc<Vec<ListedToken>>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::internal)?;
+        self.tokens
+            .get_listed_tokens(&mut storage)
+            .await
+            .map_err(Error::internal)
+    }
+
+    async fn token_allowed_for_fees(&self, token_id: TokenId) -> Result<bool, Error> {
+        let (sender, receiver) = oneshot::channel();
+        self.ticker_requests
+            .clone()
+            .send(Traced::new(TickerRequest::IsTokenAllowed {
+                token: TokenLike::Id(token_id),
+                response: sender,
+            }))
+            .await
+            .map_err(Error::internal)?;
+
+        receiver
+            .await
+            .map_err(Error::internal)?
+            .map_err(Error::internal)
+    }
+
+    /// Returns the listed tokens allowed to pay fees in. They are requested from the ticker
+    /// in bulk only when the list of tokens is reloaded, so browsing the tokens doesn't
+    /// delay the fee requests handled by the ticker.
+    async fn allowed_tokens(
+        &self,
+        listed: &Arc<Vec<ListedToken>>,
+    ) -> Result<Arc<HashSet<TokenId>>, Error> {
+        if let Some((cached_for, allowed)) = &*self.allowed_tokens.read().await {
+            if Arc::ptr_eq(cached_for, listed) {
+                return Ok(allowed.clone());
+            }
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        self.ticker_requests
+            .clone()
+            .send(Traced::new(TickerRequest::GetAllowedTokens {
+                tokens: listed.iter().map(|listed| listed.token.clone()).collect(),
+                response: sender,
+            }))
+            .await
+            .map_err(Error::internal)?;
+        let allowed = receiver
+            .await
+            .map_err(Error::internal)?
+            .map_err(Error::internal)?;
+
+        let allowed = Arc::new(allowed);
+        *self.allowed_tokens.write().await = Some((listed.clone(), allowed.clone()));
+        Ok(allowed)
+    }
+
+    /// Returns the tokens in ascending order of the IDs, starting from the token following the cursor.
+    async fn tokens_page(
+        &self,
+        cursor: Option<TokenId>,
+        limit: u32,
+    ) -> Result<Vec<TokenInfo>, Error> {
+        let listed = self.listed_tokens().await?;
+        let allowed = self.allowed_tokens(&listed).await?;
+        let page = listed
+            .iter()
+            .filter(|listed| cursor.map_or(true, |cursor| listed.token.id > cursor))
+            .take(limit as usize)
+            .map(|listed| {
+                let enabled_for_fees = allowed.contains(&listed.token.id);
+                TokenInfo::new(listed.token.clone(), enabled_for_fees, listed.listed_at)
+            })
+            .collect();
+
+        Ok(page)
+    }
+
+    async fn token(&self, token_like: TokenLike) -> Result<Option<TokenInfo>, Error> {
+        let token = {
+            let mut storage = self.pool.access_storage().await.map_err(Error::internal)?;
+            self.tokens
+                .get_token(&mut storage, token_like)
+                .await
+                .map_err(Error::internal)?
+        };
+        let token = match token {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        // Token could be listed after the list was cached, its listing time is unknown then
+        // and it's checked by the ticker separately.
+        let listed = self.listed_tokens().await?;
+        let info = match listed.iter().find(|listed| listed.token.id == token.id) {
+            Some(listed_token) => {
+                let enabled_for_fees = self.allowed_tokens(&listed).await?.contains(&token.id);
+                TokenInfo::new(token, enabled_for_fees, listed_token.listed_at)
+            }
+            None => {
+                let enabled_for_fees = self.token_allowed_for_fees(token.id).await?;
+                TokenInfo::new(token, enabled_for_fees, None)
+            }
+        };
+        Ok(Some(info))
+    }
+}
+
+// Server implementation
+
+/// The list of tokens changes rarely, so it can be cached by the clients and proxies
+/// for as long as it's cached by the server.
+async fn tokens(
+    data: web::Data<ApiTokensData>,
+    web::Query(query): web::Query<PaginationQuery<TokenId>>,
+) -> Result<HttpResponse, Error> {
+    validate_pagination(&query)?;
+
+    let tokens = data.tokens_page(query.cursor, query.limit).await?;
+    let page = Paginated::new(tokens, query.limit, |token| token.id);
+
+    Ok(HttpResponse::Ok()
+        .header(
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", LISTED_TOKENS_TTL.as_secs()),
+        )
+        .json(Response::ok(page)))
+}
+
+async fn token(
+    data: web::Data<ApiTokensData>,
+    web::Path(token_like): web::Path<String>,
+) -> JsonResult<Option<TokenInfo>> {
+    let token = data.token(TokenLike::parse(&token_like)).await?;
+
+    ok(token)
+}
+
+pub fn api_scope(
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+    ticker_requests: mpsc::Sender<Traced<TickerRequest>>,
+) -> Scope {
+    let data = ApiTokensData {
+        pool,
+        tokens,
+        ticker_requests,
+        allowed_tokens: Arc::default(),
+    };
+
+    web::scope("tokens")
+        .data(data)
+        .route("", web::get().to(self::tokens))
+        .route("{token}", web::get().to(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use super::{
+        super::{super::v1::test_utils::TestServerConfig, api_scope as api_v2_scope},
+        *,
+    };
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_tokens_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        // Walk through all the tokens using the cursors.
+        let mut tokens = Vec::new();
+        let mut uri = "/api/v2/tokens?limit=1".to_owned();
+        loop {
+            let req = test::TestRequest::get().uri(&uri).to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(
+                resp.headers().get(header::CACHE_CONTROL).unwrap(),
+                "public, max-age=60"
+            );
+
+            let page: Response<Paginated<TokenInfo, TokenId>> = test::read_body_json(resp).await;
+            let page = page.into_result().unwrap().unwrap();
+            tokens.extend(page.list);
+            match page.next_cursor {
+                Some(cursor) => uri = format!("/api/v2/tokens?limit=1&cursor={}", cursor),
+                None => break,
+            }
+        }
+
+        let expected_ids = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let mut ids: Vec<_> = storage
+                .tokens_schema()
+                .load_tokens()
+                .await?
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            ids.sort_unstable();
+            ids
+        };
+        let ids: Vec<_> = tokens.iter().map(|token| token.id).collect();
+        assert_eq!(ids, expected_ids);
+
+        // Fee ticker doesn't accept the fees in `PHNX`.
+        let phnx = tokens.iter().find(|token| token.symbol == "PHNX").unwrap();
+        assert!(!phnx.enabled_for_fees);
+        assert!(tokens[0].enabled_for_fees);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/tokens/PHNX")
+            .to_request();
+        let token: Response<TokenInfo> = test::read_response_json(&mut app, req).await;
+        assert_eq!(token.into_result().unwrap().as_ref(), Some(phnx));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! Transactions part of API v2 implementation.
+
+// Built-in uses
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+
+// Workspace uses
+use zksync_types::tx::TxHash;
+
+// Local uses
+use super::{
+    super::v1::transactions::{
+        ApiTransactionsData, BatchStatus, FastProcessingQuery, IncomingTx, IncomingTxBatch,
+        Receipt, SubmitBatchResponse, TxData,
+    },
+    ok, Error, JsonResult,
+};
+use crate::api_server::{
+    api_keys::rest_api_client,
+    tx_sender::{SubmitError, TxSender},
+};
+
+impl From<SubmitError> for Error {
+    fn from(inner: SubmitError) -> Self {
+        let mut error = Error::new(inner.error_code(), &inner);
+        error.body.retry_after = inner.retry_after().map(|delay| delay.as_secs());
+        error.body.data = inner.details();
+        error
+    }
+}
+
+// Server implementation
+
+async fn tx_receipt(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> JsonResult<Option<Receipt>> {
+    let receipt = data.tx_status(tx_hash).await.map_err(Error::internal)?;
+
+    ok(receipt)
+}
+
+async fn tx_data(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> JsonResult<Option<TxData>> {
+    let tx_data = data.tx_data(tx_hash).await.map_err(Error::internal)?;
+
+    ok(tx_data.map(TxData::from))
+}
+
+async fn batch_status(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(batch_hash): web::Path<TxHash>,
+) -> JsonResult<Option<BatchStatus>> {
+    let batch_status = data
+        .batch_status(batch_hash)
+        .await
+        .map_err(Error::internal)?;
+
+    ok(batch_status)
+}
+
+async fn submit_tx(
+    req: HttpRequest,
+    data: web::Data<ApiTransactionsData>,
+    Json(body): Json<IncomingTx>,
+    web::Query(query): web::Query<FastProcessingQuery>,
+) -> JsonResult<TxHash> {
+    let api_client = rest_api_client(&req);
+    let tx_hash = data
+        .tx_sender
+        .submit_tx(
+            body.tx,
+            body.signature,
+            query.fast_processing,
+            api_client.as_deref(),
+        )
+        .await?;
+
+    ok(tx_hash)
+}
+
+async fn submit_tx_batch(
+    data: web::Data<ApiTransactionsData>,
+    Json(body): Json<IncomingTxBatch>,
+) -> JsonResult<SubmitBatchResponse> {
+    let txs = body.txs.into_iter().zip(std::iter::repeat(None)).collect();
+
+    let transaction_hashes = data.tx_sender.submit_txs_batch(txs, body.signature).await?;
+
+    ok(SubmitBatchResponse {
+        batch_hash: TxHash::batch_hash(&transaction_hashes),
+        transaction_hashes,
+    })
+}
+
+pub fn api_scope(tx_sender: TxSender) -> Scope {
+    let data = ApiTransactionsData::new(tx_sender);
+
+    web::scope("transactions")
+        .data(data)
+        .route("submit", web::post().to(submit_tx))
+        .route("submit/batch", web::post().to(submit_tx_batch))
+        .route("batches/{batch_hash}", web::get().to(batch_status))
+        .route("{tx_hash}", web::get().to(tx_receipt))
+        .route("{tx_hash}/data", web::get().to(tx_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::Value;
+    use zksync_types::{BlockNumber, SignedZkSyncTx};
+
+    use super::{
+        super::{
+            super::v1::test_utils::TestServerConfig, api_scope as api_v2_scope, ErrorCode, Response,
+        },
+        *,
+    };
+    use crate::api_server::helpers::try_parse_tx_hash;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_transactions_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let committed_tx_hash = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let transactions = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            try_parse_tx_hash(&transactions[0].tx_hash).unwrap()
+        };
+
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        // Receipt and data of the committed transaction.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/transactions/{}", committed_tx_hash))
+            .to_request();
+        let receipt: Response<Receipt> = test::read_response_json(&mut app, req).await;
+        assert_eq!(
+            receipt.into_result().unwrap(),
+            Some(Receipt::Verified {
+                block: BlockNumber(1)
+            })
+        );
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/transactions/{}/data", committed_tx_hash))
+            .to_request();
+        let tx_data: Response<TxData> = test::read_response_json(&mut app, req).await;
+        let tx_data = tx_data.into_result().unwrap().unwrap();
+        assert_eq!(SignedZkSyncTx::from(tx_data).hash(), committed_tx_hash);
+
+        // Unknown transaction has the `null` result.
+        let unknown_tx_hash = TestServerConfig::gen_zk_txs(1).txs[1].0.hash();
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v2/transactions/{}", unknown_tx_hash))
+            .to_request();
+        let receipt: Response<Receipt> = test::read_response_json(&mut app, req).await;
+        assert_eq!(receipt.into_result().unwrap(), None);
+
+        // Submission errors are returned in the envelope with their codes.
+        let tx = TestServerConfig::gen_zk_txs(100).txs[0].0.clone();
+        let req = test::TestRequest::post()
+            .uri("/api/v2/transactions/submit?fastProcessing=true")
+            .set_json(&IncomingTx {
+                tx,
+                signature: None,
+            })
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(
+            error.error_code(),
+            Some(ErrorCode::UnsupportedFastProcessing)
+        );
+
+        // Malformed hashes are rejected.
+        let req = test::TestRequest::get()
+            .uri("/api/v2/transactions/not_a_hash")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Response<Value> = test::read_body_json(resp).await;
+        let error = body.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Webhooks part of API v2 implementation.
+//!
+//! Webhooks are registered by the integrators authenticated by the API key, and the events
+//! are delivered by the webhook notifier of the Core server.
+
+// Built-in uses
+
+// External uses
+use actix_web::{web, HttpRequest, Scope};
+
+// Workspace uses
+use zksync_api_client::rest::v2::{NewWebhook, Webhook, MAX_WEBHOOKS_PER_CLIENT};
+use zksync_storage::{webhooks::records::StoredWebhookSubscription, ConnectionPool};
+use zksync_types::Address;
+
+// Local uses
+use super::{ok, Error, ErrorCode, JsonResult};
+use crate::api_server::api_keys::rest_api_client;
+
+/// Shared data between `api/v2/webhooks` endpoints.
+#[derive(Clone)]
+struct ApiWebhooksData {
+    pool: ConnectionPool,
+}
+
+fn webhook(subscription: StoredWebhookSubscription) -> Webhook {
+    Webhook {
+        id: subscription.id as u64,
+        address: Address::from_slice(&subscription.address),
+        url: subscription.url,
+        secret: None,
+        created_at: subscription.created_at,
+    }
+}
+
+/// Returns the name of the integrator, webhooks are only available with an API key.
+fn client_name(req: &HttpRequest) -> Result<String, Error> {
+    rest_api_client(req)
+        .map(|client| client.name.clone())
+        .ok_or_else(|| Error::new(ErrorCode::Unauthorized, "Webhooks require an API key"))
+}
+
+/// Generates the key of the event signatures.
+fn generate_secret() -> String {
+    hex::encode(zksync_crypto::rand::random::<[u8; 32]>())
+}
+
+// Server implementation
+
+async fn webhooks(data: web::Data<ApiWebhooksData>, req: HttpRequest) -> JsonResult<Vec<Webhook>> {
+    let client = client_name(&req)?;
+
+    let mut storage = data.pool.access_storage().await.map_err(Error::internal)?;
+    let subscriptions = storage
+        .webhooks_schema()
+        .load_client_subscriptions(&client)
+        .await
+        .map_err(Error::internal)?;
+
+    ok(subscriptions.into_iter().map(webhook).collect())
+}
+
+/// The secret of the webhook is only returned in the response to this request.
+async fn add_webhook(
+    data: web::Data<ApiWebhooksData>,
+    req: HttpRequest,
+    web::Json(new_webhook): web::Json<NewWebhook>,
+) -> JsonResult<Webhook> {
+    let client = client_name(&req)?;
+    new_webhook.validate().map_err(Error::invalid_params)?;
+
+    let mut storage = data.pool.access_storage().await.map_err(Error::internal)?;
+    let mut transaction = storage.start_transaction().await.map_err(Error::internal)?;
+    let registered = transaction
+        .webhooks_schema()
+        .load_client_subscriptions(&client)
+        .await
+        .map_err(Error::internal)?;
+    if registered.len() >= MAX_WEBHOOKS_PER_CLIENT {
+        return Err(Error::invalid_params(format!(
+            "Integrator can't register more than {} webhooks",
+            MAX_WEBHOOKS_PER_CLIENT
+        )));
+    }
+
+    let secret = generate_secret();
+    let subscription = transaction
+        .webhooks_schema()
+        .add_subscription(&client, new_webhook.address, &new_webhook.url, &secret)
+        .await
+        .map_err(Error::internal)?;
+    transaction.commit().await.map_err(Error::internal)?;
+
+    vlog::info!(
+        "Integrator {} has registered the webhook #{}",
+        client,
+        subscription.id
+    );
+    ok(Webhook {
+        secret: Some(secret),
+        ..webhook(subscription)
+    })
+}
+
+async fn remove_webhook(
+    data: web::Data<ApiWebhooksData>,
+    req: HttpRequest,
+    web::Path(id): web::Path<u64>,
+) -> JsonResult<()> {
+    let client = client_name(&req)?;
+
+    let mut storage = data.pool.access_storage().await.map_err(Error::internal)?;
+    let removed = storage
+        .webhooks_schema()
+        .remove_subscription(&client, id as i64)
+        .await
+        .map_err(Error::internal)?;
+    if !removed {
+        return Err(Error::invalid_params("Unknown webhook"));
+    }
+
+    ok(())
+}
+
+pub fn api_scope(pool: ConnectionPool) -> Scope {
+    let data = ApiWebhooksData { pool };
+
+    web::scope("webhooks")
+        .data(data)
+        .route("", web::get().to(webhooks))
+        .route("", web::post().to(add_webhook))
+        .route("{id}", web::delete().to(remove_webhook))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::{http::StatusCode, test, App, HttpMessage};
+    use serde_json::Value;
+
+    use super::{
+        super::{super::v1::test_utils::TestServerConfig, api_scope as api_v2_scope, Response},
+        *,
+    };
+    use crate::api_server::api_keys::ApiKeys;
+    use zksync_config::configs::api::{ApiAuth, ApiKey};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn test_webhooks_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let api_keys = ApiKeys::from_config(&ApiAuth {
+            require_api_key: false,
+            api_keys: Some(vec![ApiKey {
+                name: "webhooks-test".into(),
+                key: "webhooks-test-key".into(),
+                hourly_quota: 0,
+            }]),
+        });
+        let client = api_keys.authenticate("webhooks-test-key").unwrap();
+        let mut app =
+            test::init_service(App::new().service(api_v2_scope(cfg.tx_sender(), &cfg.config)))
+                .await;
+
+        // Requests without the API key are rejected.
+        let req = test::TestRequest::get()
+            .uri("/api/v2/webhooks")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let authorized = |req: test::TestRequest| {
+            let req = req.to_request();
+            req.extensions_mut().insert(Arc::clone(&client));
+            req
+        };
+
+        let new_webhook = NewWebhook {
+            address: Address::random(),
+            url: "https://wallet.io/hook".into(),
+        };
+        let req = authorized(
+            test::TestRequest::post()
+                .uri("/api/v2/webhooks")
+                .set_json(&new_webhook),
+        );
+        let added: Response<Webhook> = test::read_response_json(&mut app, req).await;
+        let added = added.into_result().unwrap().unwrap();
+        assert_eq!(added.address, new_webhook.address);
+        assert_eq!(added.url, new_webhook.url);
+        assert!(added.secret.is_some());
+
+        // Secret is not returned once the webhook is registered.
+        let req = authorized(test::TestRequest::get().uri("/api/v2/webhooks"));
+        let webhooks: Response<Vec<Webhook>> = test::read_response_json(&mut app, req).await;
+        let webhooks = webhooks.into_result().unwrap().unwrap();
+        assert_eq!(
+            webhooks.last(),
+            Some(&Webhook {
+                secret: None,
+                ..added.clone()
+            })
+        );
+
+        let req = authorized(test::TestRequest::post().uri("/api/v2/webhooks").set_json(
+            &NewWebhook {
+                url: "ftp://wallet.io/hook".into(),
+                ..new_webhook
+            },
+        ));
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let uri = format!("/api/v2/webhooks/{}", added.id);
+        let req = authorized(test::TestRequest::delete().uri(&uri));
+        let resp: Response<Value> = test::read_response_json(&mut app, req).await;
+        assert!(resp.into_result().is_ok());
+
+        // Webhook can't be removed twice.
+        let req = authorized(test::TestRequest::delete().uri(&uri));
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Validation of the JSON RPC batch requests.
+//!
+//! Calls of the batch are dispatched concurrently by the `jsonrpc_core` and the responses are
+//! returned in the same order. This middleware only rejects the batches not allowed by the
+//! JSON RPC 2.0 spec (empty ones) and the batches exceeding the configured size, so a single
+//! HTTP request can't make the server execute an unbounded number of calls.
+
+// Built-in uses
+
+// External uses
+use jsonrpc_core::{
+    futures::future::{self, Either, FutureResult},
+    Error, ErrorCode, Failure, Id, Metadata, Middleware, Output, Request, Response, Version,
+};
+
+// Workspace uses
+
+// Local uses
+
+#[derive(Debug, Clone, Copy)]
+pub struct BatchLimiter {
+    max_batch_size: usize,
+}
+
+impl BatchLimiter {
+    pub fn new(max_batch_size: usize) -> Self {
+        Self { max_batch_size }
+    }
+
+    fn reject(error: Error) -> Option<Response> {
+        // The request id can't be determined for the batch, so `null` is used as the spec requires.
+        Some(Response::Single(Output::Failure(Failure {
+            jsonrpc: Some(Version::V2),
+            error,
+            id: Id::Null,
+        })))
+    }
+}
+
+impl<M: Metadata> Middleware<M> for BatchLimiter {
+    type Future = FutureResult<Option<Response>, ()>;
+    type CallFuture = FutureResult<Option<Output>, ()>;
+
+    fn on_request<F, X>(&self, request: Request, meta: M, next: F) -> Either<Self::Future, X>
+    where
+        F: Fn(Request, M) -> X + Send + Sync,
+        X: jsonrpc_core::futures::Future<Item = Option<Response>, Error = ()> + Send + 'static,
+    {
+        match &request {
+            Request::Batch(calls) if calls.is_empty() => {
+                Either::A(future::ok(Self::reject(Error::invalid_request())))
+            }
+            Request::Batch(calls) if calls.len() > self.max_batch_size => {
+                let error = Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: format!(
+                        "Batch contains {} calls, the limit is {}",
+                        calls.len(),
+                        self.max_batch_size
+                    ),
+                    data: None,
+                };
+                Either::A(future::ok(Self::reject(error)))
+            }
+            _ => Either::B(next(request, meta)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_core::{MetaIoHandler, Params, Value};
+    use serde_json::json;
+
+    use super::*;
+
+    fn io_handler(max_batch_size: usize) -> MetaIoHandler<(), BatchLimiter> {
+        let mut io = MetaIoHandler::with_middleware(BatchLimiter::new(max_batch_size));
+        io.add_method("echo", |params: Params| {
+            let (value,): (Value,) = params.parse()?;
+            Ok(value)
+        });
+        io
+    }
+
+    fn handle(io: &MetaIoHandler<(), BatchLimiter>, request: Value) -> Value {
+        let response = io
+            .handle_request_sync(&request.to_string(), ())
+            .expect("No response");
+        serde_json::from_str(&response).unwrap()
+    }
+
+    fn call(id: u64, value: u64) -> Value {
+        json!({ "jsonrpc": "2.0", "method": "echo", "params": [value], "id": id })
+    }
+
+    #[test]
+    fn batch_is_dispatched() {
+        let io = io_handler(2);
+
+        let response = handle(&io, json!([call(1, 10), call(2, 20)]));
+        assert_eq!(
+            response,
+            json!([
+                { "jsonrpc": "2.0", "result": 10, "id": 1 },
+                { "jsonrpc": "2.0", "result": 20, "id": 2 },
+            ])
+        );
+
+        // Single calls are not affected.
+        let response = handle(&io, call(3, 30));
+        assert_eq!(response, json!({ "jsonrpc": "2.0", "result": 30, "id": 3 }));
+    }
+
+    #[test]
+    fn invalid_batches_are_rejected() {
+        let io = io_handler(2);
+
+        for request in vec![json!([]), json!([call(1, 10), call(2, 20), call(3, 30)])] {
+            let response = handle(&io, request);
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(
+                response["error"]["code"],
+                json!(ErrorCode::InvalidRequest.code())
+            );
+        }
+    }
+}
@@ -1,88 +1,177 @@
+// Built-in uses
+use std::time::Duration;
 // External uses
 use jsonrpc_core::ErrorCode;
+use serde_json::Value;
 // Workspace uses
+use zksync_api_client::rest::v1::ErrorCode as ApiErrorCode;
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, tx_error::TxAddError};
+use crate::{
+    api_server::tx_sender::SubmitError, tx_error::TxAddError, utils::ens_resolver::EnsError,
+};
 
-#[derive(Debug, Clone, Copy)]
-pub enum RpcErrorCodes {
-    NonceMismatch = 101,
-    IncorrectTx = 103,
-    FeeTooLow = 104,
-    InappropriateFeeToken = 105,
+/// Converts the API error code into the JSON RPC one.
+///
+/// The API codes match the JSON RPC ones, except for the errors which used to be reported
+/// with the more general JSON RPC codes. These errors keep their legacy codes for compatibility,
+/// and are distinguished by the `codeName` field of the error data.
+pub fn rpc_error_code(code: ApiErrorCode) -> ErrorCode {
+    let code = match code {
+        ApiErrorCode::BatchFeeTooLow => ApiErrorCode::FeeTooLow,
+        ApiErrorCode::EmptyBatch
+        | ApiErrorCode::BatchTooBig
+        | ApiErrorCode::BatchWithdrawalsOverload
+        | ApiErrorCode::StorageUnavailable
+        | ApiErrorCode::CommunicationCoreServer => ApiErrorCode::Other,
+        code => code,
+    };
+    (code.as_u64() as i64).into()
+}
 
-    MissingEthSignature = 200,
-    EIP1271SignatureVerificationFail = 201,
-    IncorrectEthSignature = 202,
-    ChangePkNotAuthorized = 203,
+/// Adds the retryability of the error to its data. Non-object data is moved
+/// to the `reason` field.
+fn with_retry_info(data: Option<Value>, retry_after: Option<Duration>) -> Value {
+    let mut data = match data {
+        Some(Value::Object(object)) => object,
+        Some(reason) => {
+            let mut object = serde_json::Map::new();
+            object.insert("reason".to_owned(), reason);
+            object
+        }
+        None => serde_json::Map::new(),
+    };
+    data.insert("retryable".to_owned(), retry_after.is_some().into());
+    if let Some(retry_after) = retry_after {
+        data.insert("retryAfter".to_owned(), retry_after.as_secs().into());
+    }
+    Value::Object(data)
+}
 
-    Other = 300,
-    AccountCloseDisabled = 301,
-    OperationsLimitReached = 302,
-    UnsupportedFastProcessing = 303,
+/// Error returned for the requests exceeding the rate limit of the client IP address.
+pub fn rate_limit_exceeded(retry_after: Duration) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: rpc_error_code(ApiErrorCode::RateLimitExceeded),
+        message: "Too many requests".to_owned(),
+        data: Some(with_retry_info(None, Some(retry_after))),
+    }
 }
 
-impl From<TxAddError> for RpcErrorCodes {
-    fn from(error: TxAddError) -> Self {
-        match error {
-            TxAddError::NonceMismatch => Self::NonceMismatch,
-            TxAddError::IncorrectTx => Self::IncorrectTx,
-            TxAddError::TxFeeTooLow => Self::FeeTooLow,
-            TxAddError::TxBatchFeeTooLow => Self::FeeTooLow,
-            TxAddError::MissingEthSignature => Self::MissingEthSignature,
-            TxAddError::EIP1271SignatureVerificationFail => Self::EIP1271SignatureVerificationFail,
-            TxAddError::IncorrectEthSignature => Self::IncorrectEthSignature,
-            TxAddError::ChangePkNotAuthorized => Self::ChangePkNotAuthorized,
-            TxAddError::Other => Self::Other,
-            TxAddError::DbError => Self::Other,
-            TxAddError::EmptyBatch => Self::Other,
-            TxAddError::BatchTooBig => Self::Other,
-            TxAddError::BatchWithdrawalsOverload => Self::Other,
-        }
+/// Error returned for the requests with an unknown API key.
+pub fn invalid_api_key() -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: rpc_error_code(ApiErrorCode::Unauthorized),
+        message: "Invalid API key".to_owned(),
+        data: Some(with_retry_info(None, None)),
     }
 }
 
-impl Into<ErrorCode> for RpcErrorCodes {
-    fn into(self) -> ErrorCode {
-        (self as i64).into()
+impl From<EnsError> for jsonrpc_core::Error {
+    fn from(inner: EnsError) -> Self {
+        match inner {
+            EnsError::Resolution(err) => {
+                vlog::warn!("Unable to resolve the ENS name: {}", err);
+                Self::internal_error()
+            }
+            err => Self::invalid_params(err.to_string()),
+        }
     }
 }
 
 impl From<SubmitError> for jsonrpc_core::Error {
     fn from(inner: SubmitError) -> Self {
-        match inner {
+        let code_name = inner.error_code().name();
+        let code = rpc_error_code(inner.error_code());
+        let retry_after = inner.retry_after();
+        let data = inner.details();
+        let mut error = match inner {
             SubmitError::AccountCloseDisabled => Self {
-                code: RpcErrorCodes::AccountCloseDisabled.into(),
+                code,
                 message: "Account close tx is disabled.".to_string(),
                 data: None,
             },
 
             SubmitError::InvalidParams(msg) => Self::invalid_params(msg),
             SubmitError::UnsupportedFastProcessing => Self {
-                code: RpcErrorCodes::UnsupportedFastProcessing.into(),
+                code,
                 message: "Fast processing available only for 'withdraw' operation type."
                     .to_string(),
                 data: None,
             },
+            SubmitError::FastProcessingDisabled => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::TokenTxRestricted { .. } => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::Paused(_) => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::Maintenance(_) => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::IncorrectTx(message) => Self {
-                code: RpcErrorCodes::IncorrectTx.into(),
+                code,
                 message,
                 data: None,
             },
             SubmitError::TxAdd(inner) => Self {
-                code: RpcErrorCodes::from(inner).into(),
+                code,
                 message: inner.to_string(),
                 data: None,
             },
+            // Messages are the same as the ones of the corresponding `TxAddError`.
+            SubmitError::FeeTooLow { .. } => Self {
+                code,
+                message: TxAddError::TxFeeTooLow.to_string(),
+                data: None,
+            },
+            SubmitError::BatchFeeTooLow { .. } => Self {
+                code,
+                message: TxAddError::TxBatchFeeTooLow.to_string(),
+                data: None,
+            },
             SubmitError::InappropriateFeeToken => Self {
-                code: RpcErrorCodes::InappropriateFeeToken.into(),
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::BatchExecutionFailed { .. } => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::CosignatureRejected => Self {
+                code,
                 message: inner.to_string(),
                 data: None,
             },
-            SubmitError::CommunicationCoreServer(reason) => Self {
-                code: RpcErrorCodes::Other.into(),
+            SubmitError::CommunicationCoreServer(_) => Self {
+                code,
                 message: "Error communicating core server".to_string(),
-                data: Some(reason.into()),
+                data: None,
+            },
+            SubmitError::CoreServerUnavailable | SubmitError::CosignerUnavailable(_) => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::RateLimited { .. } => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::ApiKeyRequired | SubmitError::ApiKeyQuotaExceeded { .. } => Self {
+                code,
+                message: inner.to_string(),
+                data: None,
             },
             SubmitError::Internal(msg) => Self {
                 code: ErrorCode::InternalError,
@@ -94,6 +183,47 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message,
                 data: None,
             },
+        };
+        let mut data = with_retry_info(data.or(error.data), retry_after);
+        data["codeName"] = code_name.into();
+        error.data = Some(data);
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_error_codes() {
+        let cases = vec![
+            (SubmitError::TxAdd(TxAddError::NonceMismatch), 101),
+            (SubmitError::TxAdd(TxAddError::IncorrectTx), 103),
+            (SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow), 104),
+            (SubmitError::TxAdd(TxAddError::EmptyBatch), 300),
+            (SubmitError::TxAdd(TxAddError::DbError), 300),
+            (SubmitError::InappropriateFeeToken, 105),
+            (SubmitError::TxAdd(TxAddError::MissingEthSignature), 200),
+            (SubmitError::TxAdd(TxAddError::ChangePkNotAuthorized), 203),
+            (SubmitError::AccountCloseDisabled, 301),
+            (SubmitError::UnsupportedFastProcessing, 303),
+            (SubmitError::CommunicationCoreServer("timeout".into()), 300),
+            // Code of the newer error doesn't collide with the legacy ones.
+            (
+                SubmitError::BatchExecutionFailed {
+                    tx_index: 1,
+                    reason: "Not enough balance".into(),
+                },
+                114,
+            ),
+        ];
+
+        for (error, code) in cases {
+            let code_name = error.error_code().name();
+            let error = jsonrpc_core::Error::from(error);
+            assert_eq!(error.code, ErrorCode::ServerError(code), "{}", code_name);
+            assert_eq!(error.data.unwrap()["codeName"], code_name);
         }
     }
 }
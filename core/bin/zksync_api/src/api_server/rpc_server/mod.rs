@@ -6,8 +6,9 @@ use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use jsonrpc_core::{Error, IoHandler, MetaIoHandler, Metadata, Middleware, Result};
+use jsonrpc_core::{Error, MetaIoHandler, Metadata, Middleware, Result};
 use jsonrpc_http_server::ServerBuilder;
+use vlog::Traced;
 
 // Workspace uses
 use zksync_config::ZkSyncConfig;
@@ -29,14 +30,19 @@ use crate::{
 use bigdecimal::BigDecimal;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+mod batch;
 pub mod error;
 mod rpc_impl;
 mod rpc_trait;
 pub mod types;
 
+use self::batch::BatchLimiter;
 pub use self::rpc_trait::Rpc;
 use self::types::*;
-use super::tx_sender::TxSender;
+use super::{
+    api_keys::ApiKeys, event_notify::EventNotifierRequest, proxy, rate_limit::RateLimits, tls,
+    tx_sender::TxSender,
+};
 
 #[derive(Clone)]
 pub struct RpcApp {
@@ -50,13 +56,17 @@ pub struct RpcApp {
     pub confirmations_for_eth_event: u64,
 
     tx_sender: TxSender,
+    api_keys: ApiKeys,
 }
 
 impl RpcApp {
     pub fn new(
         connection_pool: ConnectionPool,
-        sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
-        ticker_request_sender: mpsc::Sender<TickerRequest>,
+        sign_verify_request_sender: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+        ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
+        event_notifier: mpsc::Sender<EventNotifierRequest>,
+        rate_limits: &RateLimits,
+        api_keys: ApiKeys,
         config: &ZkSyncConfig,
     ) -> Self {
         let runtime_handle = tokio::runtime::Handle::try_current()
@@ -70,7 +80,9 @@ impl RpcApp {
             sign_verify_request_sender,
             ticker_request_sender,
             config,
-        );
+        )
+        .with_event_notifier(event_notifier)
+        .with_rate_limits(rate_limits);
 
         RpcApp {
             runtime_handle,
@@ -83,6 +95,7 @@ impl RpcApp {
             confirmations_for_eth_event,
 
             tx_sender,
+            api_keys,
         }
     }
 
@@ -242,15 +255,15 @@ impl RpcApp {
     }
 
     async fn token_allowed_for_fees(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         token: TokenLike,
     ) -> Result<bool> {
         let (sender, receiver) = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::IsTokenAllowed {
+            .send(Traced::new(TickerRequest::IsTokenAllowed {
                 token: token.clone(),
                 response: sender,
-            })
+            }))
             .await
             .expect("ticker receiver dropped");
         receiver
@@ -263,17 +276,17 @@ impl RpcApp {
     }
 
     async fn ticker_batch_fee_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
     ) -> Result<BatchFee> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetBatchTxFee {
+            .send(Traced::new(TickerRequest::GetBatchTxFee {
                 transactions,
                 token: token.clone(),
                 response: req.0,
-            })
+            }))
             .await
             .expect("ticker receiver dropped");
         let resp = req.1.await.expect("ticker answer sender dropped");
@@ -284,19 +297,19 @@ impl RpcApp {
     }
 
     async fn ticker_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         tx_type: TxFeeTypes,
         address: Address,
         token: TokenLike,
     ) -> Result<Fee> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetTxFee {
+            .send(Traced::new(TickerRequest::GetTxFee {
                 tx_type,
                 address,
                 token: token.clone(),
                 response: req.0,
-            })
+            }))
             .await
             .expect("ticker receiver dropped");
         let resp = req.1.await.expect("ticker answer sender dropped");
@@ -312,17 +325,17 @@ impl RpcApp {
     }
 
     async fn ticker_price_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         token: TokenLike,
         req_type: TokenPriceRequestType,
     ) -> Result<BigDecimal> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetTokenPrice {
+            .send(Traced::new(TickerRequest::GetTokenPrice {
                 token: token.clone(),
                 response: req.0,
                 req_type,
-            })
+            }))
             .await
             .expect("ticker receiver dropped");
         let resp = req.1.await.expect("ticker answer sender dropped");
@@ -408,30 +421,49 @@ impl RpcApp {
 #[allow(clippy::too_many_arguments)]
 pub fn start_rpc_server(
     connection_pool: ConnectionPool,
-    sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
-    ticker_request_sender: mpsc::Sender<TickerRequest>,
+    sign_verify_request_sender: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+    ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
+    event_notifier: mpsc::Sender<EventNotifierRequest>,
+    rate_limits: RateLimits,
+    api_keys: ApiKeys,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
     let addr = config.api.json_rpc.http_bind_addr();
+    let max_batch_size = config.api.json_rpc.max_batch_size;
+    let tls_acceptor =
+        tls::acceptor(&config.api.tls).expect("Invalid TLS configuration of the API servers");
+    let trust_forwarded = config.api.rate_limit.use_forwarded_ip;
 
     let rpc_app = RpcApp::new(
         connection_pool,
         sign_verify_request_sender,
         ticker_request_sender,
+        event_notifier,
+        &rate_limits,
+        api_keys,
         &config,
     );
+    let rate_limits = rate_limits.behind_proxy();
+    let (bound_addr_sender, bound_addr_receiver) = std::sync::mpsc::channel();
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_notify);
-        let mut io = IoHandler::new();
+        let mut io = MetaIoHandler::with_middleware(BatchLimiter::new(max_batch_size));
         rpc_app.extend(&mut io);
 
         let server = ServerBuilder::new(io)
+            .request_middleware(rate_limits)
             .threads(super::THREADS_PER_SERVER)
-            .start_http(&addr)
+            .start_http(&proxy::upstream_bind_addr())
             .unwrap();
+        bound_addr_sender.send(*server.address()).ok();
         server.wait();
     });
+
+    let upstream = bound_addr_receiver
+        .recv()
+        .expect("JSON RPC HTTP server has failed to start");
+    proxy::start_proxy(addr, tls_acceptor, upstream, trust_forwarded);
 }
 
 #[cfg(test)]
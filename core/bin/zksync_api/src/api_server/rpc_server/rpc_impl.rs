@@ -4,8 +4,9 @@ use std::time::Instant;
 use bigdecimal::BigDecimal;
 use jsonrpc_core::{Error, Result};
 // Workspace uses
+use zksync_api_client::rest::v1::ErrorCode as ApiErrorCode;
 use zksync_types::{
-    tx::{TxEthSignature, TxHash},
+    tx::{PackedEthSignature, TxEthSignature, TxHash},
     Address, BatchFee, Fee, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -15,9 +16,11 @@ use crate::{api_server::tx_sender::SubmitError, fee_ticker::TokenPriceRequestTyp
 use super::{error::*, types::*, RpcApp};
 
 impl RpcApp {
-    pub async fn _impl_account_info(self, address: Address) -> Result<AccountInfoResp> {
+    pub async fn _impl_account_info(self, address: String) -> Result<AccountInfoResp> {
         let start = Instant::now();
 
+        let address = self.tx_sender.ens_resolver.parse_address(&address).await?;
+
         let account_state = self.get_account_state(address).await?;
 
         let depositing_ops = self.get_ongoing_deposits_impl(address).await?;
@@ -96,11 +99,21 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        api_key: Option<String>,
     ) -> Result<TxHash> {
         let start = Instant::now();
+        let api_client = match api_key {
+            Some(api_key) => Some(
+                self.api_keys
+                    .authenticate(&api_key)
+                    .ok_or_else(invalid_api_key)?,
+            ),
+            None => None,
+        };
+
         let result = self
             .tx_sender
-            .submit_tx(*tx, *signature, fast_processing)
+            .submit_tx(*tx, *signature, fast_processing, api_client.as_deref())
             .await
             .map_err(Error::from);
         metrics::histogram!("api.rpc.tx_submit", start.elapsed());
@@ -201,6 +214,10 @@ impl RpcApp {
         token: TokenLike,
     ) -> Result<Fee> {
         let start = Instant::now();
+        if tx_type == TxFeeTypes::FastWithdraw {
+            self.tx_sender.ensure_fast_processing_enabled().await?;
+        }
+
         let ticker = self.tx_sender.ticker_requests.clone();
         let token_allowed = Self::token_allowed_for_fees(ticker.clone(), token.clone()).await?;
         if !token_allowed {
@@ -221,11 +238,14 @@ impl RpcApp {
         let start = Instant::now();
         if tx_types.len() != addresses.len() {
             return Err(Error {
-                code: RpcErrorCodes::IncorrectTx.into(),
+                code: rpc_error_code(ApiErrorCode::IncorrectTx),
                 message: "Number of tx_types must be equal to the number of addresses".to_string(),
                 data: None,
             });
         }
+        if tx_types.contains(&TxFeeTypes::FastWithdraw) {
+            self.tx_sender.ensure_fast_processing_enabled().await?;
+        }
 
         let ticker = self.tx_sender.ticker_requests.clone();
         let token_allowed = Self::token_allowed_for_fees(ticker.clone(), token.clone()).await?;
@@ -262,4 +282,63 @@ impl RpcApp {
         metrics::histogram!("api.rpc.get_eth_tx_for_withdrawal", start.elapsed());
         result
     }
+
+    pub async fn _impl_get_tx_message_to_sign(self, tx: Box<ZkSyncTx>) -> Result<Option<String>> {
+        let start = Instant::now();
+        let result = self
+            .tx_sender
+            .tx_message_to_sign(&tx)
+            .await
+            .map_err(Error::from);
+        metrics::histogram!("api.rpc.get_tx_message_to_sign", start.elapsed());
+        result
+    }
+
+    pub async fn _impl_get_cosigner(self, address: Address) -> Result<CosignerInfoResp> {
+        let start = Instant::now();
+        let registration = self
+            .access_storage()
+            .await?
+            .cosigners_schema()
+            .load_registration(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Unable to load the co-signer of {:?}: {}", address, err);
+                Error::internal_error()
+            })?;
+
+        let result = match registration {
+            Some(registration) => CosignerInfoResp {
+                cosigner: registration
+                    .cosigner
+                    .map(|cosigner| Address::from_slice(&cosigner)),
+                next_nonce: registration.registration_nonce as u32 + 1,
+            },
+            None => CosignerInfoResp {
+                cosigner: None,
+                next_nonce: 1,
+            },
+        };
+        metrics::histogram!("api.rpc.get_cosigner", start.elapsed());
+        Ok(result)
+    }
+
+    pub async fn _impl_register_cosigner(
+        self,
+        address: Address,
+        cosigner: Option<Address>,
+        nonce: u32,
+        signature: PackedEthSignature,
+        cosigner_signature: Option<PackedEthSignature>,
+    ) -> Result<bool> {
+        let start = Instant::now();
+        let result = self
+            .tx_sender
+            .register_cosigner(address, cosigner, nonce, signature, cosigner_signature)
+            .await
+            .map(|()| true)
+            .map_err(Error::from);
+        metrics::histogram!("api.rpc.register_cosigner", start.elapsed());
+        result
+    }
 }
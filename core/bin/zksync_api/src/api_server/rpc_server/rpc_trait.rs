@@ -7,7 +7,7 @@ use jsonrpc_derive::rpc;
 
 // Workspace uses
 use zksync_types::{
-    tx::{TxEthSignature, TxHash},
+    tx::{PackedEthSignature, TxEthSignature, TxHash},
     Address, BatchFee, Fee, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -18,8 +18,9 @@ pub type FutureResp<T> = Box<dyn futures01::Future<Item = T, Error = Error> + Se
 
 #[rpc]
 pub trait Rpc {
+    /// `addr` is either the account address or the ENS name resolving it.
     #[rpc(name = "account_info", returns = "AccountInfoResp")]
-    fn account_info(&self, addr: Address) -> FutureResp<AccountInfoResp>;
+    fn account_info(&self, addr: String) -> FutureResp<AccountInfoResp>;
 
     #[rpc(name = "ethop_info", returns = "ETHOpInfoResp")]
     fn ethop_info(&self, serial_id: u32) -> FutureResp<ETHOpInfoResp>;
@@ -27,12 +28,14 @@ pub trait Rpc {
     #[rpc(name = "tx_info", returns = "ETHOpInfoResp")]
     fn tx_info(&self, hash: TxHash) -> FutureResp<TransactionInfoResp>;
 
+    /// `api_key` of the trusted integrator may be required for the fast processing.
     #[rpc(name = "tx_submit", returns = "TxHash")]
     fn tx_submit(
         &self,
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        api_key: Option<String>,
     ) -> FutureResp<TxHash>;
 
     #[rpc(name = "submit_txs_batch", returns = "Vec<TxHash>")]
@@ -73,10 +76,32 @@ pub trait Rpc {
 
     #[rpc(name = "get_eth_tx_for_withdrawal", returns = "Option<String>")]
     fn get_eth_tx_for_withdrawal(&self, withdrawal_hash: TxHash) -> FutureResp<Option<String>>;
+
+    /// Returns the message the user has to sign with the Ethereum key to submit the transaction,
+    /// or `None` if the transaction doesn't require the Ethereum signature.
+    #[rpc(name = "get_tx_message_to_sign", returns = "Option<String>")]
+    fn get_tx_message_to_sign(&self, tx: Box<ZkSyncTx>) -> FutureResp<Option<String>>;
+
+    /// Returns the co-signer registered by the account for the 2-Factor authentication.
+    #[rpc(name = "get_cosigner", returns = "CosignerInfoResp")]
+    fn get_cosigner(&self, address: Address) -> FutureResp<CosignerInfoResp>;
+
+    /// Registers the co-signer of the account, or opts the account out of the co-signing
+    /// if `cosigner` is `null`. Changes of the existing registration must be signed
+    /// by the current co-signer as well.
+    #[rpc(name = "register_cosigner", returns = "bool")]
+    fn register_cosigner(
+        &self,
+        address: Address,
+        cosigner: Option<Address>,
+        nonce: u32,
+        signature: PackedEthSignature,
+        cosigner_signature: Option<PackedEthSignature>,
+    ) -> FutureResp<bool>;
 }
 
 impl Rpc for RpcApp {
-    fn account_info(&self, addr: Address) -> FutureResp<AccountInfoResp> {
+    fn account_info(&self, addr: String) -> FutureResp<AccountInfoResp> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move { handle.spawn(self_._impl_account_info(addr)).await.unwrap() };
@@ -107,12 +132,13 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        api_key: Option<String>,
     ) -> FutureResp<TxHash> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_tx_submit(tx, signature, fast_processing))
+                .spawn(self_._impl_tx_submit(tx, signature, fast_processing, api_key))
                 .await
                 .unwrap()
         };
@@ -218,4 +244,53 @@ impl Rpc for RpcApp {
         };
         Box::new(resp.boxed().compat())
     }
+
+    fn get_tx_message_to_sign(&self, tx: Box<ZkSyncTx>) -> FutureResp<Option<String>> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move {
+            handle
+                .spawn(self_._impl_get_tx_message_to_sign(tx))
+                .await
+                .unwrap()
+        };
+        Box::new(resp.boxed().compat())
+    }
+
+    fn get_cosigner(&self, address: Address) -> FutureResp<CosignerInfoResp> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move {
+            handle
+                .spawn(self_._impl_get_cosigner(address))
+                .await
+                .unwrap()
+        };
+        Box::new(resp.boxed().compat())
+    }
+
+    fn register_cosigner(
+        &self,
+        address: Address,
+        cosigner: Option<Address>,
+        nonce: u32,
+        signature: PackedEthSignature,
+        cosigner_signature: Option<PackedEthSignature>,
+    ) -> FutureResp<bool> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move {
+            handle
+                .spawn(self_._impl_register_cosigner(
+                    address,
+                    cosigner,
+                    nonce,
+                    signature,
+                    cosigner_signature,
+                ))
+                .await
+                .unwrap()
+        };
+        Box::new(resp.boxed().compat())
+    }
 }
@@ -6,10 +6,12 @@ use num::{BigUint, ToPrimitive};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
+use zksync_crypto::{serialization::FrSerde, Fr};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
-    tx::TxEthSignature, Account, AccountId, Address, Nonce, PriorityOp, PubKeyHash, TokenId,
-    ZkSyncPriorityOp, ZkSyncTx,
+    tx::{TxEthSignature, TxHash},
+    Account, AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, TokenId,
+    ZkSyncPriorityOp, ZkSyncTx, H256,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
@@ -157,6 +159,65 @@ pub struct TransactionInfoResp {
     pub block: Option<BlockInfo>,
 }
 
+/// Stage of the transaction processing reported to the `tx_status` subscribers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum TxStatusStage {
+    /// Transaction is accepted by the server and sent to the mempool.
+    Received,
+    /// Transaction is executed in the pending block.
+    Executed,
+    /// Block with the transaction is committed.
+    Committed,
+    /// Block with the transaction is verified.
+    Verified,
+}
+
+/// Notification sent to the `tx_status` subscribers once the transaction reaches the next stage.
+/// The subscription is finished once the transaction is verified or its execution fails.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatusNotification {
+    pub tx_hash: TxHash,
+    pub stage: TxStatusStage,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    pub block: Option<BlockInfo>,
+}
+
+impl TxStatusNotification {
+    /// Returns `true` if no more notifications follow this one.
+    pub fn is_final(&self) -> bool {
+        self.stage == TxStatusStage::Verified || self.success == Some(false)
+    }
+}
+
+/// Stage of the block processing reported to the `blocks` subscribers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockStage {
+    /// Pending block is sealed by the server and is going to be committed on L1.
+    Sealed,
+    /// Commit transaction of the block is confirmed on L1.
+    Committed,
+    /// Verify transaction of the block is confirmed on L1.
+    Verified,
+}
+
+/// Notification sent to the `blocks` subscribers once a block reaches the next stage.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockNotification {
+    pub block_number: BlockNumber,
+    pub stage: BlockStage,
+    #[serde(with = "FrSerde")]
+    pub new_state_root: Fr,
+    /// Hash of the L1 commit transaction, known once the block is committed.
+    pub commit_tx_hash: Option<H256>,
+    /// Hash of the L1 verify transaction, known once the block is verified.
+    pub verify_tx_hash: Option<H256>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ETHOpInfoResp {
@@ -171,6 +232,16 @@ pub struct ContractAddressResp {
     pub gov_contract: String,
 }
 
+/// Co-signer registered by the account for the 2-Factor authentication of its transactions.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CosignerInfoResp {
+    /// Co-signer of the account, `None` if the account isn't opted in to the co-signing.
+    pub cosigner: Option<Address>,
+    /// Nonce the next registration of the account must be signed with.
+    pub next_nonce: u32,
+}
+
 /// Flattened `PriorityOp` object representing a deposit operation.
 /// Used in the `OngoingDepositsResp`.
 #[derive(Debug, Serialize, Deserialize)]
@@ -8,14 +8,20 @@ use jsonrpc_core::{MetaIoHandler, Result};
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, PubSubHandler, Session, SubscriptionId};
 use jsonrpc_ws_server::RequestContext;
+use vlog::Traced;
 // Workspace uses
 use zksync_storage::ConnectionPool;
 use zksync_types::{tx::TxHash, ActionType, Address};
 // Local uses
 use crate::fee_ticker::TickerRequest;
 use crate::{
-    api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
-    api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
+    api_server::event_notify::{EventNotifierRequest, EventSubscribeRequest},
+    api_server::rpc_server::types::{
+        BlockNotification, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+        TxStatusNotification,
+    },
+    api_server::runtime_flags::MaintenanceInfo,
+    api_server::{api_keys::ApiKeys, proxy, rate_limit::RateLimits, tls},
     signature_checker::VerifyTxSignatureRequest,
 };
 use zksync_config::ZkSyncConfig;
@@ -79,6 +85,61 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "maintenance",
+        subscribe,
+        name = "maintenance_subscribe",
+        alias("maintenance_sub")
+    )]
+    fn subscribe_maintenance(&self, meta: Self::Metadata, subscriber: Subscriber<MaintenanceInfo>);
+    #[pubsub(
+        subscription = "maintenance",
+        unsubscribe,
+        name = "maintenance_unsubscribe"
+    )]
+    fn unsubscribe_maintenance(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "tx_status",
+        subscribe,
+        name = "tx_status_subscribe",
+        alias("tx_status_sub")
+    )]
+    fn subscribe_tx_status(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<TxStatusNotification>,
+        hash: TxHash,
+    );
+    #[pubsub(
+        subscription = "tx_status",
+        unsubscribe,
+        name = "tx_status_unsubscribe"
+    )]
+    fn unsubscribe_tx_status(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "blocks",
+        subscribe,
+        name = "blocks_subscribe",
+        alias("blocks_sub")
+    )]
+    fn subscribe_blocks(&self, meta: Self::Metadata, subscriber: Subscriber<BlockNotification>);
+    #[pubsub(subscription = "blocks", unsubscribe, name = "blocks_unsubscribe")]
+    fn unsubscribe_blocks(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -167,6 +228,78 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_maintenance(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<MaintenanceInfo>,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::Maintenance { subscriber },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_maintenance(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
+
+    fn subscribe_tx_status(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<TxStatusNotification>,
+        hash: TxHash,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(
+                EventSubscribeRequest::TransactionStatus { hash, subscriber },
+            ))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_tx_status(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
+
+    fn subscribe_blocks(&self, _meta: Self::Metadata, subscriber: Subscriber<BlockNotification>) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(EventSubscribeRequest::Blocks {
+                subscriber,
+            }))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_blocks(
+        &self,
+        _meta: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -176,33 +309,36 @@ struct RpcSubApp {
 #[allow(clippy::too_many_arguments)]
 pub fn start_ws_server(
     db_pool: ConnectionPool,
-    sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
-    ticker_request_sender: mpsc::Sender<TickerRequest>,
+    sign_verify_request_sender: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+    ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
+    event_sub_sender: mpsc::Sender<EventNotifierRequest>,
+    rate_limits: RateLimits,
+    api_keys: ApiKeys,
     panic_notify: mpsc::Sender<bool>,
     config: &ZkSyncConfig,
 ) {
     let addr = config.api.json_rpc.ws_bind_addr();
-
-    let (event_sub_sender, event_sub_receiver) = mpsc::channel(2048);
-
-    start_sub_notifier(
-        db_pool.clone(),
-        event_sub_receiver,
-        config.api.common.caches_size,
-        config.chain.state_keeper.miniblock_iteration_interval(),
-    );
+    let tls_acceptor =
+        tls::acceptor(&config.api.tls).expect("Invalid TLS configuration of the API servers");
+    let trust_forwarded = config.api.rate_limit.use_forwarded_ip;
 
     let req_rpc_app = super::rpc_server::RpcApp::new(
         db_pool,
         sign_verify_request_sender,
         ticker_request_sender,
+        event_sub_sender.clone(),
+        &rate_limits,
+        api_keys,
         config,
     );
 
+    let (bound_addr_sender, bound_addr_receiver) = std::sync::mpsc::channel();
     std::thread::spawn(move || {
         let _panic_sentinel = ThreadPanicNotify(panic_notify);
 
-        let mut io = PubSubHandler::new(MetaIoHandler::default());
+        let mut io = PubSubHandler::new(MetaIoHandler::with_middleware(
+            rate_limits.ws_session_limiter(),
+        ));
 
         req_rpc_app.extend(&mut io);
 
@@ -221,10 +357,17 @@ pub fn start_ws_server(
             |context: &RequestContext| Arc::new(Session::new(context.sender())),
         )
         .max_connections(1000)
+        .request_middleware(rate_limits.behind_proxy())
         .event_loop_executor(task_executor.executor())
-        .start(&addr)
+        .start(&proxy::upstream_bind_addr())
         .expect("Unable to start RPC ws server");
+        bound_addr_sender.send(*server.addr()).ok();
 
         server.wait().expect("rpc ws server start");
     });
+
+    let upstream = bound_addr_receiver
+        .recv()
+        .expect("JSON RPC WS server has failed to start");
+    proxy::start_proxy(addr, tls_acceptor, upstream, trust_forwarded);
 }
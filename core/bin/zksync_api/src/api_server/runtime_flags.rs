@@ -0,0 +1,172 @@
+//! Runtime flags switched by the operator via the admin API, as observed by the API server.
+//!
+//! Flags are checked for every submitted transaction, so they are loaded from the database
+//! with a single query and cached for a short time instead of being loaded on every request.
+
+// Built-in uses
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+// Workspace uses
+use zksync_storage::{
+    runtime_flags::{RuntimeFlag, RuntimeFlagState},
+    ConnectionPool, QueryResult, StorageProcessor,
+};
+
+/// Generation of the runtime flags, incremented every time the flags are switched through
+/// this process, so the switch is observed by all the caches of the process right away.
+static RUNTIME_FLAGS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Period the loaded flags are used for. This is the time the flags switched through
+/// another API server take to be picked up.
+pub const RUNTIME_FLAGS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Scheduled maintenance announced by the operator. While the maintenance is in progress,
+/// new transactions are rejected, but the rest of the API keeps working.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceInfo {
+    /// Whether the maintenance is in progress.
+    pub enabled: bool,
+    /// Message of the operator describing the maintenance.
+    pub message: Option<String>,
+    /// Expected end of the maintenance.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceInfo {
+    /// Creates the maintenance info from the state of the flag as of `now`. Maintenance is over
+    /// once its expected end has passed, even if the operator hasn't disabled it.
+    pub fn from_state(state: RuntimeFlagState, now: DateTime<Utc>) -> Self {
+        let expired = state.until.map_or(false, |until| until <= now);
+        Self {
+            enabled: state.enabled && !expired,
+            message: state.reason,
+            until: state.until,
+        }
+    }
+
+    pub async fn load(storage: &mut StorageProcessor<'_>) -> QueryResult<Self> {
+        let state = storage
+            .runtime_flags_schema()
+            .load_state(RuntimeFlag::Maintenance)
+            .await?;
+        Ok(Self::from_state(state, Utc::now()))
+    }
+}
+
+/// Values of the runtime flags loaded at once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeFlagsSnapshot {
+    pub fast_processing: RuntimeFlagState,
+    pub paused: RuntimeFlagState,
+    maintenance: RuntimeFlagState,
+}
+
+impl RuntimeFlagsSnapshot {
+    pub async fn load(storage: &mut StorageProcessor<'_>) -> QueryResult<Self> {
+        let states = storage
+            .runtime_flags_schema()
+            .load_states(&[
+                RuntimeFlag::FastProcessing,
+                RuntimeFlag::Paused,
+                RuntimeFlag::Maintenance,
+            ])
+            .await?;
+        let mut states = states.into_iter();
+        let mut next = || states.next().expect("State is loaded for every flag");
+
+        Ok(Self {
+            fast_processing: next(),
+            paused: next(),
+            maintenance: next(),
+        })
+    }
+
+    /// Returns the maintenance state as of now.
+    pub fn maintenance(&self) -> MaintenanceInfo {
+        MaintenanceInfo::from_state(self.maintenance.clone(), Utc::now())
+    }
+}
+
+#[derive(Debug)]
+struct CachedSnapshot {
+    generation: u64,
+    loaded_at: Instant,
+    snapshot: Arc<RuntimeFlagsSnapshot>,
+}
+
+/// Cache of the runtime flags shared by the API handlers.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeFlagsCache {
+    cache: Arc<RwLock<Option<CachedSnapshot>>>,
+}
+
+impl RuntimeFlagsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Invalidates the flags cached by all the caches of the process, must be called
+    /// after the flags are switched in the database.
+    pub fn invalidate_all() {
+        RUNTIME_FLAGS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Returns the cached flags, loading them from the database if the cache has expired.
+    pub async fn snapshot(
+        &self,
+        pool: &ConnectionPool,
+    ) -> anyhow::Result<Arc<RuntimeFlagsSnapshot>> {
+        let generation = RUNTIME_FLAGS_GENERATION.load(Ordering::SeqCst);
+        if let Some(cached) = self.cache.read().await.as_ref() {
+            if cached.generation == generation
+                && cached.loaded_at.elapsed() < RUNTIME_FLAGS_CACHE_TTL
+            {
+                return Ok(cached.snapshot.clone());
+            }
+        }
+
+        let mut storage = pool.access_storage().await?;
+        let snapshot = Arc::new(RuntimeFlagsSnapshot::load(&mut storage).await?);
+        *self.cache.write().await = Some(CachedSnapshot {
+            generation,
+            loaded_at: Instant::now(),
+            snapshot: snapshot.clone(),
+        });
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintenance_expires() {
+        let now = Utc::now();
+        let state = |until| RuntimeFlagState {
+            enabled: true,
+            reason: Some("Upgrade".to_owned()),
+            until,
+        };
+
+        assert!(MaintenanceInfo::from_state(state(None), now).enabled);
+        assert!(
+            MaintenanceInfo::from_state(state(Some(now + chrono::Duration::minutes(1))), now)
+                .enabled
+        );
+
+        let expired = MaintenanceInfo::from_state(state(Some(now)), now);
+        assert!(!expired.enabled);
+        assert_eq!(expired.message.as_deref(), Some("Upgrade"));
+    }
+}
@@ -0,0 +1,60 @@
+//! TLS termination of the public API servers.
+//!
+//! The REST API server accepts the TLS connections itself. JSON RPC servers don't support TLS,
+//! so the connections to them are terminated by the proxy (see the `proxy` module).
+
+// External uses
+use anyhow::{format_err, Context};
+use openssl::{
+    ssl::{SslAcceptor, SslAcceptorBuilder, SslFiletype, SslMethod, SslVerifyMode},
+    x509::X509Name,
+};
+
+// Workspace uses
+use zksync_config::configs::api::Tls;
+
+/// Creates the builder of the TLS acceptor with the configured certificate,
+/// or returns `None` if TLS is disabled.
+pub fn acceptor_builder(config: &Tls) -> anyhow::Result<Option<SslAcceptorBuilder>> {
+    let cert_path = match &config.cert_path {
+        Some(cert_path) => cert_path,
+        None => return Ok(None),
+    };
+    let key_path = config
+        .key_path
+        .as_ref()
+        .ok_or_else(|| format_err!("Private key of the TLS certificate is not set"))?;
+
+    let mut builder = SslAcceptor::mozilla_intermediate(SslMethod::tls())?;
+    builder
+        .set_certificate_chain_file(cert_path)
+        .with_context(|| format!("Unable to load the TLS certificate {}", cert_path))?;
+    builder
+        .set_private_key_file(key_path, SslFiletype::PEM)
+        .with_context(|| format!("Unable to load the TLS private key {}", key_path))?;
+    builder
+        .check_private_key()
+        .context("Private key doesn't match the TLS certificate")?;
+
+    if let Some(client_ca_path) = &config.client_ca_path {
+        let load_error = || {
+            format!(
+                "Unable to load the client CA certificates {}",
+                client_ca_path
+            )
+        };
+        builder
+            .set_ca_file(client_ca_path)
+            .with_context(load_error)?;
+        builder.set_client_ca_list(
+            X509Name::load_client_ca_file(client_ca_path).with_context(load_error)?,
+        );
+        builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    }
+    Ok(Some(builder))
+}
+
+/// Creates the TLS acceptor with the configured certificate, or returns `None` if TLS is disabled.
+pub fn acceptor(config: &Tls) -> anyhow::Result<Option<SslAcceptor>> {
+    Ok(acceptor_builder(config)?.map(SslAcceptorBuilder::build))
+}
@@ -1,7 +1,12 @@
 //! Helper module to submit transactions into the zkSync Network.
 
 // Built-in uses
-use std::{fmt::Display, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+    time::Duration,
+};
 
 // External uses
 use bigdecimal::BigDecimal;
@@ -11,37 +16,90 @@ use futures::{
     prelude::*,
 };
 use num::{bigint::ToBigInt, BigUint, Zero};
+use serde_json::{json, Value};
 use thiserror::Error;
+use vlog::Traced;
 
 // Workspace uses
+use zksync_api_client::rest::v1::{ErrorCode, ForcedExitEligibility, ForcedExitIneligibility};
 use zksync_config::ZkSyncConfig;
-use zksync_storage::ConnectionPool;
+use zksync_eth_client::EthereumGateway;
+use zksync_state::state::ZkSyncState;
+use zksync_storage::{outbox::OutboxRequest, ConnectionPool};
 use zksync_types::{
+    block::ExecutedOperations,
+    tokens::TokenTxType,
     tx::EthSignData,
-    tx::{SignedZkSyncTx, TxEthSignature, TxHash},
-    Address, BatchFee, Fee, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx,
+    tx::{
+        EIP712Domain, EthSignMessageTemplate, PackedEthSignature, SignedZkSyncTx, TxEthSignature,
+        TxHash,
+    },
+    Account, AccountMap, Address, BatchFee, BlockNumber, Fee, PubKeyHash, Token, TokenId,
+    TokenLike, TxFeeTypes, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
 
 // Local uses
 use crate::{
-    core_api_client::CoreApiClient,
+    api_server::{
+        api_keys::ApiClient,
+        event_notify::EventNotifierRequest,
+        rate_limit::RateLimits,
+        runtime_flags::{MaintenanceInfo, RuntimeFlagsCache},
+    },
+    core_api_client::{CoreApiClient, CoreApiError, OutboxState},
     fee_ticker::{TickerRequest, TokenPriceRequestType},
     signature_checker::{TxVariant, VerifiedTx, VerifyTxSignatureRequest},
     tx_error::TxAddError,
-    utils::token_db_cache::TokenDBCache,
+    utils::{
+        cosigner::registration_message, ens_resolver::EnsResolver, rate_limiter::RateLimiter,
+        token_db_cache::TokenDBCache,
+    },
 };
 
+/// Amount of attempts to forward the request to the Core before giving up.
+const CORE_REQUEST_ATTEMPTS: usize = 3;
+/// Interval between the attempts to forward the request to the Core.
+const CORE_REQUEST_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+/// Amount of checks for the result of the request claimed by the Core.
+const CORE_RESULT_CHECKS: usize = 10;
+/// Interval between the checks for the result of the request claimed by the Core.
+const CORE_RESULT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct TxSender {
     pub core_api_client: CoreApiClient,
-    pub sign_verify_requests: mpsc::Sender<VerifyTxSignatureRequest>,
-    pub ticker_requests: mpsc::Sender<TickerRequest>,
+    pub sign_verify_requests: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+    pub ticker_requests: mpsc::Sender<Traced<TickerRequest>>,
 
     pub pool: ConnectionPool,
     pub tokens: TokenDBCache,
+    /// Runtime flags switched by the operator, checked for every submitted transaction.
+    pub runtime_flags: RuntimeFlagsCache,
     /// Mimimum age of the account for `ForcedExit` operations to be allowed.
     pub forced_exit_minimum_account_age: chrono::Duration,
     pub enforce_pubkey_change_fee: bool,
+    /// Accepted templates of the Ethereum sign message, the current one goes first.
+    pub sign_message_templates: Vec<EthSignMessageTemplate>,
+    /// Symbols the renamed tokens may be referred by in the Ethereum sign messages,
+    /// keyed by the actual symbols.
+    pub legacy_token_symbols: HashMap<String, String>,
+    /// Domain of the EIP-712 typed data signed by the accounts instead of the text messages.
+    pub eip712_domain: EIP712Domain,
+    /// Operator key signing the soft confirmations of the accepted transactions.
+    pub soft_confirmation_key: Option<H256>,
+    /// Whether the batches are executed against the last committed state before being accepted.
+    pub simulate_txs_batches: bool,
+    /// Notifier of the transaction status subscribers about the accepted transactions.
+    pub event_notifier: Option<mpsc::Sender<EventNotifierRequest>>,
+    /// Limiter of the transactions submitted by the same account, shared by all the API servers.
+    pub address_rate_limiter: Option<RateLimiter<Address>>,
+    /// Whether the privileged submission options require an API key.
+    pub require_api_key: bool,
+    /// Resolver of the ENS names accepted instead of the account addresses.
+    pub ens_resolver: EnsResolver,
+    /// Whether the co-signing service is configured, co-signatures themselves are checked
+    /// by the signature checker.
+    pub cosigning_enabled: bool,
 }
 
 #[derive(Debug, Error)]
@@ -52,15 +110,57 @@ pub enum SubmitError {
     InvalidParams(String),
     #[error("Fast processing available only for 'withdraw' operation type.")]
     UnsupportedFastProcessing,
+    #[error("Fast processing of withdrawals is temporarily disabled.")]
+    FastProcessingDisabled,
+    #[error("Server is temporarily paused and doesn't accept new transactions: {0}.")]
+    Paused(String),
+    #[error(
+        "Server is under maintenance and doesn't accept new transactions: {}.",
+        .0.message.as_deref().unwrap_or("no details provided")
+    )]
+    Maintenance(MaintenanceInfo),
     #[error("Incorrect transaction: {0}.")]
     IncorrectTx(String),
     #[error("Transaction adding error: {0}.")]
     TxAdd(TxAddError),
+    /// Fee of the transaction is lower than the required one, the amounts are in the fee token.
+    /// The message is the same as the one of the corresponding `TxAddError`.
+    #[error("Transaction adding error: {}.", TxAddError::TxFeeTooLow)]
+    FeeTooLow {
+        required: BigDecimal,
+        provided: BigDecimal,
+    },
+    /// Summary fee of the batch is lower than the required one, the amounts are in USD.
+    #[error("Transaction adding error: {}.", TxAddError::TxBatchFeeTooLow)]
+    BatchFeeTooLow {
+        required_usd: BigDecimal,
+        provided_usd: BigDecimal,
+    },
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Batch transaction with index {tx_index} fails: {reason}.")]
+    BatchExecutionFailed { tx_index: usize, reason: String },
+    #[error("{tx_type} transactions are not allowed for the token {token}, allowed transaction types: {allowed}.")]
+    TokenTxRestricted {
+        token: String,
+        tx_type: TokenTxType,
+        allowed: String,
+    },
+    #[error("Transaction is not approved by the co-signer of the account.")]
+    CosignatureRejected,
 
     #[error("Communication error with the core server: {0}.")]
     CommunicationCoreServer(String),
+    #[error("Core server is temporarily unavailable.")]
+    CoreServerUnavailable,
+    #[error("Co-signing service is unavailable: {0}.")]
+    CosignerUnavailable(String),
+    #[error("Too many transactions are submitted by the account, retry in {} seconds.", .retry_after.as_secs())]
+    RateLimited { retry_after: Duration },
+    #[error("Requested option is only available to the clients with an API key.")]
+    ApiKeyRequired,
+    #[error("Hourly quota of the API key is exhausted, retry in {} seconds.", .retry_after.as_secs())]
+    ApiKeyQuotaExceeded { retry_after: Duration },
     #[error("Internal error.")]
     Internal(anyhow::Error),
     #[error("{0}")]
@@ -83,6 +183,88 @@ impl SubmitError {
     fn invalid_params(msg: impl Display) -> Self {
         Self::InvalidParams(msg.to_string())
     }
+
+    /// Returns the code of the error returned by the API.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::AccountCloseDisabled => ErrorCode::AccountCloseDisabled,
+            Self::InvalidParams(_) => ErrorCode::InvalidParams,
+            Self::UnsupportedFastProcessing => ErrorCode::UnsupportedFastProcessing,
+            Self::FastProcessingDisabled => ErrorCode::FastProcessingDisabled,
+            Self::Paused(_) => ErrorCode::Paused,
+            Self::Maintenance(_) => ErrorCode::Maintenance,
+            Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
+            Self::TxAdd(err) => err.error_code(),
+            Self::FeeTooLow { .. } => ErrorCode::FeeTooLow,
+            Self::BatchFeeTooLow { .. } => ErrorCode::BatchFeeTooLow,
+            Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
+            Self::BatchExecutionFailed { .. } => ErrorCode::BatchExecutionFailed,
+            Self::TokenTxRestricted { .. } => ErrorCode::TokenTxRestricted,
+            Self::CosignatureRejected => ErrorCode::CosignatureRejected,
+            Self::CommunicationCoreServer(_) => ErrorCode::CommunicationCoreServer,
+            Self::CoreServerUnavailable => ErrorCode::CoreServerUnavailable,
+            Self::CosignerUnavailable(_) => ErrorCode::ServiceUnavailable,
+            Self::RateLimited { .. } => ErrorCode::RateLimitExceeded,
+            Self::ApiKeyRequired => ErrorCode::Unauthorized,
+            Self::ApiKeyQuotaExceeded { .. } => ErrorCode::ApiKeyQuotaExceeded,
+            Self::Internal(_) => ErrorCode::Internal,
+            Self::Other(_) => ErrorCode::Other,
+        }
+    }
+
+    /// Returns the structured details of the error returned by the API along with its code,
+    /// so the clients don't have to parse the message.
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            Self::Maintenance(maintenance) => serde_json::to_value(maintenance).ok(),
+            Self::TxAdd(err) => err.details(),
+            Self::FeeTooLow { required, provided } => Some(json!({
+                "requiredFee": required.to_string(),
+                "providedFee": provided.to_string(),
+            })),
+            Self::BatchFeeTooLow {
+                required_usd,
+                provided_usd,
+            } => Some(json!({
+                "requiredFeeUsd": required_usd.to_string(),
+                "providedFeeUsd": provided_usd.to_string(),
+            })),
+            Self::BatchExecutionFailed { tx_index, reason } => Some(json!({
+                "txIndex": tx_index,
+                "reason": reason,
+            })),
+            Self::TokenTxRestricted {
+                token,
+                tx_type,
+                allowed,
+            } => Some(json!({
+                "token": token,
+                "txType": tx_type,
+                "allowed": allowed,
+            })),
+            Self::CommunicationCoreServer(reason) | Self::CosignerUnavailable(reason) => {
+                Some(json!({ "reason": reason }))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the suggested delay before retrying the submission, or `None`
+    /// if the error is permanent.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Maintenance(MaintenanceInfo {
+                until: Some(until), ..
+            }) => {
+                let remaining = (*until - Utc::now()).to_std().unwrap_or_default();
+                Some(remaining.max(Duration::from_secs(1)))
+            }
+            Self::RateLimited { retry_after } | Self::ApiKeyQuotaExceeded { retry_after } => {
+                Some(*retry_after)
+            }
+            _ => self.error_code().retry_after(),
+        }
+    }
 }
 
 macro_rules! internal_error {
@@ -99,11 +281,11 @@ macro_rules! internal_error {
 impl TxSender {
     pub fn new(
         connection_pool: ConnectionPool,
-        sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
-        ticker_request_sender: mpsc::Sender<TickerRequest>,
+        sign_verify_request_sender: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+        ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         config: &ZkSyncConfig,
     ) -> Self {
-        let core_api_client = CoreApiClient::new(config.api.private.url.clone());
+        let core_api_client = CoreApiClient::from_config(&config.api.private);
 
         Self::with_client(
             core_api_client,
@@ -117,8 +299,8 @@ impl TxSender {
     pub(crate) fn with_client(
         core_api_client: CoreApiClient,
         connection_pool: ConnectionPool,
-        sign_verify_request_sender: mpsc::Sender<VerifyTxSignatureRequest>,
-        ticker_request_sender: mpsc::Sender<TickerRequest>,
+        sign_verify_request_sender: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
+        ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         config: &ZkSyncConfig,
     ) -> Self {
         let forced_exit_minimum_account_age = chrono::Duration::seconds(
@@ -131,30 +313,95 @@ impl TxSender {
             sign_verify_requests: sign_verify_request_sender,
             ticker_requests: ticker_request_sender,
             tokens: TokenDBCache::new(),
+            runtime_flags: RuntimeFlagsCache::new(),
 
             enforce_pubkey_change_fee: config.api.common.enforce_pubkey_change_fee,
             forced_exit_minimum_account_age,
+            sign_message_templates: config.api.eth_sign_message.templates(),
+            legacy_token_symbols: config.api.eth_sign_message.legacy_token_symbols(),
+            eip712_domain: EIP712Domain::new(
+                config.eth_client.chain_id,
+                config.contracts.contract_addr,
+            ),
+            soft_confirmation_key: config.api.common.soft_confirmation_private_key,
+            simulate_txs_batches: config.api.common.simulate_txs_batches,
+            event_notifier: None,
+            address_rate_limiter: None,
+            require_api_key: config.api.auth.require_api_key,
+            ens_resolver: EnsResolver::new(
+                EthereumGateway::from_config(config),
+                &config.api.ens,
+                config.api.common.caches_size,
+            ),
+            cosigning_enabled: config.api.cosigner.service_url.is_some(),
         }
     }
 
+    /// Sets the sender of the events about the accepted transactions.
+    pub fn with_event_notifier(
+        mut self,
+        event_notifier: mpsc::Sender<EventNotifierRequest>,
+    ) -> Self {
+        self.event_notifier = Some(event_notifier);
+        self
+    }
+
+    /// Sets the limits of the transactions submitted by the same account.
+    pub fn with_rate_limits(mut self, rate_limits: &RateLimits) -> Self {
+        self.address_rate_limiter = rate_limits.address.clone();
+        self
+    }
+
+    /// The span of the submission is propagated to the signature checker and the fee ticker.
+    ///
+    /// `api_client` is the integrator authenticated by the API key, required for the privileged
+    /// options (the fast processing) if `require_api_key` is set.
+    #[tracing::instrument(
+        skip(self, tx, signature, fast_processing, api_client),
+        fields(tx_hash = %tx.hash())
+    )]
     pub async fn submit_tx(
         &self,
-        mut tx: ZkSyncTx,
+        tx: ZkSyncTx,
         signature: Option<TxEthSignature>,
         fast_processing: Option<bool>,
+        api_client: Option<&ApiClient>,
     ) -> Result<TxHash, SubmitError> {
-        if tx.is_close() {
-            return Err(SubmitError::AccountCloseDisabled);
+        let verified_tx = self
+            .verify_submitted_tx(tx, signature, fast_processing, api_client)
+            .await?;
+        let result = self.check_and_submit_tx(&verified_tx).await;
+        if let Err(err) = &result {
+            self.record_rejected_txs(&[verified_tx.tx], err).await;
         }
+        result
+    }
 
-        if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
-            self.check_forced_exit(forced_exit).await?;
+    /// Performs the stateless checks of the transaction and verifies its signature.
+    /// Transactions rejected at this stage are not recorded, since their sender
+    /// is not authenticated.
+    async fn verify_submitted_tx(
+        &self,
+        mut tx: ZkSyncTx,
+        signature: Option<TxEthSignature>,
+        fast_processing: Option<bool>,
+        api_client: Option<&ApiClient>,
+    ) -> Result<SignedZkSyncTx, SubmitError> {
+        self.ensure_txs_accepted().await?;
+        self.check_rate_limit(std::iter::once(tx.account()))?;
+
+        if tx.is_close() {
+            return Err(SubmitError::AccountCloseDisabled);
         }
 
         let fast_processing = fast_processing.unwrap_or_default(); // `None` => false
         if fast_processing && !tx.is_withdraw() {
             return Err(SubmitError::UnsupportedFastProcessing);
         }
+        if fast_processing {
+            self.ensure_fast_processing_enabled().await?;
+            self.ensure_privileged_client(api_client)?;
+        }
 
         if let ZkSyncTx::Withdraw(withdraw) = &mut tx {
             if withdraw.fast {
@@ -170,6 +417,19 @@ impl TxSender {
             withdraw.fast = fast_processing;
         }
 
+        self.verify_tx_info(&tx, signature).await
+    }
+
+    async fn check_and_submit_tx(
+        &self,
+        verified_tx: &SignedZkSyncTx,
+    ) -> Result<TxHash, SubmitError> {
+        let tx = &verified_tx.tx;
+        if let ZkSyncTx::ForcedExit(forced_exit) = tx {
+            self.check_forced_exit(forced_exit).await?;
+        }
+        self.check_token_tx_restriction(tx).await?;
+
         let tx_fee_info = tx.get_fee_info();
 
         let ticker_request_sender = self.ticker_requests.clone();
@@ -204,41 +464,93 @@ impl TxSender {
                     token
                 );
 
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+                return Err(SubmitError::FeeTooLow {
+                    required: required_fee,
+                    provided: provided_fee,
+                });
             }
         }
 
-        let verified_tx = self.glm_verify_tx_info(&tx, signature.clone()).await?;
-
         // Send verified transactions to the mempool.
-        self.core_api_client
-            .send_tx(verified_tx)
-            .await
-            .map_err(SubmitError::communication_core_server)?
-            .map_err(SubmitError::TxAdd)?;
+        let request = OutboxRequest::Tx(Box::new(verified_tx.clone()));
+        self.forward_to_core(&request, |outbox_id| {
+            self.core_api_client.send_tx(verified_tx, outbox_id)
+        })
+        .await?;
+        self.notify_txs_received(vec![tx.hash()]);
         // if everything is OK, return the transactions hashes.
         Ok(tx.hash())
     }
 
+    #[tracing::instrument(skip(self, txs, eth_signature), fields(batch_size = txs.len()))]
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
         eth_signature: Option<TxEthSignature>,
     ) -> Result<Vec<TxHash>, SubmitError> {
+        let (verified_txs, verified_signature) =
+            self.verify_submitted_txs_batch(txs, eth_signature).await?;
+        let result = self
+            .check_and_submit_txs_batch(&verified_txs, &verified_signature)
+            .await;
+        if let Err(err) = &result {
+            let batch: Vec<_> = verified_txs.into_iter().map(|tx| tx.tx).collect();
+            self.record_rejected_txs(&batch, err).await;
+        }
+        result
+    }
+
+    /// Same as `verify_submitted_tx`, but for the batch of transactions.
+    async fn verify_submitted_txs_batch(
+        &self,
+        txs: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        eth_signature: Option<TxEthSignature>,
+    ) -> Result<(Vec<SignedZkSyncTx>, Option<TxEthSignature>), SubmitError> {
         debug_assert!(txs.is_empty(), "Transaction batch cannot be empty");
 
+        self.ensure_txs_accepted().await?;
+        self.check_rate_limit(txs.iter().map(|(tx, _)| tx.account()))?;
+
         if txs.iter().any(|tx| tx.0.is_close()) {
             return Err(SubmitError::AccountCloseDisabled);
         }
 
+        let mut verified_txs = Vec::new();
+        let mut verified_signature = None;
+
+        if let Some(signature) = eth_signature {
+            // User provided the signature for the whole batch.
+            let (verified_batch, sign_data) = self.verify_txs_batch_info(txs, signature).await?;
+
+            verified_signature = Some(sign_data.signature);
+            verified_txs.extend(verified_batch.into_iter());
+        } else {
+            // Otherwise, we process every transaction in turn.
+            for (tx, signature) in txs {
+                let verified_tx = self.verify_tx_info(&tx, signature).await?;
+                verified_txs.push(verified_tx);
+            }
+        }
+        Ok((verified_txs, verified_signature))
+    }
+
+    async fn check_and_submit_txs_batch(
+        &self,
+        verified_txs: &[SignedZkSyncTx],
+        verified_signature: &Option<TxEthSignature>,
+    ) -> Result<Vec<TxHash>, SubmitError> {
+        for tx in verified_txs {
+            self.check_token_tx_restriction(&tx.tx).await?;
+        }
+
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
         let mut transaction_types = vec![];
 
         let eth_token = TokenLike::Id(TokenId(0));
 
-        for tx in &txs {
-            let tx_fee_info = tx.0.get_fee_info();
+        for tx in verified_txs {
+            let tx_fee_info = tx.tx.get_fee_info();
 
             if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
                 if provided_fee == BigUint::zero() {
@@ -306,36 +618,404 @@ impl TxSender {
                 scaled_provided_fee_in_usd.to_string(),
                 (required_total_usd_fee.clone() - scaled_provided_fee_in_usd.clone()).to_string(),
             );
-            return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
+            return Err(SubmitError::BatchFeeTooLow {
+                required_usd: required_total_usd_fee,
+                provided_usd: provided_total_usd_fee,
+            });
         }
 
-        let mut verified_txs = Vec::new();
-        let mut verified_signature = None;
+        if self.simulate_txs_batches {
+            self.check_txs_batch_execution(verified_txs).await?;
+        }
 
-        if let Some(signature) = eth_signature {
-            // User provided the signature for the whole batch.
-            let (verified_batch, sign_data) =
-                self.glm_verify_txs_batch_info(txs, signature).await?;
+        let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
+        // Send verified transactions to the mempool.
+        let request = OutboxRequest::TxsBatch(verified_txs.to_vec(), verified_signature.clone());
+        self.forward_to_core(&request, |outbox_id| {
+            self.core_api_client
+                .send_txs_batch(verified_txs, verified_signature, outbox_id)
+        })
+        .await?;
+        self.notify_txs_received(tx_hashes.clone());
 
-            verified_signature = Some(sign_data.signature);
-            verified_txs.extend(verified_batch.into_iter());
-        } else {
-            // Otherwise, we process every transaction in turn.
-            for (tx, signature) in txs {
-                let verified_tx = self.glm_verify_tx_info(&tx, signature).await?;
-                verified_txs.push(verified_tx);
+        Ok(tx_hashes)
+    }
+
+    /// Registers the co-signer of the account, or opts the account out of the co-signing
+    /// if `cosigner` is `None`. The registration message must be signed with the Ethereum key
+    /// of the account, so only the accounts controlled by the regular Ethereum keys may opt in.
+    ///
+    /// Changes of the existing registration must be approved by the current co-signer as well,
+    /// otherwise the second factor could be removed with the account key alone.
+    pub async fn register_cosigner(
+        &self,
+        address: Address,
+        cosigner: Option<Address>,
+        nonce: u32,
+        signature: PackedEthSignature,
+        cosigner_signature: Option<PackedEthSignature>,
+    ) -> Result<(), SubmitError> {
+        if cosigner.is_some() && !self.cosigning_enabled {
+            return Err(SubmitError::invalid_params(
+                "Co-signing is not supported by this server",
+            ));
+        }
+
+        let message = registration_message(cosigner, nonce, self.eip712_domain.chain_id);
+        let signer = signature.signature_recover_signer(message.as_bytes());
+        if !matches!(signer, Ok(signer) if signer == address) {
+            return Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature));
+        }
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let registration = storage
+            .cosigners_schema()
+            .load_registration(address)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        let expected_nonce = registration
+            .as_ref()
+            .map_or(1, |registration| registration.registration_nonce + 1);
+        if i64::from(nonce) != expected_nonce {
+            return Err(SubmitError::InvalidParams(format!(
+                "Registration nonce must be {}",
+                expected_nonce
+            )));
+        }
+
+        if let Some(current) = registration.and_then(|registration| registration.cosigner) {
+            let current = Address::from_slice(&current);
+            let approved = cosigner_signature.map_or(false, |cosigner_signature| {
+                matches!(
+                    cosigner_signature.signature_recover_signer(message.as_bytes()),
+                    Ok(signer) if signer == current
+                )
+            });
+            if !approved {
+                return Err(SubmitError::CosignatureRejected);
             }
         }
 
-        let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
-        // Send verified transactions to the mempool.
-        self.core_api_client
-            .send_txs_batch(verified_txs, verified_signature)
+        let saved = storage
+            .cosigners_schema()
+            .set_cosigner(address, cosigner, expected_nonce)
+            .await
+            .map_err(SubmitError::internal)?;
+        if !saved {
+            // Another registration with the same nonce was saved concurrently.
+            return Err(SubmitError::invalid_params(
+                "Registration nonce is already used",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Notifies the transaction status subscribers that the transactions were accepted to the mempool.
+    /// Subscriptions are best-effort, so the full channel doesn't affect the submission.
+    fn notify_txs_received(&self, tx_hashes: Vec<TxHash>) {
+        if let Some(event_notifier) = &self.event_notifier {
+            event_notifier
+                .clone()
+                .try_send(EventNotifierRequest::TxsReceived(tx_hashes))
+                .unwrap_or_default();
+        }
+    }
+
+    /// Records the rejected transactions along with the error, so the submitters can find out
+    /// the reason later. Only the transactions with the verified signatures are recorded, so the
+    /// rejections can't be planted for other accounts. Rate limiting errors are not recorded,
+    /// otherwise the flood of the transactions would flood the storage as well.
+    /// Failure to record the transactions is not critical, since they were rejected anyway.
+    async fn record_rejected_txs(&self, txs: &[ZkSyncTx], error: &SubmitError) {
+        if matches!(
+            error,
+            SubmitError::RateLimited { .. } | SubmitError::ApiKeyQuotaExceeded { .. }
+        ) {
+            return;
+        }
+
+        let error_code = error.error_code().as_u64() as u32;
+        let recorded = match self.pool.access_storage().await {
+            Ok(mut storage) => {
+                storage
+                    .rejected_txs_schema()
+                    .record(txs, error_code, &error.to_string())
+                    .await
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = recorded {
+            vlog::warn!("Unable to record the rejected transactions: {}", err);
+        }
+    }
+
+    /// Executes the batch against the state of the involved accounts, so the batch that is going
+    /// to fail is rejected before being sent to the mempool.
+    ///
+    /// Operations of the pending block and the transactions waiting in the mempool are applied
+    /// on top of the last committed state, so the batch spending the funds received by the pending
+    /// transactions or following their nonces is not rejected.
+    async fn check_txs_batch_execution(&self, txs: &[SignedZkSyncTx]) -> Result<(), SubmitError> {
+        let batch_addresses: Vec<_> = txs
+            .iter()
+            .flat_map(|tx| tx_addresses(&tx.tx))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let account_ids: HashSet<_> = txs
+            .iter()
+            .filter_map(|tx| match &tx.tx {
+                ZkSyncTx::ForcedExit(forced_exit) => Some(forced_exit.initiator_account_id),
+                _ => None,
+            })
+            .collect();
+
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let pending_ops: Vec<_> = storage
+            .chain()
+            .block_schema()
+            .load_pending_block()
+            .await
+            .map_err(SubmitError::internal)?
+            .map(|block| block.success_operations)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|op| {
+                operation_addresses(op)
+                    .iter()
+                    .any(|address| batch_addresses.contains(address))
+            })
+            .collect();
+        let mempool_txs = storage
+            .chain()
+            .mempool_schema()
+            .load_address_txs(&batch_addresses)
             .await
-            .map_err(SubmitError::communication_core_server)?
-            .map_err(SubmitError::TxAdd)?;
+            .map_err(SubmitError::internal)?;
 
-        Ok(tx_hashes)
+        // Counterparties of the pending transactions are loaded as well, so their effects
+        // on the batch accounts can be applied.
+        let addresses: HashSet<_> = batch_addresses
+            .iter()
+            .copied()
+            .chain(pending_ops.iter().flat_map(operation_addresses))
+            .chain(mempool_txs.iter().flat_map(|tx| tx_addresses(&tx.tx)))
+            .collect();
+        let mut accounts = AccountMap::default();
+        for address in addresses {
+            let state = storage
+                .chain()
+                .account_schema()
+                .account_state_by_address(address)
+                .await
+                .map_err(SubmitError::internal)?;
+            accounts.extend(state.committed);
+        }
+        for account_id in account_ids {
+            let state = storage
+                .chain()
+                .account_schema()
+                .account_state_by_id(account_id)
+                .await
+                .map_err(SubmitError::internal)?;
+            accounts.extend(state.committed);
+        }
+
+        // Block number doesn't affect the execution of the transactions.
+        let mut state = ZkSyncState::from_acc_map(accounts, BlockNumber(0));
+        let created_accounts = apply_pending_txs(&mut state, &pending_ops, &mempool_txs);
+        simulate_txs_batch(&mut state, created_accounts, txs)
+            .map_err(|(tx_index, reason)| SubmitError::BatchExecutionFailed { tx_index, reason })
+    }
+
+    /// Forwards the request to the Core through the outbox, so the request is processed
+    /// exactly once even if it has to be resent after a network failure.
+    ///
+    /// If the Core stays unreachable, the request is abandoned unless the Core has already
+    /// started processing it. In the latter case the result stored by the Core is awaited.
+    /// Processed requests are removed from the outbox by the Core.
+    async fn forward_to_core<F, Fut>(
+        &self,
+        request: &OutboxRequest,
+        send: F,
+    ) -> Result<(), SubmitError>
+    where
+        F: Fn(i64) -> Fut,
+        Fut: Future<Output = Result<Result<(), TxAddError>, CoreApiError>>,
+    {
+        let outbox_id = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .outbox_schema()
+            .add_request(request)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        let mut last_error = None;
+        for attempt in 0..CORE_REQUEST_ATTEMPTS {
+            if attempt > 0 {
+                tokio::time::delay_for(CORE_REQUEST_RETRY_INTERVAL).await;
+            }
+
+            let err = match send(outbox_id).await {
+                Ok(result) => return result.map_err(SubmitError::TxAdd),
+                Err(err) => err,
+            };
+            match err.outbox_state() {
+                // The request is being processed by the Core, its result is awaited below.
+                Some(OutboxState::InProgress) => {
+                    last_error = Some(err);
+                    break;
+                }
+                // The request was abandoned or removed, so it will never be processed.
+                Some(OutboxState::Unknown) => {
+                    return Err(SubmitError::communication_core_server(err));
+                }
+                // The request wasn't sent, and there is no point in retrying it right away.
+                None if matches!(err, CoreApiError::Unavailable) => {
+                    last_error = Some(err);
+                    break;
+                }
+                None => {
+                    vlog::warn!(
+                        "Failed to forward the request {} to the Core: {}",
+                        outbox_id,
+                        err
+                    );
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        let abandoned = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .outbox_schema()
+            .abandon_request(outbox_id)
+            .await
+            .map_err(SubmitError::internal)?;
+        if abandoned {
+            return Err(match last_error.expect("At least one attempt was made") {
+                CoreApiError::Unavailable => SubmitError::CoreServerUnavailable,
+                err => SubmitError::communication_core_server(err),
+            });
+        }
+
+        // The Core has started processing the request, so the user must get its result.
+        for _ in 0..CORE_RESULT_CHECKS {
+            let result = self
+                .pool
+                .access_storage()
+                .await
+                .map_err(SubmitError::internal)?
+                .outbox_schema()
+                .load_result(outbox_id)
+                .await
+                .map_err(SubmitError::internal)?;
+
+            if let Some(result) = result {
+                let result: Result<(), TxAddError> =
+                    serde_json::from_value(result).map_err(SubmitError::internal)?;
+                return result.map_err(SubmitError::TxAdd);
+            }
+            tokio::time::delay_for(CORE_RESULT_CHECK_INTERVAL).await;
+        }
+
+        Err(SubmitError::communication_core_server(
+            "the request was accepted by the core server, but its result is not known yet",
+        ))
+    }
+
+    /// Returns `true` if the fast processing of withdrawals is enabled by the operator.
+    pub async fn fast_processing_enabled(&self) -> Result<bool, SubmitError> {
+        let flags = self
+            .runtime_flags
+            .snapshot(&self.pool)
+            .await
+            .map_err(SubmitError::internal)?;
+        Ok(flags.fast_processing.enabled)
+    }
+
+    /// Rejects the transactions of the accounts submitting the transactions too often.
+    /// Every transaction takes a token from the bucket of its account.
+    fn check_rate_limit(
+        &self,
+        tx_accounts: impl IntoIterator<Item = Address>,
+    ) -> Result<(), SubmitError> {
+        let limiter = match &self.address_rate_limiter {
+            Some(limiter) => limiter,
+            None => return Ok(()),
+        };
+
+        let mut txs_count = HashMap::<_, u32>::new();
+        for account in tx_accounts {
+            *txs_count.entry(account).or_default() += 1;
+        }
+        for (account, count) in txs_count {
+            limiter.check_n(account, count).map_err(|retry_after| {
+                metrics::counter!("api.rate_limit.rejected_txs", 1);
+                SubmitError::RateLimited { retry_after }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the client may use the privileged submission options, and takes
+    /// the request from the quota of its API key.
+    fn ensure_privileged_client(&self, api_client: Option<&ApiClient>) -> Result<(), SubmitError> {
+        match api_client {
+            Some(client) => client.take_quota().map_err(|retry_after| {
+                metrics::counter!("api.auth.quota_exceeded", 1, "client" => client.name.clone());
+                SubmitError::ApiKeyQuotaExceeded { retry_after }
+            }),
+            None if self.require_api_key => Err(SubmitError::ApiKeyRequired),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects new transactions while the server is paused or under maintenance.
+    pub async fn ensure_txs_accepted(&self) -> Result<(), SubmitError> {
+        let flags = self
+            .runtime_flags
+            .snapshot(&self.pool)
+            .await
+            .map_err(SubmitError::internal)?;
+        if flags.paused.enabled {
+            let reason = flags
+                .paused
+                .reason
+                .clone()
+                .unwrap_or_else(|| "maintenance in progress".to_string());
+            return Err(SubmitError::Paused(reason));
+        }
+
+        let maintenance = flags.maintenance();
+        if maintenance.enabled {
+            return Err(SubmitError::Maintenance(maintenance));
+        }
+        Ok(())
+    }
+
+    /// Rejects the fast withdrawals while the fast processing is disabled.
+    pub async fn ensure_fast_processing_enabled(&self) -> Result<(), SubmitError> {
+        if self.fast_processing_enabled().await? {
+            Ok(())
+        } else {
+            Err(SubmitError::FastProcessingDisabled)
+        }
     }
 
     pub async fn get_txs_fee_in_wei(
@@ -344,6 +1024,9 @@ impl TxSender {
         address: Address,
         token: TokenLike,
     ) -> Result<Fee, SubmitError> {
+        if tx_type == TxFeeTypes::FastWithdraw {
+            self.ensure_fast_processing_enabled().await?;
+        }
         Self::ticker_request(self.ticker_requests.clone(), tx_type, address, token).await
     }
 
@@ -352,9 +1035,56 @@ impl TxSender {
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
     ) -> Result<BatchFee, SubmitError> {
+        if transactions
+            .iter()
+            .any(|(tx_type, _)| *tx_type == TxFeeTypes::FastWithdraw)
+        {
+            self.ensure_fast_processing_enabled().await?;
+        }
         Self::ticker_batch_fee_request(self.ticker_requests.clone(), transactions, token).await
     }
 
+    /// Checks that the transaction type is allowed by the operator for the token used in the transaction.
+    async fn check_token_tx_restriction(&self, tx: &ZkSyncTx) -> Result<(), SubmitError> {
+        let (token_id, tx_type) = match tx.token_tx_type() {
+            Some(token_tx_type) => token_tx_type,
+            None => return Ok(()),
+        };
+
+        let restriction = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .tokens_schema()
+            .load_tx_restriction(token_id)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        match restriction {
+            Some(restriction) if !restriction.allows(tx_type) => {
+                let token = self.token_info_from_id(token_id).await?;
+                let allowed = if restriction.allowed_tx_types.is_empty() {
+                    "none".to_string()
+                } else {
+                    restriction
+                        .allowed_tx_types
+                        .iter()
+                        .map(TokenTxType::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                Err(SubmitError::TokenTxRestricted {
+                    token: token.symbol,
+                    tx_type,
+                    allowed,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// For forced exits, we must check that target account exists for more
     /// than 24 hours in order to give new account owners give an opportunity
     /// to set the signing key. While `ForcedExit` operation doesn't do anything
@@ -364,60 +1094,181 @@ impl TxSender {
         &self,
         forced_exit: &zksync_types::ForcedExit,
     ) -> Result<(), SubmitError> {
+        let eligibility = self
+            .forced_exit_eligibility(forced_exit.target, forced_exit.token.into())
+            .await?;
+
+        // Only the age of the account is checked on submission. The signing key and the balance
+        // in the committed state may still be changed by the pending operations (e.g. a deposit),
+        // so they are checked by the state keeper when the transaction is executed.
+        let reason = eligibility.reasons.into_iter().find(|reason| {
+            matches!(
+                reason,
+                ForcedExitIneligibility::AccountNotFound
+                    | ForcedExitIneligibility::AccountTooNew { .. }
+            )
+        });
+        match reason {
+            None => Ok(()),
+            Some(ForcedExitIneligibility::AccountNotFound) => {
+                Err(SubmitError::invalid_params("Target account does not exist"))
+            }
+            Some(ForcedExitIneligibility::AccountTooNew { .. }) => {
+                let msg = format!(
+                    "Target account exists less than required minimum amount ({} hours)",
+                    self.forced_exit_minimum_account_age.num_hours()
+                );
+                Err(SubmitError::InvalidParams(msg))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Runs the checks of the `ForcedExit` of the token balance of the target account
+    /// against the last committed state, so the tools automating exits can skip
+    /// the ineligible accounts instead of submitting the transaction.
+    /// The signing key and the balance are not checked if the account is not committed yet.
+    pub async fn forced_exit_eligibility(
+        &self,
+        target: Address,
+        token: TokenLike,
+    ) -> Result<ForcedExitEligibility, SubmitError> {
         let mut storage = self
             .pool
             .access_storage()
             .await
             .map_err(SubmitError::internal)?;
 
-        let target_account_address = forced_exit.target;
+        let token = self
+            .tokens
+            .get_token(&mut storage, token)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or_else(|| SubmitError::invalid_params("Token not found"))?;
 
         let account_age = storage
             .chain()
             .operations_ext_schema()
-            .account_created_on(&target_account_address)
+            .account_created_on(&target)
             .await
-            .map_err(|err| internal_error!(err, forced_exit))?;
-
-        match account_age {
-            Some(age) if Utc::now() - age < self.forced_exit_minimum_account_age => {
-                let msg = format!(
-                    "Target account exists less than required minimum amount ({} hours)",
-                    self.forced_exit_minimum_account_age.num_hours()
-                );
-
-                Err(SubmitError::InvalidParams(msg))
+            .map_err(SubmitError::internal)?;
+        let account = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(target)
+            .await
+            .map_err(SubmitError::internal)?
+            .committed;
+
+        let account_age = match account_age {
+            Some(account_age) => account_age,
+            None => {
+                return Ok(ForcedExitEligibility::new(vec![
+                    ForcedExitIneligibility::AccountNotFound,
+                ]))
             }
-            None => Err(SubmitError::invalid_params("Target account does not exist")),
+        };
 
-            Some(..) => Ok(()),
+        let mut reasons = Vec::new();
+        let eligible_at = account_age + self.forced_exit_minimum_account_age;
+        if Utc::now() < eligible_at {
+            reasons.push(ForcedExitIneligibility::AccountTooNew { eligible_at });
         }
+        if let Some((_, account)) = account {
+            if account.pub_key_hash != PubKeyHash::default() {
+                reasons.push(ForcedExitIneligibility::SigningKeySet);
+            }
+            if account.get_balance(token.id).is_zero() {
+                reasons.push(ForcedExitIneligibility::ZeroBalance);
+            }
+        }
+
+        Ok(ForcedExitEligibility::new(reasons))
     }
 
-    /// Returns a message that user has to sign to send the transaction.
+    /// Returns a message that user has to sign to send the transaction,
+    /// formatted with the current template and the token symbol and decimals from the database.
+    /// `ChangePubKey` is signed with the message authorizing the new public key instead.
     /// If the transaction doesn't need a message signature, returns `None`.
-    /// If any error is encountered during the message generation, returns `jsonrpc_core::Error`.
-    #[allow(dead_code)]
-    async fn tx_message_to_sign(&self, tx: &ZkSyncTx) -> Result<Option<Vec<u8>>, SubmitError> {
-        Ok(match tx {
-            ZkSyncTx::Transfer(tx) => {
-                let token = self.token_info_from_id(tx.token).await?;
+    pub async fn tx_message_to_sign(&self, tx: &ZkSyncTx) -> Result<Option<String>, SubmitError> {
+        if let ZkSyncTx::ChangePubKey(tx) = tx {
+            let message = tx.get_eth_signed_data().map_err(SubmitError::internal)?;
+            let message = String::from_utf8(message).map_err(SubmitError::internal)?;
+            return Ok(Some(message));
+        }
+
+        let message = self
+            .tx_sign_message(tx, &self.sign_message_templates[0], false)
+            .await?;
+        Ok(message.map(|(message, _)| message))
+    }
 
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some(msg)
+    /// Returns the Ethereum sign message of the transaction formatted with the template,
+    /// or `None` if the transaction doesn't need a message signature.
+    ///
+    /// If `use_legacy_symbol` is set and the token has a legacy symbol, the token is referred
+    /// by it. The returned flag tells whether the symbol was substituted.
+    async fn tx_sign_message(
+        &self,
+        tx: &ZkSyncTx,
+        template: &EthSignMessageTemplate,
+        use_legacy_symbol: bool,
+    ) -> Result<Option<(String, bool)>, SubmitError> {
+        let message = match tx {
+            ZkSyncTx::Transfer(tx) => {
+                let (token, symbol) = self.sign_message_token(tx.token, use_legacy_symbol).await?;
+                let message =
+                    tx.get_ethereum_sign_message_with_template(template, &symbol, token.decimals);
+                (message, symbol != token.symbol)
             }
             ZkSyncTx::Withdraw(tx) => {
-                let token = self.token_info_from_id(tx.token).await?;
-
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some(msg)
+                let (token, symbol) = self.sign_message_token(tx.token, use_legacy_symbol).await?;
+                let message =
+                    tx.get_ethereum_sign_message_with_template(template, &symbol, token.decimals);
+                (message, symbol != token.symbol)
             }
-            _ => None,
-        })
+            _ => return Ok(None),
+        };
+        Ok(Some(message))
+    }
+
+    /// Returns the token and the symbol it's referred by in the sign message.
+    async fn sign_message_token(
+        &self,
+        token_id: TokenId,
+        use_legacy_symbol: bool,
+    ) -> Result<(Token, String), SubmitError> {
+        let token = self.token_info_from_id(token_id).await?;
+        let symbol = self
+            .legacy_token_symbols
+            .get(&token.symbol)
+            .filter(|_| use_legacy_symbol)
+            .unwrap_or(&token.symbol)
+            .clone();
+        Ok((token, symbol))
+    }
+
+    /// Same as `tx_sign_message`, but for the batch of transactions. The returned flag
+    /// tells whether the symbol was substituted in any of the messages.
+    async fn txs_batch_sign_messages(
+        &self,
+        txs: &[(ZkSyncTx, Option<TxEthSignature>)],
+        template: &EthSignMessageTemplate,
+        use_legacy_symbols: bool,
+    ) -> Result<(Vec<Option<Vec<u8>>>, bool), SubmitError> {
+        let mut messages = Vec::with_capacity(txs.len());
+        let mut substituted = false;
+        for (tx, _) in txs {
+            let message = self
+                .tx_sign_message(tx, template, use_legacy_symbols)
+                .await?
+                .map(|(message, legacy_symbol)| {
+                    substituted |= legacy_symbol;
+                    message.into_bytes()
+                });
+            messages.push(message);
+        }
+        Ok((messages, substituted))
     }
 
     async fn token_info_from_id(&self, token_id: TokenId) -> Result<Token, SubmitError> {
@@ -436,17 +1287,17 @@ impl TxSender {
     }
 
     async fn ticker_batch_fee_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         transactions: Vec<(TxFeeTypes, Address)>,
         token: TokenLike,
     ) -> Result<BatchFee, SubmitError> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetBatchTxFee {
+            .send(Traced::new(TickerRequest::GetBatchTxFee {
                 transactions,
                 token: token.clone(),
                 response: req.0,
-            })
+            }))
             .await
             .map_err(SubmitError::internal)?;
         let resp = req.1.await.map_err(SubmitError::internal)?;
@@ -454,19 +1305,19 @@ impl TxSender {
     }
 
     async fn ticker_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         tx_type: TxFeeTypes,
         address: Address,
         token: TokenLike,
     ) -> Result<Fee, SubmitError> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetTxFee {
+            .send(Traced::new(TickerRequest::GetTxFee {
                 tx_type,
                 address,
                 token: token.clone(),
                 response: req.0,
-            })
+            }))
             .await
             .map_err(SubmitError::internal)?;
 
@@ -475,15 +1326,15 @@ impl TxSender {
     }
 
     async fn token_allowed_for_fees(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         token: TokenLike,
     ) -> Result<bool, SubmitError> {
         let (sender, receiver) = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::IsTokenAllowed {
+            .send(Traced::new(TickerRequest::IsTokenAllowed {
                 token: token.clone(),
                 response: sender,
-            })
+            }))
             .await
             .expect("ticker receiver dropped");
         receiver
@@ -493,198 +1344,185 @@ impl TxSender {
     }
 
     async fn ticker_price_request(
-        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        mut ticker_request_sender: mpsc::Sender<Traced<TickerRequest>>,
         token: TokenLike,
         req_type: TokenPriceRequestType,
     ) -> Result<BigDecimal, SubmitError> {
         let req = oneshot::channel();
         ticker_request_sender
-            .send(TickerRequest::GetTokenPrice {
+            .send(Traced::new(TickerRequest::GetTokenPrice {
                 token: token.clone(),
                 response: req.0,
                 req_type,
-            })
+            }))
             .await
             .map_err(SubmitError::internal)?;
         let resp = req.1.await.map_err(SubmitError::internal)?;
         resp.map_err(|err| internal_error!(err))
     }
 
-    // Methods for Golem workaround:
-    // TODO: Remove this code after Golem update [ZKS-173]
-
-    async fn glm_tx_message_to_sign(
+    /// Verifies the transaction signatures against the current sign message template
+    /// and then against the legacy one, if it's still accepted.
+    async fn verify_tx_info(
         &self,
         tx: &ZkSyncTx,
-    ) -> Result<Option<(Token, Vec<u8>)>, SubmitError> {
-        Ok(match tx {
-            ZkSyncTx::Transfer(tx) => {
-                let token = self.token_info_from_id(tx.token).await?;
-
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some((token, msg))
-            }
-            ZkSyncTx::Withdraw(tx) => {
-                let token = self.token_info_from_id(tx.token).await?;
-
-                let msg = tx
-                    .get_ethereum_sign_message(&token.symbol, token.decimals)
-                    .into_bytes();
-                Some((token, msg))
-            }
-            _ => None,
-        })
-    }
-
-    fn glm_force_tx_message_to_sign(&self, tx: &ZkSyncTx, decimals: u8) -> Option<Vec<u8>> {
-        match tx {
-            ZkSyncTx::Transfer(tx) => {
-                let msg = tx.get_ethereum_sign_message("tGLM", decimals).into_bytes();
-                Some(msg)
-            }
+        signature: Option<TxEthSignature>,
+    ) -> Result<SignedZkSyncTx, SubmitError> {
+        let (current, legacy) = self.sign_message_templates.split_first().unwrap();
+        let result = self
+            .verify_tx_info_with_template(tx, signature.clone(), current)
+            .await;
 
-            ZkSyncTx::Withdraw(tx) => {
-                let msg = tx.get_ethereum_sign_message("tGLM", decimals).into_bytes();
-                Some(msg)
+        match result {
+            Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature)) => {
+                for template in legacy {
+                    if let Ok(tx) = self
+                        .verify_tx_info_with_template(tx, signature.clone(), template)
+                        .await
+                    {
+                        vlog::debug!("Accepted sign message of version {}", template.version);
+                        return Ok(tx);
+                    }
+                }
+                result
             }
-
-            _ => None,
+            result => result,
         }
     }
 
-    fn glm_force_txs_batch_message_to_sign(
+    /// Same as `verify_tx_info`, but for the batch of transactions.
+    async fn verify_txs_batch_info(
         &self,
-        txs: &[(ZkSyncTx, Option<TxEthSignature>)],
-        decimals: u8,
-    ) -> Vec<Option<Vec<u8>>> {
-        txs.iter()
-            .map(|(tx, _)| self.glm_force_tx_message_to_sign(tx, decimals))
-            .collect()
-    }
+        batch: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
+        signature: TxEthSignature,
+    ) -> Result<(Vec<SignedZkSyncTx>, EthSignData), SubmitError> {
+        let (current, legacy) = self.sign_message_templates.split_first().unwrap();
+        let result = self
+            .verify_txs_batch_info_with_template(batch.clone(), signature.clone(), current)
+            .await;
 
-    async fn glm_txs_batch_message_to_sign(
-        &self,
-        txs: &[(ZkSyncTx, Option<TxEthSignature>)],
-    ) -> Result<(Option<Token>, Vec<Option<Vec<u8>>>), SubmitError> {
-        let mut messages_to_sign = vec![];
-        let mut batch_token = None;
-
-        for tx in txs {
-            let msg = match self.glm_tx_message_to_sign(&tx.0).await? {
-                Some((token, msg)) => {
-                    if batch_token.is_none() {
-                        batch_token = Some(token.clone());
+        match result {
+            Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature)) => {
+                for template in legacy {
+                    if let Ok(verified) = self
+                        .verify_txs_batch_info_with_template(
+                            batch.clone(),
+                            signature.clone(),
+                            template,
+                        )
+                        .await
+                    {
+                        vlog::debug!("Accepted sign message of version {}", template.version);
+                        return Ok(verified);
                     }
-                    Some(msg)
                 }
-                None => None,
-            };
-            messages_to_sign.push(msg);
+                result
+            }
+            result => result,
         }
-
-        Ok((batch_token, messages_to_sign))
     }
 
-    async fn glm_verify_tx_info(
+    /// Verifies the transaction signatures against the sign message formatted with the template.
+    /// If the signature doesn't match, the message referring the token by its legacy symbol
+    /// is checked as well.
+    async fn verify_tx_info_with_template(
         &self,
         tx: &ZkSyncTx,
         signature: Option<TxEthSignature>,
+        template: &EthSignMessageTemplate,
     ) -> Result<SignedZkSyncTx, SubmitError> {
-        if let Some((token, msg_to_sign)) = self.glm_tx_message_to_sign(&tx).await? {
-            let verify_result = verify_tx_info_message_signature(
-                &tx,
-                signature.clone(),
-                Some(msg_to_sign.clone()),
+        let msg_to_sign = self.tx_sign_message(tx, template, false).await?;
+        let result = verify_tx_info_message_signature(
+            tx,
+            signature.clone(),
+            msg_to_sign.map(|(message, _)| message.into_bytes()),
+            &self.eip712_domain,
+            self.sign_verify_requests.clone(),
+        )
+        .await;
+        if result.is_ok() {
+            return result.map(VerifiedTx::unwrap_tx);
+        }
+
+        if let Some((legacy_msg_to_sign, true)) = self.tx_sign_message(tx, template, true).await? {
+            let legacy_result = verify_tx_info_message_signature(
+                tx,
+                signature,
+                Some(legacy_msg_to_sign.into_bytes()),
+                &self.eip712_domain,
                 self.sign_verify_requests.clone(),
             )
             .await;
-
-            match verify_result {
-                Ok(tx) => Ok(tx.unwrap_tx()),
-                Err(err) => {
-                    if token.symbol == "GNT" {
-                        let msg_to_sign = self.glm_force_tx_message_to_sign(tx, token.decimals);
-
-                        let verified_tx = verify_tx_info_message_signature(
-                            &tx,
-                            signature.clone(),
-                            msg_to_sign,
-                            self.sign_verify_requests.clone(),
-                        )
-                        .await?
-                        .unwrap_tx();
-
-                        Ok(verified_tx)
-                    } else {
-                        Err(err)
-                    }
-                }
+            if let Ok(verified_tx) = legacy_result {
+                vlog::debug!("Accepted sign message with the legacy token symbol");
+                return Ok(verified_tx.unwrap_tx());
             }
-        } else {
-            let verified_tx = verify_tx_info_message_signature(
-                &tx,
-                signature.clone(),
-                None,
-                self.sign_verify_requests.clone(),
-            )
-            .await?
-            .unwrap_tx();
-
-            Ok(verified_tx)
         }
+        result.map(VerifiedTx::unwrap_tx)
     }
 
-    async fn glm_verify_txs_batch_info(
+    /// Same as `verify_tx_info_with_template`, but for the batch of transactions.
+    /// Tokens of all the transactions which have legacy symbols are referred by them at once.
+    async fn verify_txs_batch_info_with_template(
         &self,
         batch: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
         signature: TxEthSignature,
+        template: &EthSignMessageTemplate,
     ) -> Result<(Vec<SignedZkSyncTx>, EthSignData), SubmitError> {
-        let (batch_token, messages_to_sign) = self.glm_txs_batch_message_to_sign(&batch).await?;
-
-        let verify_result = verify_txs_batch_signature(
+        let (messages_to_sign, _) = self
+            .txs_batch_sign_messages(&batch, template, false)
+            .await?;
+        let result = verify_txs_batch_signature(
             batch.clone(),
             signature.clone(),
             messages_to_sign,
+            &self.eip712_domain,
             self.sign_verify_requests.clone(),
         )
         .await;
+        if result.is_ok() {
+            return result.map(VerifiedTx::unwrap_batch);
+        }
 
-        match (verify_result, batch_token) {
-            (Ok(tx), _) => Ok(tx.unwrap_batch()),
-            (Err(_), Some(token)) if token.symbol == "GNT" => {
-                let messages_to_sign =
-                    self.glm_force_txs_batch_message_to_sign(&batch, token.decimals);
-                Ok(verify_txs_batch_signature(
-                    batch,
-                    signature,
-                    messages_to_sign,
-                    self.sign_verify_requests.clone(),
-                )
-                .await?
-                .unwrap_batch())
+        let (legacy_messages_to_sign, substituted) =
+            self.txs_batch_sign_messages(&batch, template, true).await?;
+        if substituted {
+            let legacy_result = verify_txs_batch_signature(
+                batch,
+                signature,
+                legacy_messages_to_sign,
+                &self.eip712_domain,
+                self.sign_verify_requests.clone(),
+            )
+            .await;
+            if let Ok(verified) = legacy_result {
+                vlog::debug!("Accepted batch sign message with the legacy token symbols");
+                return Ok(verified.unwrap_batch());
             }
-            (Err(e), _) => Err(e),
         }
+        result.map(VerifiedTx::unwrap_batch)
     }
 }
 
 async fn send_verify_request_and_recv(
     request: VerifyTxSignatureRequest,
-    mut req_channel: mpsc::Sender<VerifyTxSignatureRequest>,
+    mut req_channel: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
     receiver: oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
 ) -> Result<VerifiedTx, SubmitError> {
     // Send the check request.
     req_channel
-        .send(request)
+        .send(Traced::new(request))
         .await
         .map_err(SubmitError::internal)?;
     // Wait for the check result.
     receiver
         .await
         .map_err(|err| internal_error!(err))?
-        .map_err(SubmitError::TxAdd)
+        .map_err(|err| match err {
+            TxAddError::CosignatureRejected => SubmitError::CosignatureRejected,
+            TxAddError::CosignerUnavailable => SubmitError::CosignerUnavailable(err.to_string()),
+            err => SubmitError::TxAdd(err),
+        })
 }
 
 /// Send a request for Ethereum signature verification and wait for the response.
@@ -693,8 +1531,10 @@ async fn verify_tx_info_message_signature(
     tx: &ZkSyncTx,
     signature: Option<TxEthSignature>,
     msg_to_sign: Option<Vec<u8>>,
-    req_channel: mpsc::Sender<VerifyTxSignatureRequest>,
+    eip712_domain: &EIP712Domain,
+    req_channel: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
 ) -> Result<VerifiedTx, SubmitError> {
+    let msg_to_sign = eth_sign_message(tx, signature.as_ref(), msg_to_sign, eip712_domain);
     let eth_sign_data = match msg_to_sign {
         Some(message_to_sign) => {
             let signature = signature.ok_or(SubmitError::TxAdd(TxAddError::MissingEthSignature))?;
@@ -720,9 +1560,25 @@ async fn verify_tx_info_message_signature(
     send_verify_request_and_recv(request, req_channel, receiever).await
 }
 
+/// Returns the message signed by the Ethereum key of the account: the hash of the typed data
+/// for the EIP-712 signatures, or the text message built from the template otherwise.
+fn eth_sign_message(
+    tx: &ZkSyncTx,
+    signature: Option<&TxEthSignature>,
+    text_message: Option<Vec<u8>>,
+    eip712_domain: &EIP712Domain,
+) -> Option<Vec<u8>> {
+    match signature {
+        Some(TxEthSignature::EIP712Signature(_)) => tx
+            .eip712_sign_hash(eip712_domain)
+            .map(|hash| hash.as_bytes().to_vec()),
+        _ => text_message,
+    }
+}
+
 pub(crate) fn get_batch_sign_message<'a, I: Iterator<Item = &'a ZkSyncTx>>(txs: I) -> Vec<u8> {
     tiny_keccak::keccak256(
-        txs.flat_map(|tx| tx.get_bytes())
+        txs.flat_map(|tx| tx.get_signed_bytes())
             .collect::<Vec<u8>>()
             .as_slice(),
     )
@@ -737,10 +1593,12 @@ async fn verify_txs_batch_signature(
     batch: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
     signature: TxEthSignature,
     msgs_to_sign: Vec<Option<Vec<u8>>>,
-    req_channel: mpsc::Sender<VerifyTxSignatureRequest>,
+    eip712_domain: &EIP712Domain,
+    req_channel: mpsc::Sender<Traced<VerifyTxSignatureRequest>>,
 ) -> Result<VerifiedTx, SubmitError> {
     let mut txs = Vec::with_capacity(batch.len());
     for (tx, message) in batch.into_iter().zip(msgs_to_sign.into_iter()) {
+        let message = eth_sign_message(&tx.0, tx.1.as_ref(), message, eip712_domain);
         // If we have more signatures provided than required,
         // we will verify those too.
         let eth_sign_data = if let (Some(signature), Some(message)) = (tx.1, message) {
@@ -767,6 +1625,111 @@ async fn verify_txs_batch_signature(
     send_verify_request_and_recv(request, req_channel, receiver).await
 }
 
+/// Returns the addresses of the zkSync accounts involved into the transaction.
+fn tx_addresses(tx: &ZkSyncTx) -> Vec<Address> {
+    match tx {
+        ZkSyncTx::Transfer(transfer) => vec![transfer.from, transfer.to],
+        tx => vec![tx.account()],
+    }
+}
+
+/// Returns the addresses of the zkSync accounts involved into the executed operation.
+fn operation_addresses(op: &ExecutedOperations) -> Vec<Address> {
+    match op {
+        ExecutedOperations::Tx(tx) => tx_addresses(&tx.signed_tx.tx),
+        ExecutedOperations::PriorityOp(op) => match &op.priority_op.data {
+            ZkSyncPriorityOp::Deposit(deposit) => vec![deposit.to],
+            ZkSyncPriorityOp::FullExit(full_exit) => vec![full_exit.eth_address],
+        },
+    }
+}
+
+/// Applies the operations of the pending block and the transactions from the mempool to the state,
+/// the failing ones are skipped since they are going to fail in the block as well.
+///
+/// Accounts created by the pending block get the IDs assigned by the state keeper. Returns
+/// the addresses of the accounts created by the mempool transactions, which IDs are not known yet.
+fn apply_pending_txs(
+    state: &mut ZkSyncState,
+    pending_ops: &[ExecutedOperations],
+    mempool_txs: &[SignedZkSyncTx],
+) -> HashSet<Address> {
+    for op in pending_ops {
+        let created = match op {
+            ExecutedOperations::Tx(tx) => match &tx.op {
+                Some(ZkSyncOp::TransferToNew(op)) => Some((op.to, op.tx.to)),
+                _ => None,
+            },
+            ExecutedOperations::PriorityOp(op) => match &op.op {
+                ZkSyncOp::Deposit(op) => Some((op.account_id, op.priority_op.to)),
+                _ => None,
+            },
+        };
+        if let Some((account_id, address)) = created {
+            if state.get_account_by_address(&address).is_none() {
+                state.insert_account(account_id, Account::default_with_address(&address));
+            }
+        }
+
+        match op {
+            ExecutedOperations::Tx(tx) => {
+                state.execute_tx(tx.signed_tx.tx.clone()).ok();
+            }
+            ExecutedOperations::PriorityOp(op) => {
+                state.execute_priority_op(op.priority_op.data.clone());
+            }
+        }
+    }
+
+    let mut created_accounts = HashSet::new();
+    for tx in mempool_txs {
+        if let Ok(success) = state.execute_tx(tx.tx.clone()) {
+            if let ZkSyncOp::TransferToNew(op) = success.executed_op {
+                created_accounts.insert(op.tx.to);
+            }
+        }
+    }
+    created_accounts
+}
+
+/// Executes the batch transactions in order, so the intra-batch effects are taken into account.
+/// Returns the index of the first failing transaction along with the failure reason.
+///
+/// Accounts created by the batch or by the pending transactions (`created_accounts`) get the IDs
+/// that may differ from the ones assigned by the state keeper, so the transactions starting
+/// from the first one initiated by such an account are not checked.
+fn simulate_txs_batch(
+    state: &mut ZkSyncState,
+    mut created_accounts: HashSet<Address>,
+    txs: &[SignedZkSyncTx],
+) -> Result<(), (usize, String)> {
+    for (tx_index, tx) in txs.iter().enumerate() {
+        let initiated_by_created = match &tx.tx {
+            ZkSyncTx::ForcedExit(forced_exit) => {
+                !created_accounts.is_empty()
+                    && state
+                        .get_account(forced_exit.initiator_account_id)
+                        .is_none()
+            }
+            tx => created_accounts.contains(&tx.account()),
+        };
+        if initiated_by_created {
+            break;
+        }
+
+        match state.execute_tx(tx.tx.clone()) {
+            Ok(success) => {
+                if let ZkSyncOp::TransferToNew(op) = success.executed_op {
+                    created_accounts.insert(op.tx.to);
+                }
+            }
+            Err(err) => return Err((tx_index, err.to_string())),
+        }
+    }
+
+    Ok(())
+}
+
 /// Scales the fee provided by user up to check whether the provided fee is enough to cover our expenses for
 /// maintaining the protocol.
 ///
@@ -804,6 +1767,42 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{Account, AccountId, Nonce, Transfer};
+
+    use crate::api_server::rest::v1::test_utils::{dummy_fee_ticker, TestServerConfig};
+
+    /// Creates the signature checker which accepts the transactions only if their Ethereum
+    /// messages are signed by the accounts.
+    fn eth_message_sign_verifier() -> mpsc::Sender<Traced<VerifyTxSignatureRequest>> {
+        let (sender, mut receiver) = mpsc::channel::<Traced<VerifyTxSignatureRequest>>(10);
+
+        actix_rt::spawn(async move {
+            while let Some(item) = receiver.next().await {
+                let item = item.into_inner();
+                let signed_by_account = |tx: &SignedZkSyncTx| match &tx.eth_sign_data {
+                    Some(EthSignData {
+                        signature: TxEthSignature::EthereumSignature(signature),
+                        message,
+                    }) => signature.signature_recover_signer(message).ok() == Some(tx.tx.account()),
+                    _ => false,
+                };
+                let is_signed = match &item.tx {
+                    TxVariant::Tx(tx) => signed_by_account(tx),
+                    TxVariant::Batch(txs, _) => txs.iter().all(signed_by_account),
+                };
+
+                let response = if is_signed {
+                    Ok(VerifiedTx::unverified(item.tx))
+                } else {
+                    Err(TxAddError::IncorrectEthSignature)
+                };
+                item.response.send(response).unwrap_or_default();
+            }
+        });
+
+        sender
+    }
 
     #[test]
     fn test_scaling_user_fee_by_two() {
@@ -834,4 +1833,416 @@ mod tests {
 
         assert_eq!(provided_fee_scaled_by_five_percent, scaled_fee);
     }
+
+    #[test]
+    fn submit_error_retry_after() {
+        let maintenance = |until| {
+            SubmitError::Maintenance(MaintenanceInfo {
+                enabled: true,
+                message: None,
+                until,
+            })
+        };
+
+        let retry_after = maintenance(Some(Utc::now() + chrono::Duration::minutes(10)))
+            .retry_after()
+            .unwrap();
+        assert!(retry_after > Duration::from_secs(590) && retry_after <= Duration::from_secs(600));
+        assert_eq!(
+            maintenance(Some(Utc::now() - chrono::Duration::minutes(1))).retry_after(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            maintenance(None).retry_after(),
+            ErrorCode::Maintenance.retry_after()
+        );
+
+        assert!(SubmitError::communication_core_server("timeout")
+            .retry_after()
+            .is_some());
+        assert_eq!(SubmitError::invalid_params("amount").retry_after(), None);
+        assert_eq!(
+            SubmitError::TxAdd(TxAddError::IncorrectEthSignature).retry_after(),
+            None
+        );
+    }
+
+    /// Checks that the batch is executed with the intra-batch effects taken into account
+    /// and the first failing transaction is reported.
+    #[test]
+    fn simulate_txs_batch_failing_tx() {
+        let alice = ZkSyncAccount::rand();
+        alice.set_account_id(Some(AccountId(1)));
+        let bob = ZkSyncAccount::rand();
+        bob.set_account_id(Some(AccountId(2)));
+
+        let initial_state = || {
+            let mut accounts = AccountMap::default();
+            for (account_id, owner, balance) in &[(1, &alice, 100u64), (2, &bob, 0)] {
+                let mut account = Account::default_with_address(&owner.address);
+                account.pub_key_hash = owner.pubkey_hash;
+                account.add_balance(TokenId(0), &(*balance).into());
+                accounts.insert(AccountId(*account_id), account);
+            }
+            ZkSyncState::from_acc_map(accounts, BlockNumber(0))
+        };
+        let transfer = |from: &ZkSyncAccount, to: &ZkSyncAccount, amount: u64, nonce: u32| {
+            let (transfer, _) = from.sign_transfer(
+                TokenId(0),
+                "ETH",
+                amount.into(),
+                0u64.into(),
+                &to.address,
+                Some(Nonce(nonce)),
+                false,
+            );
+            SignedZkSyncTx::from(ZkSyncTx::Transfer(Box::new(transfer)))
+        };
+
+        // Bob spends the funds received within the same batch.
+        let batch = vec![transfer(&alice, &bob, 60, 0), transfer(&bob, &alice, 30, 0)];
+        assert_eq!(
+            simulate_txs_batch(&mut initial_state(), HashSet::new(), &batch),
+            Ok(())
+        );
+
+        let batch = vec![transfer(&alice, &bob, 60, 0), transfer(&alice, &bob, 60, 1)];
+        let (tx_index, reason) =
+            simulate_txs_batch(&mut initial_state(), HashSet::new(), &batch).unwrap_err();
+        assert_eq!(tx_index, 1);
+        assert!(reason.contains("Not enough balance"));
+
+        let batch = vec![transfer(&alice, &bob, 10, 0), transfer(&alice, &bob, 10, 0)];
+        let (tx_index, reason) =
+            simulate_txs_batch(&mut initial_state(), HashSet::new(), &batch).unwrap_err();
+        assert_eq!(tx_index, 1);
+        assert!(reason.contains("Nonce mismatch"));
+
+        // Batch spends the funds received by the pending transaction and follows its nonce.
+        let pending = vec![transfer(&alice, &bob, 60, 0)];
+        let batch = vec![transfer(&bob, &alice, 30, 0), transfer(&alice, &bob, 10, 1)];
+        let (tx_index, _) =
+            simulate_txs_batch(&mut initial_state(), HashSet::new(), &batch).unwrap_err();
+        assert_eq!(tx_index, 0);
+
+        let mut state = initial_state();
+        let created_accounts = apply_pending_txs(&mut state, &[], &pending);
+        assert!(created_accounts.is_empty());
+        assert_eq!(
+            simulate_txs_batch(&mut state, created_accounts, &batch),
+            Ok(())
+        );
+    }
+
+    /// Checks that the transactions signed with the messages referring the tokens by
+    /// their legacy symbols are accepted, both alone and in a batch.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn legacy_token_symbols() -> anyhow::Result<()> {
+        const GNT_TOKEN_ID: TokenId = TokenId(16);
+
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let mut tx_sender = TxSender::with_client(
+            CoreApiClient::new("http://127.0.0.1:1".to_owned(), "secret".to_owned()),
+            cfg.pool.clone(),
+            eth_message_sign_verifier(),
+            dummy_fee_ticker(),
+            &cfg.config,
+        );
+        tx_sender.legacy_token_symbols = vec![("GNT".to_owned(), "tGLM".to_owned())]
+            .into_iter()
+            .collect();
+        let template = tx_sender.sign_message_templates[0].clone();
+
+        let alice = ZkSyncAccount::rand();
+        alice.set_account_id(Some(AccountId(1)));
+        let transfer = |token_id: TokenId, nonce: u32| {
+            let (transfer, _) = alice.sign_transfer(
+                token_id,
+                "",
+                100u64.into(),
+                1u64.into(),
+                &Address::random(),
+                Some(Nonce(nonce)),
+                false,
+            );
+            transfer
+        };
+        let sign = |transfer: &Transfer, symbol: &str| {
+            let message = transfer.get_ethereum_sign_message_with_template(&template, symbol, 18);
+            let signature =
+                PackedEthSignature::sign(&alice.eth_private_key, message.as_bytes()).unwrap();
+            (message, TxEthSignature::EthereumSignature(signature))
+        };
+
+        // Single transaction.
+        let gnt_transfer = transfer(GNT_TOKEN_ID, 0);
+        let tx = ZkSyncTx::Transfer(Box::new(gnt_transfer.clone()));
+        let (message, signature) = sign(&gnt_transfer, "tGLM");
+        assert_eq!(
+            tx_sender.tx_sign_message(&tx, &template, true).await?,
+            Some((message.clone(), true))
+        );
+        assert_eq!(
+            tx_sender.tx_sign_message(&tx, &template, false).await?,
+            Some((sign(&gnt_transfer, "GNT").0, false))
+        );
+
+        let verified = tx_sender.verify_tx_info(&tx, Some(signature)).await?;
+        assert_eq!(
+            verified.eth_sign_data.unwrap().message,
+            message.into_bytes()
+        );
+        tx_sender
+            .verify_tx_info(&tx, Some(sign(&gnt_transfer, "GNT").1))
+            .await?;
+        let result = tx_sender
+            .verify_tx_info(&tx, Some(sign(&gnt_transfer, "ETH").1))
+            .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature))
+        ));
+
+        // Batch with both the renamed token and the token without the legacy symbol.
+        let eth_transfer = transfer(TokenId(0), 1);
+        let batch = vec![
+            (tx.clone(), Some(sign(&gnt_transfer, "tGLM").1)),
+            (
+                ZkSyncTx::Transfer(Box::new(eth_transfer.clone())),
+                Some(sign(&eth_transfer, "ETH").1),
+            ),
+        ];
+        let (messages, substituted) = tx_sender
+            .txs_batch_sign_messages(&batch, &template, true)
+            .await?;
+        assert!(substituted);
+        assert_eq!(
+            messages,
+            vec![
+                Some(sign(&gnt_transfer, "tGLM").0.into_bytes()),
+                Some(sign(&eth_transfer, "ETH").0.into_bytes()),
+            ]
+        );
+
+        let batch_message = get_batch_sign_message(batch.iter().map(|(tx, _)| tx));
+        let batch_signature = TxEthSignature::EthereumSignature(PackedEthSignature::sign(
+            &alice.eth_private_key,
+            &batch_message,
+        )?);
+        let (txs, _) = tx_sender
+            .verify_txs_batch_info(batch, batch_signature.clone())
+            .await?;
+        assert_eq!(txs.len(), 2);
+
+        let batch = vec![(tx, Some(sign(&gnt_transfer, "ETH").1))];
+        let result = tx_sender
+            .verify_txs_batch_info(batch, batch_signature)
+            .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature))
+        ));
+
+        Ok(())
+    }
+
+    /// Checks that the co-signer registrations are signed by the accounts, can't be replayed
+    /// and have to be approved by the current co-signers.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn cosigner_registrations() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        let mut tx_sender = TxSender::with_client(
+            CoreApiClient::new("http://127.0.0.1:1".to_owned(), "secret".to_owned()),
+            cfg.pool.clone(),
+            eth_message_sign_verifier(),
+            dummy_fee_ticker(),
+            &cfg.config,
+        );
+        let chain_id = tx_sender.eip712_domain.chain_id;
+        let sign = |key: &H256, cosigner: Option<Address>, nonce: u32| {
+            let message = registration_message(cosigner, nonce, chain_id);
+            PackedEthSignature::sign(key, message.as_bytes()).unwrap()
+        };
+
+        let account = ZkSyncAccount::rand();
+        let (first_key, second_key) = (H256::random(), H256::random());
+        let first = PackedEthSignature::address_from_private_key(&first_key)?;
+        let second = PackedEthSignature::address_from_private_key(&second_key)?;
+        let account_signature = sign(&account.eth_private_key, Some(first), 1);
+
+        // Co-signers can't be registered if the co-signing service is not configured.
+        tx_sender.cosigning_enabled = false;
+        let result = tx_sender
+            .register_cosigner(
+                account.address,
+                Some(first),
+                1,
+                account_signature.clone(),
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(SubmitError::InvalidParams(_))));
+        tx_sender.cosigning_enabled = true;
+
+        // Registration must be signed by the account.
+        let result = tx_sender
+            .register_cosigner(
+                account.address,
+                Some(first),
+                1,
+                sign(&first_key, Some(first), 1),
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(SubmitError::TxAdd(TxAddError::IncorrectEthSignature))
+        ));
+
+        tx_sender
+            .register_cosigner(
+                account.address,
+                Some(first),
+                1,
+                account_signature.clone(),
+                None,
+            )
+            .await?;
+        // The same registration can't be replayed.
+        let result = tx_sender
+            .register_cosigner(account.address, Some(first), 1, account_signature, None)
+            .await;
+        assert!(matches!(result, Err(SubmitError::InvalidParams(_))));
+
+        // Changes of the co-signer must be approved by the current one.
+        let account_signature = sign(&account.eth_private_key, Some(second), 2);
+        let result = tx_sender
+            .register_cosigner(
+                account.address,
+                Some(second),
+                2,
+                account_signature.clone(),
+                Some(sign(&second_key, Some(second), 2)),
+            )
+            .await;
+        assert!(matches!(result, Err(SubmitError::CosignatureRejected)));
+        tx_sender
+            .register_cosigner(
+                account.address,
+                Some(second),
+                2,
+                account_signature,
+                Some(sign(&first_key, Some(second), 2)),
+            )
+            .await?;
+
+        // Opting out requires the approval as well.
+        tx_sender
+            .register_cosigner(
+                account.address,
+                None,
+                3,
+                sign(&account.eth_private_key, None, 3),
+                Some(sign(&second_key, None, 3)),
+            )
+            .await?;
+        let registration = cfg
+            .pool
+            .access_storage()
+            .await?
+            .cosigners_schema()
+            .load_registration(account.address)
+            .await?
+            .expect("Registration is saved");
+        assert_eq!(registration.cosigner, None);
+        assert_eq!(registration.registration_nonce, 3);
+
+        Ok(())
+    }
+
+    /// Checks that the messages to sign are returned for the transactions which need
+    /// the Ethereum signatures.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn tx_messages_to_sign() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let tx_sender = TxSender::with_client(
+            CoreApiClient::new("http://127.0.0.1:1".to_owned(), "secret".to_owned()),
+            cfg.pool.clone(),
+            eth_message_sign_verifier(),
+            dummy_fee_ticker(),
+            &cfg.config,
+        );
+        let template = &tx_sender.sign_message_templates[0];
+
+        let alice = ZkSyncAccount::rand();
+        alice.set_account_id(Some(AccountId(1)));
+        let recipient = Address::random();
+
+        let (transfer, _) = alice.sign_transfer(
+            TokenId(1),
+            "PHNX",
+            100u64.into(),
+            1u64.into(),
+            &recipient,
+            None,
+            false,
+        );
+        assert_eq!(
+            tx_sender
+                .tx_message_to_sign(&ZkSyncTx::Transfer(Box::new(transfer.clone())))
+                .await?,
+            Some(transfer.get_ethereum_sign_message_with_template(template, "PHNX", 18))
+        );
+
+        let (withdraw, _) = alice.sign_withdraw(
+            TokenId(1),
+            "PHNX",
+            100u64.into(),
+            1u64.into(),
+            &recipient,
+            None,
+            false,
+        );
+        assert_eq!(
+            tx_sender
+                .tx_message_to_sign(&ZkSyncTx::Withdraw(Box::new(withdraw.clone())))
+                .await?,
+            Some(withdraw.get_ethereum_sign_message_with_template(template, "PHNX", 18))
+        );
+
+        let change_pubkey =
+            alice.sign_change_pubkey_tx(None, false, TokenId(0), 1u64.into(), false);
+        let message = tx_sender
+            .tx_message_to_sign(&ZkSyncTx::ChangePubKey(Box::new(change_pubkey.clone())))
+            .await?
+            .expect("ChangePubKey is signed with the message");
+        assert!(message.starts_with("Register zkSync pubkey:"));
+        assert_eq!(message.into_bytes(), change_pubkey.get_eth_signed_data()?);
+
+        let forced_exit = alice.sign_forced_exit(TokenId(0), 1u64.into(), &recipient, None, false);
+        assert_eq!(
+            tx_sender
+                .tx_message_to_sign(&ZkSyncTx::ForcedExit(Box::new(forced_exit)))
+                .await?,
+            None
+        );
+
+        Ok(())
+    }
 }
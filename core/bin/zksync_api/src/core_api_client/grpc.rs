@@ -0,0 +1,232 @@
+//! gRPC transport of the requests to the Core mempool.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tonic::{
+    metadata::{MetadataKey, MetadataValue},
+    transport::{Channel, Endpoint},
+    Code, IntoRequest, Request, Status,
+};
+
+use zksync_core_api_grpc::{
+    auth::{message_body, RequestSignature},
+    v1::{self, core_api_client::CoreApiClient as GrpcClient, tx_add_error, tx_add_result},
+};
+use zksync_types::{tx::TxEthSignature, Nonce, SignedZkSyncTx};
+
+use super::{CoreApiError, OutboxState};
+use crate::tx_error::TxAddError;
+
+impl From<v1::TxAddError> for TxAddError {
+    fn from(error: v1::TxAddError) -> Self {
+        match error.code() {
+            tx_add_error::Code::NonceMismatch => Self::NonceMismatch,
+            tx_add_error::Code::NonceTooHigh => Self::NonceTooHigh {
+                min_nonce: Nonce(error.min_nonce),
+                max_nonce: Nonce(error.max_nonce),
+            },
+            tx_add_error::Code::IncorrectTx => Self::IncorrectTx,
+            tx_add_error::Code::TxFeeTooLow => Self::TxFeeTooLow,
+            tx_add_error::Code::TxBatchFeeTooLow => Self::TxBatchFeeTooLow,
+            tx_add_error::Code::Eip1271SignatureVerificationFail => {
+                Self::EIP1271SignatureVerificationFail
+            }
+            tx_add_error::Code::MissingEthSignature => Self::MissingEthSignature,
+            tx_add_error::Code::IncorrectEthSignature => Self::IncorrectEthSignature,
+            tx_add_error::Code::ChangePkNotAuthorized => Self::ChangePkNotAuthorized,
+            tx_add_error::Code::DbError => Self::DbError,
+            tx_add_error::Code::EmptyBatch => Self::EmptyBatch,
+            tx_add_error::Code::BatchTooBig => Self::BatchTooBig,
+            tx_add_error::Code::BatchWithdrawalsOverload => Self::BatchWithdrawalsOverload,
+            tx_add_error::Code::TooManyPendingTxs => Self::TooManyPendingTxs {
+                limit: error.limit as usize,
+            },
+            // Unknown codes are decoded as `Unspecified`.
+            tx_add_error::Code::Other | tx_add_error::Code::Unspecified => Self::Other,
+        }
+    }
+}
+
+/// Returns `true` if the request has failed because the Core is unreachable or failing,
+/// rather than because of the request itself.
+pub(super) fn is_transient(status: &Status) -> bool {
+    // Transport errors (e.g. refused connections) are reported with the `Unknown` code.
+    matches!(
+        status.code(),
+        Code::Unavailable | Code::DeadlineExceeded | Code::Internal | Code::Unknown
+    )
+}
+
+pub(super) fn outbox_state(status: &Status) -> Option<OutboxState> {
+    match status.code() {
+        Code::Aborted => Some(OutboxState::InProgress),
+        Code::NotFound => Some(OutboxState::Unknown),
+        _ => None,
+    }
+}
+
+fn decode_result(result: v1::TxAddResult) -> Result<Result<(), TxAddError>, CoreApiError> {
+    match result.outcome {
+        Some(tx_add_result::Outcome::Accepted(_)) => Ok(Ok(())),
+        Some(tx_add_result::Outcome::Error(error)) => Ok(Err(error.into())),
+        None => Err(Status::internal("Core returned an empty result").into()),
+    }
+}
+
+fn tx_request(tx: &SignedZkSyncTx, outbox_id: i64) -> v1::SendTxRequest {
+    v1::SendTxRequest {
+        outbox_id,
+        tx: Some(tx.into()),
+    }
+}
+
+fn txs_batch_request(
+    txs: &[SignedZkSyncTx],
+    eth_signature: &Option<TxEthSignature>,
+    outbox_id: i64,
+) -> v1::SendTxsBatchRequest {
+    v1::SendTxsBatchRequest {
+        outbox_id,
+        txs: txs.iter().map(Into::into).collect(),
+        eth_signature: eth_signature.as_ref().map(Into::into),
+    }
+}
+
+/// Signs the request to the method with the given path, the same way as the HTTP requests.
+/// `body` is the encoded request message. The context of the current trace is passed along
+/// with the signature, so the Core continues the trace.
+fn signed<T>(
+    request: impl IntoRequest<T>,
+    secret_auth: &str,
+    path: &str,
+    body: &[u8],
+) -> Request<T> {
+    let mut request = request.into_request();
+    let signature = RequestSignature::new(secret_auth, "POST", path, body);
+    for (key, value) in signature.headers().iter() {
+        let value =
+            MetadataValue::from_str(value).expect("Signature is not a valid metadata value");
+        request.metadata_mut().insert(*key, value);
+    }
+    for (key, value) in vlog::TraceContext::current().headers() {
+        if let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::from_str(value),
+        ) {
+            request.metadata_mut().insert(key, value);
+        }
+    }
+    request
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct GrpcTransport {
+    endpoint: Endpoint,
+    channel: Arc<Mutex<Option<Channel>>>,
+    secret_auth: String,
+}
+
+impl GrpcTransport {
+    pub fn new(url: &str, timeout: Duration, secret_auth: String) -> Self {
+        let endpoint = Endpoint::from_shared(url.to_owned())
+            .expect("Invalid URL of the Core gRPC API")
+            .timeout(timeout);
+
+        Self {
+            endpoint,
+            channel: Arc::default(),
+            secret_auth,
+        }
+    }
+
+    /// Returns the client of the shared channel. The channel is connected on the first request,
+    /// so the transport can be created outside of the runtime.
+    fn client(&self) -> GrpcClient<Channel> {
+        let mut channel = self.channel.lock().unwrap();
+        let channel = channel.get_or_insert_with(|| {
+            self.endpoint
+                .connect_lazy()
+                .expect("Unable to create the Core gRPC channel")
+        });
+
+        GrpcClient::new(channel.clone())
+    }
+
+    pub async fn send_tx(
+        &self,
+        tx: &SignedZkSyncTx,
+        outbox_id: i64,
+    ) -> Result<Result<(), TxAddError>, CoreApiError> {
+        let request = tx_request(tx, outbox_id);
+        let body = message_body(&request);
+        let request = signed(request, &self.secret_auth, v1::SEND_TX_PATH, &body);
+        let response = self.client().send_tx(request).await?;
+
+        decode_result(response.into_inner())
+    }
+
+    pub async fn send_txs_batch(
+        &self,
+        txs: &[SignedZkSyncTx],
+        eth_signature: &Option<TxEthSignature>,
+        outbox_id: i64,
+    ) -> Result<Result<(), TxAddError>, CoreApiError> {
+        let request = txs_batch_request(txs, eth_signature, outbox_id);
+        let body = message_body(&request);
+        let request = signed(request, &self.secret_auth, v1::SEND_TXS_BATCH_PATH, &body);
+        let response = self.client().send_txs_batch(request).await?;
+
+        decode_result(response.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_add_error_conversion() {
+        let mut error = v1::TxAddError {
+            min_nonce: 1,
+            max_nonce: 5,
+            ..v1::TxAddError::default()
+        };
+        error.set_code(tx_add_error::Code::NonceTooHigh);
+        assert!(matches!(
+            TxAddError::from(error),
+            TxAddError::NonceTooHigh { min_nonce, max_nonce }
+                if min_nonce == Nonce(1) && max_nonce == Nonce(5)
+        ));
+
+        // Codes unknown to the client are decoded as the internal error.
+        let error = v1::TxAddError {
+            code: 1000,
+            ..v1::TxAddError::default()
+        };
+        assert!(matches!(TxAddError::from(error), TxAddError::Other));
+    }
+
+    #[test]
+    fn empty_result_is_error() {
+        let result = decode_result(v1::TxAddResult { outcome: None });
+        assert!(matches!(result, Err(CoreApiError::Grpc(_))));
+
+        let result = decode_result(v1::TxAddResult {
+            outcome: Some(tx_add_result::Outcome::Accepted(v1::Accepted {})),
+        });
+        assert!(matches!(result, Ok(Ok(()))));
+    }
+
+    #[test]
+    fn outbox_state_is_reported() {
+        let error = CoreApiError::from(Status::aborted("Request is being processed right now"));
+        assert_eq!(error.outbox_state(), Some(OutboxState::InProgress));
+        let error = CoreApiError::from(Status::not_found("Request is not in the outbox"));
+        assert_eq!(error.outbox_state(), Some(OutboxState::Unknown));
+        let error = CoreApiError::from(Status::internal("Failed to process the request"));
+        assert_eq!(error.outbox_state(), None);
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::{header::CONTENT_TYPE, Method, StatusCode, Url};
+use thiserror::Error;
+
+use zksync_config::configs::api::PrivateApi;
+use zksync_core_api_grpc::auth::RequestSignature;
+pub use zksync_types::EthBlockId;
+use zksync_types::{
+    tx::{TxEthSignature, TxHash},
+    Address, PriorityOp, QueuedPriorityOp, SignedZkSyncTx, ZkSyncTx, H256,
+};
+
+use crate::tx_error::TxAddError;
+
+mod grpc;
+
+/// Default timeout of the requests to the Core.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default maximum number of attempts for the idempotent requests.
+const DEFAULT_REQUEST_ATTEMPTS: u32 = 3;
+/// Default number of the consecutive failures after which the Core is considered unavailable.
+const DEFAULT_CIRCUIT_BREAKER_THRESHOLD: u32 = 5;
+/// Default time after which the requests to the unavailable Core are sent again.
+const DEFAULT_CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(10);
+/// Interval before the first retry of the idempotent request, doubled for each next retry.
+const RETRY_BASE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Error of the request to the Core.
+#[derive(Debug, Error)]
+pub enum CoreApiError {
+    /// The Core has failed too many requests in a row, so the request wasn't sent.
+    #[error("core server is unavailable")]
+    Unavailable,
+    #[error("{0}")]
+    Request(#[from] reqwest::Error),
+    #[error("{0}")]
+    Grpc(#[from] tonic::Status),
+}
+
+/// State of the outbox request reported by the Core instead of its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxState {
+    /// The request is being processed right now.
+    InProgress,
+    /// The request is not in the outbox, so it will never be processed.
+    Unknown,
+}
+
+impl CoreApiError {
+    /// Returns `true` if the request has failed because the Core is unreachable or failing,
+    /// rather than because of the request itself.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Request(err) => is_transient(err),
+            Self::Grpc(status) => grpc::is_transient(status),
+            Self::Unavailable => false,
+        }
+    }
+
+    /// Returns the state of the outbox request if the Core has reported it instead of the result.
+    pub fn outbox_state(&self) -> Option<OutboxState> {
+        match self {
+            Self::Request(err) => match err.status()? {
+                StatusCode::CONFLICT => Some(OutboxState::InProgress),
+                StatusCode::GONE => Some(OutboxState::Unknown),
+                _ => None,
+            },
+            Self::Grpc(status) => grpc::outbox_state(status),
+            Self::Unavailable => None,
+        }
+    }
+}
+
+/// Returns `true` if the request has failed because the Core is unreachable or failing,
+/// rather than because of the request itself.
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_connect()
+        || err.is_timeout()
+        || err
+            .status()
+            .map_or(false, |status| status.is_server_error())
+}
+
+/// Tracks the consecutive failures of the requests to the Core. Once there are too many of them,
+/// the circuit is open: the requests are rejected without being sent until the cooldown passes.
+/// After that the requests are sent again, and the first failed one opens the circuit again.
+#[derive(Debug)]
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            consecutive_failures: 0,
+            open_until: None,
+        }
+    }
+
+    fn is_open(&self, now: Instant) -> bool {
+        matches!(self.open_until, Some(open_until) if now < open_until)
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= self.threshold {
+            self.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+/// `CoreApiClient` is capable of interacting with a private zkSync Core API.
+///
+/// Each request is signed with the secret shared with the Core (see `zksync_core_api_grpc::auth`).
+/// Every attempt of the retried request is signed anew, since the Core accepts each signed
+/// request only once.
+///
+/// The idempotent requests are retried with backoff in case of the network failures.
+/// If the Core fails a number of requests in a row, it's considered unavailable for a while,
+/// and the requests fail with `CoreApiError::Unavailable` without being sent.
+///
+/// If the gRPC transport is configured, the transactions are sent to the Core mempool through it,
+/// while the rest of the requests are still sent through the HTTP API.
+#[derive(Debug, Clone)]
+pub struct CoreApiClient {
+    client: reqwest::Client,
+    addr: String,
+    secret_auth: String,
+    request_attempts: u32,
+    circuit_breaker: Arc<Mutex<CircuitBreaker>>,
+    grpc: Option<grpc::GrpcTransport>,
+}
+
+impl CoreApiClient {
+    pub fn new(addr: String, secret_auth: String) -> Self {
+        Self::with_options(
+            addr,
+            secret_auth,
+            DEFAULT_TIMEOUT,
+            DEFAULT_REQUEST_ATTEMPTS,
+            CircuitBreaker::new(
+                DEFAULT_CIRCUIT_BREAKER_THRESHOLD,
+                DEFAULT_CIRCUIT_BREAKER_COOLDOWN,
+            ),
+        )
+    }
+
+    pub fn from_config(config: &PrivateApi) -> Self {
+        let mut client = Self::with_options(
+            config.url.clone(),
+            config.secret_auth.clone(),
+            config.client_timeout(),
+            config.client_request_attempts,
+            CircuitBreaker::new(
+                config.client_circuit_breaker_threshold,
+                config.client_circuit_breaker_cooldown(),
+            ),
+        );
+        client.grpc = config.grpc_url.as_ref().map(|url| {
+            grpc::GrpcTransport::new(url, config.client_timeout(), config.secret_auth.clone())
+        });
+
+        client
+    }
+
+    fn with_options(
+        addr: String,
+        secret_auth: String,
+        timeout: Duration,
+        request_attempts: u32,
+        circuit_breaker: CircuitBreaker,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Unable to create the Core API client");
+
+        Self {
+            client,
+            addr,
+            secret_auth,
+            request_attempts: request_attempts.max(1),
+            circuit_breaker: Arc::new(Mutex::new(circuit_breaker)),
+            grpc: None,
+        }
+    }
+
+    /// Sends a new transaction to the Core mempool.
+    ///
+    /// `outbox_id` is the ID of the request stored in the outbox, the Core processes
+    /// the requests with the same ID only once.
+    pub async fn send_tx(
+        &self,
+        tx: &SignedZkSyncTx,
+        outbox_id: i64,
+    ) -> Result<Result<(), TxAddError>, CoreApiError> {
+        if let Some(grpc) = &self.grpc {
+            let request = grpc.send_tx(tx, outbox_id);
+            return self.with_circuit_breaker(request).await;
+        }
+
+        let endpoint = format!("{}/new_tx?outbox_id={}", self.addr, outbox_id);
+        self.post(&endpoint, tx).await
+    }
+
+    /// Sends a new transactions batch to the Core mempool.
+    ///
+    /// `outbox_id` is the ID of the request stored in the outbox, the Core processes
+    /// the requests with the same ID only once.
+    pub async fn send_txs_batch(
+        &self,
+        txs: &[SignedZkSyncTx],
+        eth_signature: &Option<TxEthSignature>,
+        outbox_id: i64,
+    ) -> Result<Result<(), TxAddError>, CoreApiError> {
+        if let Some(grpc) = &self.grpc {
+            let request = grpc.send_txs_batch(txs, eth_signature, outbox_id);
+            return self.with_circuit_breaker(request).await;
+        }
+
+        let endpoint = format!("{}/new_txs_batch?outbox_id={}", self.addr, outbox_id);
+        let data = (txs, eth_signature);
+
+        self.post(&endpoint, data).await
+    }
+
+    /// Queries information about unconfirmed deposit operations for a certain address from a Core.
+    pub async fn get_unconfirmed_deposits(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PriorityOp>, CoreApiError> {
+        let endpoint = format!(
+            "{}/unconfirmed_deposits/0x{}",
+            self.addr,
+            hex::encode(address)
+        );
+        self.get(&endpoint).await
+    }
+
+    /// Queries information about unconfirmed priority operations for a certain address from a Core.
+    pub async fn get_unconfirmed_ops(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PriorityOp>, CoreApiError> {
+        let endpoint = format!("{}/unconfirmed_ops/0x{}", self.addr, hex::encode(address));
+        self.get(&endpoint).await
+    }
+
+    /// Queries information about unconfirmed priority operation from a Core.
+    pub async fn get_unconfirmed_op(
+        &self,
+        eth_tx_hash: H256,
+    ) -> Result<Option<(EthBlockId, PriorityOp)>, CoreApiError> {
+        let endpoint = format!(
+            "{}/unconfirmed_op/0x{}",
+            self.addr,
+            hex::encode(eth_tx_hash)
+        );
+        self.get(&endpoint).await
+    }
+
+    /// Queries the priority operations queue observed by the Core, optionally filtered by
+    /// the initiator address.
+    pub async fn get_priority_queue(
+        &self,
+        address: Option<Address>,
+    ) -> Result<Vec<QueuedPriorityOp>, CoreApiError> {
+        let endpoint = match address {
+            Some(address) => format!("{}/priority_queue/0x{}", self.addr, hex::encode(address)),
+            None => format!("{}/priority_queue", self.addr),
+        };
+        self.get(&endpoint).await
+    }
+
+    /// Queries the not yet executed priority operations moving funds to a certain address,
+    /// such as deposits to this address.
+    pub async fn get_incoming_priority_ops(
+        &self,
+        address: Address,
+    ) -> Result<Vec<QueuedPriorityOp>, CoreApiError> {
+        let endpoint = format!(
+            "{}/priority_queue/0x{}/incoming",
+            self.addr,
+            hex::encode(address)
+        );
+        self.get(&endpoint).await
+    }
+
+    /// Makes the Core seal the pending block without waiting for the block timeout.
+    pub async fn seal_block(&self) -> Result<(), CoreApiError> {
+        let endpoint = format!("{}/seal_block", self.addr);
+        self.post(&endpoint, ()).await
+    }
+
+    /// Removes the transaction from the Core mempool, along with the batch it belongs to.
+    /// Returns the hashes of the removed transactions.
+    pub async fn evict_tx(&self, tx_hash: TxHash) -> Result<Vec<TxHash>, CoreApiError> {
+        let endpoint = format!("{}/evict_tx/{}", self.addr, tx_hash);
+        self.post(&endpoint, ()).await
+    }
+
+    /// Removes all the transactions of the account from the Core mempool.
+    /// Returns the hashes of the removed transactions.
+    pub async fn evict_account_txs(&self, address: Address) -> Result<Vec<TxHash>, CoreApiError> {
+        let endpoint = format!("{}/evict_account_txs/0x{}", self.addr, hex::encode(address));
+        self.post(&endpoint, ()).await
+    }
+
+    /// Queries the transactions of the account waiting in the Core mempool,
+    /// in the order of execution.
+    pub async fn get_queued_txs(&self, address: Address) -> Result<Vec<ZkSyncTx>, CoreApiError> {
+        let endpoint = format!("{}/queued_txs/0x{}", self.addr, hex::encode(address));
+        self.get(&endpoint).await
+    }
+
+    /// Checks that the Core server is reachable.
+    pub async fn ping(&self) -> Result<(), CoreApiError> {
+        let endpoint = format!("{}/ping", self.addr);
+        self.get(&endpoint).await
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, CoreApiError> {
+        let mut attempt = 0;
+        loop {
+            match self.request(Method::GET, url, Vec::new()).await {
+                Err(CoreApiError::Request(err))
+                    if is_transient(&err) && attempt + 1 < self.request_attempts =>
+                {
+                    vlog::debug!("Retrying the request to the Core {}: {}", url, err);
+                    tokio::time::delay_for(RETRY_BASE_INTERVAL * 2u32.pow(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        request: impl serde::Serialize,
+    ) -> Result<T, CoreApiError> {
+        let body =
+            serde_json::to_vec(&request).expect("Unable to serialize the request to the Core");
+        self.request(Method::POST, url, body).await
+    }
+
+    async fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Vec<u8>,
+    ) -> Result<T, CoreApiError> {
+        let request = self.signed_request(method, url, body);
+        self.with_circuit_breaker(async { Self::send(request).await.map_err(CoreApiError::from) })
+            .await
+    }
+
+    /// Sends the request unless the Core is considered unavailable, and records its outcome.
+    async fn with_circuit_breaker<T>(
+        &self,
+        request: impl Future<Output = Result<T, CoreApiError>>,
+    ) -> Result<T, CoreApiError> {
+        if self.circuit_breaker.lock().unwrap().is_open(Instant::now()) {
+            metrics::counter!("api.core_api_client.rejected_requests", 1);
+            return Err(CoreApiError::Unavailable);
+        }
+
+        let result = request.await;
+
+        let mut circuit_breaker = self.circuit_breaker.lock().unwrap();
+        match &result {
+            Err(err) if err.is_transient() => {
+                circuit_breaker.record_failure(Instant::now());
+                if circuit_breaker.is_open(Instant::now()) {
+                    vlog::warn!("Core server is considered unavailable: {}", err);
+                }
+            }
+            _ => circuit_breaker.record_success(),
+        }
+
+        result
+    }
+
+    /// Builds the request signed with the shared secret. The signature covers the path
+    /// along with the query, the same way the Core receives them. The context of the current
+    /// trace is passed in the headers, so the Core continues the trace.
+    fn signed_request(&self, method: Method, url: &str, body: Vec<u8>) -> reqwest::RequestBuilder {
+        let parsed_url = Url::parse(url).expect("Invalid URL of the Core API");
+        let path = match parsed_url.query() {
+            Some(query) => format!("{}?{}", parsed_url.path(), query),
+            None => parsed_url.path().to_owned(),
+        };
+        let signature = RequestSignature::new(&self.secret_auth, method.as_str(), &path, &body);
+
+        let mut request = self.client.request(method, parsed_url);
+        for (name, value) in signature.headers().iter() {
+            request = request.header(*name, value.as_str());
+        }
+        for (name, value) in vlog::TraceContext::current().headers() {
+            request = request.header(name, value);
+        }
+        if !body.is_empty() {
+            request = request.header(CONTENT_TYPE, "application/json").body(body);
+        }
+        request
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, reqwest::Error> {
+        request.send().await?.error_for_status()?.json().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circuit_breaker() {
+        let cooldown = Duration::from_secs(10);
+        let mut circuit_breaker = CircuitBreaker::new(2, cooldown);
+        let now = Instant::now();
+
+        circuit_breaker.record_failure(now);
+        assert!(!circuit_breaker.is_open(now));
+        // Successful request resets the counter.
+        circuit_breaker.record_success();
+        circuit_breaker.record_failure(now);
+        assert!(!circuit_breaker.is_open(now));
+
+        circuit_breaker.record_failure(now);
+        assert!(circuit_breaker.is_open(now));
+        assert!(circuit_breaker.is_open(now + cooldown / 2));
+        // Requests are allowed after the cooldown, and the next failure opens the circuit again.
+        assert!(!circuit_breaker.is_open(now + cooldown));
+        circuit_breaker.record_failure(now + cooldown);
+        assert!(circuit_breaker.is_open(now + cooldown));
+
+        circuit_breaker.record_success();
+        assert!(!circuit_breaker.is_open(now + cooldown));
+    }
+}
@@ -1,27 +1,69 @@
 //! Module capable of checking the onchain operations, such as
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
+//!
+//! Onchain `ChangePubKey` authorization facts are indexed by the Ethereum watcher, so
+//! they're checked against the database first, and the contract is only queried for the
+//! facts that weren't indexed yet.
+
+use std::time::{Duration, Instant};
 
 use web3::{contract::Options, types::Address};
 use zksync_contracts::eip1271_contract;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_storage::ConnectionPool;
 use zksync_types::{
     tx::EIP1271Signature,
     {Nonce, PubKeyHash},
 };
 
+use crate::utils::shared_lru_cache::SharedLruCache;
+
 /// isValidSignature return value according to EIP1271 standard
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Capacity of the cache of the accounts checked for being the smart contracts.
+const CONTRACT_ACCOUNTS_CACHE_SIZE: usize = 10_000;
+/// Smart wallets are often deployed right before their first use, so the accounts without
+/// the code are checked again after this interval.
+const NOT_CONTRACT_TTL: Duration = Duration::from_secs(60);
+
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
+    pool: ConnectionPool,
+    /// Whether the account is a smart contract, along with the time of the check.
+    contract_accounts: SharedLruCache<Address, (bool, Instant)>,
 }
 
 impl EthereumChecker {
-    pub fn new(client: EthereumGateway) -> Self {
-        Self { client }
+    pub fn new(client: EthereumGateway, pool: ConnectionPool) -> Self {
+        Self {
+            client,
+            pool,
+            contract_accounts: SharedLruCache::new(CONTRACT_ACCOUNTS_CACHE_SIZE),
+        }
+    }
+
+    /// Checks whether the account is a smart contract (e.g. a smart wallet like Argent or
+    /// Gnosis Safe), i.e. its Ethereum signatures should be checked with EIP1271.
+    pub async fn is_contract(&self, address: Address) -> Result<bool, anyhow::Error> {
+        match self.contract_accounts.get(&address) {
+            Some((true, _)) => return Ok(true),
+            Some((false, checked_at)) if checked_at.elapsed() < NOT_CONTRACT_TTL => {
+                return Ok(false)
+            }
+            _ => {}
+        }
+
+        let start = Instant::now();
+        let is_contract = !self.client.code(address).await?.is_empty();
+        metrics::histogram!("eth_checker.is_contract", start.elapsed());
+
+        self.contract_accounts
+            .insert(address, (is_contract, Instant::now()));
+        Ok(is_contract)
     }
 
     /// Transforms the message into an array expected by EIP-1271 standard.
@@ -68,14 +110,8 @@ impl EthereumChecker {
         Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
     }
 
-    pub async fn is_new_pubkey_hash_authorized(
-        &self,
-        address: Address,
-        nonce: Nonce,
-        pub_key_hash: &PubKeyHash,
-    ) -> Result<bool, anyhow::Error> {
-        let auth_fact: Vec<u8> = self
-            .client
+    async fn auth_fact(&self, address: Address, nonce: Nonce) -> Result<Vec<u8>, anyhow::Error> {
+        self.client
             .call_main_contract_function(
                 "authFacts",
                 (address, u64::from(*nonce)),
@@ -84,7 +120,46 @@ impl EthereumChecker {
                 None,
             )
             .await
-            .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))?;
+            .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))
+    }
+
+    /// Loads the public key hash authorized for the given account nonce from the facts
+    /// indexed by the Ethereum watcher. Errors are not critical, since the contract can
+    /// be queried instead, so they're only reported.
+    async fn cached_auth_fact(&self, address: Address, nonce: Nonce) -> Option<PubKeyHash> {
+        let start = Instant::now();
+        let result = match self.pool.access_storage().await {
+            Ok(mut storage) => {
+                storage
+                    .chain()
+                    .account_schema()
+                    .load_auth_fact(address, nonce)
+                    .await
+            }
+            Err(err) => Err(err.into()),
+        };
+
+        metrics::histogram!("eth_checker.cached_auth_fact", start.elapsed());
+        result.unwrap_or_else(|err| {
+            vlog::warn!(
+                "Unable to load the cached onchain authorization fact: {}",
+                err
+            );
+            None
+        })
+    }
+
+    pub async fn is_new_pubkey_hash_authorized(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        pub_key_hash: &PubKeyHash,
+    ) -> Result<bool, anyhow::Error> {
+        if let Some(authorized_pub_key_hash) = self.cached_auth_fact(address, nonce).await {
+            return Ok(&authorized_pub_key_hash == pub_key_hash);
+        }
+
+        let auth_fact = self.auth_fact(address, nonce).await?;
         Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
     }
 }
@@ -98,6 +173,7 @@ mod tests {
     use zksync_eth_client::ethereum_gateway::EthereumGateway;
     use zksync_eth_client::ETHDirectClient;
     use zksync_eth_signer::PrivateKeySigner;
+    use zksync_storage::ConnectionPool;
     use zksync_types::{
         tx::{EIP1271Signature, PackedEthSignature},
         Address,
@@ -130,7 +206,7 @@ mod tests {
             1.0,
         ));
 
-        let eth_checker = EthereumChecker::new(client);
+        let eth_checker = EthereumChecker::new(client, ConnectionPool::new(Some(1)));
 
         let result = eth_checker
             .is_eip1271_signature_correct(
@@ -142,6 +218,20 @@ mod tests {
             .expect("Check failed");
 
         assert_eq!(result, true, "Signature is incorrect");
+
+        let is_contract = eth_checker
+            .is_contract(config.eip1271.contract_address)
+            .await
+            .expect("Check failed");
+        assert!(is_contract, "Smart wallet is not recognized as a contract");
+        let is_contract = eth_checker
+            .is_contract(Address::repeat_byte(0x11))
+            .await
+            .expect("Check failed");
+        assert!(
+            !is_contract,
+            "Account without code is recognized as a contract"
+        );
     }
 
     /// This test checks that the actual signature data taken from
@@ -5,7 +5,10 @@ use futures::{
 use std::{collections::HashMap, sync::Arc, time::Instant};
 
 use tokio::sync::Mutex;
+use vlog::Traced;
+use zksync_config::configs::ticker::TokenPriceBounds;
 use zksync_storage::ConnectionPool;
+use zksync_types::Address;
 
 use crate::{
     fee_ticker::{
@@ -23,8 +26,8 @@ static TICKER_CHANNEL_SIZE: usize = 32000;
 /// Create `n` tickers and balance the load between them.
 pub(crate) struct TickerBalancer<API: TokenPriceAPI, INFO, WATCHER> {
     tickers: Vec<FeeTicker<TickerApi<API>, INFO, WATCHER>>,
-    channels: Vec<Sender<TickerRequest>>,
-    requests: Receiver<TickerRequest>,
+    channels: Vec<Sender<Traced<TickerRequest>>>,
+    requests: Receiver<Traced<TickerRequest>>,
 }
 
 impl<API, INFO, WATCHER> TickerBalancer<API, INFO, WATCHER>
@@ -38,9 +41,10 @@ where
         ticker_info: INFO,
         ticker_config: TickerConfig,
         validator: FeeTokenValidator<WATCHER>,
-        requests: Receiver<TickerRequest>,
+        requests: Receiver<Traced<TickerRequest>>,
         db_pool: ConnectionPool,
         number_of_tickers: u8,
+        price_bounds: HashMap<Address, TokenPriceBounds>,
     ) -> Self {
         let mut tickers = vec![];
         let mut channels = vec![];
@@ -53,7 +57,8 @@ where
             let ticker_api = TickerApi::new(db_pool.clone(), token_price_api.clone())
                 .with_token_db_cache(token_db_cache.clone())
                 .with_price_cache(price_cache.clone())
-                .with_gas_price_cache(gas_price_cache.clone());
+                .with_gas_price_cache(gas_price_cache.clone())
+                .with_price_bounds(price_bounds.clone());
             let (request_sender, request_receiver) = mpsc::channel(TICKER_CHANNEL_SIZE);
             tickers.push(FeeTicker::new(
                 ticker_api,
@@ -106,6 +111,7 @@ mod tests {
         channel::{mpsc, oneshot},
         SinkExt, StreamExt,
     };
+    use vlog::Traced;
     use zksync_types::{TokenId, TxFeeTypes};
 
     #[tokio::test]
@@ -128,12 +134,12 @@ mod tests {
         for i in 0..50 {
             let channel = oneshot::channel();
             request_sender
-                .send(TickerRequest::GetTxFee {
+                .send(Traced::new(TickerRequest::GetTxFee {
                     tx_type: TxFeeTypes::Withdraw,
                     address: Default::default(),
                     token: TokenId(i).into(),
                     response: channel.0,
-                })
+                }))
                 .await
                 .unwrap();
             if let Some(TickerRequest::GetTxFee {
@@ -141,7 +147,10 @@ mod tests {
                 address: _,
                 token,
                 response: _,
-            }) = receivers[(i % 10) as usize].next().await
+            }) = receivers[(i % 10) as usize]
+                .next()
+                .await
+                .map(Traced::into_inner)
             {
                 assert_eq!(token, TokenId(i).into());
             } else {
@@ -21,6 +21,8 @@ use num::{
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
+use tracing::Instrument;
+use vlog::Traced;
 
 // Workspace deps
 use zksync_config::{configs::ticker::TokenPriceSource, ZkSyncConfig};
@@ -181,12 +183,18 @@ pub enum TickerRequest {
         token: TokenLike,
         response: oneshot::Sender<Result<bool, anyhow::Error>>,
     },
+    /// Returns the IDs of the given tokens which can be used to pay fees.
+    /// Unlike `IsTokenAllowed`, it never requests the remote market data.
+    GetAllowedTokens {
+        tokens: Vec<Token>,
+        response: oneshot::Sender<Result<HashSet<TokenId>, anyhow::Error>>,
+    },
 }
 
 struct FeeTicker<API, INFO, WATCHER> {
     api: API,
     info: INFO,
-    requests: Receiver<TickerRequest>,
+    requests: Receiver<Traced<TickerRequest>>,
     config: TickerConfig,
     validator: FeeTokenValidator<WATCHER>,
 }
@@ -194,7 +202,7 @@ struct FeeTicker<API, INFO, WATCHER> {
 #[must_use]
 pub fn run_ticker_task(
     db_pool: ConnectionPool,
-    tricker_requests: Receiver<TickerRequest>,
+    tricker_requests: Receiver<Traced<TickerRequest>>,
     config: &ZkSyncConfig,
 ) -> JoinHandle<()> {
     let ticker_config = TickerConfig {
@@ -227,7 +235,8 @@ pub fn run_ticker_task(
             let token_price_api =
                 CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
 
-            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
+            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api)
+                .with_price_bounds(config.ticker.price_bounds());
             let ticker_info = TickerInfo::new(db_pool);
             let fee_ticker = FeeTicker::new(
                 ticker_api,
@@ -254,6 +263,7 @@ pub fn run_ticker_task(
                 tricker_requests,
                 db_pool,
                 config.ticker.number_of_ticker_actors,
+                config.ticker.price_bounds(),
             );
             ticker_balancer.spawn_tickers();
             tokio::spawn(ticker_balancer.run())
@@ -265,7 +275,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
     fn new(
         api: API,
         info: INFO,
-        requests: Receiver<TickerRequest>,
+        requests: Receiver<Traced<TickerRequest>>,
         config: TickerConfig,
         validator: FeeTokenValidator<WATCHER>,
     ) -> Self {
@@ -288,43 +298,54 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo, WATCHER: TokenWatcher> FeeTicker<AP
     async fn run(mut self) {
         while let Some(request) = self.requests.next().await {
             let start = Instant::now();
-            match request {
-                TickerRequest::GetTxFee {
-                    tx_type,
-                    token,
-                    response,
-                    address,
-                } => {
-                    let fee = self
-                        .get_fee_from_ticker_in_wei(tx_type, token, address)
-                        .await;
-                    metrics::histogram!("ticker.get_tx_fee", start.elapsed());
-                    response.send(fee).unwrap_or_default()
-                }
-                TickerRequest::GetTokenPrice {
-                    token,
-                    response,
-                    req_type,
-                } => {
-                    let price = self.get_token_price(token, req_type).await;
-                    metrics::histogram!("ticker.get_token_price", start.elapsed());
-                    response.send(price).unwrap_or_default();
-                }
-                TickerRequest::IsTokenAllowed { token, response } => {
-                    let allowed = self.validator.token_allowed(token).await;
-                    metrics::histogram!("ticker.is_token_allowed", start.elapsed());
-                    response.send(allowed).unwrap_or_default();
-                }
-                TickerRequest::GetBatchTxFee {
-                    transactions,
-                    token,
-                    response,
-                } => {
-                    let fee = self.get_batch_from_ticker_in_wei(token, transactions).await;
-                    metrics::histogram!("ticker.get_tx_fee", start.elapsed());
-                    response.send(fee).unwrap_or_default()
+            // Requests are handled within the span of the sender.
+            let (span, request) = request.into_parts();
+            async {
+                match request {
+                    TickerRequest::GetTxFee {
+                        tx_type,
+                        token,
+                        response,
+                        address,
+                    } => {
+                        let fee = self
+                            .get_fee_from_ticker_in_wei(tx_type, token, address)
+                            .await;
+                        metrics::histogram!("ticker.get_tx_fee", start.elapsed());
+                        response.send(fee).unwrap_or_default()
+                    }
+                    TickerRequest::GetTokenPrice {
+                        token,
+                        response,
+                        req_type,
+                    } => {
+                        let price = self.get_token_price(token, req_type).await;
+                        metrics::histogram!("ticker.get_token_price", start.elapsed());
+                        response.send(price).unwrap_or_default();
+                    }
+                    TickerRequest::IsTokenAllowed { token, response } => {
+                        let allowed = self.validator.token_allowed(token).await;
+                        metrics::histogram!("ticker.is_token_allowed", start.elapsed());
+                        response.send(allowed).unwrap_or_default();
+                    }
+                    TickerRequest::GetAllowedTokens { tokens, response } => {
+                        let allowed = self.validator.allowed_tokens(tokens).await;
+                        metrics::histogram!("ticker.get_allowed_tokens", start.elapsed());
+                        response.send(allowed).unwrap_or_default();
+                    }
+                    TickerRequest::GetBatchTxFee {
+                        transactions,
+                        token,
+                        response,
+                    } => {
+                        let fee = self.get_batch_from_ticker_in_wei(token, transactions).await;
+                        metrics::histogram!("ticker.get_tx_fee", start.elapsed());
+                        response.send(fee).unwrap_or_default()
+                    }
                 }
             }
+            .instrument(span)
+            .await;
         }
     }
 
@@ -3,13 +3,15 @@ use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
 use num::rational::Ratio;
-use num::BigUint;
+use num::{BigUint, ToPrimitive};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use zksync_config::configs::ticker::TokenPriceBounds;
 use zksync_storage::ConnectionPool;
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice};
+use zksync_utils::ratio_to_big_decimal;
 
 pub mod coingecko;
 pub mod coinmarkercap;
@@ -79,6 +81,8 @@ pub(super) struct TickerApi<T: TokenPriceAPI> {
     token_db_cache: TokenDBCache,
     price_cache: Arc<Mutex<HashMap<TokenId, TokenCacheEntry>>>,
     gas_price_cache: Arc<Mutex<Option<(BigUint, Instant)>>>,
+    /// Plausible USD price ranges by the token addresses.
+    price_bounds: HashMap<Address, TokenPriceBounds>,
 
     token_price_api: T,
 }
@@ -91,6 +95,7 @@ impl<T: TokenPriceAPI> TickerApi<T> {
             token_db_cache,
             price_cache: Default::default(),
             gas_price_cache: Default::default(),
+            price_bounds: HashMap::new(),
             token_price_api,
         }
     }
@@ -121,6 +126,43 @@ impl<T: TokenPriceAPI> TickerApi<T> {
         }
     }
 
+    pub fn with_price_bounds(self, price_bounds: HashMap<Address, TokenPriceBounds>) -> Self {
+        Self {
+            price_bounds,
+            ..self
+        }
+    }
+
+    /// Rejects the price reported by the price source if it's out of the plausible range
+    /// configured for the token.
+    fn check_price_bounds(
+        &self,
+        token: &Token,
+        price: TokenPrice,
+    ) -> Result<TokenPrice, anyhow::Error> {
+        let bounds = match self.price_bounds.get(&token.address) {
+            Some(bounds) => bounds,
+            None => return Ok(price),
+        };
+
+        let usd_price = ratio_to_big_decimal(&price.usd_price, 18)
+            .to_f64()
+            .unwrap_or_default();
+        if bounds.contains(usd_price) {
+            return Ok(price);
+        }
+
+        vlog::error!(
+            "Price of the token {} is out of the plausible range: {} USD, expected from {} to {} USD",
+            token.symbol,
+            usd_price,
+            bounds.min_usd_price,
+            bounds.max_usd_price
+        );
+        metrics::counter!("ticker.price_out_of_bounds", 1, "token" => token.symbol.clone());
+        anyhow::bail!("Price of the token {} is out of bounds", token.symbol)
+    }
+
     // Version of `update_stored_value` which returns a `Result` for convenient error handling.
     async fn _update_stored_value(
         &self,
@@ -223,10 +265,12 @@ impl<T: TokenPriceAPI + Send + Sync> FeeTickerAPI for TickerApi<T> {
             return Ok(cached_value);
         }
 
+        // Prices out of bounds are not stored, so the last accepted price is used instead.
         let api_price = self
             .token_price_api
             .get_price(&token.symbol)
             .await
+            .and_then(|price| self.check_price_bounds(&token, price))
             .map_err(|e| vlog::warn!("Failed to get price: {}", e));
         if let Ok(api_price) = api_price {
             self.update_stored_value(token.id, api_price.clone(), false)
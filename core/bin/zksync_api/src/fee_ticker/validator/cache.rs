@@ -1,10 +1,16 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
+use bigdecimal::BigDecimal;
 use tokio::sync::Mutex;
 
 use zksync_storage::ConnectionPool;
 use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike};
 
+use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
+
 use crate::utils::token_db_cache::TokenDBCache;
 
 #[derive(Debug, Clone)]
@@ -23,6 +29,7 @@ pub(crate) struct TokenInDBCache {
 pub(crate) struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    fee_disabled: Arc<Mutex<HashSet<TokenId>>>,
 }
 
 impl TokenInDBCache {
@@ -50,6 +57,13 @@ impl TokenInMemoryCache {
             ..self
         }
     }
+
+    pub fn with_fee_disabled(self, fee_disabled: HashSet<TokenId>) -> Self {
+        Self {
+            fee_disabled: Arc::new(Mutex::new(fee_disabled)),
+            ..self
+        }
+    }
 }
 
 impl From<TokenInMemoryCache> for TokenCacheWrapper {
@@ -77,6 +91,59 @@ impl TokenCacheWrapper {
         }
     }
 
+    pub async fn is_fee_disabled(&self, token_id: TokenId) -> anyhow::Result<bool> {
+        match self {
+            Self::DB(cache) => {
+                cache
+                    .inner
+                    .is_fee_disabled(&mut cache.pool.access_storage().await?, token_id)
+                    .await
+            }
+            Self::Memory(cache) => Ok(cache.fee_disabled.lock().await.contains(&token_id)),
+        }
+    }
+
+    pub async fn get_fee_disabled_tokens(&self) -> anyhow::Result<HashSet<TokenId>> {
+        match self {
+            Self::DB(cache) => {
+                cache
+                    .inner
+                    .get_fee_disabled_tokens(&mut cache.pool.access_storage().await?)
+                    .await
+            }
+            Self::Memory(cache) => Ok(cache.fee_disabled.lock().await.clone()),
+        }
+    }
+
+    /// Returns the IDs of the tokens with the stored market volume not less than the given one.
+    pub async fn get_tokens_by_market_volume(
+        &self,
+        min_market_volume: &BigDecimal,
+    ) -> anyhow::Result<HashSet<TokenId>> {
+        match self {
+            Self::DB(cache) => {
+                let tokens = cache
+                    .pool
+                    .access_storage()
+                    .await?
+                    .tokens_schema()
+                    .load_tokens_by_market_volume(big_decimal_to_ratio(min_market_volume)?)
+                    .await?;
+                Ok(tokens.into_iter().map(|(id, _)| id).collect())
+            }
+            Self::Memory(cache) => Ok(cache
+                .market
+                .lock()
+                .await
+                .iter()
+                .filter(|(_, market)| {
+                    ratio_to_big_decimal(&market.market_volume, 2) >= *min_market_volume
+                })
+                .map(|(id, _)| *id)
+                .collect()),
+        }
+    }
+
     pub async fn get_token_market_volume(
         &self,
         token_id: TokenId,
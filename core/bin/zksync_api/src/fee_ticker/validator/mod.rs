@@ -16,7 +16,7 @@ use chrono::Utc;
 // Workspace uses
 use zksync_types::{
     tokens::{Token, TokenLike, TokenMarketVolume},
-    Address,
+    Address, TokenId,
 };
 
 // Local uses
@@ -134,6 +134,10 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
     pub(crate) async fn token_allowed(&mut self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
+            // Operator's decision takes precedence over the market volume of the token.
+            if self.tokens_cache.is_fee_disabled(token.id).await? {
+                return Ok(false);
+            }
             if self.unconditionally_valid.contains(&token.address) {
                 return Ok(true);
             }
@@ -144,6 +148,29 @@ impl<W: TokenWatcher> FeeTokenValidator<W> {
         }
     }
 
+    /// Returns the IDs of the given tokens which can be used to pay fees. The check is performed
+    /// in bulk using the market volumes stored by the `MarketUpdater`, so the tokens whose market
+    /// volume is not fetched yet are considered not allowed.
+    pub(crate) async fn allowed_tokens(
+        &mut self,
+        tokens: Vec<Token>,
+    ) -> anyhow::Result<HashSet<TokenId>> {
+        let fee_disabled = self.tokens_cache.get_fee_disabled_tokens().await?;
+        let liquid = self
+            .tokens_cache
+            .get_tokens_by_market_volume(&self.liquidity_volume)
+            .await?;
+
+        Ok(tokens
+            .into_iter()
+            .filter(|token| !fee_disabled.contains(&token.id))
+            .filter(|token| {
+                self.unconditionally_valid.contains(&token.address) || liquid.contains(&token.id)
+            })
+            .map(|token| token.id)
+            .collect())
+    }
+
     async fn resolve_token(&self, token: TokenLike) -> anyhow::Result<Option<Token>> {
         self.tokens_cache.get_token(token).await
     }
@@ -343,4 +370,89 @@ mod tests {
         assert!(validator.tokens.get(&dai_token_address).unwrap().allowed);
         assert!(!validator.tokens.get(&phnx_token_address).unwrap().allowed);
     }
+
+    #[tokio::test]
+    async fn fee_disabled_tokens_are_rejected() {
+        let eth_token = Token::new(TokenId(0), Address::zero(), "ETH", 18);
+        let dai_token = Token::new(TokenId(1), Address::repeat_byte(1), "DAI", 18);
+
+        let mut tokens = HashMap::new();
+        tokens.insert(TokenLike::Id(eth_token.id), eth_token.clone());
+        tokens.insert(TokenLike::Id(dai_token.id), dai_token.clone());
+        let mut amounts = HashMap::new();
+        amounts.insert(dai_token.address, BigDecimal::from(200));
+        let mut unconditionally_valid = HashSet::new();
+        unconditionally_valid.insert(eth_token.address);
+        // Disabled tokens are rejected even if they are unconditionally valid
+        // or have enough liquidity.
+        let mut fee_disabled = HashSet::new();
+        fee_disabled.insert(eth_token.id);
+        fee_disabled.insert(dai_token.id);
+
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_fee_disabled(fee_disabled);
+        let watcher = InMemoryTokenWatcher {
+            amounts: Arc::new(Mutex::new(amounts)),
+        };
+        let mut validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            unconditionally_valid,
+            watcher,
+        );
+
+        for token in &[eth_token, dai_token] {
+            let allowed = validator
+                .token_allowed(TokenLike::Id(token.id))
+                .await
+                .unwrap();
+            assert!(!allowed, "{} is accepted for fees", token.symbol);
+        }
+    }
+
+    #[tokio::test]
+    async fn allowed_tokens_are_checked_in_bulk() {
+        let eth_token = Token::new(TokenId(0), Address::zero(), "ETH", 18);
+        let dai_token = Token::new(TokenId(1), Address::repeat_byte(1), "DAI", 18);
+        let phnx_token = Token::new(TokenId(2), Address::repeat_byte(2), "PHNX", 18);
+        let mkr_token = Token::new(TokenId(3), Address::repeat_byte(3), "MKR", 18);
+        let new_token = Token::new(TokenId(4), Address::repeat_byte(4), "NEW", 18);
+
+        let market_volume = |volume: u32| TokenMarketVolume {
+            market_volume: Ratio::from_integer(BigUint::from(volume)),
+            last_updated: Utc::now(),
+        };
+        let mut market = HashMap::new();
+        market.insert(dai_token.id, market_volume(200));
+        market.insert(phnx_token.id, market_volume(10));
+        market.insert(mkr_token.id, market_volume(200));
+        let mut unconditionally_valid = HashSet::new();
+        unconditionally_valid.insert(eth_token.address);
+        let mut fee_disabled = HashSet::new();
+        fee_disabled.insert(mkr_token.id);
+
+        let cache = TokenInMemoryCache::new()
+            .with_market(market)
+            .with_fee_disabled(fee_disabled);
+        // Market volume of the tokens is never requested from the watcher.
+        let watcher = InMemoryTokenWatcher {
+            amounts: Arc::new(Mutex::new(HashMap::new())),
+        };
+        let mut validator = FeeTokenValidator::new(
+            cache,
+            chrono::Duration::seconds(100),
+            BigDecimal::from(100),
+            unconditionally_valid,
+            watcher,
+        );
+
+        let allowed = validator
+            .allowed_tokens(vec![eth_token, dai_token, phnx_token, mkr_token, new_token])
+            .await
+            .unwrap();
+        let expected: HashSet<_> = vec![TokenId(0), TokenId(1)].into_iter().collect();
+        assert_eq!(allowed, expected);
+    }
 }
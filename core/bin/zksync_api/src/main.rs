@@ -19,11 +19,15 @@ async fn main() -> anyhow::Result<()> {
         })
         .expect("Error setting Ctrl+C handler");
     }
-    let connection_pool = ConnectionPool::new(None);
+    let connection_pool = ConnectionPool::dedicated(
+        "api",
+        config.db.api_pool_size,
+        config.db.slow_query_threshold(),
+    );
 
     // Run prometheus data exporter.
     let (prometheus_task_handle, _) =
-        run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, false);
+        run_prometheus_exporter(connection_pool.clone(), &config.api.prometheus, false);
 
     let task_handle = run_api(connection_pool, stop_signal_sender, &config);
 
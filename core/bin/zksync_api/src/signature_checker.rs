@@ -2,26 +2,61 @@
 //! dedicated for checking the signatures of incoming transactions.
 //! Main routine of this module operates a multithreaded event loop,
 //! which is used to spawn concurrent tasks to efficiently check the
-//! transactions signatures.
+//! transactions signatures. Requests arriving in bursts are verified
+//! together, with the CPU-bound checks spread over the dedicated `rayon` thread pool,
+//! so the transactions of a batch are verified concurrently as well.
 
 // Built-in uses
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 // External uses
 use futures::{
     channel::{mpsc, oneshot},
-    StreamExt,
+    future, StreamExt,
 };
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
 use tokio::runtime::{Builder, Handle};
+use tracing::Instrument;
+use vlog::Traced;
 // Workspace uses
 use zksync_config::ZkSyncConfig;
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
-use zksync_types::tx::EthSignData;
-use zksync_types::{tx::TxEthSignature, SignedZkSyncTx, ZkSyncTx};
+use zksync_storage::ConnectionPool;
+use zksync_types::tx::{EIP1271Signature, EthSignData, PackedEthSignature, TxHash};
+use zksync_types::{tx::TxEthSignature, Address, SignedZkSyncTx, ZkSyncTx, H256};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 // Local uses
-use crate::{eth_checker::EthereumChecker, tx_error::TxAddError};
+use crate::{
+    eth_checker::EthereumChecker,
+    tx_error::TxAddError,
+    utils::{
+        cosigner::{cosign_message, CosignerClient},
+        shared_lru_cache::SharedLruCache,
+    },
+};
+
+/// Maximum amount of requests verified together as a single burst.
+const MAX_BURST_SIZE: usize = 256;
+
+/// Cache of the transactions which passed the offchain checks, so the resubmitted transactions
+/// and the transactions repeated in several batches skip the Ethereum signer recovery and the
+/// `ZKSync` signature verification.
+#[derive(Debug, Clone)]
+pub struct SignatureCache(SharedLruCache<(TxHash, H256), ()>);
+
+impl SignatureCache {
+    pub fn new(capacity: usize) -> Self {
+        Self(SharedLruCache::new(capacity))
+    }
+
+    /// Hash of the transaction doesn't cover its signatures (e.g. the Ethereum signature
+    /// of `ChangePubKey`), so the digest of the whole signed transaction is a part of the key.
+    fn key(tx: &SignedZkSyncTx) -> (TxHash, H256) {
+        let encoded = serde_json::to_vec(tx).expect("Unserializable transaction");
+        (tx.tx.hash(), H256::from(tiny_keccak::keccak256(&encoded)))
+    }
+}
 
 /// `TxVariant` is used to form a verify request. It is possible to wrap
 /// either a single transaction, or the transaction batch.
@@ -31,6 +66,16 @@ pub enum TxVariant {
     Batch(Vec<SignedZkSyncTx>, EthSignData),
 }
 
+impl TxVariant {
+    /// Returns the transactions of the variant.
+    fn txs(&self) -> &[SignedZkSyncTx] {
+        match self {
+            Self::Tx(tx) => std::slice::from_ref(tx),
+            Self::Batch(txs, _) => txs,
+        }
+    }
+}
+
 /// Wrapper on a `TxVariant` which guarantees that (a batch of)
 /// transaction(s) was checked and signatures associated with
 /// this transactions are correct.
@@ -42,13 +87,18 @@ pub struct VerifiedTx(TxVariant);
 
 impl VerifiedTx {
     /// Checks the (batch of) transaction(s) correctness by verifying its
-    /// Ethereum signature (if required) and `ZKSync` signature.
+    /// Ethereum signature (if required), `ZKSync` signature and co-signatures.
     pub async fn verify(
         request: &mut VerifyTxSignatureRequest,
         eth_checker: &EthereumChecker,
+        cosignature_checker: Option<&CosignatureChecker>,
+        signature_cache: &SignatureCache,
     ) -> Result<Self, TxAddError> {
-        verify_eth_signature(request, eth_checker).await?;
-        verify_tx_correctness(&mut request.tx)?;
+        verify_offchain(&request.tx, signature_cache)?;
+        verify_onchain(&request.tx, eth_checker).await?;
+        if let Some(cosignature_checker) = cosignature_checker {
+            cosignature_checker.verify(request.tx.txs()).await?;
+        }
 
         Ok(Self(request.tx.clone()))
     }
@@ -76,33 +126,101 @@ impl VerifiedTx {
     }
 }
 
-/// Verifies the Ethereum signature of the (batch of) transaction(s).
-async fn verify_eth_signature(
-    request: &VerifyTxSignatureRequest,
-    eth_checker: &EthereumChecker,
+/// Performs the CPU-bound part of the check which doesn't require access to the Ethereum node:
+/// recovers the signers of the Ethereum signatures and verifies the `ZKSync` signatures.
+/// Transactions of a batch are checked in parallel.
+fn verify_offchain(tx: &TxVariant, signature_cache: &SignatureCache) -> Result<(), TxAddError> {
+    match tx {
+        TxVariant::Tx(tx) => verify_single_tx_offchain(tx, signature_cache),
+        TxVariant::Batch(txs, eth_sign_data) => {
+            let start = Instant::now();
+            verify_eth_signature_txs_batch(txs, eth_sign_data)?;
+            // In case there're signatures provided for some of transactions
+            // we still verify them.
+            let result = txs
+                .par_iter()
+                .try_for_each(|tx| verify_single_tx_offchain(tx, signature_cache));
+
+            metrics::histogram!("signature_checker.verify_batch_offchain", start.elapsed());
+            result
+        }
+    }
+}
+
+fn verify_single_tx_offchain(
+    tx: &SignedZkSyncTx,
+    signature_cache: &SignatureCache,
 ) -> Result<(), TxAddError> {
-    match &request.tx {
+    let key = SignatureCache::key(tx);
+    if signature_cache.0.get(&key).is_some() {
+        metrics::counter!("signature_checker.cache_hits", 1);
+        return Ok(());
+    }
+    metrics::counter!("signature_checker.cache_misses", 1);
+
+    verify_eth_signature_single_tx(tx)?;
+    if !tx.tx.check_correctness() {
+        return Err(TxAddError::IncorrectTx);
+    }
+
+    signature_cache.0.insert(key, ());
+    Ok(())
+}
+
+/// Performs the part of the check which requires access to the Ethereum node:
+/// checks the onchain `ChangePubKey` authorization and `EIP1271` signatures.
+async fn verify_onchain(tx: &TxVariant, eth_checker: &EthereumChecker) -> Result<(), TxAddError> {
+    match tx {
         TxVariant::Tx(tx) => {
-            verify_eth_signature_single_tx(tx, eth_checker).await?;
+            verify_onchain_single_tx(tx, eth_checker).await?;
         }
         TxVariant::Batch(txs, eth_sign_data) => {
-            verify_eth_signature_txs_batch(txs, eth_sign_data, eth_checker).await?;
-            // In case there're signatures provided for some of transactions
-            // we still verify them.
+            verify_onchain_txs_batch(txs, eth_sign_data, eth_checker).await?;
             for tx in txs {
-                verify_eth_signature_single_tx(tx, eth_checker).await?;
+                verify_onchain_single_tx(tx, eth_checker).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the Ethereum signature of the transaction. Smart wallets have to send the `EIP1271`
+/// signatures explicitly, they are checked onchain.
+fn verify_eth_signature_single_tx(tx: &SignedZkSyncTx) -> Result<(), TxAddError> {
+    let start = Instant::now();
+    if let Some(sign_data) = &tx.eth_sign_data {
+        match &sign_data.signature {
+            TxEthSignature::EthereumSignature(packed_signature) => {
+                let signer_account = packed_signature
+                    .signature_recover_signer(&sign_data.message)
+                    .ok();
+
+                if signer_account != Some(tx.tx.account()) {
+                    return Err(TxAddError::IncorrectEthSignature);
+                }
             }
+            TxEthSignature::EIP712Signature(packed_signature) => {
+                let signer_account = typed_data_signer(packed_signature, &sign_data.message);
+                if signer_account != Some(tx.tx.account()) {
+                    return Err(TxAddError::IncorrectEthSignature);
+                }
+            }
+            TxEthSignature::EIP1271Signature(_) => {}
         }
     }
 
+    metrics::histogram!(
+        "signature_checker.verify_eth_signature_single_tx",
+        start.elapsed()
+    );
     Ok(())
 }
 
-async fn verify_eth_signature_single_tx(
+async fn verify_onchain_single_tx(
     tx: &SignedZkSyncTx,
     eth_checker: &EthereumChecker,
 ) -> Result<(), TxAddError> {
-    let start = Instant::now();
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
     if let ZkSyncTx::ChangePubKey(change_pk) = &tx.tx {
         if change_pk.eth_signature.is_none() {
@@ -122,75 +240,37 @@ async fn verify_eth_signature_single_tx(
         }
     }
 
-    // Check the signature.
     if let Some(sign_data) = &tx.eth_sign_data {
-        match &sign_data.signature {
-            TxEthSignature::EthereumSignature(packed_signature) => {
-                let signer_account = packed_signature
-                    .signature_recover_signer(&sign_data.message)
-                    .or(Err(TxAddError::IncorrectEthSignature))?;
-
-                if signer_account != tx.tx.account() {
-                    return Err(TxAddError::IncorrectEthSignature);
-                }
-            }
-            TxEthSignature::EIP1271Signature(signature) => {
-                let signature_correct = eth_checker
-                    .is_eip1271_signature_correct(
-                        tx.tx.account(),
-                        &sign_data.message,
-                        signature.clone(),
-                    )
-                    .await
-                    .expect("Unable to check EIP1271 signature");
-
-                if !signature_correct {
-                    return Err(TxAddError::IncorrectTx);
-                }
-            }
-        };
+        if let TxEthSignature::EIP1271Signature(signature) = &sign_data.signature {
+            verify_eip1271_signature(tx.tx.account(), &sign_data.message, signature, eth_checker)
+                .await?;
+        }
     }
 
-    metrics::histogram!(
-        "signature_checker.verify_eth_signature_single_tx",
-        start.elapsed()
-    );
     Ok(())
 }
 
-async fn verify_eth_signature_txs_batch(
+/// Checks the Ethereum signature of the batch. Batches of the smart wallets have to be signed
+/// with the `EIP1271` signatures explicitly, they are checked onchain.
+fn verify_eth_signature_txs_batch(
     txs: &[SignedZkSyncTx],
     eth_sign_data: &EthSignData,
-    eth_checker: &EthereumChecker,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     match &eth_sign_data.signature {
         TxEthSignature::EthereumSignature(packed_signature) => {
             let signer_account = packed_signature
                 .signature_recover_signer(&eth_sign_data.message)
-                .or(Err(TxAddError::IncorrectEthSignature))?;
+                .ok();
 
-            if txs.iter().any(|tx| tx.tx.account() != signer_account) {
+            if txs.iter().any(|tx| Some(tx.tx.account()) != signer_account) {
                 return Err(TxAddError::IncorrectEthSignature);
             }
         }
-        TxEthSignature::EIP1271Signature(signature) => {
-            for tx in txs {
-                let signature_correct = eth_checker
-                    .is_eip1271_signature_correct(
-                        tx.tx.account(),
-                        &eth_sign_data.message,
-                        signature.clone(),
-                    )
-                    .await
-                    .expect("Unable to check EIP1271 signature");
-
-                if !signature_correct {
-                    return Err(TxAddError::IncorrectTx);
-                }
-            }
-        }
-    };
+        // Batches have no typed data, their hash is signed as a text message.
+        TxEthSignature::EIP712Signature(_) => return Err(TxAddError::IncorrectEthSignature),
+        TxEthSignature::EIP1271Signature(_) => {}
+    }
 
     metrics::histogram!(
         "signature_checker.verify_eth_signature_txs_batch",
@@ -199,22 +279,193 @@ async fn verify_eth_signature_txs_batch(
     Ok(())
 }
 
-/// Verifies the correctness of the ZKSync transaction(s) (including the
-/// signature check).
-fn verify_tx_correctness(tx: &mut TxVariant) -> Result<(), TxAddError> {
-    match tx {
-        TxVariant::Tx(tx) => {
-            if !tx.tx.check_correctness() {
-                return Err(TxAddError::IncorrectTx);
-            }
+async fn verify_onchain_txs_batch(
+    txs: &[SignedZkSyncTx],
+    eth_sign_data: &EthSignData,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    if let TxEthSignature::EIP1271Signature(signature) = &eth_sign_data.signature {
+        for tx in txs {
+            verify_eip1271_signature(
+                tx.tx.account(),
+                &eth_sign_data.message,
+                signature,
+                eth_checker,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Recovers the signer of the EIP-712 typed data, the message is the hash of the typed data.
+fn typed_data_signer(signature: &PackedEthSignature, message: &[u8]) -> Option<Address> {
+    if message.len() != H256::len_bytes() {
+        return None;
+    }
+    signature
+        .signature_recover_signer_from_hash(H256::from_slice(message))
+        .ok()
+}
+
+/// Checks the signature with the `isValidSignature` method of the account contract.
+/// Accounts without the code can't have such signatures.
+async fn verify_eip1271_signature(
+    account: Address,
+    message: &[u8],
+    signature: &EIP1271Signature,
+    eth_checker: &EthereumChecker,
+) -> Result<(), TxAddError> {
+    let is_contract = eth_checker.is_contract(account).await.map_err(|err| {
+        vlog::warn!(
+            "Unable to check whether {:?} is a contract: {}",
+            account,
+            err
+        );
+        TxAddError::Other
+    })?;
+    if !is_contract {
+        return Err(TxAddError::IncorrectEthSignature);
+    }
+
+    let signature_correct = eth_checker
+        .is_eip1271_signature_correct(account, message, signature.clone())
+        .await
+        .expect("Unable to check EIP1271 signature");
+
+    if !signature_correct {
+        return Err(TxAddError::EIP1271SignatureVerificationFail);
+    }
+    Ok(())
+}
+
+/// Checker of the co-signatures of the transactions made by the accounts opted in to the
+/// co-signing. It only exists if the co-signing service is configured, otherwise the
+/// co-signatures are not checked.
+#[derive(Clone)]
+pub struct CosignatureChecker {
+    pool: ConnectionPool,
+    client: CosignerClient,
+}
+
+impl CosignatureChecker {
+    pub fn new(pool: ConnectionPool, client: CosignerClient) -> Self {
+        Self { pool, client }
+    }
+
+    /// Requires the transactions of the accounts opted in to the co-signing to be approved
+    /// by their co-signers. The co-signers are loaded at once and the signatures are requested
+    /// from the co-signing service concurrently. Any refused transaction rejects all of them.
+    pub async fn verify(&self, txs: &[SignedZkSyncTx]) -> Result<(), TxAddError> {
+        let addresses: Vec<_> = txs.iter().map(|tx| tx.tx.account()).collect();
+        let cosigners = async {
+            self.pool
+                .access_storage()
+                .await?
+                .cosigners_schema()
+                .load_cosigners(&addresses)
+                .await
         }
-        TxVariant::Batch(batch, _) => {
-            if batch.iter_mut().any(|tx| !tx.tx.check_correctness()) {
-                return Err(TxAddError::IncorrectTx);
+        .await
+        .map_err(|err| {
+            vlog::warn!("Unable to load the co-signers: {}", err);
+            TxAddError::DbError
+        })?;
+
+        let requests = txs.iter().filter_map(|tx| {
+            let cosigner = *cosigners.get(&tx.tx.account())?;
+            Some(self.verify_tx(tx, cosigner))
+        });
+        future::try_join_all(requests).await?;
+        Ok(())
+    }
+
+    async fn verify_tx(&self, tx: &SignedZkSyncTx, cosigner: Address) -> Result<(), TxAddError> {
+        let signature = self
+            .client
+            .request_cosignature(&tx.tx, cosigner)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Co-signing request failed: {}", err);
+                metrics::counter!("api.cosigner.unavailable", 1);
+                TxAddError::CosignerUnavailable
+            })?;
+
+        match signature {
+            Some(signature) if verify_cosignature(&tx.tx.hash(), cosigner, &signature) => Ok(()),
+            _ => {
+                metrics::counter!("api.cosigner.rejected", 1);
+                Err(TxAddError::CosignatureRejected)
             }
         }
     }
-    Ok(())
+}
+
+/// Checks that the transaction is approved by the co-signer of its account, i.e. that
+/// the co-signature is made with the registered co-signer key.
+pub fn verify_cosignature(
+    tx_hash: &TxHash,
+    cosigner: Address,
+    signature: &PackedEthSignature,
+) -> bool {
+    signature
+        .signature_recover_signer(cosign_message(tx_hash).as_bytes())
+        .map_or(false, |signer| signer == cosigner)
+}
+
+/// Verifies a burst of requests received together.
+///
+/// Offchain checks of all the requests (and all the transactions of the batches inside them)
+/// are performed in parallel on the dedicated `rayon` thread pool, so a burst of requests doesn't
+/// occupy the event loop. Requests that passed these checks are then checked onchain concurrently.
+async fn verify_burst(
+    handle: Handle,
+    requests: Vec<Traced<VerifyTxSignatureRequest>>,
+    eth_checker: EthereumChecker,
+    cosignature_checker: Option<CosignatureChecker>,
+    signature_cache: SignatureCache,
+    thread_pool: Arc<ThreadPool>,
+) {
+    let start = Instant::now();
+    let checked = tokio::task::spawn_blocking(move || {
+        thread_pool.install(|| {
+            requests
+                .into_par_iter()
+                .map(|request| {
+                    // Every request is checked within the span of its sender.
+                    let (span, request) = request.into_parts();
+                    let result = span.in_scope(|| verify_offchain(&request.tx, &signature_cache));
+                    (span, request, result)
+                })
+                .collect::<Vec<_>>()
+        })
+    })
+    .await
+    .expect("Offchain signature check panicked");
+    metrics::histogram!("signature_checker.verify_burst_offchain", start.elapsed());
+
+    for (span, request, result) in checked {
+        if let Err(err) = result {
+            request.response.send(Err(err)).unwrap_or_default();
+            continue;
+        }
+
+        let eth_checker = eth_checker.clone();
+        let cosignature_checker = cosignature_checker.clone();
+        handle.spawn(
+            async move {
+                let mut result = verify_onchain(&request.tx, &eth_checker).await;
+                if let (Ok(()), Some(cosignature_checker)) = (&result, &cosignature_checker) {
+                    result = cosignature_checker.verify(request.tx.txs()).await;
+                }
+                let resp = result.map(|_| VerifiedTx(request.tx));
+
+                request.response.send(resp).unwrap_or_default();
+            }
+            .instrument(span),
+        );
+    }
 }
 
 /// Request for the signature check.
@@ -229,27 +480,52 @@ pub struct VerifyTxSignatureRequest {
 /// See the module documentation for details.
 pub fn start_sign_checker_detached(
     config: ZkSyncConfig,
-    input: mpsc::Receiver<VerifyTxSignatureRequest>,
+    pool: ConnectionPool,
+    input: mpsc::Receiver<Traced<VerifyTxSignatureRequest>>,
     panic_notify: mpsc::Sender<bool>,
 ) {
     let client = EthereumGateway::from_config(&config);
-    let eth_checker = EthereumChecker::new(client);
+    let cosignature_checker = CosignerClient::from_config(&config.api.cosigner)
+        .map(|client| CosignatureChecker::new(pool.clone(), client));
+    let eth_checker = EthereumChecker::new(client, pool);
+    let signature_cache = SignatureCache::new(config.api.common.signature_cache_size);
+    let thread_pool = ThreadPoolBuilder::new()
+        .num_threads(config.api.common.signature_checker_threads)
+        .thread_name(|index| format!("Signature checker worker {}", index))
+        .build()
+        .expect("failed to build thread pool for signature checks");
+    let thread_pool = Arc::new(thread_pool);
 
     /// Main signature check requests handler.
     /// Basically it receives the requests through the channel and verifies signatures,
-    /// notifying the request sender about the check result.
+    /// notifying the request sender about the check result. Requests already waiting
+    /// in the channel are taken together and verified as a single burst.
     async fn checker_routine(
         handle: Handle,
-        mut input: mpsc::Receiver<VerifyTxSignatureRequest>,
+        mut input: mpsc::Receiver<Traced<VerifyTxSignatureRequest>>,
         eth_checker: EthereumChecker,
+        cosignature_checker: Option<CosignatureChecker>,
+        signature_cache: SignatureCache,
+        thread_pool: Arc<ThreadPool>,
     ) {
-        while let Some(mut request) = input.next().await {
-            let eth_checker = eth_checker.clone();
-            handle.spawn(async move {
-                let resp = VerifiedTx::verify(&mut request, &eth_checker).await;
+        while let Some(request) = input.next().await {
+            let mut burst = vec![request];
+            while burst.len() < MAX_BURST_SIZE {
+                match input.try_next() {
+                    Ok(Some(request)) => burst.push(request),
+                    // Either the channel is empty or closed.
+                    _ => break,
+                }
+            }
 
-                request.response.send(resp).unwrap_or_default();
-            });
+            handle.spawn(verify_burst(
+                handle.clone(),
+                burst,
+                eth_checker.clone(),
+                cosignature_checker.clone(),
+                signature_cache.clone(),
+                thread_pool.clone(),
+            ));
         }
     }
 
@@ -264,7 +540,117 @@ pub fn start_sign_checker_detached(
                 .build()
                 .expect("failed to build runtime for signature processor");
             let handle = runtime.handle().clone();
-            runtime.block_on(checker_routine(handle, input, eth_checker));
+            runtime.block_on(checker_routine(
+                handle,
+                input,
+                eth_checker,
+                cosignature_checker,
+                signature_cache,
+                thread_pool,
+            ));
         })
         .expect("failed to start signature checker thread");
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{web, App};
+    use serde::Deserialize;
+    use serde_json::json;
+    use zksync_config::configs::api::Cosigner as CosignerConfig;
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::{AccountId, TokenId};
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct CosignRequest {
+        tx_hash: TxHash,
+    }
+
+    /// Starts the co-signing service which approves all the transactions with the given key.
+    fn cosigning_service(cosigner_key: H256) -> actix_web::test::TestServer {
+        actix_web::test::start(move || {
+            App::new().route(
+                "/cosign",
+                web::post().to(move |request: web::Json<CosignRequest>| async move {
+                    let message = cosign_message(&request.tx_hash);
+                    let signature = PackedEthSignature::sign(&cosigner_key, message.as_bytes())
+                        .expect("Signing the co-signature unexpectedly failed");
+                    web::Json(json!({ "signature": signature }))
+                }),
+            )
+        })
+    }
+
+    fn transfer(account: &ZkSyncAccount) -> SignedZkSyncTx {
+        account.set_account_id(Some(AccountId(1)));
+        let (transfer, _) = account.sign_transfer(
+            TokenId(0),
+            "ETH",
+            1u64.into(),
+            1u64.into(),
+            &Address::random(),
+            None,
+            false,
+        );
+        SignedZkSyncTx::from(ZkSyncTx::Transfer(Box::new(transfer)))
+    }
+
+    /// Checks that only the transactions of the accounts opted in to the co-signing
+    /// are sent to the co-signing service, and their co-signatures are required.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn cosignatures() -> anyhow::Result<()> {
+        let pool = ConnectionPool::new(Some(1));
+        let cosigner_key = H256::random();
+        let cosigner = PackedEthSignature::address_from_private_key(&cosigner_key)?;
+        let server = cosigning_service(cosigner_key);
+        let client = CosignerClient::from_config(&CosignerConfig {
+            service_url: Some(server.url("")),
+            request_timeout_ms: 1000,
+        })
+        .expect("Co-signing service is configured");
+
+        let (alice, bob, carol) = (
+            ZkSyncAccount::rand(),
+            ZkSyncAccount::rand(),
+            ZkSyncAccount::rand(),
+        );
+        {
+            let mut storage = pool.access_storage().await?;
+            storage
+                .cosigners_schema()
+                .set_cosigner(alice.address, Some(cosigner), 1)
+                .await?;
+            // Key of the Bob's co-signer is unknown to the service.
+            storage
+                .cosigners_schema()
+                .set_cosigner(bob.address, Some(Address::random()), 1)
+                .await?;
+        }
+        let checker = CosignatureChecker::new(pool, client);
+
+        checker
+            .verify(&[transfer(&alice), transfer(&carol)])
+            .await?;
+        assert!(matches!(
+            checker.verify(&[transfer(&alice), transfer(&bob)]).await,
+            Err(TxAddError::CosignatureRejected)
+        ));
+
+        server.stop().await;
+        assert!(matches!(
+            checker.verify(&[transfer(&alice)]).await,
+            Err(TxAddError::CosignerUnavailable)
+        ));
+        // Accounts without the co-signers don't depend on the service.
+        checker.verify(&[transfer(&carol)]).await?;
+
+        Ok(())
+    }
+}
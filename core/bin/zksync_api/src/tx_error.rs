@@ -1,11 +1,17 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use thiserror::Error;
+use zksync_api_client::rest::v1::ErrorCode;
+use zksync_types::Nonce;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
 pub enum TxAddError {
     #[error("Tx nonce is too low.")]
     NonceMismatch,
 
+    #[error("Tx nonce is too high, allowed nonces are from {min_nonce} to {max_nonce}.")]
+    NonceTooHigh { min_nonce: Nonce, max_nonce: Nonce },
+
     #[error("Tx is incorrect")]
     IncorrectTx,
 
@@ -41,4 +47,54 @@ pub enum TxAddError {
 
     #[error("The number of withdrawals in the batch is too big")]
     BatchWithdrawalsOverload,
+
+    #[error("Too many pending transactions of the account in the mempool, the limit is {limit}")]
+    TooManyPendingTxs { limit: usize },
+
+    // Errors below are only reported by the signature checker.
+    #[error("Transaction is not approved by the co-signer of the account")]
+    CosignatureRejected,
+
+    #[error("Co-signing service is unavailable")]
+    CosignerUnavailable,
+}
+
+impl TxAddError {
+    /// Returns the code of the error returned by the API.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::NonceMismatch => ErrorCode::NonceMismatch,
+            Self::NonceTooHigh { .. } => ErrorCode::NonceTooHigh,
+            Self::IncorrectTx => ErrorCode::IncorrectTx,
+            Self::TxFeeTooLow => ErrorCode::FeeTooLow,
+            Self::TxBatchFeeTooLow => ErrorCode::BatchFeeTooLow,
+            Self::EIP1271SignatureVerificationFail => ErrorCode::EIP1271SignatureVerificationFail,
+            Self::MissingEthSignature => ErrorCode::MissingEthSignature,
+            Self::IncorrectEthSignature => ErrorCode::IncorrectEthSignature,
+            Self::ChangePkNotAuthorized => ErrorCode::ChangePkNotAuthorized,
+            Self::Other => ErrorCode::Other,
+            Self::DbError => ErrorCode::StorageUnavailable,
+            Self::EmptyBatch => ErrorCode::EmptyBatch,
+            Self::BatchTooBig => ErrorCode::BatchTooBig,
+            Self::BatchWithdrawalsOverload => ErrorCode::BatchWithdrawalsOverload,
+            Self::TooManyPendingTxs { .. } => ErrorCode::TooManyPendingTxs,
+            Self::CosignatureRejected => ErrorCode::CosignatureRejected,
+            Self::CosignerUnavailable => ErrorCode::ServiceUnavailable,
+        }
+    }
+
+    /// Returns the structured details of the error returned by the API along with its code.
+    pub fn details(&self) -> Option<Value> {
+        match self {
+            Self::NonceTooHigh {
+                min_nonce,
+                max_nonce,
+            } => Some(json!({
+                "minNonce": min_nonce,
+                "maxNonce": max_nonce,
+            })),
+            Self::TooManyPendingTxs { limit } => Some(json!({ "limit": limit })),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,127 @@
+//! Client of the co-signing service for the accounts opted in to the 2-Factor authentication.
+//!
+//! Account registers the co-signer key by signing the registration message with its Ethereum
+//! key. After that, every transaction of the account is sent to the co-signing service before
+//! being accepted. The service either signs the hash of the transaction with the co-signer key,
+//! or refuses to do so, e.g. if the second factor isn't confirmed by the account owner.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_config::configs::api::Cosigner as CosignerConfig;
+use zksync_types::{
+    tx::{PackedEthSignature, TxHash},
+    Address, ZkSyncTx,
+};
+
+/// Request sent to the co-signing service.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CosignRequest<'a> {
+    tx: &'a ZkSyncTx,
+    tx_hash: TxHash,
+    cosigner: Address,
+}
+
+/// Response of the co-signing service, the signature is missing if the service refuses
+/// to co-sign the transaction.
+#[derive(Debug, Deserialize)]
+struct CosignResponse {
+    signature: Option<PackedEthSignature>,
+}
+
+/// Returns the message signed by the account to register the co-signer, or to opt out
+/// of the co-signing if `cosigner` is `None`. The nonce of the registrations protects them
+/// from being replayed.
+pub fn registration_message(cosigner: Option<Address>, nonce: u32, chain_id: u64) -> String {
+    let action = match cosigner {
+        Some(cosigner) => format!("Register zkSync co-signer: {:?}", cosigner),
+        None => "Remove zkSync co-signer".to_string(),
+    };
+    format!("{}\nChain ID: {}\nNonce: {}", action, chain_id, nonce)
+}
+
+/// Returns the message signed by the co-signer to approve the transaction.
+pub fn cosign_message(tx_hash: &TxHash) -> String {
+    format!("Co-sign zkSync transaction: {}", tx_hash.to_string())
+}
+
+/// Client of the co-signing service.
+#[derive(Debug, Clone)]
+pub struct CosignerClient {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl CosignerClient {
+    /// Creates the client, returns `None` if the co-signing service is not configured.
+    pub fn from_config(config: &CosignerConfig) -> Option<Self> {
+        let service_url = config.service_url.as_ref()?;
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout())
+            .build()
+            .expect("Unable to create the co-signing service client");
+
+        Some(Self {
+            client,
+            url: format!("{}/cosign", service_url.trim_end_matches('/')),
+        })
+    }
+
+    /// Requests the signature of the transaction made with the co-signer key.
+    /// Returns `None` if the service refuses to co-sign the transaction.
+    pub async fn request_cosignature(
+        &self,
+        tx: &ZkSyncTx,
+        cosigner: Address,
+    ) -> Result<Option<PackedEthSignature>, reqwest::Error> {
+        let start = Instant::now();
+        let request = CosignRequest {
+            tx,
+            tx_hash: tx.hash(),
+            cosigner,
+        };
+        let response: CosignResponse = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        metrics::histogram!("api.cosigner.request", start.elapsed());
+        Ok(response.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosigner_messages() {
+        let cosigner: Address = "00000000000c2e074ec69a0dfb2997ba6c7d2e1e".parse().unwrap();
+        assert_eq!(
+            registration_message(Some(cosigner), 1, 9),
+            "Register zkSync co-signer: 0x00000000000c2e074ec69a0dfb2997ba6c7d2e1e\n\
+             Chain ID: 9\n\
+             Nonce: 1"
+        );
+        assert_eq!(
+            registration_message(None, 2, 9),
+            "Remove zkSync co-signer\nChain ID: 9\nNonce: 2"
+        );
+
+        let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+        assert_eq!(
+            cosign_message(&tx_hash),
+            format!("Co-sign zkSync transaction: sync-tx:{}", "01".repeat(32))
+        );
+    }
+}
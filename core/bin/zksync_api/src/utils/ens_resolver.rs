@@ -0,0 +1,261 @@
+//! Resolver of the ENS names accepted by the API instead of the account addresses.
+//!
+//! Names are resolved with the Ethereum node in two calls: the ENS registry returns the
+//! resolver contract of the name, and the resolver returns the address the name points to.
+//! Results are cached for the configured time, including the names that don't resolve.
+//! Resolution is limited by the timeout, and the failed resolutions are cached for a short time
+//! too, so the public endpoints can't make the server flood the Ethereum node with requests.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use thiserror::Error;
+use tokio::time;
+use web3::contract::Options;
+
+// Workspace uses
+use zksync_config::configs::api::Ens as EnsConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::{Address, H256};
+
+// Local uses
+use crate::utils::shared_lru_cache::SharedLruCache;
+
+/// Time the failed resolutions are cached for.
+const FAILED_RESOLUTION_TTL: Duration = Duration::from_secs(10);
+
+/// Minimal ABI of the ENS registry and the public resolver, only the used functions are declared.
+const ENS_ABI: &str = r#"[
+    {
+        "constant": true,
+        "inputs": [{ "name": "node", "type": "bytes32" }],
+        "name": "resolver",
+        "outputs": [{ "name": "", "type": "address" }],
+        "payable": false,
+        "stateMutability": "view",
+        "type": "function"
+    },
+    {
+        "constant": true,
+        "inputs": [{ "name": "node", "type": "bytes32" }],
+        "name": "addr",
+        "outputs": [{ "name": "", "type": "address" }],
+        "payable": false,
+        "stateMutability": "view",
+        "type": "function"
+    }
+]"#;
+
+fn ens_contract() -> ethabi::Contract {
+    ethabi::Contract::load(ENS_ABI.as_bytes()).expect("ENS contract abi")
+}
+
+#[derive(Debug, Error)]
+pub enum EnsError {
+    #[error("ENS names are not supported by this server")]
+    Unsupported,
+    #[error("{0} is neither an address nor a valid ENS name")]
+    InvalidName(String),
+    #[error("ENS name {0} is not resolved to any address")]
+    NotResolved(String),
+    #[error("Unable to resolve the ENS name: {0}")]
+    Resolution(anyhow::Error),
+}
+
+/// Checks whether the string looks like an ENS name rather than an address or an account ID.
+pub fn is_ens_name(s: &str) -> bool {
+    s.contains('.')
+        && s.split('.')
+            .all(|label| !label.is_empty() && !label.chars().any(char::is_whitespace))
+}
+
+/// Computes the `namehash` of the name according to EIP-137.
+pub fn namehash(name: &str) -> H256 {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return H256(node);
+    }
+
+    for label in name.rsplit('.') {
+        let label_hash = tiny_keccak::keccak256(label.as_bytes());
+        node = tiny_keccak::keccak256(&[node, label_hash].concat());
+    }
+    H256(node)
+}
+
+/// Outcome of the name resolution stored in the cache.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resolution {
+    Resolved(Address),
+    NotResolved,
+    Failed,
+}
+
+#[derive(Clone)]
+pub struct EnsResolver {
+    client: EthereumGateway,
+    /// Address of the ENS registry, absent if the names resolution is disabled.
+    registry: Option<Address>,
+    /// Resolutions of the names along with the time they were made.
+    cache: SharedLruCache<String, (Resolution, Instant)>,
+    cache_ttl: Duration,
+    request_timeout: Duration,
+}
+
+impl EnsResolver {
+    pub fn new(client: EthereumGateway, config: &EnsConfig, cache_size: usize) -> Self {
+        Self {
+            client,
+            registry: config.registry_addr,
+            cache: SharedLruCache::new(cache_size),
+            cache_ttl: config.cache_ttl(),
+            request_timeout: config.request_timeout(),
+        }
+    }
+
+    /// Parses either the address, with or without the `0x` prefix, or the ENS name resolving it.
+    pub async fn parse_address(&self, s: &str) -> Result<Address, EnsError> {
+        if is_ens_name(s) {
+            return self.resolve(s).await;
+        }
+
+        s.trim_start_matches("0x")
+            .parse()
+            .map_err(|_| EnsError::InvalidName(s.to_owned()))
+    }
+
+    /// Returns the address the ENS name points to.
+    pub async fn resolve(&self, name: &str) -> Result<Address, EnsError> {
+        let registry = self.registry.ok_or(EnsError::Unsupported)?;
+        if !is_ens_name(name) {
+            return Err(EnsError::InvalidName(name.to_owned()));
+        }
+        // Names are case-insensitive, the normalized form is lowercase.
+        let name = name.to_lowercase();
+
+        if let Some((resolution, resolved_at)) = self.cache.get(&name) {
+            let ttl = match resolution {
+                Resolution::Failed => FAILED_RESOLUTION_TTL,
+                _ => self.cache_ttl,
+            };
+            if resolved_at.elapsed() < ttl {
+                return Self::resolution_result(name, resolution);
+            }
+        }
+
+        let result = time::timeout(
+            self.request_timeout,
+            self.resolve_onchain(registry, namehash(&name)),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(anyhow::format_err!(
+                "Request to the Ethereum node timed out"
+            ))
+        });
+        let resolution = match &result {
+            Ok(Some(address)) => Resolution::Resolved(*address),
+            Ok(None) => Resolution::NotResolved,
+            Err(_) => Resolution::Failed,
+        };
+        self.cache
+            .insert(name.clone(), (resolution, Instant::now()));
+
+        match result {
+            Ok(_) => Self::resolution_result(name, resolution),
+            Err(err) => Err(EnsError::Resolution(err)),
+        }
+    }
+
+    fn resolution_result(name: String, resolution: Resolution) -> Result<Address, EnsError> {
+        match resolution {
+            Resolution::Resolved(address) => Ok(address),
+            Resolution::NotResolved => Err(EnsError::NotResolved(name)),
+            Resolution::Failed => Err(EnsError::Resolution(anyhow::format_err!(
+                "resolution of {} has failed recently, try again later",
+                name
+            ))),
+        }
+    }
+
+    async fn resolve_onchain(
+        &self,
+        registry: Address,
+        node: H256,
+    ) -> Result<Option<Address>, anyhow::Error> {
+        let resolver: Address = self
+            .client
+            .call_contract_function(
+                "resolver",
+                node,
+                None,
+                Options::default(),
+                None,
+                registry,
+                ens_contract(),
+            )
+            .await?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+
+        let address: Address = self
+            .client
+            .call_contract_function(
+                "addr",
+                node,
+                None,
+                Options::default(),
+                None,
+                resolver,
+                ens_contract(),
+            )
+            .await?;
+        Ok(if address.is_zero() {
+            None
+        } else {
+            Some(address)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ens_namehash() {
+        assert_eq!(namehash(""), H256::zero());
+        assert_eq!(
+            namehash("eth"),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn ens_name_detection() {
+        for name in &["vitalik.eth", "sub.domain.eth", "Name.ETH"] {
+            assert!(is_ens_name(name), "{}", name);
+        }
+        for not_name in &[
+            "",
+            "42",
+            "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e",
+            "eth",
+            ".eth",
+            "name..eth",
+            "some name.eth",
+        ] {
+            assert!(!is_ens_name(not_name), "{}", not_name);
+        }
+    }
+}
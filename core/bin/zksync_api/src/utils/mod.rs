@@ -1,2 +1,5 @@
+pub mod cosigner;
+pub mod ens_resolver;
+pub mod rate_limiter;
 pub mod shared_lru_cache;
 pub mod token_db_cache;
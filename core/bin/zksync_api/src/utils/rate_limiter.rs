@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Interval between the removals of the buckets which are full again.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Token bucket of a single key.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+#[derive(Debug)]
+struct Buckets<K> {
+    buckets: HashMap<K, Bucket>,
+    cleaned_up_at: Instant,
+}
+
+/// `RateLimiter` limits the rate of the requests with a separate token bucket for each key
+/// (e.g. the IP address of the client).
+///
+/// Each bucket holds up to `burst` tokens and is refilled at the rate of `rate` tokens per second.
+/// Every request takes one token (or several ones, e.g. for the batch of transactions), and
+/// the requests are rejected while the bucket doesn't have enough tokens.
+/// Buckets which are full again are forgotten, so only the recently active keys are stored.
+#[derive(Debug, Clone)]
+pub struct RateLimiter<K: Eq + Hash> {
+    rate: f64,
+    burst: f64,
+    buckets: Arc<Mutex<Buckets<K>>>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    /// Creates a new rate limiter. Returns `None` if the rate is zero, i.e. the requests aren't limited.
+    pub fn new(rate: u32, burst: u32) -> Option<Self> {
+        if rate == 0 {
+            return None;
+        }
+
+        let buckets = Buckets {
+            buckets: HashMap::new(),
+            cleaned_up_at: Instant::now(),
+        };
+        Some(Self {
+            rate: rate as f64,
+            burst: burst.max(1) as f64,
+            buckets: Arc::new(Mutex::new(buckets)),
+        })
+    }
+
+    /// Takes a token from the bucket of the key. If the bucket is empty, returns the time after which
+    /// the request can be repeated, rounded up to whole seconds (as the `Retry-After` header requires).
+    pub fn check(&self, key: K) -> Result<(), Duration> {
+        self.check_n(key, 1)
+    }
+
+    /// Takes `n` tokens from the bucket of the key, the same way as `check` does. Requests taking
+    /// more than `burst` tokens take the whole bucket, so they're accepted once the bucket is full.
+    pub fn check_n(&self, key: K, n: u32) -> Result<(), Duration> {
+        self.check_n_at(key, n, Instant::now())
+    }
+
+    #[cfg(test)]
+    fn check_at(&self, key: K, now: Instant) -> Result<(), Duration> {
+        self.check_n_at(key, 1, now)
+    }
+
+    fn check_n_at(&self, key: K, n: u32, now: Instant) -> Result<(), Duration> {
+        let tokens = (n as f64).min(self.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+        if now.saturating_duration_since(buckets.cleaned_up_at) >= CLEANUP_INTERVAL {
+            let (rate, burst) = (self.rate, self.burst);
+            buckets
+                .buckets
+                .retain(|_, bucket| Self::refilled(rate, burst, *bucket, now) < burst);
+            buckets.cleaned_up_at = now;
+        }
+
+        let bucket = buckets.buckets.entry(key).or_insert(Bucket {
+            tokens: self.burst,
+            updated_at: now,
+        });
+        bucket.tokens = Self::refilled(self.rate, self.burst, *bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= tokens {
+            bucket.tokens -= tokens;
+            Ok(())
+        } else {
+            let secs = ((tokens - bucket.tokens) / self.rate).ceil();
+            Err(Duration::from_secs(secs.max(1.0) as u64))
+        }
+    }
+
+    fn refilled(rate: f64, burst: f64, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        (bucket.tokens + elapsed.as_secs_f64() * rate).min(burst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_rate_disables_limiter() {
+        assert!(RateLimiter::<u32>::new(0, 10).is_none());
+    }
+
+    #[test]
+    fn requests_are_limited() {
+        let limiter = RateLimiter::new(2, 3).unwrap();
+        let now = Instant::now();
+
+        // Burst is allowed, and the keys are limited independently.
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(1, now), Ok(()));
+        }
+        assert_eq!(limiter.check_at(1, now), Err(Duration::from_secs(1)));
+        assert_eq!(limiter.check_at(2, now), Ok(()));
+
+        // Bucket is refilled over time, but not above the burst size.
+        let later = now + Duration::from_millis(500);
+        assert_eq!(limiter.check_at(1, later), Ok(()));
+        assert!(limiter.check_at(1, later).is_err());
+
+        let much_later = now + Duration::from_secs(100);
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(1, much_later), Ok(()));
+        }
+        assert!(limiter.check_at(1, much_later).is_err());
+    }
+
+    #[test]
+    fn requests_take_several_tokens() {
+        let limiter = RateLimiter::new(1, 5).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(limiter.check_n_at(1, 3, now), Ok(()));
+        assert_eq!(limiter.check_n_at(1, 3, now), Err(Duration::from_secs(1)));
+        assert_eq!(limiter.check_n_at(1, 2, now), Ok(()));
+
+        // Requests exceeding the burst take the whole bucket.
+        assert_eq!(limiter.check_n_at(2, 10, now), Ok(()));
+        assert_eq!(limiter.check_n_at(2, 10, now), Err(Duration::from_secs(5)));
+        assert_eq!(
+            limiter.check_n_at(2, 10, now + Duration::from_secs(5)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn full_buckets_are_removed() {
+        let limiter = RateLimiter::new(1, 100).unwrap();
+        let now = Instant::now();
+
+        limiter.check_at(1, now).unwrap();
+        for _ in 0..100 {
+            limiter.check_at(2, now).unwrap();
+        }
+
+        // After the cleanup only the bucket of the second key isn't full yet.
+        let later = now + CLEANUP_INTERVAL;
+        limiter.check_at(3, later).unwrap();
+        let buckets = limiter.buckets.lock().unwrap();
+        assert!(!buckets.buckets.contains_key(&1));
+        assert!(buckets.buckets.contains_key(&2));
+        assert!(buckets.buckets.contains_key(&3));
+    }
+}
@@ -1,15 +1,80 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use tokio::sync::RwLock;
 
-use zksync_storage::StorageProcessor;
+use zksync_storage::{tokens::records::ListedToken, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
 use zksync_types::{Token, TokenId, TokenLike};
 
+/// Generation of the tokens data, incremented every time the tokens are modified
+/// by the operator. Caches filled with the data of an older generation are cleared,
+/// so the changes are picked up by all the caches of the process without a restart.
+static TOKENS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Period after which the whole cache is cleared. The generation is local to the process,
+/// so this is the time the tokens modified by the operator through another API server
+/// (or listed by the Core server) take to be picked up.
+pub const TOKENS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct CachedTokens {
+    generation: u64,
+    /// Time the cache was cleared at.
+    created_at: Instant,
+    tokens: HashMap<TokenLike, Token>,
+    /// Tokens the operator doesn't accept to pay fees in, loaded on the first request.
+    fee_disabled: Option<HashSet<TokenId>>,
+    /// All the tokens in the ascending order of the IDs.
+    listed: Option<Arc<Vec<ListedToken>>>,
+}
+
+impl CachedTokens {
+    fn new(generation: u64) -> Self {
+        Self {
+            generation,
+            created_at: Instant::now(),
+            tokens: HashMap::new(),
+            fee_disabled: None,
+            listed: None,
+        }
+    }
+
+    /// Returns `true` if the cached data may be used by the request of the given generation.
+    fn is_fresh(&self, generation: u64) -> bool {
+        self.generation == generation && self.created_at.elapsed() < TOKENS_CACHE_TTL
+    }
+
+    /// Clears the cache if it was filled with the data of an older generation or expired.
+    /// Returns `false` if the cache is newer than the given generation, so the data
+    /// loaded before the invalidation must not be stored in it.
+    fn sync(&mut self, generation: u64) -> bool {
+        if self.generation < generation || self.created_at.elapsed() >= TOKENS_CACHE_TTL {
+            *self = Self::new(self.generation.max(generation));
+        }
+        self.generation == generation
+    }
+}
+
+impl Default for CachedTokens {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Cache of the tokens data shared by the API handlers.
+///
+/// The cache is cleared once the tokens are modified by the operator through this server,
+/// and at least every `TOKENS_CACHE_TTL` otherwise.
 #[derive(Debug, Clone, Default)]
 pub struct TokenDBCache {
-    // TODO: handle stale entries, edge case when we rename token after adding it (ZKS-97)
-    cache: Arc<RwLock<HashMap<TokenLike, Token>>>,
+    cache: Arc<RwLock<CachedTokens>>,
 }
 
 impl TokenDBCache {
@@ -17,6 +82,12 @@ impl TokenDBCache {
         Self::default()
     }
 
+    /// Invalidates the cached tokens data of all the caches of the process, must be called
+    /// after the tokens are modified in the database.
+    pub fn invalidate_all() {
+        TOKENS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+
     pub async fn get_token(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -53,9 +124,10 @@ impl TokenDBCache {
         storage: &mut StorageProcessor<'_>,
         token_query: TokenLike,
     ) -> anyhow::Result<Option<Token>> {
+        let generation = TOKENS_GENERATION.load(Ordering::SeqCst);
         // Just return token from cache.
-        if let Some(token) = self.cache.read().await.get(&token_query) {
-            return Ok(Some(token.clone()));
+        if let Some(token) = self.cached_token(&token_query, generation).await {
+            return Ok(Some(token));
         }
         // Tries to fetch token from the underlying database.
         let token = {
@@ -66,12 +138,95 @@ impl TokenDBCache {
         };
         // Stores received token into the local cache.
         if let Some(token) = &token {
-            self.cache.write().await.insert(token_query, token.clone());
+            let mut cache = self.cache.write().await;
+            if cache.sync(generation) {
+                cache.tokens.insert(token_query, token.clone());
+            }
         }
 
         Ok(token)
     }
 
+    async fn cached_token(&self, token_query: &TokenLike, generation: u64) -> Option<Token> {
+        let cache = self.cache.read().await;
+        if cache.is_fresh(generation) {
+            cache.tokens.get(token_query).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the operator doesn't accept fees in the token.
+    pub async fn is_fee_disabled(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> anyhow::Result<bool> {
+        let fee_disabled = self.get_fee_disabled_tokens(storage).await?;
+        Ok(fee_disabled.contains(&token_id))
+    }
+
+    /// Returns the tokens the operator doesn't accept fees in.
+    pub async fn get_fee_disabled_tokens(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<HashSet<TokenId>> {
+        let generation = TOKENS_GENERATION.load(Ordering::SeqCst);
+        {
+            let cache = self.cache.read().await;
+            if cache.is_fresh(generation) {
+                if let Some(fee_disabled) = &cache.fee_disabled {
+                    return Ok(fee_disabled.clone());
+                }
+            }
+        }
+
+        let fee_disabled: HashSet<_> = storage
+            .tokens_schema()
+            .load_fee_disabled_tokens()
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut cache = self.cache.write().await;
+        if cache.sync(generation) {
+            cache.fee_disabled = Some(fee_disabled.clone());
+        }
+        Ok(fee_disabled)
+    }
+
+    /// Returns all the tokens in the ascending order of the IDs. The whole list is loaded
+    /// by a single query, and it also fills the cache of the lookups by the token IDs
+    /// and addresses.
+    pub async fn get_listed_tokens(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<Arc<Vec<ListedToken>>> {
+        let generation = TOKENS_GENERATION.load(Ordering::SeqCst);
+        {
+            let cache = self.cache.read().await;
+            if cache.is_fresh(generation) {
+                if let Some(listed) = &cache.listed {
+                    return Ok(listed.clone());
+                }
+            }
+        }
+
+        let listed = Arc::new(storage.tokens_schema().load_listed_tokens().await?);
+
+        let mut cache = self.cache.write().await;
+        if cache.sync(generation) {
+            for ListedToken { token, .. } in listed.iter() {
+                cache.tokens.insert(TokenLike::Id(token.id), token.clone());
+                cache
+                    .tokens
+                    .insert(TokenLike::Address(token.address), token.clone());
+            }
+            cache.listed = Some(listed.clone());
+        }
+        Ok(listed)
+    }
+
     pub async fn token_symbol(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -110,3 +265,52 @@ impl TokenDBCache {
             .await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::Address;
+
+    #[tokio::test]
+    async fn cache_is_invalidated() {
+        let cache = TokenDBCache::new();
+        let token = Token::new(TokenId(1), Address::repeat_byte(1), "ABC", 18);
+        let query = TokenLike::Id(token.id);
+
+        let generation = TOKENS_GENERATION.load(Ordering::SeqCst);
+        {
+            let mut cached = cache.cache.write().await;
+            assert!(cached.sync(generation));
+            cached.tokens.insert(query.clone(), token.clone());
+        }
+        assert_eq!(cache.cached_token(&query, generation).await, Some(token));
+
+        TokenDBCache::invalidate_all();
+        let new_generation = TOKENS_GENERATION.load(Ordering::SeqCst);
+        assert!(cache.cached_token(&query, new_generation).await.is_none());
+
+        // Data loaded before the invalidation is not stored into the cleared cache.
+        let mut cached = cache.cache.write().await;
+        assert!(cached.sync(new_generation));
+        assert!(cached.tokens.is_empty());
+        assert!(!cached.sync(generation));
+    }
+
+    #[test]
+    fn cache_expires() {
+        let mut cached = CachedTokens::new(1);
+        assert!(cached.is_fresh(1));
+        assert!(!cached.is_fresh(2));
+
+        cached.created_at -= TOKENS_CACHE_TTL;
+        assert!(!cached.is_fresh(1));
+        // The expired cache is cleared, but keeps its generation.
+        cached.tokens.insert(
+            TokenLike::Id(TokenId(1)),
+            Token::new(TokenId(1), Default::default(), "ABC", 18),
+        );
+        assert!(cached.sync(1));
+        assert!(cached.is_fresh(1));
+        assert!(cached.tokens.is_empty());
+    }
+}
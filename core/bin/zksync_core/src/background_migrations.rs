@@ -0,0 +1,187 @@
+//! Runner of the background migrations backfilling the data of the online schema migrations.
+//!
+//! Migrations are run one after another in small batches, each processed in its own database
+//! transaction together with the stored progress, so the runner can be stopped at any moment.
+//! The batch size is adjusted to the database load: if the batches become slow, the runner
+//! makes them smaller and waits longer between them, so the backfill doesn't affect the server.
+
+// Built-in uses
+use std::{
+    cmp,
+    time::{Duration, Instant},
+};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::BackgroundMigrationsConfig;
+use zksync_storage::{
+    background_migrations::{BackfillBatch, BackgroundMigration},
+    ConnectionPool,
+};
+
+/// Delay before retrying the failed batch.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Adjusts the size of the batches and the delay between them to the database load.
+#[derive(Debug)]
+struct Throttle {
+    batch_size: u32,
+    min_batch_size: u32,
+    max_batch_size: u32,
+    target_batch_duration: Duration,
+    batch_interval: Duration,
+    delay: Duration,
+}
+
+impl Throttle {
+    fn new(config: &BackgroundMigrationsConfig) -> Self {
+        Self {
+            batch_size: config.batch_size,
+            min_batch_size: cmp::min(config.min_batch_size, config.batch_size),
+            max_batch_size: config.batch_size,
+            target_batch_duration: config.target_batch_duration(),
+            batch_interval: config.batch_interval(),
+            delay: config.batch_interval(),
+        }
+    }
+
+    fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Batch taking longer than the target halves the size of the next one, while the batch
+    /// taking less than a half of the target doubles it. The database gets at least as much
+    /// time without the load as the batch took.
+    fn on_success(&mut self, batch_duration: Duration) {
+        if batch_duration > self.target_batch_duration {
+            self.batch_size = cmp::max(self.batch_size / 2, self.min_batch_size);
+        } else if batch_duration < self.target_batch_duration / 2 {
+            self.batch_size = cmp::min(self.batch_size.saturating_mul(2), self.max_batch_size);
+        }
+        self.delay = cmp::max(self.batch_interval, batch_duration);
+    }
+
+    /// Failed batch (e.g. because of the statement timeout) is retried later with the minimum size.
+    fn on_error(&mut self) {
+        self.batch_size = self.min_batch_size;
+        self.delay = RETRY_INTERVAL;
+    }
+}
+
+async fn process_batch(
+    connection_pool: &ConnectionPool,
+    migration: &dyn BackgroundMigration,
+    limit: u32,
+) -> anyhow::Result<Option<BackfillBatch>> {
+    let mut storage = connection_pool.access_storage().await?;
+    let batch = storage
+        .background_migrations_schema()
+        .process_batch(migration, limit)
+        .await?;
+    Ok(batch)
+}
+
+async fn run_migration(
+    connection_pool: &ConnectionPool,
+    migration: &dyn BackgroundMigration,
+    throttle: &mut Throttle,
+) {
+    vlog::info!("Running the background migration {}", migration.name());
+    loop {
+        let start = Instant::now();
+        match process_batch(connection_pool, migration, throttle.batch_size()).await {
+            Ok(None) => {
+                vlog::info!("Background migration {} is finished", migration.name());
+                return;
+            }
+            Ok(Some(batch)) => {
+                metrics::counter!(
+                    "background_migrations.processed_rows",
+                    batch.rows as u64,
+                    "migration" => migration.name()
+                );
+                let batch_duration = start.elapsed();
+                metrics::histogram!("background_migrations.batch", batch_duration);
+                throttle.on_success(batch_duration);
+            }
+            Err(err) => {
+                vlog::warn!(
+                    "Failed to process the batch of the background migration {}: {}",
+                    migration.name(),
+                    err
+                );
+                throttle.on_error();
+            }
+        }
+        time::delay_for(throttle.delay()).await;
+    }
+}
+
+/// Runs the task performing the background migrations in the given order.
+/// The task finishes once all the migrations are finished.
+pub fn run_background_migrations(
+    connection_pool: ConnectionPool,
+    config: BackgroundMigrationsConfig,
+    migrations: Vec<Box<dyn BackgroundMigration>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut throttle = Throttle::new(&config);
+        for migration in migrations {
+            run_migration(&connection_pool, migration.as_ref(), &mut throttle).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BackgroundMigrationsConfig {
+        BackgroundMigrationsConfig {
+            enabled: true,
+            batch_size: 1000,
+            min_batch_size: 100,
+            target_batch_duration: 1000,
+            batch_interval: 100,
+        }
+    }
+
+    /// Checks that the batch size is adjusted within the configured bounds.
+    #[test]
+    fn throttle_batch_size() {
+        let mut throttle = Throttle::new(&config());
+        assert_eq!(throttle.batch_size(), 1000);
+
+        // Slow batches shrink the batch size down to the minimum.
+        throttle.on_success(Duration::from_millis(1500));
+        assert_eq!(throttle.batch_size(), 500);
+        assert_eq!(throttle.delay(), Duration::from_millis(1500));
+        for _ in 0..5 {
+            throttle.on_success(Duration::from_millis(1500));
+        }
+        assert_eq!(throttle.batch_size(), 100);
+
+        // Batches close to the target keep the batch size.
+        throttle.on_success(Duration::from_millis(700));
+        assert_eq!(throttle.batch_size(), 100);
+        assert_eq!(throttle.delay(), Duration::from_millis(700));
+
+        // Fast batches grow the batch size up to the maximum.
+        throttle.on_success(Duration::from_millis(10));
+        assert_eq!(throttle.batch_size(), 200);
+        assert_eq!(throttle.delay(), Duration::from_millis(100));
+        for _ in 0..5 {
+            throttle.on_success(Duration::from_millis(10));
+        }
+        assert_eq!(throttle.batch_size(), 1000);
+
+        // Errors reset the batch size to the minimum.
+        throttle.on_error();
+        assert_eq!(throttle.batch_size(), 100);
+        assert_eq!(throttle.delay(), RETRY_INTERVAL);
+    }
+}
@@ -22,8 +22,19 @@ fn main() {
     let db_pool = ConnectionPool::new(Some(config.db.pool_size as u32));
 
     let storage = DBStorage::new(db_pool);
-    let eth_client = EthHttpClient::new(client, config.contracts.contract_addr);
-    let watcher = EthWatch::new(eth_client, storage, 0);
+    let eth_client = EthHttpClient::new(
+        client,
+        config.contracts.contract_addr,
+        config.contracts.governance_addr,
+        config.eth_client.chain_id,
+    );
+    let max_block_chunks = *config
+        .chain
+        .block_chunk_sizes()
+        .iter()
+        .max()
+        .expect("failed to find max block chunks size");
+    let watcher = EthWatch::new(eth_client, storage, 0, max_block_chunks);
 
     main_runtime.spawn(watcher.run(eth_req_receiver));
     main_runtime.block_on(async move {
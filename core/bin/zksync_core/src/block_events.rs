@@ -0,0 +1,254 @@
+//! Events about the blocks processed by the committer.
+//!
+//! The committer reports the executed operations and the committed and verified blocks to the
+//! optional subscribers, e.g. the webhook notifier or the event publisher. Events are reported
+//! once they are saved to the database. The subscriber lagging behind misses the events, and is
+//! notified about it with the `EventsDropped` event once it catches up.
+
+// Built-in uses
+use std::collections::HashSet;
+// External uses
+use futures::channel::mpsc::Sender;
+// Workspace uses
+use zksync_types::{
+    block::{Block, ExecutedOperations},
+    BlockNumber,
+};
+
+#[derive(Debug, Clone)]
+pub enum BlockEvent {
+    /// Operations were executed, but their block is not committed yet.
+    /// Every operation is reported only once.
+    OpsExecuted {
+        block_number: BlockNumber,
+        operations: Vec<ExecutedOperations>,
+    },
+    BlockCommitted {
+        block: Block,
+    },
+    BlockVerified {
+        block: Block,
+    },
+    /// The subscriber lagged behind, and the given amount of the preceding events was dropped.
+    EventsDropped {
+        count: usize,
+    },
+}
+
+/// Returns the hash identifying the operation in the events: the hash of the transaction
+/// with the `sync-tx:` prefix, or the hash of the Ethereum transaction which has created
+/// the priority operation.
+pub fn operation_hash(op: &ExecutedOperations) -> String {
+    match op {
+        ExecutedOperations::Tx(tx) => tx.signed_tx.tx.hash().to_string(),
+        ExecutedOperations::PriorityOp(op) => format!("{:#x}", op.priority_op.eth_hash),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Subscriber {
+    sender: Sender<BlockEvent>,
+    /// Amount of the events dropped since the last event delivered to the subscriber.
+    dropped_events: usize,
+}
+
+impl Subscriber {
+    /// Sends the event unless the subscriber's channel is full. If the preceding events were dropped,
+    /// the subscriber is notified about it first.
+    fn send(&mut self, event: BlockEvent) {
+        if self.dropped_events > 0 {
+            let notice = BlockEvent::EventsDropped {
+                count: self.dropped_events,
+            };
+            if self.sender.try_send(notice).is_err() {
+                self.dropped_events += 1;
+                metrics::counter!("block_events.dropped", 1);
+                return;
+            }
+            self.dropped_events = 0;
+        }
+
+        if let Err(err) = self.sender.try_send(event) {
+            vlog::warn!("Failed to report the block event: {}", err);
+            self.dropped_events += 1;
+            metrics::counter!("block_events.dropped", 1);
+        }
+    }
+}
+
+/// Sends the block events to the subscribers. An event is dropped for the subscriber which
+/// lags behind, so the subscribers never slow down the committer.
+#[derive(Debug, Clone, Default)]
+pub struct BlockEventsNotifier {
+    subscribers: Vec<Subscriber>,
+    /// Block of the executed operations reported to the subscribers.
+    executed_block: BlockNumber,
+    /// Operations of the `executed_block` the subscribers were already notified about.
+    notified_ops: HashSet<String>,
+}
+
+impl BlockEventsNotifier {
+    pub fn new(subscribers: Vec<Sender<BlockEvent>>) -> Self {
+        let subscribers = subscribers
+            .into_iter()
+            .map(|sender| Subscriber {
+                sender,
+                dropped_events: 0,
+            })
+            .collect();
+
+        Self {
+            subscribers,
+            ..Self::default()
+        }
+    }
+
+    /// Reports the executed operations of the block, skipping the ones already reported,
+    /// since the same operations are contained in every pending block update.
+    pub fn ops_executed(&mut self, block_number: BlockNumber, operations: &[ExecutedOperations]) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+
+        if block_number != self.executed_block {
+            self.executed_block = block_number;
+            self.notified_ops.clear();
+        }
+        let notified_ops = &mut self.notified_ops;
+        let operations: Vec<_> = operations
+            .iter()
+            .filter(|op| notified_ops.insert(operation_hash(op)))
+            .cloned()
+            .collect();
+
+        if !operations.is_empty() {
+            self.notify(BlockEvent::OpsExecuted {
+                block_number,
+                operations,
+            });
+        }
+    }
+
+    /// Reports the committed block along with its operations which weren't reported before.
+    pub fn block_committed(&mut self, block: &Block) {
+        self.ops_executed(block.block_number, &block.block_transactions);
+        self.notify(BlockEvent::BlockCommitted {
+            block: block.clone(),
+        });
+    }
+
+    pub fn block_verified(&mut self, block: &Block) {
+        self.notify(BlockEvent::BlockVerified {
+            block: block.clone(),
+        });
+    }
+
+    fn notify(&mut self, event: BlockEvent) {
+        for subscriber in &mut self.subscribers {
+            subscriber.send(event.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use futures::{channel::mpsc, StreamExt};
+    use zksync_types::{
+        AccountId, Address, ExecutedTx, Nonce, SignedZkSyncTx, TokenId, Transfer, ZkSyncTx,
+    };
+
+    fn executed_transfer(nonce: u32) -> ExecutedOperations {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(nonce),
+            None,
+        );
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx: ZkSyncTx::Transfer(Box::new(transfer)),
+                eth_sign_data: None,
+            },
+            success: true,
+            op: None,
+            fail_reason: None,
+            fail_code: None,
+            block_index: None,
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    fn reported_hashes(event: Option<BlockEvent>) -> Vec<String> {
+        match event {
+            Some(BlockEvent::OpsExecuted { operations, .. }) => {
+                operations.iter().map(operation_hash).collect()
+            }
+            event => panic!("Unexpected event: {:?}", event),
+        }
+    }
+
+    #[tokio::test]
+    async fn executed_ops_are_reported_once() {
+        let (sender, mut receiver) = mpsc::channel(10);
+        let mut notifier = BlockEventsNotifier::new(vec![sender]);
+        let (first, second) = (executed_transfer(0), executed_transfer(1));
+
+        notifier.ops_executed(BlockNumber(1), &[first.clone()]);
+        assert_eq!(
+            reported_hashes(receiver.next().await),
+            vec![operation_hash(&first)]
+        );
+
+        notifier.ops_executed(BlockNumber(1), &[first.clone(), second.clone()]);
+        assert_eq!(
+            reported_hashes(receiver.next().await),
+            vec![operation_hash(&second)]
+        );
+
+        // Nothing is reported if there are no new operations.
+        notifier.ops_executed(BlockNumber(1), &[first.clone(), second.clone()]);
+        assert!(receiver.try_next().is_err());
+
+        // Operations of the next block are reported anew.
+        notifier.ops_executed(BlockNumber(2), &[first.clone()]);
+        assert_eq!(
+            reported_hashes(receiver.next().await),
+            vec![operation_hash(&first)]
+        );
+    }
+
+    #[tokio::test]
+    async fn dropped_events_are_reported() {
+        // The channel fits two events.
+        let (sender, mut receiver) = mpsc::channel(1);
+        let mut notifier = BlockEventsNotifier::new(vec![sender]);
+
+        for nonce in 0..4 {
+            notifier.ops_executed(BlockNumber(1), &[executed_transfer(nonce)]);
+        }
+        for nonce in 0..2 {
+            assert_eq!(
+                reported_hashes(receiver.next().await),
+                vec![operation_hash(&executed_transfer(nonce))]
+            );
+        }
+
+        // The subscriber is notified about the dropped events before the next event.
+        notifier.ops_executed(BlockNumber(1), &[executed_transfer(4)]);
+        assert!(matches!(
+            receiver.next().await,
+            Some(BlockEvent::EventsDropped { count: 2 })
+        ));
+        assert_eq!(
+            reported_hashes(receiver.next().await),
+            vec![operation_hash(&executed_transfer(4))]
+        );
+    }
+}
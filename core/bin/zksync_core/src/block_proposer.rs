@@ -4,60 +4,421 @@
 //! It does it in small batches, called here `miniblocks`, which are smaller that full blocks.
 //!
 //! Right now logic of this actor is simple, but in future consensus will replace it using the same API.
+//!
+//! If several block sizes are available, block proposer may also choose the size of the created blocks
+//! to minimize the L1 cost per chunk (see `BlockSizeSelector`).
 
+// Built-in deps
+use std::time::{Duration, Instant};
 // External deps
 use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
 use tokio::{task::JoinHandle, time};
+use vlog::Traced;
 // Workspace deps
 use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{gas_counter::GasCounter, mempool::SignedTxVariant, BlockNumber};
 // Local deps
 use crate::{
     mempool::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock},
+    runtime_flags::RuntimeFlags,
     state_keeper::StateKeeperRequest,
 };
 
 fn create_mempool_req(
     last_priority_op_number: u64,
+    block_number: BlockNumber,
+    priority_ops_only: bool,
 ) -> (MempoolBlocksRequest, oneshot::Receiver<ProposedBlock>) {
     let (response_sender, receiver) = oneshot::channel();
     (
         MempoolBlocksRequest::GetBlock(GetBlockRequest {
             last_priority_op_number,
+            block_number,
+            priority_ops_only,
             response_sender,
         }),
         receiver,
     )
 }
 
+/// Interval between the checks of the prover jobs queue size.
+const BACKPRESSURE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Queue of the blocks awaiting for proof.
+#[async_trait::async_trait]
+trait ProverJobsQueue: Send + Sync {
+    async fn pending_jobs_count(&self) -> anyhow::Result<u32>;
+}
+
+#[async_trait::async_trait]
+impl ProverJobsQueue for ConnectionPool {
+    async fn pending_jobs_count(&self) -> anyhow::Result<u32> {
+        let mut storage = self.access_storage().await?;
+        Ok(storage.prover_schema().pending_jobs_count().await?)
+    }
+}
+
+/// Slows down the block proposer while the witness generators are paused
+/// because of the provers lagging behind.
+struct BackpressureThrottle<Q = ConnectionPool> {
+    jobs_queue: Q,
+    max_pending_jobs: u32,
+    /// While backpressured, only every n-th miniblock iteration is performed.
+    slow_down_factor: u64,
+    check_interval: Duration,
+
+    backpressured: bool,
+    last_check: Option<Instant>,
+    skipped_iterations: u64,
+}
+
+impl<Q: ProverJobsQueue> BackpressureThrottle<Q> {
+    fn new(jobs_queue: Q, max_pending_jobs: u32, slow_down_factor: u64) -> Self {
+        Self {
+            jobs_queue,
+            max_pending_jobs,
+            slow_down_factor,
+            check_interval: BACKPRESSURE_CHECK_INTERVAL,
+            backpressured: false,
+            last_check: None,
+            skipped_iterations: 0,
+        }
+    }
+
+    async fn update_status(&mut self) -> anyhow::Result<()> {
+        let pending_jobs = self.jobs_queue.pending_jobs_count().await?;
+
+        let backpressured = pending_jobs >= self.max_pending_jobs;
+        if backpressured != self.backpressured {
+            vlog::info!(
+                "Witness generation backpressure is {}, pending prover jobs: {}",
+                if backpressured { "on" } else { "off" },
+                pending_jobs
+            );
+        }
+        self.backpressured = backpressured;
+        Ok(())
+    }
+
+    /// Returns `true` if the current miniblock iteration should be skipped.
+    async fn should_skip_iteration(&mut self) -> bool {
+        let check_required = self
+            .last_check
+            .map(|last_check| last_check.elapsed() >= self.check_interval)
+            .unwrap_or(true);
+        if check_required {
+            self.last_check = Some(Instant::now());
+            if let Err(err) = self.update_status().await {
+                vlog::warn!("Failed to check the prover jobs queue: {}", err);
+            }
+        }
+
+        if !self.backpressured {
+            self.skipped_iterations = 0;
+            return false;
+        }
+
+        self.skipped_iterations += 1;
+        if self.skipped_iterations < self.slow_down_factor {
+            metrics::counter!("block_proposer.skipped_iterations", 1);
+            return true;
+        }
+        self.skipped_iterations = 0;
+        false
+    }
+}
+
+/// Interval between the updates of the gas price used to choose the block size.
+const GAS_PRICE_UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+/// Weight of the latest miniblock iteration in the estimated inflow of transactions.
+const INFLOW_SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Chooses the size of the created blocks that minimizes the L1 cost per chunk.
+///
+/// Every block costs the constant overhead of its commit and verify transactions, while the proving
+/// cost depends on the block size regardless of the amount of chunks actually used. The amount of chunks
+/// the next block is expected to contain is estimated from the mempool depth and the inflow of transactions
+/// over the block lifetime.
+///
+/// Fees charge only `BLOCK_OVERHEAD_COST_PER_CHUNK` of the overhead for every chunk, so a block size is chosen
+/// only if the fees of the expected chunks cover its cost. Otherwise the largest blocks are created as usual,
+/// so the overhead is spread over as many chunks as possible.
+struct BlockSizeSelector {
+    connection_pool: ConnectionPool,
+    block_chunk_sizes: Vec<usize>,
+    zkp_cost_chunk_wei: f64,
+    miniblock_iterations: u64,
+
+    gas_price_wei: Option<f64>,
+    last_gas_price_update: Option<Instant>,
+    /// Estimated amount of chunks added to the mempool during a single miniblock iteration.
+    inflow_per_iteration: f64,
+    /// Amount of chunks left in the mempool after the last proposed miniblock.
+    remaining_chunks: Option<usize>,
+    block_chunks_size: Option<usize>,
+}
+
+impl BlockSizeSelector {
+    fn new(connection_pool: ConnectionPool, config: &ZkSyncConfig) -> Self {
+        Self {
+            connection_pool,
+            block_chunk_sizes: config.chain.block_chunk_sizes(),
+            zkp_cost_chunk_wei: config.chain.state_keeper.zkp_cost_chunk_gwei as f64 * 1e9,
+            miniblock_iterations: config.chain.state_keeper.miniblock_iterations,
+            gas_price_wei: None,
+            last_gas_price_update: None,
+            inflow_per_iteration: 0.0,
+            remaining_chunks: None,
+            block_chunks_size: None,
+        }
+    }
+
+    async fn update_gas_price(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.access_storage().await?;
+        let gas_price = storage.ethereum_schema().load_average_gas_price().await?;
+
+        self.gas_price_wei = gas_price.map(|gas_price| gas_price.as_u64() as f64);
+        Ok(())
+    }
+
+    /// Updates the inflow estimation with the mempool depth observed before proposing a miniblock.
+    fn update_inflow(&mut self, pending_chunks: usize) {
+        if let Some(remaining_chunks) = self.remaining_chunks {
+            let added_chunks = pending_chunks.saturating_sub(remaining_chunks) as f64;
+            self.inflow_per_iteration = INFLOW_SMOOTHING_FACTOR * added_chunks
+                + (1.0 - INFLOW_SMOOTHING_FACTOR) * self.inflow_per_iteration;
+        }
+    }
+
+    /// Returns the new block size if it has to be changed.
+    async fn select(&mut self, pending_chunks: usize) -> Option<usize> {
+        let update_required = self
+            .last_gas_price_update
+            .map(|last_update| last_update.elapsed() >= GAS_PRICE_UPDATE_INTERVAL)
+            .unwrap_or(true);
+        if update_required {
+            self.last_gas_price_update = Some(Instant::now());
+            if let Err(err) = self.update_gas_price().await {
+                vlog::warn!(
+                    "Failed to load the gas price to choose the block size: {}",
+                    err
+                );
+            }
+        }
+        self.update_inflow(pending_chunks);
+
+        let expected_chunks =
+            pending_chunks as f64 + self.inflow_per_iteration * self.miniblock_iterations as f64;
+        let block_chunks_size = match self.gas_price_wei {
+            Some(gas_price_wei) => optimal_block_size(
+                &self.block_chunk_sizes,
+                gas_price_wei,
+                self.zkp_cost_chunk_wei,
+                expected_chunks,
+            ),
+            // Until the gas price is known, the largest blocks are created as usual.
+            None => *self
+                .block_chunk_sizes
+                .last()
+                .expect("failed to get max block size"),
+        };
+
+        if self.block_chunks_size == Some(block_chunks_size) {
+            return None;
+        }
+        vlog::info!(
+            "Block size is changed to {} chunks, expected chunks: {:.1}, gas price: {:?}",
+            block_chunks_size,
+            expected_chunks,
+            self.gas_price_wei
+        );
+        metrics::gauge!("block_proposer.block_chunks_size", block_chunks_size as f64);
+        self.block_chunks_size = Some(block_chunks_size);
+        Some(block_chunks_size)
+    }
+
+    /// Records the amount of chunks taken from the mempool by the proposed miniblock.
+    fn record_proposed(&mut self, pending_chunks: usize, proposed_chunks: usize) {
+        self.remaining_chunks = Some(pending_chunks.saturating_sub(proposed_chunks));
+    }
+}
+
+/// Returns the block size with the lowest L1 cost per chunk for the expected amount of chunks,
+/// among the sizes whose cost is covered by the fees of the expected chunks. If no size is covered,
+/// the largest one is returned. If several sizes have the same cost, the smallest one is chosen,
+/// so the block is sealed faster.
+fn optimal_block_size(
+    block_chunk_sizes: &[usize],
+    gas_price_wei: f64,
+    zkp_cost_chunk_wei: f64,
+    expected_chunks: f64,
+) -> usize {
+    let overhead_cost = GasCounter::BLOCK_OVERHEAD_COST as f64 * gas_price_wei;
+    let charged_per_chunk =
+        GasCounter::BLOCK_OVERHEAD_COST_PER_CHUNK as f64 * gas_price_wei + zkp_cost_chunk_wei;
+    let used_chunks =
+        |block_chunks_size: usize| expected_chunks.min(block_chunks_size as f64).max(1.0);
+    let block_cost =
+        |block_chunks_size: usize| overhead_cost + zkp_cost_chunk_wei * block_chunks_size as f64;
+
+    let mut optimal: Option<(usize, f64)> = None;
+    for size in block_chunk_sizes.iter().copied() {
+        if used_chunks(size) * charged_per_chunk < block_cost(size) {
+            continue;
+        }
+        let cost = block_cost(size) / used_chunks(size);
+        if optimal.map_or(true, |(_, optimal_cost)| cost < optimal_cost) {
+            optimal = Some((size, cost));
+        }
+    }
+
+    match optimal {
+        Some((size, _)) => size,
+        None => *block_chunk_sizes.last().expect("no block sizes available"),
+    }
+}
+
+/// Returns the amount of chunks required by the operations of the proposed block.
+fn proposed_block_chunks(proposed_block: &ProposedBlock) -> usize {
+    let tx_chunks: usize = proposed_block
+        .txs
+        .iter()
+        .map(|tx| match tx {
+            SignedTxVariant::Tx(tx) => tx.tx.min_chunks(),
+            SignedTxVariant::Batch(batch) => batch.txs.iter().map(|tx| tx.tx.min_chunks()).sum(),
+        })
+        .sum();
+    let priority_op_chunks: usize = proposed_block
+        .priority_ops
+        .iter()
+        .map(|op| op.data.chunks())
+        .sum();
+
+    tx_chunks + priority_op_chunks
+}
+
 struct BlockProposer {
     current_priority_op_number: u64,
+    paused: bool,
 
-    mempool_requests: mpsc::Sender<MempoolBlocksRequest>,
+    mempool_requests: mpsc::Sender<Traced<MempoolBlocksRequest>>,
     statekeeper_requests: mpsc::Sender<StateKeeperRequest>,
 }
 
 impl BlockProposer {
-    async fn propose_new_block(&mut self) -> ProposedBlock {
-        let (mempool_req, resp) = create_mempool_req(self.current_priority_op_number);
+    async fn pending_block_number(&mut self) -> BlockNumber {
+        let (response_sender, receiver) = oneshot::channel();
+        self.statekeeper_requests
+            .send(StateKeeperRequest::GetPendingBlockNumber(response_sender))
+            .await
+            .expect("state keeper receiver dropped");
+
+        receiver
+            .await
+            .expect("State keeper pending block number request failed")
+    }
+
+    async fn propose_new_block(&mut self, priority_ops_only: bool) -> ProposedBlock {
+        let block_number = self.pending_block_number().await;
+        let (mempool_req, resp) = create_mempool_req(
+            self.current_priority_op_number,
+            block_number,
+            priority_ops_only,
+        );
+        let span = tracing::debug_span!(
+            "propose_miniblock",
+            last_priority_op_number = self.current_priority_op_number
+        );
         self.mempool_requests
-            .send(mempool_req)
+            .send(Traced::with_span(span, mempool_req))
             .await
             .expect("mempool receiver dropped");
 
         resp.await.expect("Mempool new block request failed")
     }
 
-    async fn commit_new_tx_mini_batch(&mut self) {
-        let proposed_block = self.propose_new_block().await;
+    /// Returns the amount of chunks required by the committed miniblock transactions.
+    async fn commit_new_tx_mini_batch(&mut self, priority_ops_only: bool) -> usize {
+        let proposed_block = self.propose_new_block(priority_ops_only).await;
+        let proposed_chunks = proposed_block_chunks(&proposed_block);
 
         self.current_priority_op_number += proposed_block.priority_ops.len() as u64;
         self.statekeeper_requests
             .send(StateKeeperRequest::ExecuteMiniBlock(proposed_block))
             .await
             .expect("state keeper receiver dropped");
+        proposed_chunks
+    }
+
+    async fn mempool_pending_chunks(&mut self) -> usize {
+        let (response_sender, receiver) = oneshot::channel();
+        self.mempool_requests
+            .send(Traced::new(MempoolBlocksRequest::GetPendingChunks(
+                response_sender,
+            )))
+            .await
+            .expect("mempool receiver dropped");
+
+        receiver
+            .await
+            .expect("Mempool pending chunks request failed")
+    }
+
+    async fn set_block_chunks_size(&mut self, block_chunks_size: usize) {
+        self.statekeeper_requests
+            .send(StateKeeperRequest::SetBlockChunksSize(block_chunks_size))
+            .await
+            .expect("state keeper receiver dropped");
+    }
+
+    async fn seal_non_empty_block(&mut self) {
+        self.statekeeper_requests
+            .send(StateKeeperRequest::SealNonEmptyBlock)
+            .await
+            .expect("state keeper receiver dropped");
+    }
+
+    /// Regular miniblock iteration, skipped from time to time while the block proposer
+    /// is slowed down because of the provers lagging behind.
+    async fn miniblock_iteration<Q: ProverJobsQueue>(
+        &mut self,
+        throttle: Option<&mut BackpressureThrottle<Q>>,
+        block_size_selector: Option<&mut BlockSizeSelector>,
+    ) {
+        self.paused = false;
+
+        if let Some(throttle) = throttle {
+            if throttle.should_skip_iteration().await {
+                return;
+            }
+        }
+
+        if let Some(selector) = block_size_selector {
+            let pending_chunks = self.mempool_pending_chunks().await;
+            if let Some(block_chunks_size) = selector.select(pending_chunks).await {
+                self.set_block_chunks_size(block_chunks_size).await;
+            }
+            let proposed_chunks = self.commit_new_tx_mini_batch(false).await;
+            selector.record_proposed(pending_chunks, proposed_chunks);
+        } else {
+            self.commit_new_tx_mini_batch(false).await;
+        }
+    }
+
+    /// Miniblock iteration performed while the server is paused. Once the server is paused,
+    /// the pending block is sealed, so it gets committed. New transactions are not taken from
+    /// the mempool until the server is resumed, while the priority operations are still processed,
+    /// since the users can't cancel them.
+    async fn paused_iteration(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            self.seal_non_empty_block().await;
+        }
+        self.commit_new_tx_mini_batch(true).await;
     }
 }
 
@@ -65,10 +426,29 @@ impl BlockProposer {
 #[must_use]
 pub fn run_block_proposer_task(
     config: &ZkSyncConfig,
-    mempool_requests: mpsc::Sender<MempoolBlocksRequest>,
+    connection_pool: ConnectionPool,
+    mempool_requests: mpsc::Sender<Traced<MempoolBlocksRequest>>,
     mut statekeeper_requests: mpsc::Sender<StateKeeperRequest>,
+    runtime_flags: RuntimeFlags,
 ) -> JoinHandle<()> {
     let miniblock_interval = config.chain.state_keeper.miniblock_iteration_interval();
+    let witness_generator_opts = &config.prover.witness_generator;
+    let mut throttle = if witness_generator_opts.slow_down_state_keeper {
+        Some(BackpressureThrottle::new(
+            connection_pool.clone(),
+            witness_generator_opts.max_pending_jobs,
+            witness_generator_opts.state_keeper_slow_down_factor,
+        ))
+    } else {
+        None
+    };
+    let mut block_size_selector = if config.chain.state_keeper.cost_optimal_block_size
+        && config.chain.block_chunk_sizes().len() > 1
+    {
+        Some(BlockSizeSelector::new(connection_pool, config))
+    } else {
+        None
+    };
     tokio::spawn(async move {
         let mut timer = time::interval(miniblock_interval);
 
@@ -86,6 +466,7 @@ pub fn run_block_proposer_task(
 
         let mut block_proposer = BlockProposer {
             current_priority_op_number,
+            paused: false,
             mempool_requests,
             statekeeper_requests,
         };
@@ -93,7 +474,223 @@ pub fn run_block_proposer_task(
         loop {
             timer.tick().await;
 
-            block_proposer.commit_new_tx_mini_batch().await;
+            if runtime_flags.paused() {
+                block_proposer.paused_iteration().await;
+            } else {
+                block_proposer
+                    .miniblock_iteration(throttle.as_mut(), block_size_selector.as_mut())
+                    .await;
+            }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    const BLOCK_SIZES: &[usize] = &[6, 30, 700, 1400];
+    const GWEI: f64 = 1e9;
+
+    /// Checks that the block sizes are chosen only if the fees of the expected chunks cover
+    /// their cost, and the largest size is used otherwise.
+    #[test]
+    fn optimal_block_size_by_load() {
+        let gas_price = 10.0 * GWEI;
+        let zkp_cost = 500.0 * GWEI;
+
+        // Small blocks are not filled enough to cover the overhead by the fees.
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, zkp_cost, 0.0),
+            1400
+        );
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, zkp_cost, 30.0),
+            1400
+        );
+        // The largest block would be covered only if it's filled further.
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, zkp_cost, 690.0),
+            700
+        );
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, zkp_cost, 2000.0),
+            1400
+        );
+    }
+
+    /// Checks that the fees of the expected chunks are compared with the block cost
+    /// including the proving cost of the whole block.
+    #[test]
+    fn optimal_block_size_by_zkp_cost() {
+        let gas_price = 10.0 * GWEI;
+        let expected_chunks = 800.0;
+
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, 500.0 * GWEI, expected_chunks),
+            1400
+        );
+        assert_eq!(
+            optimal_block_size(BLOCK_SIZES, gas_price, 20_000.0 * GWEI, expected_chunks),
+            700
+        );
+    }
+
+    /// Checks that the paused block proposer seals the pending block once, and keeps proposing
+    /// the miniblocks with the priority operations only.
+    #[tokio::test]
+    async fn paused_block_proposer() {
+        let (mempool_requests, mut mempool_rx) = mpsc::channel(8);
+        let (statekeeper_requests, mut statekeeper_rx) = mpsc::channel(8);
+        let mut block_proposer = BlockProposer {
+            current_priority_op_number: 0,
+            paused: false,
+            mempool_requests,
+            statekeeper_requests,
+        };
+
+        let mempool = tokio::spawn(async move {
+            let mut proposed_blocks = 0;
+            while let Some(request) = mempool_rx.next().await {
+                match request.into_inner() {
+                    MempoolBlocksRequest::GetBlock(request) => {
+                        assert!(request.priority_ops_only);
+                        assert_eq!(request.block_number, BlockNumber(1));
+                        let proposed_block = ProposedBlock {
+                            priority_ops: Vec::new(),
+                            txs: Vec::new(),
+                        };
+                        request.response_sender.send(proposed_block).unwrap();
+                        proposed_blocks += 1;
+                    }
+                    request => panic!("Unexpected mempool request: {:?}", request),
+                }
+            }
+            proposed_blocks
+        });
+
+        let state_keeper = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            while let Some(request) = statekeeper_rx.next().await {
+                match request {
+                    StateKeeperRequest::GetPendingBlockNumber(sender) => {
+                        sender.send(BlockNumber(1)).unwrap();
+                    }
+                    request => requests.push(request),
+                }
+            }
+            requests
+        });
+
+        block_proposer.paused_iteration().await;
+        block_proposer.paused_iteration().await;
+        drop(block_proposer);
+
+        assert_eq!(mempool.await.unwrap(), 2);
+        let requests = state_keeper.await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(matches!(requests[0], StateKeeperRequest::SealNonEmptyBlock));
+        assert!(matches!(
+            requests[1],
+            StateKeeperRequest::ExecuteMiniBlock(_)
+        ));
+        assert!(matches!(
+            requests[2],
+            StateKeeperRequest::ExecuteMiniBlock(_)
+        ));
+    }
+
+    /// Queue of the prover jobs with the amount of pending jobs set by the test.
+    #[derive(Debug, Clone, Default)]
+    struct TestJobsQueue(Arc<AtomicU32>);
+
+    #[async_trait::async_trait]
+    impl ProverJobsQueue for TestJobsQueue {
+        async fn pending_jobs_count(&self) -> anyhow::Result<u32> {
+            Ok(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    /// Checks that the block proposer performs only every n-th miniblock iteration once
+    /// the prover jobs queue reaches the limit, and gets back to the regular pace once
+    /// the provers catch up.
+    #[tokio::test]
+    async fn backpressured_block_proposer() {
+        let (mempool_requests, mut mempool_rx) = mpsc::channel(8);
+        let (statekeeper_requests, mut statekeeper_rx) = mpsc::channel(8);
+        let mut block_proposer = BlockProposer {
+            current_priority_op_number: 0,
+            paused: false,
+            mempool_requests,
+            statekeeper_requests,
+        };
+
+        let mempool = tokio::spawn(async move {
+            let mut proposed_blocks = 0;
+            while let Some(request) = mempool_rx.next().await {
+                match request.into_inner() {
+                    MempoolBlocksRequest::GetBlock(request) => {
+                        assert!(!request.priority_ops_only);
+                        let proposed_block = ProposedBlock {
+                            priority_ops: Vec::new(),
+                            txs: Vec::new(),
+                        };
+                        request.response_sender.send(proposed_block).unwrap();
+                        proposed_blocks += 1;
+                    }
+                    request => panic!("Unexpected mempool request: {:?}", request),
+                }
+            }
+            proposed_blocks
+        });
+
+        let state_keeper = tokio::spawn(async move {
+            let mut executed_miniblocks = 0;
+            while let Some(request) = statekeeper_rx.next().await {
+                match request {
+                    StateKeeperRequest::GetPendingBlockNumber(sender) => {
+                        sender.send(BlockNumber(1)).unwrap();
+                    }
+                    StateKeeperRequest::ExecuteMiniBlock(_) => executed_miniblocks += 1,
+                    request => panic!("Unexpected state keeper request: {:?}", request),
+                }
+            }
+            executed_miniblocks
+        });
+
+        let jobs_queue = TestJobsQueue::default();
+        let mut throttle = BackpressureThrottle::new(jobs_queue.clone(), 10, 4);
+        throttle.check_interval = Duration::from_secs(0);
+
+        // Provers keep up, every iteration proposes a miniblock.
+        jobs_queue.0.store(9, Ordering::SeqCst);
+        for _ in 0..8 {
+            block_proposer
+                .miniblock_iteration(Some(&mut throttle), None)
+                .await;
+        }
+        // Provers lag behind, only every 4th iteration proposes a miniblock.
+        jobs_queue.0.store(10, Ordering::SeqCst);
+        for _ in 0..8 {
+            block_proposer
+                .miniblock_iteration(Some(&mut throttle), None)
+                .await;
+        }
+        // Provers caught up.
+        jobs_queue.0.store(0, Ordering::SeqCst);
+        for _ in 0..8 {
+            block_proposer
+                .miniblock_iteration(Some(&mut throttle), None)
+                .await;
+        }
+        drop(block_proposer);
+
+        assert_eq!(mempool.await.unwrap(), 18);
+        assert_eq!(state_keeper.await.unwrap(), 18);
+    }
+}
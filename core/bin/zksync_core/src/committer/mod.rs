@@ -6,14 +6,23 @@ use futures::channel::mpsc::{Receiver, Sender};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::{task::JoinHandle, time};
+use tracing::Instrument;
+use vlog::Traced;
 // Workspace uses
-use crate::mempool::MempoolBlocksRequest;
+use crate::{
+    block_events::{BlockEvent, BlockEventsNotifier},
+    mempool::MempoolBlocksRequest,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     block::{Block, ExecutedOperations, PendingBlock},
     AccountUpdates, Action, BlockNumber, Operation,
 };
 
+use self::token_metrics::TokenMetrics;
+
+mod token_metrics;
+
 #[derive(Debug)]
 pub enum CommitRequest {
     PendingBlock((PendingBlock, AppliedUpdatesRequest)),
@@ -40,22 +49,31 @@ pub struct ExecutedOpsNotify {
 const PROOF_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 async fn handle_new_commit_task(
-    mut rx_for_ops: Receiver<CommitRequest>,
-    mut mempool_req_sender: Sender<MempoolBlocksRequest>,
+    mut rx_for_ops: Receiver<Traced<CommitRequest>>,
+    mut mempool_req_sender: Sender<Traced<MempoolBlocksRequest>>,
     pool: ConnectionPool,
+    mut block_events: BlockEventsNotifier,
 ) {
+    let mut token_metrics = TokenMetrics::default();
     while let Some(request) = rx_for_ops.next().await {
+        // Requests are handled within the span of the state keeper which sent them.
+        let (span, request) = request.into_parts();
         match request {
             CommitRequest::Block((block_commit_request, applied_updates_req)) => {
+                let block = block_commit_request.block.clone();
                 commit_block(
                     block_commit_request,
                     applied_updates_req,
                     &pool,
                     &mut mempool_req_sender,
+                    &mut token_metrics,
                 )
+                .instrument(span)
                 .await;
+                block_events.block_committed(&block);
             }
             CommitRequest::PendingBlock((pending_block, applied_updates_req)) => {
+                let block_number = pending_block.number;
                 let mut operations = pending_block.success_operations.clone();
                 operations.extend(
                     pending_block
@@ -64,7 +82,10 @@ async fn handle_new_commit_task(
                         .into_iter()
                         .map(|tx| ExecutedOperations::Tx(Box::new(tx))),
                 );
-                save_pending_block(pending_block, applied_updates_req, &pool).await;
+                save_pending_block(pending_block, applied_updates_req, &pool)
+                    .instrument(span)
+                    .await;
+                block_events.ops_executed(block_number, &operations);
             }
         }
     }
@@ -120,7 +141,8 @@ async fn commit_block(
     block_commit_request: BlockCommitRequest,
     applied_updates_request: AppliedUpdatesRequest,
     pool: &ConnectionPool,
-    mempool_req_sender: &mut Sender<MempoolBlocksRequest>,
+    mempool_req_sender: &mut Sender<Traced<MempoolBlocksRequest>>,
+    token_metrics: &mut TokenMetrics,
 ) {
     let start = Instant::now();
     let BlockCommitRequest {
@@ -195,7 +217,9 @@ async fn commit_block(
         .expect("committer must commit the op into db");
 
     mempool_req_sender
-        .send(MempoolBlocksRequest::UpdateNonces(accounts_updated))
+        .send(Traced::new(MempoolBlocksRequest::UpdateNonces(
+            accounts_updated,
+        )))
         .await
         .map_err(|e| vlog::warn!("Failed notify mempool about account updates: {}", e))
         .unwrap_or_default();
@@ -206,9 +230,15 @@ async fn commit_block(
         .expect("Unable to commit DB transaction");
 
     metrics::histogram!("committer.commit_block", start.elapsed());
+
+    token_metrics
+        .update(&mut storage, &op.block)
+        .await
+        .map_err(|e| vlog::warn!("Failed to update token metrics: {}", e))
+        .unwrap_or_default();
 }
 
-async fn poll_for_new_proofs_task(pool: ConnectionPool) {
+async fn poll_for_new_proofs_task(pool: ConnectionPool, mut block_events: BlockEventsNotifier) {
     let mut last_verified_block = {
         let mut storage = pool
             .access_storage()
@@ -267,6 +297,8 @@ async fn poll_for_new_proofs_task(pool: ConnectionPool) {
                     .commit()
                     .await
                     .expect("Failed to commit transaction");
+
+                block_events.block_verified(&op.block);
             } else {
                 break;
             }
@@ -276,14 +308,17 @@ async fn poll_for_new_proofs_task(pool: ConnectionPool) {
 
 #[must_use]
 pub fn run_committer(
-    rx_for_ops: Receiver<CommitRequest>,
-    mempool_req_sender: Sender<MempoolBlocksRequest>,
+    rx_for_ops: Receiver<Traced<CommitRequest>>,
+    mempool_req_sender: Sender<Traced<MempoolBlocksRequest>>,
     pool: ConnectionPool,
+    block_events_subscribers: Vec<Sender<BlockEvent>>,
 ) -> JoinHandle<()> {
+    let block_events = BlockEventsNotifier::new(block_events_subscribers);
     tokio::spawn(handle_new_commit_task(
         rx_for_ops,
         mempool_req_sender,
         pool.clone(),
+        block_events.clone(),
     ));
-    tokio::spawn(poll_for_new_proofs_task(pool))
+    tokio::spawn(poll_for_new_proofs_task(pool, block_events))
 }
@@ -0,0 +1,188 @@
+//! Per-token business activity reported by the committer.
+
+// Built-in uses
+use std::collections::HashMap;
+// External uses
+use num::{BigUint, ToPrimitive};
+// Workspace uses
+use zksync_storage::{QueryResult, StorageProcessor};
+use zksync_types::{block::Block, Token, TokenId, TokenLike, ZkSyncOp, ZkSyncTx};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Activity {
+    Transfer,
+    Withdraw,
+    Fee,
+}
+
+/// Counters are integer, so the amounts are reported in the millionths of the token.
+const NATIVE_UNITS_SCALE: f64 = 1e6;
+/// USD amounts are reported in cents.
+const USD_UNITS_SCALE: f64 = 1e2;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Amounts {
+    native: f64,
+    usd: f64,
+}
+
+/// Reports transferred volume, withdrawn volume and collected fees per token, both in
+/// token units and in USD at the moment of the block commit.
+///
+/// Metrics are exported as counters incremented by the amounts of every committed block,
+/// in the millionths of the token and in the USD cents respectively.
+#[derive(Debug, Default)]
+pub(super) struct TokenMetrics {
+    /// Tokens loaded so far, including the IDs of the unknown tokens,
+    /// so they are not queried for every block.
+    tokens: HashMap<TokenId, Option<Token>>,
+}
+
+fn to_f64(value: &BigUint) -> f64 {
+    value.to_f64().unwrap_or(f64::MAX)
+}
+
+/// Converts the amount into the counter units, the values out of the `u64` range are clamped.
+fn to_counter_units(amount: f64, scale: f64) -> Option<u64> {
+    let units = (amount * scale).round();
+    if (0.0..=u64::MAX as f64).contains(&units) {
+        Some(units as u64)
+    } else {
+        None
+    }
+}
+
+impl TokenMetrics {
+    /// Reports the amounts of the successful transactions of the committed block.
+    pub async fn update(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        block: &Block,
+    ) -> QueryResult<()> {
+        let mut amounts = Vec::new();
+        for exec_op in &block.block_transactions {
+            let exec_tx = match exec_op.get_executed_tx() {
+                Some(exec_tx) if exec_tx.success => exec_tx,
+                _ => continue,
+            };
+
+            match (&exec_tx.signed_tx.tx, &exec_tx.op) {
+                (ZkSyncTx::Transfer(tx), _) => {
+                    amounts.push((tx.token, Activity::Transfer, tx.amount.clone()))
+                }
+                (ZkSyncTx::Withdraw(tx), _) => {
+                    amounts.push((tx.token, Activity::Withdraw, tx.amount.clone()))
+                }
+                (ZkSyncTx::ForcedExit(tx), Some(ZkSyncOp::ForcedExit(op))) => {
+                    if let Some(amount) = &op.withdraw_amount {
+                        amounts.push((tx.token, Activity::Withdraw, amount.0.clone()));
+                    }
+                }
+                _ => {}
+            }
+            if let Some((_, TokenLike::Id(token), _, fee)) = exec_tx.signed_tx.tx.get_fee_info() {
+                amounts.push((token, Activity::Fee, fee));
+            }
+        }
+
+        let mut prices = HashMap::new();
+        let mut block_amounts: HashMap<_, Amounts> = HashMap::new();
+        for (token_id, activity, amount) in amounts {
+            let token = match self.load_token(storage, token_id).await? {
+                Some(token) => token,
+                None => continue,
+            };
+            let usd_price = match prices.get(&token_id) {
+                Some(price) => *price,
+                None => {
+                    let price = storage
+                        .tokens_schema()
+                        .get_historical_ticker_price(token_id)
+                        .await?
+                        .map(|price| {
+                            to_f64(price.usd_price.numer()) / to_f64(price.usd_price.denom())
+                        });
+                    prices.insert(token_id, price);
+                    price
+                }
+            };
+
+            let native = to_f64(&amount) / 10f64.powi(i32::from(token.decimals));
+            let amounts = block_amounts.entry((token.symbol, activity)).or_default();
+            amounts.native += native;
+            // Tokens without the known price are reported in the token units only.
+            amounts.usd += usd_price.map(|price| native * price).unwrap_or_default();
+        }
+
+        for ((symbol, activity), amounts) in block_amounts {
+            Self::report(symbol, activity, amounts);
+        }
+
+        Ok(())
+    }
+
+    async fn load_token(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> QueryResult<Option<Token>> {
+        if let Some(token) = self.tokens.get(&token_id) {
+            return Ok(token.clone());
+        }
+
+        let token = storage
+            .tokens_schema()
+            .get_token(TokenLike::Id(token_id))
+            .await?;
+        self.tokens.insert(token_id, token.clone());
+        Ok(token)
+    }
+
+    fn report(token: String, activity: Activity, amounts: Amounts) {
+        let clamp = |amount: f64, scale: f64| {
+            to_counter_units(amount, scale).unwrap_or_else(|| {
+                vlog::warn!(
+                    "{:?} amount of the token {} is out of the metrics range: {}",
+                    activity,
+                    token,
+                    amount
+                );
+                if amount > 0.0 {
+                    u64::MAX
+                } else {
+                    0
+                }
+            })
+        };
+        let native = clamp(amounts.native, NATIVE_UNITS_SCALE);
+        let usd = clamp(amounts.usd, USD_UNITS_SCALE);
+        match activity {
+            Activity::Transfer => {
+                metrics::counter!("committer.transferred_volume", native, "token" => token.clone());
+                metrics::counter!("committer.transferred_volume_usd", usd, "token" => token);
+            }
+            Activity::Withdraw => {
+                metrics::counter!("committer.withdrawn_volume", native, "token" => token.clone());
+                metrics::counter!("committer.withdrawn_volume_usd", usd, "token" => token);
+            }
+            Activity::Fee => {
+                metrics::counter!("committer.collected_fees", native, "token" => token.clone());
+                metrics::counter!("committer.collected_fees_usd", usd, "token" => token);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_units_range() {
+        assert_eq!(to_counter_units(1.5, NATIVE_UNITS_SCALE), Some(1_500_000));
+        assert_eq!(to_counter_units(0.004, USD_UNITS_SCALE), Some(0));
+        assert_eq!(to_counter_units(f64::MAX, NATIVE_UNITS_SCALE), None);
+        assert_eq!(to_counter_units(-1.0, USD_UNITS_SCALE), None);
+        assert_eq!(to_counter_units(f64::NAN, USD_UNITS_SCALE), None);
+    }
+}
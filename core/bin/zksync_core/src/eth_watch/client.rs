@@ -1,4 +1,8 @@
-use std::{convert::TryFrom, time::Instant};
+use std::{
+    convert::{TryFrom, TryInto},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
 
 use anyhow::format_err;
 use ethabi::Hash;
@@ -8,17 +12,24 @@ use web3::{
     types::{BlockNumber, FilterBuilder, Log},
 };
 
-use zksync_contracts::zksync_contract;
+use zksync_contracts::{governance_contract, zksync_contract};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
-use zksync_types::{ethereum::CompleteWithdrawalsTx, Address, Nonce, PriorityOp, H160};
+use zksync_types::{
+    ethereum::{CompleteWithdrawalsTx, FactAuthEvent, GovernanceEvent},
+    Address, Nonce, PriorityOp, H160,
+};
 
 struct ContractTopics {
     new_priority_request: Hash,
     complete_withdrawals_event: Hash,
+    fact_auth: Hash,
+    new_token: Hash,
+    validator_status_update: Hash,
+    new_governor: Hash,
 }
 
 impl ContractTopics {
-    fn new(zksync_contract: &ethabi::Contract) -> Self {
+    fn new(zksync_contract: &ethabi::Contract, governance_contract: &ethabi::Contract) -> Self {
         Self {
             new_priority_request: zksync_contract
                 .event("NewPriorityRequest")
@@ -29,6 +40,26 @@ impl ContractTopics {
                 .event("PendingWithdrawalsComplete")
                 .expect("main contract abi error")
                 .signature(),
+
+            fact_auth: zksync_contract
+                .event("FactAuth")
+                .expect("main contract abi error")
+                .signature(),
+
+            new_token: governance_contract
+                .event("NewToken")
+                .expect("governance contract abi error")
+                .signature(),
+
+            validator_status_update: governance_contract
+                .event("ValidatorStatusUpdate")
+                .expect("governance contract abi error")
+                .signature(),
+
+            new_governor: governance_contract
+                .event("NewGovernor")
+                .expect("governance contract abi error")
+                .signature(),
         }
     }
 }
@@ -45,6 +76,16 @@ pub trait EthClient {
         from: BlockNumber,
         to: BlockNumber,
     ) -> anyhow::Result<Vec<CompleteWithdrawalsTx>>;
+    async fn get_governance_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<GovernanceEvent>>;
+    async fn get_auth_fact_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<FactAuthEvent>>;
     async fn block_number(&self) -> anyhow::Result<u64>;
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>>;
     async fn get_first_pending_withdrawal_index(&self) -> anyhow::Result<u32>;
@@ -55,37 +96,59 @@ pub struct EthHttpClient {
     client: EthereumGateway,
     topics: ContractTopics,
     zksync_contract_addr: H160,
+    governance_contract_addr: H160,
+    /// Chain ID of the L1 network the events are observed on.
+    chain_id: u64,
+    /// Whether the chain ID of the node was checked since the last failed request.
+    chain_id_checked: AtomicBool,
 }
 
 impl EthHttpClient {
-    pub fn new(client: EthereumGateway, zksync_contract_addr: H160) -> Self {
-        let topics = ContractTopics::new(&zksync_contract());
+    pub fn new(
+        client: EthereumGateway,
+        zksync_contract_addr: H160,
+        governance_contract_addr: H160,
+        chain_id: u64,
+    ) -> Self {
+        let topics = ContractTopics::new(&zksync_contract(), &governance_contract());
         Self {
             client,
             topics,
             zksync_contract_addr,
+            governance_contract_addr,
+            chain_id,
+            chain_id_checked: AtomicBool::new(false),
         }
     }
 
-    async fn get_events<T>(
+    async fn get_logs(
         &self,
+        contract_addr: H160,
         from: BlockNumber,
         to: BlockNumber,
         topics: Vec<Hash>,
-    ) -> anyhow::Result<Vec<T>>
-    where
-        T: TryFrom<Log>,
-        T::Error: Debug,
-    {
+    ) -> anyhow::Result<Vec<Log>> {
         let filter = FilterBuilder::default()
-            .address(vec![self.zksync_contract_addr])
+            .address(vec![contract_addr])
             .from_block(from)
             .to_block(to)
             .topics(Some(topics), None, None, None)
             .build();
 
-        self.client
-            .logs(filter)
+        self.client.logs(filter).await
+    }
+
+    async fn get_events<T>(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        topics: Vec<Hash>,
+    ) -> anyhow::Result<Vec<T>>
+    where
+        T: TryFrom<Log>,
+        T::Error: Debug,
+    {
+        self.get_logs(self.zksync_contract_addr, from, to, topics)
             .await?
             .into_iter()
             .map(|event| {
@@ -130,8 +193,74 @@ impl EthClient for EthHttpClient {
         result
     }
 
+    async fn get_governance_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<GovernanceEvent>> {
+        let start = Instant::now();
+
+        let topics = vec![
+            self.topics.new_token,
+            self.topics.validator_status_update,
+            self.topics.new_governor,
+        ];
+        let logs = self
+            .get_logs(self.governance_contract_addr, from, to, topics)
+            .await?;
+
+        let mut events = Vec::with_capacity(logs.len());
+        for log in logs {
+            let topic = log
+                .topics
+                .first()
+                .copied()
+                .ok_or_else(|| format_err!("Governance event log without topics: {:?}", log))?;
+
+            let event = if topic == self.topics.new_token {
+                GovernanceEvent::NewToken(log.try_into()?)
+            } else if topic == self.topics.validator_status_update {
+                GovernanceEvent::ValidatorStatusUpdate(log.try_into()?)
+            } else if topic == self.topics.new_governor {
+                GovernanceEvent::NewGovernor(log.try_into()?)
+            } else {
+                continue;
+            };
+            events.push(event);
+        }
+
+        metrics::histogram!("eth_watcher.get_governance_events", start.elapsed());
+        Ok(events)
+    }
+
+    async fn get_auth_fact_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> anyhow::Result<Vec<FactAuthEvent>> {
+        let start = Instant::now();
+
+        let result = self.get_events(from, to, vec![self.topics.fact_auth]).await;
+        metrics::histogram!("eth_watcher.get_auth_fact_events", start.elapsed());
+        result
+    }
+
     async fn block_number(&self) -> anyhow::Result<u64> {
-        Ok(self.client.block_number().await?.as_u64())
+        // Every poll of the node starts with the block number, so the events of the node
+        // connected to another network are never processed. The chain ID is checked on
+        // the first poll and after the node failed to respond, e.g. was restarted or replaced.
+        if !self.chain_id_checked.load(Ordering::SeqCst) {
+            self.client.check_chain_id(self.chain_id).await?;
+            self.chain_id_checked.store(true, Ordering::SeqCst);
+        }
+
+        match self.client.block_number().await {
+            Ok(block_number) => Ok(block_number.as_u64()),
+            Err(err) => {
+                self.chain_id_checked.store(false, Ordering::SeqCst);
+                Err(err)
+            }
+        }
     }
 
     async fn get_auth_fact(&self, address: Address, nonce: Nonce) -> anyhow::Result<Vec<u8>> {
@@ -1,5 +1,9 @@
 //! Ethereum watcher polls the Ethereum node for new events
 //! such as PriorityQueue events or NewToken events.
+//! Events emitted by the governance contract (new tokens, validator and governor changes)
+//! are applied to the storage automatically.
+//! Public key hashes authorized onchain for `ChangePubKey` operations (`FactAuth` events)
+//! are cached in the storage, so the authorization can be checked without querying the contract.
 //! New events are accepted to the zkSync network once they have the sufficient amount of confirmations.
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
@@ -23,7 +27,7 @@ use web3::types::{Address, BlockNumber};
 // Workspace deps
 use zksync_crypto::params::PRIORITY_EXPIRATION;
 use zksync_storage::ConnectionPool;
-use zksync_types::{Nonce, PriorityOp, PubKeyHash, ZkSyncPriorityOp};
+use zksync_types::{Nonce, PriorityOp, PubKeyHash, QueuedPriorityOp, SerialId, ZkSyncPriorityOp};
 
 // Local deps
 use self::{
@@ -66,6 +70,18 @@ enum WatcherMode {
     Backoff(Instant),
 }
 
+/// Priority operations selected from the queue by `EthWatchRequest::GetPriorityQueue`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriorityQueueFilter {
+    /// The whole queue.
+    All,
+    /// Operations initiated by the address.
+    InitiatedBy(Address),
+    /// Operations moving funds to the address, i.e. deposits to this address and
+    /// full exits to this Ethereum address.
+    IncomingTo(Address),
+}
+
 #[derive(Debug)]
 pub enum EthWatchRequest {
     PollETHNode,
@@ -78,6 +94,8 @@ pub enum EthWatchRequest {
     GetPriorityQueueOps {
         op_start_id: u64,
         max_chunks: usize,
+        /// Block the operations are requested for.
+        block_number: zksync_types::BlockNumber,
         resp: oneshot::Sender<Vec<PriorityOp>>,
     },
     GetUnconfirmedDeposits {
@@ -95,6 +113,10 @@ pub enum EthWatchRequest {
     GetPendingWithdrawalsQueueIndex {
         resp: oneshot::Sender<anyhow::Result<u32>>,
     },
+    GetPriorityQueue {
+        filter: PriorityQueueFilter,
+        resp: oneshot::Sender<Vec<QueuedPriorityOp>>,
+    },
 }
 
 pub struct EthWatch<W: EthClient, S: Storage> {
@@ -103,17 +125,33 @@ pub struct EthWatch<W: EthClient, S: Storage> {
     eth_state: ETHState,
     /// All ethereum events are accepted after sufficient confirmations to eliminate risk of block reorg.
     number_of_confirmations_for_event: u64,
+    /// Serial ID of the first priority operation not yet taken into the block, as
+    /// loaded on start and then reported by the last priority operations request.
+    first_unprocessed_serial_id: SerialId,
+    /// Block the first unprocessed priority operation is expected to be included into, as
+    /// loaded on start and then reported by the last priority operations request.
+    pending_block_number: zksync_types::BlockNumber,
+    /// Maximum number of chunks of the priority operations the block proposer takes into a single block.
+    max_block_chunks: usize,
     mode: WatcherMode,
 }
 
 impl<W: EthClient, S: Storage> EthWatch<W, S> {
-    pub fn new(client: W, storage: S, number_of_confirmations_for_event: u64) -> Self {
+    pub fn new(
+        client: W,
+        storage: S,
+        number_of_confirmations_for_event: u64,
+        max_block_chunks: usize,
+    ) -> Self {
         Self {
             client,
             storage,
             eth_state: ETHState::default(),
             mode: WatcherMode::Working,
             number_of_confirmations_for_event,
+            first_unprocessed_serial_id: 0,
+            pending_block_number: zksync_types::BlockNumber(1),
+            max_block_chunks,
         }
     }
 
@@ -161,6 +199,43 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
         Ok(())
     }
 
+    async fn update_governance(
+        &mut self,
+        previous_block_with_accepted_events: u64,
+        new_block_with_accepted_events: u64,
+    ) -> anyhow::Result<()> {
+        // Get new events emitted by the governance contract (e.g. new tokens added)
+        let governance_events = self
+            .client
+            .get_governance_events(
+                BlockNumber::Number(previous_block_with_accepted_events.into()),
+                BlockNumber::Number(new_block_with_accepted_events.into()),
+            )
+            .await?;
+
+        self.storage
+            .store_governance_events(governance_events)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_auth_facts(
+        &mut self,
+        previous_block_with_accepted_events: u64,
+        new_block_with_accepted_events: u64,
+    ) -> anyhow::Result<()> {
+        let auth_facts = self
+            .client
+            .get_auth_fact_events(
+                BlockNumber::Number(previous_block_with_accepted_events.into()),
+                BlockNumber::Number(new_block_with_accepted_events.into()),
+            )
+            .await?;
+
+        self.storage.store_auth_facts(auth_facts).await?;
+        Ok(())
+    }
+
     async fn process_new_blocks(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
         debug_assert!(self.eth_state.last_ethereum_block() < last_ethereum_block);
 
@@ -188,6 +263,10 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
     }
 
     async fn restore_state_from_eth(&mut self, last_ethereum_block: u64) -> anyhow::Result<()> {
+        // Operations executed before the restart are not requested by the state keeper anymore.
+        self.first_unprocessed_serial_id = self.storage.load_next_priority_op_serial_id().await?;
+        self.pending_block_number = self.storage.load_pending_block_number().await?;
+
         let (unconfirmed_queue, priority_queue) = self
             .update_eth_state(last_ethereum_block, PRIORITY_EXPIRATION)
             .await?;
@@ -215,6 +294,18 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
         )
         .await?;
 
+        self.update_governance(
+            previous_block_with_accepted_events,
+            new_block_with_accepted_events,
+        )
+        .await?;
+
+        self.update_auth_facts(
+            previous_block_with_accepted_events,
+            new_block_with_accepted_events,
+        )
+        .await?;
+
         let unconfirmed_queue = self.get_unconfirmed_ops(current_ethereum_block).await?;
         let priority_queue = self
             .client
@@ -255,6 +346,12 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
         nonce: Nonce,
         pub_key_hash: &PubKeyHash,
     ) -> anyhow::Result<bool> {
+        // Facts are cached once they get enough confirmations, the contract is queried only
+        // for the recently authorized ones or the ones set before the watcher has started.
+        if let Some(authorized_pub_key_hash) = self.storage.load_auth_fact(address, nonce).await? {
+            return Ok(&authorized_pub_key_hash == pub_key_hash);
+        }
+
         let auth_fact = self.client.get_auth_fact(address, nonce).await?;
         Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
     }
@@ -306,6 +403,91 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
             .collect()
     }
 
+    fn queued_op(&self, op: &PriorityOp, confirmed: bool) -> QueuedPriorityOp {
+        QueuedPriorityOp {
+            op: op.clone(),
+            confirmed,
+            confirmation_block: op.eth_block + self.number_of_confirmations_for_event,
+            confirmations: self
+                .eth_state
+                .last_ethereum_block()
+                .saturating_sub(op.eth_block),
+            // Positions are set once the whole queue is assembled.
+            queue_position: 0,
+            expected_block: self.pending_block_number,
+            processing_position: 0,
+        }
+    }
+
+    /// Returns all the priority operations that are known to the Ethereum watcher, but not yet
+    /// included into the zkSync block, ordered by the serial ID.
+    fn get_whole_priority_queue(&self) -> Vec<QueuedPriorityOp> {
+        let mut confirmed_ops: Vec<_> = self
+            .eth_state
+            .priority_queue()
+            .values()
+            .map(|op| op.as_ref())
+            .filter(|op| op.serial_id >= self.first_unprocessed_serial_id)
+            .map(|op| self.queued_op(op, true))
+            .collect();
+        confirmed_ops.sort_by_key(|queued| queued.op.serial_id);
+
+        let unconfirmed_ops = self
+            .eth_state
+            .unconfirmed_queue()
+            .iter()
+            .map(|op| self.queued_op(op, false));
+
+        // Operations are taken into blocks in the queue order, the block proposer fills every block
+        // with as many priority operations as fit into the largest block.
+        let mut expected_block = self.pending_block_number;
+        let mut block_chunks = 0;
+        let mut processing_position = 0;
+        confirmed_ops
+            .into_iter()
+            .chain(unconfirmed_ops)
+            .enumerate()
+            .map(|(position, mut queued)| {
+                let chunks = queued.op.data.chunks();
+                if block_chunks + chunks > self.max_block_chunks && block_chunks > 0 {
+                    expected_block = expected_block + 1;
+                    block_chunks = 0;
+                    processing_position = 0;
+                }
+                block_chunks += chunks;
+
+                queued.queue_position = position;
+                queued.expected_block = expected_block;
+                queued.processing_position = processing_position;
+                processing_position += 1;
+                queued
+            })
+            .collect()
+    }
+
+    /// Returns the priority operations that are known to the Ethereum watcher, but not yet
+    /// included into the zkSync block, ordered by the serial ID.
+    /// Positions of the returned operations are relative to the whole queue.
+    fn get_priority_queue(&self, filter: PriorityQueueFilter) -> Vec<QueuedPriorityOp> {
+        self.get_whole_priority_queue()
+            .into_iter()
+            .filter(|queued| match (filter, &queued.op.data) {
+                (PriorityQueueFilter::All, _) => true,
+                (PriorityQueueFilter::InitiatedBy(address), ZkSyncPriorityOp::Deposit(deposit)) => {
+                    deposit.from == address
+                }
+                (PriorityQueueFilter::IncomingTo(address), ZkSyncPriorityOp::Deposit(deposit)) => {
+                    deposit.to == address
+                }
+                (
+                    PriorityQueueFilter::InitiatedBy(address)
+                    | PriorityQueueFilter::IncomingTo(address),
+                    ZkSyncPriorityOp::FullExit(full_exit),
+                ) => full_exit.eth_address == address,
+            })
+            .collect()
+    }
+
     async fn poll_eth_node(&mut self) -> anyhow::Result<()> {
         let start = Instant::now();
         let last_block_number = self.client.block_number().await?;
@@ -405,8 +587,11 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
                 EthWatchRequest::GetPriorityQueueOps {
                     op_start_id,
                     max_chunks,
+                    block_number,
                     resp,
                 } => {
+                    self.first_unprocessed_serial_id = op_start_id;
+                    self.pending_block_number = block_number;
                     resp.send(self.get_priority_requests(op_start_id, max_chunks))
                         .unwrap_or_default();
                 }
@@ -441,6 +626,10 @@ impl<W: EthClient, S: Storage> EthWatch<W, S> {
                     resp.send(pending_withdrawals_queue_index)
                         .unwrap_or_default();
                 }
+                EthWatchRequest::GetPriorityQueue { filter, resp } => {
+                    resp.send(self.get_priority_queue(filter))
+                        .unwrap_or_default();
+                }
             }
         }
     }
@@ -454,14 +643,26 @@ pub fn start_eth_watch(
     db_pool: ConnectionPool,
 ) -> JoinHandle<()> {
     let client = EthereumGateway::from_config(&config_options);
-    let eth_client = EthHttpClient::new(client, config_options.contracts.contract_addr);
+    let eth_client = EthHttpClient::new(
+        client,
+        config_options.contracts.contract_addr,
+        config_options.contracts.governance_addr,
+        config_options.eth_client.chain_id,
+    );
 
     let storage = DBStorage::new(db_pool);
+    let max_block_chunks = *config_options
+        .chain
+        .block_chunk_sizes()
+        .iter()
+        .max()
+        .expect("failed to find max block chunks size");
 
     let eth_watch = EthWatch::new(
         eth_client,
         storage,
         config_options.eth_watch.confirmations_for_eth_event,
+        max_block_chunks,
     );
 
     tokio::spawn(eth_watch.run(eth_req_receiver));
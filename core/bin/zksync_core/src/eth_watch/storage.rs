@@ -1,7 +1,10 @@
 use anyhow::format_err;
 
 use zksync_storage::ConnectionPool;
-use zksync_types::ethereum::CompleteWithdrawalsTx;
+use zksync_types::{
+    ethereum::{CompleteWithdrawalsTx, FactAuthEvent, GovernanceEvent, NewTokenEvent},
+    Address, BlockNumber, Nonce, PubKeyHash, Token, TokenLike,
+};
 
 #[async_trait::async_trait]
 pub trait Storage {
@@ -9,6 +12,25 @@ pub trait Storage {
         &mut self,
         complete_withdrawals_txs: Vec<CompleteWithdrawalsTx>,
     ) -> anyhow::Result<()>;
+
+    async fn store_governance_events(
+        &mut self,
+        governance_events: Vec<GovernanceEvent>,
+    ) -> anyhow::Result<()>;
+
+    async fn store_auth_facts(&mut self, auth_facts: Vec<FactAuthEvent>) -> anyhow::Result<()>;
+
+    async fn load_auth_fact(
+        &self,
+        address: Address,
+        nonce: Nonce,
+    ) -> anyhow::Result<Option<PubKeyHash>>;
+
+    /// Loads the serial ID of the first priority operation that is not executed yet.
+    async fn load_next_priority_op_serial_id(&self) -> anyhow::Result<u64>;
+
+    /// Loads the number of the block that is not committed yet.
+    async fn load_pending_block_number(&self) -> anyhow::Result<BlockNumber>;
 }
 
 pub struct DBStorage {
@@ -44,4 +66,122 @@ impl Storage for DBStorage {
 
         Ok(())
     }
+
+    async fn store_governance_events(
+        &mut self,
+        governance_events: Vec<GovernanceEvent>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        let mut transaction = storage.start_transaction().await?;
+        for event in governance_events {
+            match event {
+                GovernanceEvent::NewToken(NewTokenEvent { address, id }) => {
+                    let stored_token = transaction
+                        .tokens_schema()
+                        .get_token(TokenLike::Id(id))
+                        .await?;
+                    // Tokens may be already added manually (e.g. genesis tokens with the
+                    // proper symbols), so we don't want to override them.
+                    if stored_token.is_some() {
+                        continue;
+                    }
+
+                    // The only way to know decimals is to query ERC20 contract 'decimals' function
+                    // that may or may not (in most cases, may not) be there, so we just assume it to be 18
+                    let decimals = 18;
+                    let token = Token::new(id, address, &format!("ERC20-{}", *id), decimals);
+                    vlog::info!("Adding new token from the governance contract: {:?}", token);
+                    transaction.tokens_schema().store_token(token).await?;
+                }
+                GovernanceEvent::ValidatorStatusUpdate(event) => {
+                    vlog::info!(
+                        "Validator {:?} status changed, active: {}",
+                        event.address,
+                        event.is_active
+                    );
+                    transaction
+                        .governance_schema()
+                        .set_validator_status(event.address, event.is_active)
+                        .await?;
+                }
+                GovernanceEvent::NewGovernor(event) => {
+                    vlog::info!("Network governor changed to {:?}", event.address);
+                    transaction
+                        .governance_schema()
+                        .set_network_governor(event.address)
+                        .await?;
+                }
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn store_auth_facts(&mut self, auth_facts: Vec<FactAuthEvent>) -> anyhow::Result<()> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        let mut transaction = storage.start_transaction().await?;
+        for fact in auth_facts {
+            transaction
+                .chain()
+                .account_schema()
+                .store_auth_fact(fact.address, fact.nonce, &fact.pub_key_hash)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn load_auth_fact(
+        &self,
+        address: Address,
+        nonce: Nonce,
+    ) -> anyhow::Result<Option<PubKeyHash>> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        storage
+            .chain()
+            .account_schema()
+            .load_auth_fact(address, nonce)
+            .await
+    }
+
+    async fn load_next_priority_op_serial_id(&self) -> anyhow::Result<u64> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        storage
+            .chain()
+            .operations_schema()
+            .next_priority_op_serial_id()
+            .await
+    }
+
+    async fn load_pending_block_number(&self) -> anyhow::Result<BlockNumber> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|e| format_err!("Can't access storage: {}", e))?;
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        Ok(last_committed_block + 1)
+    }
 }
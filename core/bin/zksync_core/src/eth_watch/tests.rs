@@ -4,22 +4,30 @@ use std::collections::HashMap;
 use web3::types::{Address, BlockNumber};
 
 use zksync_types::{
-    ethereum::CompleteWithdrawalsTx, AccountId, Deposit, FullExit, Nonce, PriorityOp, TokenId,
-    ZkSyncPriorityOp,
+    ethereum::{CompleteWithdrawalsTx, FactAuthEvent, GovernanceEvent, NewTokenEvent},
+    AccountId, Deposit, FullExit, Nonce, PriorityOp, PubKeyHash, TokenId, ZkSyncPriorityOp,
 };
 
-use crate::eth_watch::{client::EthClient, storage::Storage, EthWatch};
+use crate::eth_watch::{client::EthClient, storage::Storage, EthWatch, PriorityQueueFilter};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 struct FakeStorage {
     withdrawal_txs: Vec<CompleteWithdrawalsTx>,
+    governance_events: Vec<GovernanceEvent>,
+    auth_facts: Vec<FactAuthEvent>,
+    next_priority_op_serial_id: u64,
+    pending_block_number: zksync_types::BlockNumber,
 }
 
 impl FakeStorage {
     fn new() -> Self {
         Self {
             withdrawal_txs: vec![],
+            governance_events: vec![],
+            auth_facts: vec![],
+            next_priority_op_serial_id: 0,
+            pending_block_number: zksync_types::BlockNumber(1),
         }
     }
 }
@@ -33,11 +41,46 @@ impl Storage for FakeStorage {
         self.withdrawal_txs.extend(complete_withdrawals_txs);
         Ok(())
     }
+
+    async fn store_governance_events(
+        &mut self,
+        governance_events: Vec<GovernanceEvent>,
+    ) -> anyhow::Result<()> {
+        self.governance_events.extend(governance_events);
+        Ok(())
+    }
+
+    async fn store_auth_facts(&mut self, auth_facts: Vec<FactAuthEvent>) -> anyhow::Result<()> {
+        self.auth_facts.extend(auth_facts);
+        Ok(())
+    }
+
+    async fn load_auth_fact(
+        &self,
+        address: Address,
+        nonce: Nonce,
+    ) -> anyhow::Result<Option<PubKeyHash>> {
+        Ok(self
+            .auth_facts
+            .iter()
+            .find(|fact| fact.address == address && fact.nonce == nonce)
+            .map(|fact| fact.pub_key_hash))
+    }
+
+    async fn load_next_priority_op_serial_id(&self) -> anyhow::Result<u64> {
+        Ok(self.next_priority_op_serial_id)
+    }
+
+    async fn load_pending_block_number(&self) -> anyhow::Result<zksync_types::BlockNumber> {
+        Ok(self.pending_block_number)
+    }
 }
 
 struct FakeEthClientData {
     priority_ops: HashMap<u64, Vec<PriorityOp>>,
     withdrawals: HashMap<u64, Vec<CompleteWithdrawalsTx>>,
+    governance_events: HashMap<u64, Vec<GovernanceEvent>>,
+    auth_facts: HashMap<u64, Vec<FactAuthEvent>>,
     last_block_number: u64,
 }
 
@@ -46,10 +89,28 @@ impl FakeEthClientData {
         Self {
             priority_ops: Default::default(),
             withdrawals: Default::default(),
+            governance_events: Default::default(),
+            auth_facts: Default::default(),
             last_block_number: 0,
         }
     }
 
+    fn add_governance_events(&mut self, eth_block: u64, events: &[GovernanceEvent]) {
+        self.last_block_number = max(eth_block, self.last_block_number);
+        self.governance_events
+            .entry(eth_block)
+            .or_insert_with(Vec::new)
+            .extend_from_slice(events);
+    }
+
+    fn add_auth_facts(&mut self, eth_block: u64, facts: &[FactAuthEvent]) {
+        self.last_block_number = max(eth_block, self.last_block_number);
+        self.auth_facts
+            .entry(eth_block)
+            .or_insert_with(Vec::new)
+            .extend_from_slice(facts);
+    }
+
     fn add_operations(&mut self, ops: &[PriorityOp]) {
         for op in ops {
             self.last_block_number = max(op.eth_block, self.last_block_number);
@@ -77,6 +138,17 @@ impl FakeEthClient {
         self.inner.write().await.add_operations(ops);
     }
 
+    async fn add_governance_events(&mut self, eth_block: u64, events: &[GovernanceEvent]) {
+        self.inner
+            .write()
+            .await
+            .add_governance_events(eth_block, events);
+    }
+
+    async fn add_auth_facts(&mut self, eth_block: u64, facts: &[FactAuthEvent]) {
+        self.inner.write().await.add_auth_facts(eth_block, facts);
+    }
+
     async fn block_to_number(&self, block: &BlockNumber) -> u64 {
         match block {
             BlockNumber::Latest => self.inner.read().await.last_block_number,
@@ -121,6 +193,38 @@ impl EthClient for FakeEthClient {
         Ok(withdrawals)
     }
 
+    async fn get_governance_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<GovernanceEvent>, anyhow::Error> {
+        let from = self.block_to_number(&from).await;
+        let to = self.block_to_number(&to).await;
+        let mut events = vec![];
+        for number in from..=to {
+            if let Some(block_events) = self.inner.read().await.governance_events.get(&number) {
+                events.extend_from_slice(block_events);
+            }
+        }
+        Ok(events)
+    }
+
+    async fn get_auth_fact_events(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<FactAuthEvent>, anyhow::Error> {
+        let from = self.block_to_number(&from).await;
+        let to = self.block_to_number(&to).await;
+        let mut facts = vec![];
+        for number in from..=to {
+            if let Some(block_facts) = self.inner.read().await.auth_facts.get(&number) {
+                facts.extend_from_slice(block_facts);
+            }
+        }
+        Ok(facts)
+    }
+
     async fn block_number(&self) -> Result<u64, anyhow::Error> {
         Ok(self.inner.read().await.last_block_number)
     }
@@ -144,7 +248,8 @@ impl EthClient for FakeEthClient {
 
 fn create_watcher<T: EthClient>(client: T) -> EthWatch<T, FakeStorage> {
     let storage = FakeStorage::new();
-    EthWatch::new(client, storage, 1)
+    // Two deposits fit into the block.
+    EthWatch::new(client, storage, 1, 12)
 }
 
 #[tokio::test]
@@ -409,3 +514,164 @@ async fn test_restore_and_poll_time_lag() {
     priority_queues.get(&0).unwrap();
     priority_queues.get(&1).unwrap();
 }
+
+#[tokio::test]
+async fn test_priority_queue() {
+    let mut client = FakeEthClient::new();
+    let from_addr = [1u8; 20].into();
+
+    client
+        .add_operations(&[
+            PriorityOp {
+                serial_id: 0,
+                data: ZkSyncPriorityOp::Deposit(Deposit {
+                    from: from_addr,
+                    token: TokenId(0),
+                    amount: Default::default(),
+                    to: Default::default(),
+                }),
+                deadline_block: 0,
+                eth_hash: [2; 32].into(),
+                eth_block: 2,
+            },
+            PriorityOp {
+                serial_id: 1,
+                data: ZkSyncPriorityOp::Deposit(Deposit {
+                    from: Default::default(),
+                    token: TokenId(0),
+                    amount: Default::default(),
+                    to: Default::default(),
+                }),
+                deadline_block: 0,
+                eth_hash: [3; 32].into(),
+                eth_block: 3,
+            },
+            PriorityOp {
+                serial_id: 2,
+                data: ZkSyncPriorityOp::FullExit(FullExit {
+                    account_id: AccountId(1),
+                    eth_address: from_addr,
+                    token: TokenId(0),
+                }),
+                deadline_block: 0,
+                eth_hash: [4; 32].into(),
+                eth_block: 4,
+            },
+        ])
+        .await;
+
+    let mut watcher = create_watcher(client);
+    watcher.poll_eth_node().await.unwrap();
+
+    let queue = watcher.get_priority_queue(PriorityQueueFilter::All);
+    let serial_ids: Vec<_> = queue.iter().map(|queued| queued.op.serial_id).collect();
+    assert_eq!(serial_ids, vec![0, 1, 2]);
+    assert!(queue[0].confirmed);
+    assert!(queue[1].confirmed);
+    assert!(!queue[2].confirmed);
+    assert_eq!(queue[2].confirmation_block, 5);
+    let confirmations: Vec<_> = queue.iter().map(|queued| queued.confirmations).collect();
+    assert_eq!(confirmations, vec![2, 1, 0]);
+    // Operations that don't fit into the pending block are expected in the next ones.
+    let expected_blocks: Vec<_> = queue.iter().map(|queued| *queued.expected_block).collect();
+    assert_eq!(expected_blocks, vec![1, 1, 2]);
+    let processing_positions: Vec<_> = queue
+        .iter()
+        .map(|queued| queued.processing_position)
+        .collect();
+    assert_eq!(processing_positions, vec![0, 1, 0]);
+
+    // Check filtering by the initiator address.
+    let queue = watcher.get_priority_queue(PriorityQueueFilter::InitiatedBy(from_addr));
+    let serial_ids: Vec<_> = queue.iter().map(|queued| queued.op.serial_id).collect();
+    assert_eq!(serial_ids, vec![0, 2]);
+    // Position is calculated relative to the whole queue.
+    assert_eq!(queue[1].queue_position, 2);
+
+    // Check filtering by the recipient address.
+    let queue = watcher.get_priority_queue(PriorityQueueFilter::IncomingTo(from_addr));
+    let serial_ids: Vec<_> = queue.iter().map(|queued| queued.op.serial_id).collect();
+    assert_eq!(serial_ids, vec![2]);
+    let queue = watcher.get_priority_queue(PriorityQueueFilter::IncomingTo(Default::default()));
+    let serial_ids: Vec<_> = queue.iter().map(|queued| queued.op.serial_id).collect();
+    assert_eq!(serial_ids, vec![0, 1]);
+
+    // Operations that were already taken into blocks must not be returned,
+    // including the ones executed before the restart.
+    watcher.storage.next_priority_op_serial_id = 1;
+    watcher.storage.pending_block_number = zksync_types::BlockNumber(5);
+    watcher.restore_state_from_eth(5).await.unwrap();
+    let queue = watcher.get_priority_queue(PriorityQueueFilter::All);
+    let serial_ids: Vec<_> = queue.iter().map(|queued| queued.op.serial_id).collect();
+    assert_eq!(serial_ids, vec![1, 2]);
+    assert_eq!(queue[0].queue_position, 0);
+    let expected_blocks: Vec<_> = queue.iter().map(|queued| *queued.expected_block).collect();
+    assert_eq!(expected_blocks, vec![5, 5]);
+    assert_eq!(queue[1].processing_position, 1);
+}
+
+/// Checks that governance events are passed to the storage only after they get enough confirmations.
+#[tokio::test]
+async fn test_governance_events() {
+    let mut client = FakeEthClient::new();
+    let new_token = GovernanceEvent::NewToken(NewTokenEvent {
+        address: [1u8; 20].into(),
+        id: TokenId(1),
+    });
+    client.add_governance_events(3, &[new_token.clone()]).await;
+    client
+        .add_governance_events(
+            5,
+            &[GovernanceEvent::NewToken(NewTokenEvent {
+                address: [2u8; 20].into(),
+                id: TokenId(2),
+            })],
+        )
+        .await;
+
+    let mut watcher = create_watcher(client);
+    watcher.poll_eth_node().await.unwrap();
+    assert_eq!(watcher.eth_state.last_ethereum_block(), 5);
+    // The event from the 5th block is not confirmed yet.
+    assert_eq!(watcher.storage.governance_events, vec![new_token]);
+}
+
+/// Checks that the confirmed onchain authorization facts are cached, and the `ChangePubKey`
+/// authorization is checked against the cache without querying the contract.
+#[tokio::test]
+async fn test_auth_facts() {
+    let mut client = FakeEthClient::new();
+    let address: Address = [1u8; 20].into();
+    let pub_key_hash = PubKeyHash::from_bytes(&[3u8; 20]).unwrap();
+    let fact = FactAuthEvent {
+        address,
+        nonce: Nonce(0),
+        pub_key_hash,
+    };
+    client.add_auth_facts(3, &[fact.clone()]).await;
+    client
+        .add_auth_facts(
+            5,
+            &[FactAuthEvent {
+                address,
+                nonce: Nonce(1),
+                pub_key_hash,
+            }],
+        )
+        .await;
+
+    let mut watcher = create_watcher(client);
+    watcher.poll_eth_node().await.unwrap();
+    // The fact from the 5th block is not confirmed yet.
+    assert_eq!(watcher.storage.auth_facts, vec![fact]);
+
+    // `FakeEthClient::get_auth_fact` is unreachable, so the cached fact is used.
+    assert!(watcher
+        .is_new_pubkey_hash_authorized(address, Nonce(0), &pub_key_hash)
+        .await
+        .unwrap());
+    assert!(!watcher
+        .is_new_pubkey_hash_authorized(address, Nonce(0), &PubKeyHash::zero())
+        .await
+        .unwrap());
+}
@@ -0,0 +1,135 @@
+//! Events published to the message broker, every event is encoded as a JSON object
+//! with the `type` field and the `sequence` number of the event within its topic.
+
+// External uses
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_crypto::{serialization::FrSerde, Fr};
+use zksync_types::{block::ExecutedOperations, AccountId, BlockNumber};
+// Local uses
+use crate::block_events::{operation_hash, BlockEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChainEvent {
+    #[serde(rename_all = "camelCase")]
+    BlockCommitted {
+        block_number: BlockNumber,
+        #[serde(with = "FrSerde")]
+        new_state_root: Fr,
+        fee_account: AccountId,
+        /// Number of the block chunks.
+        block_size: usize,
+        tx_hashes: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    BlockVerified {
+        block_number: BlockNumber,
+        tx_hashes: Vec<String>,
+    },
+    /// Transaction or priority operation was executed, but its block is not committed yet.
+    #[serde(rename_all = "camelCase")]
+    TxExecuted {
+        /// Hash of the transaction with the `sync-tx:` prefix, or the hash of the Ethereum
+        /// transaction which has created the priority operation.
+        tx_hash: String,
+        block_number: BlockNumber,
+        success: bool,
+        fail_reason: Option<String>,
+        operation: ExecutedOperations,
+    },
+    /// The publisher lagged behind the server, and the given amount of the preceding events
+    /// was not published. The consumer should reload the missed data from the API.
+    #[serde(rename_all = "camelCase")]
+    EventsDropped { count: usize },
+}
+
+/// Event along with its sequence number within the topic. Numbers start from zero once
+/// the server is started, and a gap in the numbers means that the consumer missed the events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedEvent<'a> {
+    pub sequence: u64,
+    #[serde(flatten)]
+    pub event: &'a ChainEvent,
+}
+
+impl ChainEvent {
+    /// Returns the events to be published for the block event of the committer.
+    pub fn from_block_event(event: BlockEvent) -> Vec<Self> {
+        match event {
+            BlockEvent::OpsExecuted {
+                block_number,
+                operations,
+            } => operations
+                .into_iter()
+                .map(|operation| {
+                    let (success, fail_reason) = match &operation {
+                        ExecutedOperations::Tx(tx) => (tx.success, tx.fail_reason.clone()),
+                        ExecutedOperations::PriorityOp(_) => (true, None),
+                    };
+                    Self::TxExecuted {
+                        tx_hash: operation_hash(&operation),
+                        block_number,
+                        success,
+                        fail_reason,
+                        operation,
+                    }
+                })
+                .collect(),
+            BlockEvent::BlockCommitted { block } => vec![Self::BlockCommitted {
+                block_number: block.block_number,
+                new_state_root: block.new_root_hash,
+                fee_account: block.fee_account,
+                block_size: block.block_chunks_size,
+                tx_hashes: block
+                    .block_transactions
+                    .iter()
+                    .map(operation_hash)
+                    .collect(),
+            }],
+            BlockEvent::BlockVerified { block } => vec![Self::BlockVerified {
+                block_number: block.block_number,
+                tx_hashes: block
+                    .block_transactions
+                    .iter()
+                    .map(operation_hash)
+                    .collect(),
+            }],
+            BlockEvent::EventsDropped { count } => vec![Self::EventsDropped { count }],
+        }
+    }
+
+    /// Kind of the event.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BlockCommitted { .. } => "block_committed",
+            Self::BlockVerified { .. } => "block_verified",
+            Self::TxExecuted { .. } => "tx_executed",
+            Self::EventsDropped { .. } => "events_dropped",
+        }
+    }
+
+    /// Kinds of the topics the event is published to, used as suffixes of the topic names.
+    /// The notice about the dropped events is published to every topic, so all the consumers
+    /// are aware of it.
+    pub fn topic_kinds(&self) -> &'static [&'static str] {
+        match self {
+            Self::BlockCommitted { .. } => &["block_committed"],
+            Self::BlockVerified { .. } => &["block_verified"],
+            Self::TxExecuted { .. } => &["tx_executed"],
+            Self::EventsDropped { .. } => &["block_committed", "block_verified", "tx_executed"],
+        }
+    }
+
+    /// Key of the event, events with the same key keep their order within the Kafka topic.
+    pub fn key(&self) -> String {
+        let block_number = match self {
+            Self::BlockCommitted { block_number, .. }
+            | Self::BlockVerified { block_number, .. }
+            | Self::TxExecuted { block_number, .. } => block_number,
+            Self::EventsDropped { .. } => return self.kind().to_string(),
+        };
+        block_number.to_string()
+    }
+}
@@ -0,0 +1,45 @@
+//! Kafka backend of the event publisher.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use rdkafka::{
+    producer::{FutureProducer, FutureRecord},
+    ClientConfig,
+};
+// Workspace uses
+use zksync_config::EventPublisherConfig;
+// Local uses
+use super::EventSink;
+
+pub struct KafkaSink {
+    producer: FutureProducer,
+    timeout: Duration,
+}
+
+impl KafkaSink {
+    /// Creates the producer, the connection to the brokers is established lazily.
+    pub fn new(config: &EventPublisherConfig) -> anyhow::Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &config.servers.join(","))
+            .set("message.timeout.ms", &config.publish_timeout.to_string())
+            .create()?;
+
+        Ok(Self {
+            producer,
+            timeout: config.publish_timeout(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(&mut self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()> {
+        let record = FutureRecord::to(topic).key(key).payload(payload);
+        self.producer
+            .send(record, self.timeout)
+            .await
+            .map_err(|(err, _)| err)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+//! Publisher of the block and transaction events to the message broker.
+//!
+//! The publisher subscribes to the block events reported by the committer, and publishes
+//! them to the Kafka topics or the NATS subjects, so the downstream indexers don't have to
+//! poll the database. Brokers support is enabled by the `kafka` and `nats` features of the crate.
+//!
+//! Events are published in the order they were reported, at least once: the event is retried
+//! until the broker accepts it, so the event whose acknowledgement was lost is published again.
+//! Every event carries its sequence number within the topic, which lets the consumers skip
+//! the duplicates. Events are not persisted, so the events are lost if the publisher lags
+//! behind the committer, or the server is restarted before they are published. In the first
+//! case the `eventsDropped` notice is published to every topic, and in the second one the
+//! sequence numbers start from zero again.
+
+// Built-in uses
+use std::{collections::HashMap, time::Instant};
+// External uses
+use futures::{channel::mpsc, StreamExt};
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+#[cfg(any(feature = "kafka", feature = "nats"))]
+use zksync_config::configs::event_publisher::EventPublisherBackend;
+use zksync_config::EventPublisherConfig;
+// Local uses
+use self::events::{ChainEvent, PublishedEvent};
+use crate::block_events::BlockEvent;
+
+pub mod events;
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+
+/// Connection to the message broker.
+#[async_trait::async_trait]
+trait EventSink: Send {
+    /// Publishes the message, returns once it's accepted by the broker.
+    async fn publish(&mut self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()>;
+}
+
+/// Creates the sink of the configured backend. The connection to the broker is established
+/// once the first event is published, so the unavailable broker doesn't prevent the server
+/// from starting.
+fn create_sink(config: &EventPublisherConfig) -> anyhow::Result<Box<dyn EventSink>> {
+    match config.backend {
+        #[cfg(feature = "kafka")]
+        EventPublisherBackend::Kafka => Ok(Box::new(kafka::KafkaSink::new(config)?)),
+        #[cfg(feature = "nats")]
+        EventPublisherBackend::Nats => Ok(Box::new(nats::NatsSink::new(config))),
+        #[allow(unreachable_patterns)]
+        backend => anyhow::bail!(
+            "Event publisher backend {:?} is not supported by this build, \
+             enable the corresponding feature",
+            backend
+        ),
+    }
+}
+
+struct EventPublisher {
+    sink: Box<dyn EventSink>,
+    config: EventPublisherConfig,
+    /// Sequence numbers of the next events of the topics.
+    sequences: HashMap<&'static str, u64>,
+}
+
+impl EventPublisher {
+    async fn publish_event(&mut self, event: ChainEvent) {
+        for &kind in event.topic_kinds() {
+            let sequence = self.sequences.entry(kind).or_default();
+            let published_event = PublishedEvent {
+                sequence: *sequence,
+                event: &event,
+            };
+            *sequence += 1;
+
+            let topic = self.config.topic(kind);
+            let payload = serde_json::to_vec(&published_event).expect("Unserializable chain event");
+            self.publish_message(&topic, &event.key(), &payload).await;
+        }
+    }
+
+    async fn publish_message(&mut self, topic: &str, key: &str, payload: &[u8]) {
+        loop {
+            let start = Instant::now();
+            match self.sink.publish(topic, key, payload).await {
+                Ok(()) => {
+                    metrics::histogram!("event_publisher.publish", start.elapsed());
+                    return;
+                }
+                Err(err) => {
+                    vlog::warn!("Failed to publish the event to {}: {}", topic, err);
+                    metrics::counter!("event_publisher.failed", 1);
+                    time::delay_for(self.config.retry_interval()).await;
+                }
+            }
+        }
+    }
+}
+
+/// Runs the task publishing the block events reported by the committer to the message broker.
+pub fn run_event_publisher(
+    config: EventPublisherConfig,
+    mut block_events: mpsc::Receiver<BlockEvent>,
+) -> anyhow::Result<JoinHandle<()>> {
+    let sink = create_sink(&config)?;
+    vlog::info!(
+        "Publishing the events to {:?} at {}",
+        config.backend,
+        config.servers.join(",")
+    );
+    let mut publisher = EventPublisher {
+        sink,
+        config,
+        sequences: HashMap::new(),
+    };
+
+    Ok(tokio::spawn(async move {
+        while let Some(event) = block_events.next().await {
+            for event in ChainEvent::from_block_event(event) {
+                publisher.publish_event(event).await;
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_events::operation_hash;
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+    use zksync_config::configs::event_publisher::EventPublisherBackend;
+    use zksync_crypto::{ff::Field, Fr};
+    use zksync_types::{
+        block::{Block, ExecutedOperations},
+        AccountId, Address, BlockNumber, ExecutedTx, Nonce, SignedZkSyncTx, TokenId, Transfer,
+        ZkSyncTx,
+    };
+
+    fn executed_transfer() -> ExecutedOperations {
+        let transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(0),
+            None,
+        );
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx: ZkSyncTx::Transfer(Box::new(transfer)),
+                eth_sign_data: None,
+            },
+            success: false,
+            op: None,
+            fail_reason: Some("Not enough balance".into()),
+            fail_code: None,
+            block_index: None,
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    /// Sink collecting the published messages.
+    #[derive(Debug, Default, Clone)]
+    struct MockSink(Arc<Mutex<Vec<(String, serde_json::Value)>>>);
+
+    #[async_trait::async_trait]
+    impl EventSink for MockSink {
+        async fn publish(&mut self, topic: &str, _key: &str, payload: &[u8]) -> anyhow::Result<()> {
+            let message = serde_json::from_slice(payload)?;
+            self.0.lock().unwrap().push((topic.to_string(), message));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn sequence_numbers() {
+        let sink = MockSink::default();
+        let mut publisher = EventPublisher {
+            sink: Box::new(sink.clone()),
+            config: EventPublisherConfig {
+                enabled: true,
+                backend: EventPublisherBackend::Kafka,
+                servers: Vec::new(),
+                topic_prefix: "zksync".into(),
+                publish_timeout: 1000,
+                retry_interval: 1000,
+            },
+            sequences: HashMap::new(),
+        };
+
+        let block_events = vec![
+            BlockEvent::OpsExecuted {
+                block_number: BlockNumber(1),
+                operations: vec![executed_transfer(), executed_transfer()],
+            },
+            BlockEvent::EventsDropped { count: 3 },
+            BlockEvent::BlockVerified {
+                block: Block::new(
+                    BlockNumber(1),
+                    Fr::zero(),
+                    AccountId(0),
+                    Vec::new(),
+                    (0, 0),
+                    10,
+                    1_000_000.into(),
+                    1_500_000.into(),
+                ),
+            },
+        ];
+        for event in block_events {
+            for event in ChainEvent::from_block_event(event) {
+                publisher.publish_event(event).await;
+            }
+        }
+
+        let messages: Vec<_> = sink
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(topic, message)| {
+                (
+                    topic.clone(),
+                    message["type"].as_str().unwrap().to_string(),
+                    message["sequence"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        let expected_messages = vec![
+            ("zksync.tx_executed", "txExecuted", 0),
+            ("zksync.tx_executed", "txExecuted", 1),
+            ("zksync.block_committed", "eventsDropped", 0),
+            ("zksync.block_verified", "eventsDropped", 0),
+            ("zksync.tx_executed", "eventsDropped", 2),
+            ("zksync.block_verified", "blockVerified", 1),
+        ];
+        assert_eq!(
+            messages,
+            expected_messages
+                .into_iter()
+                .map(|(topic, kind, sequence)| (topic.to_string(), kind.to_string(), sequence))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn chain_events() {
+        let operation = executed_transfer();
+        let tx_hash = operation_hash(&operation);
+
+        let events = ChainEvent::from_block_event(BlockEvent::OpsExecuted {
+            block_number: BlockNumber(3),
+            operations: vec![operation.clone()],
+        });
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind(), "tx_executed");
+        assert_eq!(events[0].key(), "3");
+        let value = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(value["type"], "txExecuted");
+        assert_eq!(value["txHash"], tx_hash.as_str());
+        assert_eq!(value["success"], false);
+        assert_eq!(value["failReason"], "Not enough balance");
+
+        let block = Block::new(
+            BlockNumber(3),
+            Fr::zero(),
+            AccountId(0),
+            vec![operation],
+            (0, 0),
+            10,
+            1_000_000.into(),
+            1_500_000.into(),
+        );
+        let events = ChainEvent::from_block_event(BlockEvent::BlockCommitted { block });
+        assert_eq!(events[0].kind(), "block_committed");
+        let value = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(value["type"], "blockCommitted");
+        assert_eq!(value["blockNumber"], 3);
+        assert_eq!(value["blockSize"], 10);
+        assert_eq!(value["txHashes"], serde_json::json!([tx_hash]));
+    }
+}
@@ -0,0 +1,303 @@
+//! Declarative specification of the genesis state.
+//!
+//! By default the genesis block contains only the fee account, and the token list of the network
+//! is added to the database. The specification file allows to set up reproducible devnets and
+//! test fixtures: it defines the pre-funded accounts, the initial token registry, the fee account
+//! and the governance parameters, and the same file always results in the same genesis root.
+//!
+//! Example of the specification:
+//!
+//! ```json
+//! {
+//!     "feeAccount": "0xde03a0b5963f75f1c8485b355ff6d30f3093bde7",
+//!     "tokens": [
+//!         { "address": "0x3bdfbbfdcf051c6ec5a741cc0fde89e30ff2f824", "decimals": 18, "symbol": "MLTT" }
+//!     ],
+//!     "accounts": [
+//!         {
+//!             "address": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+//!             "pubKeyHash": "sync:8d23a2f2e3b5c7e1f0b3a4d3e2f1c0b9a8d7e6f5",
+//!             "balances": { "ETH": "1000000000000000000", "MLTT": "500" }
+//!         }
+//!     ],
+//!     "governance": {
+//!         "tokenTxRestrictions": { "MLTT": ["Withdraw", "ForcedExit"] },
+//!         "fastProcessing": false
+//!     }
+//! }
+//! ```
+//!
+//! Omitted `feeAccount` and `tokens` are taken from the server config and the token list
+//! of the network respectively.
+//!
+//! Note that the pre-funded accounts are not known to the zkSync contract, so such genesis
+//! can't be restored from Ethereum and must not be used for the public networks.
+
+// Built-in uses
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+// External uses
+use anyhow::{ensure, format_err};
+use serde::{Deserialize, Serialize};
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_types::{
+    tokens::{get_genesis_token_list, TokenTxRestriction, TokenTxType},
+    Account, AccountId, Address, PubKeyHash, Token, TokenGenesisListItem, TokenId,
+};
+use zksync_utils::BigUintSerdeWrapper;
+
+/// Symbol of the token which is always present in the network with the ID 0.
+const ETH_SYMBOL: &str = "ETH";
+
+/// Account created in the genesis block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisAccount {
+    pub address: Address,
+    /// Signing key of the account, so it can send transactions without `ChangePubKey`.
+    #[serde(default)]
+    pub pub_key_hash: Option<PubKeyHash>,
+    /// Balances of the account by the token symbol.
+    #[serde(default)]
+    pub balances: BTreeMap<String, BigUintSerdeWrapper>,
+}
+
+/// Governance parameters the network is started with.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisGovernance {
+    /// Transaction types allowed for the tokens by the token symbol.
+    #[serde(default)]
+    pub token_tx_restrictions: BTreeMap<String, Vec<TokenTxType>>,
+    /// Whether the fast processing of withdrawals is enabled. Default value of the flag is used if not set.
+    #[serde(default)]
+    pub fast_processing: Option<bool>,
+}
+
+/// Genesis specification file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisSpec {
+    #[serde(default)]
+    pub fee_account: Option<Address>,
+    /// Tokens registered in the genesis with the IDs starting from 1, in the given order.
+    #[serde(default)]
+    pub tokens: Option<Vec<TokenGenesisListItem>>,
+    /// Accounts created in the genesis in the given order. The fee account always gets the ID 0,
+    /// and the rest of the accounts get the consecutive IDs starting from 1.
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+    #[serde(default)]
+    pub governance: GenesisGovernance,
+}
+
+/// Genesis state built from the specification.
+#[derive(Debug, Clone)]
+pub struct Genesis {
+    pub tokens: Vec<Token>,
+    pub accounts: Vec<(AccountId, Account)>,
+    pub tx_restrictions: Vec<TokenTxRestriction>,
+    pub fast_processing: Option<bool>,
+}
+
+impl GenesisSpec {
+    /// Loads the specification from the JSON file. Omitted fields are filled from the config.
+    pub fn load(path: &Path, config: &ZkSyncConfig) -> anyhow::Result<Self> {
+        let spec = fs::read_to_string(path)
+            .map_err(|err| format_err!("Cannot read genesis spec {}: {}", path.display(), err))?;
+        let spec: Self = serde_json::from_str(&spec)
+            .map_err(|err| format_err!("Invalid genesis spec {}: {}", path.display(), err))?;
+        spec.with_defaults(config)
+    }
+
+    /// Specification of the default genesis: the fee account from the config
+    /// and the token list of the network.
+    pub fn from_config(config: &ZkSyncConfig) -> anyhow::Result<Self> {
+        Self::default().with_defaults(config)
+    }
+
+    fn with_defaults(mut self, config: &ZkSyncConfig) -> anyhow::Result<Self> {
+        if self.fee_account.is_none() {
+            self.fee_account = Some(config.chain.state_keeper.fee_account_addr);
+        }
+        if self.tokens.is_none() {
+            let network = config.chain.eth.network.to_string();
+            self.tokens = Some(
+                get_genesis_token_list(&network)
+                    .map_err(|err| format_err!("Initial token list not found: {}", err))?,
+            );
+        }
+        Ok(self)
+    }
+
+    /// Builds the genesis state, checking that the specification is consistent.
+    pub fn build(&self) -> anyhow::Result<Genesis> {
+        let fee_account = self
+            .fee_account
+            .ok_or_else(|| format_err!("Fee account is not set"))?;
+
+        let mut tokens = Vec::new();
+        let mut token_ids = HashMap::new();
+        token_ids.insert(ETH_SYMBOL.to_string(), TokenId(0));
+        for (id, token) in (1..).zip(self.tokens.iter().flatten()) {
+            let address = Address::from_str(token.address.trim_start_matches("0x"))
+                .map_err(|err| format_err!("Invalid address of token {}: {}", token.symbol, err))?;
+            let token = Token::new(TokenId(id), address, &token.symbol, token.decimals);
+            ensure!(
+                token_ids.insert(token.symbol.clone(), token.id).is_none(),
+                "Token {} is specified more than once",
+                token.symbol
+            );
+            tokens.push(token);
+        }
+        let token_id = |symbol: &str| {
+            token_ids
+                .get(symbol)
+                .copied()
+                .ok_or_else(|| format_err!("Unknown token {}", symbol))
+        };
+
+        let mut accounts = vec![(AccountId(0), Account::default_with_address(&fee_account))];
+        let mut addresses = HashSet::new();
+        for spec in &self.accounts {
+            ensure!(
+                addresses.insert(spec.address),
+                "Account {:#x} is specified more than once",
+                spec.address
+            );
+            // The fee account can be pre-funded too, it keeps its ID.
+            let account = if spec.address == fee_account {
+                &mut accounts[0].1
+            } else {
+                let id = AccountId(accounts.len() as u32);
+                accounts.push((id, Account::default_with_address(&spec.address)));
+                &mut accounts.last_mut().unwrap().1
+            };
+            if let Some(pub_key_hash) = spec.pub_key_hash {
+                account.pub_key_hash = pub_key_hash;
+            }
+            for (symbol, balance) in &spec.balances {
+                account.set_balance(token_id(symbol)?, balance.0.clone());
+            }
+        }
+
+        let tx_restrictions = self
+            .governance
+            .token_tx_restrictions
+            .iter()
+            .map(|(symbol, allowed_tx_types)| {
+                Ok(TokenTxRestriction::new(
+                    token_id(symbol)?,
+                    allowed_tx_types.clone(),
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Genesis {
+            tokens,
+            accounts,
+            tx_restrictions,
+            fast_processing: self.governance.fast_processing,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::BigUint;
+    use zksync_state::state::ZkSyncState;
+    use zksync_types::{BlockNumber, Fr};
+
+    fn spec() -> GenesisSpec {
+        serde_json::from_value(serde_json::json!({
+            "feeAccount": "0xde03a0b5963f75f1c8485b355ff6d30f3093bde7",
+            "tokens": [
+                { "address": "0x3bdfbbfdcf051c6ec5a741cc0fde89e30ff2f824", "decimals": 18, "symbol": "MLTT" }
+            ],
+            "accounts": [
+                {
+                    "address": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                    "pubKeyHash": "sync:8d23a2f2e3b5c7e1f0b3a4d3e2f1c0b9a8d7e6f5",
+                    "balances": { "ETH": "1000000000000000000", "MLTT": "500" }
+                },
+                {
+                    "address": "0xde03a0b5963f75f1c8485b355ff6d30f3093bde7",
+                    "balances": { "ETH": "1" }
+                }
+            ],
+            "governance": {
+                "tokenTxRestrictions": { "MLTT": ["Withdraw"] },
+                "fastProcessing": false
+            }
+        }))
+        .unwrap()
+    }
+
+    fn root_hash(genesis: &Genesis) -> Fr {
+        let accounts = genesis.accounts.iter().cloned().collect();
+        ZkSyncState::from_acc_map(accounts, BlockNumber(1)).root_hash()
+    }
+
+    #[test]
+    fn build_genesis() {
+        let genesis = spec().build().unwrap();
+
+        assert_eq!(genesis.tokens.len(), 1);
+        assert_eq!(genesis.tokens[0].id, TokenId(1));
+        assert_eq!(genesis.tokens[0].symbol, "MLTT");
+
+        assert_eq!(genesis.accounts.len(), 2);
+        let (fee_account_id, fee_account) = &genesis.accounts[0];
+        assert_eq!(*fee_account_id, AccountId(0));
+        assert_eq!(fee_account.get_balance(TokenId(0)), BigUint::from(1u32));
+        let (account_id, account) = &genesis.accounts[1];
+        assert_eq!(*account_id, AccountId(1));
+        assert_eq!(account.get_balance(TokenId(1)), BigUint::from(500u32));
+        assert_ne!(account.pub_key_hash, PubKeyHash::default());
+
+        assert_eq!(
+            genesis.tx_restrictions,
+            vec![TokenTxRestriction::new(
+                TokenId(1),
+                vec![TokenTxType::Withdraw]
+            )]
+        );
+        assert_eq!(genesis.fast_processing, Some(false));
+    }
+
+    /// Checks that the same specification always results in the same genesis root.
+    #[test]
+    fn genesis_root_is_deterministic() {
+        let root = root_hash(&spec().build().unwrap());
+        assert_eq!(root, root_hash(&spec().build().unwrap()));
+
+        let mut spec = spec();
+        spec.accounts.clear();
+        assert_ne!(root, root_hash(&spec.build().unwrap()));
+    }
+
+    #[test]
+    fn inconsistent_spec() {
+        let mut unknown_token = spec();
+        unknown_token.accounts[0]
+            .balances
+            .insert("DAI".to_string(), BigUint::from(1u32).into());
+        assert!(unknown_token.build().is_err());
+
+        let mut duplicate_account = spec();
+        let account = duplicate_account.accounts[0].clone();
+        duplicate_account.accounts.push(account);
+        assert!(duplicate_account.build().is_err());
+
+        let mut duplicate_token = spec();
+        let token = duplicate_token.tokens.as_ref().unwrap()[0].clone();
+        duplicate_token.tokens.as_mut().unwrap().push(token);
+        assert!(duplicate_token.build().is_err());
+    }
+}
@@ -1,32 +1,53 @@
 use crate::eth_watch::EthWatchRequest;
-use zksync_storage::StorageProcessor;
-use zksync_types::{tokens::get_genesis_token_list, tx::TxHash, Token, TokenId};
+use std::path::Path;
+use zksync_storage::{runtime_flags::RuntimeFlag, StorageProcessor};
+use zksync_types::tx::TxHash;
 
 use crate::{
+    background_migrations::run_background_migrations,
     block_proposer::run_block_proposer_task,
     committer::run_committer,
     eth_watch::start_eth_watch,
+    event_publisher::run_event_publisher,
+    genesis::GenesisSpec,
     mempool::run_mempool_tasks,
     private_api::start_private_core_api,
-    state_keeper::{start_state_keeper, ZkSyncStateInitParams, ZkSyncStateKeeper},
+    runtime_flags::{run_runtime_flags_updater, RuntimeFlags},
+    state_keeper::{
+        standby::{start_leader_lock_holder, StandbyStateKeeper},
+        start_state_keeper, ZkSyncStateInitParams, ZkSyncStateKeeper,
+    },
+    stats_aggregator::run_stats_aggregator,
+    webhook_notifier::run_webhook_notifier,
 };
 use futures::{
     channel::{mpsc, oneshot},
     future, SinkExt,
 };
 use tokio::task::JoinHandle;
-use zksync_config::ZkSyncConfig;
+use zksync_cold_storage::run_cold_storage_archiver;
+use zksync_config::{ColdStorageConfig, StatsConfig, WebhooksConfig, ZkSyncConfig};
+use zksync_eth_client::EthereumGateway;
+use zksync_state::tree::StateTree;
 use zksync_storage::ConnectionPool;
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 32_768;
 
+pub mod background_migrations;
 pub mod balancer;
+pub mod block_events;
 pub mod block_proposer;
 pub mod committer;
 pub mod eth_watch;
+pub mod event_publisher;
+pub mod genesis;
 pub mod mempool;
 pub mod private_api;
+pub mod runtime_flags;
 pub mod state_keeper;
+pub mod stats_aggregator;
+pub mod tx_traces;
+pub mod webhook_notifier;
 
 pub async fn insert_pending_withdrawals(
     storage: &mut StorageProcessor<'_>,
@@ -91,42 +112,63 @@ pub async fn wait_for_tasks(task_futures: Vec<JoinHandle<()>>) {
     }
 }
 
-/// Inserts the initial information about zkSync tokens into the database.
-pub async fn genesis_init(config: &ZkSyncConfig) {
+/// Creates the genesis block and inserts the initial information about zkSync tokens
+/// and governance parameters into the database.
+///
+/// If the genesis specification file is not provided, the genesis contains only
+/// the fee account from the config and the token list of the network.
+pub async fn genesis_init(config: &ZkSyncConfig, genesis_spec: Option<&Path>) {
+    let spec = match genesis_spec {
+        Some(path) => GenesisSpec::load(path, config),
+        None => GenesisSpec::from_config(config),
+    }
+    .expect("Failed to load genesis spec");
+    let genesis = spec.build().expect("Invalid genesis spec");
     let pool = ConnectionPool::new(Some(1));
 
-    vlog::info!("Generating genesis block.");
-    ZkSyncStateKeeper::create_genesis_block(
-        pool.clone(),
-        &config.chain.state_keeper.fee_account_addr,
-    )
-    .await;
+    vlog::info!(
+        "Generating genesis block with {} accounts.",
+        genesis.accounts.len()
+    );
+    let account_tree = StateTree::open(config.chain.state_keeper.account_tree_path.as_deref())
+        .expect("Failed to open the account tree");
+    ZkSyncStateKeeper::create_genesis_block(pool.clone(), &genesis.accounts, account_tree).await;
+
+    let mut storage = pool.access_storage().await.expect("failed to access db");
     vlog::info!("Adding initial tokens to db");
-    let genesis_tokens = get_genesis_token_list(&config.chain.eth.network.to_string())
-        .expect("Initial token list not found");
-    for (id, token) in (1..).zip(genesis_tokens) {
+    for token in genesis.tokens {
         vlog::info!(
-            "Adding token: {}, id:{}, address: {}, decimals: {}",
+            "Adding token: {}, id:{}, address: {:#x}, decimals: {}",
             token.symbol,
-            id,
+            token.id,
             token.address,
             token.decimals
         );
-        pool.access_storage()
-            .await
-            .expect("failed to access db")
+        storage
             .tokens_schema()
-            .store_token(Token {
-                id: TokenId(id as u16),
-                symbol: token.symbol,
-                address: token.address[2..]
-                    .parse()
-                    .expect("failed to parse token address"),
-                decimals: token.decimals,
-            })
+            .store_token(token)
             .await
             .expect("failed to store token");
     }
+    for restriction in genesis.tx_restrictions {
+        vlog::info!(
+            "Restricting token {} to transactions: {:?}",
+            restriction.token_id,
+            restriction.allowed_tx_types
+        );
+        storage
+            .tokens_schema()
+            .store_tx_restriction(&restriction)
+            .await
+            .expect("failed to store token tx restriction");
+    }
+    if let Some(fast_processing) = genesis.fast_processing {
+        storage
+            .runtime_flags_schema()
+            .set_enabled(RuntimeFlag::FastProcessing, fast_processing)
+            .await
+            .expect("failed to store fast processing flag");
+    }
 }
 
 /// Starts the core application, which has the following sub-modules:
@@ -137,6 +179,10 @@ pub async fn genesis_init(config: &ZkSyncConfig) {
 /// - block proposer, module to create block proposals for state keeper.
 /// - committer, module to store pending and completed blocks into the database.
 /// - private Core API server.
+///
+/// Modules are started only once the leader lock is acquired, so there is only one active
+/// Core instance at a time. In the standby mode the committed state is kept up to date
+/// while waiting for the lock, so the state keeper starts without rebuilding it.
 pub async fn run_core(
     connection_pool: ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
@@ -152,6 +198,33 @@ pub async fn run_core(
     let (mempool_block_request_sender, mempool_block_request_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
 
+    // Ensure that Ethereum Watcher observes the expected L1 network.
+    EthereumGateway::from_config(&config)
+        .check_l1_network(&config)
+        .await?;
+
+    // Only one Core instance creates blocks at a time. The standby instance waits until the leader
+    // releases the lock, while the regular one refuses to start if the lock is held.
+    let standby_mode = config.chain.state_keeper.standby_mode;
+    let account_tree_path = config.chain.state_keeper.account_tree_path.as_deref();
+    let (leader_lock_task, leader_lock_acquired) = start_leader_lock_holder(
+        ConnectionPool::dedicated("leader_lock", 1, config.db.slow_query_threshold()),
+        standby_mode,
+        panic_notify.clone(),
+    );
+    let standby_state = if standby_mode {
+        vlog::info!("Starting in the standby mode");
+        let account_tree = StateTree::open(account_tree_path)?;
+        let standby = StandbyStateKeeper::new(connection_pool.clone(), account_tree).await?;
+        Some(standby.run_until_promoted(leader_lock_acquired).await?)
+    } else {
+        anyhow::ensure!(
+            leader_lock_acquired.await?,
+            "Leader lock is held by another server instance, only the standby server can wait for it"
+        );
+        None
+    };
+
     // Start Ethereum Watcher.
     let eth_watch_task = start_eth_watch(
         &config,
@@ -165,27 +238,70 @@ pub async fn run_core(
     insert_pending_withdrawals(&mut storage_processor, eth_watch_req_sender.clone()).await;
 
     // Start State Keeper.
-    let state_keeper_init = ZkSyncStateInitParams::restore_from_db(&mut storage_processor).await?;
+    let state_keeper_init = match standby_state {
+        Some(state) => state,
+        None => {
+            let account_tree = StateTree::open(account_tree_path)?;
+            ZkSyncStateInitParams::restore_from_db(&mut storage_processor, account_tree).await?
+        }
+    };
     let pending_block = state_keeper_init
         .get_pending_block(&mut storage_processor)
-        .await;
+        .await?;
+
+    // Start updating the runtime flags switched by the operator.
+    let runtime_flags = RuntimeFlags::default();
+    let runtime_flags_task =
+        run_runtime_flags_updater(connection_pool.clone(), runtime_flags.clone());
 
-    let state_keeper = ZkSyncStateKeeper::new(
+    let mut state_keeper = ZkSyncStateKeeper::new(
         state_keeper_init,
         config.chain.state_keeper.fee_account_addr,
         state_keeper_req_receiver,
         proposed_blocks_sender,
-        config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.block_chunk_sizes(),
         config.chain.state_keeper.miniblock_iterations as usize,
         config.chain.state_keeper.fast_block_miniblock_iterations as usize,
     );
+    state_keeper.set_runtime_flags(runtime_flags.clone());
+    state_keeper.set_retried_tx_failures(
+        config
+            .chain
+            .state_keeper
+            .retried_tx_failures
+            .clone()
+            .unwrap_or_default(),
+    );
     let state_keeper_task = start_state_keeper(state_keeper, pending_block);
 
+    // Start the subscribers of the block events reported by the committer.
+    let mut block_events_subscribers = Vec::new();
+    let mut block_events_tasks = Vec::new();
+    let webhooks_config = WebhooksConfig::from_env();
+    if webhooks_config.enabled {
+        let (sender, receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+        block_events_tasks.push(run_webhook_notifier(
+            connection_pool.clone(),
+            webhooks_config,
+            receiver,
+        ));
+        block_events_subscribers.push(sender);
+    }
+    if config.event_publisher.enabled {
+        let (sender, receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+        block_events_tasks.push(run_event_publisher(
+            config.event_publisher.clone(),
+            receiver,
+        )?);
+        block_events_subscribers.push(sender);
+    }
+
     // Start committer.
     let committer_task = run_committer(
         proposed_blocks_receiver,
         mempool_block_request_sender.clone(),
         connection_pool.clone(),
+        block_events_subscribers,
     );
 
     // Start mempool.
@@ -202,25 +318,57 @@ pub async fn run_core(
     // Start block proposer.
     let proposer_task = run_block_proposer_task(
         &config,
+        connection_pool.clone(),
         mempool_block_request_sender.clone(),
         state_keeper_req_sender.clone(),
+        runtime_flags,
     );
 
     // Start private API.
     start_private_core_api(
+        connection_pool.clone(),
         panic_notify.clone(),
         mempool_tx_request_sender,
         eth_watch_req_sender,
+        state_keeper_req_sender,
         config.api.private.clone(),
     );
 
-    let task_futures = vec![
+    let mut task_futures = vec![
         eth_watch_task,
         state_keeper_task,
         committer_task,
         mempool_task,
         proposer_task,
+        runtime_flags_task,
+        leader_lock_task,
     ];
+    task_futures.extend(block_events_tasks);
+
+    // Start moving the proofs and witnesses of the verified blocks to the cold storage.
+    let cold_storage_config = ColdStorageConfig::from_env();
+    if cold_storage_config.enabled {
+        task_futures.push(run_cold_storage_archiver(
+            connection_pool.clone(),
+            cold_storage_config,
+        ));
+    }
+
+    // Start precomputing the network statistics reported by the API.
+    let stats_config = StatsConfig::from_env();
+    if stats_config.enabled {
+        task_futures.push(run_stats_aggregator(connection_pool.clone(), stats_config));
+    }
+
+    // Start backfilling the data of the online schema migrations. The task finishes
+    // once all the migrations are done, so it's not awaited with the other modules.
+    if config.background_migrations.enabled {
+        run_background_migrations(
+            connection_pool.clone(),
+            config.background_migrations.clone(),
+            zksync_storage::background_migrations::background_migrations(),
+        );
+    }
 
     Ok(task_futures)
 }
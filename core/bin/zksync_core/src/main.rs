@@ -19,11 +19,15 @@ async fn main() -> anyhow::Result<()> {
         })
         .expect("Error setting Ctrl+C handler");
     }
-    let connection_pool = ConnectionPool::new(None);
+    let connection_pool = ConnectionPool::dedicated(
+        "core",
+        config.db.core_pool_size,
+        config.db.slow_query_threshold(),
+    );
 
     // Run prometheus data exporter.
     let (prometheus_task_handle, counter_task_handle) =
-        run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, true);
+        run_prometheus_exporter(connection_pool.clone(), &config.api.prometheus, true);
 
     let task_handles = run_core(connection_pool, stop_signal_sender, &config)
         .await
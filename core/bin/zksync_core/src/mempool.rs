@@ -16,7 +16,7 @@
 
 // Built-in deps
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 // External uses
@@ -32,15 +32,20 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
+use tracing::Instrument;
+use vlog::Traced;
 
 // Workspace uses
-use zksync_config::ZkSyncConfig;
+use zksync_config::{
+    configs::chain::{Mempool, StateKeeper},
+    ZkSyncConfig,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
-    tx::TxEthSignature,
-    AccountId, AccountUpdate, AccountUpdates, Address, Nonce, PriorityOp, SignedZkSyncTx,
-    TransferOp, TransferToNewOp, ZkSyncTx,
+    tx::{TxEthSignature, TxHash},
+    AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, Nonce, PriorityOp,
+    SignedZkSyncTx, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
 // Local uses
@@ -55,6 +60,9 @@ pub enum TxAddError {
     #[error("Tx nonce is too low.")]
     NonceMismatch,
 
+    #[error("Tx nonce is too high, allowed nonces are from {min_nonce} to {max_nonce}.")]
+    NonceTooHigh { min_nonce: Nonce, max_nonce: Nonce },
+
     #[error("Tx is incorrect")]
     IncorrectTx,
 
@@ -90,6 +98,9 @@ pub enum TxAddError {
 
     #[error("The number of withdrawals in the batch is too big")]
     BatchWithdrawalsOverload,
+
+    #[error("Too many pending transactions of the account in the mempool, the limit is {limit}")]
+    TooManyPendingTxs { limit: usize },
 }
 
 #[derive(Clone, Debug, Default)]
@@ -107,6 +118,10 @@ impl ProposedBlock {
 #[derive(Debug)]
 pub struct GetBlockRequest {
     pub last_priority_op_number: u64,
+    /// Number of the pending block the proposed miniblock is going to be included into.
+    pub block_number: BlockNumber,
+    /// If set, only priority operations are proposed, e.g. while the server is paused.
+    pub priority_ops_only: bool,
     pub response_sender: oneshot::Sender<ProposedBlock>,
 }
 
@@ -125,6 +140,30 @@ pub enum MempoolTransactionRequest {
         Option<TxEthSignature>,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Remove the transactions matching the filter from the mempool.
+    /// oneshot is used to receive the hashes of the removed transactions.
+    EvictTxs(EvictionFilter, oneshot::Sender<anyhow::Result<Vec<TxHash>>>),
+    /// Get the transactions of the account waiting in the mempool, in the order of execution.
+    /// Transactions already proposed for the pending block are not included.
+    GetAccountTxs(Address, oneshot::Sender<Vec<ZkSyncTx>>),
+}
+
+/// Transactions removed from the mempool by the operator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvictionFilter {
+    /// A single transaction. If it belongs to a batch, the whole batch is removed.
+    Tx(TxHash),
+    /// All the transactions of the account, along with the batches containing them.
+    Account(Address),
+}
+
+impl EvictionFilter {
+    fn matches(self, tx: &SignedZkSyncTx) -> bool {
+        match self {
+            Self::Tx(tx_hash) => tx.hash() == tx_hash,
+            Self::Account(address) => tx.account() == address,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -133,13 +172,223 @@ pub enum MempoolBlocksRequest {
     UpdateNonces(AccountUpdates),
     /// Get transactions from the mempool.
     GetBlock(GetBlockRequest),
+    /// Get the amount of chunks required by all the transactions in the mempool.
+    GetPendingChunks(oneshot::Sender<usize>),
+}
+
+/// Limits on the amount of chunks that transactions of a certain type
+/// can occupy in a single proposed block.
+#[derive(Debug, Clone, Default)]
+pub struct ChunksQuotas {
+    transfer: Option<usize>,
+    withdraw: Option<usize>,
+    change_pubkey: Option<usize>,
+    forced_exit: Option<usize>,
+}
+
+impl ChunksQuotas {
+    pub fn from_config(config: &StateKeeper) -> Self {
+        Self {
+            transfer: config.transfer_chunks_quota,
+            withdraw: config.withdraw_chunks_quota,
+            change_pubkey: config.change_pubkey_chunks_quota,
+            forced_exit: config.forced_exit_chunks_quota,
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.transfer.is_none()
+            && self.withdraw.is_none()
+            && self.change_pubkey.is_none()
+            && self.forced_exit.is_none()
+    }
+
+    fn quota(&self, tx: &ZkSyncTx) -> Option<usize> {
+        match tx {
+            ZkSyncTx::Transfer(_) => self.transfer,
+            ZkSyncTx::Withdraw(_) => self.withdraw,
+            ZkSyncTx::ChangePubKey(_) => self.change_pubkey,
+            ZkSyncTx::ForcedExit(_) => self.forced_exit,
+            ZkSyncTx::Close(_) => None,
+        }
+    }
+}
+
+/// Amount of chunks already occupied by each transaction type in the proposed block.
+#[derive(Debug, Default, Clone)]
+struct ChunksUsage {
+    transfer: usize,
+    withdraw: usize,
+    change_pubkey: usize,
+    forced_exit: usize,
+}
+
+impl ChunksUsage {
+    fn used_mut(&mut self, tx: &ZkSyncTx) -> Option<&mut usize> {
+        match tx {
+            ZkSyncTx::Transfer(_) => Some(&mut self.transfer),
+            ZkSyncTx::Withdraw(_) => Some(&mut self.withdraw),
+            ZkSyncTx::ChangePubKey(_) => Some(&mut self.change_pubkey),
+            ZkSyncTx::ForcedExit(_) => Some(&mut self.forced_exit),
+            ZkSyncTx::Close(_) => None,
+        }
+    }
+
+    fn used(&self, tx: &ZkSyncTx) -> usize {
+        match tx {
+            ZkSyncTx::Transfer(_) => self.transfer,
+            ZkSyncTx::Withdraw(_) => self.withdraw,
+            ZkSyncTx::ChangePubKey(_) => self.change_pubkey,
+            ZkSyncTx::ForcedExit(_) => self.forced_exit,
+            ZkSyncTx::Close(_) => 0,
+        }
+    }
+
+    /// Checks whether transactions with the provided chunks amounts fit into the quotas.
+    ///
+    /// A single transaction of the type that doesn't occupy any chunks of the block yet always fits,
+    /// so the type with the quota smaller than a single transaction is not starved.
+    fn fits(&self, quotas: &ChunksQuotas, txs: &[(&ZkSyncTx, usize)]) -> bool {
+        let mut usage = self.clone();
+        usage.add(txs);
+
+        txs.iter().all(|(tx, _)| match quotas.quota(tx) {
+            Some(quota) => {
+                let is_single = || {
+                    txs.iter()
+                        .filter(|(other, _)| {
+                            std::mem::discriminant(*other) == std::mem::discriminant(*tx)
+                        })
+                        .count()
+                        == 1
+                };
+                usage.used(tx) <= quota || (self.used(tx) == 0 && is_single())
+            }
+            None => true,
+        })
+    }
+
+    fn add(&mut self, txs: &[(&ZkSyncTx, usize)]) {
+        for (tx, chunks) in txs {
+            if let Some(used) = self.used_mut(tx) {
+                *used += chunks;
+            }
+        }
+    }
+}
+
+/// Returns the transactions of the queued element.
+fn element_txs(element: &SignedTxVariant) -> &[SignedZkSyncTx] {
+    match element {
+        SignedTxVariant::Tx(tx) => std::slice::from_ref(tx),
+        SignedTxVariant::Batch(batch) => batch.txs.as_slice(),
+    }
+}
+
+/// Queue of the transactions and batches ready to be included into the block.
+/// Along with the queue, it counts the queued transactions of every account and
+/// the chunks they require, so neither the limit of the pending transactions nor
+/// the mempool depth requires scanning the queue.
+#[derive(Debug, Default)]
+struct ReadyTxs {
+    queue: VecDeque<SignedTxVariant>,
+    pending_txs: HashMap<Address, usize>,
+    pending_chunks: usize,
+}
+
+impl ReadyTxs {
+    fn count_added(&mut self, element: &SignedTxVariant) {
+        for tx in element_txs(element) {
+            *self.pending_txs.entry(tx.account()).or_default() += 1;
+            self.pending_chunks += tx.min_chunks();
+        }
+    }
+
+    fn count_removed(&mut self, element: &SignedTxVariant) {
+        for tx in element_txs(element) {
+            self.pending_chunks -= tx.min_chunks();
+            let account = tx.account();
+            if let Some(pending) = self.pending_txs.get_mut(&account) {
+                *pending -= 1;
+                if *pending == 0 {
+                    self.pending_txs.remove(&account);
+                }
+            }
+        }
+    }
+
+    /// Returns the amount of the queued transactions of the account.
+    fn pending_txs(&self, account: &Address) -> usize {
+        self.pending_txs.get(account).copied().unwrap_or_default()
+    }
+
+    /// Returns the minimal amount of chunks required by all the queued transactions,
+    /// the same way it's counted for the proposed blocks.
+    fn pending_chunks(&self) -> usize {
+        self.pending_chunks
+    }
+
+    fn push_back(&mut self, element: SignedTxVariant) {
+        self.count_added(&element);
+        self.queue.push_back(element);
+    }
+
+    fn push_front(&mut self, element: SignedTxVariant) {
+        self.count_added(&element);
+        self.queue.push_front(element);
+    }
+
+    fn pop_front(&mut self) -> Option<SignedTxVariant> {
+        let element = self.queue.pop_front()?;
+        self.count_removed(&element);
+        Some(element)
+    }
+
+    fn retain(&mut self, mut f: impl FnMut(&SignedTxVariant) -> bool) {
+        let queue = std::mem::take(&mut self.queue);
+        for element in queue {
+            if f(&element) {
+                self.queue.push_back(element);
+            } else {
+                self.count_removed(&element);
+            }
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &SignedTxVariant> {
+        self.queue.iter()
+    }
+
+    fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl Extend<SignedTxVariant> for ReadyTxs {
+    fn extend<T: IntoIterator<Item = SignedTxVariant>>(&mut self, iter: T) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl From<VecDeque<SignedTxVariant>> for ReadyTxs {
+    fn from(queue: VecDeque<SignedTxVariant>) -> Self {
+        let mut ready_txs = Self::default();
+        ready_txs.extend(queue);
+        ready_txs
+    }
 }
 
 struct MempoolState {
     // account and last committed nonce
     account_nonces: HashMap<Address, Nonce>,
     account_ids: HashMap<AccountId, Address>,
-    ready_txs: VecDeque<SignedTxVariant>,
+    ready_txs: ReadyTxs,
+    /// Maximum difference between the transaction nonce and the committed nonce of the account, if limited.
+    max_nonce_lookahead: Option<u32>,
+    /// Maximum amount of the queued transactions of a single account.
+    max_pending_txs_per_account: usize,
 }
 
 impl MempoolState {
@@ -160,6 +409,18 @@ impl MempoolState {
         batch.txs.iter().map(|tx| self.chunks_for_tx(&tx.tx)).sum()
     }
 
+    /// Returns the transactions of the block element alongside with the chunks required for each of them.
+    fn chunks_per_tx<'a>(&self, element: &'a SignedTxVariant) -> Vec<(&'a ZkSyncTx, usize)> {
+        match element {
+            SignedTxVariant::Tx(tx) => vec![(&tx.tx, self.chunks_for_tx(&tx.tx))],
+            SignedTxVariant::Batch(batch) => batch
+                .txs
+                .iter()
+                .map(|tx| (&tx.tx, self.chunks_for_tx(&tx.tx)))
+                .collect(),
+        }
+    }
+
     fn required_chunks(&self, element: &SignedTxVariant) -> usize {
         match element {
             SignedTxVariant::Tx(tx) => self.chunks_for_tx(&tx.tx),
@@ -167,7 +428,7 @@ impl MempoolState {
         }
     }
 
-    async fn restore_from_db(db_pool: &ConnectionPool) -> Self {
+    async fn restore_from_db(db_pool: &ConnectionPool, config: &Mempool) -> Self {
         let mut storage = db_pool.access_storage().await.expect("mempool db restore");
         let mut transaction = storage
             .start_transaction()
@@ -200,12 +461,13 @@ impl MempoolState {
 
         // Load transactions that were not yet processed and are awaiting in the
         // mempool.
-        let ready_txs: VecDeque<_> = transaction
+        let ready_txs: ReadyTxs = transaction
             .chain()
             .mempool_schema()
             .load_txs()
             .await
-            .expect("Attempt to restore mempool txs from DB failed");
+            .expect("Attempt to restore mempool txs from DB failed")
+            .into();
 
         transaction
             .commit()
@@ -221,6 +483,8 @@ impl MempoolState {
             account_nonces,
             account_ids,
             ready_txs,
+            max_nonce_lookahead: config.max_nonce_lookahead,
+            max_pending_txs_per_account: config.max_pending_txs_per_account,
         }
     }
 
@@ -228,47 +492,135 @@ impl MempoolState {
         *self.account_nonces.get(address).unwrap_or(&Nonce(0))
     }
 
+    /// Checks that the transaction nonce is not lower than the committed nonce of the account
+    /// and not too far ahead of it, if the lookahead is limited.
+    fn check_nonce(&self, tx: &SignedZkSyncTx) -> Result<(), TxAddError> {
+        let min_nonce = self.nonce(&tx.account());
+        if tx.nonce() < min_nonce {
+            return Err(TxAddError::NonceMismatch);
+        }
+
+        if let Some(lookahead) = self.max_nonce_lookahead {
+            let max_nonce = Nonce(min_nonce.saturating_add(lookahead));
+            if tx.nonce() > max_nonce {
+                return Err(TxAddError::NonceTooHigh {
+                    min_nonce,
+                    max_nonce,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that adding the given transactions doesn't exceed the limit
+    /// of the queued transactions for any of the accounts.
+    fn check_pending_txs_limit<'a>(
+        &self,
+        txs: impl Iterator<Item = &'a SignedZkSyncTx>,
+    ) -> Result<(), TxAddError> {
+        let mut new_txs = HashMap::new();
+        for tx in txs {
+            *new_txs.entry(tx.account()).or_insert(0) += 1;
+        }
+
+        for (account, count) in new_txs {
+            let pending = self.ready_txs.pending_txs(&account);
+            if pending + count > self.max_pending_txs_per_account {
+                return Err(TxAddError::TooManyPendingTxs {
+                    limit: self.max_pending_txs_per_account,
+                });
+            }
+        }
+        Ok(())
+    }
+
     fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
         // Correctness should be checked by `signature_checker`, thus
         // `tx.check_correctness()` is not invoked here.
 
-        if tx.nonce() >= self.nonce(&tx.account()) {
-            self.ready_txs.push_back(tx.into());
-            Ok(())
-        } else {
-            Err(TxAddError::NonceMismatch)
-        }
+        self.check_nonce(&tx)?;
+        self.check_pending_txs_limit(std::iter::once(&tx))?;
+        self.ready_txs.push_back(tx.into());
+        Ok(())
     }
 
     fn add_batch(&mut self, batch: SignedTxsBatch) -> Result<(), TxAddError> {
         assert_ne!(batch.batch_id, 0, "Batch ID was not set");
 
         for tx in batch.txs.iter() {
-            if tx.nonce() < self.nonce(&tx.account()) {
-                return Err(TxAddError::NonceMismatch);
-            }
+            self.check_nonce(tx)?;
         }
+        self.check_pending_txs_limit(batch.txs.iter())?;
 
         self.ready_txs.push_back(SignedTxVariant::Batch(batch));
 
         Ok(())
     }
+
+    /// Returns the hashes of the queued transactions matching the filter.
+    /// Batches are evicted as a whole, so all the transactions of the affected batches are returned.
+    fn txs_to_evict(&self, filter: EvictionFilter) -> Vec<TxHash> {
+        self.ready_txs
+            .iter()
+            .filter(|element| match element {
+                SignedTxVariant::Tx(tx) => filter.matches(tx),
+                SignedTxVariant::Batch(batch) => batch.txs.iter().any(|tx| filter.matches(tx)),
+            })
+            .flat_map(SignedTxVariant::hashes)
+            .collect()
+    }
+
+    /// Returns the queued transactions of the account, including the ones from the batches.
+    fn account_txs(&self, address: Address) -> Vec<ZkSyncTx> {
+        self.ready_txs
+            .iter()
+            .flat_map(element_txs)
+            .filter(|tx| tx.account() == address)
+            .map(|tx| tx.tx.clone())
+            .collect()
+    }
+
+    /// Removes the queued transactions and batches containing any of the given transactions.
+    fn remove_txs(&mut self, tx_hashes: &[TxHash]) {
+        let tx_hashes: HashSet<_> = tx_hashes.iter().collect();
+        self.ready_txs.retain(|element| {
+            !element
+                .hashes()
+                .iter()
+                .any(|tx_hash| tx_hashes.contains(tx_hash))
+        });
+    }
 }
 
 struct MempoolBlocksHandler {
     mempool_state: Arc<RwLock<MempoolState>>,
-    requests: mpsc::Receiver<MempoolBlocksRequest>,
+    requests: mpsc::Receiver<Traced<MempoolBlocksRequest>>,
     eth_watch_req: mpsc::Sender<EthWatchRequest>,
     max_block_size_chunks: usize,
+    chunks_quotas: ChunksQuotas,
+    /// Chunks taken by the transactions proposed for the pending block, so the quotas are
+    /// applied to the whole block rather than to each of its miniblocks.
+    chunks_usage: ChunksUsage,
+    /// Number of the block `chunks_usage` is tracked for, the usage is reset once it's sealed.
+    chunks_usage_block: BlockNumber,
 }
 
 impl MempoolBlocksHandler {
-    async fn propose_new_block(&mut self, current_unprocessed_priority_op: u64) -> ProposedBlock {
+    async fn propose_new_block(
+        &mut self,
+        current_unprocessed_priority_op: u64,
+        block_number: BlockNumber,
+        priority_ops_only: bool,
+    ) -> ProposedBlock {
         let start = std::time::Instant::now();
         let (chunks_left, priority_ops) = self
-            .select_priority_ops(current_unprocessed_priority_op)
+            .select_priority_ops(current_unprocessed_priority_op, block_number)
             .await;
-        let (_chunks_left, txs) = self.prepare_tx_for_block(chunks_left).await;
+        let txs = if priority_ops_only {
+            Vec::new()
+        } else {
+            self.prepare_tx_for_block(chunks_left, block_number).await.1
+        };
 
         if !priority_ops.is_empty() {
             vlog::debug!("Proposed priority ops for block: {:?}", priority_ops);
@@ -284,6 +636,7 @@ impl MempoolBlocksHandler {
     async fn select_priority_ops(
         &self,
         current_unprocessed_priority_op: u64,
+        block_number: BlockNumber,
     ) -> (usize, Vec<PriorityOp>) {
         let eth_watch_resp = oneshot::channel();
         self.eth_watch_req
@@ -291,6 +644,7 @@ impl MempoolBlocksHandler {
             .send(EthWatchRequest::GetPriorityQueueOps {
                 op_start_id: current_unprocessed_priority_op,
                 max_chunks: self.max_block_size_chunks,
+                block_number,
                 resp: eth_watch_resp.0,
             })
             .await
@@ -311,20 +665,48 @@ impl MempoolBlocksHandler {
     async fn prepare_tx_for_block(
         &mut self,
         mut chunks_left: usize,
+        block_number: BlockNumber,
     ) -> (usize, Vec<SignedTxVariant>) {
         let mut txs_for_commit = Vec::new();
+        // Transactions that do not fit into the quotas, and the accounts that sent them.
+        // All the subsequent transactions of such accounts are skipped as well to not break the nonce order.
+        let mut skipped_txs = Vec::new();
+        let mut skipped_accounts = HashSet::new();
+        let check_quotas = !self.chunks_quotas.is_unlimited();
+        if block_number != self.chunks_usage_block {
+            self.chunks_usage = ChunksUsage::default();
+            self.chunks_usage_block = block_number;
+        }
 
         let mut mempool = self.mempool_state.write().await;
         while let Some(tx) = mempool.ready_txs.pop_front() {
             let chunks_for_tx = mempool.required_chunks(&tx);
-            if chunks_left >= chunks_for_tx {
-                txs_for_commit.push(tx);
-                chunks_left -= chunks_for_tx;
-            } else {
+            if chunks_left < chunks_for_tx {
                 // Push the taken tx back, it does not fit.
                 mempool.ready_txs.push_front(tx);
                 break;
             }
+
+            if check_quotas {
+                let chunks_per_tx = mempool.chunks_per_tx(&tx);
+                let accounts: Vec<_> = chunks_per_tx.iter().map(|(tx, _)| tx.account()).collect();
+
+                let account_skipped = accounts.iter().any(|acc| skipped_accounts.contains(acc));
+                if account_skipped || !self.chunks_usage.fits(&self.chunks_quotas, &chunks_per_tx) {
+                    skipped_accounts.extend(accounts);
+                    skipped_txs.push(tx);
+                    continue;
+                }
+                self.chunks_usage.add(&chunks_per_tx);
+            }
+
+            txs_for_commit.push(tx);
+            chunks_left -= chunks_for_tx;
+        }
+
+        // Return skipped transactions to the queue, preserving their order.
+        for tx in skipped_txs.into_iter().rev() {
+            mempool.ready_txs.push_front(tx);
         }
 
         (chunks_left, txs_for_commit)
@@ -333,70 +715,85 @@ impl MempoolBlocksHandler {
     async fn run(mut self) {
         vlog::info!("Block mempool handler is  running");
         while let Some(request) = self.requests.next().await {
-            match request {
-                MempoolBlocksRequest::GetBlock(block) => {
-                    // Generate proposed block.
-                    let proposed_block =
-                        self.propose_new_block(block.last_priority_op_number).await;
-
-                    // Send the proposed block to the request initiator.
-                    block
-                        .response_sender
-                        .send(proposed_block)
-                        .expect("mempool proposed block response send failed");
-                }
-                MempoolBlocksRequest::UpdateNonces(updates) => {
-                    for (id, update) in updates {
-                        match update {
-                            AccountUpdate::Create { address, nonce } => {
-                                let mut mempool = self.mempool_state.write().await;
-                                mempool.account_ids.insert(id, address);
-                                mempool.account_nonces.insert(address, nonce);
-                            }
-                            AccountUpdate::Delete { address, .. } => {
-                                let mut mempool = self.mempool_state.write().await;
-                                mempool.account_ids.remove(&id);
-                                mempool.account_nonces.remove(&address);
-                            }
-                            AccountUpdate::UpdateBalance { new_nonce, .. } => {
-                                let address = self
+            // Requests are handled within the span of the sender.
+            let (span, request) = request.into_parts();
+            self.handle_request(request).instrument(span).await;
+        }
+    }
+
+    async fn handle_request(&mut self, request: MempoolBlocksRequest) {
+        match request {
+            MempoolBlocksRequest::GetBlock(block) => {
+                // Generate proposed block.
+                let proposed_block = self
+                    .propose_new_block(
+                        block.last_priority_op_number,
+                        block.block_number,
+                        block.priority_ops_only,
+                    )
+                    .await;
+
+                // Send the proposed block to the request initiator.
+                block
+                    .response_sender
+                    .send(proposed_block)
+                    .expect("mempool proposed block response send failed");
+            }
+            MempoolBlocksRequest::GetPendingChunks(response_sender) => {
+                let pending_chunks = self.mempool_state.read().await.ready_txs.pending_chunks();
+                response_sender.send(pending_chunks).unwrap_or_default();
+            }
+            MempoolBlocksRequest::UpdateNonces(updates) => {
+                for (id, update) in updates {
+                    match update {
+                        AccountUpdate::Create { address, nonce } => {
+                            let mut mempool = self.mempool_state.write().await;
+                            mempool.account_ids.insert(id, address);
+                            mempool.account_nonces.insert(address, nonce);
+                        }
+                        AccountUpdate::Delete { address, .. } => {
+                            let mut mempool = self.mempool_state.write().await;
+                            mempool.account_ids.remove(&id);
+                            mempool.account_nonces.remove(&address);
+                        }
+                        AccountUpdate::UpdateBalance { new_nonce, .. } => {
+                            let address = self
+                                .mempool_state
+                                .read()
+                                .await
+                                .account_ids
+                                .get(&id)
+                                .cloned();
+                            if let Some(address) = address {
+                                if let Some(nonce) = self
                                     .mempool_state
-                                    .read()
+                                    .write()
                                     .await
-                                    .account_ids
-                                    .get(&id)
-                                    .cloned();
-                                if let Some(address) = address {
-                                    if let Some(nonce) = self
-                                        .mempool_state
-                                        .write()
-                                        .await
-                                        .account_nonces
-                                        .get_mut(&address)
-                                    {
-                                        *nonce = new_nonce;
-                                    }
+                                    .account_nonces
+                                    .get_mut(&address)
+                                {
+                                    *nonce = new_nonce;
                                 }
                             }
-                            AccountUpdate::ChangePubKeyHash { new_nonce, .. } => {
-                                let address = self
+                        }
+                        AccountUpdate::ChangePubKeyHash { new_nonce, .. } => {
+                            let address = self
+                                .mempool_state
+                                .read()
+                                .await
+                                .account_ids
+                                .get(&id)
+                                .cloned();
+
+                            if let Some(address) = address {
+                                if let Some(nonce) = self
                                     .mempool_state
-                                    .read()
+                                    .write()
                                     .await
-                                    .account_ids
-                                    .get(&id)
-                                    .cloned();
-
-                                if let Some(address) = address {
-                                    if let Some(nonce) = self
-                                        .mempool_state
-                                        .write()
-                                        .await
-                                        .account_nonces
-                                        .get_mut(&address)
-                                    {
-                                        *nonce = new_nonce;
-                                    }
+                                    .account_nonces
+                                    .get_mut(&address)
+                                {
+                                    *nonce = new_nonce;
                                 }
                             }
                         }
@@ -508,6 +905,32 @@ impl MempoolTransactionsHandler {
         self.mempool_state.write().await.add_batch(batch)
     }
 
+    async fn evict_txs(&mut self, filter: EvictionFilter) -> anyhow::Result<Vec<TxHash>> {
+        // The lock is held until the transactions are removed from the database,
+        // so they can't be proposed for a block in the meantime.
+        let mut mempool_state = self.mempool_state.write().await;
+        let tx_hashes = mempool_state.txs_to_evict(filter);
+        if tx_hashes.is_empty() {
+            return Ok(tx_hashes);
+        }
+
+        self.db_pool
+            .access_storage()
+            .await?
+            .chain()
+            .mempool_schema()
+            .remove_txs(&tx_hashes)
+            .await?;
+        mempool_state.remove_txs(&tx_hashes);
+
+        vlog::warn!(
+            "{} transactions were evicted from the mempool by the operator ({:?})",
+            tx_hashes.len(),
+            filter
+        );
+        Ok(tx_hashes)
+    }
+
     async fn run(mut self) {
         vlog::info!("Transaction mempool handler is  running");
         while let Some(request) = self.requests.next().await {
@@ -520,6 +943,14 @@ impl MempoolTransactionsHandler {
                     let tx_add_result = self.add_batch(txs, eth_signature).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
+                MempoolTransactionRequest::EvictTxs(filter, resp) => {
+                    let evict_result = self.evict_txs(filter).await;
+                    resp.send(evict_result).unwrap_or_default();
+                }
+                MempoolTransactionRequest::GetAccountTxs(address, resp) => {
+                    let txs = self.mempool_state.read().await.account_txs(address);
+                    resp.send(txs).unwrap_or_default();
+                }
             }
         }
     }
@@ -529,7 +960,7 @@ impl MempoolTransactionsHandler {
 pub fn run_mempool_tasks(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
-    block_requests: mpsc::Receiver<MempoolBlocksRequest>,
+    block_requests: mpsc::Receiver<Traced<MempoolBlocksRequest>>,
     eth_watch_req: mpsc::Sender<EthWatchRequest>,
     config: &ZkSyncConfig,
     number_of_mempool_transaction_handlers: u8,
@@ -537,11 +968,12 @@ pub fn run_mempool_tasks(
 ) -> JoinHandle<()> {
     let config = config.clone();
     tokio::spawn(async move {
-        let mempool_state = Arc::new(RwLock::new(MempoolState::restore_from_db(&db_pool).await));
+        let mempool_state = Arc::new(RwLock::new(
+            MempoolState::restore_from_db(&db_pool, &config.chain.mempool).await,
+        ));
         let max_block_size_chunks = *config
             .chain
-            .state_keeper
-            .block_chunk_sizes
+            .block_chunk_sizes()
             .iter()
             .max()
             .expect("failed to find max block chunks size");
@@ -568,8 +1000,279 @@ pub fn run_mempool_tasks(
             requests: block_requests,
             eth_watch_req,
             max_block_size_chunks,
+            chunks_quotas: ChunksQuotas::from_config(&config.chain.state_keeper),
+            chunks_usage: ChunksUsage::default(),
+            chunks_usage_block: BlockNumber(0),
         };
         tasks.push(tokio::spawn(blocks_handler.run()));
         wait_for_tasks(tasks).await
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{TokenId, Transfer, Withdraw};
+
+    fn transfer() -> ZkSyncTx {
+        Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(0),
+            None,
+        )
+        .into()
+    }
+
+    fn withdraw() -> ZkSyncTx {
+        Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(0),
+            None,
+        )
+        .into()
+    }
+
+    #[test]
+    fn chunks_quotas() {
+        let quotas = ChunksQuotas {
+            transfer: Some(4),
+            ..Default::default()
+        };
+        let transfer = transfer();
+        let withdraw = withdraw();
+
+        let mut usage = ChunksUsage::default();
+        assert!(usage.fits(&quotas, &[(&transfer, 2), (&transfer, 2)]));
+        assert!(!usage.fits(&quotas, &[(&transfer, 2), (&transfer, 3)]));
+
+        usage.add(&[(&transfer, 2), (&transfer, 2)]);
+        assert!(!usage.fits(&quotas, &[(&transfer, 1)]));
+        // Withdrawals are not limited.
+        assert!(usage.fits(&quotas, &[(&withdraw, 100)]));
+    }
+
+    /// Checks that at least one transaction of the type fits into the block,
+    /// even if its quota is smaller than the transaction.
+    #[test]
+    fn chunks_quotas_smaller_than_tx() {
+        let quotas = ChunksQuotas {
+            withdraw: Some(1),
+            ..Default::default()
+        };
+        let withdraw = withdraw();
+
+        let mut usage = ChunksUsage::default();
+        assert!(usage.fits(&quotas, &[(&withdraw, 6)]));
+
+        // Several withdrawals of a batch still have to fit into the quota.
+        assert!(!usage.fits(&quotas, &[(&withdraw, 6), (&withdraw, 6)]));
+
+        usage.add(&[(&withdraw, 6)]);
+        assert!(!usage.fits(&quotas, &[(&withdraw, 6)]));
+    }
+
+    /// Checks that the quotas are applied to all the miniblocks of the pending block,
+    /// and the usage is reset once the block is sealed.
+    #[tokio::test]
+    async fn chunks_quotas_across_miniblocks() {
+        let transfer_from = |byte: u8| -> SignedTxVariant {
+            let mut tx = transfer();
+            if let ZkSyncTx::Transfer(tx) = &mut tx {
+                tx.from = Address::repeat_byte(byte);
+            }
+            SignedZkSyncTx::from(tx).into()
+        };
+        let mempool_state = MempoolState {
+            account_nonces: HashMap::new(),
+            account_ids: HashMap::new(),
+            ready_txs: ReadyTxs::default(),
+            max_nonce_lookahead: None,
+            max_pending_txs_per_account: 10,
+        };
+        let mut handler = MempoolBlocksHandler {
+            mempool_state: Arc::new(RwLock::new(mempool_state)),
+            requests: mpsc::channel(1).1,
+            eth_watch_req: mpsc::channel(1).0,
+            max_block_size_chunks: 100,
+            // Two transfers to the new accounts per block.
+            chunks_quotas: ChunksQuotas {
+                transfer: Some(TransferToNewOp::CHUNKS * 2),
+                ..Default::default()
+            },
+            chunks_usage: ChunksUsage::default(),
+            chunks_usage_block: BlockNumber(0),
+        };
+        let mempool_state = handler.mempool_state.clone();
+        let add_txs = |txs: Vec<SignedTxVariant>| {
+            let mempool_state = mempool_state.clone();
+            async move { mempool_state.write().await.ready_txs.extend(txs) }
+        };
+
+        add_txs(vec![transfer_from(3)]).await;
+        let (_, txs) = handler.prepare_tx_for_block(100, BlockNumber(1)).await;
+        assert_eq!(txs.len(), 1);
+
+        // The second miniblock of the same block takes only one transfer.
+        add_txs(vec![transfer_from(4), transfer_from(5)]).await;
+        let (_, txs) = handler.prepare_tx_for_block(100, BlockNumber(1)).await;
+        assert_eq!(txs.len(), 1);
+        let (_, txs) = handler.prepare_tx_for_block(100, BlockNumber(1)).await;
+        assert!(txs.is_empty());
+
+        // The skipped transfer goes to the next block.
+        let (_, txs) = handler.prepare_tx_for_block(100, BlockNumber(2)).await;
+        assert_eq!(txs.len(), 1);
+        assert_eq!(mempool_state.read().await.ready_txs.len(), 0);
+    }
+
+    #[test]
+    fn evict_txs() {
+        let transfer: SignedZkSyncTx = transfer().into();
+        let withdraw: SignedZkSyncTx = withdraw().into();
+        let mut other_transfer = transfer.clone();
+        if let ZkSyncTx::Transfer(tx) = &mut other_transfer.tx {
+            tx.from = Address::repeat_byte(3);
+        }
+
+        let mut state = MempoolState {
+            account_nonces: HashMap::new(),
+            account_ids: HashMap::new(),
+            ready_txs: VecDeque::from(vec![
+                other_transfer.clone().into(),
+                SignedTxVariant::batch(vec![transfer.clone(), withdraw.clone()], 1, None),
+            ])
+            .into(),
+            max_nonce_lookahead: None,
+            max_pending_txs_per_account: 10,
+        };
+
+        assert_eq!(
+            state.ready_txs.pending_chunks(),
+            TransferOp::CHUNKS * 2 + withdraw.tx.min_chunks()
+        );
+
+        // The whole batch is evicted along with the transaction.
+        let tx_hashes = state.txs_to_evict(EvictionFilter::Tx(withdraw.hash()));
+        assert_eq!(tx_hashes, vec![transfer.hash(), withdraw.hash()]);
+
+        // Only the transactions of the account are taken from the batch.
+        assert_eq!(
+            state.account_txs(transfer.account()),
+            vec![transfer.tx.clone()]
+        );
+        assert_eq!(
+            state.account_txs(withdraw.account()),
+            vec![withdraw.tx.clone()]
+        );
+
+        let tx_hashes = state.txs_to_evict(EvictionFilter::Account(other_transfer.account()));
+        assert_eq!(tx_hashes, vec![other_transfer.hash()]);
+        state.remove_txs(&tx_hashes);
+        assert_eq!(state.ready_txs.len(), 1);
+
+        let tx_hashes = state.txs_to_evict(EvictionFilter::Account(transfer.account()));
+        state.remove_txs(&tx_hashes);
+        assert_eq!(state.ready_txs.len(), 0);
+        assert_eq!(state.ready_txs.pending_txs(&transfer.account()), 0);
+    }
+
+    /// Checks that the nonces are limited only if the lookahead is configured.
+    #[test]
+    fn nonce_lookahead() {
+        let with_nonce = |nonce: u32| {
+            let mut tx = transfer();
+            if let ZkSyncTx::Transfer(tx) = &mut tx {
+                tx.nonce = Nonce(nonce);
+            }
+            SignedZkSyncTx::from(tx)
+        };
+        let mut state = MempoolState {
+            account_nonces: HashMap::new(),
+            account_ids: HashMap::new(),
+            ready_txs: ReadyTxs::default(),
+            max_nonce_lookahead: None,
+            max_pending_txs_per_account: 10,
+        };
+        state.account_nonces.insert(transfer().account(), Nonce(5));
+
+        assert!(matches!(
+            state.check_nonce(&with_nonce(4)),
+            Err(TxAddError::NonceMismatch)
+        ));
+        assert!(state.check_nonce(&with_nonce(u32::MAX)).is_ok());
+
+        state.max_nonce_lookahead = Some(10);
+        assert!(state.check_nonce(&with_nonce(15)).is_ok());
+        assert!(matches!(
+            state.check_nonce(&with_nonce(16)),
+            Err(TxAddError::NonceTooHigh {
+                min_nonce: Nonce(5),
+                max_nonce: Nonce(15),
+            })
+        ));
+    }
+
+    #[test]
+    fn pending_txs_limit() {
+        let with_nonce = |mut tx: ZkSyncTx, nonce: u32| {
+            match &mut tx {
+                ZkSyncTx::Transfer(tx) => tx.nonce = Nonce(nonce),
+                ZkSyncTx::Withdraw(tx) => tx.nonce = Nonce(nonce),
+                _ => unreachable!(),
+            }
+            SignedZkSyncTx::from(tx)
+        };
+        let mut other_transfer = transfer();
+        if let ZkSyncTx::Transfer(tx) = &mut other_transfer {
+            tx.from = Address::repeat_byte(3);
+        }
+
+        let mut state = MempoolState {
+            account_nonces: HashMap::new(),
+            account_ids: HashMap::new(),
+            ready_txs: VecDeque::from(vec![SignedTxVariant::batch(
+                vec![with_nonce(transfer(), 0), with_nonce(withdraw(), 1)],
+                1,
+                None,
+            )])
+            .into(),
+            max_nonce_lookahead: Some(10),
+            max_pending_txs_per_account: 3,
+        };
+
+        // Batch exceeding the limit is rejected as a whole.
+        let batch = SignedTxsBatch {
+            txs: vec![with_nonce(transfer(), 2), with_nonce(transfer(), 3)],
+            batch_id: 2,
+            eth_signature: None,
+        };
+        assert!(matches!(
+            state.add_batch(batch),
+            Err(TxAddError::TooManyPendingTxs { limit: 3 })
+        ));
+        assert_eq!(state.ready_txs.len(), 1);
+
+        state.add_tx(with_nonce(transfer(), 2)).unwrap();
+        assert!(matches!(
+            state.add_tx(with_nonce(transfer(), 3)),
+            Err(TxAddError::TooManyPendingTxs { limit: 3 })
+        ));
+        // Other accounts are not affected.
+        state.add_tx(with_nonce(other_transfer, 0)).unwrap();
+
+        // Transactions taken from the queue are not counted anymore.
+        state.ready_txs.pop_front().unwrap();
+        assert_eq!(state.ready_txs.pending_txs(&transfer().account()), 1);
+        state.add_tx(with_nonce(transfer(), 3)).unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+//! gRPC transport of the private API.
+//!
+//! Serves the requests adding the transactions into the mempool, the same way the
+//! `new_tx` and `new_txs_batch` HTTP endpoints do. The requests are signed the same way
+//! as the HTTP ones, the signature is passed in the metadata and covers the protobuf
+//! encoding of the request message.
+
+use super::{trace_txs, AppState, MempoolResponse};
+use crate::mempool::{MempoolTransactionRequest, TxAddError};
+use std::{convert::TryFrom, net::SocketAddr, sync::Arc};
+use tonic::{metadata::MetadataMap, transport::Server, Request, Response, Status};
+use tracing::Instrument;
+use vlog::TraceContext;
+use zksync_core_api_grpc::{
+    auth::{
+        message_body, RequestSignature, RequestVerifier, NONCE_HEADER, SIGNATURE_HEADER,
+        TIMESTAMP_HEADER,
+    },
+    v1::{
+        self,
+        core_api_server::{CoreApi, CoreApiServer},
+        tx_add_error, tx_add_result,
+    },
+};
+use zksync_types::{tx::TxEthSignature, SignedZkSyncTx};
+
+impl From<TxAddError> for v1::TxAddError {
+    fn from(err: TxAddError) -> Self {
+        let mut error = v1::TxAddError::default();
+        let code = match err {
+            TxAddError::NonceMismatch => tx_add_error::Code::NonceMismatch,
+            TxAddError::NonceTooHigh {
+                min_nonce,
+                max_nonce,
+            } => {
+                error.min_nonce = *min_nonce;
+                error.max_nonce = *max_nonce;
+                tx_add_error::Code::NonceTooHigh
+            }
+            TxAddError::IncorrectTx => tx_add_error::Code::IncorrectTx,
+            TxAddError::TxFeeTooLow => tx_add_error::Code::TxFeeTooLow,
+            TxAddError::TxBatchFeeTooLow => tx_add_error::Code::TxBatchFeeTooLow,
+            TxAddError::EIP1271SignatureVerificationFail => {
+                tx_add_error::Code::Eip1271SignatureVerificationFail
+            }
+            TxAddError::MissingEthSignature => tx_add_error::Code::MissingEthSignature,
+            TxAddError::IncorrectEthSignature => tx_add_error::Code::IncorrectEthSignature,
+            TxAddError::ChangePkNotAuthorized => tx_add_error::Code::ChangePkNotAuthorized,
+            TxAddError::Other => tx_add_error::Code::Other,
+            TxAddError::DbError => tx_add_error::Code::DbError,
+            TxAddError::EmptyBatch => tx_add_error::Code::EmptyBatch,
+            TxAddError::BatchTooBig => tx_add_error::Code::BatchTooBig,
+            TxAddError::BatchWithdrawalsOverload => tx_add_error::Code::BatchWithdrawalsOverload,
+            TxAddError::TooManyPendingTxs { limit } => {
+                error.limit = limit as u64;
+                tx_add_error::Code::TooManyPendingTxs
+            }
+        };
+        error.set_code(code);
+        error
+    }
+}
+
+/// Checks the signature of the request to the method with the given path,
+/// `body` is the encoded request message.
+fn check_signature(
+    verifier: &RequestVerifier,
+    metadata: &MetadataMap,
+    path: &str,
+    body: &[u8],
+) -> Result<(), Status> {
+    let value = |key| metadata.get(key).and_then(|value| value.to_str().ok());
+    RequestSignature::from_headers(
+        value(TIMESTAMP_HEADER),
+        value(NONCE_HEADER),
+        value(SIGNATURE_HEADER),
+    )
+    .and_then(|signature| verifier.verify(&signature, "POST", path, body))
+    .map_err(|err| {
+        vlog::warn!("Unauthorized request to the private gRPC API: {}", err);
+        Status::unauthenticated(err.to_string())
+    })
+}
+
+/// Returns the context of the API server trace the request was sent within.
+fn trace_context(metadata: &MetadataMap) -> TraceContext {
+    TraceContext::from_headers(|name| Some(metadata.get(name)?.to_str().ok()?.to_owned()))
+}
+
+fn decode_tx(tx: Option<v1::SignedTx>) -> Result<SignedZkSyncTx, Status> {
+    tx.ok_or_else(|| anyhow::format_err!("Transaction is not set"))
+        .and_then(SignedZkSyncTx::try_from)
+        .map_err(|err| Status::invalid_argument(format!("Malformed request: {}", err)))
+}
+
+/// Zero outbox ID means the request is not stored in the outbox.
+fn outbox_id(id: i64) -> Option<i64> {
+    Some(id).filter(|&id| id != 0)
+}
+
+fn encode_result(response: MempoolResponse) -> Result<v1::TxAddResult, Status> {
+    let outcome = match response {
+        MempoolResponse::Processed(Ok(())) => tx_add_result::Outcome::Accepted(v1::Accepted {}),
+        MempoolResponse::Processed(Err(err)) => tx_add_result::Outcome::Error(err.into()),
+        MempoolResponse::InProgress => {
+            return Err(Status::aborted("Request is being processed right now"))
+        }
+        MempoolResponse::Unknown => return Err(Status::not_found("Request is not in the outbox")),
+    };
+
+    Ok(v1::TxAddResult {
+        outcome: Some(outcome),
+    })
+}
+
+async fn process_tx(
+    state: &AppState,
+    request: v1::SendTxRequest,
+    trace_context: TraceContext,
+) -> Result<v1::TxAddResult, Status> {
+    let tx = decode_tx(request.tx)?;
+    let span = tracing::info_span!("new_tx", tx_hash = %tx.tx.hash().to_string());
+    trace_txs(&span, &trace_context, std::iter::once(&tx));
+
+    let response = state
+        .process_mempool_request(outbox_id(request.outbox_id), |sender| {
+            MempoolTransactionRequest::NewTx(Box::new(tx), sender)
+        })
+        .instrument(span)
+        .await
+        .map_err(|err| {
+            vlog::warn!("Failed to process the mempool request: {}", err);
+            Status::internal("Failed to process the request")
+        })?;
+
+    encode_result(response)
+}
+
+async fn process_txs_batch(
+    state: &AppState,
+    request: v1::SendTxsBatchRequest,
+    trace_context: TraceContext,
+) -> Result<v1::TxAddResult, Status> {
+    let txs = request
+        .txs
+        .into_iter()
+        .map(|tx| decode_tx(Some(tx)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let eth_signature = request
+        .eth_signature
+        .map(TxEthSignature::try_from)
+        .transpose()
+        .map_err(|err| Status::invalid_argument(format!("Malformed request: {}", err)))?;
+    let span = tracing::info_span!("new_txs_batch", txs = txs.len());
+    trace_txs(&span, &trace_context, &txs);
+
+    let response = state
+        .process_mempool_request(outbox_id(request.outbox_id), |sender| {
+            MempoolTransactionRequest::NewTxsBatch(txs, eth_signature, sender)
+        })
+        .instrument(span)
+        .await
+        .map_err(|err| {
+            vlog::warn!("Failed to process the mempool request: {}", err);
+            Status::internal("Failed to process the request")
+        })?;
+
+    encode_result(response)
+}
+
+#[derive(Debug)]
+struct CoreApiService {
+    state: AppState,
+    verifier: Arc<RequestVerifier>,
+}
+
+#[tonic::async_trait]
+impl CoreApi for CoreApiService {
+    async fn send_tx(
+        &self,
+        request: Request<v1::SendTxRequest>,
+    ) -> Result<Response<v1::TxAddResult>, Status> {
+        check_signature(
+            &self.verifier,
+            request.metadata(),
+            v1::SEND_TX_PATH,
+            &message_body(request.get_ref()),
+        )?;
+        let trace_context = trace_context(request.metadata());
+        process_tx(&self.state, request.into_inner(), trace_context)
+            .await
+            .map(Response::new)
+    }
+
+    async fn send_txs_batch(
+        &self,
+        request: Request<v1::SendTxsBatchRequest>,
+    ) -> Result<Response<v1::TxAddResult>, Status> {
+        check_signature(
+            &self.verifier,
+            request.metadata(),
+            v1::SEND_TXS_BATCH_PATH,
+            &message_body(request.get_ref()),
+        )?;
+        let trace_context = trace_context(request.metadata());
+        process_txs_batch(&self.state, request.into_inner(), trace_context)
+            .await
+            .map(Response::new)
+    }
+}
+
+/// Runs the gRPC server of the private API until it fails.
+pub(super) async fn run_server(
+    bind_addr: SocketAddr,
+    state: AppState,
+    verifier: Arc<RequestVerifier>,
+) -> Result<(), tonic::transport::Error> {
+    Server::builder()
+        .add_service(CoreApiServer::new(CoreApiService { state, verifier }))
+        .serve(bind_addr)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::Nonce;
+
+    #[test]
+    fn requests_authorization() {
+        let verifier = RequestVerifier::new("secret".to_owned());
+        let message = v1::SendTxRequest {
+            outbox_id: 1,
+            tx: None,
+        };
+        let body = message_body(&message);
+        let signature = RequestSignature::new("secret", "POST", v1::SEND_TX_PATH, &body);
+
+        let requests = vec![
+            (None, v1::SEND_TX_PATH, false),
+            (
+                Some(RequestSignature::new(
+                    "other secret",
+                    "POST",
+                    v1::SEND_TX_PATH,
+                    &body,
+                )),
+                v1::SEND_TX_PATH,
+                false,
+            ),
+            (Some(signature.clone()), v1::SEND_TXS_BATCH_PATH, false),
+            (Some(signature.clone()), v1::SEND_TX_PATH, true),
+            // The same request is not accepted twice.
+            (Some(signature), v1::SEND_TX_PATH, false),
+        ];
+        for (signature, path, authorized) in requests {
+            let mut metadata = MetadataMap::new();
+            if let Some(signature) = signature {
+                for (key, value) in signature.headers().iter() {
+                    metadata.insert(*key, value.parse().unwrap());
+                }
+            }
+
+            assert_eq!(
+                check_signature(&verifier, &metadata, path, &body).is_ok(),
+                authorized
+            );
+        }
+    }
+
+    #[test]
+    fn tx_add_error_conversion() {
+        let error = v1::TxAddError::from(TxAddError::NonceTooHigh {
+            min_nonce: Nonce(1),
+            max_nonce: Nonce(5),
+        });
+        assert_eq!(error.code(), tx_add_error::Code::NonceTooHigh);
+        assert_eq!((error.min_nonce, error.max_nonce), (1, 5));
+
+        let error = v1::TxAddError::from(TxAddError::TooManyPendingTxs { limit: 100 });
+        assert_eq!(error.code(), tx_add_error::Code::TooManyPendingTxs);
+        assert_eq!(error.limit, 100);
+    }
+}
@@ -0,0 +1,663 @@
+//! zkSync core private API server.
+//!
+//! This file contains endpoint expected to be used by
+//! other components of zkSync stack **only**. This API must not be
+//! available from outside of the cluster.
+//!
+//! All the incoming data is assumed to be correct and not double-checked
+//! for correctness. Because of that, every request must be signed with the secret
+//! shared with the API server (see `zksync_core_api_grpc::auth`), so the requests
+//! can be neither forged nor replayed, and the server should be bound to an internal
+//! network interface.
+
+use crate::{
+    eth_watch::{EthWatchRequest, PriorityQueueFilter},
+    mempool::{EvictionFilter, MempoolTransactionRequest, TxAddError},
+    state_keeper::StateKeeperRequest,
+    tx_traces,
+};
+use actix_web::{
+    dev::{Payload, PayloadStream, Service, ServiceRequest, ServiceResponse, Transform},
+    web, App, HttpMessage, HttpRequest, HttpResponse, HttpServer,
+};
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, LocalBoxFuture},
+    sink::SinkExt,
+    stream, FutureExt, StreamExt,
+};
+use serde::Deserialize;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll},
+    thread,
+};
+use tracing::{Instrument, Span};
+use vlog::TraceContext;
+use zksync_config::configs::api::PrivateApi;
+use zksync_core_api_grpc::auth::{
+    AuthError, RequestSignature, RequestVerifier, NONCE_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use zksync_storage::{outbox::OutboxClaim, ConnectionPool};
+use zksync_types::{
+    tx::{TxEthSignature, TxHash},
+    Address, SignedZkSyncTx, H256,
+};
+use zksync_utils::panic_notify::ThreadPanicNotify;
+
+mod grpc;
+mod outbox;
+
+#[derive(Debug, Clone)]
+struct AppState {
+    connection_pool: ConnectionPool,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
+}
+
+/// Returns the context of the API server trace the request was sent within.
+fn trace_context(req: &HttpRequest) -> TraceContext {
+    TraceContext::from_headers(|name| Some(req.headers().get(name)?.to_str().ok()?.to_owned()))
+}
+
+/// Makes the span of the request adding the transactions continue the trace of the API server,
+/// and remembers its context, so the execution of the transactions continues the same trace.
+fn trace_txs<'a>(
+    span: &Span,
+    parent: &TraceContext,
+    txs: impl IntoIterator<Item = &'a SignedZkSyncTx>,
+) {
+    parent.attach_to(span);
+    let context = TraceContext::of(span);
+    for tx in txs {
+        tx_traces::remember(tx.tx.hash(), context.clone());
+    }
+}
+
+/// Checks the signature of the HTTP request with the given body.
+fn check_request_signature(
+    verifier: &RequestVerifier,
+    req: &HttpRequest,
+    body: &[u8],
+) -> Result<(), AuthError> {
+    let header = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let signature = RequestSignature::from_headers(
+        header(TIMESTAMP_HEADER),
+        header(NONCE_HEADER),
+        header(SIGNATURE_HEADER),
+    )?;
+    let path = req
+        .uri()
+        .path_and_query()
+        .map_or_else(|| req.path(), |path| path.as_str());
+
+    verifier.verify(&signature, req.method().as_str(), path, body)
+}
+
+/// Middleware rejecting the requests which are not signed by the API server.
+///
+/// The signature covers the body, so the whole body is read before the check
+/// and then passed to the handler.
+struct SignatureAuth(Arc<RequestVerifier>);
+
+impl<S, B> Transform<S> for SignatureAuth
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>
+        + 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type InitError = ();
+    type Transform = SignatureAuthMiddleware<S>;
+    type Future = future::Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        future::ok(SignatureAuthMiddleware {
+            service: Rc::new(RefCell::new(service)),
+            verifier: self.0.clone(),
+        })
+    }
+}
+
+struct SignatureAuthMiddleware<S> {
+    service: Rc<RefCell<S>>,
+    verifier: Arc<RequestVerifier>,
+}
+
+impl<S, B> Service for SignatureAuthMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>
+        + 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let verifier = self.verifier.clone();
+
+        async move {
+            let mut body = web::BytesMut::new();
+            let mut payload = req.take_payload();
+            while let Some(chunk) = payload.next().await {
+                body.extend_from_slice(&chunk?);
+            }
+            let body = body.freeze();
+
+            if let Err(err) = check_request_signature(&verifier, req.request(), &body) {
+                vlog::warn!("Unauthorized request to the private API: {}", err);
+                return Err(actix_web::error::ErrorUnauthorized(err));
+            }
+
+            let payload: PayloadStream = Box::pin(stream::once(future::ok(body)));
+            req.set_payload(Payload::Stream(payload));
+            let response = service.borrow_mut().call(req);
+            response.await
+        }
+        .boxed_local()
+    }
+}
+
+/// Query of the requests forwarded by the API server through the outbox.
+#[derive(Debug, Deserialize)]
+struct OutboxQuery {
+    /// ID of the request in the outbox, if set the request is processed exactly once.
+    outbox_id: Option<i64>,
+}
+
+/// Result of the mempool request forwarded by the API server.
+#[derive(Debug)]
+enum MempoolResponse {
+    /// The request is processed, either right now or during the previous delivery.
+    Processed(Result<(), TxAddError>),
+    /// The request with the same outbox ID is being processed right now.
+    InProgress,
+    /// The request doesn't exist in the outbox: either it was abandoned by the API server
+    /// or removed long after being processed.
+    Unknown,
+}
+
+impl AppState {
+    /// Sends the request to the mempool and returns the result of its processing.
+    ///
+    /// Requests forwarded through the outbox are claimed before being processed, and their results
+    /// are stored, so the repeated delivery of the same request returns the stored result.
+    async fn process_mempool_request(
+        &self,
+        outbox_id: Option<i64>,
+        request: impl FnOnce(oneshot::Sender<Result<(), TxAddError>>) -> MempoolTransactionRequest,
+    ) -> anyhow::Result<MempoolResponse> {
+        if let Some(outbox_id) = outbox_id {
+            let claim = self
+                .connection_pool
+                .access_storage()
+                .await?
+                .outbox_schema()
+                .claim_request(outbox_id)
+                .await?;
+
+            match claim {
+                OutboxClaim::Claimed => {}
+                OutboxClaim::Processed(result) => {
+                    return Ok(MempoolResponse::Processed(serde_json::from_value(result)?))
+                }
+                OutboxClaim::InProgress => return Ok(MempoolResponse::InProgress),
+                OutboxClaim::Unknown => return Ok(MempoolResponse::Unknown),
+            }
+        }
+
+        let response = self.send_to_mempool(request).await?;
+        if let Some(outbox_id) = outbox_id {
+            self.store_outbox_result(outbox_id, &response).await?;
+        }
+
+        Ok(MempoolResponse::Processed(response))
+    }
+
+    /// Sends the request to the mempool and waits for the result of its processing.
+    async fn send_to_mempool(
+        &self,
+        request: impl FnOnce(oneshot::Sender<Result<(), TxAddError>>) -> MempoolTransactionRequest,
+    ) -> anyhow::Result<Result<(), TxAddError>> {
+        let (sender, receiver) = oneshot::channel();
+        let mut mempool_sender = self.mempool_tx_sender.clone();
+        mempool_sender.send(request(sender)).await?;
+
+        Ok(receiver.await?)
+    }
+
+    /// Stores the result of the outbox request, so its repeated deliveries get the same result.
+    async fn store_outbox_result(
+        &self,
+        outbox_id: i64,
+        result: &Result<(), TxAddError>,
+    ) -> anyhow::Result<()> {
+        let result = serde_json::to_value(result).expect("Unable to serialize TxAddError");
+        self.connection_pool
+            .access_storage()
+            .await?
+            .outbox_schema()
+            .store_result(outbox_id, result)
+            .await
+    }
+
+    /// Sends the request to the mempool and returns a JSON representation of the result.
+    async fn mempool_request(
+        &self,
+        outbox_id: Option<i64>,
+        request: impl FnOnce(oneshot::Sender<Result<(), TxAddError>>) -> MempoolTransactionRequest,
+    ) -> actix_web::Result<HttpResponse> {
+        let response = self
+            .process_mempool_request(outbox_id, request)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Failed to process the mempool request: {}", err);
+                HttpResponse::InternalServerError().finish()
+            })?;
+
+        Ok(match response {
+            MempoolResponse::Processed(result) => HttpResponse::Ok().json(result),
+            MempoolResponse::InProgress => HttpResponse::Conflict().finish(),
+            MempoolResponse::Unknown => HttpResponse::Gone().finish(),
+        })
+    }
+}
+
+/// Adds a new transaction into the mempool.
+/// Returns a JSON representation of `Result<(), TxAddError>`.
+/// Expects transaction to be checked on the API side.
+#[actix_web::post("/new_tx")]
+async fn new_tx(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Json(tx): web::Json<SignedZkSyncTx>,
+    web::Query(query): web::Query<OutboxQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let span = tracing::info_span!("new_tx", tx_hash = %tx.tx.hash().to_string());
+    trace_txs(&span, &trace_context(&req), std::iter::once(&tx));
+
+    data.mempool_request(query.outbox_id, |sender| {
+        MempoolTransactionRequest::NewTx(Box::new(tx), sender)
+    })
+    .instrument(span)
+    .await
+}
+
+/// Adds a new transactions batch into the mempool.
+/// Returns a JSON representation of `Result<(), TxAddError>`.
+/// Expects transaction to be checked on the API side.
+#[actix_web::post("/new_txs_batch")]
+async fn new_txs_batch(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    web::Json((txs, eth_signature)): web::Json<(Vec<SignedZkSyncTx>, Option<TxEthSignature>)>,
+    web::Query(query): web::Query<OutboxQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let span = tracing::info_span!("new_txs_batch", txs = txs.len());
+    trace_txs(&span, &trace_context(&req), &txs);
+
+    data.mempool_request(query.outbox_id, |sender| {
+        MempoolTransactionRequest::NewTxsBatch(txs, eth_signature, sender)
+    })
+    .instrument(span)
+    .await
+}
+
+/// Obtains information about unconfirmed deposits known for a certain address.
+#[actix_web::get("/unconfirmed_deposits/{address}")]
+async fn unconfirmed_deposits(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = EthWatchRequest::GetUnconfirmedDeposits {
+        address,
+        resp: sender,
+    };
+    let mut eth_watch_sender = data.eth_watch_req_sender.clone();
+    eth_watch_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Obtains information about unconfirmed operations known for a certain address.
+#[actix_web::get("/unconfirmed_ops/{address}")]
+async fn unconfirmed_ops(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = EthWatchRequest::GetUnconfirmedOps {
+        address,
+        resp: sender,
+    };
+    let mut eth_watch_sender = data.eth_watch_req_sender.clone();
+    eth_watch_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Obtains information about unconfirmed deposits known for a certain address.
+#[actix_web::get("/unconfirmed_op/{tx_hash}")]
+async fn unconfirmed_op(
+    data: web::Data<AppState>,
+    web::Path(eth_hash): web::Path<H256>,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = EthWatchRequest::GetUnconfirmedOpByHash {
+        eth_hash: eth_hash.as_ref().to_vec(),
+        resp: sender,
+    };
+    let mut eth_watch_sender = data.eth_watch_req_sender.clone();
+    eth_watch_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Obtains the priority operations known to the Ethereum watcher, but not yet executed.
+async fn priority_queue_for(
+    eth_watch_req_sender: &mpsc::Sender<EthWatchRequest>,
+    filter: PriorityQueueFilter,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = EthWatchRequest::GetPriorityQueue {
+        filter,
+        resp: sender,
+    };
+    let mut eth_watch_sender = eth_watch_req_sender.clone();
+    eth_watch_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Obtains the whole priority operations queue.
+#[actix_web::get("/priority_queue")]
+async fn priority_queue(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    priority_queue_for(&data.eth_watch_req_sender, PriorityQueueFilter::All).await
+}
+
+/// Obtains the priority operations queue for a certain address.
+#[actix_web::get("/priority_queue/{address}")]
+async fn priority_queue_by_address(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    priority_queue_for(
+        &data.eth_watch_req_sender,
+        PriorityQueueFilter::InitiatedBy(address),
+    )
+    .await
+}
+
+/// Obtains the not yet executed priority operations moving funds to a certain address.
+#[actix_web::get("/priority_queue/{address}/incoming")]
+async fn incoming_priority_ops(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    priority_queue_for(
+        &data.eth_watch_req_sender,
+        PriorityQueueFilter::IncomingTo(address),
+    )
+    .await
+}
+
+/// Removes the transactions matching the filter from the mempool.
+async fn evict_txs(
+    mempool_tx_sender: &mpsc::Sender<MempoolTransactionRequest>,
+    filter: EvictionFilter,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let mut mempool_sender = mempool_tx_sender.clone();
+    mempool_sender
+        .send(MempoolTransactionRequest::EvictTxs(filter, sender))
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let evicted = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?
+        .map_err(|err| {
+            vlog::warn!("Failed to evict transactions from the mempool: {}", err);
+            HttpResponse::InternalServerError().finish()
+        })?;
+
+    Ok(HttpResponse::Ok().json(evicted))
+}
+
+/// Removes the transaction from the mempool, along with the batch it belongs to.
+/// Returns the hashes of the removed transactions.
+#[actix_web::post("/evict_tx/{tx_hash}")]
+async fn evict_tx(
+    data: web::Data<AppState>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> actix_web::Result<HttpResponse> {
+    evict_txs(&data.mempool_tx_sender, EvictionFilter::Tx(tx_hash)).await
+}
+
+/// Removes all the transactions of the account from the mempool, along with the batches
+/// containing them. Returns the hashes of the removed transactions.
+#[actix_web::post("/evict_account_txs/{address}")]
+async fn evict_account_txs(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    evict_txs(&data.mempool_tx_sender, EvictionFilter::Account(address)).await
+}
+
+/// Obtains the transactions of the account waiting in the mempool, in the order of execution.
+/// Transactions of the pending block are not included.
+#[actix_web::get("/queued_txs/{address}")]
+async fn queued_txs(
+    data: web::Data<AppState>,
+    web::Path(address): web::Path<Address>,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let mut mempool_sender = data.mempool_tx_sender.clone();
+    mempool_sender
+        .send(MempoolTransactionRequest::GetAccountTxs(address, sender))
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let txs = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(txs))
+}
+
+/// Makes the state keeper seal the pending block without waiting for the block timeout.
+/// Nothing happens if the pending block is empty.
+#[actix_web::post("/seal_block")]
+async fn seal_block(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut state_keeper_sender = data.state_keeper_req_sender.clone();
+    state_keeper_sender
+        .send(StateKeeperRequest::SealNonEmptyBlock)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(()))
+}
+
+/// Responds once the server is running, used by the API servers to check that the Core is reachable.
+#[actix_web::get("/ping")]
+async fn ping() -> HttpResponse {
+    HttpResponse::Ok().json(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn start_private_core_api(
+    connection_pool: ConnectionPool,
+    panic_notify: mpsc::Sender<bool>,
+    mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
+    config: PrivateApi,
+) {
+    thread::Builder::new()
+        .name("core-private-api".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_notify.clone());
+            let mut actix_runtime = actix_rt::System::new("core-private-api-server");
+
+            actix_runtime.block_on(async move {
+                let bind_addr = config.bind_addr();
+                let grpc_bind_addr = config.grpc_bind_addr();
+                let verifier = Arc::new(RequestVerifier::new(config.secret_auth));
+
+                let app_state = AppState {
+                    connection_pool,
+                    mempool_tx_sender,
+                    eth_watch_req_sender,
+                    state_keeper_req_sender,
+                };
+
+                actix_rt::spawn(outbox::run_outbox_processor(app_state.clone()));
+
+                // Start HTTP server.
+                let http_server = {
+                    let app_state = app_state.clone();
+                    let verifier = verifier.clone();
+
+                    HttpServer::new(move || {
+                        // By calling `register_data` instead of `data` we're avoiding double
+                        // `Arc` wrapping of the object.
+                        App::new()
+                            .wrap(SignatureAuth(verifier.clone()))
+                            .wrap(actix_web::middleware::Logger::default())
+                            .app_data(web::Data::new(app_state.clone()))
+                            .service(new_tx)
+                            .service(new_txs_batch)
+                            .service(unconfirmed_op)
+                            .service(unconfirmed_ops)
+                            .service(unconfirmed_deposits)
+                            .service(priority_queue)
+                            .service(priority_queue_by_address)
+                            .service(incoming_priority_ops)
+                            .service(seal_block)
+                            .service(evict_tx)
+                            .service(evict_account_txs)
+                            .service(queued_txs)
+                            .service(ping)
+                    })
+                    .bind(&bind_addr)
+                    .expect("failed to bind")
+                    .run()
+                };
+
+                // Start gRPC server along with the HTTP one, if it's enabled.
+                match grpc_bind_addr {
+                    Some(grpc_bind_addr) => {
+                        let grpc_server = grpc::run_server(grpc_bind_addr, app_state, verifier);
+                        tokio::select! {
+                            result = http_server => result,
+                            result = grpc_server => {
+                                result.expect("gRPC server failed");
+                                Ok(())
+                            }
+                        }
+                    }
+                    None => http_server.await,
+                }
+            })
+        })
+        .expect("failed to start prover server");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test};
+
+    #[actix_rt::test]
+    async fn requests_authorization() {
+        let verifier = Arc::new(RequestVerifier::new("secret".to_owned()));
+        let mut app = test::init_service(App::new().wrap(SignatureAuth(verifier)).route(
+            "/",
+            web::post().to(|body: String| HttpResponse::Ok().body(body)),
+        ))
+        .await;
+
+        let signature = RequestSignature::new("secret", "POST", "/", b"body");
+        let requests = vec![
+            (None, "body", StatusCode::UNAUTHORIZED),
+            (
+                Some(RequestSignature::new("other secret", "POST", "/", b"body")),
+                "body",
+                StatusCode::UNAUTHORIZED,
+            ),
+            (
+                Some(signature.clone()),
+                "other body",
+                StatusCode::UNAUTHORIZED,
+            ),
+            (Some(signature.clone()), "body", StatusCode::OK),
+            // The same request is not accepted twice.
+            (Some(signature), "body", StatusCode::UNAUTHORIZED),
+        ];
+        for (signature, body, expected_status) in requests {
+            let mut request = test::TestRequest::post().uri("/").set_payload(body);
+            if let Some(signature) = signature {
+                for (name, value) in signature.headers().iter() {
+                    request = request.header(*name, value.as_str());
+                }
+            }
+
+            // Unauthorized requests are rejected by the middleware with an error.
+            match app.call(request.to_request()).await {
+                Ok(response) => {
+                    assert_eq!(response.status(), expected_status);
+                    // The handler receives the body read by the middleware.
+                    assert_eq!(test::read_body(response).await, body);
+                }
+                Err(err) => {
+                    assert_eq!(err.as_response_error().status_code(), expected_status);
+                }
+            }
+        }
+    }
+}
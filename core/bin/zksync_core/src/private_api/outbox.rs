@@ -0,0 +1,121 @@
+//! Processing of the outbox requests left unprocessed.
+//!
+//! The API server abandons the requests it has failed to deliver, but it may fail itself before
+//! doing so, and the Core may be restarted while processing a claimed request. Such requests are
+//! picked up from the outbox and processed by the Core, so every stored request gets its result.
+//!
+//! The interrupted processing may have added the transactions to the mempool already, so the
+//! request is considered processed if all of its transactions are known to the Core.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::channel::oneshot;
+use zksync_storage::outbox::OutboxRequest;
+use zksync_types::SignedZkSyncTx;
+
+use super::AppState;
+use crate::mempool::{MempoolTransactionRequest, TxAddError};
+
+/// Interval between the checks of the outbox.
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Time after which the request not delivered by the API server is processed by the Core.
+/// The API server gives up on the request much earlier, unless it fails.
+const UNDELIVERED_REQUEST_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Time the processed requests are kept for, so their repeated deliveries still get the result.
+const PROCESSED_REQUEST_RETENTION: Duration = Duration::from_secs(60 * 60);
+/// Maximum number of the requests processed at once.
+const PENDING_REQUESTS_LIMIT: i64 = 100;
+
+fn mempool_request(
+    request: OutboxRequest,
+    sender: oneshot::Sender<Result<(), TxAddError>>,
+) -> MempoolTransactionRequest {
+    match request {
+        OutboxRequest::Tx(tx) => MempoolTransactionRequest::NewTx(tx, sender),
+        OutboxRequest::TxsBatch(txs, eth_signature) => {
+            MempoolTransactionRequest::NewTxsBatch(txs, eth_signature, sender)
+        }
+    }
+}
+
+fn ago(now: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    now - chrono::Duration::from_std(duration).expect("Duration is out of range")
+}
+
+/// Periodically processes the outbox requests left unprocessed and removes the old processed ones.
+pub(super) async fn run_outbox_processor(state: AppState) {
+    // Requests claimed before the start were being processed by the previous run of the Core.
+    let started_at = Utc::now();
+    let mut timer = tokio::time::interval(OUTBOX_POLL_INTERVAL);
+    loop {
+        timer.tick().await;
+        if let Err(err) = process_pending_requests(&state, started_at).await {
+            vlog::warn!("Failed to process the pending outbox requests: {}", err);
+        }
+    }
+}
+
+async fn process_pending_requests(
+    state: &AppState,
+    started_at: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let requests = {
+        let mut storage = state.connection_pool.access_storage().await?;
+        storage
+            .outbox_schema()
+            .remove_processed_requests(ago(now, PROCESSED_REQUEST_RETENTION))
+            .await?;
+        storage
+            .outbox_schema()
+            .claim_pending_requests(
+                ago(now, UNDELIVERED_REQUEST_TIMEOUT),
+                started_at,
+                PENDING_REQUESTS_LIMIT,
+            )
+            .await?
+    };
+
+    for (outbox_id, request) in requests {
+        vlog::info!("Processing the pending outbox request {}", outbox_id);
+        let result = if txs_known(state, &request).await? {
+            Ok(())
+        } else {
+            state
+                .send_to_mempool(|sender| mempool_request(request, sender))
+                .await?
+        };
+        state.store_outbox_result(outbox_id, &result).await?;
+        metrics::counter!("private_api.outbox.pending_requests", 1);
+    }
+    Ok(())
+}
+
+/// Returns `true` if all the transactions of the request are either in the mempool or executed.
+async fn txs_known(state: &AppState, request: &OutboxRequest) -> anyhow::Result<bool> {
+    let txs: Vec<&SignedZkSyncTx> = match request {
+        OutboxRequest::Tx(tx) => vec![tx.as_ref()],
+        OutboxRequest::TxsBatch(txs, _) => txs.iter().collect(),
+    };
+
+    let mut storage = state.connection_pool.access_storage().await?;
+    for tx in txs {
+        let tx_hash = tx.tx.hash();
+        let known = storage
+            .chain()
+            .mempool_schema()
+            .contains_tx(tx_hash)
+            .await?
+            || storage
+                .chain()
+                .operations_schema()
+                .get_executed_operation(tx_hash.as_ref())
+                .await?
+                .is_some();
+        if !known {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
@@ -0,0 +1,103 @@
+//! Runtime flags switched by the operator via the admin API.
+//!
+//! Flags are stored in the database; this module keeps their local copy up to date,
+//! so the core components can check them without accessing the database.
+
+// Built-in uses
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_storage::{runtime_flags::RuntimeFlag, ConnectionPool};
+
+/// Interval between the updates of the local copy of the flags.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Local copy of the runtime flags shared between the core components.
+#[derive(Debug, Clone)]
+pub struct RuntimeFlags {
+    fast_processing: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+}
+
+impl Default for RuntimeFlags {
+    fn default() -> Self {
+        Self {
+            fast_processing: Arc::new(AtomicBool::new(RuntimeFlag::FastProcessing.default_value())),
+            paused: Arc::new(AtomicBool::new(RuntimeFlag::Paused.default_value())),
+        }
+    }
+}
+
+impl RuntimeFlags {
+    /// Returns `true` if the blocks with fast withdrawals should be sealed faster than usual.
+    pub fn fast_processing_enabled(&self) -> bool {
+        self.fast_processing.load(Ordering::Relaxed)
+    }
+
+    pub fn set_fast_processing_enabled(&self, enabled: bool) {
+        self.fast_processing.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the server is paused by the operator and no new blocks should be created.
+    pub fn paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Relaxed);
+    }
+
+    async fn update(&self, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let fast_processing = storage
+            .runtime_flags_schema()
+            .is_enabled(RuntimeFlag::FastProcessing)
+            .await?;
+        let paused = storage
+            .runtime_flags_schema()
+            .is_enabled(RuntimeFlag::Paused)
+            .await?;
+
+        if fast_processing != self.fast_processing_enabled() {
+            vlog::info!(
+                "Fast processing of withdrawals is {}",
+                if fast_processing {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            );
+            self.set_fast_processing_enabled(fast_processing);
+        }
+        if paused != self.paused() {
+            vlog::warn!(
+                "Server is {} by the operator",
+                if paused { "paused" } else { "resumed" }
+            );
+            self.set_paused(paused);
+        }
+        Ok(())
+    }
+}
+
+/// Periodically loads the runtime flags from the database.
+#[must_use]
+pub fn run_runtime_flags_updater(pool: ConnectionPool, flags: RuntimeFlags) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(UPDATE_INTERVAL);
+        loop {
+            timer.tick().await;
+
+            if let Err(err) = flags.update(&pool).await {
+                vlog::warn!("Unable to update the runtime flags: {}", err);
+            }
+        }
+    })
+}
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::VecDeque;
 use std::time::Instant;
 // External uses
 use futures::{
@@ -7,10 +7,16 @@ use futures::{
     SinkExt,
 };
 use itertools::Itertools;
+use num::BigUint;
 use tokio::task::JoinHandle;
+use tracing::Span;
+use vlog::{TraceContext, Traced};
 // Workspace uses
 use zksync_crypto::ff;
-use zksync_state::state::{CollectedFee, OpSuccess, ZkSyncState};
+use zksync_state::{
+    state::{CollectedFee, OpSuccess, ZkSyncState},
+    tree::StateTree,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     block::{
@@ -19,16 +25,19 @@ use zksync_types::{
     },
     gas_counter::GasCounter,
     mempool::SignedTxVariant,
-    tx::{TxHash, ZkSyncTx},
+    tx::{TxFailureCode, TxHash, ZkSyncTx},
     Account, AccountId, AccountTree, AccountUpdate, AccountUpdates, ActionType, Address,
-    BlockNumber, PriorityOp, SignedZkSyncTx,
+    BlockNumber, PriorityOp, PubKeyHash, SignedZkSyncTx,
 };
 // Local uses
 use crate::{
     committer::{AppliedUpdatesRequest, BlockCommitRequest, CommitRequest},
     mempool::ProposedBlock,
+    runtime_flags::RuntimeFlags,
+    tx_traces,
 };
 
+pub mod standby;
 #[cfg(test)]
 mod tests;
 
@@ -37,11 +46,19 @@ pub enum ExecutedOpId {
     PriorityOp(u64),
 }
 
+#[derive(Debug)]
 pub enum StateKeeperRequest {
     GetAccount(Address, oneshot::Sender<Option<(AccountId, Account)>>),
     GetLastUnprocessedPriorityOp(oneshot::Sender<u64>),
+    /// Returns the number of the block being filled with the miniblocks.
+    GetPendingBlockNumber(oneshot::Sender<BlockNumber>),
     ExecuteMiniBlock(ProposedBlock),
     SealBlock,
+    /// Seals the pending block unless it has no operations, used when the server is paused
+    /// or the block is sealed by the operator.
+    SealNonEmptyBlock,
+    /// Sets the amount of chunks after which the blocks are sealed.
+    SetBlockChunksSize(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +77,8 @@ struct PendingBlock {
     collected_fees: Vec<CollectedFee>,
     /// Number of stored account updates in the db (from `account_updates` field)
     stored_account_updates: usize,
+    /// Traces of the executed transactions, the commit of the block is linked to them.
+    tx_traces: Vec<TraceContext>,
 }
 
 impl PendingBlock {
@@ -76,8 +95,37 @@ impl PendingBlock {
             fast_processing_required: false,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
+            tx_traces: Vec::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.success_operations.is_empty() && self.failed_txs.is_empty()
+    }
+
+    /// Remembers the part of the pending block state the failed transactions change.
+    fn checkpoint(&self) -> PendingBlockCheckpoint {
+        PendingBlockCheckpoint {
+            failed_txs_len: self.failed_txs.len(),
+            gas_counter: self.gas_counter.clone(),
+            fast_processing_required: self.fast_processing_required,
         }
     }
+
+    /// Reverts the changes made by the failed transactions since the checkpoint.
+    fn rollback(&mut self, checkpoint: PendingBlockCheckpoint) {
+        self.failed_txs.truncate(checkpoint.failed_txs_len);
+        self.gas_counter = checkpoint.gas_counter;
+        self.fast_processing_required = checkpoint.fast_processing_required;
+    }
+}
+
+/// State of the pending block before the transaction execution, see `PendingBlock::checkpoint`.
+#[derive(Debug, Clone)]
+struct PendingBlockCheckpoint {
+    failed_txs_len: usize,
+    gas_counter: GasCounter,
+    fast_processing_required: bool,
 }
 
 /// Responsible for tx processing and block forming.
@@ -91,11 +139,22 @@ pub struct ZkSyncStateKeeper {
     pending_block: PendingBlock,
 
     rx_for_blocks: mpsc::Receiver<StateKeeperRequest>,
-    tx_for_commitments: mpsc::Sender<CommitRequest>,
+    tx_for_commitments: mpsc::Sender<Traced<CommitRequest>>,
 
     available_block_chunk_sizes: Vec<usize>,
+    /// Amount of chunks after which the block is sealed. The largest available block size,
+    /// unless another one is chosen by the block proposer.
+    block_chunks_size: usize,
     max_miniblock_iterations: usize,
     fast_miniblock_iterations: usize,
+    runtime_flags: RuntimeFlags,
+
+    /// Failures of the transactions that are retried once in a later block instead of being rejected.
+    retried_tx_failures: Vec<TxFailureCode>,
+    /// Failed transactions of the pending block to be retried once it's sealed.
+    deferred_txs: Vec<SignedTxVariant>,
+    /// Deferred transactions of the previous blocks to be retried in the pending block.
+    retried_txs: VecDeque<SignedTxVariant>,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -106,8 +165,7 @@ pub struct ZkSyncStateKeeper {
 }
 
 pub struct ZkSyncStateInitParams {
-    pub tree: AccountTree,
-    pub acc_id_by_addr: HashMap<Address, AccountId>,
+    pub tree: StateTree,
     pub last_block_number: BlockNumber,
     pub unprocessed_priority_op: u64,
 }
@@ -120,9 +178,13 @@ impl Default for ZkSyncStateInitParams {
 
 impl ZkSyncStateInitParams {
     pub fn new() -> Self {
+        Self::with_tree(StateTree::default())
+    }
+
+    /// Creates the empty state with the given account tree.
+    pub fn with_tree(tree: StateTree) -> Self {
         Self {
-            tree: AccountTree::new(zksync_crypto::params::account_tree_depth()),
-            acc_id_by_addr: HashMap::new(),
+            tree,
             last_block_number: BlockNumber(0),
             unprocessed_priority_op: 0,
         }
@@ -131,34 +193,85 @@ impl ZkSyncStateInitParams {
     pub async fn get_pending_block(
         &self,
         storage: &mut zksync_storage::StorageProcessor<'_>,
-    ) -> Option<SendablePendingBlock> {
-        let pending_block = storage
+    ) -> anyhow::Result<Option<SendablePendingBlock>> {
+        let pending_block = storage.chain().block_schema().load_pending_block().await?;
+
+        match pending_block {
+            Some(pending_block) if *pending_block.number == *self.last_block_number + 1 => {
+                return Ok(Some(pending_block));
+            }
+            Some(pending_block) if pending_block.number <= self.last_block_number => {
+                // If after generating several pending block node generated
+                // full blocks, they may be sealed on the first iteration
+                // and stored pending block will be outdated.
+                // Thus, if the stored pending block has the lower number than
+                // last committed one, we just ignore it.
+            }
+            Some(pending_block) => {
+                // Blocks preceding the pending one were reverted, so it can't be restored.
+                vlog::warn!(
+                    "Pending block #{} doesn't follow the last committed block #{} and will be discarded",
+                    pending_block.number,
+                    self.last_block_number
+                );
+            }
+            None => {}
+        }
+
+        self.return_reverted_txs_to_mempool(storage).await?;
+        Ok(None)
+    }
+
+    /// Returns transactions executed after the last committed block (e.g. ones from reverted blocks
+    /// or the discarded pending block) back to the mempool, so they will be executed again.
+    async fn return_reverted_txs_to_mempool(
+        &self,
+        storage: &mut zksync_storage::StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let mut transaction = storage.start_transaction().await?;
+
+        let returned_txs = transaction
+            .chain()
+            .mempool_schema()
+            .return_executed_txs_to_mempool(self.last_block_number)
+            .await
+            .map_err(|err| {
+                anyhow::format_err!(
+                    "Failed to return reverted transactions to the mempool: {}",
+                    err
+                )
+            })?;
+        transaction
             .chain()
             .block_schema()
-            .load_pending_block()
+            .remove_pending_blocks(self.last_block_number)
             .await
-            .unwrap_or_default()?;
-
-        if pending_block.number <= self.last_block_number {
-            // If after generating several pending block node generated
-            // full blocks, they may be sealed on the first iteration
-            // and stored pending block will be outdated.
-            // Thus, if the stored pending block has the lower number than
-            // last committed one, we just ignore it.
-            return None;
-        }
+            .map_err(|err| {
+                anyhow::format_err!("Failed to remove discarded pending blocks: {}", err)
+            })?;
 
-        // We've checked that pending block is greater than the last committed block,
-        // but it must be greater exactly by 1.
-        assert_eq!(*pending_block.number, *self.last_block_number + 1);
+        transaction.commit().await?;
 
-        Some(pending_block)
+        if returned_txs > 0 {
+            vlog::info!(
+                "{} transactions executed after block #{} were returned to the mempool",
+                returned_txs,
+                self.last_block_number
+            );
+        }
+        Ok(())
     }
 
+    /// Restores the committed state from the database. The in-memory account tree is built
+    /// from scratch, while the tree stored in RocksDB is brought up to date with the committed state.
     pub async fn restore_from_db(
         storage: &mut zksync_storage::StorageProcessor<'_>,
+        tree: StateTree,
     ) -> Result<Self, anyhow::Error> {
-        let mut init_params = Self::new();
+        let mut init_params = match tree.stored_block() {
+            Some(_) => Self::with_tree(tree),
+            None => Self::new(),
+        };
         init_params.load_from_db(storage).await?;
 
         Ok(init_params)
@@ -167,6 +280,99 @@ impl ZkSyncStateInitParams {
     async fn load_account_tree(
         &mut self,
         storage: &mut zksync_storage::StorageProcessor<'_>,
+    ) -> Result<BlockNumber, anyhow::Error> {
+        let block_number = match self.tree.stored_block() {
+            Some(stored_block) => self.update_stored_tree(storage, stored_block).await?,
+            None => self.load_memory_tree(storage).await?,
+        };
+
+        if *block_number != 0 {
+            let storage_root_hash = storage
+                .chain()
+                .block_schema()
+                .get_block(block_number)
+                .await?
+                .expect("restored block must exist");
+            assert_eq!(
+                storage_root_hash.new_root_hash,
+                self.tree.root_hash(),
+                "restored root_hash is different"
+            );
+        }
+        Ok(block_number)
+    }
+
+    /// Brings the account tree stored in RocksDB up to date with the last committed block.
+    ///
+    /// The updates of the blocks committed since the tree was flushed are applied to it. The tree
+    /// which is empty, is ahead of the committed state (e.g. its last block wasn't committed before
+    /// the restart or was reverted) or doesn't match it afterwards is reconciled with the whole
+    /// committed state instead.
+    async fn update_stored_tree(
+        &mut self,
+        storage: &mut zksync_storage::StorageProcessor<'_>,
+        stored_block: BlockNumber,
+    ) -> Result<BlockNumber, anyhow::Error> {
+        let start = Instant::now();
+        let last_committed = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+
+        let mut updated = false;
+        if *stored_block != 0 && stored_block <= last_committed {
+            if let Some((_, updates)) = storage
+                .chain()
+                .state_schema()
+                .load_state_diff(stored_block, Some(last_committed))
+                .await?
+            {
+                self.tree.apply_updates(updates);
+            }
+            let root_hash = storage
+                .chain()
+                .block_schema()
+                .get_block_root_hash(last_committed)
+                .await?;
+            updated = root_hash == Some(self.tree.root_hash());
+        }
+
+        if !updated {
+            vlog::info!(
+                "Reconciling the stored account tree of block #{} with the committed state",
+                stored_block
+            );
+            let (_, accounts) = storage
+                .chain()
+                .state_schema()
+                .load_committed_state(Some(last_committed))
+                .await?;
+            self.tree.reconcile(&accounts);
+        }
+        self.tree.flush(last_committed)?;
+
+        vlog::info!(
+            "Stored account tree updated from block #{} to #{}",
+            stored_block,
+            last_committed
+        );
+        metrics::histogram!("state_keeper.update_stored_tree", start.elapsed());
+        Ok(last_committed)
+    }
+
+    /// Returns the in-memory account tree, used to restore it from the cache stored in the database.
+    fn memory_tree(&mut self) -> &mut AccountTree {
+        match &mut self.tree {
+            StateTree::Memory(tree, _) => tree,
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("Account tree is stored in RocksDB"),
+        }
+    }
+
+    async fn load_memory_tree(
+        &mut self,
+        storage: &mut zksync_storage::StorageProcessor<'_>,
     ) -> Result<BlockNumber, anyhow::Error> {
         let (last_cached_block_number, accounts) = if let Some((block, _)) = storage
             .chain()
@@ -193,11 +399,11 @@ impl ZkSyncStateInitParams {
             .get_account_tree_cache_block(last_cached_block_number)
             .await?
         {
-            self.tree
+            self.memory_tree()
                 .set_internals(serde_json::from_value(account_tree_cache)?);
         } else {
-            self.tree.root_hash();
-            let account_tree_cache = self.tree.get_internals();
+            self.memory_tree().root_hash();
+            let account_tree_cache = self.memory_tree().get_internals();
             storage
                 .chain()
                 .block_schema()
@@ -237,19 +443,6 @@ impl ZkSyncStateInitParams {
                 }
             }
         }
-        if *block_number != 0 {
-            let storage_root_hash = storage
-                .chain()
-                .block_schema()
-                .get_block(block_number)
-                .await?
-                .expect("restored block must exist");
-            assert_eq!(
-                storage_root_hash.new_root_hash,
-                self.tree.root_hash(),
-                "restored root_hash is different"
-            );
-        }
         Ok(block_number)
     }
 
@@ -283,10 +476,7 @@ impl ZkSyncStateInitParams {
 
         if let Some((block_number, updates)) = state_diff {
             for (id, update) in updates.into_iter() {
-                let updated_account = Account::apply_update(self.remove_account(id), update);
-                if let Some(account) = updated_account {
-                    self.insert_account(id, account);
-                }
+                self.apply_account_update(id, update);
             }
             self.unprocessed_priority_op =
                 Self::unprocessed_priority_op_id(storage, block_number).await?;
@@ -295,18 +485,20 @@ impl ZkSyncStateInitParams {
         Ok(())
     }
 
+    /// Applies the update to the account, creating or removing it if needed.
+    pub fn apply_account_update(&mut self, id: AccountId, update: AccountUpdate) {
+        let updated_account = Account::apply_update(self.remove_account(id), update);
+        if let Some(account) = updated_account {
+            self.insert_account(id, account);
+        }
+    }
+
     pub fn insert_account(&mut self, id: AccountId, acc: Account) {
-        self.acc_id_by_addr.insert(acc.address, id);
         self.tree.insert(*id, acc);
     }
 
     pub fn remove_account(&mut self, id: AccountId) -> Option<Account> {
-        if let Some(acc) = self.tree.remove(*id) {
-            self.acc_id_by_addr.remove(&acc.address);
-            Some(acc)
-        } else {
-            None
-        }
+        self.tree.remove(*id)
     }
 
     async fn unprocessed_priority_op_id(
@@ -338,7 +530,7 @@ impl ZkSyncStateKeeper {
         initial_state: ZkSyncStateInitParams,
         fee_account_address: Address,
         rx_for_blocks: mpsc::Receiver<StateKeeperRequest>,
-        tx_for_commitments: mpsc::Sender<CommitRequest>,
+        tx_for_commitments: mpsc::Sender<Traced<CommitRequest>>,
         available_block_chunk_sizes: Vec<usize>,
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
@@ -351,11 +543,7 @@ impl ZkSyncStateKeeper {
             .all(|(a, b)| a < b);
         assert!(is_sorted);
 
-        let state = ZkSyncState::new(
-            initial_state.tree,
-            initial_state.acc_id_by_addr,
-            initial_state.last_block_number + 1,
-        );
+        let state = ZkSyncState::new(initial_state.tree, initial_state.last_block_number + 1);
 
         let (fee_account_id, _) = state
             .get_account_by_address(&fee_account_address)
@@ -370,8 +558,14 @@ impl ZkSyncStateKeeper {
             tx_for_commitments,
             pending_block: PendingBlock::new(initial_state.unprocessed_priority_op, max_block_size),
             available_block_chunk_sizes,
+            block_chunks_size: max_block_size,
             max_miniblock_iterations,
             fast_miniblock_iterations,
+            runtime_flags: RuntimeFlags::default(),
+
+            retried_tx_failures: Vec::new(),
+            deferred_txs: Vec::new(),
+            retried_txs: VecDeque::new(),
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -383,6 +577,16 @@ impl ZkSyncStateKeeper {
         keeper
     }
 
+    /// Sets the runtime flags shared with the other core components.
+    pub fn set_runtime_flags(&mut self, runtime_flags: RuntimeFlags) {
+        self.runtime_flags = runtime_flags;
+    }
+
+    /// Sets the failures of the transactions that are retried once in a later block.
+    pub fn set_retried_tx_failures(&mut self, retried_tx_failures: Vec<TxFailureCode>) {
+        self.retried_tx_failures = retried_tx_failures;
+    }
+
     pub async fn initialize(&mut self, pending_block: Option<SendablePendingBlock>) {
         let start = Instant::now();
         if let Some(pending_block) = pending_block {
@@ -426,7 +630,12 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.initialize", start.elapsed());
     }
 
-    pub async fn create_genesis_block(pool: ConnectionPool, fee_account_address: &Address) {
+    /// Creates the genesis block with the given accounts. The first account must be the fee account.
+    pub async fn create_genesis_block(
+        pool: ConnectionPool,
+        genesis_accounts: &[(AccountId, Account)],
+        mut account_tree: StateTree,
+    ) {
         let start = Instant::now();
         let mut storage = pool
             .access_storage()
@@ -448,16 +657,52 @@ impl ZkSyncStateKeeper {
             *last_committed == 0 && accounts.is_empty(),
             "db should be empty"
         );
-        let fee_account = Account::default_with_address(fee_account_address);
-        let db_account_update = AccountUpdate::Create {
-            address: *fee_account_address,
-            nonce: fee_account.nonce,
-        };
-        accounts.insert(AccountId(0), fee_account);
+        assert_eq!(
+            genesis_accounts.first().map(|(id, _)| *id),
+            Some(AccountId(0)),
+            "genesis must start with the fee account"
+        );
+        let mut db_account_updates = Vec::new();
+        for (id, account) in genesis_accounts {
+            db_account_updates.push((
+                *id,
+                AccountUpdate::Create {
+                    address: account.address,
+                    nonce: account.nonce,
+                },
+            ));
+            if account.pub_key_hash != PubKeyHash::default() {
+                db_account_updates.push((
+                    *id,
+                    AccountUpdate::ChangePubKeyHash {
+                        old_pub_key_hash: PubKeyHash::default(),
+                        new_pub_key_hash: account.pub_key_hash,
+                        old_nonce: account.nonce,
+                        new_nonce: account.nonce,
+                    },
+                ));
+            }
+            // Balances are sorted, so the same genesis always results in the same updates.
+            let balances = account
+                .get_nonzero_balances()
+                .into_iter()
+                .sorted_by_key(|(token, _)| *token);
+            for (token, balance) in balances {
+                db_account_updates.push((
+                    *id,
+                    AccountUpdate::UpdateBalance {
+                        old_nonce: account.nonce,
+                        new_nonce: account.nonce,
+                        balance_update: (token, BigUint::from(0u32), balance.0),
+                    },
+                ));
+            }
+            accounts.insert(*id, account.clone());
+        }
         transaction
             .chain()
             .state_schema()
-            .commit_state_update(BlockNumber(0), &[(AccountId(0), db_account_update)], 0)
+            .commit_state_update(BlockNumber(0), &db_account_updates, 0)
             .await
             .expect("db fail");
         transaction
@@ -471,9 +716,14 @@ impl ZkSyncStateKeeper {
             .commit()
             .await
             .expect("Unable to commit transaction in statekeeper");
-        let state = ZkSyncState::from_acc_map(accounts, last_committed + 1);
-        let root_hash = state.root_hash();
-        vlog::info!("Genesis block created, state: {}", state.root_hash());
+        // The stored tree may remain from the previous genesis, so it's reconciled instead of
+        // being filled from scratch.
+        account_tree.reconcile(&accounts);
+        account_tree
+            .flush(BlockNumber(0))
+            .expect("Failed to flush the account tree");
+        let root_hash = account_tree.root_hash();
+        vlog::info!("Genesis block created, state: {}", root_hash);
         println!("CONTRACTS_GENESIS_ROOT=0x{}", ff::to_hex(&root_hash));
         metrics::histogram!("state_keeper.create_genesis_block", start.elapsed());
     }
@@ -482,22 +732,75 @@ impl ZkSyncStateKeeper {
         self.initialize(pending_block).await;
 
         while let Some(req) = self.rx_for_blocks.next().await {
-            match req {
-                StateKeeperRequest::GetAccount(addr, sender) => {
-                    sender.send(self.account(&addr)).unwrap_or_default();
-                }
-                StateKeeperRequest::GetLastUnprocessedPriorityOp(sender) => {
-                    sender
-                        .send(self.current_unprocessed_priority_op)
-                        .unwrap_or_default();
-                }
-                StateKeeperRequest::ExecuteMiniBlock(proposed_block) => {
-                    self.execute_proposed_block(proposed_block).await;
-                }
-                StateKeeperRequest::SealBlock => {
+            self.handle_request(req).await;
+        }
+    }
+
+    async fn handle_request(&mut self, req: StateKeeperRequest) {
+        match req {
+            StateKeeperRequest::GetAccount(addr, sender) => {
+                sender.send(self.account(&addr)).unwrap_or_default();
+            }
+            StateKeeperRequest::GetLastUnprocessedPriorityOp(sender) => {
+                sender
+                    .send(self.current_unprocessed_priority_op)
+                    .unwrap_or_default();
+            }
+            StateKeeperRequest::GetPendingBlockNumber(sender) => {
+                sender.send(self.state.block_number).unwrap_or_default();
+            }
+            StateKeeperRequest::ExecuteMiniBlock(proposed_block) => {
+                self.execute_proposed_block(proposed_block).await;
+            }
+            StateKeeperRequest::SealBlock => {
+                self.seal_pending_block().await;
+            }
+            StateKeeperRequest::SealNonEmptyBlock => {
+                // There is no point in creating an empty block.
+                if !self.pending_block.is_empty() {
                     self.seal_pending_block().await;
                 }
             }
+            StateKeeperRequest::SetBlockChunksSize(block_chunks_size) => {
+                self.set_block_chunks_size(block_chunks_size);
+            }
+        }
+    }
+
+    /// Sets the size of the blocks being created. The pending block is resized as well,
+    /// unless it contains operations already.
+    fn set_block_chunks_size(&mut self, block_chunks_size: usize) {
+        if !self
+            .available_block_chunk_sizes
+            .contains(&block_chunks_size)
+        {
+            vlog::warn!("Block size {} is not available", block_chunks_size);
+            return;
+        }
+
+        if block_chunks_size != self.block_chunks_size {
+            vlog::debug!("Block size is set to {} chunks", block_chunks_size);
+        }
+        self.block_chunks_size = block_chunks_size;
+        if self.pending_block.success_operations.is_empty() {
+            self.pending_block.chunks_left = block_chunks_size;
+        }
+    }
+
+    /// Called when the operation does not fit into the pending block.
+    /// Seals the pending block, unless it has no operations while its size is reduced: sealing
+    /// would not help the operation fit, so the block is extended to the largest size instead.
+    async fn make_room_for_operation(&mut self) {
+        let max_block_size = *self
+            .available_block_chunk_sizes
+            .last()
+            .expect("failed to get max block size");
+        if self.pending_block.success_operations.is_empty()
+            && self.pending_block.chunks_left < max_block_size
+        {
+            self.pending_block.chunks_left = max_block_size;
+        } else {
+            self.seal_pending_block().await;
         }
     }
 
@@ -506,7 +809,7 @@ impl ZkSyncStateKeeper {
         let mut executed_ops = Vec::new();
 
         // We want to store this variable before moving anything from the pending block.
-        let empty_proposed_block = proposed_block.is_empty();
+        let empty_proposed_block = proposed_block.is_empty() && self.retried_txs.is_empty();
 
         let mut priority_op_queue = proposed_block
             .priority_ops
@@ -518,43 +821,69 @@ impl ZkSyncStateKeeper {
                     executed_ops.push(exec_op);
                 }
                 Err(priority_op) => {
-                    self.seal_pending_block().await;
+                    self.make_room_for_operation().await;
 
                     priority_op_queue.push_front(priority_op);
                 }
             }
         }
 
-        let mut tx_queue = proposed_block.txs.into_iter().collect::<VecDeque<_>>();
-        while let Some(variant) = tx_queue.pop_front() {
+        // Transactions deferred in the previous blocks are executed first.
+        // Each element of the queue is marked whether it's being retried.
+        let mut tx_queue = self
+            .retried_txs
+            .drain(..)
+            .map(|variant| (variant, true))
+            .chain(
+                proposed_block
+                    .txs
+                    .into_iter()
+                    .map(|variant| (variant, false)),
+            )
+            .collect::<VecDeque<_>>();
+        while let Some((variant, retried)) = tx_queue.pop_front() {
             match &variant {
                 SignedTxVariant::Tx(tx) => {
+                    let checkpoint = self.pending_block.checkpoint();
                     match self.apply_tx(tx) {
                         Ok(exec_op) => {
+                            if !retried
+                                && self.defer_failed_txs(
+                                    &variant,
+                                    std::slice::from_ref(&exec_op),
+                                    checkpoint,
+                                )
+                            {
+                                continue;
+                            }
                             executed_ops.push(exec_op);
                         }
                         Err(_) => {
                             // We could not execute the tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.make_room_for_operation().await;
 
-                            tx_queue.push_front(variant);
+                            tx_queue.push_front((variant, retried));
                         }
                     }
                 }
                 SignedTxVariant::Batch(batch) => {
+                    let checkpoint = self.pending_block.checkpoint();
                     match self.apply_batch(&batch.txs, batch.batch_id) {
                         Ok(mut ops) => {
+                            if !retried && self.defer_failed_txs(&variant, &ops, checkpoint) {
+                                continue;
+                            }
                             executed_ops.append(&mut ops);
                         }
                         Err(_) => {
                             // We could not execute the batch tx due to either of block size limit
                             // or the withdraw operations limit, so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.make_room_for_operation().await;
 
-                            tx_queue.push_front(variant);
+                            tx_queue.push_front((variant, retried));
                         }
                     }
                 }
@@ -565,8 +894,11 @@ impl ZkSyncStateKeeper {
             self.pending_block.pending_block_iteration += 1;
         }
 
-        // If pending block contains withdrawals we seal it faster
-        let max_miniblock_iterations = if self.pending_block.fast_processing_required {
+        // If pending block contains withdrawals we seal it faster, unless fast processing
+        // was disabled by the operator. In that case fast withdrawals are processed as regular ones.
+        let max_miniblock_iterations = if self.pending_block.fast_processing_required
+            && self.runtime_flags.fast_processing_enabled()
+        {
             self.fast_miniblock_iterations
         } else {
             self.max_miniblock_iterations
@@ -587,6 +919,41 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.execute_proposed_block", start.elapsed());
     }
 
+    /// Defers the failed transaction (or all the transactions of the failed batch) to retry it
+    /// once in a later block, if its failure is configured to be retried.
+    /// Returns `true` if the transaction was deferred, in which case the pending block is
+    /// restored to the checkpoint taken before the transaction execution.
+    fn defer_failed_txs(
+        &mut self,
+        variant: &SignedTxVariant,
+        executed_ops: &[ExecutedOperations],
+        checkpoint: PendingBlockCheckpoint,
+    ) -> bool {
+        let failure_code = executed_ops.iter().find_map(|op| match op {
+            ExecutedOperations::Tx(tx) if !tx.success => tx.fail_code,
+            _ => None,
+        });
+        let failure_code = match failure_code {
+            Some(failure_code) => failure_code,
+            None => return false,
+        };
+        if !self.retried_tx_failures.contains(&failure_code) {
+            return false;
+        }
+
+        // Transactions of the batch fail all together, so none of them is left in the block.
+        self.pending_block.rollback(checkpoint);
+        self.deferred_txs.push(variant.clone());
+
+        vlog::debug!(
+            "Transactions {:?} failed ({:?}) and will be retried in the next block",
+            variant.hashes(),
+            failure_code
+        );
+        metrics::counter!("state_keeper.deferred_txs", executed_ops.len() as u64);
+        true
+    }
+
     // Err if there is no space in current block
     fn apply_priority_op(
         &mut self,
@@ -678,7 +1045,9 @@ impl ZkSyncStateKeeper {
             }
         }
 
-        let all_updates = self.state.execute_txs_batch(txs);
+        let span = tracing::info_span!("execute_txs_batch", batch_id);
+        let span = self.continue_tx_trace(span, txs);
+        let all_updates = span.in_scope(|| self.state.execute_txs_batch(txs));
         let mut executed_operations = Vec::new();
 
         for (tx, tx_updates) in txs.iter().zip(all_updates) {
@@ -701,6 +1070,7 @@ impl ZkSyncStateKeeper {
                         success: true,
                         op: Some(executed_op),
                         fail_reason: None,
+                        fail_code: None,
                         block_index: Some(block_index),
                         created_at: chrono::Utc::now(),
                         batch_id: Some(batch_id),
@@ -717,6 +1087,7 @@ impl ZkSyncStateKeeper {
                         success: false,
                         op: None,
                         fail_reason: Some(e.to_string()),
+                        fail_code: Some(TxFailureCode::of(&e)),
                         block_index: None,
                         created_at: chrono::Utc::now(),
                         batch_id: Some(batch_id),
@@ -767,7 +1138,9 @@ impl ZkSyncStateKeeper {
             }
         }
 
-        let tx_updates = self.state.execute_tx(tx.tx.clone());
+        let span = tracing::info_span!("execute_tx", tx_hash = %tx.tx.hash().to_string());
+        let span = self.continue_tx_trace(span, std::slice::from_ref(tx));
+        let tx_updates = span.in_scope(|| self.state.execute_tx(tx.tx.clone()));
 
         let exec_result = match tx_updates {
             Ok(OpSuccess {
@@ -788,6 +1161,7 @@ impl ZkSyncStateKeeper {
                     success: true,
                     op: Some(executed_op),
                     fail_reason: None,
+                    fail_code: None,
                     block_index: Some(block_index),
                     created_at: chrono::Utc::now(),
                     batch_id: None,
@@ -804,6 +1178,7 @@ impl ZkSyncStateKeeper {
                     success: false,
                     op: None,
                     fail_reason: Some(e.to_string()),
+                    fail_code: Some(TxFailureCode::of(&e)),
                     block_index: None,
                     created_at: chrono::Utc::now(),
                     batch_id: None,
@@ -817,23 +1192,34 @@ impl ZkSyncStateKeeper {
         Ok(exec_result)
     }
 
+    /// Makes the span of the transactions execution continue the trace the transactions were
+    /// received within, if it's known, and remembers the trace for the block commit.
+    fn continue_tx_trace(&mut self, span: Span, txs: &[SignedZkSyncTx]) -> Span {
+        // Transactions of the batch are received within the same trace.
+        let contexts = txs
+            .iter()
+            .filter_map(|tx| tx_traces::take(&tx.tx.hash()))
+            .collect::<Vec<_>>();
+        if let Some(context) = contexts.first() {
+            context.attach_to(&span);
+            self.pending_block.tx_traces.push(TraceContext::of(&span));
+        }
+        span
+    }
+
     /// Finalizes the pending block, transforming it into a full block.
     async fn seal_pending_block(&mut self) {
         let start = Instant::now();
         let mut pending_block = std::mem::replace(
             &mut self.pending_block,
-            PendingBlock::new(
-                self.current_unprocessed_priority_op,
-                *self
-                    .available_block_chunk_sizes
-                    .last()
-                    .expect("failed to get max block size"),
-            ),
+            PendingBlock::new(self.current_unprocessed_priority_op, self.block_chunks_size),
         );
 
         // Once block is sealed, we refresh the counters for the next block.
         self.success_txs_pending_len = 0;
         self.failed_txs_pending_len = 0;
+        // Deferred transactions are retried in the next block.
+        self.retried_txs.extend(self.deferred_txs.drain(..));
 
         // Apply fees of pending block
         let fee_updates = self
@@ -877,6 +1263,11 @@ impl ZkSyncStateKeeper {
             first_update_order_id,
         };
         pending_block.stored_account_updates = pending_block.account_updates.len();
+        // The stored account tree is flushed before the block is committed, so it may get ahead
+        // of the committed state, which is handled on restore.
+        self.state
+            .flush_tree()
+            .expect("Failed to flush the account tree");
         *self.state.block_number += 1;
 
         vlog::info!(
@@ -887,7 +1278,24 @@ impl ZkSyncStateKeeper {
             pending_block.pending_block_iteration
         );
 
-        let commit_request = CommitRequest::Block((block_commit_request, applied_updates_request));
+        let span = tracing::info_span!(
+            "commit_block",
+            block_number = *block_commit_request.block.block_number
+        );
+        // Spans of the transactions are closed already, so the block is linked to the spans
+        // of their sealing created within the transaction traces.
+        for context in &pending_block.tx_traces {
+            let tx_span = tracing::info_span!(
+                "seal_tx",
+                block_number = *block_commit_request.block.block_number
+            );
+            context.attach_to(&tx_span);
+            span.follows_from(&tx_span);
+        }
+        let commit_request = Traced::with_span(
+            span,
+            CommitRequest::Block((block_commit_request, applied_updates_request)),
+        );
         self.tx_for_commitments
             .send(commit_request)
             .await
@@ -942,7 +1350,11 @@ impl ZkSyncStateKeeper {
             pending_block.pending_block_iteration
         );
 
-        let commit_request = CommitRequest::PendingBlock((pending_block, applied_updates_request));
+        let span = tracing::info_span!("save_pending_block", block_number = *pending_block.number);
+        let commit_request = Traced::with_span(
+            span,
+            CommitRequest::PendingBlock((pending_block, applied_updates_request)),
+        );
         self.tx_for_commitments
             .send(commit_request)
             .await
@@ -0,0 +1,328 @@
+//! Hot standby mode of the state keeper.
+//!
+//! Only one Core instance (the leader) creates blocks at a time, which is ensured by the leader
+//! lock in the database. A standby instance doesn't start any of its actors until it acquires
+//! the lock, but keeps a warm copy of the committed state in memory instead: the state is
+//! restored from the database once, and afterwards the account updates stored by the committer
+//! of the leader are streamed from the journal and applied as soon as their blocks are committed.
+//!
+//! Once the leader terminates and the lock is released, the standby catches up with the last
+//! committed blocks and starts the state keeper right away, without rebuilding the state.
+
+// Built-in uses
+use std::collections::VecDeque;
+// External uses
+use futures::{
+    channel::{mpsc, oneshot},
+    future, SinkExt,
+};
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_state::tree::StateTree;
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    config::{LEADER_LOOKUP_INTERVAL, OBSERVER_MODE_PULL_INTERVAL},
+    AccountUpdateJournalEntry, BlockNumber,
+};
+// Local uses
+use super::ZkSyncStateInitParams;
+
+/// Maximum amount of journal entries loaded by a single query.
+const JOURNAL_BATCH_SIZE: u32 = 10_000;
+
+/// Starts the task which acquires the leader lock and holds it until the process terminates.
+/// The returned receiver is notified once the lock is acquired. If `wait` is not set and the lock
+/// is held by another instance, the receiver is notified with `false` right away instead.
+///
+/// The lock is held by the dedicated connection, so if the connection is lost, the task
+/// sends the stop signal to shut the server down, since the lock may be acquired by another
+/// instance already.
+pub fn start_leader_lock_holder(
+    pool: ConnectionPool,
+    wait: bool,
+    mut stop_signal: mpsc::Sender<bool>,
+) -> (JoinHandle<()>, oneshot::Receiver<bool>) {
+    let (acquired_sender, acquired_receiver) = oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let mut storage = pool
+            .access_storage()
+            .await
+            .expect("Unable to connect to the database to acquire the leader lock");
+        let mut timer = time::interval(LEADER_LOOKUP_INTERVAL);
+
+        let mut waiting_reported = false;
+        loop {
+            timer.tick().await;
+            let acquired = storage
+                .leader_lock_schema()
+                .try_acquire()
+                .await
+                .expect("Unable to acquire the leader lock");
+            if acquired {
+                break;
+            }
+            if !wait {
+                acquired_sender.send(false).unwrap_or_default();
+                return;
+            }
+            if !waiting_reported {
+                vlog::info!("Leader lock is held by another server instance, waiting for it");
+                waiting_reported = true;
+            }
+        }
+        vlog::info!("Leader lock acquired");
+        acquired_sender.send(true).unwrap_or_default();
+
+        // Check that the connection holding the lock is alive. The lock is reentrant,
+        // so the repeated acquiring by the same connection always succeeds.
+        loop {
+            timer.tick().await;
+            match storage.leader_lock_schema().try_acquire().await {
+                Ok(true) => continue,
+                Ok(false) => vlog::error!("Leader lock is lost, shutting down"),
+                Err(err) => vlog::error!(
+                    "Connection holding the leader lock is lost, shutting down: {}",
+                    err
+                ),
+            }
+            stop_signal.send(true).await.unwrap_or_default();
+            // The server is stopped by the signal, the task itself is not supposed to finish.
+            future::pending::<()>().await;
+        }
+    });
+
+    (task, acquired_receiver)
+}
+
+/// State keeper of the standby server following the state committed by the leader.
+pub struct StandbyStateKeeper {
+    pool: ConnectionPool,
+    /// Committed state as of `state.last_block_number`.
+    state: ZkSyncStateInitParams,
+    /// Sequence number of the next journal entry to load.
+    next_sequence_number: u64,
+    /// Loaded updates of the blocks that are not committed yet.
+    pending_updates: VecDeque<AccountUpdateJournalEntry>,
+}
+
+impl StandbyStateKeeper {
+    /// Restores the committed state from the database.
+    pub async fn new(pool: ConnectionPool, tree: StateTree) -> anyhow::Result<Self> {
+        let mut standby = Self {
+            pool,
+            state: ZkSyncStateInitParams::with_tree(tree),
+            next_sequence_number: 0,
+            pending_updates: VecDeque::new(),
+        };
+        standby.restore().await?;
+
+        Ok(standby)
+    }
+
+    /// Keeps the state up to date until the leader lock is acquired.
+    /// Returns the state to initialize the state keeper with.
+    pub async fn run_until_promoted(
+        mut self,
+        mut promoted: oneshot::Receiver<bool>,
+    ) -> anyhow::Result<ZkSyncStateInitParams> {
+        let mut timer = time::interval(OBSERVER_MODE_PULL_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = self.update().await {
+                // The leader may still be alive, so it's not a reason to stop.
+                vlog::error!("Unable to update the standby state: {}", err);
+            }
+
+            match promoted.try_recv() {
+                Ok(Some(_)) => break,
+                Ok(None) => continue,
+                Err(_) => anyhow::bail!("Leader lock holder stopped"),
+            }
+        }
+
+        // The former leader is gone, catch up with the blocks it has committed last.
+        self.update().await?;
+        vlog::info!(
+            "Standby server promoted to leader, last committed block: {}",
+            self.state.last_block_number
+        );
+
+        Ok(self.state)
+    }
+
+    /// Rebuilds the state from the database. Used on start and in case the followed
+    /// state diverges from the committed one, e.g. after the blocks revert.
+    async fn restore(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let tree = std::mem::take(&mut self.state.tree);
+        self.state = ZkSyncStateInitParams::restore_from_db(&mut storage, tree).await?;
+        self.next_sequence_number = storage
+            .chain()
+            .state_schema()
+            .account_updates_journal_position(self.state.last_block_number)
+            .await?;
+        self.pending_updates.clear();
+
+        Ok(())
+    }
+
+    /// Applies the updates of the blocks committed since the previous update.
+    async fn update(&mut self) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        // Block is committed in the same database transaction as its updates, so all the
+        // updates of the blocks committed by this moment are in the journal already.
+        let last_committed_block = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        if last_committed_block < self.state.last_block_number {
+            vlog::warn!(
+                "Blocks after #{} were reverted, restoring the standby state",
+                last_committed_block
+            );
+            drop(storage);
+            return self.restore().await;
+        }
+
+        loop {
+            let entries = storage
+                .chain()
+                .state_schema()
+                .load_account_updates_journal(self.next_sequence_number, JOURNAL_BATCH_SIZE)
+                .await?;
+            let loaded = entries.len();
+            if let Some(last_entry) = entries.last() {
+                self.next_sequence_number = last_entry.sequence_number + 1;
+            }
+            self.pending_updates.extend(entries);
+
+            if loaded < JOURNAL_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        let initial_block = self.state.last_block_number;
+        while self.state.last_block_number < last_committed_block {
+            let block_number = self.state.last_block_number + 1;
+            apply_block_updates(&mut self.state, &mut self.pending_updates, block_number);
+
+            let expected_root_hash = storage
+                .chain()
+                .block_schema()
+                .get_block_root_hash(block_number)
+                .await?;
+            if expected_root_hash != Some(self.state.tree.root_hash()) {
+                vlog::warn!(
+                    "Standby state diverged at block #{}, restoring it",
+                    block_number
+                );
+                drop(storage);
+                return self.restore().await;
+            }
+        }
+
+        if self.state.last_block_number != initial_block {
+            self.state.tree.flush(self.state.last_block_number)?;
+            self.state.unprocessed_priority_op = ZkSyncStateInitParams::unprocessed_priority_op_id(
+                &mut storage,
+                self.state.last_block_number,
+            )
+            .await?;
+            vlog::debug!(
+                "Standby state updated to block #{}",
+                self.state.last_block_number
+            );
+        }
+        metrics::gauge!(
+            "state_keeper.standby.last_block",
+            *self.state.last_block_number as f64
+        );
+
+        Ok(())
+    }
+}
+
+/// Applies the loaded updates of the block following the last one applied to the state.
+/// Updates of the preceding blocks are discarded: these may remain in the journal from
+/// the pending blocks that were discarded by the leader.
+fn apply_block_updates(
+    state: &mut ZkSyncStateInitParams,
+    pending_updates: &mut VecDeque<AccountUpdateJournalEntry>,
+    block_number: BlockNumber,
+) {
+    while let Some(entry) = pending_updates.front() {
+        if entry.block_number > block_number {
+            break;
+        }
+        let entry = pending_updates.pop_front().unwrap();
+        if entry.block_number == block_number {
+            state.apply_account_update(entry.account_id, entry.update);
+        }
+    }
+    state.last_block_number = block_number;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{Account, AccountId, AccountUpdate, Address, Nonce};
+
+    fn entry(
+        sequence_number: u64,
+        block_number: u32,
+        account_id: u32,
+        update: AccountUpdate,
+    ) -> AccountUpdateJournalEntry {
+        AccountUpdateJournalEntry {
+            sequence_number,
+            block_number: BlockNumber(block_number),
+            account_id: AccountId(account_id),
+            update,
+        }
+    }
+
+    fn create(address: Address) -> AccountUpdate {
+        AccountUpdate::Create {
+            address,
+            nonce: Nonce(0),
+        }
+    }
+
+    /// Checks that the updates are applied only for the given block, while the stale updates
+    /// are discarded and the updates of the subsequent blocks are kept.
+    #[test]
+    fn apply_block_updates_by_block() {
+        let mut state = ZkSyncStateInitParams::new();
+        state.insert_account(
+            AccountId(0),
+            Account::default_with_address(&Address::repeat_byte(0)),
+        );
+        state.last_block_number = BlockNumber(1);
+
+        let mut pending_updates: VecDeque<_> = vec![
+            // Stale update of the discarded pending block.
+            entry(1, 1, 5, create(Address::repeat_byte(5))),
+            entry(2, 2, 1, create(Address::repeat_byte(1))),
+            entry(3, 2, 2, create(Address::repeat_byte(2))),
+            entry(4, 3, 3, create(Address::repeat_byte(3))),
+        ]
+        .into();
+
+        apply_block_updates(&mut state, &mut pending_updates, BlockNumber(2));
+
+        assert_eq!(state.last_block_number, BlockNumber(2));
+        assert_eq!(state.tree.accounts_count(), 3);
+        assert_eq!(
+            state.tree.account_id_by_address(&Address::repeat_byte(2)),
+            Some(AccountId(2))
+        );
+        assert_eq!(
+            state.tree.account_id_by_address(&Address::repeat_byte(5)),
+            None
+        );
+        assert_eq!(pending_updates.len(), 1);
+        assert_eq!(pending_updates[0].block_number, BlockNumber(3));
+    }
+}
@@ -1,24 +1,36 @@
-use super::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
-use crate::mempool::ProposedBlock;
+use super::{CommitRequest, StateKeeperRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
+use crate::{mempool::ProposedBlock, runtime_flags::RuntimeFlags};
 use futures::{channel::mpsc, stream::StreamExt};
 use num::BigUint;
+use vlog::Traced;
 use zksync_crypto::{
     priv_key_from_fs,
     rand::{Rng, SeedableRng, XorShiftRng},
     PrivateKey,
 };
 use zksync_types::{
-    mempool::SignedTxVariant, mempool::SignedTxsBatch, tx::PackedEthSignature, AccountId, H160, *,
+    mempool::SignedTxVariant,
+    mempool::SignedTxsBatch,
+    tx::{PackedEthSignature, TxFailureCode},
+    AccountId, H160, *,
 };
 
 struct StateKeeperTester {
     state_keeper: ZkSyncStateKeeper,
-    response_rx: mpsc::Receiver<CommitRequest>,
+    response_rx: mpsc::Receiver<Traced<CommitRequest>>,
     fee_collector: AccountId,
 }
 
 impl StateKeeperTester {
     fn new(available_chunk_size: usize, max_iterations: usize, fast_iterations: usize) -> Self {
+        Self::with_block_sizes(vec![available_chunk_size], max_iterations, fast_iterations)
+    }
+
+    fn with_block_sizes(
+        available_chunk_sizes: Vec<usize>,
+        max_iterations: usize,
+        fast_iterations: usize,
+    ) -> Self {
         const CHANNEL_SIZE: usize = 32768;
         let (_request_tx, request_rx) = mpsc::channel(CHANNEL_SIZE);
         let (response_tx, response_rx) = mpsc::channel(CHANNEL_SIZE);
@@ -33,7 +45,7 @@ impl StateKeeperTester {
             fee_collector.address,
             request_rx,
             response_tx,
-            vec![available_chunk_size],
+            available_chunk_sizes,
             max_iterations,
             fast_iterations,
         );
@@ -402,7 +414,9 @@ async fn seal_pending_block() {
     assert!(tester.state_keeper.pending_block.account_updates.is_empty());
     assert_eq!(tester.state_keeper.pending_block.chunks_left, 20);
 
-    if let Some(CommitRequest::Block((block, updates))) = tester.response_rx.next().await {
+    if let Some(CommitRequest::Block((block, updates))) =
+        tester.response_rx.next().await.map(Traced::into_inner)
+    {
         let collected_fees = tester
             .state_keeper
             .state
@@ -426,6 +440,43 @@ async fn seal_pending_block() {
     }
 }
 
+/// Checks that the block is sealed on request even if it's empty, while the request
+/// sent when the server is paused only seals the block containing operations.
+#[tokio::test]
+async fn seal_non_empty_block() {
+    let mut tester = StateKeeperTester::new(20, 3, 3);
+    let block_number = tester.state_keeper.state.block_number;
+
+    tester
+        .state_keeper
+        .handle_request(StateKeeperRequest::SealNonEmptyBlock)
+        .await;
+    assert_eq!(tester.state_keeper.state.block_number, block_number);
+    assert!(tester.response_rx.try_next().is_err());
+
+    tester
+        .state_keeper
+        .handle_request(StateKeeperRequest::SealBlock)
+        .await;
+    assert_eq!(tester.state_keeper.state.block_number, block_number + 1);
+    assert!(matches!(
+        tester.response_rx.next().await.map(Traced::into_inner),
+        Some(CommitRequest::Block(_))
+    ));
+
+    let deposit = create_deposit(TokenId(0), 12u32);
+    assert!(tester.state_keeper.apply_priority_op(deposit).is_ok());
+    tester
+        .state_keeper
+        .handle_request(StateKeeperRequest::SealNonEmptyBlock)
+        .await;
+    assert_eq!(tester.state_keeper.state.block_number, block_number + 2);
+    assert!(matches!(
+        tester.response_rx.next().await.map(Traced::into_inner),
+        Some(CommitRequest::Block(_))
+    ));
+}
+
 /// Checks if block storing is done correctly by storing a block
 /// with 1 priority_op, 1 succeeded tx, 1 failed tx
 #[tokio::test]
@@ -443,7 +494,9 @@ async fn store_pending_block() {
 
     tester.state_keeper.store_pending_block().await;
 
-    if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+    if let Some(CommitRequest::PendingBlock((block, _))) =
+        tester.response_rx.next().await.map(Traced::into_inner)
+    {
         assert_eq!(block.number, tester.state_keeper.state.block_number);
         assert_eq!(
             block.chunks_left,
@@ -484,7 +537,9 @@ mod execute_proposed_block {
 
         // First batch
         apply_batch_with_two_transfers(&mut tester).await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 4);
         } else {
             panic!("Block is not received!");
@@ -494,13 +549,150 @@ mod execute_proposed_block {
         apply_batch_with_two_transfers(&mut tester).await;
 
         // Check sealed block
-        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.block.block_transactions.len(), 4);
         } else {
             panic!("Block is not received!");
         }
     }
 
+    /// Checks that the block is sealed once the size chosen by the block proposer is filled,
+    /// and that the empty pending block is extended if the operation does not fit into it.
+    #[tokio::test]
+    async fn reduced_block_size() {
+        let mut tester = StateKeeperTester::with_block_sizes(vec![4, 8], 3, 3);
+        tester.state_keeper.set_block_chunks_size(4);
+        assert_eq!(tester.state_keeper.pending_block.chunks_left, 4);
+
+        apply_batch_with_two_transfers(&mut tester).await;
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
+            assert_eq!(block.block.block_transactions.len(), 2);
+            assert_eq!(block.block.block_chunks_size, 4);
+        } else {
+            panic!("Block is not received!");
+        }
+        assert_eq!(tester.state_keeper.pending_block.chunks_left, 4);
+
+        // Batch of three transfers requires 6 chunks, so the new block is extended.
+        let txs = (1..=3)
+            .map(|id| {
+                create_account_and_transfer(&mut tester, TokenId(0), AccountId(id), 200u32, 100u32)
+            })
+            .collect();
+        let proposed_block = ProposedBlock {
+            txs: vec![SignedTxVariant::Batch(SignedTxsBatch {
+                txs,
+                batch_id: 2,
+                eth_signature: None,
+            })],
+            priority_ops: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
+            assert_eq!(block.chunks_left, 2);
+        } else {
+            panic!("Block is not received!");
+        }
+    }
+
+    /// Checks that the transactions failed with the configured reasons are retried once
+    /// in the next block instead of being rejected right away.
+    #[tokio::test]
+    async fn retry_failed_txs() {
+        let mut tester = StateKeeperTester::new(20, 3, 3);
+        tester
+            .state_keeper
+            .set_retried_tx_failures(vec![TxFailureCode::NotEnoughBalance]);
+
+        let first_transfer =
+            create_account_and_transfer(&mut tester, TokenId(0), AccountId(1), 50u32, 100u32);
+        let second_transfer =
+            create_account_and_transfer(&mut tester, TokenId(0), AccountId(2), 50u32, 100u32);
+        let proposed_block = ProposedBlock {
+            txs: vec![
+                SignedTxVariant::Tx(first_transfer),
+                SignedTxVariant::Tx(second_transfer),
+            ],
+            priority_ops: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+        assert!(tester.state_keeper.pending_block.failed_txs.is_empty());
+        assert_eq!(tester.state_keeper.deferred_txs.len(), 2);
+
+        // The first account is topped up before the next block, so its transfer succeeds,
+        // while the second transfer is rejected after the retry.
+        tester.set_balance(AccountId(1), TokenId(0), 200u32);
+        tester.state_keeper.seal_pending_block().await;
+        tester
+            .state_keeper
+            .execute_proposed_block(ProposedBlock::default())
+            .await;
+
+        assert_eq!(
+            tester.state_keeper.pending_block.success_operations.len(),
+            1
+        );
+        assert_eq!(tester.state_keeper.pending_block.failed_txs.len(), 1);
+        assert_eq!(
+            tester.state_keeper.pending_block.failed_txs[0].fail_code,
+            Some(TxFailureCode::NotEnoughBalance)
+        );
+        assert!(tester.state_keeper.deferred_txs.is_empty());
+        assert!(tester.state_keeper.retried_txs.is_empty());
+    }
+
+    /// Checks that the deferred transactions leave no trace in the pending block:
+    /// neither their gas cost nor the fast processing request is kept.
+    #[tokio::test]
+    async fn deferred_txs_are_rolled_back() {
+        let mut tester = StateKeeperTester::new(20, 3, 3);
+        tester
+            .state_keeper
+            .set_retried_tx_failures(vec![TxFailureCode::NotEnoughBalance]);
+        let empty_gas_counter = tester.state_keeper.pending_block.gas_counter.clone();
+
+        let withdrawal = create_account_and_fast_withdrawal(
+            &mut tester,
+            TokenId(0),
+            AccountId(1),
+            50u32,
+            100u32,
+        );
+        let proposed_block = ProposedBlock {
+            txs: vec![SignedTxVariant::Tx(withdrawal)],
+            priority_ops: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+
+        let pending_block = &tester.state_keeper.pending_block;
+        assert_eq!(tester.state_keeper.deferred_txs.len(), 1);
+        assert!(pending_block.failed_txs.is_empty());
+        assert!(!pending_block.fast_processing_required);
+        assert_eq!(
+            pending_block.gas_counter.commit_gas_limit(),
+            empty_gas_counter.commit_gas_limit()
+        );
+        assert_eq!(
+            pending_block.gas_counter.verify_gas_limit(),
+            empty_gas_counter.verify_gas_limit()
+        );
+    }
+
     /// Checks if executing a proposed_block is done correctly
     /// when two batches don`t fit into one block.
     /// Also, checks if number of chunks left is correct after each operation
@@ -510,7 +702,9 @@ mod execute_proposed_block {
 
         // First batch
         apply_batch_with_two_transfers(&mut tester).await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 2);
         } else {
             panic!("Block is not received!");
@@ -518,12 +712,16 @@ mod execute_proposed_block {
 
         // Second batch
         apply_batch_with_two_transfers(&mut tester).await;
-        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.block.block_transactions.len(), 2);
         } else {
             panic!("Block is not received!");
         }
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 2);
         } else {
             panic!("Block is not received!");
@@ -533,7 +731,9 @@ mod execute_proposed_block {
         apply_single_transfer(&mut tester).await;
 
         // Check sealed block
-        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.block.block_transactions.len(), 3);
         } else {
             panic!("Block is not received!");
@@ -549,7 +749,9 @@ mod execute_proposed_block {
 
         // First single tx
         apply_single_transfer(&mut tester).await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 4);
         } else {
             panic!("Block is not received!");
@@ -557,7 +759,9 @@ mod execute_proposed_block {
 
         // Second single tx
         apply_single_transfer(&mut tester).await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 2);
         } else {
             panic!("Block is not received!");
@@ -565,12 +769,16 @@ mod execute_proposed_block {
 
         // First batch
         apply_batch_with_two_transfers(&mut tester).await;
-        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.block.block_transactions.len(), 2);
         } else {
             panic!("Block is not received!");
         }
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.chunks_left, 2);
         } else {
             panic!("Block is not received!");
@@ -580,7 +788,9 @@ mod execute_proposed_block {
         apply_single_transfer(&mut tester).await;
 
         // Check sealed block
-        if let Some(CommitRequest::Block((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::Block((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(block.block.block_transactions.len(), 3);
         } else {
             panic!("Block is not received!");
@@ -608,7 +818,9 @@ mod execute_proposed_block {
             .state_keeper
             .execute_proposed_block(proposed_block)
             .await;
-        if let Some(CommitRequest::PendingBlock(_)) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock(_)) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(
                 pending_block_iteration + 1,
                 tester.state_keeper.pending_block.pending_block_iteration
@@ -641,11 +853,11 @@ mod execute_proposed_block {
             .execute_proposed_block(proposed_block)
             .await;
         assert!(matches!(
-            tester.response_rx.next().await,
+            tester.response_rx.next().await.map(Traced::into_inner),
             Some(CommitRequest::Block(_))
         ));
         assert!(matches!(
-            tester.response_rx.next().await,
+            tester.response_rx.next().await.map(Traced::into_inner),
             Some(CommitRequest::PendingBlock(_))
         ));
     }
@@ -672,7 +884,7 @@ mod execute_proposed_block {
             .execute_proposed_block(proposed_block)
             .await;
         assert!(matches!(
-            tester.response_rx.next().await,
+            tester.response_rx.next().await.map(Traced::into_inner),
             Some(CommitRequest::Block(_))
         ));
     }
@@ -704,11 +916,47 @@ mod execute_proposed_block {
 
         // We should receive the next block, since it must be sealed right after.
         assert!(matches!(
-            tester.response_rx.next().await,
+            tester.response_rx.next().await.map(Traced::into_inner),
             Some(CommitRequest::Block(_))
         ));
     }
 
+    /// Checks that fast withdrawal doesn't affect block sealing if fast processing is disabled.
+    #[tokio::test]
+    async fn fast_withdrawal_disabled() {
+        const MAX_ITERATIONS: usize = 100;
+        const FAST_ITERATIONS: usize = 0;
+
+        let mut tester = StateKeeperTester::new(6, MAX_ITERATIONS, FAST_ITERATIONS);
+        let runtime_flags = RuntimeFlags::default();
+        runtime_flags.set_fast_processing_enabled(false);
+        tester.state_keeper.set_runtime_flags(runtime_flags);
+
+        let withdraw = create_account_and_fast_withdrawal(
+            &mut tester,
+            TokenId(0),
+            AccountId(1),
+            200u32,
+            145u32,
+        );
+
+        let proposed_block = ProposedBlock {
+            priority_ops: Vec::new(),
+            txs: vec![withdraw.into()],
+        };
+
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+
+        // Block is not sealed, so only the pending block is yielded.
+        assert!(matches!(
+            tester.response_rx.next().await.map(Traced::into_inner),
+            Some(CommitRequest::PendingBlock(_))
+        ));
+    }
+
     /// Checks the following things:
     /// 1. if proposed block is empty, no pending block is yielded from the state keeper.
     /// 2. if there were no successful operations in the block, pending block iteration is not incremented after empty or rejected-only updates.
@@ -754,7 +1002,7 @@ mod execute_proposed_block {
             .await;
 
         // Pending block should be created.
-        let next_block = tester.response_rx.next().await;
+        let next_block = tester.response_rx.next().await.map(Traced::into_inner);
         assert!(next_block.is_some(), "No pending block was yielded");
 
         // Iteration should still not be incremented.
@@ -778,7 +1026,7 @@ mod execute_proposed_block {
             .await;
 
         // Pending block should be created.
-        let next_block = tester.response_rx.next().await;
+        let next_block = tester.response_rx.next().await.map(Traced::into_inner);
         assert!(next_block.is_some(), "No pending block was yielded");
 
         // Iteration should be incremented.
@@ -802,7 +1050,7 @@ mod execute_proposed_block {
             .await;
 
         // Pending block should be created.
-        let next_block = tester.response_rx.next().await;
+        let next_block = tester.response_rx.next().await.map(Traced::into_inner);
         assert!(next_block.is_some(), "No pending block was yielded");
 
         // Iteration should still be incremented.
@@ -866,7 +1114,9 @@ mod execute_proposed_block {
             .state_keeper
             .execute_proposed_block(proposed_block_1)
             .await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(*block.number, 1); // It's the first block.
             assert_eq!(block.success_operations.len(), 1);
             assert_eq!(
@@ -890,7 +1140,9 @@ mod execute_proposed_block {
             .state_keeper
             .execute_proposed_block(proposed_block_2)
             .await;
-        if let Some(CommitRequest::PendingBlock((block, _))) = tester.response_rx.next().await {
+        if let Some(CommitRequest::PendingBlock((block, _))) =
+            tester.response_rx.next().await.map(Traced::into_inner)
+        {
             assert_eq!(*block.number, 1); // It still should be the first block.
             assert_eq!(block.success_operations.len(), 1);
             assert_eq!(
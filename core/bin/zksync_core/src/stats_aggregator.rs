@@ -0,0 +1,56 @@
+//! Aggregator of the network statistics reported by the API.
+//!
+//! Statistics over the whole history (daily transaction counts, active accounts, average fees
+//! and the total value locked) are too expensive to be computed on each API request, so they
+//! are periodically precomputed into the stats tables. Every round recomputes the statistics
+//! starting from the last aggregated day, since that day could have been aggregated before
+//! it has ended.
+
+// Built-in uses
+use std::time::Instant;
+// External uses
+use chrono::NaiveDate;
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::StatsConfig;
+use zksync_storage::ConnectionPool;
+
+async fn aggregate_stats(connection_pool: &ConnectionPool) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let mut storage = connection_pool.access_storage().await?;
+
+    // The whole history is aggregated on the first round.
+    let since = storage
+        .chain()
+        .stats_schema()
+        .last_aggregated_day()
+        .await?
+        .unwrap_or_else(|| NaiveDate::from_ymd(1970, 1, 1));
+    storage
+        .chain()
+        .stats_schema()
+        .aggregate_daily_stats(since)
+        .await?;
+    storage.chain().stats_schema().aggregate_tvl().await?;
+
+    metrics::histogram!("stats_aggregator.aggregate", start.elapsed());
+    Ok(())
+}
+
+/// Runs the task periodically precomputing the network statistics.
+/// Errors are reported to the log and the round is repeated later.
+pub fn run_stats_aggregator(
+    connection_pool: ConnectionPool,
+    config: StatsConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut timer = time::interval(config.aggregation_interval());
+        loop {
+            timer.tick().await;
+
+            if let Err(err) = aggregate_stats(&connection_pool).await {
+                vlog::warn!("Failed to aggregate the network statistics: {}", err);
+            }
+        }
+    })
+}
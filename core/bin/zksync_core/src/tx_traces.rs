@@ -0,0 +1,52 @@
+//! Trace contexts of the transactions received by the private API.
+//!
+//! Transactions wait in the mempool until they're proposed for the block, so the execution
+//! of the transaction doesn't happen within the span of the request which added it. Instead,
+//! the context of the request is remembered here and looked up by the state keeper, so the
+//! execution of the transaction continues the trace started by the API server.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use vlog::TraceContext;
+use zksync_types::tx::TxHash;
+
+/// Maximum number of the remembered contexts. Transactions rejected by the mempool are never
+/// executed, so the oldest contexts are forgotten once the limit is reached.
+const TX_TRACES_CAPACITY: usize = 10_000;
+
+static TX_TRACES: Lazy<Mutex<TxTraces>> = Lazy::new(Mutex::default);
+
+#[derive(Debug, Default)]
+struct TxTraces {
+    contexts: HashMap<TxHash, TraceContext>,
+    /// Hashes of the transactions in the order they were remembered.
+    order: VecDeque<TxHash>,
+}
+
+/// Remembers the context of the trace the transaction was received within.
+/// Does nothing if the traces are not exported.
+pub fn remember(tx_hash: TxHash, context: TraceContext) {
+    if context.is_empty() {
+        return;
+    }
+
+    let mut traces = TX_TRACES.lock().unwrap();
+    if traces.contexts.insert(tx_hash, context).is_none() {
+        traces.order.push_back(tx_hash);
+    }
+    while traces.order.len() > TX_TRACES_CAPACITY {
+        if let Some(oldest) = traces.order.pop_front() {
+            traces.contexts.remove(&oldest);
+        }
+    }
+}
+
+/// Returns the context of the trace the transaction was received within, if it's known.
+pub fn take(tx_hash: &TxHash) -> Option<TraceContext> {
+    // Hash is left in the queue, it's dropped once the queue is full.
+    TX_TRACES.lock().unwrap().contexts.remove(tx_hash)
+}
@@ -0,0 +1,657 @@
+//! Notifier of the webhooks registered by the integrators for the addresses.
+//!
+//! The notifier subscribes to the block events reported by the committer, and stores the events
+//! for the webhooks subscribed to the addresses touched by the operations. Stored events are
+//! delivered signed, at most `max_concurrent_deliveries` at a time, and every delivery is retried
+//! independently with an exponential backoff, so an unavailable endpoint doesn't delay the others.
+//! Pending deliveries are kept in the database, so they survive the restart of the server.
+//!
+//! The notifier keeps the last blocks it has created the events for. The events of the blocks
+//! missed while the notifier was lagging behind or stopped are created from the stored blocks,
+//! so some of the executed operations may be reported twice, but never missed.
+//!
+//! The host of the webhook is resolved before every attempt, the event is not delivered if it
+//! resolves to a non-public address, and the connection is established to the checked address.
+//! Proxies are not used and redirects are not followed for the same reason.
+
+// Built-in uses
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+// External uses
+use chrono::Utc;
+use futures::{channel::mpsc, future, StreamExt};
+use hyper::{
+    client::{connect::dns::Name, HttpConnector},
+    header::CONTENT_TYPE,
+    service::Service,
+    Body, Request, Uri,
+};
+use hyper_tls::HttpsConnector;
+use jsonwebtoken::{crypto, Algorithm, EncodingKey};
+use tokio::{net, sync::Semaphore, task::JoinHandle, time};
+// Workspace uses
+use zksync_api_client::rest::v2::{
+    is_public_ip, WebhookEvent, WebhookEventKind, WEBHOOK_SIGNATURE_HEADER,
+};
+use zksync_config::WebhooksConfig;
+use zksync_storage::{webhooks::records::StoredWebhookDelivery, ConnectionPool};
+use zksync_types::{
+    block::{Block, ExecutedOperations},
+    Address, BlockNumber, ZkSyncPriorityOp, ZkSyncTx,
+};
+// Local uses
+use crate::block_events::{operation_hash, BlockEvent};
+
+/// Interval between the checks for the deliveries due for the next attempt.
+const DELIVERIES_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Events of the addresses touched by the operations.
+type AddressEvents = HashMap<Address, Vec<WebhookEventKind>>;
+
+/// Returns the addresses of the accounts touched by the operation, including the
+/// Ethereum addresses of the withdrawals recipients and the depositors.
+fn touched_addresses(op: &ExecutedOperations) -> Vec<Address> {
+    let mut addresses = match op {
+        ExecutedOperations::Tx(tx) => {
+            let tx = &tx.signed_tx.tx;
+            let recipient = match tx {
+                ZkSyncTx::Transfer(transfer) => Some(transfer.to),
+                ZkSyncTx::Withdraw(withdraw) => Some(withdraw.to),
+                ZkSyncTx::ForcedExit(forced_exit) => Some(forced_exit.target),
+                ZkSyncTx::ChangePubKey(_) | ZkSyncTx::Close(_) => None,
+            };
+            std::iter::once(tx.account()).chain(recipient).collect()
+        }
+        ExecutedOperations::PriorityOp(op) => match &op.priority_op.data {
+            ZkSyncPriorityOp::Deposit(deposit) => vec![deposit.from, deposit.to],
+            ZkSyncPriorityOp::FullExit(full_exit) => vec![full_exit.eth_address],
+        },
+    };
+    addresses.sort_unstable();
+    addresses.dedup();
+    addresses
+}
+
+/// Signs the event with HMAC-SHA256 keyed by the secret of the webhook.
+fn sign_event(secret: &str, body: &str) -> String {
+    crypto::sign(
+        body,
+        &EncodingKey::from_secret(secret.as_bytes()),
+        Algorithm::HS256,
+    )
+    .expect("HMAC signing can't fail")
+}
+
+/// Checks that the host of the webhook URL resolves to the public addresses only,
+/// returns the address the webhook should be connected to.
+async fn check_webhook_host(url: &str) -> anyhow::Result<IpAddr> {
+    let url: Uri = url.parse()?;
+    let host = url
+        .host()
+        .ok_or_else(|| anyhow::format_err!("Webhook URL has no host"))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let default_port = if url.scheme_str() == Some("https") {
+        443
+    } else {
+        80
+    };
+    let port = url.port_u16().unwrap_or(default_port);
+
+    let mut checked_address = None;
+    for address in net::lookup_host((host, port)).await? {
+        anyhow::ensure!(
+            is_public_ip(address.ip()),
+            "Webhook host resolves to the non-public address {}",
+            address.ip()
+        );
+        checked_address.get_or_insert(address.ip());
+    }
+    checked_address.ok_or_else(|| anyhow::format_err!("Webhook host is not resolved"))
+}
+
+/// Resolver returning the address the webhook host was checked against, so the connection
+/// is established to this address even if the host resolves to another one afterwards.
+#[derive(Debug, Clone, Copy)]
+struct CheckedAddressResolver(IpAddr);
+
+impl Service<Name> for CheckedAddressResolver {
+    type Response = std::iter::Once<IpAddr>;
+    type Error = std::io::Error;
+    type Future = future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _name: Name) -> Self::Future {
+        future::ok(std::iter::once(self.0))
+    }
+}
+
+/// Sends the signed event to the webhook.
+async fn send_event(url: &str, signature: &str, body: String) -> anyhow::Result<()> {
+    let address = check_webhook_host(url).await?;
+    let mut connector = HttpConnector::new_with_resolver(CheckedAddressResolver(address));
+    connector.enforce_http(false);
+    let client =
+        hyper::Client::builder().build::<_, Body>(HttpsConnector::new_with_connector(connector));
+
+    let request = Request::post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_SIGNATURE_HEADER, signature)
+        .body(Body::from(body))?;
+    let response = client.request(request).await?;
+    anyhow::ensure!(
+        response.status().is_success(),
+        "Webhook responded with the status {}",
+        response.status()
+    );
+    Ok(())
+}
+
+/// Removes the delivery, or schedules the next attempt if `retry_interval` is set.
+async fn finish_attempt(
+    pool: &ConnectionPool,
+    id: i64,
+    retry_interval: Option<Duration>,
+) -> anyhow::Result<()> {
+    let mut storage = pool.access_storage().await?;
+    match retry_interval {
+        Some(retry_interval) => {
+            storage
+                .webhooks_schema()
+                .reschedule_delivery(id, retry_interval)
+                .await
+        }
+        None => storage.webhooks_schema().remove_delivery(id).await,
+    }
+}
+
+/// Makes an attempt to deliver the event to the webhook.
+async fn deliver_event(
+    pool: ConnectionPool,
+    config: WebhooksConfig,
+    delivery: StoredWebhookDelivery,
+) {
+    let body = delivery.event.to_string();
+    let signature = sign_event(&delivery.secret, &body);
+    let result = time::timeout(
+        config.request_timeout(),
+        send_event(&delivery.url, &signature, body),
+    )
+    .await
+    .unwrap_or_else(|_| Err(anyhow::format_err!("Request timed out")));
+
+    let attempt = delivery.attempts as u32 + 1;
+    let retry_interval = match result {
+        Ok(()) => {
+            metrics::counter!("webhook_notifier.delivered", 1);
+            None
+        }
+        Err(err) => {
+            vlog::warn!(
+                "Failed to deliver the event to the webhook #{} (attempt {}): {}",
+                delivery.subscription_id,
+                attempt,
+                err
+            );
+            if attempt < config.max_attempts {
+                Some(config.retry_interval(attempt))
+            } else {
+                metrics::counter!("webhook_notifier.failed", 1);
+                None
+            }
+        }
+    };
+
+    if let Err(err) = finish_attempt(&pool, delivery.id, retry_interval).await {
+        vlog::warn!(
+            "Failed to record the attempt to deliver the event #{}: {}",
+            delivery.id,
+            err
+        );
+    }
+}
+
+/// Delivers the stored events due for the next attempt, at most `max_concurrent_deliveries` at a time.
+async fn deliver_pending_events(pool: ConnectionPool, config: WebhooksConfig) {
+    let permits = Arc::new(Semaphore::new(config.max_concurrent_deliveries));
+    // Claimed deliveries are not claimed again until the attempt times out,
+    // with a margin for recording its result.
+    let lease = config.request_timeout() * 2;
+    let mut timer = time::interval(DELIVERIES_POLL_INTERVAL);
+    loop {
+        timer.tick().await;
+
+        let available_permits = permits.available_permits();
+        if available_permits == 0 {
+            continue;
+        }
+        let deliveries = match pool.access_storage().await {
+            Ok(mut storage) => {
+                storage
+                    .webhooks_schema()
+                    .claim_deliveries(lease, available_permits as i64)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        let deliveries = match deliveries {
+            Ok(deliveries) => deliveries,
+            Err(err) => {
+                vlog::warn!("Failed to load the pending webhook events: {}", err);
+                continue;
+            }
+        };
+
+        for delivery in deliveries {
+            let permit = permits.clone().acquire_owned().await;
+            let (pool, config) = (pool.clone(), config.clone());
+            tokio::spawn(async move {
+                deliver_event(pool, config, delivery).await;
+                drop(permit);
+            });
+        }
+    }
+}
+
+/// Returns the events of the addresses touched by the executed operations.
+fn executed_ops_events<'a>(
+    block_number: BlockNumber,
+    operations: impl IntoIterator<Item = &'a ExecutedOperations>,
+) -> AddressEvents {
+    let mut events: AddressEvents = HashMap::new();
+    for op in operations {
+        let tx_hash = operation_hash(op);
+        let (success, fail_reason) = match op {
+            ExecutedOperations::Tx(tx) => (tx.success, tx.fail_reason.clone()),
+            ExecutedOperations::PriorityOp(_) => (true, None),
+        };
+        for address in touched_addresses(op) {
+            events
+                .entry(address)
+                .or_default()
+                .push(WebhookEventKind::TransactionExecuted {
+                    tx_hash: tx_hash.clone(),
+                    block_number,
+                    success,
+                    fail_reason: fail_reason.clone(),
+                });
+        }
+    }
+    events
+}
+
+/// Returns the events of the addresses touched by the operations of the verified block.
+fn verified_block_events(block: &Block) -> AddressEvents {
+    let mut tx_hashes: HashMap<_, Vec<_>> = HashMap::new();
+    for op in &block.block_transactions {
+        let tx_hash = operation_hash(op);
+        for address in touched_addresses(op) {
+            tx_hashes.entry(address).or_default().push(tx_hash.clone());
+        }
+    }
+
+    tx_hashes
+        .into_iter()
+        .map(|(address, tx_hashes)| {
+            let event = WebhookEventKind::BlockVerified {
+                block_number: block.block_number,
+                tx_hashes,
+            };
+            (address, vec![event])
+        })
+        .collect()
+}
+
+/// Tracks the operations of the pending block the events were created for,
+/// since the same operations are reported again with the committed block.
+#[derive(Debug, Default)]
+struct NotifiedOps {
+    block_number: BlockNumber,
+    ops: HashSet<String>,
+}
+
+impl NotifiedOps {
+    /// Returns the events of the operations not notified about yet.
+    fn events(
+        &mut self,
+        block_number: BlockNumber,
+        operations: &[ExecutedOperations],
+    ) -> AddressEvents {
+        if block_number != self.block_number {
+            self.block_number = block_number;
+            self.ops.clear();
+        }
+        let ops = &mut self.ops;
+        executed_ops_events(
+            block_number,
+            operations
+                .iter()
+                .filter(|op| ops.insert(operation_hash(op))),
+        )
+    }
+}
+
+struct WebhookNotifier {
+    pool: ConnectionPool,
+    /// Last committed block the events were created for.
+    last_committed_block: BlockNumber,
+    /// Last verified block the events were created for.
+    last_verified_block: BlockNumber,
+    notified_ops: NotifiedOps,
+}
+
+impl WebhookNotifier {
+    async fn new(pool: ConnectionPool) -> anyhow::Result<Self> {
+        let mut storage = pool.access_storage().await?;
+        let notified_blocks = storage.webhooks_schema().load_notified_blocks().await?;
+        let (last_committed_block, last_verified_block) = match notified_blocks {
+            Some(notified_blocks) => notified_blocks,
+            // On the first start, the events are created for the blocks processed afterwards only.
+            None => {
+                let last_committed_block = storage
+                    .chain()
+                    .block_schema()
+                    .get_last_committed_block()
+                    .await?;
+                let last_verified_block = storage
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_block()
+                    .await?;
+                storage
+                    .webhooks_schema()
+                    .store_notified_blocks(last_committed_block, last_verified_block)
+                    .await?;
+                (last_committed_block, last_verified_block)
+            }
+        };
+        drop(storage);
+
+        Ok(Self {
+            pool,
+            last_committed_block,
+            last_verified_block,
+            notified_ops: NotifiedOps::default(),
+        })
+    }
+
+    /// Stores the events for the webhooks subscribed to their addresses, along with the last
+    /// blocks the events were created for.
+    async fn store_events(
+        &mut self,
+        events: AddressEvents,
+        last_committed_block: BlockNumber,
+        last_verified_block: BlockNumber,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+
+        if !events.is_empty() {
+            let addresses: Vec<_> = events.keys().copied().collect();
+            let subscriptions = transaction
+                .webhooks_schema()
+                .load_address_subscriptions(&addresses)
+                .await?;
+
+            let created_at = Utc::now();
+            let mut deliveries = Vec::new();
+            for subscription in subscriptions {
+                let address = Address::from_slice(&subscription.address);
+                for kind in events.get(&address).into_iter().flatten() {
+                    let event = WebhookEvent {
+                        webhook_id: subscription.id as u64,
+                        address,
+                        kind: kind.clone(),
+                        created_at,
+                    };
+                    deliveries.push((subscription.id, serde_json::to_value(event)?));
+                }
+            }
+            transaction
+                .webhooks_schema()
+                .add_deliveries(&deliveries)
+                .await?;
+        }
+
+        if (last_committed_block, last_verified_block)
+            != (self.last_committed_block, self.last_verified_block)
+        {
+            transaction
+                .webhooks_schema()
+                .store_notified_blocks(last_committed_block, last_verified_block)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        self.last_committed_block = last_committed_block;
+        self.last_verified_block = last_verified_block;
+        Ok(())
+    }
+
+    async fn block_committed(&mut self, block: &Block) -> anyhow::Result<()> {
+        if block.block_number <= self.last_committed_block {
+            return Ok(());
+        }
+        // Operations which were not reported while the block was pending are reported with it.
+        let events = self
+            .notified_ops
+            .events(block.block_number, &block.block_transactions);
+        self.store_events(events, block.block_number, self.last_verified_block)
+            .await
+    }
+
+    async fn block_verified(&mut self, block: &Block) -> anyhow::Result<()> {
+        if block.block_number <= self.last_verified_block {
+            return Ok(());
+        }
+        let events = verified_block_events(block);
+        self.store_events(events, self.last_committed_block, block.block_number)
+            .await
+    }
+
+    /// Creates the events of the blocks committed and verified after the last notified ones.
+    async fn catch_up(&mut self) -> anyhow::Result<()> {
+        let (last_committed_block, last_verified_block) = {
+            let mut storage = self.pool.access_storage().await?;
+            let last_committed_block = storage
+                .chain()
+                .block_schema()
+                .get_last_committed_block()
+                .await?;
+            let last_verified_block = storage
+                .chain()
+                .block_schema()
+                .get_last_verified_block()
+                .await?;
+            (last_committed_block, last_verified_block)
+        };
+
+        for block_number in *self.last_committed_block + 1..=*last_committed_block {
+            let block = self.load_block(BlockNumber(block_number)).await?;
+            self.block_committed(&block).await?;
+        }
+        for block_number in *self.last_verified_block + 1..=*last_verified_block {
+            let block = self.load_block(BlockNumber(block_number)).await?;
+            self.block_verified(&block).await?;
+        }
+        Ok(())
+    }
+
+    async fn load_block(&self, block_number: BlockNumber) -> anyhow::Result<Block> {
+        self.pool
+            .access_storage()
+            .await?
+            .chain()
+            .block_schema()
+            .load_committed_block(block_number)
+            .await
+            .ok_or_else(|| anyhow::format_err!("Block {} is not found", block_number))
+    }
+
+    async fn handle_event(&mut self, event: BlockEvent) -> anyhow::Result<()> {
+        match event {
+            BlockEvent::OpsExecuted {
+                block_number,
+                operations,
+            } => {
+                if block_number <= self.last_committed_block {
+                    return Ok(());
+                }
+                let events = self.notified_ops.events(block_number, &operations);
+                self.store_events(events, self.last_committed_block, self.last_verified_block)
+                    .await
+            }
+            BlockEvent::BlockCommitted { block } => self.block_committed(&block).await,
+            BlockEvent::BlockVerified { block } => self.block_verified(&block).await,
+            BlockEvent::EventsDropped { count } => {
+                vlog::warn!(
+                    "{} block events were dropped, creating the webhook events from the stored blocks",
+                    count
+                );
+                self.catch_up().await
+            }
+        }
+    }
+}
+
+/// Runs the task creating the webhook events for the block events reported by the committer,
+/// and delivering them to the webhooks.
+pub fn run_webhook_notifier(
+    connection_pool: ConnectionPool,
+    config: WebhooksConfig,
+    mut block_events: mpsc::Receiver<BlockEvent>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        tokio::spawn(deliver_pending_events(connection_pool.clone(), config));
+
+        let mut notifier = WebhookNotifier::new(connection_pool)
+            .await
+            .expect("Unable to load the webhook notifier state");
+        // Blocks processed while the notifier was stopped.
+        if let Err(err) = notifier.catch_up().await {
+            vlog::warn!("Failed to notify the webhooks: {}", err);
+        }
+        while let Some(event) = block_events.next().await {
+            if let Err(err) = notifier.handle_event(event).await {
+                vlog::warn!("Failed to notify the webhooks: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_crypto::{ff::Field, Fr};
+    use zksync_types::{AccountId, ExecutedTx, Nonce, SignedZkSyncTx, TokenId, Transfer};
+
+    fn executed_transfer(from: Address, to: Address, nonce: u32) -> ExecutedOperations {
+        let transfer = Transfer::new(
+            AccountId(1),
+            from,
+            to,
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(nonce),
+            None,
+        );
+        ExecutedOperations::Tx(Box::new(ExecutedTx {
+            signed_tx: SignedZkSyncTx {
+                tx: ZkSyncTx::Transfer(Box::new(transfer)),
+                eth_sign_data: None,
+            },
+            success: true,
+            op: None,
+            fail_reason: None,
+            fail_code: None,
+            block_index: None,
+            created_at: Utc::now(),
+            batch_id: None,
+        }))
+    }
+
+    #[test]
+    fn webhook_events() {
+        let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let first = executed_transfer(alice, bob, 0);
+        let second = executed_transfer(alice, alice, 1);
+
+        let mut notified_ops = NotifiedOps::default();
+        let events = notified_ops.events(BlockNumber(1), &[first.clone()]);
+        assert_eq!(events[&alice].len(), 1);
+        assert_eq!(
+            events[&bob],
+            vec![WebhookEventKind::TransactionExecuted {
+                tx_hash: operation_hash(&first),
+                block_number: BlockNumber(1),
+                success: true,
+                fail_reason: None,
+            }]
+        );
+
+        let block = Block::new(
+            BlockNumber(1),
+            Fr::zero(),
+            AccountId(0),
+            vec![first.clone(), second.clone()],
+            (0, 0),
+            10,
+            1_000_000.into(),
+            1_500_000.into(),
+        );
+        // Only the operations not reported while the block was pending are reported with it.
+        let events = notified_ops.events(BlockNumber(1), &block.block_transactions);
+        assert!(!events.contains_key(&bob));
+        assert_eq!(
+            events[&alice],
+            vec![WebhookEventKind::TransactionExecuted {
+                tx_hash: operation_hash(&second),
+                block_number: BlockNumber(1),
+                success: true,
+                fail_reason: None,
+            }]
+        );
+        assert!(notified_ops
+            .events(BlockNumber(1), &block.block_transactions)
+            .is_empty());
+
+        let events = verified_block_events(&block);
+        assert_eq!(
+            events[&alice],
+            vec![WebhookEventKind::BlockVerified {
+                block_number: BlockNumber(1),
+                tx_hashes: vec![operation_hash(&first), operation_hash(&second)],
+            }]
+        );
+        assert_eq!(
+            events[&bob],
+            vec![WebhookEventKind::BlockVerified {
+                block_number: BlockNumber(1),
+                tx_hashes: vec![operation_hash(&first)],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_host_check() {
+        assert_eq!(
+            check_webhook_host("https://8.8.8.8/hook").await.unwrap(),
+            "8.8.8.8".parse::<IpAddr>().unwrap()
+        );
+        for url in &[
+            "http://127.0.0.1:3000/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+        ] {
+            assert!(check_webhook_host(url).await.is_err(), "{}", url);
+        }
+    }
+}
@@ -8,11 +8,11 @@ use std::collections::VecDeque;
 use std::str::FromStr;
 // External uses
 use num::BigUint;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
-    ethereum::{ETHOperation, EthOpId, InsertedOperationResponse, OperationType},
+    ethereum::{ETHOperation, EthOpId, GasAdjusterStats, InsertedOperationResponse, OperationType},
     Operation,
 };
 // Local uses
@@ -40,9 +40,12 @@ pub(super) trait DatabaseInterface {
     ) -> anyhow::Result<Vec<Operation>>;
 
     /// Saves a new unconfirmed operation to the database.
+    /// The nonce is assigned from the sequence of the provided additional operator account,
+    /// or of the main operator account if `None` is provided.
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
+        account: Option<Address>,
         op_type: OperationType,
         op: Option<Operation>,
         deadline_block: i64,
@@ -58,6 +61,14 @@ pub(super) trait DatabaseInterface {
         hash: &H256,
     ) -> anyhow::Result<()>;
 
+    /// Starts tracking the nonce of the additional operator account, unless it's tracked already.
+    async fn init_account_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: i64,
+    ) -> anyhow::Result<()>;
+
     /// Adds a new tx info to the previously started Ethereum operation.
     async fn update_eth_tx(
         &self,
@@ -67,12 +78,14 @@ pub(super) trait DatabaseInterface {
         new_gas_value: U256,
     ) -> anyhow::Result<()>;
 
-    /// Marks an operation as completed in the database.
+    /// Marks an operation as completed in the database, storing the amount of gas
+    /// used by the accepted transaction.
     async fn confirm_operation(
         &self,
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
@@ -92,6 +105,13 @@ pub(super) trait DatabaseInterface {
         average_gas_price: U256,
     ) -> anyhow::Result<()>;
 
+    /// Stores the snapshot of the gas adjuster state.
+    async fn update_gas_adjuster_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        stats: &GasAdjusterStats,
+    ) -> anyhow::Result<()>;
+
     async fn is_previous_operation_confirmed(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -150,6 +170,7 @@ impl DatabaseInterface for Database {
     async fn save_new_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
+        account: Option<Address>,
         op_type: OperationType,
         op: Option<Operation>,
         deadline_block: i64,
@@ -158,7 +179,8 @@ impl DatabaseInterface for Database {
     ) -> anyhow::Result<InsertedOperationResponse> {
         let result = connection
             .ethereum_schema()
-            .save_new_eth_tx(
+            .save_new_eth_tx_from_account(
+                account,
                 op_type,
                 op.map(|op| op.id.unwrap()),
                 deadline_block,
@@ -182,6 +204,18 @@ impl DatabaseInterface for Database {
             .await?)
     }
 
+    async fn init_account_nonce(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        Ok(connection
+            .ethereum_schema()
+            .init_account_nonce(address, nonce)
+            .await?)
+    }
+
     async fn update_eth_tx(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -239,11 +273,15 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         if let OperationType::Verify = op.op_type {
             let mut transaction = connection.start_transaction().await?;
 
-            transaction.ethereum_schema().confirm_eth_tx(hash).await?;
+            transaction
+                .ethereum_schema()
+                .confirm_eth_tx(hash, gas_used)
+                .await?;
             transaction
                 .chain()
                 .state_schema()
@@ -252,7 +290,10 @@ impl DatabaseInterface for Database {
 
             transaction.commit().await?;
         } else {
-            connection.ethereum_schema().confirm_eth_tx(hash).await?;
+            connection
+                .ethereum_schema()
+                .confirm_eth_tx(hash, gas_used)
+                .await?;
         }
         Ok(())
     }
@@ -282,4 +323,16 @@ impl DatabaseInterface for Database {
             .await?;
         Ok(())
     }
+
+    async fn update_gas_adjuster_stats(
+        &self,
+        connection: &mut StorageProcessor<'_>,
+        stats: &GasAdjusterStats,
+    ) -> anyhow::Result<()> {
+        connection
+            .ethereum_schema()
+            .update_gas_adjuster_stats(stats)
+            .await?;
+        Ok(())
+    }
 }
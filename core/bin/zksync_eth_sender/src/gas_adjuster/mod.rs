@@ -3,6 +3,7 @@ use std::{collections::VecDeque, marker::PhantomData, time::Instant};
 // External deps
 use zksync_basic_types::U256;
 use zksync_eth_client::EthereumGateway;
+use zksync_types::ethereum::GasAdjusterStats;
 // Local deps
 use crate::database::DatabaseInterface;
 
@@ -28,6 +29,8 @@ pub(super) struct GasAdjuster<DB: DatabaseInterface> {
     last_price_renewal: Instant,
     /// Timestamp of the last sample added to the `statistics`.
     last_sample_added: Instant,
+    /// Gas price suggested for the last sent transaction.
+    last_suggested_price: Option<U256>,
     _db: PhantomData<DB>,
 }
 
@@ -45,6 +48,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             statistics: GasStatistics::new(gas_price_limit),
             last_price_renewal: Instant::now(),
             last_sample_added: Instant::now(),
+            last_suggested_price: None,
 
             _db: PhantomData,
         }
@@ -89,6 +93,11 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
 
         // Report used price to be gathered by the statistics module.
         self.statistics.add_sample(price);
+        self.last_suggested_price = Some(price);
+        metrics::gauge!(
+            "eth_sender.gas_adjuster.suggested_price",
+            price.low_u64() as f64
+        );
 
         Ok(price)
     }
@@ -97,6 +106,8 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
     /// This method is intended to be invoked periodically, and it updates the
     /// current max gas price limit according to the configurable update interval.
     pub async fn keep_updated(&mut self, ethereum: &EthereumGateway, db: &DB) {
+        let mut stats_changed = false;
+
         if self.last_sample_added.elapsed() >= parameters::sample_adding_interval() {
             // Report the current price to be gathered by the statistics module.
             match ethereum.get_gas_price().await {
@@ -104,6 +115,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
                     self.statistics.add_sample(network_price);
 
                     self.last_sample_added = Instant::now();
+                    stats_changed = true;
                 }
                 Err(err) => {
                     vlog::warn!("Cannot add the sample gas price: {}", err);
@@ -116,6 +128,7 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
             let scale_factor = parameters::limit_scale_factor();
             self.statistics.update_limit(scale_factor);
             self.last_price_renewal = Instant::now();
+            stats_changed = true;
 
             // Update the value in the database as well.
             let mut connection = match db.acquire_connection().await {
@@ -139,6 +152,52 @@ impl<DB: DatabaseInterface> GasAdjuster<DB> {
                 vlog::warn!("Cannot update the gas limit value in the database: {}", err);
             }
         }
+
+        if stats_changed {
+            self.report_stats(db).await;
+        }
+    }
+
+    /// Returns the snapshot of the current `GasAdjuster` state.
+    pub fn stats(&self) -> GasAdjusterStats {
+        GasAdjusterStats {
+            last_suggested_price: self.last_suggested_price,
+            samples: self.statistics.samples(),
+            average_price: self.statistics.get_average_price(),
+            price_limit: self.get_current_max_price(),
+            scale_factor: parameters::limit_scale_factor(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Reports the current state to the metrics and stores it in the database,
+    /// so it can be inspected by the operators.
+    async fn report_stats(&self, db: &DB) {
+        let stats = self.stats();
+
+        metrics::gauge!(
+            "eth_sender.gas_adjuster.price_limit",
+            stats.price_limit.low_u64() as f64
+        );
+        if let Some(average_price) = stats.average_price {
+            metrics::gauge!(
+                "eth_sender.gas_adjuster.average_price",
+                average_price.low_u64() as f64
+            );
+        }
+        metrics::gauge!("eth_sender.gas_adjuster.scale_factor", stats.scale_factor);
+
+        let result = match db.acquire_connection().await {
+            Ok(mut connection) => db.update_gas_adjuster_stats(&mut connection, &stats).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            // Stats are only used for the monitoring, so the failure is not critical.
+            vlog::warn!(
+                "Cannot update the gas adjuster stats in the database: {}",
+                err
+            );
+        }
     }
 
     fn scale_up(&self, price_to_scale: U256, current_network_price: U256) -> U256 {
@@ -220,6 +279,10 @@ impl GasStatistics {
         self.current_max_price = average_price * multiplier / divider;
     }
 
+    pub fn samples(&self) -> Vec<U256> {
+        self.samples.iter().copied().collect()
+    }
+
     pub fn get_average_price(&self) -> Option<U256> {
         if self.samples.len() < Self::GAS_PRICE_SAMPLES_AMOUNT {
             None
@@ -188,6 +188,41 @@ async fn gas_price_limit_scaling() {
     }
 }
 
+/// Checks that the state of `GasAdjuster` is reported to the database once it's updated.
+#[tokio::test]
+async fn gas_adjuster_stats() {
+    // Initial price limit to set.
+    const PRICE_LIMIT: u64 = 1000;
+    // Price suggested by the Ethereum client.
+    const NETWORK_PRICE: u64 = 100;
+
+    let (mut ethereum, db) = eth_and_db_clients().await;
+    db.update_gas_price_limit(PRICE_LIMIT.into()).await.unwrap();
+
+    let mut gas_adjuster: GasAdjuster<MockDatabase> = GasAdjuster::new(&db).await;
+    ethereum.get_mut_mock().unwrap().gas_price = NETWORK_PRICE.into();
+
+    // Nothing is stored until `keep_updated` is called.
+    let suggested_price = gas_adjuster.get_gas_price(&ethereum, None).await.unwrap();
+    assert!(db.gas_adjuster_stats().await.is_none());
+
+    gas_adjuster.keep_updated(&ethereum, &db).await;
+
+    let stats = db
+        .gas_adjuster_stats()
+        .await
+        .expect("Gas adjuster stats were not stored");
+    assert_eq!(stats.last_suggested_price, Some(suggested_price));
+    assert_eq!(
+        stats.samples,
+        vec![suggested_price, U256::from(NETWORK_PRICE)]
+    );
+    // There are not enough samples to calculate the average price and update the limit.
+    assert_eq!(stats.average_price, None);
+    assert_eq!(stats.price_limit, PRICE_LIMIT.into());
+    assert_eq!(stats.scale_factor, limit_scale_factor());
+}
+
 /// Checks that if the price suggested by the Ethereum client is below the price limit,
 /// the limit is calculated as (average of samples) * scale_factor.
 #[tokio::test]
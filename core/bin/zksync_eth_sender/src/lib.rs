@@ -21,18 +21,20 @@ use zksync_types::{
     config,
     ethereum::{ETHOperation, OperationType},
     gas_counter::GasCounter,
-    Action, Operation,
+    Action, BlockNumber, Operation,
 };
 // Local uses
 use self::{
     database::{Database, DatabaseInterface},
     gas_adjuster::GasAdjuster,
+    operator_accounts::OperatorAccounts,
     transactions::*,
     tx_queue::{TxData, TxQueue, TxQueueBuilder},
 };
 
 mod database;
 mod gas_adjuster;
+mod operator_accounts;
 mod transactions;
 mod tx_queue;
 
@@ -103,6 +105,22 @@ enum TxCheckMode {
 /// 2. Withdraw operations (only if both commit/verify for the same block operations were sent).
 /// 3. Commit operations.
 ///
+/// # Operator accounts
+///
+/// `verify` operations and `completeWithdrawals` transactions may be sent from the accounts
+/// other than the main operator one. Every account has its own nonce, so the order of transactions
+/// sent from different accounts is not guaranteed by the nonce anymore. Thus, if `commit` and `verify`
+/// operations are sent from different accounts, `verify` operation is sent only once the corresponding
+/// `commit` operation is confirmed. Withdrawals are always sent after the `verify` operation is confirmed.
+///
+/// # Dry run mode
+///
+/// If `dry_run` option is set, `ETHSender` builds and signs transactions for the incoming
+/// operations and reports them to the log along with the gas estimation, but never stores them
+/// in the database and never broadcasts them. Every such transaction is considered confirmed
+/// right away, so the `verify` and `completeWithdrawals` transactions follow the `commit` ones
+/// just like they do in the normal mode.
+///
 /// # Failure policy
 ///
 /// By default, `ETHSender` expects no transactions to fail, and thus upon a failure it will
@@ -116,16 +134,28 @@ struct ETHSender<DB: DatabaseInterface> {
     db: DB,
     /// Ethereum intermediator.
     ethereum: EthereumGateway,
+    /// Additional accounts sending the operations of certain types.
+    accounts: OperatorAccounts,
     /// Queue for ordered transaction processing.
     tx_queue: TxQueue,
     /// Utility for managing the gas price for transactions.
     gas_adjuster: GasAdjuster<DB>,
     /// Settings for the `ETHSender`.
     options: ETHSenderConfig,
+    /// Last blocks committed and verified in the dry run mode. Operations are not marked
+    /// as processed in the database in this mode, so the ones for these and the preceding
+    /// blocks have to be filtered out once loaded again.
+    dry_run_last_commit: BlockNumber,
+    dry_run_last_verify: BlockNumber,
 }
 
 impl<DB: DatabaseInterface> ETHSender<DB> {
-    pub async fn new(options: ETHSenderConfig, db: DB, ethereum: EthereumGateway) -> Self {
+    pub async fn new(
+        options: ETHSenderConfig,
+        db: DB,
+        ethereum: EthereumGateway,
+        accounts: OperatorAccounts,
+    ) -> Self {
         let mut connection = db
             .acquire_connection()
             .await
@@ -141,23 +171,49 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             .await
             .expect("Failed loading ETH operations stats");
 
-        let tx_queue = TxQueueBuilder::new(options.sender.max_txs_in_flight as usize)
+        // Nonces of the additional accounts are tracked starting with their pending nonces.
+        if !options.sender.dry_run {
+            for account in accounts.accounts() {
+                let nonce = account
+                    .ethereum
+                    .pending_nonce()
+                    .await
+                    .expect("Unable to load the nonce of the operator account");
+                db.init_account_nonce(&mut connection, account.address, nonce.as_u64() as i64)
+                    .await
+                    .expect("Unable to initialize the nonce of the operator account");
+            }
+        }
+
+        let mut tx_queue = TxQueueBuilder::new(options.sender.max_txs_in_flight as usize)
             .with_sent_pending_txs(ongoing_ops.len())
             .with_commit_operations_count(stats.commit_ops)
             .with_verify_operations_count(stats.verify_ops)
-            .with_withdraw_operations_count(stats.withdraw_ops)
-            .build();
+            .with_withdraw_operations_count(stats.withdraw_ops);
+        if accounts.separate_verify_account() {
+            let unconfirmed_commit_ops = ongoing_ops
+                .iter()
+                .filter(|op| op.op_type == OperationType::Commit)
+                .count();
+            tx_queue = tx_queue.with_confirmed_commit_operations_count(
+                stats.commit_ops.saturating_sub(unconfirmed_commit_ops),
+            );
+        }
+        let tx_queue = tx_queue.build();
 
         let gas_adjuster = GasAdjuster::new(&db).await;
 
         drop(connection);
         let mut sender = Self {
             ethereum,
+            accounts,
             ongoing_ops,
             db,
             tx_queue,
             gas_adjuster,
             options,
+            dry_run_last_commit: BlockNumber(0),
+            dry_run_last_verify: BlockNumber(0),
         };
 
         // Add all the unprocessed operations to the queue.
@@ -259,26 +315,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
             match commitment {
                 OperationCommitment::Committed => {
-                    // Free a slot for the next tx in the queue.
-                    self.tx_queue.report_commitment();
-
-                    if current_op.is_verify() {
-                        let sync_op = current_op.clone().op.expect("Should be verify operation");
-                        // Number of times to call `completeWithdrawals` on the contract.
-                        // Value is equal to the number of withdrawals in the block divide by the maximum number of calls, rounded up.
-                        let number_complete_withdrawals_calls =
-                            (sync_op.block.get_withdrawals_count() - 1
-                                + config::MAX_WITHDRAWALS_TO_COMPLETE_IN_A_CALL as usize)
-                                / config::MAX_WITHDRAWALS_TO_COMPLETE_IN_A_CALL as usize;
-
-                        if number_complete_withdrawals_calls > 0 {
-                            // Complete pending withdrawals after each verify.
-                            self.add_complete_withdrawals_to_queue(
-                                number_complete_withdrawals_calls,
-                                sync_op,
-                            );
-                        }
-                    }
+                    self.report_confirmed_operation(&current_op);
                 }
                 OperationCommitment::Pending => {
                     // Poll this operation on the next iteration.
@@ -297,6 +334,30 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         metrics::histogram!("eth_sender.proceed_next_operations", start.elapsed());
     }
 
+    /// Updates the queue once the operation is confirmed: frees a slot for the next tx
+    /// and adds the `completeWithdrawals` operations after the `verify` one.
+    fn report_confirmed_operation(&mut self, op: &ETHOperation) {
+        // Free a slot for the next tx in the queue.
+        self.tx_queue.report_commitment();
+        if op.op_type == OperationType::Commit {
+            self.tx_queue.report_confirmed_commit();
+        }
+
+        if op.is_verify() {
+            let sync_op = op.clone().op.expect("Should be verify operation");
+            // Number of times to call `completeWithdrawals` on the contract.
+            // Value is equal to the number of withdrawals in the block divide by the maximum number of calls, rounded up.
+            let number_complete_withdrawals_calls = (sync_op.block.get_withdrawals_count() - 1
+                + config::MAX_WITHDRAWALS_TO_COMPLETE_IN_A_CALL as usize)
+                / config::MAX_WITHDRAWALS_TO_COMPLETE_IN_A_CALL as usize;
+
+            if number_complete_withdrawals_calls > 0 {
+                // Complete pending withdrawals after each verify.
+                self.add_complete_withdrawals_to_queue(number_complete_withdrawals_calls, sync_op);
+            }
+        }
+    }
+
     async fn process_error(err: anyhow::Error) {
         vlog::warn!("Error while trying to complete uncommitted op: {}", err);
         if err.to_string().contains(RATE_LIMIT_HTTP_CODE) {
@@ -313,6 +374,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
     /// Stores the new operation in the database and sends the corresponding transaction.
     async fn initialize_operation(&mut self, tx: TxData) -> anyhow::Result<()> {
+        if self.options.sender.dry_run {
+            return self.dry_run_operation(tx).await;
+        }
+
         let current_block = self.ethereum.block_number().await?;
         let deadline_block = self.get_deadline_block(current_block.as_u64());
         let gas_price = self
@@ -331,6 +396,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                 .db
                 .save_new_eth_tx(
                     &mut transaction,
+                    self.accounts.address(tx.op_type),
                     tx.op_type,
                     Some(tx.operation.clone()),
                     deadline_block as i64,
@@ -353,7 +419,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             };
 
             // Sign the transaction.
-            let signed_tx = Self::sign_new_tx(&self.ethereum, &new_op).await?;
+            let signed_tx = Self::sign_new_tx(self.signer(new_op.op_type), &new_op).await?;
 
             // With signed tx, update the hash in the operation entry and in the db.
             new_op.used_tx_hashes.push(signed_tx.hash);
@@ -386,6 +452,61 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         Ok(())
     }
 
+    /// Builds and signs the transaction for the operation, estimates the gas required
+    /// for it and reports everything to the log, without sending the transaction.
+    async fn dry_run_operation(&mut self, tx: TxData) -> anyhow::Result<()> {
+        let gas_price = self
+            .gas_adjuster
+            .get_gas_price(&self.ethereum, None)
+            .await?;
+        let nonce = self.signer(tx.op_type).pending_nonce().await?;
+
+        let op = ETHOperation {
+            id: 0, // Operation is not stored, so it has no ID.
+            op_type: tx.op_type,
+            op: Some(tx.operation.clone()),
+            nonce,
+            last_deadline_block: 0,
+            last_used_gas_price: gas_price,
+            used_tx_hashes: vec![],
+            encoded_tx_data: tx.raw.clone(),
+            confirmed: false,
+            final_hash: None,
+        };
+        let signed_tx = Self::sign_new_tx(self.signer(op.op_type), &op).await?;
+
+        // Estimation failure is reported rather than returned: e.g. the contract call may revert
+        // if the Ethereum node has a state that differs from the one of the server.
+        let gas_estimation = match self.ethereum.estimate_gas(tx.raw).await {
+            Ok(gas) => gas.to_string(),
+            Err(err) => format!("<failed: {}>", err),
+        };
+
+        vlog::info!(
+            "Dry run, tx is not sent: [Type: {:?}. ETH tx: {}. Gas limit: {}; gas estimation: {}. ZKSync operation: {}]. Raw tx: 0x{}",
+            op.op_type, self.eth_tx_description(&signed_tx), Self::gas_limit_for_op(&op), gas_estimation,
+            self.zksync_operation_description(&op), hex::encode(&signed_tx.raw_tx),
+        );
+
+        match op.op_type {
+            OperationType::Commit => self.dry_run_last_commit = tx.block(),
+            OperationType::Verify => self.dry_run_last_verify = tx.block(),
+            OperationType::Withdraw => {}
+        }
+        // Transaction is not sent, so it's considered confirmed right away.
+        self.report_confirmed_operation(&op);
+
+        Ok(())
+    }
+
+    /// Returns the gateway signing the transactions of the given type.
+    fn signer(&self, op_type: OperationType) -> &EthereumGateway {
+        self.accounts
+            .account(op_type)
+            .map(|account| &account.ethereum)
+            .unwrap_or(&self.ethereum)
+    }
+
     /// Helper method to obtain the string representation of the Ethereum transaction.
     /// Intended to be used for log entries.
     fn eth_tx_description(&self, tx: &SignedCallResult) -> String {
@@ -448,7 +569,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed(gas_used) => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -482,7 +603,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, gas_used)
                         .await?;
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
@@ -580,7 +701,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed(status.gas_used)
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -680,7 +801,10 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
         let tx_options = self.tx_options_from_stuck_tx(stuck_tx).await?;
 
         let raw_tx = stuck_tx.encoded_tx_data.clone();
-        let signed_tx = self.ethereum.sign_prepared_tx(raw_tx, tx_options).await?;
+        let signed_tx = self
+            .signer(stuck_tx.op_type)
+            .sign_prepared_tx(raw_tx, tx_options)
+            .await?;
 
         stuck_tx.last_deadline_block = deadline_block;
         stuck_tx.last_used_gas_price = signed_tx.gas_price;
@@ -776,8 +900,19 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
 
     /// Encodes the zkSync operation to the tx payload and adds it to the queue.
     fn add_operation_to_queue(&mut self, op: Operation) {
-        let raw_tx = self.operation_to_raw_tx(&op);
         let block_number = op.block.block_number;
+        if self.options.sender.dry_run {
+            let last_processed_block = match op.action {
+                Action::Commit => self.dry_run_last_commit,
+                Action::Verify { .. } => self.dry_run_last_verify,
+            };
+            if block_number <= last_processed_block {
+                // Operation was already processed in the dry run mode.
+                return;
+            }
+        }
+
+        let raw_tx = self.operation_to_raw_tx(&op);
 
         match &op.action {
             Action::Commit => {
@@ -834,14 +969,20 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
     }
 }
 
-#[must_use]
-pub fn run_eth_sender(pool: ConnectionPool, config: ZkSyncConfig) -> JoinHandle<()> {
+/// Starts the Ethereum sender once the Ethereum node is checked to be connected to the configured
+/// L1 network, since the transactions are signed with the configured chain ID.
+pub async fn run_eth_sender(
+    pool: ConnectionPool,
+    config: ZkSyncConfig,
+) -> anyhow::Result<JoinHandle<()>> {
     let client = EthereumGateway::from_config(&config);
+    client.check_l1_network(&config).await?;
+    let accounts = OperatorAccounts::from_config(&config);
     let db = Database::new(pool);
 
-    tokio::spawn(async move {
-        let eth_sender = ETHSender::new(config.eth_sender.clone(), db, client).await;
+    Ok(tokio::spawn(async move {
+        let eth_sender = ETHSender::new(config.eth_sender.clone(), db, client, accounts).await;
 
         eth_sender.run().await
-    })
+    }))
 }
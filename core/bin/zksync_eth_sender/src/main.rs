@@ -7,9 +7,6 @@ use zksync_storage::ConnectionPool;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // `eth_sender` doesn't require many connections to the database.
-    const ETH_SENDER_CONNECTION_POOL_SIZE: u32 = 2;
-
     vlog::init();
 
     // handle ctrl+c
@@ -23,14 +20,18 @@ async fn main() -> anyhow::Result<()> {
         .expect("Error setting Ctrl-C handler");
     }
 
-    let pool = ConnectionPool::new(Some(ETH_SENDER_CONNECTION_POOL_SIZE));
     let config = ZkSyncConfig::from_env();
+    let pool = ConnectionPool::dedicated(
+        "eth_sender",
+        config.db.eth_sender_pool_size,
+        config.db.slow_query_threshold(),
+    );
 
     // Run prometheus data exporter.
     let (prometheus_task_handle, _) =
-        run_prometheus_exporter(pool.clone(), config.api.prometheus.port, false);
+        run_prometheus_exporter(pool.clone(), &config.api.prometheus, false);
 
-    let task_handle = run_eth_sender(pool, config);
+    let task_handle = run_eth_sender(pool, config).await?;
 
     tokio::select! {
         _ = async { task_handle.await } => {
@@ -0,0 +1,80 @@
+//! Additional Ethereum accounts sending the operations of certain types.
+
+// Built-in deps
+// External uses
+use zksync_basic_types::Address;
+// Workspace uses
+use zksync_config::ZkSyncConfig;
+use zksync_eth_client::EthereumGateway;
+use zksync_types::ethereum::OperationType;
+// Local uses
+
+/// Additional account sending the operations of a certain type.
+#[derive(Debug, Clone)]
+pub(super) struct RoleAccount {
+    pub address: Address,
+    /// Gateway signing the transactions with the key of this account.
+    pub ethereum: EthereumGateway,
+}
+
+/// Accounts sending the `verify` and `withdraw` operations, if they differ from the main
+/// operator account which sends the `commit` operations.
+///
+/// Every account has its own nonce and balance, so the stuck transactions or the drained
+/// balance of one account don't block the operations sent from the others.
+#[derive(Debug, Clone, Default)]
+pub(super) struct OperatorAccounts {
+    verify: Option<RoleAccount>,
+    withdraw: Option<RoleAccount>,
+}
+
+impl OperatorAccounts {
+    pub fn from_config(config: &ZkSyncConfig) -> Self {
+        let sender = &config.eth_sender.sender;
+        let commit_address = sender.commit_account().address;
+
+        let role_account = |account: zksync_config::configs::eth_sender::OperatorAccount| {
+            if account.address == commit_address {
+                None
+            } else {
+                Some(RoleAccount {
+                    address: account.address,
+                    ethereum: EthereumGateway::from_config_with_account(config, account),
+                })
+            }
+        };
+
+        Self {
+            verify: role_account(sender.verify_account()),
+            withdraw: role_account(sender.withdraw_account()),
+        }
+    }
+
+    /// Returns the account sending the operations of the given type,
+    /// `None` means that the main operator account is used.
+    pub fn account(&self, op_type: OperationType) -> Option<&RoleAccount> {
+        match op_type {
+            OperationType::Commit => None,
+            OperationType::Verify => self.verify.as_ref(),
+            OperationType::Withdraw => self.withdraw.as_ref(),
+        }
+    }
+
+    /// Returns the address of the account sending the operations of the given type,
+    /// `None` means that the main operator account is used.
+    pub fn address(&self, op_type: OperationType) -> Option<Address> {
+        self.account(op_type).map(|account| account.address)
+    }
+
+    /// Iterates over all the additional accounts.
+    pub fn accounts(&self) -> impl Iterator<Item = &RoleAccount> {
+        self.verify.iter().chain(self.withdraw.iter())
+    }
+
+    /// Whether `commit` and `verify` operations are sent from the different accounts.
+    /// If so, the order of these operations is not guaranteed by the nonce, and `verify`
+    /// operation must not be sent until the corresponding `commit` one is confirmed.
+    pub fn separate_verify_account(&self) -> bool {
+        self.address(OperationType::Verify) != self.address(OperationType::Commit)
+    }
+}
@@ -2,18 +2,18 @@
 
 // Built-in deps
 use crate::database::DatabaseInterface;
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use tokio::sync::RwLock;
 use zksync_config::configs::eth_sender::{ETHSenderConfig, GasLimit, Sender};
 // External uses
 use web3::contract::Options;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace uses
 
 use zksync_eth_client::{clients::mock::MockEthereum, ethereum_gateway::EthereumGateway};
 use zksync_storage::StorageProcessor;
 use zksync_types::{
-    ethereum::{ETHOperation, EthOpId, InsertedOperationResponse, OperationType},
+    ethereum::{ETHOperation, EthOpId, GasAdjusterStats, InsertedOperationResponse, OperationType},
     Action, Operation,
 };
 
@@ -30,9 +30,11 @@ pub(in crate) struct MockDatabase {
     unprocessed_operations: RwLock<BTreeMap<i64, Operation>>,
     confirmed_operations: RwLock<BTreeMap<i64, ETHOperation>>,
     nonce: RwLock<i64>,
+    account_nonces: RwLock<HashMap<Address, i64>>,
     gas_price_limit: RwLock<U256>,
     pending_op_id: RwLock<EthOpId>,
     stats: RwLock<ETHStats>,
+    gas_adjuster_stats: RwLock<Option<GasAdjusterStats>>,
 }
 
 impl MockDatabase {
@@ -63,6 +65,11 @@ impl MockDatabase {
         }
     }
 
+    /// Returns the last stored gas adjuster stats.
+    pub async fn gas_adjuster_stats(&self) -> Option<GasAdjusterStats> {
+        self.gas_adjuster_stats.read().await.clone()
+    }
+
     pub async fn update_gas_price_limit(&self, value: U256) -> anyhow::Result<()> {
         let mut gas_price_limit = self.gas_price_limit.write().await;
         (*gas_price_limit) = value;
@@ -101,6 +108,12 @@ impl MockDatabase {
             .is_none());
     }
 
+    /// Ensures that no Ethereum operations are stored in the database.
+    pub async fn assert_no_operations_stored(&self) {
+        assert!(self.unconfirmed_operations.read().await.is_empty());
+        assert!(self.confirmed_operations.read().await.is_empty());
+    }
+
     async fn next_nonce(&self) -> anyhow::Result<i64> {
         let old_value = *(self.nonce.read().await);
         let mut new_value = self.nonce.write().await;
@@ -108,6 +121,16 @@ impl MockDatabase {
 
         Ok(old_value)
     }
+
+    async fn next_account_nonce(&self, address: Address) -> anyhow::Result<i64> {
+        let mut account_nonces = self.account_nonces.write().await;
+        let nonce = account_nonces
+            .get_mut(&address)
+            .ok_or_else(|| anyhow::format_err!("Nonce of the account is not initialized"))?;
+        *nonce += 1;
+
+        Ok(*nonce - 1)
+    }
 }
 
 #[async_trait::async_trait]
@@ -148,6 +171,16 @@ impl DatabaseInterface for MockDatabase {
         Ok(())
     }
 
+    async fn update_gas_adjuster_stats(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        stats: &GasAdjusterStats,
+    ) -> anyhow::Result<()> {
+        *self.gas_adjuster_stats.write().await = Some(stats.clone());
+
+        Ok(())
+    }
+
     async fn restore_state(
         &self,
         connection: &mut StorageProcessor<'_>,
@@ -161,6 +194,7 @@ impl DatabaseInterface for MockDatabase {
     async fn save_new_eth_tx(
         &self,
         _connection: &mut StorageProcessor<'_>,
+        account: Option<Address>,
         op_type: OperationType,
         op: Option<Operation>,
         deadline_block: i64,
@@ -171,7 +205,10 @@ impl DatabaseInterface for MockDatabase {
         let mut pending_op_id = self.pending_op_id.write().await;
         *pending_op_id = id + 1;
 
-        let nonce = self.next_nonce().await?;
+        let nonce = match account {
+            Some(address) => self.next_account_nonce(address).await?,
+            None => self.next_nonce().await?,
+        };
 
         // Store with the assigned ID.
         let state = ETHOperation {
@@ -220,6 +257,21 @@ impl DatabaseInterface for MockDatabase {
         Ok(())
     }
 
+    async fn init_account_nonce(
+        &self,
+        _connection: &mut StorageProcessor<'_>,
+        address: Address,
+        nonce: i64,
+    ) -> anyhow::Result<()> {
+        self.account_nonces
+            .write()
+            .await
+            .entry(address)
+            .or_insert(nonce);
+
+        Ok(())
+    }
+
     async fn update_eth_tx(
         &self,
         _connection: &mut StorageProcessor<'_>,
@@ -249,6 +301,7 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
         _op: &ETHOperation,
+        _gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut unconfirmed_operations = self.unconfirmed_operations.write().await;
         let mut op_idx: Option<i64> = None;
@@ -362,8 +415,13 @@ async fn build_eth_sender(
             wait_confirmations: super::WAIT_CONFIRMATIONS,
             tx_poll_period: 0,
             is_enabled: true,
+            dry_run: false,
             operator_commit_eth_addr: Default::default(),
             operator_private_key: Default::default(),
+            operator_verify_private_key: None,
+            operator_verify_eth_addr: None,
+            operator_withdraw_private_key: None,
+            operator_withdraw_eth_addr: None,
         },
         gas_price_limit: GasLimit {
             default: 1000,
@@ -373,7 +431,7 @@ async fn build_eth_sender(
         },
     };
 
-    ETHSender::new(options, db, ethereum).await
+    ETHSender::new(options, db, ethereum, Default::default()).await
 }
 
 /// Behaves the same as `ETHSender::sign_new_tx`, but does not affect nonce.
@@ -8,6 +8,7 @@ use super::{
     ETHSender, TxCheckMode,
 };
 use zksync_eth_client::ethereum_gateway::ExecutedTxStatus;
+use zksync_types::U256;
 
 const EXPECTED_WAIT_TIME_BLOCKS: u64 = 30;
 const WAIT_CONFIRMATIONS: u64 = 3;
@@ -73,6 +74,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: Some(U256::from(150_000)),
     };
     eth_sender
         .ethereum
@@ -86,6 +88,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -99,6 +102,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -112,6 +116,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -134,7 +139,7 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed(Some(U256::from(150_000)))
     );
 
     // Pending operation (no enough confirmations).
@@ -834,3 +839,49 @@ async fn concurrent_operations_order() {
         eth_sender.db.assert_confirmed(&withdraw_tx).await;
     }
 }
+
+/// Checks that in the dry run mode operations are processed in the same order as
+/// in the normal mode, but neither stored in the database nor sent to the Ethereum.
+#[tokio::test]
+async fn dry_run() {
+    let mut eth_sender = default_eth_sender().await;
+    eth_sender.options.sender.dry_run = true;
+
+    let commit_operation = test_data::commit_operation(0);
+    let verify_operation = test_data::verify_operation(0);
+    for operation in vec![commit_operation.clone(), verify_operation.clone()] {
+        eth_sender.db.send_operation(operation).await.unwrap();
+    }
+    eth_sender.load_new_operations().await;
+    eth_sender.proceed_next_operations().await;
+
+    // Transactions are considered confirmed right away, so the `verify` and
+    // `completeWithdrawals` transactions are processed along with the `commit` one.
+    assert!(eth_sender.ongoing_ops.is_empty());
+    assert!(eth_sender.tx_queue.pop_front().is_none());
+    assert_eq!(
+        eth_sender.dry_run_last_commit,
+        commit_operation.block.block_number
+    );
+    assert_eq!(
+        eth_sender.dry_run_last_verify,
+        verify_operation.block.block_number
+    );
+    eth_sender.db.assert_no_operations_stored().await;
+    assert!(eth_sender
+        .ethereum
+        .get_mock()
+        .unwrap()
+        .sent_txs
+        .read()
+        .await
+        .is_empty());
+
+    // Operations are not marked as processed in the database, so they will be loaded again.
+    // They should not be processed the second time.
+    for operation in vec![commit_operation, verify_operation] {
+        eth_sender.db.send_operation(operation).await.unwrap();
+    }
+    eth_sender.load_new_operations().await;
+    assert!(eth_sender.tx_queue.pop_front().is_none());
+}
@@ -59,6 +59,7 @@ pub struct TxQueueBuilder {
     commit_operations_count: usize,
     verify_operations_count: usize,
     withdraw_operations_count: usize,
+    confirmed_commit_operations_count: Option<usize>,
 }
 
 impl TxQueueBuilder {
@@ -70,6 +71,7 @@ impl TxQueueBuilder {
             commit_operations_count: 0,
             verify_operations_count: 0,
             withdraw_operations_count: 0,
+            confirmed_commit_operations_count: None,
         }
     }
 
@@ -105,6 +107,21 @@ impl TxQueueBuilder {
         }
     }
 
+    /// Makes the queue yield `verify` operations only once the corresponding `commit` operations
+    /// are confirmed rather than sent, and sets the amount of confirmed `commit` operations.
+    ///
+    /// Required if `commit` and `verify` operations are sent from different accounts,
+    /// since the order of such transactions is not guaranteed by the nonce.
+    pub fn with_confirmed_commit_operations_count(
+        self,
+        confirmed_commit_operations_count: usize,
+    ) -> Self {
+        Self {
+            confirmed_commit_operations_count: Some(confirmed_commit_operations_count),
+            ..self
+        }
+    }
+
     /// Finishes the queue building process.
     pub fn build(self) -> TxQueue {
         // Block numbers are indexed starting from 1, so we have to increment.
@@ -117,6 +134,7 @@ impl TxQueueBuilder {
             commit_operations: CounterQueue::new(self.commit_operations_count),
             verify_operations: SparseQueue::new(verify_operations_next_block),
             withdraw_operations: WithdrawalsCounterQueue::new(self.withdraw_operations_count),
+            confirmed_commit_operations: self.confirmed_commit_operations_count,
         }
     }
 }
@@ -131,7 +149,8 @@ impl TxQueueBuilder {
 ///   - If `verify` queue contains elements, and `commit` operation with corresponding
 ///     ID is committed, the `verify` operation is yielded (meaning that `verify` operations
 ///     are prioritized unless the amount of sent `commit` and `verify` operations is equal:
-///     if so, we should send the `commit` operation first).
+///     if so, we should send the `commit` operation first). If operations are sent from
+///     different accounts, `commit` operation must be confirmed rather than just sent.
 ///   - Otherwise, if `withdraw` queue contains elements, a `withdraw` operation is yielded.
 ///   - Otherwise, if `commit` queue is not empty, a `commit` operation is yielded.
 /// 3. If all the queues are empty, no operation is returned.
@@ -143,6 +162,9 @@ pub struct TxQueue {
     commit_operations: CounterQueue<TxData>,
     verify_operations: SparseQueue<TxData>,
     withdraw_operations: WithdrawalsCounterQueue,
+    /// Amount of confirmed `commit` operations, only tracked if `verify` operations
+    /// have to wait for the confirmation of the corresponding `commit` ones.
+    confirmed_commit_operations: Option<usize>,
 }
 
 impl TxQueue {
@@ -258,7 +280,10 @@ impl TxQueue {
         // If we've committed a corresponding `Commit` operation, and
         // there is a pending `verify` operation, chose it.
         let next_verify_op_id = self.verify_operations.next_id();
-        let next_commit_op_id = self.commit_operations.get_count() + 1;
+        let next_commit_op_id = match self.confirmed_commit_operations {
+            Some(confirmed_commit_operations) => confirmed_commit_operations + 1,
+            None => self.commit_operations.get_count() + 1,
+        };
         if next_verify_op_id < next_commit_op_id && self.verify_operations.has_next() {
             return Some(self.verify_operations.pop_front().unwrap());
         }
@@ -294,6 +319,13 @@ impl TxQueue {
 
         self.sent_pending_txs -= 1;
     }
+
+    /// Notifies the queue about the `commit` operation being confirmed on the Ethereum blockchain.
+    pub fn report_confirmed_commit(&mut self) {
+        if let Some(confirmed_commit_operations) = self.confirmed_commit_operations.as_mut() {
+            *confirmed_commit_operations += 1;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -461,6 +493,43 @@ mod tests {
         assert_eq!(queue.sent_pending_txs, pending_count);
     }
 
+    /// Checks that `verify` operation is not yielded until the corresponding `commit` one
+    /// is confirmed if the queue is configured to wait for confirmations.
+    #[test]
+    fn verify_after_confirmed_commit() {
+        const MAX_IN_FLY: usize = 3;
+        const COMMIT_MARK: u8 = 0;
+        const VERIFY_MARK: u8 = 1;
+
+        let mut queue = TxQueueBuilder::new(MAX_IN_FLY)
+            .with_confirmed_commit_operations_count(0)
+            .build();
+
+        queue.add_commit_operation(get_tx_data(
+            OperationType::Commit,
+            BlockNumber(1),
+            vec![COMMIT_MARK, 0],
+        ));
+        queue.add_commit_operation(get_tx_data(
+            OperationType::Commit,
+            BlockNumber(2),
+            vec![COMMIT_MARK, 1],
+        ));
+        queue.add_verify_operation(
+            1,
+            get_tx_data(OperationType::Verify, BlockNumber(1), vec![VERIFY_MARK, 0]),
+        );
+
+        // Both commit operations are sent, but `verify` waits for the confirmation.
+        assert_eq!(queue.pop_front().unwrap().raw, vec![COMMIT_MARK, 0]);
+        assert_eq!(queue.pop_front().unwrap().raw, vec![COMMIT_MARK, 1]);
+        assert_eq!(queue.pop_front(), None);
+
+        queue.report_commitment();
+        queue.report_confirmed_commit();
+        assert_eq!(queue.pop_front().unwrap().raw, vec![VERIFY_MARK, 0]);
+    }
+
     #[test]
     #[should_panic(expected = "No transactions are expected to be returned")]
     fn return_popped_empty() {
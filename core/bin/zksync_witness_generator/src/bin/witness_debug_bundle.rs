@@ -0,0 +1,54 @@
+//! Offline tool building the debug bundles of the blocks the provers failed to prove.
+//!
+//! The tool synthesizes the whole block circuit in memory, so it should be run on a machine
+//! with enough memory rather than alongside the server.
+
+use structopt::StructOpt;
+use zksync_cold_storage::ColdStorage;
+use zksync_config::ColdStorageConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+use zksync_witness_generator::debug_bundle::collect_debug_bundle;
+
+#[derive(StructOpt)]
+#[structopt(name = "zkSync witness debug bundle builder", author = "Matter Labs")]
+struct Opt {
+    /// Block to rebuild the debug bundle for. If not set, the bundles are built for all
+    /// the reported proving failures which don't have one yet.
+    #[structopt(long)]
+    block: Option<u32>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+    vlog::init();
+
+    let pool = ConnectionPool::new(Some(1));
+    let cold_storage = ColdStorage::from_config(&ColdStorageConfig::from_env());
+    if let Some(block) = opt.block {
+        let block = BlockNumber(block);
+        anyhow::ensure!(
+            collect_debug_bundle(&pool, &cold_storage, block).await?,
+            "No proving failure reported for block {}",
+            block
+        );
+        return Ok(());
+    }
+
+    loop {
+        let block = pool
+            .access_storage()
+            .await?
+            .prover_schema()
+            .debug_bundle_job()
+            .await?;
+        match block {
+            Some(block) => {
+                collect_debug_bundle(&pool, &cold_storage, block).await?;
+            }
+            None => break,
+        }
+    }
+    Ok(())
+}
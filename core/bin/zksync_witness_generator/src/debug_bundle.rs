@@ -0,0 +1,139 @@
+//! Debug bundles of the blocks the provers failed to prove.
+//!
+//! Finding the unsatisfied constraint requires synthesizing the whole block circuit with the
+//! test constraint system, which keeps every constraint in memory. So the bundles are not built
+//! by the server, but by the `witness_debug_bundle` tool run offline by the operator.
+
+// Built-in
+use std::time;
+// External
+use serde_json::json;
+// Workspace deps
+use zksync_cold_storage::ColdStorage;
+use zksync_crypto::franklin_crypto::{bellman::Circuit, circuit::test::TestConstraintSystem};
+use zksync_crypto::Engine;
+use zksync_prover_utils::prover_data::ProverData;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+/// Debugging data collected for the block the prover failed to prove.
+#[derive(Debug)]
+pub struct DebugBundle {
+    pub unsatisfied_constraint: Option<String>,
+    pub num_constraints: Option<u64>,
+    pub bundle: serde_json::Value,
+}
+
+impl DebugBundle {
+    fn error(error: String) -> Self {
+        Self {
+            unsatisfied_constraint: None,
+            num_constraints: None,
+            bundle: json!({ "error": error }),
+        }
+    }
+}
+
+/// Operations are processed by the circuit in the namespaces named `chunk number {idx}`,
+/// so the index of the operation is taken from the path of the unsatisfied constraint.
+fn failed_operation_index(constraint: &str) -> Option<usize> {
+    const CHUNK_NAMESPACE: &str = "chunk number ";
+    let start = constraint.find(CHUNK_NAMESPACE)? + CHUNK_NAMESPACE.len();
+    constraint[start..].split('/').next()?.parse().ok()
+}
+
+/// Synthesizes the circuit of the block with the test constraint system to find
+/// the first unsatisfied constraint. The bundle contains only the inputs of the operation
+/// the unsatisfied constraint belongs to, the whole witness is kept in the database anyway.
+pub fn build_debug_bundle(block_number: BlockNumber, witness: serde_json::Value) -> DebugBundle {
+    let start = time::Instant::now();
+    let prover_data: ProverData = match serde_json::from_value(witness.clone()) {
+        Ok(prover_data) => prover_data,
+        Err(err) => return DebugBundle::error(format!("failed to deserialize witness: {}", err)),
+    };
+
+    let circuit = prover_data.into_circuit(i64::from(*block_number));
+    let mut cs = TestConstraintSystem::<Engine>::new();
+    if let Err(err) = circuit.synthesize(&mut cs) {
+        return DebugBundle::error(format!("failed to synthesize circuit: {}", err));
+    }
+    let unsatisfied_constraint = cs.which_is_unsatisfied().map(str::to_string);
+    let operation = unsatisfied_constraint
+        .as_deref()
+        .and_then(failed_operation_index)
+        .and_then(|idx| Some((idx, witness["operations"].get(idx)?.clone())));
+
+    metrics::histogram!("witness_generator.build_debug_bundle", start.elapsed());
+    DebugBundle {
+        unsatisfied_constraint,
+        num_constraints: Some(cs.num_constraints() as u64),
+        bundle: json!({
+            "failedOperation": operation.map(|(idx, operation)| json!({
+                "index": idx,
+                "operation": operation,
+            })),
+        }),
+    }
+}
+
+/// Builds and stores the debug bundle for the block. Returns `false` if the proving failure
+/// was not reported for the block. The witness is loaded from the cold storage if it was
+/// moved there already.
+pub async fn collect_debug_bundle(
+    pool: &ConnectionPool,
+    cold_storage: &ColdStorage,
+    block_number: BlockNumber,
+) -> anyhow::Result<bool> {
+    let witness = {
+        let mut storage = pool.access_storage().await?;
+        if storage
+            .prover_schema()
+            .load_debug_bundle(block_number)
+            .await?
+            .is_none()
+        {
+            return Ok(false);
+        }
+        cold_storage
+            .load_witness(&mut storage, block_number)
+            .await?
+    };
+    vlog::info!("Building the debug bundle for block {}", block_number);
+
+    let debug_bundle = match witness {
+        Some(witness) => {
+            tokio::task::spawn_blocking(move || build_debug_bundle(block_number, witness)).await?
+        }
+        None => DebugBundle::error("witness for the block is not found".to_string()),
+    };
+    let mut storage = pool.access_storage().await?;
+    storage
+        .prover_schema()
+        .store_debug_bundle(
+            block_number,
+            debug_bundle.unsatisfied_constraint.as_deref(),
+            debug_bundle.num_constraints,
+            debug_bundle.bundle,
+        )
+        .await?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_operation_index() {
+        assert_eq!(
+            failed_operation_index("chunk number 12/execute_op/transfer/enforce balance"),
+            Some(12)
+        );
+        assert_eq!(failed_operation_index("chunk number 3"), Some(3));
+        assert_eq!(
+            failed_operation_index("validator balance number i 0/enforce"),
+            None
+        );
+    }
+}
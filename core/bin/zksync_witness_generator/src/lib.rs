@@ -16,14 +16,20 @@ use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 // Workspace deps
-use zksync_config::ZkSyncConfig;
-use zksync_prover_utils::api::{BlockToProveRes, ProverReq, PublishReq, WorkingOnReq};
+use zksync_config::{configs::prover::WitnessGenerator as WitnessGeneratorOpts, ZkSyncConfig};
+use zksync_crypto::proof::EncodedProofPlonk;
+use zksync_prover_utils::api::{
+    BlockToProveRes, JobFailedReq, ProofChunkReq, ProofChunkRes, ProverReq, ProverVersion,
+    PublishReq, WorkingOnReq, PROOF_CHUNK_SIZE,
+};
+use zksync_state::tree::StateTree;
 use zksync_storage::ConnectionPool;
 use zksync_types::BlockNumber;
 // Local deps
 use self::scaler::ScalerOracle;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+pub mod debug_bundle;
 mod scaler;
 mod witness_generator;
 
@@ -35,12 +41,18 @@ struct PayloadAuthToken {
     exp: usize,
 }
 
+/// Maximum size of the proof accepted via the chunked upload.
+const MAX_PROOF_SIZE: u64 = 1 << 30;
+/// Maximum total size of the unfinished proof uploads stored by the server.
+const MAX_PENDING_UPLOADS_SIZE: u64 = 4 << 30;
+
 #[derive(Debug, Clone)]
 struct AppState {
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
     scaler_oracle: Arc<RwLock<ScalerOracle>>,
     prover_timeout: Duration,
+    version: ProverVersion,
 }
 
 impl AppState {
@@ -49,6 +61,7 @@ impl AppState {
         connection_pool: ConnectionPool,
         prover_timeout: Duration,
         idle_provers: u32,
+        version: ProverVersion,
     ) -> Self {
         let scaler_oracle = Arc::new(RwLock::new(ScalerOracle::new(
             connection_pool.clone(),
@@ -60,6 +73,33 @@ impl AppState {
             connection_pool,
             scaler_oracle,
             prover_timeout,
+            version,
+        }
+    }
+
+    /// Checks that the prover was built against the same protocol and circuit versions as the server,
+    /// since proofs produced by an incompatible prover will fail the verification on L1.
+    fn check_prover_version(&self, req: &ProverReq) -> actix_web::Result<()> {
+        match &req.version {
+            Some(version) if *version == self.version => Ok(()),
+            Some(version) => {
+                vlog::warn!(
+                    "Rejected prover {} with incompatible version: {}",
+                    req.name,
+                    version
+                );
+                Err(actix_web::error::ErrorConflict(format!(
+                    "incompatible prover version: {}, server version: {}",
+                    version, self.version
+                )))
+            }
+            None => {
+                vlog::warn!("Rejected prover {} without version", req.name);
+                Err(actix_web::error::ErrorConflict(format!(
+                    "prover version is not specified, server version: {}",
+                    self.version
+                )))
+            }
         }
     }
 
@@ -113,6 +153,7 @@ async fn register(data: web::Data<AppState>, r: web::Json<ProverReq>) -> actix_w
     if r.name.is_empty() {
         return Err(actix_web::error::ErrorBadRequest("empty name"));
     }
+    data.check_prover_version(&r)?;
     let mut storage = data.access_storage().await?;
     let id = storage
         .prover_schema()
@@ -133,6 +174,7 @@ async fn block_to_prove(
     if r.name.is_empty() {
         return Err(actix_web::error::ErrorBadRequest("empty name"));
     }
+    data.check_prover_version(&r)?;
     let mut storage = data.access_storage().await?;
     let ret = storage
         .prover_schema()
@@ -208,18 +250,119 @@ async fn working_on(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Records the proving failure reported by the prover. The debug bundle for the failed block
+/// is built afterwards by the `witness_debug_bundle` tool.
+async fn job_failed(
+    data: web::Data<AppState>,
+    r: web::Json<JobFailedReq>,
+) -> actix_web::Result<HttpResponse> {
+    vlog::error!(
+        "Prover run with id {} failed to prove block {}: {}",
+        r.prover_run_id,
+        r.block,
+        r.error
+    );
+    let mut storage = data
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    storage
+        .prover_schema()
+        .report_proving_failure(BlockNumber(r.block), &r.error)
+        .await
+        .map_err(|e| {
+            vlog::warn!("failed to record proving failure: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 async fn publish(
     data: web::Data<AppState>,
     r: web::Json<PublishReq>,
 ) -> actix_web::Result<HttpResponse> {
     vlog::info!("Received a proof for block: {}", r.block);
+    store_proof(&data, r.block, &r.proof).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Receives the next part of the serialized proof. Once the proof is received completely, it's stored.
+///
+/// Chunks that don't continue the already received data (e.g. the ones sent again after a network
+/// failure) are ignored, and the prover continues the upload from the offset returned in response.
+/// Uploads are kept in the database separately for every prover run, and the ones which didn't
+/// receive a chunk for longer than the prover timeout are dropped.
+async fn publish_chunk(
+    data: web::Data<AppState>,
+    r: web::Query<ProofChunkReq>,
+    chunk: web::Bytes,
+) -> actix_web::Result<HttpResponse> {
+    if r.total_size > MAX_PROOF_SIZE {
+        return Err(actix_web::error::ErrorBadRequest("proof is too large"));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let uploaded = storage
+        .prover_schema()
+        .store_proof_chunk(
+            BlockNumber(r.block),
+            r.prover_run_id,
+            r.total_size,
+            r.offset,
+            &chunk,
+            data.prover_timeout,
+            MAX_PENDING_UPLOADS_SIZE,
+        )
+        .await
+        .map_err(|e| {
+            vlog::warn!("Failed to store the proof chunk: {}", e);
+            actix_web::error::ErrorInternalServerError("storage layer error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorServiceUnavailable("too many proof uploads"))?;
+    vlog::trace!(
+        "Received {}/{} bytes of the proof for block {} from prover run {}",
+        uploaded,
+        r.total_size,
+        r.block,
+        r.prover_run_id
+    );
+
+    if uploaded == r.total_size {
+        let proof = storage
+            .prover_schema()
+            .finish_proof_upload(BlockNumber(r.block), r.prover_run_id)
+            .await
+            .map_err(|e| {
+                vlog::warn!("Failed to load the uploaded proof: {}", e);
+                actix_web::error::ErrorInternalServerError("storage layer error")
+            })?;
+        drop(storage);
+
+        vlog::info!("Received a proof for block: {}", r.block);
+        let proof: EncodedProofPlonk = serde_json::from_slice(&proof).map_err(|e| {
+            vlog::warn!("Failed to deserialize proof for block {}: {}", r.block, e);
+            actix_web::error::ErrorBadRequest("malformed proof")
+        })?;
+        store_proof(&data, r.block, &proof).await?;
+    }
+
+    Ok(HttpResponse::Ok().json(ProofChunkRes { uploaded }))
+}
+
+async fn store_proof(
+    data: &AppState,
+    block: u32,
+    proof: &EncodedProofPlonk,
+) -> actix_web::Result<()> {
     let mut storage = data
         .access_storage()
         .await
         .map_err(actix_web::error::ErrorInternalServerError)?;
     if let Err(e) = storage
         .prover_schema()
-        .store_proof(BlockNumber(r.block), &r.proof)
+        .store_proof(BlockNumber(block), proof)
         .await
     {
         vlog::error!("failed to store received proof: {}", e);
@@ -231,7 +374,7 @@ async fn publish(
         return Err(actix_web::error::ErrorInternalServerError(message));
     }
 
-    Ok(HttpResponse::Ok().finish())
+    Ok(())
 }
 
 async fn stopped(
@@ -306,14 +449,54 @@ async fn required_replicas(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Starts the witness generator threads. Jobs are taken from the database and the witnesses
+/// are stored there, so the witness generators can be run separately from the prover server.
+pub fn run_witness_generators(
+    connection_pool: zksync_storage::ConnectionPool,
+    panic_notify: mpsc::Sender<bool>,
+    witness_generator_opts: &WitnessGeneratorOpts,
+) {
+    for idx in 0..witness_generator_opts.witness_generators {
+        let worker = format!("witness_generator_{}_{}", std::process::id(), idx);
+        vlog::info!("Starting witness generator {}", worker);
+        // Every witness generator keeps its own account tree.
+        let account_tree_path = witness_generator_opts
+            .account_tree_path
+            .as_ref()
+            .map(|path| format!("{}/{}", path, idx));
+        let account_tree = StateTree::open(account_tree_path.as_deref())
+            .expect("Failed to open the witness generator account tree");
+        let pool_maintainer = witness_generator::WitnessGenerator::new(
+            connection_pool.clone(),
+            witness_generator_opts.prepare_data_interval(),
+            worker,
+            witness_generator_opts.job_timeout(),
+            witness_generator_opts.max_pending_jobs,
+            account_tree,
+        );
+        pool_maintainer.start(panic_notify.clone());
+    }
+}
+
+/// Starts the prover API server along with the witness generators.
+/// Witness generators can be disabled by setting their amount to zero in the config
+/// if they are run as a separate application.
 pub fn run_prover_server(
     connection_pool: zksync_storage::ConnectionPool,
     panic_notify: mpsc::Sender<bool>,
     config: ZkSyncConfig,
 ) {
-    let witness_generator_opts = config.prover.witness_generator;
+    run_witness_generators(
+        connection_pool.clone(),
+        panic_notify.clone(),
+        &config.prover.witness_generator,
+    );
+
     let core_opts = config.prover.core;
     let prover_api_opts = config.api.prover;
+    let version =
+        ProverVersion::from_verification_keys(&config.chain.circuit.supported_block_chunks_sizes)
+            .expect("Failed to load the verification keys to determine the prover version");
 
     thread::Builder::new()
         .name("prover_server".to_string())
@@ -322,38 +505,6 @@ pub fn run_prover_server(
             let mut actix_runtime = actix_rt::System::new("prover-server");
 
             actix_runtime.block_on(async move {
-                let last_verified_block = {
-                    let mut storage = connection_pool
-                        .access_storage()
-                        .await
-                        .expect("Failed to access storage");
-
-                    *storage
-                        .chain()
-                        .block_schema()
-                        .get_last_verified_block()
-                        .await
-                        .expect("Failed to get last verified block number")
-                        as usize
-                };
-
-                // Start pool maintainer threads.
-                for offset in 0..witness_generator_opts.witness_generators {
-                    let start_block = (last_verified_block + offset + 1) as u32;
-                    let block_step = witness_generator_opts.witness_generators as u32;
-                    vlog::info!(
-                        "Starting witness generator ({},{})",
-                        start_block,
-                        block_step
-                    );
-                    let pool_maintainer = witness_generator::WitnessGenerator::new(
-                        connection_pool.clone(),
-                        witness_generator_opts.prepare_data_interval(),
-                        BlockNumber(start_block),
-                        BlockNumber(block_step),
-                    );
-                    pool_maintainer.start(panic_notify.clone());
-                }
                 // Start HTTP server.
                 let secret_auth = prover_api_opts.secret_auth.clone();
                 let gone_timeout = core_opts.gone_timeout();
@@ -364,6 +515,7 @@ pub fn run_prover_server(
                         connection_pool.clone(),
                         gone_timeout,
                         idle_provers,
+                        version.clone(),
                     );
 
                     let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -386,8 +538,14 @@ pub fn run_prover_server(
                         .route("/register", web::post().to(register))
                         .route("/block_to_prove", web::get().to(block_to_prove))
                         .route("/working_on", web::post().to(working_on))
+                        .route("/job_failed", web::post().to(job_failed))
                         .route("/prover_data", web::get().to(prover_data))
                         .route("/publish", web::post().to(publish))
+                        .service(
+                            web::resource("/publish_chunk")
+                                .app_data(web::PayloadConfig::new(PROOF_CHUNK_SIZE))
+                                .route(web::post().to(publish_chunk)),
+                        )
                         .route("/stopped", web::post().to(stopped))
                         .route(
                             "/api/internal/prover/replicas",
@@ -1,15 +1,25 @@
 use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
 use std::cell::RefCell;
+use structopt::StructOpt;
 use zksync_config::ZkSyncConfig;
 use zksync_prometheus_exporter::run_prometheus_exporter;
+use zksync_prover_utils::key_management::provision_keys;
 use zksync_storage::ConnectionPool;
-use zksync_witness_generator::run_prover_server;
+use zksync_witness_generator::{run_prover_server, run_witness_generators};
+
+#[derive(StructOpt)]
+#[structopt(name = "zkSync witness generator", author = "Matter Labs")]
+struct Opt {
+    /// Run only the witness generators without the prover API server.
+    /// Jobs are taken from the database, so the witness generators can be deployed separately
+    /// from the server, which should be started with the witness generators disabled.
+    #[structopt(long)]
+    witness_only: bool,
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // `witness_generator` doesn't require many connections to the database.
-    const WITNESS_GENERATOR_CONNECTION_POOL_SIZE: u32 = 2;
-
+    let opt = Opt::from_args();
     vlog::init();
 
     // handle ctrl+c
@@ -23,14 +33,38 @@ async fn main() -> anyhow::Result<()> {
         .expect("Error setting Ctrl-C handler");
     }
 
-    let connection_pool = ConnectionPool::new(Some(WITNESS_GENERATOR_CONNECTION_POOL_SIZE));
     let config = ZkSyncConfig::from_env();
+    let connection_pool = ConnectionPool::dedicated(
+        "prover",
+        config.db.prover_pool_size,
+        config.db.slow_query_threshold(),
+    );
+
+    // Keys downloading is blocking, so it should not be performed within the async context.
+    let (keys_config, chain_config) = (config.prover.keys.clone(), config.chain.clone());
+    tokio::task::spawn_blocking(move || {
+        provision_keys(
+            &keys_config,
+            &chain_config.circuit,
+            &chain_config.block_chunk_sizes(),
+        )
+    })
+    .await??;
 
     // Run prometheus data exporter.
     let (prometheus_task_handle, _) =
-        run_prometheus_exporter(connection_pool.clone(), config.api.prometheus.port, false);
+        run_prometheus_exporter(connection_pool.clone(), &config.api.prometheus, false);
 
-    run_prover_server(connection_pool, stop_signal_sender, config);
+    if opt.witness_only {
+        vlog::info!("Starting the witness generators without the prover server");
+        run_witness_generators(
+            connection_pool,
+            stop_signal_sender,
+            &config.prover.witness_generator,
+        );
+    } else {
+        run_prover_server(connection_pool, stop_signal_sender, config);
+    }
 
     tokio::select! {
         _ = async { prometheus_task_handle.await } => {
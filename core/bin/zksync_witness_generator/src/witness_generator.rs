@@ -1,36 +1,51 @@
 // Built-in
 use std::{thread, time};
 // External
+use anyhow::{bail, format_err};
 use futures::channel::mpsc;
 // Workspace deps
 use zksync_circuit::witness::utils::build_block_witness;
-use zksync_crypto::circuit::CircuitAccountTree;
+use zksync_crypto::circuit::{CircuitAccountTree, WitnessAccountTree};
 use zksync_crypto::params::account_tree_depth;
+use zksync_crypto::primitives::EthereumSerializer;
 use zksync_prover_utils::prover_data::ProverData;
+use zksync_state::tree::StateTree;
 use zksync_storage::StorageProcessor;
 use zksync_types::block::Block;
-use zksync_types::BlockNumber;
+use zksync_types::{AccountId, BlockNumber};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+/// Number of times the job assignment is refreshed within the job timeout while its witness is being built.
+const JOB_HEARTBEATS_PER_TIMEOUT: u32 = 4;
+
 /// The essential part of this structure is `maintain` function
 /// which runs forever and adds data to the database.
 ///
-/// This will generate and store in db witnesses for blocks with indexes
-/// start_block, start_block + block_step, start_block + 2*block_step, ...
+/// Blocks to generate witness for are assigned to the witness generators through the database,
+/// so if the witness generator is interrupted, its unfinished block is taken by another one
+/// once the job timeout is exceeded.
+///
+/// Witnesses are built on the blocking tasks pool, and while the witness for one block
+/// is being built, the state required for the next block is prefetched from the database.
 pub struct WitnessGenerator {
     /// Connection to the database.
     conn_pool: zksync_storage::ConnectionPool,
     /// Routine refresh interval.
     rounds_interval: time::Duration,
-
-    start_block: BlockNumber,
-    block_step: BlockNumber,
+    /// Name of the witness generator the jobs are assigned to.
+    worker: String,
+    /// Timeout after which an unfinished job is considered abandoned.
+    job_timeout: time::Duration,
+    /// Maximum amount of blocks with a witness awaiting for proof.
+    max_pending_jobs: u32,
+    /// Account tree stored in RocksDB, used to load the state of the blocks instead of the database.
+    account_tree: StateTree,
 }
 
-enum BlockInfo {
-    NotReadyBlock,
-    WithWitness,
-    NoWitness(Block),
+/// Block along with the prefetched state required to build its witness.
+struct WitnessJob {
+    block: Block,
+    account_tree: Box<dyn WitnessAccountTree + Send>,
 }
 
 impl WitnessGenerator {
@@ -38,14 +53,18 @@ impl WitnessGenerator {
     pub fn new(
         conn_pool: zksync_storage::ConnectionPool,
         rounds_interval: time::Duration,
-        start_block: BlockNumber,
-        block_step: BlockNumber,
+        worker: String,
+        job_timeout: time::Duration,
+        max_pending_jobs: u32,
+        account_tree: StateTree,
     ) -> Self {
         Self {
             conn_pool,
             rounds_interval,
-            start_block,
-            block_step,
+            worker,
+            job_timeout,
+            max_pending_jobs,
+            account_tree,
         }
     }
 
@@ -68,64 +87,141 @@ impl WitnessGenerator {
             .expect("failed to start provers server");
     }
 
-    /// Returns status of witness for block with index block_number
-    async fn should_work_on_block(
-        &self,
-        block_number: BlockNumber,
-    ) -> Result<BlockInfo, anyhow::Error> {
+    /// Checks whether the provers are lagging behind too much, so no new witnesses
+    /// should be generated until the un-proven jobs queue shrinks.
+    async fn is_backpressured(&self) -> Result<bool, anyhow::Error> {
         let mut storage = self.conn_pool.access_storage().await?;
-        let mut transaction = storage.start_transaction().await?;
-        let block = transaction
-            .chain()
-            .block_schema()
-            .get_block(block_number)
-            .await?;
-        let block_info = if let Some(block) = block {
-            let witness = transaction
-                .prover_schema()
-                .get_witness(block_number)
-                .await?;
-            if witness.is_none() {
-                BlockInfo::NoWitness(block)
-            } else {
-                BlockInfo::WithWitness
+        let pending_jobs = storage.prover_schema().pending_jobs_count().await?;
+
+        let backpressured = pending_jobs >= self.max_pending_jobs;
+        metrics::gauge!("witness_generator.pending_jobs", pending_jobs as f64);
+        metrics::gauge!(
+            "witness_generator.backpressure",
+            if backpressured { 1.0 } else { 0.0 }
+        );
+        Ok(backpressured)
+    }
+
+    /// Brings the account tree stored in RocksDB to the given block by applying the updates
+    /// between the blocks, which is much cheaper than loading the whole state from the database.
+    /// The tree is reconciled with the state loaded from the database if it's empty or doesn't
+    /// match the block afterwards.
+    async fn sync_stored_tree(
+        &mut self,
+        stored_block: BlockNumber,
+        block: BlockNumber,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<(), anyhow::Error> {
+        let mut synced = false;
+        if *stored_block != 0 && *block != 0 {
+            // The updates are reverted if the tree is ahead of the block.
+            if let Some((_, updates)) = storage
+                .chain()
+                .state_schema()
+                .load_state_diff(stored_block, Some(block))
+                .await?
+            {
+                self.account_tree.apply_updates(updates);
             }
-        } else {
-            BlockInfo::NotReadyBlock
-        };
-        transaction.commit().await?;
-        Ok(block_info)
+            let root_hash = storage
+                .chain()
+                .block_schema()
+                .get_block_root_hash(block)
+                .await?;
+            synced = root_hash == Some(self.account_tree.root_hash());
+        }
+        if !synced {
+            let (_, accounts) = storage
+                .chain()
+                .state_schema()
+                .load_committed_state(Some(block))
+                .await?;
+            self.account_tree.reconcile(&accounts);
+        }
+        self.account_tree.flush(block)?;
+        Ok(())
     }
 
+    /// Loads the account tree as of the given block. Only the given accounts are taken from
+    /// the tree stored in RocksDB, as the witness of the next block touches only them.
     async fn load_account_tree(
+        &mut self,
+        block: BlockNumber,
+        account_ids: &[u32],
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<Box<dyn WitnessAccountTree + Send>, anyhow::Error> {
+        let start = time::Instant::now();
+        let account_tree: Box<dyn WitnessAccountTree + Send> =
+            match self.account_tree.stored_block() {
+                Some(stored_block) => {
+                    self.sync_stored_tree(stored_block, block, storage).await?;
+                    let partial_tree = self
+                        .account_tree
+                        .extract_circuit_tree(account_ids)?
+                        .expect("Account tree is stored in RocksDB");
+                    Box::new(partial_tree)
+                }
+                None => Box::new(self.load_memory_account_tree(block, storage).await?),
+            };
+
+        if *block != 0 {
+            let storage_block = storage
+                .chain()
+                .block_schema()
+                .get_block(block)
+                .await?
+                .ok_or_else(|| {
+                    format_err!("Block {} for witness generator doesn't exist", block)
+                })?;
+            assert_eq!(
+                storage_block.new_root_hash,
+                account_tree.root_hash(),
+                "account tree root hash restored incorrectly"
+            );
+        }
+
+        metrics::histogram!("witness_generator.load_account_tree", start.elapsed());
+        Ok(account_tree)
+    }
+
+    /// Builds the whole account tree of the given block in memory from the state stored
+    /// in the database, using the cached tree internals.
+    async fn load_memory_account_tree(
         &self,
         block: BlockNumber,
         storage: &mut StorageProcessor<'_>,
     ) -> Result<CircuitAccountTree, anyhow::Error> {
-        let start = time::Instant::now();
         let mut circuit_account_tree = CircuitAccountTree::new(account_tree_depth());
+        let (_, accounts) = storage
+            .chain()
+            .state_schema()
+            .load_committed_state(Some(block))
+            .await?;
 
-        if let Some((cached_block, account_tree_cache)) = storage
+        // The tree stored exactly for the required block is the checkpoint left by the previous
+        // attempt to build this witness, so the interrupted job doesn't have to update the tree again.
+        let tree_cache = match storage
             .chain()
             .block_schema()
-            .get_account_tree_cache()
+            .get_account_tree_cache_block(block)
             .await?
         {
-            let (_, accounts) = storage
-                .chain()
-                .state_schema()
-                .load_committed_state(Some(block))
-                .await?;
-            for (id, account) in accounts {
-                circuit_account_tree.insert(*id, account.into());
+            Some(account_tree_cache) => Some((block, account_tree_cache)),
+            None => {
+                storage
+                    .chain()
+                    .block_schema()
+                    .get_account_tree_cache()
+                    .await?
+            }
+        };
+
+        if let Some((cached_block, account_tree_cache)) = tree_cache {
+            for (id, account) in &accounts {
+                circuit_account_tree.insert(**id, account.clone().into());
             }
             circuit_account_tree.set_internals(serde_json::from_value(account_tree_cache)?);
             if block != cached_block {
-                let (_, accounts) = storage
-                    .chain()
-                    .state_schema()
-                    .load_committed_state(Some(block))
-                    .await?;
                 if let Some((_, account_updates)) = storage
                     .chain()
                     .state_schema()
@@ -152,11 +248,6 @@ impl WitnessGenerator {
                     .await?;
             }
         } else {
-            let (_, accounts) = storage
-                .chain()
-                .state_schema()
-                .load_committed_state(Some(block))
-                .await?;
             for (id, account) in accounts {
                 circuit_account_tree.insert(*id, account.into());
             }
@@ -169,149 +260,284 @@ impl WitnessGenerator {
                 .await?;
         }
 
-        if *block != 0 {
-            let storage_block = storage
-                .chain()
-                .block_schema()
-                .get_block(block)
-                .await?
-                .expect("Block for witness generator must exist");
-            assert_eq!(
-                storage_block.new_root_hash,
-                circuit_account_tree.root_hash(),
-                "account tree root hash restored incorrectly"
+        Ok(circuit_account_tree)
+    }
+
+    /// Performs a single round of looking for the block to generate witness for.
+    /// If there is such a block, assigns it to this witness generator and prefetches
+    /// the state required to build its witness.
+    async fn find_job(&mut self) -> Result<Option<WitnessJob>, anyhow::Error> {
+        if self.is_backpressured().await? {
+            vlog::debug!(
+                "Witness generator {} is paused: too many pending prover jobs",
+                self.worker
             );
+            // Wait for the provers to catch up.
+            return Ok(None);
         }
 
-        metrics::histogram!("witness_generator.load_account_tree", start.elapsed());
-        Ok(circuit_account_tree)
+        let mut storage = self.conn_pool.access_storage().await?;
+        let block_number = match storage
+            .prover_schema()
+            .witness_job_for_next_block(&self.worker, self.job_timeout)
+            .await?
+        {
+            Some(block_number) => block_number,
+            None => return Ok(None),
+        };
+
+        match self.load_job(block_number, &mut storage).await {
+            Ok(job) => Ok(Some(job)),
+            Err(err) => {
+                // Let the job be taken again right away instead of waiting for the timeout.
+                storage
+                    .prover_schema()
+                    .release_witness_job(block_number, &self.worker)
+                    .await?;
+                Err(err)
+            }
+        }
     }
 
-    async fn prepare_witness_and_save_it(&self, block: Block) -> Result<(), anyhow::Error> {
-        let start = time::Instant::now();
-        let timer = time::Instant::now();
-        let mut storage = self.conn_pool.access_storage().await?;
+    /// Loads the block and the state required to build its witness.
+    async fn load_job(
+        &mut self,
+        block_number: BlockNumber,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<WitnessJob, anyhow::Error> {
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await?
+            .ok_or_else(|| {
+                format_err!(
+                    "Block {} for witness generation job doesn't exist",
+                    block_number
+                )
+            })?;
 
-        let mut circuit_account_tree = self
-            .load_account_tree(block.block_number - 1, &mut storage)
+        // Account 0 is required to calculate the root hash of the used subtree.
+        let mut account_ids: Vec<_> = block
+            .block_transactions
+            .iter()
+            .flat_map(|tx| tx.get_updated_account_ids())
+            .chain(vec![block.fee_account, AccountId(0)])
+            .map(|id| *id)
+            .collect();
+        account_ids.sort_unstable();
+        account_ids.dedup();
+
+        let timer = time::Instant::now();
+        let account_tree = self
+            .load_account_tree(block_number - 1, &account_ids, storage)
             .await?;
         vlog::trace!(
             "Witness generator loading circuit account tree {}s",
             timer.elapsed().as_secs()
         );
+        // Account tree is loaded and saved, so the job made a progress.
+        let assigned = storage
+            .prover_schema()
+            .record_witness_job_progress(block_number, &self.worker)
+            .await?;
+        if !assigned {
+            bail!(
+                "Witness generation job for block {} was reassigned while loading its state",
+                block_number
+            );
+        }
 
-        let timer = time::Instant::now();
-        let witness: ProverData = build_block_witness(&mut circuit_account_tree, &block)?.into();
-        vlog::trace!(
-            "Witness generator witness build {}s",
-            timer.elapsed().as_secs()
-        );
+        Ok(WitnessJob {
+            block,
+            account_tree,
+        })
+    }
+
+    /// Builds the witness for the job. This is a CPU-heavy routine, so it's expected
+    /// to be run on the blocking tasks pool.
+    fn build_witness(job: WitnessJob) -> Result<ProverData, anyhow::Error> {
+        let start = time::Instant::now();
+        let WitnessJob {
+            block,
+            mut account_tree,
+        } = job;
+        let witness = build_block_witness(&mut *account_tree, &block)?.into();
+        if account_tree.root_hash() != block.new_root_hash {
+            bail!(
+                "Account tree root hash doesn't match block {} after building its witness",
+                block.block_number
+            );
+        }
+
+        metrics::histogram!("witness_generator.build_witness", start.elapsed());
+        Ok(witness)
+    }
+
+    async fn save_witness(
+        &self,
+        block_number: BlockNumber,
+        witness: ProverData,
+    ) -> Result<(), anyhow::Error> {
+        let start = time::Instant::now();
+        let mut storage = self.conn_pool.access_storage().await?;
 
+        let commitment = EthereumSerializer::serialize_fe(&witness.public_data_commitment);
         storage
             .prover_schema()
             .store_witness(
-                block.block_number,
+                block_number,
+                commitment,
                 serde_json::to_value(witness).expect("Witness serialize to json"),
             )
             .await?;
 
-        metrics::histogram!(
-            "witness_generator.prepare_witness_and_save_it",
-            start.elapsed()
-        );
+        metrics::histogram!("witness_generator.save_witness", start.elapsed());
+        Ok(())
+    }
+
+    async fn release_job(&self, block_number: BlockNumber) -> Result<(), anyhow::Error> {
+        let mut storage = self.conn_pool.access_storage().await?;
+        storage
+            .prover_schema()
+            .release_witness_job(block_number, &self.worker)
+            .await?;
         Ok(())
     }
 
-    /// Returns next block for generating witness
-    fn next_witness_block(
-        current_block: BlockNumber,
-        block_step: BlockNumber,
-        block_info: &BlockInfo,
-    ) -> BlockNumber {
-        match block_info {
-            BlockInfo::NotReadyBlock => current_block, // Keep waiting
-            BlockInfo::WithWitness | BlockInfo::NoWitness(_) => {
-                BlockNumber(*current_block + *block_step)
-            } // Go to the next block
+    /// Refreshes the job assignment, see `ProverSchema::record_witness_job_progress`.
+    async fn record_job_progress(
+        conn_pool: &zksync_storage::ConnectionPool,
+        worker: &str,
+        block_number: BlockNumber,
+    ) -> Result<bool, anyhow::Error> {
+        let mut storage = conn_pool.access_storage().await?;
+        storage
+            .prover_schema()
+            .record_witness_job_progress(block_number, worker)
+            .await
+    }
+
+    /// Refreshes the job assignment every `interval` while the witness is being built,
+    /// so the job isn't considered abandoned no matter how long the build takes.
+    /// This future never resolves and is expected to be dropped once the witness is built.
+    async fn keep_job_assigned(
+        conn_pool: zksync_storage::ConnectionPool,
+        worker: String,
+        block_number: BlockNumber,
+        interval: time::Duration,
+    ) {
+        let mut timer = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        loop {
+            timer.tick().await;
+            match Self::record_job_progress(&conn_pool, &worker, block_number).await {
+                Ok(true) => {}
+                Ok(false) => vlog::warn!(
+                    "Witness generation job for block {} was reassigned from {}",
+                    block_number,
+                    worker
+                ),
+                Err(err) => vlog::warn!(
+                    "failed to refresh witness job for block {}: {}",
+                    block_number,
+                    err
+                ),
+            }
         }
     }
 
     /// Updates witness data in database in an infinite loop,
     /// awaiting `rounds_interval` time between updates.
-    async fn maintain(self) {
-        vlog::info!(
-            "preparing prover data routine started with start_block({}), block_step({})",
-            *self.start_block,
-            *self.block_step
-        );
-        let mut current_block = self.start_block;
+    async fn maintain(mut self) {
+        vlog::info!("preparing prover data routine started for {}", self.worker);
+        let mut prefetched_job = None;
         loop {
-            std::thread::sleep(self.rounds_interval);
-            let should_work = match self.should_work_on_block(current_block).await {
-                Ok(should_work) => should_work,
-                Err(err) => {
-                    vlog::warn!("witness for block {} check failed: {}", current_block, err);
+            let job = match prefetched_job.take() {
+                Some(job) => job,
+                None => {
+                    std::thread::sleep(self.rounds_interval);
+                    match self.find_job().await {
+                        Ok(Some(job)) => job,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            vlog::warn!("witness generation job check failed: {}", err);
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            // The prefetched job waits for the previous witness to be built, so it may be
+            // considered abandoned and reassigned meanwhile.
+            let block_number = job.block.block_number;
+            match Self::record_job_progress(&self.conn_pool, &self.worker, block_number).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    vlog::info!(
+                        "Witness generation job for block {} was reassigned from {}",
+                        block_number,
+                        self.worker
+                    );
                     continue;
                 }
+                Err(err) => {
+                    vlog::warn!(
+                        "failed to refresh witness job for block {}: {}",
+                        block_number,
+                        err
+                    );
+                }
+            }
+
+            // Build the witness on the blocking pool and prefetch the next job meanwhile,
+            // so the next witness is ready as soon as possible.
+            let start = time::Instant::now();
+            let build_handle = tokio::task::spawn_blocking(move || Self::build_witness(job));
+            let heartbeat = Self::keep_job_assigned(
+                self.conn_pool.clone(),
+                self.worker.clone(),
+                block_number,
+                self.job_timeout / JOB_HEARTBEATS_PER_TIMEOUT,
+            );
+            let build = async move {
+                tokio::select! {
+                    witness = build_handle => witness.expect("Witness builder task panicked"),
+                    _ = heartbeat => unreachable!("Witness job heartbeat never stops"),
+                }
             };
+            let (witness, next_job) = futures::join!(build, self.find_job());
 
-            let next_block = Self::next_witness_block(current_block, self.block_step, &should_work);
-            if let BlockInfo::NoWitness(block) = should_work {
-                let block_number = block.block_number;
-                if let Err(err) = self.prepare_witness_and_save_it(block).await {
-                    vlog::warn!("Witness generator ({},{}) failed to prepare witness for block: {}, err: {}",
-                        self.start_block, self.block_step, block_number, err);
-                    continue; // Retry the same block on the next iteration.
+            prefetched_job = next_job.unwrap_or_else(|err| {
+                vlog::warn!("next witness job prefetch failed: {}", err);
+                None
+            });
+
+            let result = match witness {
+                Ok(witness) => self.save_witness(block_number, witness).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                vlog::warn!(
+                    "Witness generator {} failed to prepare witness for block: {}, err: {}",
+                    self.worker,
+                    block_number,
+                    err
+                );
+                // Release the job, so the block is retried on the next iteration.
+                if let Err(err) = self.release_job(block_number).await {
+                    vlog::warn!(
+                        "failed to release witness job for block {}: {}",
+                        block_number,
+                        err
+                    );
                 }
+                continue;
             }
 
-            // Update current block.
-            current_block = next_block;
+            metrics::histogram!(
+                "witness_generator.prepare_witness_and_save_it",
+                start.elapsed()
+            );
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use zksync_crypto::Fr;
-    use zksync_types::{AccountId, U256};
-
-    #[test]
-    fn test_next_witness_block() {
-        assert_eq!(
-            WitnessGenerator::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NotReadyBlock
-            ),
-            BlockNumber(3)
-        );
-        assert_eq!(
-            WitnessGenerator::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::WithWitness
-            ),
-            BlockNumber(7)
-        );
-        let empty_block = Block::new(
-            BlockNumber(0),
-            Fr::default(),
-            AccountId(0),
-            vec![],
-            (0, 0),
-            0,
-            U256::default(),
-            U256::default(),
-        );
-        assert_eq!(
-            WitnessGenerator::next_witness_block(
-                BlockNumber(3),
-                BlockNumber(4),
-                &BlockInfo::NoWitness(empty_block)
-            ),
-            BlockNumber(7)
-        );
-    }
-}
@@ -9,6 +9,7 @@ use zksync_circuit::witness::{deposit::DepositWitness, Witness};
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::{params::total_tokens, proof::EncodedProofPlonk};
 use zksync_prover::{client, ApiClient};
+use zksync_prover_utils::api::ProverVersion;
 use zksync_types::{block::Block, AccountId, Address, BlockNumber, TokenId, H256};
 // Local deps
 use zksync_circuit::witness::utils::get_used_subtree_root_hash;
@@ -47,6 +48,10 @@ fn supported_block_sizes() -> Vec<usize> {
         .supported_block_chunks_sizes
 }
 
+fn prover_version() -> ProverVersion {
+    ProverVersion::from_verification_keys(&supported_block_sizes()).unwrap()
+}
+
 fn smallest_block_size() -> usize {
     supported_block_sizes()[0]
 }
@@ -59,6 +64,7 @@ fn client_with_empty_worker_name_panics() {
         "",
         Duration::from_secs(1),
         CORRECT_PROVER_SECRET_AUTH,
+        prover_version(),
     );
 }
 
@@ -72,6 +78,7 @@ async fn client_with_incorrect_secret_auth() {
         "foo",
         Duration::from_secs(1),
         INCORRECT_PROVER_SECRET_AUTH,
+        prover_version(),
     );
 
     assert_eq!(
@@ -84,6 +91,32 @@ async fn client_with_incorrect_secret_auth() {
     );
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "db_test"), ignore)]
+async fn client_with_incompatible_version() {
+    let block_size_chunks = smallest_block_size();
+    let addr = spawn_server(Duration::from_secs(1), Duration::from_secs(1)).await;
+    let mut version = prover_version();
+    version.circuit_version = "vk-0000000000000000".into();
+    let client = client::ApiClient::new(
+        &format!("http://{}", &addr).parse().unwrap(),
+        "foo",
+        Duration::from_secs(1),
+        CORRECT_PROVER_SECRET_AUTH,
+        version,
+    );
+
+    let err = client
+        .register_prover(block_size_chunks)
+        .expect_err("prover with incompatible version was registered");
+    assert!(err.to_string().contains("incompatible prover version"));
+
+    let err = client
+        .block_to_prove(block_size_chunks)
+        .expect_err("prover with incompatible version got a job");
+    assert!(err.to_string().contains("incompatible prover version"));
+}
+
 #[tokio::test]
 #[cfg_attr(not(feature = "db_test"), ignore)]
 async fn api_client_register_start_and_stop_of_prover() {
@@ -94,6 +127,7 @@ async fn api_client_register_start_and_stop_of_prover() {
         "foo",
         Duration::from_secs(1),
         CORRECT_PROVER_SECRET_AUTH,
+        prover_version(),
     );
     let id = client
         .register_prover(block_size_chunks)
@@ -133,6 +167,7 @@ async fn api_client_simple_simulation() {
         "foo",
         time::Duration::from_secs(1),
         CORRECT_PROVER_SECRET_AUTH,
+        prover_version(),
     );
 
     // call block_to_prove and check its none
@@ -380,6 +415,38 @@ pub async fn test_operation_and_wanted_prover_data(
     )
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "db_test"), ignore)]
+async fn api_client_publish_proof() {
+    let prover_timeout = time::Duration::from_secs(1);
+    let rounds_interval = time::Duration::from_secs(10);
+    let addr = spawn_server(prover_timeout, rounds_interval).await;
+
+    let client = client::ApiClient::new(
+        &format!("http://{}", &addr).parse().unwrap(),
+        "foo",
+        time::Duration::from_secs(1),
+        CORRECT_PROVER_SECRET_AUTH,
+        prover_version(),
+    );
+    let proof = EncodedProofPlonk::default();
+    client
+        .publish(1, 1, proof.clone())
+        .expect("failed to publish proof");
+
+    let db_connection = connect_to_db().await;
+    let mut storage = db_connection
+        .access_storage()
+        .await
+        .expect("Failed to connect to db");
+    let stored = storage
+        .prover_schema()
+        .load_proof(BlockNumber(1))
+        .await
+        .expect("failed to load proof");
+    assert_eq!(stored, Some(proof));
+}
+
 #[tokio::test]
 #[cfg_attr(not(feature = "db_test"), ignore)]
 async fn api_server_publish_dummy() {
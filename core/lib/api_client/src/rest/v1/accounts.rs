@@ -8,7 +8,8 @@ use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_types::{
-    tx::TxHash, AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, H256,
+    tx::TxHash, AccountId, AccountUpdateJournalEntry, Address, BlockNumber, Nonce, PriorityOp,
+    PubKeyHash, TokenId, ZkSyncTx, H256,
 };
 use zksync_utils::{remove_prefix, BigUintSerdeWrapper};
 
@@ -109,6 +110,14 @@ impl AccountReceipts {
     }
 }
 
+/// Query parameters for the journal of the committed account updates.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct AccountUpdatesQuery {
+    /// Sequence number of the first journal entry to return.
+    pub from: u64,
+    pub limit: u32,
+}
+
 /// Direction to perform search of transactions to.
 #[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -135,6 +144,9 @@ pub struct AccountTxReceipt {
     #[serde(flatten)]
     pub receipt: Receipt,
     pub hash: TxHash,
+    /// Memo attached to the `Transfer` transaction by the sender.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -153,6 +165,56 @@ pub struct PendingAccountOpReceipt {
     pub hash: H256,
 }
 
+/// Nonces of the account to send the transactions with.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonces {
+    /// Nonce of the account in accordance with the actual committed block.
+    pub committed: Nonce,
+    /// Nonce of the next transaction, taking into account the transactions
+    /// of the pending block and the transactions waiting in the mempool.
+    pub next: Nonce,
+}
+
+/// Transaction of the account waiting in the mempool to be included into a block.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAccountTx {
+    pub tx_hash: TxHash,
+    pub nonce: Nonce,
+    /// Token the fee is paid in.
+    pub fee_token: TokenId,
+    /// Fee amount, zero if the fee is paid by another transaction of the batch.
+    pub fee: BigUintSerdeWrapper,
+    pub tx: ZkSyncTx,
+}
+
+/// Way the account owner authorized the signing key.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum SigningKeyAuthType {
+    /// `ChangePubKey` transaction was signed by the Ethereum private key of the account.
+    EthereumSignature,
+    /// Public key hash was authorized by the `setAuthPubkeyHash` call of the zkSync contract.
+    Onchain,
+}
+
+/// Signing key status of the account, which lets the wallets decide
+/// which onboarding step to show.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKeyStatus {
+    /// Whether the signing key is set in the committed account state.
+    pub is_set: bool,
+    /// Hash of the account signing key, absent if the key is not set.
+    pub pub_key_hash: Option<PubKeyHash>,
+    /// Way the current signing key was authorized, absent if the key is not set.
+    pub auth_type: Option<SigningKeyAuthType>,
+    /// Whether the zkSync contract has an authorization fact for the next `ChangePubKey`
+    /// transaction of the account, which is not executed yet. Only the facts already
+    /// indexed by the server are taken into account.
+    pub onchain_auth_pending: bool,
+}
+
 impl From<AccountId> for AccountQuery {
     fn from(v: AccountId) -> Self {
         Self::Id(v)
@@ -300,4 +362,54 @@ impl Client {
             .send()
             .await
     }
+
+    /// Gets the committed nonce of the account and the nonce of its next transaction.
+    pub async fn account_nonces(
+        &self,
+        account: impl Into<AccountQuery>,
+    ) -> Result<AccountNonces, ClientError> {
+        let account = account.into();
+
+        self.get(&format!("accounts/{}/nonces", account))
+            .send()
+            .await
+    }
+
+    /// Gets the transactions of the account waiting in the mempool, in the order of execution.
+    /// Transactions of the pending block are not included.
+    pub async fn account_pending_txs(
+        &self,
+        account: impl Into<AccountQuery>,
+    ) -> Result<Vec<PendingAccountTx>, ClientError> {
+        let account = account.into();
+
+        self.get(&format!("accounts/{}/transactions/pending", account))
+            .send()
+            .await
+    }
+
+    /// Gets the signing key status of the account.
+    pub async fn account_signing_key(
+        &self,
+        account: impl Into<AccountQuery>,
+    ) -> Result<Option<SigningKeyStatus>, ClientError> {
+        let account = account.into();
+
+        self.get(&format!("accounts/{}/signing_key", account))
+            .send()
+            .await
+    }
+
+    /// Returns the committed account updates in the order they were applied,
+    /// starting from the journal entry with the given sequence number.
+    pub async fn account_updates(
+        &self,
+        from: u64,
+        limit: u32,
+    ) -> Result<Vec<AccountUpdateJournalEntry>, ClientError> {
+        self.get("accounts/updates")
+            .query(&AccountUpdatesQuery { from, limit })
+            .send()
+            .await
+    }
 }
@@ -8,8 +8,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 // Workspace uses
-use zksync_crypto::{serialization::FrSerde, Fr};
-use zksync_types::{tx::TxHash, BlockNumber};
+use zksync_crypto::{proof::EncodedProofPlonk, serialization::FrSerde, Fr};
+use zksync_types::{ethereum::EthOpId, tx::TxHash, BlockNumber, H256, U256};
 
 // Local uses
 use super::{
@@ -32,6 +32,33 @@ pub struct BlockInfo {
     pub verified_at: Option<DateTime<Utc>>,
 }
 
+/// Stage of the block processing (commit or verify) linked to the L1 transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStageInfo {
+    /// Time when the stage operation was created by the server.
+    pub created_at: DateTime<Utc>,
+    /// ID of the Ethereum operation sending the stage to L1, if it's already sent.
+    pub eth_op_id: Option<EthOpId>,
+    /// Hash of the L1 transaction accepted by the network.
+    pub tx_hash: Option<H256>,
+    /// Gas used by the accepted L1 transaction.
+    pub gas_used: Option<U256>,
+    /// Time when the L1 transaction was confirmed.
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+/// Timeline of the block processing with its L1 linkage.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMetadata {
+    pub block_number: BlockNumber,
+    /// Time when the block was created, i.e. its commit operation was created.
+    pub created_at: DateTime<Utc>,
+    pub commit: BlockStageInfo,
+    pub verify: Option<BlockStageInfo>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionInfo {
@@ -53,6 +80,28 @@ impl Client {
         self.get(&format!("blocks/{}", *block_number)).send().await
     }
 
+    /// Returns the processing timeline of the block with the specified number
+    /// or null if block doesn't exist.
+    pub async fn block_metadata(
+        &self,
+        block_number: BlockNumber,
+    ) -> client::Result<Option<BlockMetadata>> {
+        self.get(&format!("blocks/{}/metadata", *block_number))
+            .send()
+            .await
+    }
+
+    /// Returns the proof of the block with the specified number or null if the proof
+    /// doesn't exist. Proofs moved to the cold storage are loaded as well.
+    pub async fn block_proof(
+        &self,
+        block_number: BlockNumber,
+    ) -> client::Result<Option<EncodedProofPlonk>> {
+        self.get(&format!("blocks/{}/proof", *block_number))
+            .send()
+            .await
+    }
+
     /// Returns information about transactions of the block with the specified number.
     pub async fn block_transactions(
         &self,
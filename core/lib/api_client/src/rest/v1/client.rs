@@ -1,8 +1,11 @@
 //! Built-in API client.
 
+// Built-in uses
+use std::time::Duration;
 // External uses
 use reqwest::StatusCode;
 use serde::{de::DeserializeOwned, ser::Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
 // Public uses
@@ -19,7 +22,7 @@ pub use super::{
     Pagination,
 };
 // Local uses
-use super::error::ErrorBody;
+use super::error::{ErrorBody, ErrorCode};
 
 pub type Result<T> = std::result::Result<T, ClientError>;
 
@@ -39,17 +42,48 @@ pub enum ClientError {
     NotFound(String),
 }
 
+impl ClientError {
+    /// Returns the code of the error returned by the server, if any.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::BadRequest { body, .. } => body.error_code(),
+            _ => None,
+        }
+    }
+
+    /// Returns the structured details of the error returned by the server, if any.
+    pub fn data(&self) -> Option<&Value> {
+        match self {
+            Self::BadRequest { body, .. } => body.data.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the suggested delay before retrying the failed request, or `None`
+    /// if the request shouldn't be retried as is.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::BadRequest { body, .. } => body.retry_after(),
+            _ => None,
+        }
+    }
+}
+
 impl From<reqwest::Error> for ClientError {
     fn from(inner: reqwest::Error) -> Self {
         Self::Other(inner)
     }
 }
 
+/// Header with the API key of the trusted integrator.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
 /// Client reference implementation for interacting with zkSync REST API v1.
 #[derive(Debug, Clone)]
 pub struct Client {
     inner: reqwest::Client,
     url: String,
+    api_key: Option<String>,
 }
 
 impl Client {
@@ -58,6 +92,21 @@ impl Client {
         Self {
             inner: reqwest::Client::new(),
             url,
+            api_key: None,
+        }
+    }
+
+    /// Sets the API key sent with every request, required for the privileged
+    /// submission options (e.g. the fast processing of withdrawals).
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    fn with_auth(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(api_key) => request.header(API_KEY_HEADER, api_key),
+            None => request,
         }
     }
 
@@ -69,7 +118,7 @@ impl Client {
     pub(crate) fn get(&self, method: impl AsRef<str>) -> ClientRequestBuilder {
         let url = self.endpoint(method.as_ref());
         ClientRequestBuilder {
-            inner: self.inner.get(&url),
+            inner: self.with_auth(self.inner.get(&url)),
             url,
         }
     }
@@ -78,7 +127,7 @@ impl Client {
     pub(crate) fn post(&self, method: impl AsRef<str>) -> ClientRequestBuilder {
         let url = self.endpoint(method.as_ref());
         ClientRequestBuilder {
-            inner: self.inner.post(&url),
+            inner: self.with_auth(self.inner.post(&url)),
             url,
         }
     }
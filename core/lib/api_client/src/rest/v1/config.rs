@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::Address;
+use zksync_types::{network::Network, Address};
 
 // Local uses
 use super::client::{self, Client};
@@ -19,8 +19,37 @@ pub struct Contracts {
     pub contract: Address,
 }
 
+/// Addresses of the zkSync contracts deployed on Ethereum.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkContracts {
+    pub main: Address,
+    pub governance: Address,
+    pub verifier: Address,
+}
+
+/// Configuration of the network, allowing the clients to configure themselves from the server.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConfig {
+    pub network: Network,
+    pub chain_id: u64,
+    pub contracts: NetworkContracts,
+    /// Sizes of the blocks in chunks that can be produced by the server, in ascending order.
+    pub block_chunk_sizes: Vec<usize>,
+    /// Whether the `ChangePubKey` transactions have to pay the fee.
+    pub enforce_pubkey_change_fee: bool,
+    /// Minimum age of the account in seconds required to be the target of the `ForcedExit` transaction.
+    pub forced_exit_minimum_account_age_secs: u64,
+}
+
 /// Configuration API part.
 impl Client {
+    /// Returns the whole configuration of the network.
+    pub async fn network_config(&self) -> client::Result<NetworkConfig> {
+        self.get("config").send().await
+    }
+
     pub async fn contracts(&self) -> client::Result<Contracts> {
         self.get("config/contracts").send().await
     }
@@ -32,4 +61,10 @@ impl Client {
     pub async fn network(&self) -> client::Result<String> {
         self.get("config/network").send().await
     }
+
+    /// Returns the address of the operator key signing the soft confirmations of the transactions,
+    /// `None` if the soft confirmations are disabled.
+    pub async fn soft_confirmation_signer(&self) -> client::Result<Option<Address>> {
+        self.get("config/soft_confirmation_signer").send().await
+    }
 }
@@ -1,8 +1,12 @@
 // Built-in uses
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
 
 // External uses
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 // Workspace uses
 
@@ -24,9 +28,197 @@ pub struct ErrorBody {
     /// Error location in the source code.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub location: String,
-    /// Internal error code.
+    /// Stable numeric error code, see [`ErrorCode`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<u64>,
+    /// Stable string identifier of the error code, see [`ErrorCode`].
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub code_name: String,
+    /// Whether the request may succeed if it's retried as is.
+    #[serde(default)]
+    pub retryable: bool,
+    /// Suggested delay in seconds before retrying the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    /// Structured details of the error, depending on the error code, e.g. the required
+    /// and the provided fees for the `feeTooLow` error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl ErrorBody {
+    /// Returns the error code, if it's known to this version of the client.
+    ///
+    /// The code is looked up by its string identifier, since the numeric codes of the
+    /// transaction submission errors are the legacy ones.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        ErrorCode::from_name(&self.code_name)
+    }
+
+    /// Returns the suggested delay before retrying the request, or `None`
+    /// if the request shouldn't be retried as is.
+    pub fn retry_after(&self) -> Option<Duration> {
+        if !self.retryable {
+            return None;
+        }
+        Some(Duration::from_secs(self.retry_after.unwrap_or_default()))
+    }
+}
+
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal => $name:literal,)+) => {
+        /// Machine-readable code of the API error.
+        ///
+        /// Both the numeric codes and the string identifiers are stable: codes are never reused
+        /// or changed between releases, so the clients can rely on them instead of the error messages.
+        /// The numeric codes are returned by the API v2. The API v1 and the JSON RPC API keep
+        /// returning their legacy numeric codes for the errors known before, so the API v1 errors
+        /// should be identified by the `codeName` field.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$doc])* $variant = $code,)+
+        }
+
+        impl ErrorCode {
+            /// All the known error codes.
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant,)+];
+
+            /// Returns the numeric error code.
+            pub fn as_u64(self) -> u64 {
+                self as u64
+            }
+
+            /// Returns the string identifier of the error code.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $name,)+
+                }
+            }
+
+            /// Returns the error code by its numeric value.
+            pub fn from_u64(code: u64) -> Option<Self> {
+                match code {
+                    $($code => Some(ErrorCode::$variant),)+
+                    _ => None,
+                }
+            }
+
+            /// Returns the error code by its string identifier.
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(ErrorCode::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // Transaction errors.
+    /// Transaction nonce is lower than the account nonce.
+    NonceMismatch = 101 => "nonceMismatch",
+    /// Transaction nonce is too far ahead of the account nonce.
+    NonceTooHigh = 102 => "nonceTooHigh",
+    /// Transaction is incorrect.
+    IncorrectTx = 103 => "incorrectTx",
+    /// Transaction fee is too low.
+    FeeTooLow = 104 => "feeTooLow",
+    /// Token can't be used to pay fees.
+    InappropriateFeeToken = 105 => "inappropriateFeeToken",
+    /// Summary fee of the transactions batch is too low.
+    BatchFeeTooLow = 106 => "batchFeeTooLow",
+    /// Transactions batch is empty.
+    EmptyBatch = 107 => "emptyBatch",
+    /// Transactions batch does not fit into any of the supported block sizes.
+    BatchTooBig = 108 => "batchTooBig",
+    /// Transactions batch contains too many withdrawals.
+    BatchWithdrawalsOverload = 109 => "batchWithdrawalsOverload",
+    // Codes 110-113 are reserved, they are used by the other errors of the API v1.
+    /// Transaction of the batch fails being executed against the committed state.
+    BatchExecutionFailed = 114 => "batchExecutionFailed",
+
+    // Signature errors.
+    /// Ethereum signature is required but missing.
+    MissingEthSignature = 200 => "missingEthSignature",
+    /// EIP-1271 signature could not be verified.
+    EIP1271SignatureVerificationFail = 201 => "eip1271SignatureVerificationFail",
+    /// Ethereum signature is incorrect.
+    IncorrectEthSignature = 202 => "incorrectEthSignature",
+    /// `ChangePubKey` transaction is not authorized onchain.
+    ChangePkNotAuthorized = 203 => "changePkNotAuthorized",
+    /// Transaction of the account opted in to the co-signing is not approved by its co-signer.
+    CosignatureRejected = 204 => "cosignatureRejected",
+
+    // Server policy errors. Code 302 is reserved.
+    /// Any other error of the transaction processing.
+    Other = 300 => "other",
+    /// Account close transactions are disabled.
+    AccountCloseDisabled = 301 => "accountCloseDisabled",
+    /// Fast processing is requested for the transaction other than withdrawal.
+    UnsupportedFastProcessing = 303 => "unsupportedFastProcessing",
+    /// Fast processing of withdrawals is temporarily disabled.
+    FastProcessingDisabled = 304 => "fastProcessingDisabled",
+    /// Transaction type is not allowed for the token.
+    TokenTxRestricted = 305 => "tokenTxRestricted",
+    /// Server is paused and doesn't accept transactions.
+    Paused = 306 => "paused",
+    /// Server is under maintenance and doesn't accept transactions.
+    Maintenance = 307 => "maintenance",
+    /// Account has too many transactions waiting in the mempool.
+    TooManyPendingTxs = 308 => "tooManyPendingTxs",
+    /// Hourly quota of the privileged requests of the API key is exhausted.
+    ApiKeyQuotaExceeded = 309 => "apiKeyQuotaExceeded",
+
+    // Request errors.
+    /// Request is incorrect.
+    InvalidRequest = 400 => "invalidRequest",
+    /// Request parameters are incorrect.
+    InvalidParams = 401 => "invalidParams",
+    /// Requested functionality is not implemented.
+    NotImplemented = 402 => "notImplemented",
+    /// Client has sent too many requests or transactions, the request should be repeated later.
+    RateLimitExceeded = 403 => "rateLimitExceeded",
+    /// API key is invalid, or the requested option requires an API key.
+    Unauthorized = 404 => "unauthorized",
+
+    // Server errors.
+    /// Internal server error.
+    Internal = 500 => "internal",
+    /// Error of the communication with the core server.
+    CommunicationCoreServer = 501 => "communicationCoreServer",
+    /// Database is not available.
+    StorageUnavailable = 502 => "storageUnavailable",
+    /// Service is temporarily unavailable.
+    ServiceUnavailable = 503 => "serviceUnavailable",
+    /// Core server has failed too many requests in a row and is considered unavailable.
+    CoreServerUnavailable = 504 => "coreServerUnavailable",
+}
+
+impl ErrorCode {
+    /// Returns the suggested delay before retrying the request failed with this error,
+    /// or `None` if the error is permanent and the request shouldn't be retried as is.
+    pub fn retry_after(self) -> Option<Duration> {
+        let secs = match self {
+            // Transactions with the preceding nonces may still be on their way.
+            ErrorCode::NonceTooHigh => 5,
+            // Pending transactions of the account are going to be included into the next blocks.
+            ErrorCode::TooManyPendingTxs => 10,
+            ErrorCode::Paused | ErrorCode::Maintenance => 60,
+            ErrorCode::RateLimitExceeded => 1,
+            ErrorCode::ApiKeyQuotaExceeded => 60,
+            ErrorCode::ServiceUnavailable | ErrorCode::CoreServerUnavailable => 10,
+            ErrorCode::Internal => 5,
+            ErrorCode::CommunicationCoreServer | ErrorCode::StorageUnavailable => 1,
+            _ => return None,
+        };
+        Some(Duration::from_secs(secs))
+    }
+
+    /// Returns `true` if the request failed with this error may succeed if it's retried as is.
+    pub fn is_retryable(self) -> bool {
+        self.retry_after().is_some()
+    }
 }
 
 impl Display for ErrorBody {
@@ -34,3 +226,42 @@ impl Display for ErrorBody {
         f.write_str(&self.title)
     }
 }
+
+#[test]
+fn error_codes_are_unique() {
+    let mut codes = std::collections::HashSet::new();
+    let mut names = std::collections::HashSet::new();
+
+    for &code in ErrorCode::ALL {
+        assert!(codes.insert(code.as_u64()), "Duplicate code {:?}", code);
+        assert!(names.insert(code.name()), "Duplicate name {:?}", code);
+        assert_eq!(ErrorCode::from_u64(code.as_u64()), Some(code));
+        assert_eq!(ErrorCode::from_name(code.name()), Some(code));
+    }
+    assert_eq!(ErrorCode::from_u64(302), None);
+    for code in 110..=113 {
+        assert_eq!(ErrorCode::from_u64(code), None);
+    }
+}
+
+#[test]
+fn error_codes_retryability() {
+    let transient = [
+        ErrorCode::CommunicationCoreServer,
+        ErrorCode::StorageUnavailable,
+        ErrorCode::Maintenance,
+    ];
+    let permanent = [
+        ErrorCode::InvalidParams,
+        ErrorCode::IncorrectEthSignature,
+        ErrorCode::NonceMismatch,
+        ErrorCode::FeeTooLow,
+    ];
+
+    for code in &transient {
+        assert!(code.is_retryable(), "{:?} should be retryable", code);
+    }
+    for code in &permanent {
+        assert!(!code.is_retryable(), "{:?} shouldn't be retryable", code);
+    }
+}
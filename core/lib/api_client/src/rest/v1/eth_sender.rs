@@ -0,0 +1,82 @@
+//! Ethereum sender part of API implementation.
+
+// Built-in uses
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{
+    ethereum::{ETHOperationSummary, EthOpId, GasAdjusterStats, OperationType},
+    BlockNumber, H256, U256,
+};
+
+// Local uses
+use super::client::{self, Client};
+
+// Data transfer objects.
+
+/// Query parameters for the Ethereum operations log.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct EthOperationsQuery {
+    /// Return operations preceding the operation with this ID.
+    /// If not set, the latest operations are returned.
+    pub before: Option<EthOpId>,
+    pub limit: u32,
+}
+
+/// Information about the Ethereum operation sent by the Ethereum sender.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EthOperationInfo {
+    pub id: EthOpId,
+    pub op_type: OperationType,
+    /// Number of the zkSync block processed by the operation, if any.
+    pub block: Option<BlockNumber>,
+    pub nonce: U256,
+    pub last_used_gas_price: U256,
+    /// Hashes of all the sent transactions, the latest one goes last.
+    pub tx_hashes: Vec<H256>,
+    pub confirmed: bool,
+    /// Hash of the transaction accepted by the network.
+    pub final_hash: Option<H256>,
+    /// Gas used by the accepted transaction, known for confirmed operations only.
+    pub gas_used: Option<U256>,
+}
+
+impl EthOperationInfo {
+    pub fn new(summary: ETHOperationSummary) -> Self {
+        Self {
+            id: summary.id,
+            op_type: summary.op_type,
+            block: summary.block_number,
+            nonce: summary.nonce,
+            last_used_gas_price: summary.last_used_gas_price,
+            tx_hashes: summary.used_tx_hashes,
+            confirmed: summary.confirmed,
+            final_hash: summary.final_hash,
+            gas_used: summary.gas_used,
+        }
+    }
+}
+
+/// Ethereum sender API part.
+impl Client {
+    /// Returns the last reported state of the gas adjuster used to choose
+    /// the gas price for the transactions sent to Ethereum.
+    pub async fn gas_adjuster_stats(&self) -> client::Result<Option<GasAdjusterStats>> {
+        self.get("eth_sender/gas_adjuster").send().await
+    }
+
+    /// Returns the log of the Ethereum operations in the descending order of their IDs.
+    pub async fn eth_operations(
+        &self,
+        before: Option<EthOpId>,
+        limit: u32,
+    ) -> client::Result<Vec<EthOperationInfo>> {
+        self.get("eth_sender/operations")
+            .query(&EthOperationsQuery { before, limit })
+            .send()
+            .await
+    }
+}
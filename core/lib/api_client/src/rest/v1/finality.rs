@@ -0,0 +1,91 @@
+//! Finality estimation part of API implementation.
+
+// Built-in uses
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{tokens::TokenTxType, tx::TxHash, BlockNumber};
+
+// Local uses
+use super::client::{self, Client};
+
+// Data transfer objects.
+
+/// Query parameters of the finality estimate of a transaction sent right now.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityQuery {
+    /// Type of the transaction, determines how much space of the block it takes.
+    /// The fee doesn't affect the estimate, since the mempool is processed in the FIFO order.
+    pub tx_type: Option<TokenTxType>,
+    /// Whether the withdrawal is requested with the fast processing.
+    #[serde(default)]
+    pub fast_processing: bool,
+}
+
+/// Current load of the network the finality estimate is based on.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkLoad {
+    /// Average interval between the recent blocks in seconds, depends on how fast the blocks are filled.
+    pub block_interval: Option<u64>,
+    /// Amount of the committed blocks waiting for a proof.
+    pub prover_queue: u32,
+    /// Amount of the block chunks taken by the transactions waiting in the mempool.
+    pub mempool_chunks: u32,
+    /// Amount of the operations sent to Ethereum and not confirmed yet.
+    pub eth_sender_backlog: u32,
+}
+
+/// Estimated time until the transaction (or a transaction sent right now) is committed
+/// and verified on Ethereum.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityEstimate {
+    /// Block containing the transaction, if it's executed already.
+    pub block: Option<BlockNumber>,
+    /// Seconds until the block is committed on Ethereum, zero if it's committed already.
+    /// `None` if there are not enough recent blocks to estimate it.
+    pub time_to_commit: Option<u64>,
+    /// Seconds until the block is verified on Ethereum and becomes final, zero if it's final already.
+    /// `None` if there are not enough recent blocks to estimate it.
+    pub time_to_verify: Option<u64>,
+    pub load: NetworkLoad,
+}
+
+/// Finality estimation API part.
+impl Client {
+    /// Estimates the finality time of a transaction sent right now.
+    pub async fn finality_estimate(&self) -> client::Result<FinalityEstimate> {
+        self.get("finality").send().await
+    }
+
+    /// Estimates the finality time of a transaction of the given type sent right now,
+    /// taking the transactions waiting in the mempool into account.
+    pub async fn tx_type_finality_estimate(
+        &self,
+        tx_type: TokenTxType,
+        fast_processing: bool,
+    ) -> client::Result<FinalityEstimate> {
+        self.get("finality")
+            .query(&FinalityQuery {
+                tx_type: Some(tx_type),
+                fast_processing,
+            })
+            .send()
+            .await
+    }
+
+    /// Estimates the remaining finality time of the transaction.
+    /// Returns `None` for the unknown and the rejected transactions.
+    pub async fn tx_finality_estimate(
+        &self,
+        tx_hash: TxHash,
+    ) -> client::Result<Option<FinalityEstimate>> {
+        self.get(&format!("finality/{}", tx_hash.to_string()))
+            .send()
+            .await
+    }
+}
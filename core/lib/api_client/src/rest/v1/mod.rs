@@ -8,16 +8,23 @@ use zksync_types::BlockNumber;
 
 // Public uses
 pub use self::{
-    blocks::{BlockInfo, TransactionInfo},
-    client::{Client, ClientError},
-    config::Contracts,
-    error::ErrorBody,
-    operations::{PriorityOpData, PriorityOpQuery, PriorityOpQueryError, PriorityOpReceipt},
+    blocks::{BlockInfo, BlockMetadata, BlockStageInfo, TransactionInfo},
+    client::{Client, ClientError, API_KEY_HEADER},
+    config::{Contracts, NetworkConfig, NetworkContracts},
+    error::{ErrorBody, ErrorCode},
+    eth_sender::{EthOperationInfo, EthOperationsQuery},
+    finality::{FinalityEstimate, FinalityQuery, NetworkLoad},
+    operations::{
+        PriorityOpData, PriorityOpQuery, PriorityOpQueryError, PriorityOpQueueEntry,
+        PriorityOpQueueStatus, PriorityOpReceipt,
+    },
     search::BlockSearchQuery,
-    tokens::{TokenPriceKind, TokenPriceQuery},
+    tokens::{AccountBalance, BalancesSnapshotQuery, TokenPriceKind, TokenPriceQuery},
     transactions::{
-        FastProcessingQuery, IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee,
-        Receipt, TxData,
+        BatchStatus, FastProcessingQuery, ForcedExitEligibility, ForcedExitIneligibility,
+        IncomingTx, IncomingTxBatch, IncomingTxBatchForFee, IncomingTxForFee, Receipt,
+        SoftConfirmation, SoftConfirmationStage, SubmitBatchResponse, TxData, TxStatus,
+        TxStatusesQuery, MAX_TX_STATUSES,
     },
 };
 
@@ -27,6 +34,8 @@ mod blocks;
 mod client;
 mod config;
 mod error;
+mod eth_sender;
+mod finality;
 mod operations;
 mod search;
 mod tokens;
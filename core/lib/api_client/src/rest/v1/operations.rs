@@ -13,7 +13,7 @@ use super::{
 };
 
 // Workspace uses
-use zksync_types::{ZkSyncOp, H256};
+use zksync_types::{Address, BlockNumber, QueuedPriorityOp, ZkSyncOp, ZkSyncPriorityOp, H256};
 
 // Data transfer objects.
 
@@ -43,6 +43,63 @@ pub struct PriorityOpData {
     pub serial_id: u64,
 }
 
+/// Status of the priority operation in the queue.
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityOpQueueStatus {
+    /// Operation doesn't have enough confirmations yet.
+    Unconfirmed,
+    /// Operation is confirmed and waits to be included into the block.
+    Confirmed,
+}
+
+/// Priority operation that was sent to the L1, but not yet included into the zkSync block.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpQueueEntry {
+    pub serial_id: u64,
+    pub data: ZkSyncPriorityOp,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    pub status: PriorityOpQueueStatus,
+    /// Ethereum block starting from which operation is considered confirmed.
+    pub confirmation_eth_block: u64,
+    /// Ethereum block until which operation must be included into the zkSync block.
+    pub deadline_eth_block: u64,
+    /// Number of Ethereum blocks mined after the block with the operation.
+    pub confirmations: u64,
+    /// Position of the operation in the queue, operations are processed starting from the zero position.
+    pub queue_position: usize,
+    /// zkSync block the operation is expected to be included into.
+    pub expected_block: BlockNumber,
+    /// Position of the operation among the priority operations of the expected block.
+    pub processing_position: usize,
+}
+
+impl From<QueuedPriorityOp> for PriorityOpQueueEntry {
+    fn from(queued: QueuedPriorityOp) -> Self {
+        let status = if queued.confirmed {
+            PriorityOpQueueStatus::Confirmed
+        } else {
+            PriorityOpQueueStatus::Unconfirmed
+        };
+
+        Self {
+            serial_id: queued.op.serial_id,
+            data: queued.op.data,
+            eth_hash: queued.op.eth_hash,
+            eth_block: queued.op.eth_block,
+            status,
+            confirmation_eth_block: queued.confirmation_block,
+            deadline_eth_block: queued.op.deadline_block,
+            confirmations: queued.confirmations,
+            queue_position: queued.queue_position,
+            expected_block: queued.expected_block,
+            processing_position: queued.processing_position,
+        }
+    }
+}
+
 impl From<u64> for PriorityOpQuery {
     fn from(v: u64) -> Self {
         Self::Id(v)
@@ -120,4 +177,31 @@ impl Client {
             .send()
             .await
     }
+
+    /// Gets the queue of priority operations that are not yet included into the block.
+    pub async fn priority_op_queue(&self) -> Result<Vec<PriorityOpQueueEntry>, ClientError> {
+        self.get("operations/queue").send().await
+    }
+
+    /// Gets the queue of priority operations initiated by the given address that are not yet
+    /// included into the block.
+    pub async fn priority_op_queue_for(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PriorityOpQueueEntry>, ClientError> {
+        self.get(&format!("operations/queue/{:?}", address))
+            .send()
+            .await
+    }
+
+    /// Gets the not yet executed priority operations moving funds to the given address,
+    /// i.e. deposits to this address and full exits to this Ethereum address.
+    pub async fn incoming_priority_ops(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PriorityOpQueueEntry>, ClientError> {
+        self.get(&format!("operations/queue/{:?}/incoming", address))
+            .send()
+            .await
+    }
 }
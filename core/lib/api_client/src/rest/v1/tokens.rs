@@ -7,7 +7,8 @@ use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use zksync_types::{Token, TokenLike};
+use zksync_types::{AccountId, Address, BlockNumber, Token, TokenLike};
+use zksync_utils::BigUintSerdeWrapper;
 
 // Local uses
 use super::client::{self, Client};
@@ -28,6 +29,25 @@ pub struct TokenPriceQuery {
     pub kind: TokenPriceKind,
 }
 
+/// Query parameters for the token balances snapshot.
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+pub struct BalancesSnapshotQuery {
+    /// Verified block to take the snapshot at.
+    pub block: BlockNumber,
+    /// Return balances of the accounts following the account with this ID.
+    pub after: Option<AccountId>,
+    pub limit: u32,
+}
+
+/// Balance of the account in the token balances snapshot.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalance {
+    pub account_id: AccountId,
+    pub address: Address,
+    pub balance: BigUintSerdeWrapper,
+}
+
 /// Tokens API part.
 impl Client {
     pub async fn tokens(&self) -> client::Result<Vec<Token>> {
@@ -48,4 +68,23 @@ impl Client {
             .send()
             .await
     }
+
+    /// Returns the non-zero balances of the token as of the given verified block
+    /// in the ascending order of the account IDs.
+    pub async fn token_balances(
+        &self,
+        token: &TokenLike,
+        block: BlockNumber,
+        after: Option<AccountId>,
+        limit: u32,
+    ) -> client::Result<Vec<AccountBalance>> {
+        self.get(&format!("tokens/{}/balances", token))
+            .query(&BalancesSnapshotQuery {
+                block,
+                after,
+                limit,
+            })
+            .send()
+            .await
+    }
 }
@@ -3,11 +3,12 @@
 // Built-in uses
 
 // External uses
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_types::{
-    tx::{EthSignData, TxEthSignature, TxHash},
+    tx::{EthSignData, PackedEthSignature, TxEthSignature, TxFailureCode, TxHash},
     Address, BatchFee, BlockNumber, Fee, SignedZkSyncTx, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
@@ -69,6 +70,46 @@ pub struct IncomingTxBatch {
     pub signature: Option<TxEthSignature>,
 }
 
+/// Maximum number of transaction hashes in the single bulk status request.
+pub const MAX_TX_STATUSES: usize = 1000;
+
+/// Bulk transaction status request.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatusesQuery {
+    pub tx_hashes: Vec<TxHash>,
+}
+
+/// Status of a single transaction from the bulk status request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatus {
+    pub tx_hash: TxHash,
+    /// Receipt of the transaction, `None` if the transaction is unknown.
+    pub receipt: Option<Receipt>,
+}
+
+/// Status of the transactions batch, the batch transactions are executed atomically.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStatus {
+    pub batch_hash: TxHash,
+    /// Statuses of the batch transactions in the batch order.
+    pub transactions: Vec<TxStatus>,
+    /// Time when the batch was accepted to the mempool.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Result of the transactions batch submission.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitBatchResponse {
+    /// Hashes of the batch transactions in the batch order.
+    pub transaction_hashes: Vec<TxHash>,
+    /// Hash to track the batch as a whole, see [`TxHash::batch_hash`].
+    pub batch_hash: TxHash,
+}
+
 /// Transaction (or priority operation) receipt.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(tag = "status", rename_all = "camelCase")]
@@ -83,7 +124,143 @@ pub enum Receipt {
     /// The block which contains this transaction has been verified.
     Verified { block: BlockNumber },
     /// The transaction has been rejected for some reasons.
-    Rejected { reason: Option<String> },
+    Rejected {
+        reason: Option<String>,
+        /// Machine-readable class of the failure.
+        code: Option<TxFailureCode>,
+    },
+}
+
+impl Receipt {
+    /// Creates the receipt of the rejected transaction with the stored failure code.
+    /// Unknown codes, e.g. the ones stored by the newer server version, are omitted.
+    pub fn rejected(reason: Option<String>, code: Option<&str>) -> Self {
+        let code = code.and_then(|code| code.parse::<TxFailureCode>().ok());
+        Self::Rejected { reason, code }
+    }
+}
+
+/// Stage of the transaction processing attested by the soft confirmation.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(tag = "stage", rename_all = "camelCase")]
+pub enum SoftConfirmationStage {
+    /// The transaction has been accepted to the memory pool. Transactions are taken from the
+    /// memory pool in the order of their sequence numbers.
+    Accepted { mempool_seq_no: u64 },
+    /// The transaction has been executed and included into the block at the given position.
+    /// The block may be still pending.
+    Included {
+        block: BlockNumber,
+        block_index: u32,
+    },
+}
+
+/// Receipt signed by the operator key attesting that the transaction has been accepted
+/// by the server and the position of the transaction in the processing order.
+///
+/// The signature is the Ethereum signature of the [`SoftConfirmation::message`], so it can
+/// be verified by any party knowing the address of the operator key, which is provided by
+/// the `config/soft_confirmation_signer` endpoint. The message includes the L1 chain ID and
+/// the address of the zkSync contract, so the confirmation issued for one network can't be
+/// presented as the confirmation for another one.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftConfirmation {
+    /// Chain ID of the L1 network the zkSync network is deployed to.
+    pub chain_id: u64,
+    /// Address of the zkSync contract.
+    pub contract_address: Address,
+    pub tx_hash: TxHash,
+    pub stage: SoftConfirmationStage,
+    /// Time of the attested event, i.e. the acceptance to the memory pool or the execution.
+    pub timestamp: DateTime<Utc>,
+    /// Address of the operator key that signed the confirmation.
+    pub signer: Address,
+    pub signature: PackedEthSignature,
+}
+
+impl SoftConfirmation {
+    /// Returns the message signed by the operator key.
+    pub fn message(
+        chain_id: u64,
+        contract_address: Address,
+        tx_hash: TxHash,
+        stage: SoftConfirmationStage,
+        timestamp: DateTime<Utc>,
+    ) -> String {
+        let stage = match stage {
+            SoftConfirmationStage::Accepted { mempool_seq_no } => {
+                format!("Accepted to the mempool: #{}", mempool_seq_no)
+            }
+            SoftConfirmationStage::Included { block, block_index } => {
+                format!("Included into the block #{} at #{}", block, block_index)
+            }
+        };
+
+        format!(
+            "zkSync soft confirmation\nChain ID: {}\nContract: {:?}\nTransaction: {}\n{}\nTimestamp: {}",
+            chain_id,
+            contract_address,
+            tx_hash,
+            stage,
+            timestamp.timestamp()
+        )
+    }
+
+    /// Checks that the confirmation is signed by its signer. The signer itself should be
+    /// compared with the known address of the operator key, and the chain ID and the contract
+    /// address with the ones of the expected network.
+    pub fn verify(&self) -> bool {
+        let message = Self::message(
+            self.chain_id,
+            self.contract_address,
+            self.tx_hash,
+            self.stage,
+            self.timestamp,
+        );
+        self.signature
+            .signature_recover_signer(message.as_bytes())
+            .map(|signer| signer == self.signer)
+            .unwrap_or(false)
+    }
+}
+
+/// Reason why the `ForcedExit` of the target account would be rejected.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum ForcedExitIneligibility {
+    /// Target account does not exist.
+    AccountNotFound,
+    /// Target account was created recently, so its owner may still set the signing key.
+    AccountTooNew {
+        #[serde(rename = "eligibleAt")]
+        eligible_at: DateTime<Utc>,
+    },
+    /// Target account has the signing key set, so its owner can withdraw the funds.
+    /// Such a `ForcedExit` is accepted, but fails on execution.
+    SigningKeySet,
+    /// Target account has no balance of the token to withdraw.
+    /// Such a `ForcedExit` is accepted, but fails on execution.
+    ZeroBalance,
+}
+
+/// Result of the `ForcedExit` eligibility check for the target account and token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForcedExitEligibility {
+    /// Whether the `ForcedExit` would be accepted and executed successfully.
+    pub eligible: bool,
+    /// Reasons to reject the `ForcedExit`, empty if it's eligible.
+    pub reasons: Vec<ForcedExitIneligibility>,
+}
+
+impl ForcedExitEligibility {
+    pub fn new(reasons: Vec<ForcedExitIneligibility>) -> Self {
+        Self {
+            eligible: reasons.is_empty(),
+            reasons,
+        }
+    }
 }
 
 impl From<TxData> for SignedZkSyncTx {
@@ -154,18 +331,55 @@ impl Client {
             .await
     }
 
+    /// Checks whether the `ForcedExit` of the token balance of the target account
+    /// would be accepted, without submitting the transaction.
+    pub async fn forced_exit_eligibility(
+        &self,
+        target: Address,
+        token: &TokenLike,
+    ) -> Result<ForcedExitEligibility, ClientError> {
+        self.get(&format!("transactions/forced_exit/{:#x}/{}", target, token))
+            .send()
+            .await
+    }
+
+    /// Checks whether the fast processing of withdrawals is currently available.
+    pub async fn fast_processing_enabled(&self) -> Result<bool, ClientError> {
+        self.get("transactions/fast_processing").send().await
+    }
+
     /// Sends a new transactions batch to the memory pool.
+    /// Returns the hashes of the batch transactions along with the hash of the batch itself.
     pub async fn submit_tx_batch(
         &self,
         txs: Vec<ZkSyncTx>,
         signature: Option<TxEthSignature>,
-    ) -> Result<Vec<TxHash>, ClientError> {
+    ) -> Result<SubmitBatchResponse, ClientError> {
         self.post("transactions/submit/batch")
             .body(&IncomingTxBatch { txs, signature })
             .send()
             .await
     }
 
+    /// Gets actual receipts of the given transactions in the order of the hashes.
+    pub async fn tx_statuses(&self, tx_hashes: Vec<TxHash>) -> Result<Vec<TxStatus>, ClientError> {
+        self.post("transactions/statuses")
+            .body(&TxStatusesQuery { tx_hashes })
+            .send()
+            .await
+    }
+
+    /// Gets the actual status of the transactions batch by its hash.
+    /// Returns `None` if the batch was not accepted to the mempool.
+    pub async fn batch_status(
+        &self,
+        batch_hash: TxHash,
+    ) -> Result<Option<BatchStatus>, ClientError> {
+        self.get(&format!("transactions/batches/{}", batch_hash.to_string()))
+            .send()
+            .await
+    }
+
     /// Gets actual transaction receipt.
     pub async fn tx_status(&self, tx_hash: TxHash) -> Result<Option<Receipt>, ClientError> {
         self.get(&format!("transactions/{}", tx_hash.to_string()))
@@ -173,6 +387,20 @@ impl Client {
             .await
     }
 
+    /// Gets the soft confirmation of the transaction signed by the operator key.
+    /// Returns `None` if the transaction is unknown or rejected.
+    pub async fn tx_soft_confirmation(
+        &self,
+        tx_hash: TxHash,
+    ) -> Result<Option<SoftConfirmation>, ClientError> {
+        self.get(&format!(
+            "transactions/{}/confirmation",
+            tx_hash.to_string()
+        ))
+        .send()
+        .await
+    }
+
     /// Gets transaction content.
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, ClientError> {
         self.get(&format!("transactions/{}/data", tx_hash.to_string()))
@@ -208,3 +436,39 @@ impl Client {
             .await
     }
 }
+
+#[test]
+fn soft_confirmation_verify() {
+    let private_key = zksync_types::H256::repeat_byte(0x11);
+    let tx_hash = TxHash::default();
+    let stage = SoftConfirmationStage::Included {
+        block: BlockNumber(5),
+        block_index: 2,
+    };
+    let timestamp = Utc::now();
+    let contract_address = Address::repeat_byte(0x22);
+
+    let message = SoftConfirmation::message(9, contract_address, tx_hash, stage, timestamp);
+    let mut confirmation = SoftConfirmation {
+        chain_id: 9,
+        contract_address,
+        tx_hash,
+        stage,
+        timestamp,
+        signer: PackedEthSignature::address_from_private_key(&private_key).unwrap(),
+        signature: PackedEthSignature::sign(&private_key, message.as_bytes()).unwrap(),
+    };
+    assert!(confirmation.verify());
+
+    // The confirmation can't be presented for another network.
+    confirmation.chain_id = 1;
+    assert!(!confirmation.verify());
+    confirmation.chain_id = 9;
+
+    // The confirmation can't be altered without invalidating the signature.
+    confirmation.stage = SoftConfirmationStage::Included {
+        block: BlockNumber(5),
+        block_index: 1,
+    };
+    assert!(!confirmation.verify());
+}
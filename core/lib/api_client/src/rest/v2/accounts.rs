@@ -0,0 +1,195 @@
+//! Accounts part of the API v2 data transfer objects.
+
+// Built-in uses
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_types::BlockNumber;
+
+// Local uses
+
+/// Type of the transaction in the account history.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TxHistoryType {
+    Transfer,
+    Withdraw,
+    ChangePubKey,
+    ForcedExit,
+    Close,
+    Deposit,
+    FullExit,
+}
+
+impl TxHistoryType {
+    /// Returns the type as it's named in the transaction data.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::Withdraw => "Withdraw",
+            Self::ChangePubKey => "ChangePubKey",
+            Self::ForcedExit => "ForcedExit",
+            Self::Close => "Close",
+            Self::Deposit => "Deposit",
+            Self::FullExit => "FullExit",
+        }
+    }
+}
+
+/// Side of the transaction the account participates on.
+/// Transactions of the account to itself match both directions.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TxDirection {
+    /// Transactions sent by the account, including the deposits from its Ethereum address.
+    Outgoing,
+    /// Transactions received by the account.
+    Incoming,
+}
+
+/// Filter of the account transactions history:
+/// `?[token={token}]&[txType={type}]&[direction={direction}]&[fromTime={time}]&[toTime={time}]`.
+///
+/// The time range includes the `fromTime` and excludes the `toTime`,
+/// the time is specified in the RFC 3339 format.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TxHistoryQuery {
+    /// Token ID, address or symbol, either the transferred token or the fee one.
+    pub token: Option<String>,
+    pub tx_type: Option<TxHistoryType>,
+    pub direction: Option<TxDirection>,
+    pub from_time: Option<DateTime<Utc>>,
+    pub to_time: Option<DateTime<Utc>>,
+}
+
+/// Cursor of the account transactions history, formatted as
+/// `{block_number},{block_index},{hash}` with the empty index for rejected transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxHistoryCursor {
+    pub block_number: BlockNumber,
+    /// Transaction index in block, absent for rejected transactions.
+    pub block_index: Option<u32>,
+    /// Raw hash bytes of the transaction or the priority operation.
+    pub hash: Vec<u8>,
+}
+
+impl Display for TxHistoryCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let block_index = self
+            .block_index
+            .map(|index| index.to_string())
+            .unwrap_or_default();
+        write!(
+            f,
+            "{},{},{}",
+            *self.block_number,
+            block_index,
+            hex::encode(&self.hash)
+        )
+    }
+}
+
+impl FromStr for TxHistoryCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(',').collect();
+        if parts.len() != 3 {
+            return Err(format!("Incorrect history cursor: {}", s));
+        }
+
+        let block_number = parts[0]
+            .parse()
+            .map_err(|err| format!("Incorrect block number in the history cursor: {}", err))?;
+        let block_index =
+            match parts[1] {
+                "" => None,
+                index => Some(index.parse().map_err(|err| {
+                    format!("Incorrect block index in the history cursor: {}", err)
+                })?),
+            };
+        let hash = hex::decode(parts[2])
+            .map_err(|err| format!("Incorrect hash in the history cursor: {}", err))?;
+
+        Ok(Self {
+            block_number: BlockNumber(block_number),
+            block_index,
+            hash,
+        })
+    }
+}
+
+impl Serialize for TxHistoryCursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxHistoryCursor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Transaction of the account history.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxHistoryItem {
+    /// Transaction hash with the `sync-tx:` prefix, or the Ethereum hash of the priority operation.
+    pub tx_hash: String,
+    pub block_number: BlockNumber,
+    /// Transaction index in block, absent for rejected transactions.
+    pub block_index: Option<u32>,
+    /// Ethereum block of the priority operation, absent for transactions.
+    pub eth_block: Option<u64>,
+    /// Serial ID of the priority operation, absent for transactions.
+    pub priority_op_id: Option<u64>,
+    /// Transaction data with the token symbols instead of the token IDs.
+    pub tx: Value,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    /// Whether the verify of the transaction block is confirmed in the L1.
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_cursor_format() {
+        let cursor = TxHistoryCursor {
+            block_number: BlockNumber(12),
+            block_index: Some(3),
+            hash: vec![0xab, 0xcd],
+        };
+        assert_eq!(cursor.to_string(), "12,3,abcd");
+        assert_eq!("12,3,abcd".parse::<TxHistoryCursor>(), Ok(cursor));
+
+        let rejected_tx_cursor = TxHistoryCursor {
+            block_number: BlockNumber(12),
+            block_index: None,
+            hash: vec![0xab, 0xcd],
+        };
+        assert_eq!(rejected_tx_cursor.to_string(), "12,,abcd");
+        assert_eq!(
+            "12,,abcd".parse::<TxHistoryCursor>(),
+            Ok(rejected_tx_cursor)
+        );
+
+        for incorrect in &["", "12,3", "12,x,abcd", "12,3,xyz", "12,3,abcd,1"] {
+            assert!(incorrect.parse::<TxHistoryCursor>().is_err());
+        }
+    }
+}
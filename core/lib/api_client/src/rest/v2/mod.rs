@@ -0,0 +1,220 @@
+//! Data transfer objects of the second version of the REST API.
+//!
+//! Every response of the API v2 is wrapped into the `{ "result": ..., "error": ... }` envelope,
+//! exactly one of the fields is not null. Lists are paginated with cursors: the client passes
+//! the `nextCursor` of the previous page to get the next one.
+
+// Built-in uses
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+// External uses
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Workspace uses
+
+// Local uses
+pub use super::v1::{ErrorCode, MAX_LIMIT};
+
+// Public uses
+pub use self::accounts::{
+    TxDirection, TxHistoryCursor, TxHistoryItem, TxHistoryQuery, TxHistoryType,
+};
+pub use self::rejected_txs::{RejectedTx, RejectedTxsQuery};
+pub use self::search::{SearchQuery, SearchResult};
+pub use self::stats::{
+    DailyStats, NetworkStats, StatsQuery, TokenFeeStats, TokenTvl, DEFAULT_STATS_DAYS,
+    MAX_STATS_DAYS,
+};
+pub use self::tokens::TokenInfo;
+pub use self::webhooks::{
+    is_public_ip, NewWebhook, Webhook, WebhookEvent, WebhookEventKind, MAX_WEBHOOKS_PER_CLIENT,
+    MAX_WEBHOOK_URL_LENGTH, WEBHOOK_SIGNATURE_HEADER,
+};
+
+mod accounts;
+mod rejected_txs;
+mod search;
+mod stats;
+mod tokens;
+mod webhooks;
+
+/// Amount of the items returned if the limit is not specified in the request.
+pub const DEFAULT_LIMIT: u32 = 20;
+
+/// Envelope of the API v2 responses.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response<T> {
+    pub result: Option<T>,
+    pub error: Option<Error>,
+}
+
+impl<T> Response<T> {
+    pub fn ok(result: T) -> Self {
+        Self {
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn error(error: Error) -> Self {
+        Self {
+            result: None,
+            error: Some(error),
+        }
+    }
+
+    /// Converts the envelope into the result. Successful response may have a `null` result
+    /// if the requested entity doesn't exist.
+    pub fn into_result(self) -> Result<Option<T>, Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.result),
+        }
+    }
+}
+
+/// Error of the API v2 request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Error {
+    /// Stable numeric error code, see [`ErrorCode`].
+    pub code: u64,
+    /// Stable string identifier of the error code, see [`ErrorCode`].
+    pub code_name: String,
+    /// Human-readable description of the error, not intended to be parsed.
+    pub message: String,
+    /// Suggested delay in seconds before retrying the request, absent if the request
+    /// shouldn't be retried as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    /// Structured details of the error, depending on the error code, e.g. the required
+    /// and the provided fees for the `feeTooLow` error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl Error {
+    pub fn new(code: ErrorCode, message: impl Display) -> Self {
+        Self {
+            code: code.as_u64(),
+            code_name: code.name().to_owned(),
+            message: message.to_string(),
+            retry_after: code.retry_after().map(|delay| delay.as_secs()),
+            data: None,
+        }
+    }
+
+    /// Returns the error code, if it's known to this version of the client.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        ErrorCode::from_u64(self.code)
+    }
+
+    /// Returns the suggested delay before retrying the request, or `None`
+    /// if the request shouldn't be retried as is.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after.map(Duration::from_secs)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code_name)
+    }
+}
+
+fn default_limit() -> u32 {
+    DEFAULT_LIMIT
+}
+
+/// Query of the paginated list: `?[cursor={cursor}]&[limit={limit}]`.
+///
+/// Returns up to `limit` items following the item with the `cursor` (not including it),
+/// or the first `limit` items if the cursor is not set.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct PaginationQuery<C> {
+    #[serde(default)]
+    pub cursor: Option<C>,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+impl<C> PaginationQuery<C> {
+    /// Checks the limit of the query, returns the error message if it's incorrect.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.limit == 0 || self.limit > MAX_LIMIT {
+            return Err(format!("Limit should be between {} and {}", 1, MAX_LIMIT));
+        }
+        Ok(())
+    }
+}
+
+/// Page of the paginated list.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Paginated<T, C> {
+    pub list: Vec<T>,
+    pub limit: u32,
+    /// Cursor of the next page, absent if this page is the last one.
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> Paginated<T, C> {
+    /// Creates the page of the list requested with the given limit, `cursor` returns
+    /// the cursor pointing to the item.
+    pub fn new(list: Vec<T>, limit: u32, cursor: impl FnOnce(&T) -> C) -> Self {
+        // Page shorter than the limit means that there are no more items.
+        let next_cursor = if list.len() < limit as usize {
+            None
+        } else {
+            list.last().map(cursor)
+        };
+
+        Self {
+            list,
+            limit,
+            next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_cursor() {
+        let page = Paginated::new(vec![5_u32, 4, 3], 3, |item| *item);
+        assert_eq!(page.next_cursor, Some(3));
+
+        let last_page = Paginated::new(vec![2_u32, 1], 3, |item| *item);
+        assert_eq!(last_page.next_cursor, None);
+
+        let empty_page = Paginated::new(Vec::<u32>::new(), 3, |item| *item);
+        assert_eq!(empty_page.next_cursor, None);
+    }
+
+    #[test]
+    fn error_envelope() {
+        let response: Response<u32> =
+            Response::error(Error::new(ErrorCode::InvalidParams, "Incorrect limit"));
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "result": null,
+                "error": {
+                    "code": 401,
+                    "codeName": "invalidParams",
+                    "message": "Incorrect limit",
+                }
+            })
+        );
+
+        let error = response.into_result().unwrap_err();
+        assert_eq!(error.error_code(), Some(ErrorCode::InvalidParams));
+        assert_eq!(error.retry_after(), None);
+    }
+}
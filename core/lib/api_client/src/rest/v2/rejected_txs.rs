@@ -0,0 +1,71 @@
+//! Rejected transactions part of the API v2 data transfer objects.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_types::{tx::TxHash, Address};
+
+// Local uses
+use super::Error;
+
+/// Filter of the rejected transactions: `?txHash={hash}` or `?address={address}`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RejectedTxsQuery {
+    /// Hash of the transaction, with the `sync-tx:` prefix.
+    pub tx_hash: Option<TxHash>,
+    /// Address of the account which has initiated the transactions.
+    pub address: Option<Address>,
+}
+
+impl RejectedTxsQuery {
+    /// Checks that exactly one of the filters is set, returns the error message otherwise.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tx_hash.is_some() == self.address.is_some() {
+            return Err("Either the transaction hash or the account address should be set".into());
+        }
+        Ok(())
+    }
+}
+
+/// Transaction rejected by the server on submission, so it has never reached the mempool.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedTx {
+    /// ID of the rejection, used as the pagination cursor.
+    pub id: u64,
+    pub tx_hash: TxHash,
+    /// Address of the account which has initiated the transaction.
+    pub address: Address,
+    pub tx: Value,
+    /// Error the submission was rejected with, the same as returned to the submitter.
+    pub error: Error,
+    pub rejected_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejected_txs_query() {
+        let query: RejectedTxsQuery = serde_json::from_value(serde_json::json!({
+            "txHash": TxHash::default().to_string(),
+        }))
+        .unwrap();
+        assert_eq!(query.tx_hash, Some(TxHash::default()));
+        assert!(query.validate().is_ok());
+
+        let query = RejectedTxsQuery {
+            address: Some(Address::zero()),
+            ..query
+        };
+        assert!(query.validate().is_err());
+        assert!(RejectedTxsQuery::default().validate().is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Search part of the API v2 data transfer objects.
+
+// Built-in uses
+
+// External uses
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, H256};
+
+// Local uses
+
+/// Query of the explorer search: `?query={query}`.
+///
+/// The query can be either of:
+///
+/// - The number of the block.
+/// - The state root hash of the block or the hash of its commit/verify Ethereum transaction.
+/// - The hash of the transaction, with or without the `sync-tx:` prefix.
+/// - The hash of the Ethereum transaction which has created the priority operation.
+/// - The address of the account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub query: String,
+}
+
+/// Entity the search query is resolved to, along with the data required to link to it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SearchResult {
+    #[serde(rename_all = "camelCase")]
+    Block { block_number: BlockNumber },
+    /// Transaction either included in the block or still waiting in the mempool.
+    #[serde(rename_all = "camelCase")]
+    Transaction {
+        tx_hash: TxHash,
+        /// Block of the transaction, absent if it's not executed yet.
+        block_number: Option<BlockNumber>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PriorityOperation {
+        /// Hash of the Ethereum transaction which has created the operation.
+        eth_hash: H256,
+        serial_id: u64,
+        block_number: BlockNumber,
+    },
+    /// Account is resolved by any valid address, even if it doesn't exist in the network yet.
+    #[serde(rename_all = "camelCase")]
+    Account {
+        address: Address,
+        /// ID of the account, absent if the account doesn't exist.
+        account_id: Option<AccountId>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_result_serialization() {
+        let result = SearchResult::Transaction {
+            tx_hash: TxHash::default(),
+            block_number: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "type": "transaction",
+                "txHash": TxHash::default().to_string(),
+                "blockNumber": null,
+            })
+        );
+
+        let result = SearchResult::Block {
+            block_number: BlockNumber(1),
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({ "type": "block", "blockNumber": 1 })
+        );
+    }
+}
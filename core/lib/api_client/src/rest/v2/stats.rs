@@ -0,0 +1,104 @@
+//! Network statistics part of the API v2 data transfer objects.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::TokenId;
+use zksync_utils::BigUintSerdeWrapper;
+
+// Local uses
+
+/// Amount of the days returned if it's not specified in the request.
+pub const DEFAULT_STATS_DAYS: u32 = 30;
+/// Maximum amount of the days that can be requested at once.
+pub const MAX_STATS_DAYS: u32 = 365;
+
+fn default_stats_days() -> u32 {
+    DEFAULT_STATS_DAYS
+}
+
+/// Query of the network statistics: `?[days={days}]`.
+///
+/// Daily statistics are returned for the last `days` days (in UTC), including the current one.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct StatsQuery {
+    #[serde(default = "default_stats_days")]
+    pub days: u32,
+}
+
+impl StatsQuery {
+    /// Checks the amount of the requested days, returns the error message if it's incorrect.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.days == 0 || self.days > MAX_STATS_DAYS {
+            return Err(format!("Days should be between 1 and {}", MAX_STATS_DAYS));
+        }
+        Ok(())
+    }
+}
+
+/// Total value locked in the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTvl {
+    pub token_id: TokenId,
+    pub symbol: String,
+    /// Sum of the verified balances of all the accounts.
+    pub amount: BigUintSerdeWrapper,
+}
+
+/// Fees paid in the token during a single day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenFeeStats {
+    pub token_id: TokenId,
+    pub symbol: String,
+    /// Amount of the transactions which have paid the fee in the token.
+    pub tx_count: u64,
+    pub average_fee: BigUintSerdeWrapper,
+}
+
+/// Statistics of a single day (in UTC).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    /// Amount of the executed transactions and priority operations.
+    pub tx_count: u64,
+    /// Amount of the distinct addresses which have initiated a transaction.
+    pub active_accounts: u64,
+    pub fees: Vec<TokenFeeStats>,
+}
+
+/// Network statistics precomputed by the server, so the numbers may be
+/// behind the latest blocks by the aggregation interval.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStats {
+    /// Total value locked in every token, ordered by the token ID.
+    pub tvl: Vec<TokenTvl>,
+    /// Statistics of the requested days in the descending order, the days without
+    /// any transactions are omitted.
+    pub daily: Vec<DailyStats>,
+    /// Time of the last aggregation, absent if the statistics were never aggregated.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_query() {
+        let query: StatsQuery = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(query.days, DEFAULT_STATS_DAYS);
+        assert!(query.validate().is_ok());
+
+        for &(days, is_valid) in &[(1, true), (MAX_STATS_DAYS, true), (0, false), (366, false)] {
+            assert_eq!(StatsQuery { days }.validate().is_ok(), is_valid);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Tokens part of the API v2 data transfer objects.
+
+// Built-in uses
+
+// External uses
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{Address, Token, TokenId};
+
+// Local uses
+
+/// Token supported by the network along with its metadata.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub id: TokenId,
+    pub address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Whether the operator accepts the fees in the token.
+    pub enabled_for_fees: bool,
+    /// Time the token was listed at, absent for the tokens listed before it was tracked.
+    pub listed_at: Option<DateTime<Utc>>,
+}
+
+impl TokenInfo {
+    pub fn new(token: Token, enabled_for_fees: bool, listed_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            id: token.id,
+            address: token.address,
+            symbol: token.symbol,
+            decimals: token.decimals,
+            enabled_for_fees,
+            listed_at,
+        }
+    }
+}
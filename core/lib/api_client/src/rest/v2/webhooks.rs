@@ -0,0 +1,257 @@
+//! Webhooks part of the API v2 data transfer objects.
+//!
+//! Integrators authenticated by the API key register the webhooks for the addresses, and the
+//! server sends a `POST` request with the JSON encoded [`WebhookEvent`] to the webhook URL
+//! every time a transaction touching the address is executed or the block with such
+//! transactions is verified. Failed deliveries are retried with an exponential backoff.
+//!
+//! Webhooks can only target the public hosts: the URLs with the loopback, private,
+//! link-local and other special-purpose addresses are rejected, and the redirects of the
+//! webhook responses are not followed.
+
+// Built-in uses
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+// External uses
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+// Workspace uses
+use zksync_types::{Address, BlockNumber};
+
+// Local uses
+
+/// Header with the signature of the delivered event: HMAC-SHA256 of the request body keyed
+/// by the secret of the webhook, encoded with the URL-safe base64 without padding.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Zksync-Signature";
+/// Maximum number of the webhooks registered by the same integrator.
+pub const MAX_WEBHOOKS_PER_CLIENT: usize = 100;
+/// Maximum length of the webhook URL.
+pub const MAX_WEBHOOK_URL_LENGTH: usize = 2048;
+
+/// Webhook to be registered: `POST /api/v2/webhooks`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NewWebhook {
+    pub address: Address,
+    /// URL receiving the events, either `http` or `https` one with a public host.
+    pub url: String,
+}
+
+impl NewWebhook {
+    /// Checks the webhook URL, returns the error message if it's incorrect.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.url.len() > MAX_WEBHOOK_URL_LENGTH {
+            return Err(format!(
+                "Webhook URL should not be longer than {} characters",
+                MAX_WEBHOOK_URL_LENGTH
+            ));
+        }
+        let url = Url::parse(&self.url).map_err(|err| format!("Invalid webhook URL: {}", err))?;
+        if url.scheme() != "https" && url.scheme() != "http" {
+            return Err("Webhook URL should be an HTTP or HTTPS one".into());
+        }
+
+        // IP hosts are normalized by the parser, IPv6 ones are enclosed in brackets.
+        let host = url.host_str().unwrap_or_default();
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let is_public = match host.parse::<IpAddr>() {
+            Ok(ip) => is_public_ip(ip),
+            Err(_) => {
+                let domain = host.trim_end_matches('.').to_ascii_lowercase();
+                domain.contains('.') && !domain.ends_with(".localhost")
+            }
+        };
+        if !is_public {
+            return Err("Webhook URL should point to a public host".into());
+        }
+        Ok(())
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // "This network" 0.0.0.0/8.
+        || a == 0
+        // Shared address space 100.64.0.0/10.
+        || (a == 100 && (b & 0xc0) == 64)
+        // IETF protocol assignments 192.0.0.0/24.
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking 198.18.0.0/15.
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved 240.0.0.0/4.
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = ip.to_ipv4() {
+        // IPv4-mapped and IPv4-compatible addresses, except for `::` and `::1`
+        // which are handled below.
+        if !ip.is_unspecified() && ip != Ipv4Addr::new(0, 0, 0, 1) {
+            return is_public_ipv4(ip);
+        }
+    }
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local fc00::/7.
+        || (first & 0xfe00) == 0xfc00
+        // Link-local fe80::/10 and the deprecated site-local fec0::/10.
+        || (first & 0xffc0) == 0xfe80
+        || (first & 0xffc0) == 0xfec0
+        // Documentation 2001:db8::/32.
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// Checks whether the address is a public one, i.e. the webhooks can be delivered to it.
+/// The loopback, private, link-local (including the cloud metadata services) and other
+/// special-purpose addresses are not public.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+/// Webhook registered by the integrator.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: u64,
+    pub address: Address,
+    pub url: String,
+    /// Key of the event signatures, only returned once the webhook is registered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Activity of the subscribed address.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WebhookEventKind {
+    /// Transaction or priority operation touching the address was executed, but its block
+    /// is neither committed nor verified yet.
+    #[serde(rename_all = "camelCase")]
+    TransactionExecuted {
+        /// Hash of the transaction with the `sync-tx:` prefix, or the hash of the Ethereum
+        /// transaction which has created the priority operation.
+        tx_hash: String,
+        block_number: BlockNumber,
+        success: bool,
+        fail_reason: Option<String>,
+    },
+    /// Block with the transactions touching the address was verified.
+    #[serde(rename_all = "camelCase")]
+    BlockVerified {
+        block_number: BlockNumber,
+        /// Hashes of the block transactions touching the address, in the same format as in
+        /// the `transactionExecuted` events.
+        tx_hashes: Vec<String>,
+    },
+}
+
+/// Event delivered to the webhook.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    pub webhook_id: u64,
+    pub address: Address,
+    #[serde(flatten)]
+    pub kind: WebhookEventKind,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_webhook_validation() {
+        let webhook = NewWebhook {
+            address: Address::zero(),
+            url: "https://wallet.io/hook".into(),
+        };
+        assert!(webhook.validate().is_ok());
+
+        let rejected_urls = [
+            "ftp://wallet.io/hook",
+            "wallet.io",
+            "http://localhost:3000/hook",
+            "http://api.localhost/hook",
+            "http://intranet/hook",
+            "http://127.0.0.1/hook",
+            "http://0x7f.1/hook",
+            "http://0.0.0.0/hook",
+            "http://10.0.0.1/hook",
+            "http://172.16.5.4/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://[::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+        ];
+        for url in &rejected_urls {
+            let webhook = NewWebhook {
+                url: url.to_string(),
+                ..webhook.clone()
+            };
+            assert!(webhook.validate().is_err(), "{}", url);
+        }
+        let webhook = NewWebhook {
+            url: format!("https://wallet.io/{}", "a".repeat(MAX_WEBHOOK_URL_LENGTH)),
+            ..webhook
+        };
+        assert!(webhook.validate().is_err());
+    }
+
+    #[test]
+    fn public_ips() {
+        for ip in &["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in &[
+            "127.0.0.1",
+            "10.1.2.3",
+            "169.254.169.254",
+            "198.18.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "::ffff:192.168.0.1",
+            "fc00::1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn webhook_event_serialization() {
+        let event = WebhookEvent {
+            webhook_id: 1,
+            address: Address::zero(),
+            kind: WebhookEventKind::BlockVerified {
+                block_number: BlockNumber(5),
+                tx_hashes: vec![],
+            },
+            created_at: Utc::now(),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["type"], "blockVerified");
+        assert_eq!(value["webhookId"], 1);
+        assert_eq!(value["blockNumber"], 5);
+
+        let deserialized: WebhookEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, event);
+    }
+}
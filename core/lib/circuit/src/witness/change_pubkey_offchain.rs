@@ -10,7 +10,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -53,7 +53,10 @@ impl Witness for ChangePubkeyOffChainWitness<Bn256> {
     type OperationType = ChangePubKeyOp;
     type CalculateOpsInput = SigDataInput;
 
-    fn apply_tx(tree: &mut CircuitAccountTree, change_pubkey_offchain: &ChangePubKeyOp) -> Self {
+    fn apply_tx(
+        tree: &mut dyn WitnessAccountTree,
+        change_pubkey_offchain: &ChangePubKeyOp,
+    ) -> Self {
         let change_pubkey_data = ChangePubkeyOffChainData {
             account_id: *change_pubkey_offchain.account_id,
             address: eth_address_to_fr(&change_pubkey_offchain.tx.account),
@@ -133,7 +136,7 @@ impl Witness for ChangePubkeyOffChainWitness<Bn256> {
 
 impl ChangePubkeyOffChainWitness<Bn256> {
     fn apply_data(
-        tree: &mut CircuitAccountTree,
+        tree: &mut dyn WitnessAccountTree,
         change_pubkey_offcahin: ChangePubkeyOffChainData,
     ) -> Self {
         //preparing data and base witness
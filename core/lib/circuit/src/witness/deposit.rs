@@ -9,7 +9,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -51,7 +51,7 @@ impl Witness for DepositWitness<Bn256> {
     type OperationType = DepositOp;
     type CalculateOpsInput = ();
 
-    fn apply_tx(tree: &mut CircuitAccountTree, deposit: &DepositOp) -> Self {
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, deposit: &DepositOp) -> Self {
         let deposit_data = DepositData {
             amount: deposit.priority_op.amount.to_string().parse().unwrap(),
             token: *deposit.priority_op.token as u32,
@@ -176,7 +176,7 @@ impl<E: RescueEngine> DepositWitness<E> {
 }
 
 impl DepositWitness<Bn256> {
-    fn apply_data(tree: &mut CircuitAccountTree, deposit: &DepositData) -> Self {
+    fn apply_data(tree: &mut dyn WitnessAccountTree, deposit: &DepositData) -> Self {
         //preparing data and base witness
         let before_root = tree.root_hash();
         vlog::debug!("deposit Initial root = {}", before_root);
@@ -10,7 +10,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -59,7 +59,7 @@ impl Witness for ForcedExitWitness<Bn256> {
     type OperationType = ForcedExitOp;
     type CalculateOpsInput = SigDataInput;
 
-    fn apply_tx(tree: &mut CircuitAccountTree, forced_exit: &ForcedExitOp) -> Self {
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, forced_exit: &ForcedExitOp) -> Self {
         let forced_exit_data = ForcedExitData {
             amount: forced_exit
                 .withdraw_amount
@@ -231,7 +231,7 @@ impl<E: RescueEngine> ForcedExitWitness<E> {
 }
 
 impl ForcedExitWitness<Bn256> {
-    fn apply_data(tree: &mut CircuitAccountTree, forced_exit: &ForcedExitData) -> Self {
+    fn apply_data(tree: &mut dyn WitnessAccountTree, forced_exit: &ForcedExitData) -> Self {
         //preparing data and base witness
         let before_root = tree.root_hash();
         vlog::debug!("Initial root = {}", before_root);
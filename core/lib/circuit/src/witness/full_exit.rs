@@ -9,7 +9,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -51,7 +51,7 @@ impl Witness for FullExitWitness<Bn256> {
     type CalculateOpsInput = ();
 
     fn apply_tx(
-        tree: &mut CircuitAccountTree,
+        tree: &mut dyn WitnessAccountTree,
         (full_exit, is_success): &(FullExitOp, bool),
     ) -> Self {
         let full_exit = FullExitData {
@@ -151,7 +151,7 @@ impl Witness for FullExitWitness<Bn256> {
 
 impl FullExitWitness<Bn256> {
     fn apply_data(
-        tree: &mut CircuitAccountTree,
+        tree: &mut dyn WitnessAccountTree,
         full_exit: &FullExitData,
         is_success: bool,
     ) -> Self {
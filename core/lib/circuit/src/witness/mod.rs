@@ -1,6 +1,6 @@
 use zksync_crypto::franklin_crypto::bellman::pairing::bn256::Bn256;
 
-use zksync_crypto::circuit::account::CircuitAccountTree;
+use zksync_crypto::circuit::account::WitnessAccountTree;
 
 use crate::operation::Operation;
 
@@ -41,7 +41,7 @@ pub trait Witness {
     type CalculateOpsInput;
 
     /// Applies the operation to the Circuit account tree, generating the witness data.
-    fn apply_tx(tree: &mut CircuitAccountTree, op: &Self::OperationType) -> Self;
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, op: &Self::OperationType) -> Self;
 
     /// Obtains the pubdata from the witness.
     fn get_pubdata(&self) -> Vec<bool>;
@@ -5,7 +5,7 @@ use zksync_crypto::franklin_crypto::bellman::pairing::{
 };
 // Workspace deps
 use zksync_crypto::circuit::{
-    account::CircuitAccountTree, utils::le_bit_vector_into_field_element,
+    account::WitnessAccountTree, utils::le_bit_vector_into_field_element,
 };
 use zksync_crypto::params::CHUNK_BIT_WIDTH;
 // Local deps
@@ -17,7 +17,7 @@ use crate::{
     witness::utils::get_audits,
 };
 
-pub fn noop_operation(tree: &CircuitAccountTree, acc_id: u32) -> Operation<Bn256> {
+pub fn noop_operation(tree: &dyn WitnessAccountTree, acc_id: u32) -> Operation<Bn256> {
     let signature_data = SignatureData::init_empty();
     let first_sig_msg = Fr::zero();
     let second_sig_msg = Fr::zero();
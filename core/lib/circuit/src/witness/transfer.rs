@@ -10,7 +10,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, le_bit_vector_into_field_element},
     },
     params::{
@@ -58,7 +58,7 @@ impl Witness for TransferWitness<Bn256> {
     type OperationType = TransferOp;
     type CalculateOpsInput = SigDataInput;
 
-    fn apply_tx(tree: &mut CircuitAccountTree, transfer: &TransferOp) -> Self {
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, transfer: &TransferOp) -> Self {
         let transfer_data = TransferData {
             amount: transfer.tx.amount.to_u128().unwrap(),
             fee: transfer.tx.fee.to_u128().unwrap(),
@@ -198,7 +198,7 @@ impl<E: RescueEngine> TransferWitness<E> {
 }
 
 impl TransferWitness<Bn256> {
-    fn apply_data(tree: &mut CircuitAccountTree, transfer: &TransferData) -> Self {
+    fn apply_data(tree: &mut dyn WitnessAccountTree, transfer: &TransferData) -> Self {
         //preparing data and base witness
         let before_root = tree.root_hash();
         vlog::debug!("Initial root = {}", before_root);
@@ -8,7 +8,7 @@ use zksync_crypto::franklin_crypto::{
 };
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -58,7 +58,7 @@ impl Witness for TransferToNewWitness<Bn256> {
     type OperationType = TransferToNewOp;
     type CalculateOpsInput = SigDataInput;
 
-    fn apply_tx(tree: &mut CircuitAccountTree, transfer_to_new: &TransferToNewOp) -> Self {
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, transfer_to_new: &TransferToNewOp) -> Self {
         let transfer_data = TransferToNewData {
             amount: transfer_to_new.tx.amount.to_string().parse().unwrap(),
             fee: transfer_to_new.tx.fee.to_string().parse().unwrap(),
@@ -223,7 +223,7 @@ impl<E: RescueEngine> TransferToNewWitness<E> {
 }
 
 impl TransferToNewWitness<Bn256> {
-    fn apply_data(tree: &mut CircuitAccountTree, transfer_to_new: &TransferToNewData) -> Self {
+    fn apply_data(tree: &mut dyn WitnessAccountTree, transfer_to_new: &TransferToNewData) -> Self {
         //preparing data and base witness
         let before_root = tree.root_hash();
         vlog::debug!("Initial root = {}", before_root);
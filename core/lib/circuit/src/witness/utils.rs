@@ -15,7 +15,7 @@ use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::{Balance, CircuitAccount, CircuitAccountTree},
+        account::{Balance, CircuitAccount, WitnessAccountTree},
         utils::{be_bit_vector_into_bytes, le_bit_vector_into_field_element},
     },
     merkle_tree::{hasher::Hasher, RescueHasher},
@@ -44,11 +44,11 @@ use crate::{
     utils::sign_rescue,
 };
 
-/// Wrapper around `CircuitAccountTree`
+/// Wrapper around `WitnessAccountTree`
 /// that simplifies witness generation
 /// used for testing
 pub struct WitnessBuilder<'a> {
-    pub account_tree: &'a mut CircuitAccountTree,
+    pub account_tree: &'a mut dyn WitnessAccountTree,
     pub fee_account_id: AccountId,
     pub block_number: BlockNumber,
     pub initial_root_hash: Fr,
@@ -65,7 +65,7 @@ pub struct WitnessBuilder<'a> {
 
 impl<'a> WitnessBuilder<'a> {
     pub fn new(
-        account_tree: &'a mut CircuitAccountTree,
+        account_tree: &'a mut dyn WitnessAccountTree,
         fee_account_id: AccountId,
         block_number: BlockNumber,
     ) -> WitnessBuilder {
@@ -326,11 +326,10 @@ pub fn public_data_commitment<E: JubjubEngine>(
 }
 
 pub fn get_audits(
-    tree: &CircuitAccountTree,
+    tree: &dyn WitnessAccountTree,
     account_address: u32,
     token: u32,
 ) -> (Vec<Option<Fr>>, Vec<Option<Fr>>) {
-    let default_account = CircuitAccount::default();
     let audit_account: Vec<Option<Fr>> = tree
         .merkle_path(account_address)
         .into_iter()
@@ -339,7 +338,7 @@ pub fn get_audits(
 
     let audit_balance: Vec<Option<Fr>> = tree
         .get(account_address)
-        .unwrap_or(&default_account)
+        .unwrap_or_default()
         .subtree
         .merkle_path(token)
         .into_iter()
@@ -349,7 +348,7 @@ pub fn get_audits(
 }
 
 pub fn apply_leaf_operation<Fa: Fn(&mut CircuitAccount<Bn256>), Fb: Fn(&mut Balance<Bn256>)>(
-    tree: &mut CircuitAccountTree,
+    tree: &mut dyn WitnessAccountTree,
     account_address: u32,
     token: u32,
     fa: Fa,
@@ -382,7 +381,7 @@ pub fn apply_leaf_operation<Fa: Fn(&mut CircuitAccount<Bn256>), Fb: Fn(&mut Bala
 }
 
 pub fn apply_fee(
-    tree: &mut CircuitAccountTree,
+    tree: &mut dyn WitnessAccountTree,
     validator_address: u32,
     token: u32,
     fee: u128,
@@ -576,28 +575,24 @@ impl SigDataInput {
 }
 
 /// Get root hash of the used subtree.
-pub fn get_used_subtree_root_hash(account_tree: &CircuitAccountTree) -> Fr {
+pub fn get_used_subtree_root_hash(account_tree: &dyn WitnessAccountTree) -> Fr {
     // We take account 0, and hash it with it's Merkle proof.
     let account_index = 0;
     let account_merkle_path = account_tree.merkle_path(account_index);
-    let account = account_tree
-        .get(account_index)
-        .cloned()
-        .unwrap_or_else(CircuitAccount::default);
-    let mut current_hash = account_tree.hasher.hash_bits(account.get_bits_le());
+    let account = account_tree.get(account_index).unwrap_or_default();
+    let hasher = RescueHasher::<Bn256>::default();
+    let mut current_hash = hasher.hash_bits(account.get_bits_le());
     for merkle_path_item in account_merkle_path
         .iter()
         .take(used_account_subtree_depth())
     {
-        current_hash = account_tree
-            .hasher
-            .compress(&current_hash, &merkle_path_item.0, 0);
+        current_hash = hasher.compress(&current_hash, &merkle_path_item.0, 0);
     }
     current_hash
 }
 
 pub fn build_block_witness<'a>(
-    account_tree: &'a mut CircuitAccountTree,
+    account_tree: &'a mut dyn WitnessAccountTree,
     block: &Block,
 ) -> Result<WitnessBuilder<'a>, anyhow::Error> {
     let block_number = block.block_number;
@@ -10,7 +10,7 @@ use zksync_crypto::franklin_crypto::{
 // Workspace deps
 use zksync_crypto::{
     circuit::{
-        account::CircuitAccountTree,
+        account::WitnessAccountTree,
         utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
     },
     params::{
@@ -53,7 +53,7 @@ impl Witness for WithdrawWitness<Bn256> {
     type OperationType = WithdrawOp;
     type CalculateOpsInput = SigDataInput;
 
-    fn apply_tx(tree: &mut CircuitAccountTree, withdraw: &WithdrawOp) -> Self {
+    fn apply_tx(tree: &mut dyn WitnessAccountTree, withdraw: &WithdrawOp) -> Self {
         let withdraw_data = WithdrawData {
             amount: withdraw.tx.amount.to_u128().unwrap(),
             fee: withdraw.tx.fee.to_u128().unwrap(),
@@ -185,7 +185,7 @@ impl<E: RescueEngine> WithdrawWitness<E> {
 }
 
 impl WithdrawWitness<Bn256> {
-    fn apply_data(tree: &mut CircuitAccountTree, withdraw: &WithdrawData) -> Self {
+    fn apply_data(tree: &mut dyn WitnessAccountTree, withdraw: &WithdrawData) -> Self {
         //preparing data and base witness
         let before_root = tree.root_hash();
         vlog::debug!("Initial root = {}", before_root);
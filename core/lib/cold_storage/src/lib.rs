@@ -0,0 +1,209 @@
+//! Cold storage for the proofs and witnesses of the verified blocks.
+//!
+//! Proofs and witnesses take up most of the database, while each of them is used only
+//! once: to prove and verify the block. Once the block is verified for long enough,
+//! its blobs are moved to the object store, and the database keeps only their keys.
+//! Archived blobs can still be loaded on demand (e.g. for data restore or audits)
+//! through the same interface that is used for the blobs stored in the database.
+
+// Built-in uses
+use std::{sync::Arc, time::Instant};
+// External uses
+use tokio::{task::JoinHandle, time};
+// Workspace uses
+use zksync_config::ColdStorageConfig;
+use zksync_crypto::proof::EncodedProofPlonk;
+use zksync_storage::{prover::ColdStorageBlob, ConnectionPool, StorageProcessor};
+use zksync_types::BlockNumber;
+// Local uses
+pub use self::object_store::{FileObjectStore, ObjectStore};
+
+mod object_store;
+
+/// Returns the key of the blob in the object store.
+fn object_key(blob: ColdStorageBlob, block: BlockNumber) -> String {
+    match blob {
+        ColdStorageBlob::Proof => format!("proofs/{}.json", *block),
+        ColdStorageBlob::Witness => format!("witnesses/{}.json", *block),
+    }
+}
+
+/// Provides access to the proofs and witnesses regardless of whether they
+/// are stored in the database or were moved to the object store.
+#[derive(Debug, Clone)]
+pub struct ColdStorage {
+    store: Arc<dyn ObjectStore>,
+}
+
+impl ColdStorage {
+    pub fn new(store: Arc<dyn ObjectStore>) -> Self {
+        Self { store }
+    }
+
+    pub fn from_config(config: &ColdStorageConfig) -> Self {
+        Self::new(Arc::new(FileObjectStore::new(&config.path)))
+    }
+
+    /// Loads the proof for the block either from the database or from the object store.
+    pub async fn load_proof(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<EncodedProofPlonk>> {
+        if let Some(proof) = storage.prover_schema().load_proof(block).await? {
+            return Ok(Some(proof));
+        }
+
+        match self
+            .load_archived(storage, ColdStorageBlob::Proof, block)
+            .await?
+        {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Loads the witness for the block either from the database or from the object store.
+    pub async fn load_witness(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<serde_json::Value>> {
+        if let Some(witness) = storage.prover_schema().get_witness(block).await? {
+            return Ok(Some(witness));
+        }
+
+        match self
+            .load_archived(storage, ColdStorageBlob::Witness, block)
+            .await?
+        {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Loads the archived proof for the block from the object store without looking up its key
+    /// in the database, so the proofs can be restored after the database is lost (e.g. by
+    /// data restore).
+    pub async fn restore_proof(
+        &self,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<EncodedProofPlonk>> {
+        let key = object_key(ColdStorageBlob::Proof, block);
+        match self.store.get(&key).await? {
+            Some(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_archived(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        blob: ColdStorageBlob,
+        block: BlockNumber,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let key = match storage
+            .prover_schema()
+            .cold_storage_key(blob, block)
+            .await?
+        {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let start = Instant::now();
+        let data = self.store.get(&key).await?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} for block {} is missing in the object store",
+                blob.as_str(),
+                *block
+            )
+        })?;
+
+        metrics::histogram!("cold_storage.load", start.elapsed());
+        Ok(Some(data))
+    }
+
+    /// Moves the blobs of the blocks verified more than `config.archive_age` ago
+    /// to the object store. Returns the amount of the moved blobs.
+    pub async fn archive(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        config: &ColdStorageConfig,
+    ) -> anyhow::Result<usize> {
+        let mut archived = 0;
+        for &blob in &[ColdStorageBlob::Proof, ColdStorageBlob::Witness] {
+            let blocks = storage
+                .prover_schema()
+                .blocks_to_archive(blob, config.archive_age(), config.batch_size)
+                .await?;
+
+            for block in blocks {
+                let data = match blob {
+                    ColdStorageBlob::Proof => storage
+                        .prover_schema()
+                        .load_proof(block)
+                        .await?
+                        .map(|proof| serde_json::to_vec(&proof)),
+                    ColdStorageBlob::Witness => storage
+                        .prover_schema()
+                        .get_witness(block)
+                        .await?
+                        .map(|witness| serde_json::to_vec(&witness)),
+                };
+                // The blob may be archived concurrently.
+                let data = match data {
+                    Some(data) => data?,
+                    None => continue,
+                };
+
+                // The blob is removed from the database only after it was
+                // successfully uploaded, so an interrupted round is simply repeated.
+                let key = object_key(blob, block);
+                self.store.put(&key, data).await?;
+                storage
+                    .prover_schema()
+                    .archive_blob(blob, block, &key)
+                    .await?;
+                archived += 1;
+            }
+        }
+
+        metrics::counter!("cold_storage.archived", archived as u64);
+        Ok(archived)
+    }
+}
+
+/// Runs the task periodically moving the proofs and witnesses of the verified blocks
+/// to the object store. Errors are reported to the log and the round is repeated later.
+pub fn run_cold_storage_archiver(
+    connection_pool: ConnectionPool,
+    config: ColdStorageConfig,
+) -> JoinHandle<()> {
+    let cold_storage = ColdStorage::from_config(&config);
+
+    tokio::spawn(async move {
+        let mut timer = time::interval(config.archive_interval());
+        loop {
+            timer.tick().await;
+
+            let mut storage = match connection_pool.access_storage().await {
+                Ok(storage) => storage,
+                Err(err) => {
+                    vlog::warn!("Unable to access the storage: {}", err);
+                    continue;
+                }
+            };
+
+            match cold_storage.archive(&mut storage, &config).await {
+                Ok(0) => {}
+                Ok(archived) => {
+                    vlog::info!("Moved {} blobs to the cold storage", archived);
+                }
+                Err(err) => {
+                    vlog::warn!("Failed to move blobs to the cold storage: {}", err);
+                }
+            }
+        }
+    })
+}
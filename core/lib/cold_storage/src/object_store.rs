@@ -0,0 +1,93 @@
+// Built-in uses
+use std::{io::ErrorKind, path::PathBuf};
+// External uses
+use async_trait::async_trait;
+use tokio::{fs, io::AsyncWriteExt};
+
+/// Storage for the blobs addressed by their keys.
+#[async_trait]
+pub trait ObjectStore: std::fmt::Debug + Send + Sync {
+    /// Stores the blob under the given key, overwriting the existing one.
+    async fn put(&self, key: &str, data: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Loads the blob stored under the given key, returns `None` if there is no such blob.
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+}
+
+/// Object store keeping blobs as files in the local directory.
+///
+/// Any object store that can be mounted into the file system (e.g. a network
+/// file system or a bucket mounted via FUSE) can be used through it.
+#[derive(Debug, Clone)]
+pub struct FileObjectStore {
+    root: PathBuf,
+}
+
+impl FileObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for FileObjectStore {
+    async fn put(&self, key: &str, data: Vec<u8>) -> anyhow::Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // Write to the temporary file first, so an interrupted upload never
+        // leaves a partially written blob under the actual key.
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(&data).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&tmp_path, &path).await?;
+
+        // The blob is removed from the database once it's stored, so the rename
+        // must be persisted as well.
+        if let Some(parent) = path.parent() {
+            fs::File::open(parent).await?.sync_all().await?;
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)).await {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn file_object_store() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = FileObjectStore::new(dir.path());
+
+        assert_eq!(store.get("proofs/1.json").await?, None);
+
+        store.put("proofs/1.json", b"proof".to_vec()).await?;
+        assert_eq!(store.get("proofs/1.json").await?, Some(b"proof".to_vec()));
+
+        // Blobs are overwritten.
+        store.put("proofs/1.json", b"new proof".to_vec()).await?;
+        assert_eq!(
+            store.get("proofs/1.json").await?,
+            Some(b"new proof".to_vec())
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,14 @@
 /// External uses
 use serde::Deserialize;
 /// Built-in uses
-use std::net::SocketAddr;
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+// Workspace uses
+use zksync_types::{tx::EthSignMessageTemplate, Address, H256};
 // Local uses
 use crate::envy_load;
 
@@ -22,6 +29,20 @@ pub struct ApiConfig {
     pub prover: ProverApi,
     /// Configuration options for the Prometheus exporter.
     pub prometheus: Prometheus,
+    /// Configuration options for the Ethereum sign message of the transactions.
+    pub eth_sign_message: EthSignMessage,
+    /// Configuration options for the rate limiting of the public API servers.
+    pub rate_limit: RateLimit,
+    /// Configuration options for the authentication of the trusted integrators.
+    pub auth: ApiAuth,
+    /// Configuration options for the TLS termination of the public API servers.
+    pub tls: Tls,
+    /// Configuration options for the health checks of the API server.
+    pub health: Health,
+    /// Configuration options for the ENS names resolution.
+    pub ens: Ens,
+    /// Configuration options for the co-signing of the transactions.
+    pub cosigner: Cosigner,
 }
 
 impl ApiConfig {
@@ -34,6 +55,13 @@ impl ApiConfig {
             private: envy_load!("private", "API_PRIVATE_"),
             prover: envy_load!("prover", "API_PROVER_"),
             prometheus: envy_load!("prometheus", "API_PROMETHEUS_"),
+            eth_sign_message: envy_load!("eth_sign_message", "API_ETH_SIGN_MESSAGE_"),
+            rate_limit: envy_load!("rate_limit", "API_RATE_LIMIT_"),
+            auth: envy_load!("auth", "API_AUTH_"),
+            tls: envy_load!("tls", "API_TLS_"),
+            health: envy_load!("health", "API_HEALTH_"),
+            ens: envy_load!("ens", "API_ENS_"),
+            cosigner: envy_load!("cosigner", "API_COSIGNER_"),
         }
     }
 }
@@ -47,6 +75,18 @@ pub struct Common {
     // Type of value is seconds.
     pub forced_exit_minimum_account_age_secs: u64,
     pub enforce_pubkey_change_fee: bool,
+    // Private key used to sign the soft confirmations of the transactions.
+    // If not set, the soft confirmations are not provided.
+    pub soft_confirmation_private_key: Option<H256>,
+    // Whether the batches are simulated against the pending state before being accepted,
+    // so the batches that are going to fail are rejected right away.
+    pub simulate_txs_batches: bool,
+    // Capacity of the cache of the transactions with the verified signatures, so the signatures
+    // of the resubmitted transactions are not checked again.
+    pub signature_cache_size: usize,
+    // Number of threads verifying the signatures of the transactions, including the transactions
+    // of the batches which are verified concurrently. Zero means the number of CPUs.
+    pub signature_checker_threads: usize,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -85,13 +125,45 @@ impl ProverApi {
 pub struct PrivateApi {
     /// Port to which the API server is listening.
     pub port: u16,
+    /// Address of the network interface the API server is listening on.
+    /// Must be an internal interface, since the API is not intended to be publicly available.
+    pub bind_ip: IpAddr,
     /// URL to access API server.
     pub url: String,
+    /// Secret used to sign the requests to the API server.
+    pub secret_auth: String,
+    /// Timeout of the requests to the private API in milliseconds.
+    pub client_timeout_ms: u64,
+    /// Maximum number of attempts for the idempotent requests to the private API.
+    pub client_request_attempts: u32,
+    /// Number of the consecutive failed requests after which the private API is considered
+    /// unavailable and the requests to it are rejected without being sent.
+    pub client_circuit_breaker_threshold: u32,
+    /// Time in seconds after which the requests to the unavailable private API are sent again.
+    pub client_circuit_breaker_cooldown_secs: u64,
+    /// Port of the gRPC transport of the private API. If not set, the gRPC server is not started.
+    pub grpc_port: Option<u16>,
+    /// URL to access the gRPC transport of the private API. If set, the API server sends
+    /// the transactions to the Core through it instead of the HTTP API.
+    pub grpc_url: Option<String>,
 }
 
 impl PrivateApi {
     pub fn bind_addr(&self) -> SocketAddr {
-        SocketAddr::new("0.0.0.0".parse().unwrap(), self.port)
+        SocketAddr::new(self.bind_ip, self.port)
+    }
+
+    pub fn grpc_bind_addr(&self) -> Option<SocketAddr> {
+        self.grpc_port
+            .map(|port| SocketAddr::new(self.bind_ip, port))
+    }
+
+    pub fn client_timeout(&self) -> Duration {
+        Duration::from_millis(self.client_timeout_ms)
+    }
+
+    pub fn client_circuit_breaker_cooldown(&self) -> Duration {
+        Duration::from_secs(self.client_circuit_breaker_cooldown_secs)
     }
 }
 
@@ -119,6 +191,8 @@ pub struct JsonRpc {
     pub ws_port: u16,
     /// URL to access WebSocket RPC server.
     pub ws_url: String,
+    /// Maximum number of calls in the batch request.
+    pub max_batch_size: usize,
 }
 
 impl JsonRpc {
@@ -135,13 +209,262 @@ impl JsonRpc {
 pub struct Prometheus {
     /// Port to which the Prometheus exporter server is listening.
     pub port: u16,
+    /// Bucket boundaries of the histograms. Durations are recorded in nanoseconds.
+    /// If not set, histograms are exported as summaries with the default quantiles.
+    pub histogram_buckets: Option<Vec<u64>>,
+    /// Value of the `instance` label added to all the metrics.
+    pub instance: Option<String>,
+    /// Value of the `environment` label added to all the metrics.
+    pub environment: Option<String>,
+    /// Value of the `network` label added to all the metrics.
+    pub network: Option<String>,
+}
+
+/// Template of the message signed by the Ethereum key of the account for `Transfer`
+/// and `Withdraw` transactions.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EthSignMessage {
+    /// Version of the template used for the new transactions.
+    pub version: u32,
+    /// Template of the message, see `EthSignMessageTemplate` for the supported placeholders.
+    /// Since the configuration is passed through the environment, line breaks are written as `\n`.
+    pub template: String,
+    /// Network name substituted into the `{network}` placeholder.
+    pub network_name: Option<String>,
+    /// Version of the previous template, accepted along with the current one
+    /// while the clients migrate to the new format.
+    pub legacy_version: Option<u32>,
+    /// Previous template, accepted along with the current one while the clients migrate to the new format.
+    pub legacy_template: Option<String>,
+    /// Symbols the renamed tokens may be referred by in the signed messages instead of their
+    /// actual symbols.
+    pub legacy_token_symbols: Option<Vec<LegacyTokenSymbol>>,
+}
+
+impl EthSignMessage {
+    fn parse_template(
+        version: u32,
+        template: &str,
+        network: Option<String>,
+    ) -> EthSignMessageTemplate {
+        EthSignMessageTemplate::new(version, template.replace("\\n", "\n"), network)
+    }
+
+    /// Returns the accepted templates, the current one goes first.
+    pub fn templates(&self) -> Vec<EthSignMessageTemplate> {
+        let mut templates = vec![Self::parse_template(
+            self.version,
+            &self.template,
+            self.network_name.clone(),
+        )];
+        if let (Some(version), Some(template)) = (self.legacy_version, &self.legacy_template) {
+            templates.push(Self::parse_template(
+                version,
+                template,
+                self.network_name.clone(),
+            ));
+        }
+        templates
+    }
+
+    /// Returns the legacy symbols of the tokens keyed by the actual symbols.
+    pub fn legacy_token_symbols(&self) -> HashMap<String, String> {
+        self.legacy_token_symbols
+            .iter()
+            .flatten()
+            .map(|symbol| (symbol.symbol.clone(), symbol.legacy_symbol.clone()))
+            .collect()
+    }
+}
+
+/// Symbol of the renamed token accepted in the signed messages along with its actual symbol.
+///
+/// Parsed from the string of the `<symbol>:<legacy symbol>` format.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "String")]
+pub struct LegacyTokenSymbol {
+    /// Actual symbol of the token.
+    pub symbol: String,
+    /// Symbol the token may be referred by in the signed messages.
+    pub legacy_symbol: String,
+}
+
+impl TryFrom<String> for LegacyTokenSymbol {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.split(':').collect::<Vec<_>>().as_slice() {
+            [symbol, legacy_symbol] if !symbol.is_empty() && !legacy_symbol.is_empty() => {
+                Ok(Self {
+                    symbol: symbol.to_string(),
+                    legacy_symbol: legacy_symbol.to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Legacy token symbol should be in the `<symbol>:<legacy symbol>` format, got {}",
+                value
+            )),
+        }
+    }
+}
+
+/// Limits of the requests rate of the public API servers. Requests are counted with the
+/// token buckets: each bucket holds up to `burst` requests and is refilled at the rate of
+/// `rps` requests per second. Zero `rps` disables the corresponding limit.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RateLimit {
+    /// Rate of the requests from a single IP address, applied to the REST and HTTP JSON RPC APIs
+    /// and to the WebSocket handshakes.
+    pub per_ip_rps: u32,
+    /// Number of the requests from a single IP address allowed in a burst.
+    pub per_ip_burst: u32,
+    /// Rate of the messages of a single WebSocket session.
+    pub per_ws_session_rps: u32,
+    /// Number of the messages of a single WebSocket session allowed in a burst.
+    pub per_ws_session_burst: u32,
+    /// Rate of the transactions submitted by a single account, applied to all the APIs.
+    /// Every transaction of the batch is counted.
+    pub per_address_rps: u32,
+    /// Number of the transactions submitted by a single account allowed in a burst.
+    pub per_address_burst: u32,
+    /// Whether the IP address of the client is taken from the `X-Forwarded-For` header set by
+    /// the proxy. Must only be enabled if the API servers are reachable through the trusted proxy only.
+    pub use_forwarded_ip: bool,
+}
+
+/// API key of the trusted integrator allowed to use the privileged submission options
+/// (e.g. the fast processing of withdrawals).
+///
+/// Parsed from the string of the `<name>:<key>:<hourly quota>` format.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "String")]
+pub struct ApiKey {
+    /// Name of the integrator, used in the logs and metrics instead of the key itself.
+    pub name: String,
+    /// Secret key sent by the integrator in the requests.
+    pub key: String,
+    /// Maximum number of the privileged requests per hour, zero means no limit.
+    pub hourly_quota: u32,
+}
+
+impl TryFrom<String> for ApiKey {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parts: Vec<_> = value.split(':').collect();
+        if parts.len() != 3 || parts[0].is_empty() || parts[1].is_empty() {
+            // The value is not included into the message, since it contains the secret key.
+            return Err(
+                "API key should be in the `<name>:<key>:<hourly quota>` format".to_string(),
+            );
+        }
+
+        let hourly_quota = parts[2]
+            .parse()
+            .map_err(|err| format!("Invalid quota of the API key {}: {}", parts[0], err))?;
+        Ok(Self {
+            name: parts[0].to_string(),
+            key: parts[1].to_string(),
+            hourly_quota,
+        })
+    }
+}
+
+/// Authentication of the trusted integrators by the API keys.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ApiAuth {
+    /// Whether the privileged submission options require an API key.
+    /// If disabled, the options are available to every client.
+    pub require_api_key: bool,
+    /// API keys of the trusted integrators.
+    pub api_keys: Option<Vec<ApiKey>>,
+}
+
+impl ApiAuth {
+    /// Returns the configured API keys.
+    pub fn api_keys(&self) -> &[ApiKey] {
+        self.api_keys.as_deref().unwrap_or_default()
+    }
+}
+
+/// TLS termination of the REST and JSON RPC (HTTP and WebSocket) API servers, for the deployments
+/// without a reverse proxy. If the certificate is not set, the servers accept plain connections.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Tls {
+    /// Path to the PEM file with the server certificate chain.
+    pub cert_path: Option<String>,
+    /// Path to the PEM file with the private key of the server certificate.
+    pub key_path: Option<String>,
+    /// Path to the PEM file with the CA certificates. If set, the clients must present
+    /// a certificate signed by one of them.
+    pub client_ca_path: Option<String>,
+}
+
+impl Tls {
+    /// Returns `true` if the servers accept TLS connections only.
+    pub fn enabled(&self) -> bool {
+        self.cert_path.is_some()
+    }
+}
+
+/// Health checks of the API server, reported by the `/healthz` and `/readyz` endpoints.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Health {
+    /// Time after which a dependency that didn't respond is considered unavailable.
+    pub check_timeout_ms: u64,
+    /// Maximum number of the committed blocks not yet confirmed on Ethereum
+    /// before the `eth_sender` is reported as lagging.
+    pub max_eth_sender_lag_blocks: u32,
+}
+
+impl Health {
+    pub fn check_timeout(&self) -> Duration {
+        Duration::from_millis(self.check_timeout_ms)
+    }
+}
+
+/// Resolution of the ENS names accepted by the API instead of the addresses.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Ens {
+    /// Address of the ENS registry contract. If not set, ENS names are not accepted.
+    pub registry_addr: Option<Address>,
+    /// Time in seconds the resolved names are cached for.
+    pub cache_ttl_secs: u64,
+    /// Timeout of the name resolution with the Ethereum node in ms.
+    pub request_timeout_ms: u64,
+}
+
+impl Ens {
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.cache_ttl_secs)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
+/// Co-signing of the transactions of the accounts opted in to the 2-Factor authentication
+/// with a co-signer key.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Cosigner {
+    /// URL of the co-signing service. If not set, co-signers can't be registered and
+    /// the co-signatures are not checked.
+    pub service_url: Option<String>,
+    /// Timeout of the co-signing request in ms.
+    pub request_timeout_ms: u64,
+}
+
+impl Cosigner {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configs::test_utils::set_env;
-    use std::net::IpAddr;
+    use crate::configs::test_utils::{addr, hash, set_env};
 
     fn expected_config() -> ApiConfig {
         ApiConfig {
@@ -149,6 +472,12 @@ mod tests {
                 caches_size: 10_000,
                 forced_exit_minimum_account_age_secs: 0,
                 enforce_pubkey_change_fee: true,
+                soft_confirmation_private_key: Some(hash(
+                    "9a8f35215f64fc057a243b2543e3a0a216428fadcc9c8457e6eb2983b899d50a",
+                )),
+                simulate_txs_batches: true,
+                signature_cache_size: 100_000,
+                signature_checker_threads: 4,
             },
             admin: AdminApi {
                 port: 8080,
@@ -164,17 +493,85 @@ mod tests {
                 http_url: "http://127.0.0.1:3030".into(),
                 ws_port: 3031,
                 ws_url: "ws://127.0.0.1:3031".into(),
+                max_batch_size: 100,
             },
             private: PrivateApi {
                 port: 8090,
+                bind_ip: "127.0.0.1".parse().unwrap(),
                 url: "http://127.0.0.1:8090".into(),
+                secret_auth: "sample".into(),
+                client_timeout_ms: 10_000,
+                client_request_attempts: 3,
+                client_circuit_breaker_threshold: 5,
+                client_circuit_breaker_cooldown_secs: 10,
+                grpc_port: Some(8091),
+                grpc_url: Some("http://127.0.0.1:8091".into()),
             },
             prover: ProverApi {
                 port: 8088,
                 url: "http://127.0.0.1:8088".into(),
                 secret_auth: "sample".into(),
             },
-            prometheus: Prometheus { port: 3312 },
+            prometheus: Prometheus {
+                port: 3312,
+                histogram_buckets: Some(vec![1_000_000, 10_000_000, 100_000_000, 1_000_000_000]),
+                instance: Some("api-1".into()),
+                environment: Some("staging".into()),
+                network: None,
+            },
+            eth_sign_message: EthSignMessage {
+                version: 2,
+                template: "{tx_type} {amount} {token}\\nNetwork: {network}".into(),
+                network_name: Some("mainnet".into()),
+                legacy_version: Some(1),
+                legacy_template: Some("{tx_type} {amount} {token}".into()),
+                legacy_token_symbols: Some(vec![LegacyTokenSymbol {
+                    symbol: "GNT".into(),
+                    legacy_symbol: "tGLM".into(),
+                }]),
+            },
+            rate_limit: RateLimit {
+                per_ip_rps: 50,
+                per_ip_burst: 100,
+                per_ws_session_rps: 10,
+                per_ws_session_burst: 30,
+                per_address_rps: 5,
+                per_address_burst: 20,
+                use_forwarded_ip: true,
+            },
+            auth: ApiAuth {
+                require_api_key: true,
+                api_keys: Some(vec![
+                    ApiKey {
+                        name: "wallet".into(),
+                        key: "sample-key".into(),
+                        hourly_quota: 100,
+                    },
+                    ApiKey {
+                        name: "exchange".into(),
+                        key: "other-key".into(),
+                        hourly_quota: 0,
+                    },
+                ]),
+            },
+            tls: Tls {
+                cert_path: Some("/etc/tls/api.crt".into()),
+                key_path: Some("/etc/tls/api.key".into()),
+                client_ca_path: None,
+            },
+            health: Health {
+                check_timeout_ms: 2_000,
+                max_eth_sender_lag_blocks: 10,
+            },
+            ens: Ens {
+                registry_addr: Some(addr("00000000000C2E074eC69A0dFb2997BA6C7d2e1e")),
+                cache_ttl_secs: 300,
+                request_timeout_ms: 3_000,
+            },
+            cosigner: Cosigner {
+                service_url: Some("http://127.0.0.1:8095".into()),
+                request_timeout_ms: 5_000,
+            },
         }
     }
 
@@ -184,6 +581,10 @@ mod tests {
 API_COMMON_CACHES_SIZE="10000"
 API_COMMON_FORCED_EXIT_MINIMUM_ACCOUNT_AGE_SECS="0"
 API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
+API_COMMON_SOFT_CONFIRMATION_PRIVATE_KEY="0x9a8f35215f64fc057a243b2543e3a0a216428fadcc9c8457e6eb2983b899d50a"
+API_COMMON_SIMULATE_TXS_BATCHES=true
+API_COMMON_SIGNATURE_CACHE_SIZE="100000"
+API_COMMON_SIGNATURE_CHECKER_THREADS="4"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
@@ -193,12 +594,48 @@ API_JSON_RPC_HTTP_PORT="3030"
 API_JSON_RPC_HTTP_URL="http://127.0.0.1:3030"
 API_JSON_RPC_WS_PORT="3031"
 API_JSON_RPC_WS_URL="ws://127.0.0.1:3031"
+API_JSON_RPC_MAX_BATCH_SIZE="100"
 API_PRIVATE_PORT="8090"
+API_PRIVATE_BIND_IP="127.0.0.1"
 API_PRIVATE_URL="http://127.0.0.1:8090"
+API_PRIVATE_SECRET_AUTH="sample"
+API_PRIVATE_CLIENT_TIMEOUT_MS="10000"
+API_PRIVATE_CLIENT_REQUEST_ATTEMPTS="3"
+API_PRIVATE_CLIENT_CIRCUIT_BREAKER_THRESHOLD="5"
+API_PRIVATE_CLIENT_CIRCUIT_BREAKER_COOLDOWN_SECS="10"
+API_PRIVATE_GRPC_PORT="8091"
+API_PRIVATE_GRPC_URL="http://127.0.0.1:8091"
 API_PROVER_PORT="8088"
 API_PROVER_URL="http://127.0.0.1:8088"
 API_PROVER_SECRET_AUTH="sample"
 API_PROMETHEUS_PORT="3312"
+API_PROMETHEUS_HISTOGRAM_BUCKETS="1000000,10000000,100000000,1000000000"
+API_PROMETHEUS_INSTANCE="api-1"
+API_PROMETHEUS_ENVIRONMENT="staging"
+API_ETH_SIGN_MESSAGE_VERSION="2"
+API_ETH_SIGN_MESSAGE_TEMPLATE="{tx_type} {amount} {token}\nNetwork: {network}"
+API_ETH_SIGN_MESSAGE_NETWORK_NAME="mainnet"
+API_ETH_SIGN_MESSAGE_LEGACY_VERSION="1"
+API_ETH_SIGN_MESSAGE_LEGACY_TEMPLATE="{tx_type} {amount} {token}"
+API_ETH_SIGN_MESSAGE_LEGACY_TOKEN_SYMBOLS="GNT:tGLM"
+API_RATE_LIMIT_PER_IP_RPS="50"
+API_RATE_LIMIT_PER_IP_BURST="100"
+API_RATE_LIMIT_PER_WS_SESSION_RPS="10"
+API_RATE_LIMIT_PER_WS_SESSION_BURST="30"
+API_RATE_LIMIT_PER_ADDRESS_RPS="5"
+API_RATE_LIMIT_PER_ADDRESS_BURST="20"
+API_RATE_LIMIT_USE_FORWARDED_IP="true"
+API_AUTH_REQUIRE_API_KEY="true"
+API_AUTH_API_KEYS="wallet:sample-key:100,exchange:other-key:0"
+API_TLS_CERT_PATH="/etc/tls/api.crt"
+API_TLS_KEY_PATH="/etc/tls/api.key"
+API_HEALTH_CHECK_TIMEOUT_MS="2000"
+API_HEALTH_MAX_ETH_SENDER_LAG_BLOCKS="10"
+API_ENS_REGISTRY_ADDR="0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e"
+API_ENS_CACHE_TTL_SECS="300"
+API_ENS_REQUEST_TIMEOUT_MS="3000"
+API_COSIGNER_SERVICE_URL="http://127.0.0.1:8095"
+API_COSIGNER_REQUEST_TIMEOUT_MS="5000"
         "#;
         set_env(config);
 
@@ -226,11 +663,68 @@ API_PROMETHEUS_PORT="3312"
         );
         assert_eq!(
             config.private.bind_addr(),
-            SocketAddr::new(bind_broadcast_addr, config.private.port)
+            SocketAddr::new("127.0.0.1".parse().unwrap(), config.private.port)
+        );
+        assert_eq!(
+            config.private.grpc_bind_addr(),
+            Some(SocketAddr::new("127.0.0.1".parse().unwrap(), 8091))
+        );
+        assert_eq!(config.private.client_timeout(), Duration::from_secs(10));
+        assert_eq!(config.ens.cache_ttl(), Duration::from_secs(300));
+        assert_eq!(config.cosigner.request_timeout(), Duration::from_secs(5));
+        assert_eq!(
+            config.private.client_circuit_breaker_cooldown(),
+            Duration::from_secs(10)
         );
         assert_eq!(
             config.json_rpc.http_bind_addr(),
             SocketAddr::new(bind_broadcast_addr, config.json_rpc.http_port)
         );
+        assert_eq!(
+            config.eth_sign_message.templates(),
+            vec![
+                EthSignMessageTemplate::new(
+                    2,
+                    "{tx_type} {amount} {token}\nNetwork: {network}",
+                    Some("mainnet".into())
+                ),
+                EthSignMessageTemplate::new(
+                    1,
+                    "{tx_type} {amount} {token}",
+                    Some("mainnet".into())
+                ),
+            ]
+        );
+        assert_eq!(
+            config.eth_sign_message.legacy_token_symbols()["GNT"],
+            "tGLM"
+        );
+        assert_eq!(config.auth.api_keys().len(), 2);
+        assert!(config.tls.enabled());
+        assert_eq!(config.health.check_timeout(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn api_key_parsing() {
+        assert!(ApiKey::try_from("wallet:key".to_string()).is_err());
+        assert!(ApiKey::try_from(":key:10".to_string()).is_err());
+        assert!(ApiKey::try_from("wallet:key:-1".to_string()).is_err());
+
+        let auth = ApiAuth {
+            require_api_key: false,
+            api_keys: None,
+        };
+        assert!(auth.api_keys().is_empty());
+    }
+
+    #[test]
+    fn legacy_token_symbol_parsing() {
+        assert!(LegacyTokenSymbol::try_from("GNT".to_string()).is_err());
+        assert!(LegacyTokenSymbol::try_from(":tGLM".to_string()).is_err());
+        assert!(LegacyTokenSymbol::try_from("GNT:tGLM:GLM".to_string()).is_err());
+
+        let symbol = LegacyTokenSymbol::try_from("GNT:tGLM".to_string()).unwrap();
+        assert_eq!(symbol.symbol, "GNT");
+        assert_eq!(symbol.legacy_symbol, "tGLM");
     }
 }
@@ -0,0 +1,121 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the background migrations backfilling the data of the online
+/// schema migrations while the server is running.
+///
+/// Variables which are not set take the default values, so the server which isn't configured
+/// yet still completes the backfill required by the following releases.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct BackgroundMigrationsConfig {
+    /// Whether the background migrations should be run by the server.
+    pub enabled: bool,
+    /// Maximum amount of rows processed in one batch.
+    pub batch_size: u32,
+    /// Minimum amount of rows processed in one batch under the load.
+    pub min_batch_size: u32,
+    /// Batches taking longer than this are considered a sign of the database load,
+    /// so the following batches are made smaller, in ms.
+    pub target_batch_duration: u64,
+    /// Minimum interval between the batches in ms.
+    pub batch_interval: u64,
+}
+
+impl Default for BackgroundMigrationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            batch_size: 10_000,
+            min_batch_size: 100,
+            target_batch_duration: 1000,
+            batch_interval: 100,
+        }
+    }
+}
+
+impl BackgroundMigrationsConfig {
+    pub fn from_env() -> Self {
+        envy_load!("background_migrations", "BACKGROUND_MIGRATIONS_")
+    }
+
+    /// Converts `self.target_batch_duration` into `Duration`.
+    pub fn target_batch_duration(&self) -> Duration {
+        Duration::from_millis(self.target_batch_duration)
+    }
+
+    /// Converts `self.batch_interval` into `Duration`.
+    pub fn batch_interval(&self) -> Duration {
+        Duration::from_millis(self.batch_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> BackgroundMigrationsConfig {
+        BackgroundMigrationsConfig {
+            enabled: true,
+            batch_size: 10_000,
+            min_batch_size: 100,
+            target_batch_duration: 1000,
+            batch_interval: 100,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+BACKGROUND_MIGRATIONS_ENABLED="true"
+BACKGROUND_MIGRATIONS_BATCH_SIZE="10000"
+BACKGROUND_MIGRATIONS_MIN_BATCH_SIZE="100"
+BACKGROUND_MIGRATIONS_TARGET_BATCH_DURATION="1000"
+BACKGROUND_MIGRATIONS_BATCH_INTERVAL="100"
+        "#;
+        set_env(config);
+
+        let actual = BackgroundMigrationsConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks that the variables which are not set take the default values.
+    #[test]
+    fn from_env_defaults() {
+        let config = r#"
+BACKGROUND_MIGRATIONS_DEFAULTS_BATCH_SIZE="500"
+        "#;
+        set_env(config);
+
+        let actual: BackgroundMigrationsConfig = envy::prefixed("BACKGROUND_MIGRATIONS_DEFAULTS_")
+            .from_env()
+            .unwrap();
+        assert_eq!(
+            actual,
+            BackgroundMigrationsConfig {
+                batch_size: 500,
+                ..BackgroundMigrationsConfig::default()
+            }
+        );
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.target_batch_duration(),
+            Duration::from_millis(config.target_batch_duration)
+        );
+        assert_eq!(
+            config.batch_interval(),
+            Duration::from_millis(config.batch_interval)
+        );
+    }
+}
@@ -3,8 +3,7 @@ use serde::Deserialize;
 /// Built-in uses
 use std::time::Duration;
 // Local uses
-use zksync_types::network::Network;
-use zksync_types::Address;
+use zksync_types::{network::Network, tx::TxFailureCode, Address};
 
 use crate::envy_load;
 
@@ -16,6 +15,8 @@ pub struct ChainConfig {
     pub eth: Eth,
     /// State keeper / block generating configuration.
     pub state_keeper: StateKeeper,
+    /// Mempool configuration.
+    pub mempool: Mempool,
 }
 
 impl ChainConfig {
@@ -24,8 +25,33 @@ impl ChainConfig {
             circuit: envy_load!("circuit", "CHAIN_CIRCUIT_"),
             eth: envy_load!("eth", "CHAIN_ETH_"),
             state_keeper: envy_load!("state_keeper", "CHAIN_STATE_KEEPER_"),
+            mempool: envy_load!("mempool", "CHAIN_MEMPOOL_"),
         }
     }
+
+    /// Returns the block sizes that can be produced by the server, in ascending order.
+    ///
+    /// If `state_keeper.auto_block_sizes` is set, these are all the sizes supported by the circuit
+    /// that don't exceed the largest configured block size, so every block gets the smallest circuit
+    /// it fits into. Otherwise it's just `state_keeper.block_chunk_sizes`.
+    pub fn block_chunk_sizes(&self) -> Vec<usize> {
+        if !self.state_keeper.auto_block_sizes {
+            return self.state_keeper.block_chunk_sizes.clone();
+        }
+
+        let max_block_size = *self
+            .state_keeper
+            .block_chunk_sizes
+            .iter()
+            .max()
+            .expect("failed to find max block chunks size");
+        self.circuit
+            .supported_block_chunks_sizes
+            .iter()
+            .copied()
+            .filter(|&size| size <= max_block_size)
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -55,6 +81,9 @@ pub struct StateKeeper {
     /// otherwise block will never be proven. This list can contain not all the values though: e.g. for local
     /// development usually a couple of smallest block sizes is enough.
     pub block_chunk_sizes: Vec<usize>,
+    /// Whether all the supported block sizes up to the largest one from `block_chunk_sizes` should be used,
+    /// so the smallest circuit that fits the block is chosen. Provers must be able to prove all of these sizes.
+    pub auto_block_sizes: bool,
     /// Time between two miniblocks created by mempool / block_proposer.
     pub miniblock_iteration_interval: u64,
     /// Maximum amount of miniblock iterations before sealing the block.
@@ -62,6 +91,33 @@ pub struct StateKeeper {
     /// Maximum amount of miniblock iterations in case of block containing a fast withdrawal request.
     pub fast_block_miniblock_iterations: u64,
     pub fee_account_addr: Address,
+    /// Maximum amount of chunks that transfers can occupy in a single proposed block.
+    /// If not set, the amount is not limited.
+    pub transfer_chunks_quota: Option<usize>,
+    /// Maximum amount of chunks that withdrawals can occupy in a single proposed block.
+    /// If not set, the amount is not limited.
+    pub withdraw_chunks_quota: Option<usize>,
+    /// Maximum amount of chunks that `ChangePubKey` transactions can occupy in a single proposed block.
+    /// If not set, the amount is not limited.
+    pub change_pubkey_chunks_quota: Option<usize>,
+    /// Maximum amount of chunks that forced exits can occupy in a single proposed block.
+    /// If not set, the amount is not limited.
+    pub forced_exit_chunks_quota: Option<usize>,
+    /// Whether the server should start as a hot standby: instead of creating blocks, it keeps the state
+    /// up to date by following the account updates journal and takes over once the active server is gone.
+    pub standby_mode: bool,
+    /// Whether the block proposer should choose the size of every block to minimize the L1 cost per chunk
+    /// given the mempool depth and the gas price, among the sizes whose cost is covered by the fees.
+    /// If not set, blocks are sealed only once the largest size is filled.
+    pub cost_optimal_block_size: bool,
+    /// Estimated cost of proving a single block chunk in gwei, used to compare the block sizes.
+    pub zkp_cost_chunk_gwei: u64,
+    /// Failures of the transactions that are retried once in a later block instead of rejecting
+    /// the transaction right away. If not set, all the failed transactions are rejected.
+    pub retried_tx_failures: Option<Vec<TxFailureCode>>,
+    /// Path to the RocksDB database of the account tree. If not set, the tree is kept in memory
+    /// and is rebuilt from the database on every start.
+    pub account_tree_path: Option<String>,
 }
 
 impl StateKeeper {
@@ -71,6 +127,17 @@ impl StateKeeper {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Mempool {
+    /// Maximum difference between the transaction nonce and the committed nonce of the account.
+    /// Transactions with nonces that are too far ahead are rejected by mempool.
+    /// If not set, the nonces are not limited.
+    pub max_nonce_lookahead: Option<u32>,
+    /// Maximum amount of transactions of a single account waiting in the mempool.
+    /// Transactions exceeding the limit are rejected by mempool.
+    pub max_pending_txs_per_account: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,10 +157,27 @@ mod tests {
             },
             state_keeper: StateKeeper {
                 block_chunk_sizes: vec![6, 30],
+                auto_block_sizes: false,
                 miniblock_iteration_interval: 200,
                 miniblock_iterations: 10,
                 fast_block_miniblock_iterations: 5,
                 fee_account_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                transfer_chunks_quota: Some(20),
+                withdraw_chunks_quota: None,
+                change_pubkey_chunks_quota: None,
+                forced_exit_chunks_quota: None,
+                standby_mode: false,
+                cost_optimal_block_size: true,
+                zkp_cost_chunk_gwei: 500,
+                retried_tx_failures: Some(vec![
+                    TxFailureCode::NotEnoughBalance,
+                    TxFailureCode::NonceMismatch,
+                ]),
+                account_tree_path: None,
+            },
+            mempool: Mempool {
+                max_nonce_lookahead: Some(100),
+                max_pending_txs_per_account: 50,
             },
         }
     }
@@ -109,10 +193,18 @@ CHAIN_CIRCUIT_BALANCE_TREE_DEPTH="11"
 CHAIN_ETH_MAX_NUMBER_OF_WITHDRAWALS_PER_BLOCK="10"
 CHAIN_ETH_NETWORK="localhost"
 CHAIN_STATE_KEEPER_BLOCK_CHUNK_SIZES="6,30"
+CHAIN_STATE_KEEPER_AUTO_BLOCK_SIZES="false"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATION_INTERVAL="200"
 CHAIN_STATE_KEEPER_MINIBLOCK_ITERATIONS="10"
 CHAIN_STATE_KEEPER_FAST_BLOCK_MINIBLOCK_ITERATIONS="5"
 CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+CHAIN_STATE_KEEPER_TRANSFER_CHUNKS_QUOTA="20"
+CHAIN_STATE_KEEPER_STANDBY_MODE="false"
+CHAIN_STATE_KEEPER_COST_OPTIMAL_BLOCK_SIZE="true"
+CHAIN_STATE_KEEPER_ZKP_COST_CHUNK_GWEI="500"
+CHAIN_STATE_KEEPER_RETRIED_TX_FAILURES="notEnoughBalance,nonceMismatch"
+CHAIN_MEMPOOL_MAX_NONCE_LOOKAHEAD="100"
+CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="50"
         "#;
         set_env(config);
 
@@ -130,4 +222,15 @@ CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
             Duration::from_millis(config.state_keeper.miniblock_iteration_interval)
         );
     }
+
+    /// Checks that the produced block sizes are chosen according to the config.
+    #[test]
+    fn block_chunk_sizes() {
+        let mut config = expected_config();
+        config.state_keeper.block_chunk_sizes = vec![6, 150];
+        assert_eq!(config.block_chunk_sizes(), vec![6, 150]);
+
+        config.state_keeper.auto_block_sizes = true;
+        assert_eq!(config.block_chunk_sizes(), vec![6, 30, 74, 150]);
+    }
 }
@@ -0,0 +1,84 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for moving the proofs and witnesses of the verified blocks
+/// out of the database to the object store.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ColdStorageConfig {
+    /// Whether the blobs should be moved to the cold storage.
+    pub enabled: bool,
+    /// Root directory of the object store.
+    pub path: String,
+    /// Minimum time passed since the block verification after which its blobs are moved, in seconds.
+    pub archive_age: u64,
+    /// Interval between the archiving rounds in ms.
+    pub archive_interval: u64,
+    /// Maximum amount of blobs of each kind moved in one round.
+    pub batch_size: u32,
+}
+
+impl ColdStorageConfig {
+    pub fn from_env() -> Self {
+        envy_load!("cold_storage", "COLD_STORAGE_")
+    }
+
+    /// Converts `self.archive_age` into `Duration`.
+    pub fn archive_age(&self) -> Duration {
+        Duration::from_secs(self.archive_age)
+    }
+
+    /// Converts `self.archive_interval` into `Duration`.
+    pub fn archive_interval(&self) -> Duration {
+        Duration::from_millis(self.archive_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> ColdStorageConfig {
+        ColdStorageConfig {
+            enabled: true,
+            path: "/var/lib/zksync/cold_storage".into(),
+            archive_age: 604800,
+            archive_interval: 60000,
+            batch_size: 100,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+COLD_STORAGE_ENABLED="true"
+COLD_STORAGE_PATH="/var/lib/zksync/cold_storage"
+COLD_STORAGE_ARCHIVE_AGE="604800"
+COLD_STORAGE_ARCHIVE_INTERVAL="60000"
+COLD_STORAGE_BATCH_SIZE="100"
+        "#;
+        set_env(config);
+
+        let actual = ColdStorageConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.archive_age(),
+            Duration::from_secs(config.archive_age)
+        );
+        assert_eq!(
+            config.archive_interval(),
+            Duration::from_millis(config.archive_interval)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+// Built-in uses
+use std::time::Duration;
 // External uses
 use serde::Deserialize;
 
@@ -5,21 +7,58 @@ use serde::Deserialize;
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DBConfig {
     /// Amount of open connections to the database held by server in the pool.
+    /// Used as the size of the subsystem pools that are not configured explicitly.
     pub pool_size: usize,
+    /// Amount of connections in the pool of the Core actors (state keeper, committer, mempool, etc).
+    pub core_pool_size: usize,
+    /// Amount of connections in the pool of the API server.
+    pub api_pool_size: usize,
+    /// Amount of connections in the pool of the Ethereum sender.
+    pub eth_sender_pool_size: usize,
+    /// Amount of connections in the pool of the prover server and witness generators.
+    pub prover_pool_size: usize,
+    /// Execution time of the query after which it's reported as slow, in ms.
+    pub slow_query_threshold: u64,
     /// Database URL.
     pub url: String,
 }
 
 impl DBConfig {
     pub fn from_env() -> Self {
+        let pool_size = parse_pool_size("DB_POOL_SIZE");
+
         Self {
-            pool_size: std::env::var("DB_POOL_SIZE")
-                .expect("DB_POOL_SIZE is set")
+            pool_size,
+            core_pool_size: parse_subsystem_pool_size("DB_CORE_POOL_SIZE", pool_size),
+            api_pool_size: parse_subsystem_pool_size("DB_API_POOL_SIZE", pool_size),
+            eth_sender_pool_size: parse_subsystem_pool_size("DB_ETH_SENDER_POOL_SIZE", pool_size),
+            prover_pool_size: parse_subsystem_pool_size("DB_PROVER_POOL_SIZE", pool_size),
+            slow_query_threshold: std::env::var("DB_SLOW_QUERY_THRESHOLD")
+                .expect("DB_SLOW_QUERY_THRESHOLD is set")
                 .parse()
                 .unwrap(),
             url: std::env::var("DATABASE_URL").expect("DATABASE_URL is set"),
         }
     }
+
+    /// Converts `self.slow_query_threshold` into `Duration`.
+    pub fn slow_query_threshold(&self) -> Duration {
+        Duration::from_millis(self.slow_query_threshold)
+    }
+}
+
+fn parse_pool_size(name: &str) -> usize {
+    std::env::var(name)
+        .unwrap_or_else(|_| panic!("{} is set", name))
+        .parse()
+        .unwrap()
+}
+
+/// Parses the size of the subsystem pool, falling back to the default pool size if it's not set.
+fn parse_subsystem_pool_size(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .map(|size| size.parse().unwrap())
+        .unwrap_or(default)
 }
 
 #[cfg(test)]
@@ -30,6 +69,11 @@ mod tests {
     fn expected_config() -> DBConfig {
         DBConfig {
             pool_size: 10,
+            core_pool_size: 4,
+            api_pool_size: 4,
+            eth_sender_pool_size: 2,
+            prover_pool_size: 2,
+            slow_query_threshold: 1000,
             url: "postgres://postgres@localhost/plasma".into(),
         }
     }
@@ -38,6 +82,11 @@ mod tests {
     fn from_env() {
         let config = r#"
 DB_POOL_SIZE="10"
+DB_CORE_POOL_SIZE="4"
+DB_API_POOL_SIZE="4"
+DB_ETH_SENDER_POOL_SIZE="2"
+DB_PROVER_POOL_SIZE="2"
+DB_SLOW_QUERY_THRESHOLD="1000"
 DATABASE_URL="postgres://postgres@localhost/plasma"
         "#;
         set_env(config);
@@ -45,4 +94,27 @@ DATABASE_URL="postgres://postgres@localhost/plasma"
         let actual = DBConfig::from_env();
         assert_eq!(actual, expected_config());
     }
+
+    /// Checks that the pools without the configured size have the default one.
+    #[test]
+    fn default_subsystem_pool_size() {
+        set_env(r#"DB_TEST_CONFIGURED_POOL_SIZE="4""#);
+
+        assert_eq!(
+            parse_subsystem_pool_size("DB_TEST_CONFIGURED_POOL_SIZE", 10),
+            4
+        );
+        assert_eq!(parse_subsystem_pool_size("DB_TEST_UNSET_POOL_SIZE", 10), 10);
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.slow_query_threshold(),
+            Duration::from_millis(config.slow_query_threshold)
+        );
+    }
 }
@@ -34,6 +34,16 @@ pub struct Sender {
     pub operator_private_key: H256,
     /// Address of the operator account.
     pub operator_commit_eth_addr: Address,
+    /// Private key of the account sending the `verify` operations.
+    /// If not set, the operator account is used.
+    pub operator_verify_private_key: Option<H256>,
+    /// Address of the account sending the `verify` operations.
+    pub operator_verify_eth_addr: Option<Address>,
+    /// Private key of the account sending the `completeWithdrawals` transactions.
+    /// If not set, the operator account is used.
+    pub operator_withdraw_private_key: Option<H256>,
+    /// Address of the account sending the `completeWithdrawals` transactions.
+    pub operator_withdraw_eth_addr: Option<Address>,
     /// mount of confirmations required to consider L1 transaction committed.
     pub wait_confirmations: u64,
     /// Amount of blocks we will wait before considering L1 transaction stuck.
@@ -44,6 +54,16 @@ pub struct Sender {
     pub max_txs_in_flight: u64,
     /// Whether sender should interact with L1 or not.
     pub is_enabled: bool,
+    /// If set, operations are signed and logged, but never broadcast to L1.
+    /// Intended for staging environments that use the mainnet data.
+    pub dry_run: bool,
+}
+
+/// Ethereum account used to send the transactions of a certain type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperatorAccount {
+    pub address: Address,
+    pub private_key: H256,
 }
 
 impl Sender {
@@ -51,6 +71,51 @@ impl Sender {
     pub fn tx_poll_period(&self) -> Duration {
         Duration::from_secs(self.tx_poll_period)
     }
+
+    /// Account sending the `commit` operations.
+    pub fn commit_account(&self) -> OperatorAccount {
+        OperatorAccount {
+            address: self.operator_commit_eth_addr,
+            private_key: self.operator_private_key,
+        }
+    }
+
+    /// Account sending the `verify` operations.
+    pub fn verify_account(&self) -> OperatorAccount {
+        self.role_account(
+            "verify",
+            self.operator_verify_eth_addr,
+            self.operator_verify_private_key,
+        )
+    }
+
+    /// Account sending the `completeWithdrawals` transactions.
+    pub fn withdraw_account(&self) -> OperatorAccount {
+        self.role_account(
+            "withdraw",
+            self.operator_withdraw_eth_addr,
+            self.operator_withdraw_private_key,
+        )
+    }
+
+    fn role_account(
+        &self,
+        role: &str,
+        address: Option<Address>,
+        private_key: Option<H256>,
+    ) -> OperatorAccount {
+        match (address, private_key) {
+            (Some(address), Some(private_key)) => OperatorAccount {
+                address,
+                private_key,
+            },
+            (None, None) => self.commit_account(),
+            _ => panic!(
+                "Both the address and the private key of the {} account should be set",
+                role
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -90,10 +155,17 @@ mod tests {
                 tx_poll_period: 3,
                 max_txs_in_flight: 3,
                 is_enabled: true,
+                dry_run: false,
                 operator_private_key: hash(
                     "27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be",
                 ),
                 operator_commit_eth_addr: addr("de03a0B5963f75f1C8485B355fF6D30f3093BDE7"),
+                operator_verify_private_key: Some(hash(
+                    "2f7fe8a1fc2b7b4a2e1c6b1f3a9f1c0d5b8e3a7c6d2f1e0b9a8c7d6e5f4a3b2c",
+                )),
+                operator_verify_eth_addr: Some(addr("b1f4fd2d5b3a5c1e9a6b4c0e7d8f9a1b2c3d4e5f")),
+                operator_withdraw_private_key: None,
+                operator_withdraw_eth_addr: None,
             },
             gas_price_limit: GasLimit {
                 default: 400000000000,
@@ -112,8 +184,11 @@ ETH_SENDER_SENDER_EXPECTED_WAIT_TIME_BLOCK="30"
 ETH_SENDER_SENDER_TX_POLL_PERIOD="3"
 ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
 ETH_SENDER_SENDER_IS_ENABLED="true"
+ETH_SENDER_SENDER_DRY_RUN="false"
 ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
 ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR="0xde03a0B5963f75f1C8485B355fF6D30f3093BDE7"
+ETH_SENDER_SENDER_OPERATOR_VERIFY_PRIVATE_KEY="0x2f7fe8a1fc2b7b4a2e1c6b1f3a9f1c0d5b8e3a7c6d2f1e0b9a8c7d6e5f4a3b2c"
+ETH_SENDER_SENDER_OPERATOR_VERIFY_ETH_ADDR="0xb1f4fd2d5b3a5c1e9a6b4c0e7d8f9a1b2c3d4e5f"
 ETH_SENDER_GAS_PRICE_LIMIT_DEFAULT="400000000000"
 ETH_SENDER_GAS_PRICE_LIMIT_UPDATE_INTERVAL="150"
 ETH_SENDER_GAS_PRICE_LIMIT_SAMPLE_INTERVAL="15"
@@ -135,6 +210,19 @@ ETH_SENDER_GAS_PRICE_LIMIT_SCALE_FACTOR="1"
             Duration::from_secs(config.sender.tx_poll_period)
         );
 
+        assert_eq!(
+            config.sender.verify_account(),
+            OperatorAccount {
+                address: config.sender.operator_verify_eth_addr.unwrap(),
+                private_key: config.sender.operator_verify_private_key.unwrap(),
+            }
+        );
+        // Withdraw account is not set, so the operator account is used.
+        assert_eq!(
+            config.sender.withdraw_account(),
+            config.sender.commit_account()
+        );
+
         assert_eq!(
             config.gas_price_limit.update_interval(),
             Duration::from_secs(config.gas_price_limit.update_interval)
@@ -0,0 +1,94 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Message broker the events are published to.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventPublisherBackend {
+    Kafka,
+    Nats,
+}
+
+/// Configuration for the publisher of the block and transaction events to the message broker.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EventPublisherConfig {
+    /// Whether the events should be published by the server.
+    pub enabled: bool,
+    pub backend: EventPublisherBackend,
+    /// Addresses of the Kafka brokers or the NATS servers.
+    pub servers: Vec<String>,
+    /// Prefix of the Kafka topics or the NATS subjects, e.g. `zksync` for `zksync.block_committed`.
+    pub topic_prefix: String,
+    /// Timeout of publishing a single event in ms.
+    pub publish_timeout: u64,
+    /// Interval between the attempts to publish an event in ms.
+    pub retry_interval: u64,
+}
+
+impl EventPublisherConfig {
+    pub fn from_env() -> Self {
+        envy_load!("event_publisher", "EVENT_PUBLISHER_")
+    }
+
+    /// Returns the topic of the events of the given kind.
+    pub fn topic(&self, kind: &str) -> String {
+        format!("{}.{}", self.topic_prefix, kind)
+    }
+
+    /// Converts `self.publish_timeout` into `Duration`.
+    pub fn publish_timeout(&self) -> Duration {
+        Duration::from_millis(self.publish_timeout)
+    }
+
+    /// Converts `self.retry_interval` into `Duration`.
+    pub fn retry_interval(&self) -> Duration {
+        Duration::from_millis(self.retry_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> EventPublisherConfig {
+        EventPublisherConfig {
+            enabled: false,
+            backend: EventPublisherBackend::Kafka,
+            servers: vec!["127.0.0.1:9092".into(), "127.0.0.1:9093".into()],
+            topic_prefix: "zksync".into(),
+            publish_timeout: 5000,
+            retry_interval: 1000,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+EVENT_PUBLISHER_ENABLED="false"
+EVENT_PUBLISHER_BACKEND="kafka"
+EVENT_PUBLISHER_SERVERS="127.0.0.1:9092,127.0.0.1:9093"
+EVENT_PUBLISHER_TOPIC_PREFIX="zksync"
+EVENT_PUBLISHER_PUBLISH_TIMEOUT="5000"
+EVENT_PUBLISHER_RETRY_INTERVAL="1000"
+        "#;
+        set_env(config);
+
+        let actual = EventPublisherConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(config.topic("tx_executed"), "zksync.tx_executed");
+        assert_eq!(config.publish_timeout(), Duration::from_millis(5000));
+        assert_eq!(config.retry_interval(), Duration::from_millis(1000));
+    }
+}
@@ -1,22 +1,29 @@
 // Public re-exports
 pub use self::{
-    api::ApiConfig, chain::ChainConfig, contracts::ContractsConfig, db::DBConfig,
+    api::ApiConfig, background_migrations::BackgroundMigrationsConfig, chain::ChainConfig,
+    cold_storage::ColdStorageConfig, contracts::ContractsConfig, db::DBConfig,
     dev_liquidity_token_watcher::DevLiquidityTokenWatcherConfig, eth_client::ETHClientConfig,
-    eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, misc::MiscConfig, prover::ProverConfig,
-    ticker::TickerConfig,
+    eth_sender::ETHSenderConfig, eth_watch::ETHWatchConfig, event_publisher::EventPublisherConfig,
+    misc::MiscConfig, prover::ProverConfig, stats::StatsConfig, ticker::TickerConfig,
+    webhooks::WebhooksConfig,
 };
 
 pub mod api;
+pub mod background_migrations;
 pub mod chain;
+pub mod cold_storage;
 pub mod contracts;
 pub mod db;
 pub mod dev_liquidity_token_watcher;
 pub mod eth_client;
 pub mod eth_sender;
 pub mod eth_watch;
+pub mod event_publisher;
 pub mod misc;
 pub mod prover;
+pub mod stats;
 pub mod ticker;
+pub mod webhooks;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
@@ -11,6 +11,7 @@ pub struct ProverConfig {
     pub prover: Prover,
     pub core: Core,
     pub witness_generator: WitnessGenerator,
+    pub keys: Keys,
 }
 
 impl ProverConfig {
@@ -19,6 +20,7 @@ impl ProverConfig {
             prover: envy_load!("prover.prover", "PROVER_PROVER_"),
             core: envy_load!("prover.core", "PROVER_CORE_"),
             witness_generator: envy_load!("prover.witness_generator", "PROVER_WITNESS_GENERATOR_"),
+            keys: envy_load!("prover.keys", "PROVER_KEYS_"),
         }
     }
 }
@@ -71,8 +73,20 @@ impl Core {
 pub struct WitnessGenerator {
     /// Interval to check whether a new witness generation job should be started in ms.
     pub prepare_data_interval: u64,
-    /// Amount of witness generator threads.
+    /// Amount of witness generator threads. Zero means that the witness generators are run separately.
     pub witness_generators: usize,
+    /// Maximum amount of blocks with a witness awaiting for proof. Once it's reached,
+    /// witness generators stop producing new witnesses until provers catch up.
+    pub max_pending_jobs: u32,
+    /// Whether the block proposer should slow down while the witness generators are throttled.
+    pub slow_down_state_keeper: bool,
+    /// While the block proposer is slowed down, only every n-th miniblock iteration is performed.
+    pub state_keeper_slow_down_factor: u64,
+    /// Timeout after which an unfinished witness generation job is taken by another witness generator in ms.
+    pub job_timeout: u64,
+    /// Path to the RocksDB database of the account tree. Every witness generator keeps its tree
+    /// in a subdirectory named after its index. If not set, the state is loaded from the database.
+    pub account_tree_path: Option<String>,
 }
 
 impl WitnessGenerator {
@@ -80,6 +94,21 @@ impl WitnessGenerator {
     pub fn prepare_data_interval(&self) -> Duration {
         Duration::from_millis(self.prepare_data_interval)
     }
+
+    /// Converts `self.job_timeout` into `Duration`.
+    pub fn job_timeout(&self) -> Duration {
+        Duration::from_millis(self.job_timeout)
+    }
+}
+
+/// Settings of the proving and verification keys provisioning.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Keys {
+    /// Whether to download the missing keys on startup.
+    pub download: bool,
+    /// Network location of the keys. Expected to contain the files laid out the same way
+    /// as in the local `keys` directory, along with the `SHA256SUMS` manifest.
+    pub network_url: String,
 }
 
 #[cfg(test)]
@@ -101,6 +130,15 @@ mod tests {
             witness_generator: WitnessGenerator {
                 prepare_data_interval: 500,
                 witness_generators: 2,
+                max_pending_jobs: 100,
+                slow_down_state_keeper: false,
+                state_keeper_slow_down_factor: 4,
+                job_timeout: 600000,
+                account_tree_path: None,
+            },
+            keys: Keys {
+                download: false,
+                network_url: "-".into(),
             },
         }
     }
@@ -115,6 +153,12 @@ PROVER_CORE_GONE_TIMEOUT="60000"
 PROVER_CORE_IDLE_PROVERS="1"
 PROVER_WITNESS_GENERATOR_PREPARE_DATA_INTERVAL="500"
 PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
+PROVER_WITNESS_GENERATOR_MAX_PENDING_JOBS="100"
+PROVER_WITNESS_GENERATOR_SLOW_DOWN_STATE_KEEPER="false"
+PROVER_WITNESS_GENERATOR_STATE_KEEPER_SLOW_DOWN_FACTOR="4"
+PROVER_WITNESS_GENERATOR_JOB_TIMEOUT="600000"
+PROVER_KEYS_DOWNLOAD="false"
+PROVER_KEYS_NETWORK_URL="-"
         "#;
         set_env(config);
 
@@ -149,5 +193,9 @@ PROVER_WITNESS_GENERATOR_WITNESS_GENERATORS="2"
             config.witness_generator.prepare_data_interval(),
             Duration::from_millis(config.witness_generator.prepare_data_interval)
         );
+        assert_eq!(
+            config.witness_generator.job_timeout(),
+            Duration::from_millis(config.witness_generator.job_timeout)
+        );
     }
 }
@@ -0,0 +1,62 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the aggregation of the network statistics reported by the API.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct StatsConfig {
+    /// Whether the statistics should be aggregated by the server.
+    pub enabled: bool,
+    /// Interval between the aggregation rounds in ms.
+    pub aggregation_interval: u64,
+}
+
+impl StatsConfig {
+    pub fn from_env() -> Self {
+        envy_load!("stats", "STATS_")
+    }
+
+    /// Converts `self.aggregation_interval` into `Duration`.
+    pub fn aggregation_interval(&self) -> Duration {
+        Duration::from_millis(self.aggregation_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> StatsConfig {
+        StatsConfig {
+            enabled: true,
+            aggregation_interval: 300000,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+STATS_ENABLED="true"
+STATS_AGGREGATION_INTERVAL="300000"
+        "#;
+        set_env(config);
+
+        let actual = StatsConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(
+            config.aggregation_interval(),
+            Duration::from_millis(config.aggregation_interval)
+        );
+    }
+}
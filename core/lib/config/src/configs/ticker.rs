@@ -1,4 +1,5 @@
 // Built-in uses
+use std::{collections::HashMap, convert::TryFrom};
 // External uses
 use serde::Deserialize;
 // Workspace uses
@@ -12,6 +13,58 @@ pub enum TokenPriceSource {
     CoinMarketCap,
 }
 
+/// Plausible range of the token USD price. Prices outside of the range reported by the price
+/// source are considered incorrect, e.g. caused by the wrong decimals or the manipulation of a thin market.
+///
+/// Parsed from the string of the `<token address>:<min USD price>:<max USD price>` format.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String")]
+pub struct TokenPriceBounds {
+    pub token: Address,
+    pub min_usd_price: f64,
+    pub max_usd_price: f64,
+}
+
+impl TokenPriceBounds {
+    /// Returns `true` if the price is within the bounds.
+    pub fn contains(&self, usd_price: f64) -> bool {
+        self.min_usd_price <= usd_price && usd_price <= self.max_usd_price
+    }
+}
+
+impl TryFrom<String> for TokenPriceBounds {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parts: Vec<_> = value.split(':').collect();
+        if parts.len() != 3 {
+            return Err(format!(
+                "Token price bounds should be in the `<token>:<min>:<max>` format, got {}",
+                value
+            ));
+        }
+
+        let token = parts[0]
+            .trim_start_matches("0x")
+            .parse()
+            .map_err(|err| format!("Invalid token address {}: {}", parts[0], err))?;
+        let parse_price = |price: &str| {
+            price
+                .parse::<f64>()
+                .map_err(|err| format!("Invalid token price {}: {}", price, err))
+        };
+        let bounds = Self {
+            token,
+            min_usd_price: parse_price(parts[1])?,
+            max_usd_price: parse_price(parts[2])?,
+        };
+        if bounds.min_usd_price > bounds.max_usd_price {
+            return Err(format!("Empty token price bounds: {}", value));
+        }
+        Ok(bounds)
+    }
+}
+
 /// Configuration for the fee ticker.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TickerConfig {
@@ -37,6 +90,8 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// List of tokens for which subsidions are disabled.
     pub not_subsidized_tokens: Vec<Address>,
+    /// Plausible USD price ranges of the tokens.
+    pub token_price_bounds: Vec<TokenPriceBounds>,
 }
 
 impl TickerConfig {
@@ -53,6 +108,14 @@ impl TickerConfig {
 
         (self.token_price_source, url)
     }
+
+    /// Returns the plausible USD price ranges by the token addresses.
+    pub fn price_bounds(&self) -> HashMap<Address, TokenPriceBounds> {
+        self.token_price_bounds
+            .iter()
+            .map(|bounds| (bounds.token, *bounds))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +139,18 @@ mod tests {
                 addr("2b591e99afe9f32eaa6214f7b7629768c40eeb39"),
                 addr("34083bbd70d394110487feaa087da875a54624ec"),
             ],
+            token_price_bounds: vec![
+                TokenPriceBounds {
+                    token: addr("0000000000000000000000000000000000000000"),
+                    min_usd_price: 1.0,
+                    max_usd_price: 1_000_000.0,
+                },
+                TokenPriceBounds {
+                    token: addr("6b175474e89094c44da98b954eedeac495271d0f"),
+                    min_usd_price: 0.5,
+                    max_usd_price: 2.0,
+                },
+            ],
         }
     }
 
@@ -93,6 +168,7 @@ FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
 FEE_TICKER_LIQUIDITY_VOLUME=100
 FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
+FEE_TICKER_TOKEN_PRICE_BOUNDS="0x0000000000000000000000000000000000000000:1:1000000,0x6b175474e89094c44da98b954eedeac495271d0f:0.5:2"
         "#;
         set_env(config);
 
@@ -122,5 +198,23 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL)
         );
+
+        let price_bounds = config.price_bounds();
+        let dai_bounds = price_bounds[&addr("6b175474e89094c44da98b954eedeac495271d0f")];
+        assert!(dai_bounds.contains(1.0));
+        assert!(!dai_bounds.contains(0.01));
+        assert!(!dai_bounds.contains(100.0));
+    }
+
+    #[test]
+    fn invalid_price_bounds() {
+        for bounds in &[
+            "0x6b175474e89094c44da98b954eedeac495271d0f:1",
+            "0x6b175474e89094c44da98b954eedeac495271d0f:2:1",
+            "0x6b175474e89094c44da98b954eedeac495271d0f:one:2",
+            "DAI:0.5:2",
+        ] {
+            assert!(TokenPriceBounds::try_from(bounds.to_string()).is_err());
+        }
     }
 }
@@ -0,0 +1,78 @@
+// Built-in uses
+use std::time::Duration;
+// External uses
+use serde::Deserialize;
+// Local uses
+use crate::envy_load;
+
+/// Configuration for the delivery of the webhook notifications about the address activity.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WebhooksConfig {
+    /// Whether the webhook notifications should be sent by the server.
+    pub enabled: bool,
+    /// Timeout of a single delivery request in ms.
+    pub request_timeout: u64,
+    /// Maximum number of the delivery attempts of a single event.
+    pub max_attempts: u32,
+    /// Interval before the first retry in ms, doubled after every failed attempt.
+    pub retry_interval: u64,
+    /// Maximum number of the events delivered at the same time.
+    pub max_concurrent_deliveries: usize,
+}
+
+impl WebhooksConfig {
+    pub fn from_env() -> Self {
+        envy_load!("webhooks", "WEBHOOKS_")
+    }
+
+    /// Converts `self.request_timeout` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout)
+    }
+
+    /// Returns the interval before the given retry, starting from 1.
+    pub fn retry_interval(&self, retry: u32) -> Duration {
+        Duration::from_millis(self.retry_interval) * 2u32.saturating_pow(retry.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::test_utils::set_env;
+
+    fn expected_config() -> WebhooksConfig {
+        WebhooksConfig {
+            enabled: true,
+            request_timeout: 5000,
+            max_attempts: 5,
+            retry_interval: 10000,
+            max_concurrent_deliveries: 100,
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let config = r#"
+WEBHOOKS_ENABLED="true"
+WEBHOOKS_REQUEST_TIMEOUT="5000"
+WEBHOOKS_MAX_ATTEMPTS="5"
+WEBHOOKS_RETRY_INTERVAL="10000"
+WEBHOOKS_MAX_CONCURRENT_DELIVERIES="100"
+        "#;
+        set_env(config);
+
+        let actual = WebhooksConfig::from_env();
+        assert_eq!(actual, expected_config());
+    }
+
+    /// Checks the correctness of the config helper methods.
+    #[test]
+    fn methods() {
+        let config = expected_config();
+
+        assert_eq!(config.request_timeout(), Duration::from_millis(5000));
+        assert_eq!(config.retry_interval(1), Duration::from_millis(10000));
+        assert_eq!(config.retry_interval(3), Duration::from_millis(40000));
+    }
+}
@@ -1,8 +1,9 @@
 use serde::Deserialize;
 
 pub use crate::configs::{
-    ApiConfig, ChainConfig, ContractsConfig, DBConfig, DevLiquidityTokenWatcherConfig,
-    ETHClientConfig, ETHSenderConfig, ETHWatchConfig, MiscConfig, ProverConfig, TickerConfig,
+    ApiConfig, BackgroundMigrationsConfig, ChainConfig, ColdStorageConfig, ContractsConfig,
+    DBConfig, DevLiquidityTokenWatcherConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
+    EventPublisherConfig, MiscConfig, ProverConfig, StatsConfig, TickerConfig, WebhooksConfig,
 };
 
 pub mod configs;
@@ -11,12 +12,14 @@ pub mod test_config;
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ZkSyncConfig {
     pub api: ApiConfig,
+    pub background_migrations: BackgroundMigrationsConfig,
     pub chain: ChainConfig,
     pub contracts: ContractsConfig,
     pub db: DBConfig,
     pub eth_client: ETHClientConfig,
     pub eth_sender: ETHSenderConfig,
     pub eth_watch: ETHWatchConfig,
+    pub event_publisher: EventPublisherConfig,
     pub prover: ProverConfig,
     pub ticker: TickerConfig,
 }
@@ -25,12 +28,14 @@ impl ZkSyncConfig {
     pub fn from_env() -> Self {
         Self {
             api: ApiConfig::from_env(),
+            background_migrations: BackgroundMigrationsConfig::from_env(),
             chain: ChainConfig::from_env(),
             contracts: ContractsConfig::from_env(),
             db: DBConfig::from_env(),
             eth_client: ETHClientConfig::from_env(),
             eth_sender: ETHSenderConfig::from_env(),
             eth_watch: ETHWatchConfig::from_env(),
+            event_publisher: EventPublisherConfig::from_env(),
             prover: ProverConfig::from_env(),
             ticker: TickerConfig::from_env(),
         }
@@ -0,0 +1,292 @@
+//! Signatures of the requests to the private API.
+//!
+//! Requests are signed by the API server with HMAC-SHA256 using the secret shared with the Core.
+//! The signature covers the method, the path and the body of the request along with its timestamp
+//! and a random nonce, so a signed request can't be altered, and the Core accepts it only within
+//! `MAX_CLOCK_SKEW` from its timestamp and only once.
+//!
+//! HTTP requests carry the signature in the headers, gRPC requests carry it in the metadata
+//! under the same keys, and their body is the protobuf encoding of the request message.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+
+use hmac::{Hmac, Mac, NewMac};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Header carrying the UNIX timestamp of the request in seconds.
+pub const TIMESTAMP_HEADER: &str = "x-zksync-timestamp";
+/// Header carrying the random nonce of the request.
+pub const NONCE_HEADER: &str = "x-zksync-nonce";
+/// Header carrying the hex-encoded signature of the request.
+pub const SIGNATURE_HEADER: &str = "x-zksync-signature";
+
+/// Maximum difference between the timestamp of the request and the time it's received at.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+/// Error of the request signature check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AuthError {
+    #[error("request is not signed")]
+    Missing,
+    #[error("request timestamp is too far from the current time")]
+    Expired,
+    #[error("request signature is invalid")]
+    Invalid,
+    #[error("request was already received")]
+    Replayed,
+}
+
+fn unix_timestamp() -> u64 {
+    UNIX_EPOCH
+        .elapsed()
+        .expect("System time is before the UNIX epoch")
+        .as_secs()
+}
+
+fn request_mac(
+    secret: &str,
+    method: &str,
+    path: &str,
+    timestamp: u64,
+    nonce: &str,
+    body: &[u8],
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_varkey(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    let signed_data = format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method,
+        path,
+        timestamp,
+        nonce,
+        hex::encode(Sha256::digest(body))
+    );
+    mac.update(signed_data.as_bytes());
+    mac
+}
+
+/// Encodes the gRPC request message, the encoding is the signed body of the request.
+pub fn message_body(message: &impl prost::Message) -> Vec<u8> {
+    let mut body = Vec::with_capacity(message.encoded_len());
+    message
+        .encode(&mut body)
+        .expect("Vector has enough capacity for the message");
+    body
+}
+
+/// Signature of the request along with the signed timestamp and nonce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestSignature {
+    pub timestamp: u64,
+    pub nonce: String,
+    pub signature: String,
+}
+
+impl RequestSignature {
+    /// Signs the request with the current timestamp and a random nonce.
+    pub fn new(secret: &str, method: &str, path: &str, body: &[u8]) -> Self {
+        let timestamp = unix_timestamp();
+        let nonce = hex::encode(rand::random::<[u8; 16]>());
+        let mac = request_mac(secret, method, path, timestamp, &nonce, body);
+
+        Self {
+            timestamp,
+            nonce,
+            signature: hex::encode(mac.finalize().into_bytes()),
+        }
+    }
+
+    /// Parses the signature from the values of the headers, if all of them are set.
+    pub fn from_headers(
+        timestamp: Option<&str>,
+        nonce: Option<&str>,
+        signature: Option<&str>,
+    ) -> Result<Self, AuthError> {
+        match (timestamp, nonce, signature) {
+            (Some(timestamp), Some(nonce), Some(signature)) => Ok(Self {
+                timestamp: timestamp.parse().map_err(|_| AuthError::Invalid)?,
+                nonce: nonce.to_owned(),
+                signature: signature.to_owned(),
+            }),
+            _ => Err(AuthError::Missing),
+        }
+    }
+
+    /// Returns the values of the headers carrying the signature.
+    pub fn headers(&self) -> [(&'static str, String); 3] {
+        [
+            (TIMESTAMP_HEADER, self.timestamp.to_string()),
+            (NONCE_HEADER, self.nonce.clone()),
+            (SIGNATURE_HEADER, self.signature.clone()),
+        ]
+    }
+
+    /// Checks the signature and the timestamp of the request, but not the uniqueness of the nonce.
+    fn verify(
+        &self,
+        secret: &str,
+        method: &str,
+        path: &str,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), AuthError> {
+        let max_skew = MAX_CLOCK_SKEW.as_secs();
+        // The timestamp comes from the request headers, so the bounds must not overflow.
+        if self.timestamp < now.saturating_sub(max_skew)
+            || self.timestamp > now.saturating_add(max_skew)
+        {
+            return Err(AuthError::Expired);
+        }
+
+        let signature = hex::decode(&self.signature).map_err(|_| AuthError::Invalid)?;
+        request_mac(secret, method, path, self.timestamp, &self.nonce, body)
+            .verify(&signature)
+            .map_err(|_| AuthError::Invalid)
+    }
+}
+
+/// Nonces of the accepted requests, in the order they were accepted in.
+#[derive(Debug, Default)]
+struct AcceptedNonces {
+    nonces: HashSet<String>,
+    queue: VecDeque<(Instant, String)>,
+}
+
+/// Verifier of the signed requests received by the Core.
+///
+/// The nonces of the accepted requests are kept while their timestamps may still be accepted,
+/// so every signed request is accepted only once.
+#[derive(Debug)]
+pub struct RequestVerifier {
+    secret: String,
+    accepted: Mutex<AcceptedNonces>,
+}
+
+impl RequestVerifier {
+    pub fn new(secret: String) -> Self {
+        Self {
+            secret,
+            accepted: Mutex::default(),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        signature: &RequestSignature,
+        method: &str,
+        path: &str,
+        body: &[u8],
+    ) -> Result<(), AuthError> {
+        signature.verify(&self.secret, method, path, body, unix_timestamp())?;
+
+        // The timestamp of the request accepted at some moment is no longer accepted
+        // after `2 * MAX_CLOCK_SKEW`, so its nonce can be forgotten.
+        let now = Instant::now();
+        let mut accepted = self.accepted.lock().unwrap();
+        while let Some((accepted_at, _)) = accepted.queue.front() {
+            if now.duration_since(*accepted_at) < 2 * MAX_CLOCK_SKEW {
+                break;
+            }
+            let (_, nonce) = accepted.queue.pop_front().unwrap();
+            accepted.nonces.remove(&nonce);
+        }
+
+        if !accepted.nonces.insert(signature.nonce.clone()) {
+            return Err(AuthError::Replayed);
+        }
+        accepted.queue.push_back((now, signature.nonce.clone()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_request() {
+        let signature = RequestSignature::new("secret", "POST", "/new_tx?outbox_id=1", b"{}");
+        let now = signature.timestamp;
+
+        assert_eq!(
+            signature.verify("secret", "POST", "/new_tx?outbox_id=1", b"{}", now),
+            Ok(())
+        );
+        let altered = vec![
+            ("other secret", "POST", "/new_tx?outbox_id=1", &b"{}"[..]),
+            ("secret", "GET", "/new_tx?outbox_id=1", b"{}"),
+            ("secret", "POST", "/new_tx?outbox_id=2", b"{}"),
+            ("secret", "POST", "/new_tx?outbox_id=1", b"[]"),
+        ];
+        for (secret, method, path, body) in altered {
+            assert_eq!(
+                signature.verify(secret, method, path, body, now),
+                Err(AuthError::Invalid)
+            );
+        }
+
+        let max_skew = MAX_CLOCK_SKEW.as_secs();
+        for now in &[now - max_skew - 1, now + max_skew + 1] {
+            assert_eq!(
+                signature.verify("secret", "POST", "/new_tx?outbox_id=1", b"{}", *now),
+                Err(AuthError::Expired)
+            );
+        }
+    }
+
+    #[test]
+    fn extreme_timestamps_are_expired() {
+        let now = unix_timestamp();
+        let signature =
+            RequestSignature::from_headers(Some("18446744073709551615"), Some("nonce"), Some("00"))
+                .unwrap();
+        assert_eq!(signature.timestamp, u64::MAX);
+        assert_eq!(
+            signature.verify("secret", "GET", "/ping", &[], now),
+            Err(AuthError::Expired)
+        );
+
+        let signature = RequestSignature::new("secret", "GET", "/ping", &[]);
+        for now in &[0, u64::MAX] {
+            assert_eq!(
+                signature.verify("secret", "GET", "/ping", &[], *now),
+                Err(AuthError::Expired)
+            );
+        }
+    }
+
+    #[test]
+    fn requests_are_accepted_once() {
+        let verifier = RequestVerifier::new("secret".to_owned());
+        let signature = RequestSignature::new("secret", "GET", "/ping", &[]);
+
+        assert_eq!(verifier.verify(&signature, "GET", "/ping", &[]), Ok(()));
+        assert_eq!(
+            verifier.verify(&signature, "GET", "/ping", &[]),
+            Err(AuthError::Replayed)
+        );
+
+        let signature = RequestSignature::new("secret", "GET", "/ping", &[]);
+        assert_eq!(verifier.verify(&signature, "GET", "/ping", &[]), Ok(()));
+    }
+
+    #[test]
+    fn signature_headers() {
+        let signature = RequestSignature::new("secret", "GET", "/ping", &[]);
+        let [timestamp, nonce, value] = signature.headers();
+        let parsed = RequestSignature::from_headers(
+            Some(timestamp.1.as_str()),
+            Some(nonce.1.as_str()),
+            Some(value.1.as_str()),
+        );
+        assert_eq!(parsed, Ok(signature));
+
+        let missing = RequestSignature::from_headers(Some(timestamp.1.as_str()), None, None);
+        assert_eq!(missing, Err(AuthError::Missing));
+    }
+}
@@ -0,0 +1,381 @@
+//! Conversions between the transactions and their protobuf messages.
+//!
+//! The transactions are decoded without verifying their zkSync signatures, the same way
+//! as they are deserialized from JSON: the signatures are verified by the mempool.
+
+use std::convert::TryFrom;
+
+use anyhow::{ensure, format_err};
+use num::BigUint;
+
+use zksync_types::{
+    tx::{
+        ChangePubKey, Close, EIP1271Signature, EthSignData, ForcedExit, PackedEthSignature,
+        PackedPublicKey, PackedSignature, Transfer, TxEthSignature, TxSignature, Withdraw,
+    },
+    AccountId, Address, Nonce, PubKeyHash, SignedZkSyncTx, TokenId, ZkSyncTx,
+};
+
+use crate::v1::{self, eth_signature, signed_tx};
+
+fn decode_address(bytes: &[u8]) -> anyhow::Result<Address> {
+    ensure!(bytes.len() == Address::len_bytes(), "Address size mismatch");
+    Ok(Address::from_slice(bytes))
+}
+
+fn decode_amount(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+impl From<&TxSignature> for v1::Signature {
+    fn from(signature: &TxSignature) -> Self {
+        Self {
+            pub_key: signature
+                .pub_key
+                .serialize_packed()
+                .expect("Failed to serialize the public key"),
+            signature: signature
+                .signature
+                .serialize_packed()
+                .expect("Failed to serialize the signature"),
+        }
+    }
+}
+
+impl TryFrom<Option<v1::Signature>> for TxSignature {
+    type Error = anyhow::Error;
+
+    fn try_from(signature: Option<v1::Signature>) -> anyhow::Result<Self> {
+        let signature = signature.ok_or_else(|| format_err!("Signature is not set"))?;
+        Ok(Self {
+            pub_key: PackedPublicKey::deserialize_packed(&signature.pub_key)?,
+            signature: PackedSignature::deserialize_packed(&signature.signature)?,
+        })
+    }
+}
+
+impl From<&TxEthSignature> for v1::EthSignature {
+    fn from(signature: &TxEthSignature) -> Self {
+        let signature = match signature {
+            TxEthSignature::EthereumSignature(signature) => {
+                eth_signature::Signature::Ethereum(signature.serialize_packed().to_vec())
+            }
+            TxEthSignature::EIP1271Signature(signature) => {
+                eth_signature::Signature::Eip1271(signature.0.clone())
+            }
+            TxEthSignature::EIP712Signature(signature) => {
+                eth_signature::Signature::Eip712(signature.serialize_packed().to_vec())
+            }
+        };
+
+        Self {
+            signature: Some(signature),
+        }
+    }
+}
+
+impl TryFrom<v1::EthSignature> for TxEthSignature {
+    type Error = anyhow::Error;
+
+    fn try_from(signature: v1::EthSignature) -> anyhow::Result<Self> {
+        let signature = match signature.signature {
+            Some(eth_signature::Signature::Ethereum(bytes)) => {
+                Self::EthereumSignature(PackedEthSignature::deserialize_packed(&bytes)?)
+            }
+            Some(eth_signature::Signature::Eip1271(bytes)) => {
+                Self::EIP1271Signature(EIP1271Signature(bytes))
+            }
+            Some(eth_signature::Signature::Eip712(bytes)) => {
+                Self::EIP712Signature(PackedEthSignature::deserialize_packed(&bytes)?)
+            }
+            None => anyhow::bail!("Ethereum signature is not set"),
+        };
+        Ok(signature)
+    }
+}
+
+impl From<&ZkSyncTx> for signed_tx::Tx {
+    fn from(tx: &ZkSyncTx) -> Self {
+        match tx {
+            ZkSyncTx::Transfer(tx) => Self::Transfer(v1::Transfer {
+                account_id: *tx.account_id,
+                from: tx.from.as_bytes().to_vec(),
+                to: tx.to.as_bytes().to_vec(),
+                token: u32::from(*tx.token),
+                amount: tx.amount.to_bytes_be(),
+                fee: tx.fee.to_bytes_be(),
+                nonce: *tx.nonce,
+                signature: Some((&tx.signature).into()),
+                memo: tx.memo.clone().unwrap_or_default(),
+            }),
+            ZkSyncTx::Withdraw(tx) => Self::Withdraw(v1::Withdraw {
+                account_id: *tx.account_id,
+                from: tx.from.as_bytes().to_vec(),
+                to: tx.to.as_bytes().to_vec(),
+                token: u32::from(*tx.token),
+                amount: tx.amount.to_bytes_be(),
+                fee: tx.fee.to_bytes_be(),
+                nonce: *tx.nonce,
+                signature: Some((&tx.signature).into()),
+                fast: tx.fast,
+            }),
+            ZkSyncTx::ChangePubKey(tx) => Self::ChangePubKey(v1::ChangePubKey {
+                account_id: *tx.account_id,
+                account: tx.account.as_bytes().to_vec(),
+                new_pk_hash: tx.new_pk_hash.data.to_vec(),
+                fee_token: u32::from(*tx.fee_token),
+                fee: tx.fee.to_bytes_be(),
+                nonce: *tx.nonce,
+                signature: Some((&tx.signature).into()),
+                eth_signature: tx
+                    .eth_signature
+                    .as_ref()
+                    .map(|signature| signature.serialize_packed().to_vec())
+                    .unwrap_or_default(),
+            }),
+            ZkSyncTx::ForcedExit(tx) => Self::ForcedExit(v1::ForcedExit {
+                initiator_account_id: *tx.initiator_account_id,
+                target: tx.target.as_bytes().to_vec(),
+                token: u32::from(*tx.token),
+                fee: tx.fee.to_bytes_be(),
+                nonce: *tx.nonce,
+                signature: Some((&tx.signature).into()),
+            }),
+            ZkSyncTx::Close(tx) => Self::Close(v1::Close {
+                account: tx.account.as_bytes().to_vec(),
+                nonce: *tx.nonce,
+                signature: Some((&tx.signature).into()),
+            }),
+        }
+    }
+}
+
+fn decode_token(token: u32) -> anyhow::Result<TokenId> {
+    let token = u16::try_from(token).map_err(|_| format_err!("Token ID is out of range"))?;
+    Ok(TokenId(token))
+}
+
+impl TryFrom<signed_tx::Tx> for ZkSyncTx {
+    type Error = anyhow::Error;
+
+    fn try_from(tx: signed_tx::Tx) -> anyhow::Result<Self> {
+        // Transactions are created without the signatures, so they are not verified here.
+        let tx = match tx {
+            signed_tx::Tx::Transfer(tx) => {
+                let mut transfer = Transfer::new(
+                    AccountId(tx.account_id),
+                    decode_address(&tx.from)?,
+                    decode_address(&tx.to)?,
+                    decode_token(tx.token)?,
+                    decode_amount(&tx.amount),
+                    decode_amount(&tx.fee),
+                    Nonce(tx.nonce),
+                    None,
+                );
+                transfer.signature = TxSignature::try_from(tx.signature)?;
+                transfer.memo = Some(tx.memo).filter(|memo| !memo.is_empty());
+                transfer.into()
+            }
+            signed_tx::Tx::Withdraw(tx) => {
+                let mut withdraw = Withdraw::new(
+                    AccountId(tx.account_id),
+                    decode_address(&tx.from)?,
+                    decode_address(&tx.to)?,
+                    decode_token(tx.token)?,
+                    decode_amount(&tx.amount),
+                    decode_amount(&tx.fee),
+                    Nonce(tx.nonce),
+                    None,
+                );
+                withdraw.signature = TxSignature::try_from(tx.signature)?;
+                withdraw.fast = tx.fast;
+                withdraw.into()
+            }
+            signed_tx::Tx::ChangePubKey(tx) => {
+                let eth_signature = if tx.eth_signature.is_empty() {
+                    None
+                } else {
+                    Some(PackedEthSignature::deserialize_packed(&tx.eth_signature)?)
+                };
+                let mut change_pubkey = ChangePubKey::new(
+                    AccountId(tx.account_id),
+                    decode_address(&tx.account)?,
+                    PubKeyHash::from_bytes(&tx.new_pk_hash)?,
+                    decode_token(tx.fee_token)?,
+                    decode_amount(&tx.fee),
+                    Nonce(tx.nonce),
+                    None,
+                    eth_signature,
+                );
+                change_pubkey.signature = TxSignature::try_from(tx.signature)?;
+                change_pubkey.into()
+            }
+            signed_tx::Tx::ForcedExit(tx) => {
+                let mut forced_exit = ForcedExit::new(
+                    AccountId(tx.initiator_account_id),
+                    decode_address(&tx.target)?,
+                    decode_token(tx.token)?,
+                    decode_amount(&tx.fee),
+                    Nonce(tx.nonce),
+                    None,
+                );
+                forced_exit.signature = TxSignature::try_from(tx.signature)?;
+                forced_exit.into()
+            }
+            signed_tx::Tx::Close(tx) => ZkSyncTx::Close(Box::new(Close {
+                account: decode_address(&tx.account)?,
+                nonce: Nonce(tx.nonce),
+                signature: TxSignature::try_from(tx.signature)?,
+            })),
+        };
+        Ok(tx)
+    }
+}
+
+impl From<&SignedZkSyncTx> for v1::SignedTx {
+    fn from(tx: &SignedZkSyncTx) -> Self {
+        Self {
+            tx: Some((&tx.tx).into()),
+            eth_sign_data: tx.eth_sign_data.as_ref().map(|data| v1::EthSignData {
+                signature: Some((&data.signature).into()),
+                message: data.message.clone(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<v1::SignedTx> for SignedZkSyncTx {
+    type Error = anyhow::Error;
+
+    fn try_from(tx: v1::SignedTx) -> anyhow::Result<Self> {
+        let eth_sign_data = match tx.eth_sign_data {
+            Some(data) => {
+                let signature = data
+                    .signature
+                    .ok_or_else(|| format_err!("Ethereum signature is not set"))?;
+                Some(EthSignData {
+                    signature: TxEthSignature::try_from(signature)?,
+                    message: data.message,
+                })
+            }
+            None => None,
+        };
+        let tx = tx.tx.ok_or_else(|| format_err!("Transaction is not set"))?;
+
+        Ok(Self {
+            tx: ZkSyncTx::try_from(tx)?,
+            eth_sign_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::H256;
+
+    fn assert_roundtrip(tx: SignedZkSyncTx) {
+        let hash = tx.hash();
+        let decoded = SignedZkSyncTx::try_from(v1::SignedTx::from(&tx)).unwrap();
+        assert_eq!(decoded.hash(), hash);
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&tx).unwrap()
+        );
+    }
+
+    #[test]
+    fn transactions_roundtrip() {
+        let mut transfer = Transfer::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(3),
+            BigUint::from(1_000_000u32),
+            BigUint::from(100u32),
+            Nonce(4),
+            Some(TxSignature::default()),
+        );
+        transfer.memo = Some("invoice 42".to_owned());
+        let eth_signature = PackedEthSignature::sign(&H256::repeat_byte(7), b"message").unwrap();
+        assert_roundtrip(SignedZkSyncTx {
+            tx: transfer.into(),
+            eth_sign_data: Some(EthSignData {
+                signature: TxEthSignature::EthereumSignature(eth_signature.clone()),
+                message: b"message".to_vec(),
+            }),
+        });
+
+        let mut withdraw = Withdraw::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            TokenId(0),
+            BigUint::from(0u32),
+            BigUint::from(100u32),
+            Nonce(5),
+            None,
+        );
+        withdraw.fast = true;
+        assert_roundtrip(SignedZkSyncTx {
+            tx: withdraw.into(),
+            eth_sign_data: Some(EthSignData {
+                signature: TxEthSignature::EIP1271Signature(EIP1271Signature(vec![1; 65])),
+                message: b"message".to_vec(),
+            }),
+        });
+
+        let change_pubkey = ChangePubKey::new(
+            AccountId(1),
+            Address::repeat_byte(1),
+            PubKeyHash::default(),
+            TokenId(0),
+            BigUint::from(100u32),
+            Nonce(6),
+            None,
+            Some(eth_signature),
+        );
+        assert_roundtrip(ZkSyncTx::from(change_pubkey).into());
+
+        let forced_exit = ForcedExit::new(
+            AccountId(1),
+            Address::repeat_byte(2),
+            TokenId(3),
+            BigUint::from(100u32),
+            Nonce(7),
+            None,
+        );
+        assert_roundtrip(ZkSyncTx::from(forced_exit).into());
+    }
+
+    #[test]
+    fn malformed_transactions() {
+        let forced_exit = ForcedExit::new(
+            AccountId(1),
+            Address::repeat_byte(2),
+            TokenId(3),
+            BigUint::from(100u32),
+            Nonce(7),
+            None,
+        );
+        let tx = v1::SignedTx::from(&SignedZkSyncTx::from(ZkSyncTx::from(forced_exit)));
+
+        let mut missing_signature = tx.clone();
+        if let Some(signed_tx::Tx::ForcedExit(tx)) = &mut missing_signature.tx {
+            tx.signature = None;
+        }
+        assert!(SignedZkSyncTx::try_from(missing_signature).is_err());
+
+        let mut invalid_target = tx.clone();
+        if let Some(signed_tx::Tx::ForcedExit(tx)) = &mut invalid_target.tx {
+            tx.target = vec![1; 19];
+        }
+        assert!(SignedZkSyncTx::try_from(invalid_target).is_err());
+
+        let mut invalid_token = tx;
+        if let Some(signed_tx::Tx::ForcedExit(tx)) = &mut invalid_token.tx {
+            tx.token = u32::from(u16::max_value()) + 1;
+        }
+        assert!(SignedZkSyncTx::try_from(invalid_token).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+//! Definitions of the gRPC service of the zkSync Core private API.
+//!
+//! The service complements the HTTP private API with the transport for the requests on the
+//! hot path: the transactions forwarded by the API server to the Core mempool.
+//! The transactions are converted to and from their protobuf messages in the `convert` module.
+//!
+//! Requests of both transports are signed the same way, see the `auth` module.
+
+pub mod auth;
+mod convert;
+
+pub mod v1 {
+    tonic::include_proto!("zksync.core_api.v1");
+
+    /// Paths of the service methods, the signatures of the requests cover them.
+    pub const SEND_TX_PATH: &str = "/zksync.core_api.v1.CoreApi/SendTx";
+    pub const SEND_TXS_BATCH_PATH: &str = "/zksync.core_api.v1.CoreApi/SendTxsBatch";
+}
@@ -8,6 +8,7 @@ use crate::franklin_crypto::bellman::pairing::Engine;
 use crate::franklin_crypto::bellman::pairing::bn256::{Bn256, Fr};
 use crate::franklin_crypto::rescue::RescueEngine;
 use crate::merkle_tree::hasher::Hasher;
+use crate::merkle_tree::persistent_smt::{MerkleTreeStore, PersistentMerkleTree};
 use crate::merkle_tree::{RescueHasher, SparseMerkleTree};
 use crate::primitives::{GetBits, GetBitsFixed};
 
@@ -16,6 +17,80 @@ pub type CircuitAccountTree = SparseMerkleTree<CircuitAccount<Bn256>, Fr, Rescue
 /// Balance tree for accounts used in the `zksync_circuit`.
 pub type CircuitBalanceTree = SparseMerkleTree<Balance<Bn256>, Fr, RescueHasher<Bn256>>;
 
+/// Account tree the witness of the block is built against: either the whole tree kept in memory,
+/// or the tree read from the key-value store, e.g. the part of the stored account tree
+/// extracted for the accounts of the block.
+pub trait WitnessAccountTree {
+    /// Obtains the account for a certain index.
+    fn get(&self, index: u32) -> Option<CircuitAccount<Bn256>>;
+    /// Inserts the account to the tree.
+    fn insert(&mut self, index: u32, account: CircuitAccount<Bn256>);
+    /// Removes the account with a given index, and returns it (if it existed in the tree).
+    fn remove(&mut self, index: u32) -> Option<CircuitAccount<Bn256>>;
+    /// Returns the Merkle root hash of the tree.
+    fn root_hash(&self) -> Fr;
+    /// Creates a proof of existence for a certain account of the tree.
+    fn merkle_path(&self, index: u32) -> Vec<(Fr, bool)>;
+    /// Returns the capacity of the tree (how many accounts can the tree hold).
+    fn capacity(&self) -> u64;
+}
+
+impl WitnessAccountTree for CircuitAccountTree {
+    fn get(&self, index: u32) -> Option<CircuitAccount<Bn256>> {
+        SparseMerkleTree::get(self, index).cloned()
+    }
+
+    fn insert(&mut self, index: u32, account: CircuitAccount<Bn256>) {
+        SparseMerkleTree::insert(self, index, account)
+    }
+
+    fn remove(&mut self, index: u32) -> Option<CircuitAccount<Bn256>> {
+        SparseMerkleTree::remove(self, index)
+    }
+
+    fn root_hash(&self) -> Fr {
+        SparseMerkleTree::root_hash(self)
+    }
+
+    fn merkle_path(&self, index: u32) -> Vec<(Fr, bool)> {
+        SparseMerkleTree::merkle_path(self, index)
+    }
+
+    fn capacity(&self) -> u64 {
+        SparseMerkleTree::capacity(self)
+    }
+}
+
+impl<S> WitnessAccountTree
+    for PersistentMerkleTree<CircuitAccount<Bn256>, Fr, RescueHasher<Bn256>, S>
+where
+    S: MerkleTreeStore<CircuitAccount<Bn256>, Fr>,
+{
+    fn get(&self, index: u32) -> Option<CircuitAccount<Bn256>> {
+        PersistentMerkleTree::get(self, index).expect("Failed to read the account tree")
+    }
+
+    fn insert(&mut self, index: u32, account: CircuitAccount<Bn256>) {
+        PersistentMerkleTree::insert(self, index, account).expect("Failed to read the account tree")
+    }
+
+    fn remove(&mut self, index: u32) -> Option<CircuitAccount<Bn256>> {
+        PersistentMerkleTree::remove(self, index).expect("Failed to read the account tree")
+    }
+
+    fn root_hash(&self) -> Fr {
+        PersistentMerkleTree::root_hash(self).expect("Failed to read the account tree")
+    }
+
+    fn merkle_path(&self, index: u32) -> Vec<(Fr, bool)> {
+        PersistentMerkleTree::merkle_path(self, index).expect("Failed to read the account tree")
+    }
+
+    fn capacity(&self) -> u64 {
+        PersistentMerkleTree::capacity(self)
+    }
+}
+
 pub fn empty_account_as_field_elements<E: Engine>() -> Vec<E::Fr> {
     let acc = CircuitAccount::<Bn256>::default();
     let bits = acc.get_bits_le();
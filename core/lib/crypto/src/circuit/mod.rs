@@ -1,4 +1,4 @@
-pub use account::CircuitAccountTree;
+pub use account::{CircuitAccountTree, WitnessAccountTree};
 
 pub mod account;
 pub mod utils;
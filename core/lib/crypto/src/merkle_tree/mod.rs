@@ -1,5 +1,6 @@
 pub mod hasher;
 pub mod parallel_smt;
+pub mod persistent_smt;
 pub mod rescue_hasher;
 #[cfg(test)]
 mod tests;
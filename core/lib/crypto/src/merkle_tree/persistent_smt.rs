@@ -0,0 +1,377 @@
+//! Sparse Merkle tree which keeps its items and node hashes in the key-value store,
+//! so the tree doesn't have to fit into memory and doesn't have to be rebuilt on restart.
+//!
+//! Hashes are calculated exactly as in the [`SparseMerkleTree`], so both trees
+//! produce the same root hashes and Merkle paths for the same items.
+//!
+//! [`SparseMerkleTree`]: super::parallel_smt::SparseMerkleTree
+use super::hasher::Hasher;
+use crate::primitives::GetBits;
+
+use fnv::{FnvHashMap, FnvHashSet};
+use std::fmt::Debug;
+
+/// Index of the node in the tree. The root has index 1, and children of the
+/// node `N` have indices `2 * N` and `2 * N + 1`.
+type NodeIndex = u64;
+
+/// Leaf index: 0 <= i < N.
+type ItemIndex = u64;
+
+/// Hashes of the nodes up to this depth are kept in memory, so the hashes near the root,
+/// which are involved in every update, are not read from the store.
+const CACHED_DEPTH: usize = 16;
+
+/// Set of changes to be written to the store atomically.
+#[derive(Debug, Clone)]
+pub struct MerkleTreeBatch<T, Hash> {
+    /// Updated items, `None` means that the item was removed.
+    pub items: Vec<(ItemIndex, Option<T>)>,
+    /// Updated node hashes.
+    pub hashes: Vec<(NodeIndex, Hash)>,
+    /// Version of the tree after the changes, e.g. the number of the block.
+    pub version: u64,
+}
+
+/// Key-value store backing the [`PersistentMerkleTree`].
+pub trait MerkleTreeStore<T, Hash> {
+    /// Loads the item with the given index, if it's present in the store.
+    fn load_item(&self, index: ItemIndex) -> anyhow::Result<Option<T>>;
+    /// Loads the hash of the node with the given index, if it's present in the store.
+    fn load_hash(&self, index: NodeIndex) -> anyhow::Result<Option<Hash>>;
+    /// Loads the indices of all the items present in the store.
+    fn load_indices(&self) -> anyhow::Result<Vec<ItemIndex>>;
+    /// Loads the version of the stored tree, `0` for an empty store.
+    fn load_version(&self) -> anyhow::Result<u64>;
+    /// Atomically applies the set of changes.
+    fn write_batch(&mut self, batch: MerkleTreeBatch<T, Hash>) -> anyhow::Result<()>;
+}
+
+/// Store keeping the whole tree in memory.
+#[derive(Debug, Clone)]
+pub struct MemoryMerkleTreeStore<T, Hash> {
+    items: FnvHashMap<ItemIndex, T>,
+    hashes: FnvHashMap<NodeIndex, Hash>,
+    version: u64,
+}
+
+impl<T, Hash> Default for MemoryMerkleTreeStore<T, Hash> {
+    fn default() -> Self {
+        Self {
+            items: FnvHashMap::default(),
+            hashes: FnvHashMap::default(),
+            version: 0,
+        }
+    }
+}
+
+impl<T: Clone, Hash: Clone> MerkleTreeStore<T, Hash> for MemoryMerkleTreeStore<T, Hash> {
+    fn load_item(&self, index: ItemIndex) -> anyhow::Result<Option<T>> {
+        Ok(self.items.get(&index).cloned())
+    }
+
+    fn load_hash(&self, index: NodeIndex) -> anyhow::Result<Option<Hash>> {
+        Ok(self.hashes.get(&index).cloned())
+    }
+
+    fn load_indices(&self) -> anyhow::Result<Vec<ItemIndex>> {
+        Ok(self.items.keys().copied().collect())
+    }
+
+    fn load_version(&self) -> anyhow::Result<u64> {
+        Ok(self.version)
+    }
+
+    fn write_batch(&mut self, batch: MerkleTreeBatch<T, Hash>) -> anyhow::Result<()> {
+        for (index, item) in batch.items {
+            match item {
+                Some(item) => self.items.insert(index, item),
+                None => self.items.remove(&index),
+            };
+        }
+        self.hashes.extend(batch.hashes);
+        self.version = batch.version;
+        Ok(())
+    }
+}
+
+impl<T, Hash> MemoryMerkleTreeStore<T, Hash> {
+    /// Converts the items of the store, keeping the node hashes as is. The conversion
+    /// must preserve the hashes of the items.
+    pub fn map_items<U>(self, f: impl Fn(T) -> U) -> MemoryMerkleTreeStore<U, Hash> {
+        MemoryMerkleTreeStore {
+            items: self
+                .items
+                .into_iter()
+                .map(|(index, item)| (index, f(item)))
+                .collect(),
+            hashes: self.hashes,
+            version: self.version,
+        }
+    }
+}
+
+/// Sparse Merkle tree stored in the [`MerkleTreeStore`].
+///
+/// Changes are kept in memory until the [`flush`] call, which writes them to the store
+/// in one batch along with the new version of the tree. Unlike the in-memory tree, every
+/// insertion updates the hashes on the path to the root immediately, so the root hash
+/// is always available without recalculation.
+///
+/// Hashes of the nodes close to the root are cached in memory, and the rest of the tree
+/// is read from the store on demand, so the tree doesn't have to fit into memory.
+/// Clones of the tree share the underlying store if the store is shared by its clones,
+/// so only one of them should be flushed.
+///
+/// [`flush`]: PersistentMerkleTree::flush
+#[derive(Debug, Clone)]
+pub struct PersistentMerkleTree<T, Hash, H, S>
+where
+    T: GetBits,
+    Hash: Clone + Debug,
+    H: Hasher<Hash>,
+    S: MerkleTreeStore<T, Hash>,
+{
+    store: S,
+    hasher: H,
+    /// Fixed depth of the tree, determining the overall tree capacity.
+    tree_depth: usize,
+    /// Hashes of the "default" nodes (e.g. ones that are absent in the tree) for every depth.
+    prehashed: Vec<Hash>,
+    /// Items changed since the last flush.
+    dirty_items: FnvHashMap<ItemIndex, Option<T>>,
+    /// Node hashes changed since the last flush.
+    dirty_hashes: FnvHashMap<NodeIndex, Hash>,
+    /// Flushed hashes of the nodes up to `CACHED_DEPTH`.
+    cached_hashes: FnvHashMap<NodeIndex, Hash>,
+    /// Version of the tree as of the last flush.
+    version: u64,
+}
+
+impl<T, Hash, H, S> PersistentMerkleTree<T, Hash, H, S>
+where
+    T: GetBits + Default + Clone,
+    Hash: Clone + Debug,
+    H: Hasher<Hash> + Default,
+    S: MerkleTreeStore<T, Hash>,
+{
+    /// Opens the tree of certain depth stored in the given store.
+    /// An empty store corresponds to an empty tree.
+    pub fn new(store: S, tree_depth: usize) -> anyhow::Result<Self> {
+        assert!(tree_depth > 1);
+        let version = store.load_version()?;
+        let hasher = H::default();
+
+        let mut prehashed = Vec::with_capacity(tree_depth + 1);
+        let mut cur = hasher.hash_bits(T::default().get_bits_le());
+        prehashed.push(cur.clone());
+        for i in 0..tree_depth {
+            cur = hasher.compress(&cur, &cur, i);
+            prehashed.push(cur.clone());
+        }
+        prehashed.reverse();
+
+        let mut cached_hashes = FnvHashMap::default();
+        for index in 1..1 << (tree_depth.min(CACHED_DEPTH) + 1) {
+            if let Some(hash) = store.load_hash(index)? {
+                cached_hashes.insert(index, hash);
+            }
+        }
+
+        Ok(Self {
+            store,
+            hasher,
+            tree_depth,
+            prehashed,
+            dirty_items: FnvHashMap::default(),
+            dirty_hashes: FnvHashMap::default(),
+            cached_hashes,
+            version,
+        })
+    }
+
+    /// Returns the capacity of the tree (how many items can the tree hold).
+    pub fn capacity(&self) -> u64 {
+        1 << self.tree_depth
+    }
+
+    /// Returns the version of the tree as of the last flush.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns the underlying store. Changes made since the last flush are not written to it.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the indices of all the elements of the tree.
+    pub fn indices(&self) -> anyhow::Result<Vec<u32>> {
+        let mut indices: FnvHashSet<ItemIndex> = self.store.load_indices()?.into_iter().collect();
+        for (index, item) in &self.dirty_items {
+            match item {
+                Some(_) => indices.insert(*index),
+                None => indices.remove(index),
+            };
+        }
+
+        Ok(indices.into_iter().map(|index| index as u32).collect())
+    }
+
+    /// Obtains the element for a certain index.
+    pub fn get(&self, index: u32) -> anyhow::Result<Option<T>> {
+        let index = ItemIndex::from(index);
+        match self.dirty_items.get(&index) {
+            Some(item) => Ok(item.clone()),
+            None => self.store.load_item(index),
+        }
+    }
+
+    /// Inserts an element to the tree and updates the hashes up to the root.
+    pub fn insert(&mut self, index: u32, item: T) -> anyhow::Result<()> {
+        let leaf_hash = self.hasher.hash_bits(item.get_bits_le());
+        self.dirty_items.insert(ItemIndex::from(index), Some(item));
+        self.update_path(index, leaf_hash)
+    }
+
+    /// Removes an element with a given index, and returns the removed
+    /// element (if it existed in the tree).
+    pub fn remove(&mut self, index: u32) -> anyhow::Result<Option<T>> {
+        let old = self.get(index)?;
+        let leaf_hash = self.prehashed[self.tree_depth].clone();
+        self.dirty_items.insert(ItemIndex::from(index), None);
+        self.update_path(index, leaf_hash)?;
+        Ok(old)
+    }
+
+    /// Returns the Merkle root hash of the tree.
+    pub fn root_hash(&self) -> anyhow::Result<Hash> {
+        self.node_hash(1, 0)
+    }
+
+    /// Creates a proof of existence for a certain element of the tree.
+    /// The format of the proof is the same as for the in-memory tree: every element
+    /// is the hash of the neighbor node on the current layer and the direction.
+    pub fn merkle_path(&self, index: u32) -> anyhow::Result<Vec<(Hash, bool)>> {
+        assert!(ItemIndex::from(index) < self.capacity());
+
+        let mut cur_index: NodeIndex = (1 << self.tree_depth) + ItemIndex::from(index);
+        let mut proof = Vec::with_capacity(self.tree_depth);
+        for depth in (1..=self.tree_depth).rev() {
+            let neighbor_hash = self.node_hash(cur_index ^ 1, depth)?;
+            let going_right = (cur_index & 1) != 0;
+            proof.push((neighbor_hash, going_right));
+
+            cur_index >>= 1;
+        }
+
+        Ok(proof)
+    }
+
+    /// Copies the part of the tree required to read and update the elements with the given indices
+    /// to the in-memory store: the elements themselves and the hashes of the nodes adjacent to their
+    /// paths to the root. The tree opened over this store has the same root hash and Merkle paths
+    /// of these elements, so they can be updated without reading the rest of the tree.
+    pub fn extract(&self, indices: &[u32]) -> anyhow::Result<MemoryMerkleTreeStore<T, Hash>> {
+        let mut store = MemoryMerkleTreeStore {
+            items: FnvHashMap::default(),
+            hashes: FnvHashMap::default(),
+            version: self.version,
+        };
+        store.hashes.insert(1, self.node_hash(1, 0)?);
+        for &index in indices {
+            assert!(ItemIndex::from(index) < self.capacity());
+            if let Some(item) = self.get(index)? {
+                store.items.insert(ItemIndex::from(index), item);
+            }
+
+            let mut cur_index: NodeIndex = (1 << self.tree_depth) + ItemIndex::from(index);
+            for depth in (1..=self.tree_depth).rev() {
+                for node in &[cur_index, cur_index ^ 1] {
+                    if !store.hashes.contains_key(node) {
+                        store.hashes.insert(*node, self.node_hash(*node, depth)?);
+                    }
+                }
+                cur_index >>= 1;
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// Writes all the changes made since the previous flush to the store,
+    /// marking the stored tree with the given version.
+    pub fn flush(&mut self, version: u64) -> anyhow::Result<()> {
+        if self.dirty_items.is_empty() && self.dirty_hashes.is_empty() && self.version == version {
+            return Ok(());
+        }
+
+        let batch = MerkleTreeBatch {
+            items: self.dirty_items.drain().collect(),
+            hashes: self.dirty_hashes.drain().collect(),
+            version,
+        };
+        let cached_hashes = batch
+            .hashes
+            .iter()
+            .filter(|(index, _)| Self::is_cached(*index))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.store.write_batch(batch)?;
+
+        self.cached_hashes.extend(cached_hashes);
+        self.version = version;
+        Ok(())
+    }
+
+    /// Flushes the pending changes with the current version and returns the underlying store.
+    pub fn into_store(mut self) -> anyhow::Result<S> {
+        self.flush(self.version)?;
+        Ok(self.store)
+    }
+
+    /// Sets the hash of the leaf and recalculates the hashes of all its parents.
+    fn update_path(&mut self, index: u32, leaf_hash: Hash) -> anyhow::Result<()> {
+        assert!(ItemIndex::from(index) < self.capacity());
+
+        let mut cur_index: NodeIndex = (1 << self.tree_depth) + ItemIndex::from(index);
+        self.dirty_hashes.insert(cur_index, leaf_hash);
+
+        // Parent of the node at the depth `D + 1` is located at the depth `D`.
+        for depth in (0..self.tree_depth).rev() {
+            let parent_index = cur_index >> 1;
+            let lhs = self.node_hash(parent_index * 2, depth + 1)?;
+            let rhs = self.node_hash(parent_index * 2 + 1, depth + 1)?;
+
+            // Level is used by hasher for personalization.
+            let level = self.tree_depth - depth - 1;
+            let parent_hash = self.hasher.compress(&lhs, &rhs, level);
+            self.dirty_hashes.insert(parent_index, parent_hash);
+
+            cur_index = parent_index;
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the hash of the node is kept in memory after the flush.
+    fn is_cached(index: NodeIndex) -> bool {
+        index < 1 << (CACHED_DEPTH + 1)
+    }
+
+    /// Obtains the hash of the node located at the given depth. Nodes absent both in memory
+    /// and in the store have the precomputed hash for their depth.
+    fn node_hash(&self, index: NodeIndex, depth: usize) -> anyhow::Result<Hash> {
+        if let Some(hash) = self.dirty_hashes.get(&index) {
+            return Ok(hash.clone());
+        }
+        if let Some(hash) = self.cached_hashes.get(&index) {
+            return Ok(hash.clone());
+        }
+
+        Ok(self
+            .store
+            .load_hash(index)?
+            .unwrap_or_else(|| self.prehashed[depth].clone()))
+    }
+}
@@ -1,4 +1,8 @@
-use crate::merkle_tree::{parallel_smt, RescueHasher};
+use crate::merkle_tree::{
+    parallel_smt,
+    persistent_smt::{MemoryMerkleTreeStore, PersistentMerkleTree},
+    RescueHasher,
+};
 use crate::rand::{Rng, SeedableRng, XorShiftRng};
 use crate::{Engine, Fr};
 use serde::{Deserialize, Serialize};
@@ -167,3 +171,102 @@ fn big_test() {
     let root_hash: Fr = crate::ff::from_hex(&input.root_hash).unwrap();
     assert_eq!(root_hash, tree.root_hash());
 }
+
+type TestPersistentTree =
+    PersistentMerkleTree<u64, Fr, RescueHasher<Engine>, MemoryMerkleTreeStore<u64, Fr>>;
+
+/// Checks that the persistent tree produces the same root hashes and paths as the in-memory one,
+/// and that the tree reopened from the store has the same state.
+#[test]
+fn persistent_tree_matches_in_memory_tree() -> anyhow::Result<()> {
+    let depth = 6;
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut tree = parallel_smt::SparseMerkleTree::<u64, Fr, RescueHasher<Engine>>::new(depth);
+    let mut persistent_tree = TestPersistentTree::new(MemoryMerkleTreeStore::default(), depth)?;
+    assert_eq!(tree.root_hash(), persistent_tree.root_hash()?);
+    assert_eq!(persistent_tree.version(), 0);
+
+    for _ in 0..50 {
+        let idx = rng.gen_range(0, 1 << depth);
+        let item = rng.gen::<u64>();
+
+        tree.insert(idx, item);
+        persistent_tree.insert(idx, item)?;
+        assert_eq!(tree.root_hash(), persistent_tree.root_hash()?);
+        assert_eq!(tree.merkle_path(idx), persistent_tree.merkle_path(idx)?);
+    }
+
+    // Remove some of the items and flush the changes in between.
+    persistent_tree.flush(1)?;
+    for idx in 0..10 {
+        assert_eq!(tree.remove(idx), persistent_tree.remove(idx)?);
+        assert_eq!(tree.root_hash(), persistent_tree.root_hash()?);
+    }
+
+    // Reopen the tree from the store.
+    let store = persistent_tree.into_store()?;
+    let persistent_tree = TestPersistentTree::new(store, depth)?;
+    assert_eq!(tree.root_hash(), persistent_tree.root_hash()?);
+    assert_eq!(persistent_tree.version(), 1);
+
+    let mut indices = persistent_tree.indices()?;
+    indices.sort_unstable();
+    let mut expected_indices = tree
+        .items
+        .keys()
+        .filter(|idx| **idx >= 10)
+        .map(|idx| *idx as u32)
+        .collect::<Vec<_>>();
+    expected_indices.sort_unstable();
+    assert_eq!(indices, expected_indices);
+    for idx in 0..(1 << depth) {
+        // In-memory tree keeps the default items in place of the removed ones.
+        if idx >= 10 {
+            assert_eq!(tree.get(idx).copied(), persistent_tree.get(idx)?);
+        }
+        assert_eq!(tree.merkle_path(idx), persistent_tree.merkle_path(idx)?);
+    }
+
+    Ok(())
+}
+
+/// Checks that the part of the persistent tree extracted for some items can be used
+/// to update these items instead of the whole tree.
+#[test]
+fn extracted_persistent_tree_part() -> anyhow::Result<()> {
+    let depth = 6;
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut persistent_tree = TestPersistentTree::new(MemoryMerkleTreeStore::default(), depth)?;
+    for _ in 0..50 {
+        let idx = rng.gen_range(0, 1 << depth);
+        persistent_tree.insert(idx, rng.gen::<u64>())?;
+    }
+
+    // Both the present and the absent items can be updated.
+    persistent_tree.insert(3, rng.gen::<u64>())?;
+    persistent_tree.remove(40)?;
+    let indices = [3, 17, 18, 40];
+    let mut extracted_tree = TestPersistentTree::new(persistent_tree.extract(&indices)?, depth)?;
+    assert_eq!(persistent_tree.root_hash()?, extracted_tree.root_hash()?);
+    for &idx in &indices {
+        assert_eq!(persistent_tree.get(idx)?, extracted_tree.get(idx)?);
+        assert_eq!(
+            persistent_tree.merkle_path(idx)?,
+            extracted_tree.merkle_path(idx)?
+        );
+    }
+
+    for &idx in &indices {
+        let item = rng.gen::<u64>();
+        persistent_tree.insert(idx, item)?;
+        extracted_tree.insert(idx, item)?;
+        assert_eq!(persistent_tree.root_hash()?, extracted_tree.root_hash()?);
+    }
+    persistent_tree.remove(indices[0])?;
+    extracted_tree.remove(indices[0])?;
+    assert_eq!(persistent_tree.root_hash()?, extracted_tree.root_hash()?);
+
+    Ok(())
+}
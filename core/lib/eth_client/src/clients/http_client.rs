@@ -9,8 +9,8 @@ use web3::{
     },
     transports::Http,
     types::{
-        Address, BlockId, BlockNumber, Bytes, Filter, Log, TransactionReceipt, H160, H256, U256,
-        U64,
+        Address, BlockId, BlockNumber, Bytes, CallRequest, Filter, Log, TransactionReceipt, H160,
+        H256, U256, U64,
     },
     Web3,
 };
@@ -31,7 +31,7 @@ pub struct ETHDirectClient<S: EthereumSigner> {
     sender_account: Address,
     pub contract_addr: H160,
     contract: ethabi::Contract,
-    pub chain_id: u8,
+    pub chain_id: u64,
     pub gas_price_factor: f64,
     // It's public only for testkit
     // TODO avoid public (ZKS-376)
@@ -58,7 +58,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         operator_eth_addr: H160,
         eth_signer: S,
         contract_eth_addr: H160,
-        chain_id: u8,
+        chain_id: u64,
         gas_price_factor: f64,
     ) -> Self {
         Self {
@@ -109,6 +109,19 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(block_number)
     }
 
+    /// Returns the chain ID reported by the Ethereum node.
+    pub async fn node_chain_id(&self) -> Result<u64, anyhow::Error> {
+        let start = Instant::now();
+        let chain_id = self.web3.eth().chain_id().await?;
+        anyhow::ensure!(
+            chain_id <= U256::from(u64::MAX),
+            "Chain ID {} is out of range",
+            chain_id
+        );
+        metrics::histogram!("eth_client.direct.node_chain_id", start.elapsed());
+        Ok(chain_id.as_u64())
+    }
+
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         let start = Instant::now();
         let mut network_gas_price = self.web3.eth().gas_price().await?;
@@ -187,6 +200,21 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         })
     }
 
+    pub async fn estimate_gas(&self, data: Vec<u8>) -> Result<U256, anyhow::Error> {
+        let start = Instant::now();
+        let request = CallRequest {
+            from: Some(self.sender_account),
+            to: Some(self.contract_addr),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(Bytes(data)),
+        };
+        let gas = self.web3.eth().estimate_gas(request, None).await?;
+        metrics::histogram!("eth_client.direct.estimate_gas", start.elapsed());
+        Ok(gas)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         let start = Instant::now();
         let tx = self.web3.eth().send_raw_transaction(Bytes(tx)).await?;
@@ -262,6 +290,13 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(balance)
     }
 
+    pub async fn code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        let start = Instant::now();
+        let code = self.web3.eth().code(address, None).await?;
+        metrics::histogram!("eth_client.direct.code", start.elapsed());
+        Ok(code.0)
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         self.eth_balance(self.sender_account).await
     }
@@ -353,6 +388,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     .saturating_sub(tx_block_number)
                     .as_u64();
                 let success = status.as_u64() == 1;
+                let gas_used = receipt.as_ref().and_then(|receipt| receipt.gas_used);
 
                 // Set the receipt only for failures.
                 let receipt = if success {
@@ -365,6 +401,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
                 }))
             }
             _ => Ok(None),
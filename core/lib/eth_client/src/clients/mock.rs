@@ -19,7 +19,9 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct MockEthereum {
     pub block_number: u64,
+    pub chain_id: u64,
     pub gas_price: U256,
+    pub gas_estimation: U256,
     pub tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     pub sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
 }
@@ -28,7 +30,9 @@ impl Default for MockEthereum {
     fn default() -> Self {
         Self {
             block_number: 1,
+            chain_id: 9,
             gas_price: 100.into(),
+            gas_estimation: 1_000_000.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
         }
@@ -72,6 +76,7 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
         };
         self.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -84,6 +89,7 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
         };
         self.tx_statuses.write().await.insert(*hash, status);
     }
@@ -95,10 +101,18 @@ impl MockEthereum {
         Ok(self.block_number.into())
     }
 
+    pub async fn node_chain_id(&self) -> anyhow::Result<u64> {
+        Ok(self.chain_id)
+    }
+
     pub async fn get_gas_price(&self) -> anyhow::Result<U256> {
         Ok(self.gas_price)
     }
 
+    pub async fn estimate_gas(&self, _data: Vec<u8>) -> anyhow::Result<U256> {
+        Ok(self.gas_estimation)
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         // Cut hash of transaction
         let mut hash: [u8; 32] = Default::default();
@@ -168,6 +182,10 @@ impl MockEthereum {
         unreachable!()
     }
 
+    pub async fn code(&self, _address: Address) -> Result<Vec<u8>, Error> {
+        unreachable!()
+    }
+
     pub async fn contract_balance(
         &self,
         _token_address: Address,
@@ -66,6 +66,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, block_number());
     }
 
+    pub async fn node_chain_id(&self) -> Result<u64, anyhow::Error> {
+        multiple_call!(self, node_chain_id());
+    }
+
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         multiple_call!(self, get_gas_price());
     }
@@ -94,6 +98,10 @@ impl MultiplexerEthereumClient {
         );
     }
 
+    pub async fn estimate_gas(&self, data: Vec<u8>) -> Result<U256, anyhow::Error> {
+        multiple_call!(self, estimate_gas(data));
+    }
+
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
         multiple_call!(self, send_raw_tx(tx));
     }
@@ -116,6 +124,10 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, eth_balance(address));
     }
 
+    pub async fn code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        multiple_call!(self, code(address));
+    }
+
     pub async fn allowance(
         &self,
         token_address: Address,
@@ -3,7 +3,7 @@ use web3::contract::Options;
 use web3::types::{Address, BlockId, Filter, Log, U64};
 
 use std::fmt::Debug;
-use zksync_config::ZkSyncConfig;
+use zksync_config::{configs::eth_sender::OperatorAccount, ZkSyncConfig};
 use zksync_contracts::zksync_contract;
 use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{TransactionReceipt, H160, H256, U256};
@@ -30,6 +30,8 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction, if reported by the node.
+    pub gas_used: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -49,14 +51,20 @@ pub enum EthereumGateway {
 
 impl EthereumGateway {
     pub fn from_config(config: &ZkSyncConfig) -> Self {
+        Self::from_config_with_account(config, config.eth_sender.sender.commit_account())
+    }
+
+    /// Creates a gateway signing the transactions with the key of the provided account
+    /// instead of the operator one.
+    pub fn from_config_with_account(config: &ZkSyncConfig, account: OperatorAccount) -> Self {
         if config.eth_client.web3_url.len() == 1 {
             let transport = web3::transports::Http::new(&config.eth_client.web3_url()).unwrap();
 
             EthereumGateway::Direct(ETHDirectClient::new(
                 transport,
                 zksync_contract(),
-                config.eth_sender.sender.operator_commit_eth_addr,
-                PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                account.address,
+                PrivateKeySigner::new(account.private_key),
                 config.contracts.contract_addr,
                 config.eth_client.chain_id,
                 config.eth_client.gas_price_factor,
@@ -72,8 +80,8 @@ impl EthereumGateway {
                     ETHDirectClient::new(
                         transport,
                         contract.clone(),
-                        config.eth_sender.sender.operator_commit_eth_addr,
-                        PrivateKeySigner::new(config.eth_sender.sender.operator_private_key),
+                        account.address,
+                        PrivateKeySigner::new(account.private_key),
                         config.contracts.contract_addr,
                         config.eth_client.chain_id,
                         config.eth_client.gas_price_factor,
@@ -126,6 +134,38 @@ impl EthereumGateway {
         delegate_call!(self.block_number())
     }
 
+    /// Returns the chain ID of the network the Ethereum node is connected to.
+    pub async fn node_chain_id(&self) -> Result<u64, anyhow::Error> {
+        delegate_call!(self.node_chain_id())
+    }
+
+    /// Checks that the Ethereum node is connected to the network with the expected chain ID.
+    /// Transactions are signed with the configured chain ID, so they will be rejected
+    /// (or, what is worse, replayed) if it doesn't match the actual network.
+    pub async fn check_chain_id(&self, expected_chain_id: u64) -> Result<(), anyhow::Error> {
+        let node_chain_id = self.node_chain_id().await?;
+        anyhow::ensure!(
+            node_chain_id == expected_chain_id,
+            "Ethereum node is connected to the network with chain ID {}, while chain ID {} is configured",
+            node_chain_id,
+            expected_chain_id
+        );
+        Ok(())
+    }
+
+    /// Checks that the configured L1 chain ID matches the preset of the chosen network (if any),
+    /// and that the Ethereum node is connected to this network.
+    pub async fn check_l1_network(&self, config: &ZkSyncConfig) -> Result<(), anyhow::Error> {
+        let network = config.chain.eth.network;
+        anyhow::ensure!(
+            !network.has_known_chain_id() || network.chain_id() == config.eth_client.chain_id,
+            "Configured L1 chain ID {} doesn't match the chain ID of the {} network",
+            config.eth_client.chain_id,
+            network
+        );
+        self.check_chain_id(config.eth_client.chain_id).await
+    }
+
     pub async fn get_gas_price(&self) -> Result<U256, anyhow::Error> {
         delegate_call!(self.get_gas_price())
     }
@@ -155,6 +195,12 @@ impl EthereumGateway {
         delegate_call!(self.sign_prepared_tx_for_addr(data, contract_addr, options))
     }
 
+    /// Estimates the amount of gas required to execute the call of the zkSync
+    /// contract with the given encoded data on behalf of the operator account.
+    pub async fn estimate_gas(&self, data: Vec<u8>) -> Result<U256, anyhow::Error> {
+        delegate_call!(self.estimate_gas(data))
+    }
+
     /// Sends the transaction to the Ethereum blockchain.
     /// Transaction is expected to be encoded as the byte sequence.
     pub async fn send_raw_tx(&self, tx: Vec<u8>) -> Result<H256, anyhow::Error> {
@@ -181,6 +227,11 @@ impl EthereumGateway {
         delegate_call!(self.eth_balance(address))
     }
 
+    /// Returns the code of the account on Ethereum, which is empty unless the account is a contract.
+    pub async fn code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        delegate_call!(self.code(address))
+    }
+
     pub async fn allowance(
         &self,
         token_address: Address,
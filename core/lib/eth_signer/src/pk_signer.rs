@@ -82,4 +82,25 @@ mod test {
         ];
         assert_eq!(signature, precalculated_signature);
     }
+
+    /// Checks that the chain IDs of the custom networks, which don't fit into a byte,
+    /// are encoded into the signature according to EIP-155.
+    #[tokio::test]
+    async fn test_signature_with_large_chain_id() {
+        let signer = PrivateKeySigner::new(H256::from([5; 32]));
+        let chain_id = 31337;
+        let raw_transaction = RawTransaction {
+            chain_id,
+            nonce: U256::from(1),
+            to: Some(H160::zero()),
+            value: U256::from(10),
+            gas_price: U256::from(1),
+            gas: U256::from(2),
+            data: vec![1, 2, 3],
+        };
+        let signature = signer.sign_transaction(raw_transaction).await.unwrap();
+
+        let v: u64 = rlp::Rlp::new(&signature).val_at(6).unwrap();
+        assert!(v == chain_id * 2 + 35 || v == chain_id * 2 + 36);
+    }
 }
@@ -6,8 +6,8 @@ use zksync_types::{H160, U256};
 /// Description of a Transaction, pending or in the chain.
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct RawTransaction {
-    /// Chain id: mainnet => 1, rinkeby => 4, ropsten => 43, etc.
-    pub chain_id: u8,
+    /// Chain id: mainnet => 1, rinkeby => 4, ropsten => 3, etc.
+    pub chain_id: u64,
     /// Nonce
     pub nonce: U256,
     /// Recipient (None when contract creation)
@@ -57,7 +57,7 @@ impl RawTransaction {
         let mut hash = RlpStream::new();
         hash.begin_unbounded_list();
         self.encode(&mut hash);
-        hash.append(&vec![self.chain_id]);
+        hash.append(&self.chain_id);
         hash.append(&U256::zero());
         hash.append(&U256::zero());
         hash.finalize_unbounded_list();
@@ -78,16 +78,18 @@ impl RawTransaction {
     }
 }
 
-fn to_ecdsa(sig: Signature, chain_id: u8) -> EcdsaSig {
+/// Converts the signature to the EIP-155 form, `v` includes the chain ID, so the transaction
+/// can't be replayed on another network.
+fn to_ecdsa(sig: Signature, chain_id: u64) -> EcdsaSig {
     EcdsaSig {
-        v: vec![sig.v() as u8 + chain_id * 2 + 35],
+        v: u64::from(sig.v()) + chain_id * 2 + 35,
         r: sig.r().to_vec(),
         s: sig.s().to_vec(),
     }
 }
 
 pub struct EcdsaSig {
-    v: Vec<u8>,
+    v: u64,
     r: Vec<u8>,
     s: Vec<u8>,
 }
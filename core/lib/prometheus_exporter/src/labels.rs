@@ -0,0 +1,72 @@
+//! Static labels attached to every metric emitted by the process.
+
+use metrics::{Key, KeyData, Label, Recorder, Unit};
+use zksync_config::configs::api::Prometheus;
+
+/// Recorder wrapper adding the configured static labels (instance, environment, network)
+/// to every metric before passing it to the inner recorder.
+pub struct StaticLabelsRecorder<R> {
+    inner: R,
+    labels: Vec<Label>,
+}
+
+impl<R: Recorder> StaticLabelsRecorder<R> {
+    pub fn new(inner: R, config: &Prometheus) -> Self {
+        let labels = [
+            ("instance", &config.instance),
+            ("environment", &config.environment),
+            ("network", &config.network),
+        ]
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .as_ref()
+                .map(|value| Label::new(*name, value.to_string()))
+        })
+        .collect();
+
+        Self { inner, labels }
+    }
+
+    fn labeled(&self, key: Key) -> Key {
+        if self.labels.is_empty() {
+            return key;
+        }
+
+        let labels: Vec<Label> = key
+            .labels()
+            .cloned()
+            .chain(self.labels.iter().cloned())
+            .collect();
+        KeyData::from_parts(key.name().clone(), labels).into()
+    }
+}
+
+impl<R: Recorder> Recorder for StaticLabelsRecorder<R> {
+    fn register_counter(&self, key: Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_counter(self.labeled(key), unit, description)
+    }
+
+    fn register_gauge(&self, key: Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_gauge(self.labeled(key), unit, description)
+    }
+
+    fn register_histogram(&self, key: Key, unit: Option<Unit>, description: Option<&'static str>) {
+        self.inner
+            .register_histogram(self.labeled(key), unit, description)
+    }
+
+    fn increment_counter(&self, key: Key, value: u64) {
+        self.inner.increment_counter(self.labeled(key), value)
+    }
+
+    fn update_gauge(&self, key: Key, value: f64) {
+        self.inner.update_gauge(self.labeled(key), value)
+    }
+
+    fn record_histogram(&self, key: Key, value: u64) {
+        self.inner.record_histogram(self.labeled(key), value)
+    }
+}
@@ -3,22 +3,31 @@
 use metrics_exporter_prometheus::PrometheusBuilder;
 use std::{thread, time::Duration};
 use tokio::task::JoinHandle;
+use zksync_config::configs::api::Prometheus;
 use zksync_storage::ConnectionPool;
 use zksync_types::ActionType::*;
 
+use self::labels::StaticLabelsRecorder;
+
+mod labels;
+
 const QUERY_INTERVAL: Duration = Duration::from_secs(60);
 
 pub fn run_prometheus_exporter(
     connection_pool: ConnectionPool,
-    port: u16,
+    config: &Prometheus,
     is_operation_counter_needed: bool,
 ) -> (JoinHandle<()>, Option<JoinHandle<()>>) {
-    let addr = ([0, 0, 0, 0], port);
-    let (recorder, exporter) = PrometheusBuilder::new()
-        .listen_address(addr)
+    let addr = ([0, 0, 0, 0], config.port);
+    let mut builder = PrometheusBuilder::new().listen_address(addr);
+    if let Some(buckets) = &config.histogram_buckets {
+        builder = builder.set_buckets(buckets);
+    }
+    let (recorder, exporter) = builder
         .build_with_exporter()
         .expect("failed to install Prometheus recorder");
-    metrics::set_boxed_recorder(Box::new(recorder)).expect("failed to set metrics recorder");
+    metrics::set_boxed_recorder(Box::new(StaticLabelsRecorder::new(recorder, config)))
+        .expect("failed to set metrics recorder");
 
     let prometheus_handle = tokio::spawn(async move {
         tokio::pin!(exporter);
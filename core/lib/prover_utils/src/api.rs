@@ -1,10 +1,70 @@
+use crate::fs_utils::get_block_verification_key_path;
+use crypto::{digest::Digest, sha2::Sha256};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use zksync_crypto::proof::EncodedProofPlonk;
 
+/// Version of the protocol between the provers and the prover server.
+/// Must be increased on every incompatible change of the API.
+pub const PROVER_PROTOCOL_VERSION: u32 = 1;
+
+/// Versions the prover was built against. Provers with a version different from
+/// the one of the server are not allowed to take jobs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverVersion {
+    pub protocol_version: u32,
+    /// Identifier of the circuit, which is the hash of its verification keys,
+    /// e.g. `vk-1f2e3d4c5b6a7980`.
+    pub circuit_version: String,
+}
+
+impl ProverVersion {
+    /// Creates the version of the circuit with the verification keys of the given block sizes.
+    ///
+    /// The circuit is identified by the keys themselves rather than by the directory they are
+    /// stored in, so the provers are compatible with the server only if they use the same keys.
+    pub fn from_verification_keys(block_chunks_sizes: &[usize]) -> Result<Self, anyhow::Error> {
+        let mut block_chunks_sizes = block_chunks_sizes.to_vec();
+        block_chunks_sizes.sort_unstable();
+        block_chunks_sizes.dedup();
+
+        let mut hasher = Sha256::new();
+        for block_chunks in block_chunks_sizes {
+            let path = get_block_verification_key_path(block_chunks);
+            let key = std::fs::read(&path).map_err(|err| {
+                anyhow::format_err!(
+                    "Failed to read the verification key {}: {}",
+                    path.display(),
+                    err
+                )
+            })?;
+            hasher.input(&key);
+        }
+
+        Ok(Self {
+            protocol_version: PROVER_PROTOCOL_VERSION,
+            circuit_version: format!("vk-{}", &hasher.result_str()[..16]),
+        })
+    }
+}
+
+impl fmt::Display for ProverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "protocol v{}, circuit {}",
+            self.protocol_version, self.circuit_version
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ProverReq {
     pub name: String,
     pub block_size: usize,
+    /// Provers not reporting the version are considered incompatible.
+    #[serde(default)]
+    pub version: Option<ProverVersion>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,8 +78,36 @@ pub struct WorkingOnReq {
     pub prover_run_id: i32,
 }
 
+/// Report of the prover that failed to create or verify the proof for the block.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobFailedReq {
+    pub prover_run_id: i32,
+    pub block: u32,
+    pub error: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PublishReq {
     pub block: u32,
     pub proof: EncodedProofPlonk,
 }
+
+/// Maximum size of the proof part uploaded in a single request.
+pub const PROOF_CHUNK_SIZE: usize = 1 << 20;
+
+/// Part of the serialized proof uploaded to the server. The chunk data itself is sent as the request body.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofChunkReq {
+    pub prover_run_id: i32,
+    pub block: u32,
+    /// Size of the whole serialized proof in bytes.
+    pub total_size: u64,
+    /// Position of the chunk in the serialized proof.
+    pub offset: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofChunkRes {
+    /// Amount of the proof bytes received by the server. Upload should be continued from this offset.
+    pub uploaded: u64,
+}
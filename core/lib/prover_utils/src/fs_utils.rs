@@ -19,16 +19,30 @@ pub fn get_keys_root_dir() -> PathBuf {
     out_dir
 }
 
-fn base_universal_setup_dir() -> Result<PathBuf, anyhow::Error> {
+/// Returns the directory containing all the keys: universal setup and circuit keys.
+pub fn get_base_keys_dir() -> PathBuf {
     let mut dir = PathBuf::new();
     // root is used by default for provers
     dir.push(&std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| "/".to_owned()));
     dir.push("keys");
+    dir
+}
+
+fn base_universal_setup_dir() -> Result<PathBuf, anyhow::Error> {
+    let mut dir = get_base_keys_dir();
     dir.push("setup");
     anyhow::ensure!(dir.exists(), "Universal setup dir does not exits");
     Ok(dir)
 }
 
+/// Returns path to the universal setup in the monomial form of the given power of two.
+pub fn get_universal_setup_monomial_form_path(power_of_two: u32) -> PathBuf {
+    let mut path = get_base_keys_dir();
+    path.push("setup");
+    path.push(&format!("setup_2^{}.key", power_of_two));
+    path
+}
+
 fn get_universal_setup_file_buff_reader(
     setup_file_name: &str,
 ) -> Result<BufReader<File>, anyhow::Error> {
@@ -0,0 +1,269 @@
+//! Provisioning of the universal setup and verification keys.
+//!
+//! Keys are downloaded from the configured network location, which is expected to have
+//! the same layout as the local `keys` directory (e.g. `setup/setup_2^21.key`) and to
+//! contain the `SHA256SUMS` manifest in the format of the `sha256sum` utility.
+//! Every downloaded file is checked against the manifest, and the files already present
+//! locally are reused if their checksum matches.
+
+// Built-in uses
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+// External uses
+use anyhow::format_err;
+use backoff::Operation;
+use crypto::{digest::Digest, sha2::Sha256};
+// Workspace uses
+use zksync_config::configs::{chain::Circuit, prover::Keys};
+// Local uses
+use crate::fs_utils::{
+    get_base_keys_dir, get_block_verification_key_path, get_universal_setup_monomial_form_path,
+};
+use crate::network_utils::get_backoff;
+
+/// Name of the manifest with the key files checksums.
+const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// Key file required to work with the blocks of the configured sizes.
+#[derive(Debug, Clone, PartialEq)]
+struct KeyFile {
+    /// Path relative to the keys directory, which is also used to locate the file on the network.
+    name: String,
+    /// Local path of the file.
+    path: PathBuf,
+}
+
+impl KeyFile {
+    fn new(path: PathBuf) -> Result<Self, anyhow::Error> {
+        let name = path
+            .strip_prefix(get_base_keys_dir())
+            .map_err(|_| format_err!("Key file {} is not in the keys directory", path.display()))?
+            .to_string_lossy()
+            .into_owned();
+
+        Ok(Self { name, path })
+    }
+}
+
+/// Makes sure that the universal setup and the verification keys for the given block sizes
+/// are available locally, downloading them if needed.
+///
+/// Does nothing if the keys downloading is disabled in the config.
+pub fn provision_keys(
+    config: &Keys,
+    circuit: &Circuit,
+    block_chunk_sizes: &[usize],
+) -> Result<(), anyhow::Error> {
+    if !config.download {
+        return Ok(());
+    }
+
+    let key_files = required_key_files(circuit, block_chunk_sizes)?;
+    let checksums = retry("checksums", || download_checksums(&config.network_url))?;
+
+    for key_file in key_files {
+        let checksum = checksums.get(&key_file.name).ok_or_else(|| {
+            format_err!(
+                "Key file {} is absent in the checksums manifest",
+                key_file.name
+            )
+        })?;
+
+        if key_file.path.exists() && file_checksum(&key_file.path)? == *checksum {
+            vlog::info!("Using cached key file {}", key_file.name);
+            continue;
+        }
+
+        retry(&key_file.name, || {
+            download_key_file(&config.network_url, &key_file, checksum)
+        })?;
+        vlog::info!("Key file {} is downloaded", key_file.name);
+    }
+
+    Ok(())
+}
+
+/// Returns the list of the key files needed to prove and verify blocks of the given sizes.
+fn required_key_files(
+    circuit: &Circuit,
+    block_chunk_sizes: &[usize],
+) -> Result<Vec<KeyFile>, anyhow::Error> {
+    let mut setup_powers = BTreeSet::new();
+    let mut verification_keys = Vec::new();
+
+    for &block_chunks in block_chunk_sizes {
+        let idx = circuit
+            .supported_block_chunks_sizes
+            .iter()
+            .position(|&size| size == block_chunks)
+            .ok_or_else(|| format_err!("Block size {} is not supported", block_chunks))?;
+        setup_powers.insert(circuit.supported_block_chunks_sizes_setup_powers[idx] as u32);
+
+        verification_keys.push(KeyFile::new(get_block_verification_key_path(block_chunks))?);
+    }
+
+    let mut key_files = setup_powers
+        .into_iter()
+        .map(|power_of_two| KeyFile::new(get_universal_setup_monomial_form_path(power_of_two)))
+        .collect::<Result<Vec<_>, _>>()?;
+    key_files.extend(verification_keys);
+
+    Ok(key_files)
+}
+
+/// Parses the manifest in the `sha256sum` format into the mapping from the file name to its checksum.
+fn parse_checksums(manifest: &str) -> Result<HashMap<String, String>, anyhow::Error> {
+    manifest
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(checksum), Some(name)) => Ok((
+                    name.trim_start_matches('*').to_owned(),
+                    checksum.to_lowercase(),
+                )),
+                _ => Err(format_err!("Invalid checksums manifest line: {}", line)),
+            }
+        })
+        .collect()
+}
+
+fn file_url(network_url: &str, name: &str) -> String {
+    format!(
+        "{}/{}",
+        network_url.trim_end_matches('/'),
+        name.replace('^', "%5E")
+    )
+}
+
+fn download_checksums(
+    network_url: &str,
+) -> Result<HashMap<String, String>, backoff::Error<anyhow::Error>> {
+    let manifest = reqwest::blocking::get(&file_url(network_url, CHECKSUMS_FILE))
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| backoff::Error::Transient(e.into()))?;
+
+    parse_checksums(&manifest).map_err(backoff::Error::Permanent)
+}
+
+/// Downloads the key file into the temporary file, and moves it to the destination
+/// if the checksum of the downloaded data matches the expected one.
+fn download_key_file(
+    network_url: &str,
+    key_file: &KeyFile,
+    expected_checksum: &str,
+) -> Result<(), backoff::Error<anyhow::Error>> {
+    let url = file_url(network_url, &key_file.name);
+    vlog::info!("Downloading key file from {}", url);
+
+    let mut response = reqwest::blocking::get(&url)
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| backoff::Error::Transient(e.into()))?;
+
+    let mut download_path = key_file.path.clone().into_os_string();
+    download_path.push(".download");
+    let download_path = PathBuf::from(download_path);
+
+    let checksum =
+        save_key_file(&mut response, &download_path).map_err(backoff::Error::Transient)?;
+
+    if checksum != expected_checksum {
+        let _ = fs::remove_file(&download_path);
+        return Err(backoff::Error::Transient(format_err!(
+            "Checksum mismatch for the key file {}: expected {}, got {}",
+            key_file.name,
+            expected_checksum,
+            checksum
+        )));
+    }
+
+    fs::rename(&download_path, &key_file.path).map_err(|e| backoff::Error::Permanent(e.into()))
+}
+
+fn save_key_file(input: &mut impl Read, path: &Path) -> Result<String, anyhow::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = File::create(path)?;
+    copy_with_checksum(input, Some(&mut file))
+}
+
+fn file_checksum(path: &Path) -> Result<String, anyhow::Error> {
+    copy_with_checksum(&mut File::open(path)?, None)
+}
+
+/// Reads the data until the end, optionally copying it to the output, and returns its SHA-256 checksum.
+fn copy_with_checksum(
+    input: &mut impl Read,
+    mut output: Option<&mut File>,
+) -> Result<String, anyhow::Error> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+
+        hasher.input(&buffer[..read]);
+        if let Some(output) = output.as_mut() {
+            output.write_all(&buffer[..read])?;
+        }
+    }
+
+    Ok(hasher.result_str())
+}
+
+fn retry<T>(
+    name: &str,
+    mut operation: impl FnMut() -> Result<T, backoff::Error<anyhow::Error>>,
+) -> Result<T, anyhow::Error> {
+    operation
+        .retry_notify(&mut get_backoff(), |err, next_after: Duration| {
+            let duration_secs = next_after.as_millis() as f32 / 1000.0f32;
+
+            vlog::warn!(
+                "Failed to download {} err: <{}>, retrying after: {:.1}s",
+                name,
+                err,
+                duration_secs,
+            )
+        })
+        .map_err(|e| format_err!("Can't download {}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_manifest() {
+        let manifest = "\
+            ABCD  setup/setup_2^21.key\n\
+            \n\
+            0123 *plonk-975ae851/account-32_balance-11/verification_block_6.key\n";
+
+        let checksums = parse_checksums(manifest).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["setup/setup_2^21.key"], "abcd");
+        assert_eq!(
+            checksums["plonk-975ae851/account-32_balance-11/verification_block_6.key"],
+            "0123"
+        );
+
+        assert!(parse_checksums("abcd").is_err());
+    }
+
+    #[test]
+    fn network_file_url() {
+        assert_eq!(
+            file_url("https://keys.example/", "setup/setup_2^21.key"),
+            "https://keys.example/setup/setup_2%5E21.key"
+        );
+    }
+}
@@ -18,6 +18,7 @@ use zksync_crypto::{Engine, Fr};
 pub mod api;
 pub mod exit_proof;
 pub mod fs_utils;
+pub mod key_management;
 pub mod network_utils;
 pub mod prover_data;
 pub mod serialization;
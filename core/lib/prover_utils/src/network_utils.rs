@@ -52,7 +52,7 @@ fn try_to_download_setup(
         .map_err(backoff::Error::Transient)
 }
 
-fn get_backoff() -> backoff::ExponentialBackoff {
+pub(crate) fn get_backoff() -> backoff::ExponentialBackoff {
     backoff::ExponentialBackoff {
         current_interval: Duration::from_secs(5),
         initial_interval: Duration::from_secs(5),
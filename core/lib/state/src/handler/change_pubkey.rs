@@ -1,9 +1,9 @@
-use anyhow::{ensure, format_err};
+use anyhow::ensure;
 use std::time::Instant;
 use zksync_crypto::params;
 use zksync_types::{
     operations::{ChangePubKeyOp, ZkSyncOp},
-    tx::ChangePubKey,
+    tx::{ChangePubKey, TxFailure, TxFailureCode},
     AccountUpdate, AccountUpdates,
 };
 
@@ -16,24 +16,36 @@ impl TxHandler<ChangePubKey> for ZkSyncState {
     type Op = ChangePubKeyOp;
 
     fn create_op(&self, tx: ChangePubKey) -> Result<Self::Op, anyhow::Error> {
-        let (account_id, account) = self
-            .get_account_by_address(&tx.account)
-            .ok_or_else(|| format_err!("Account does not exist"))?;
+        let (account_id, account) = self.get_account_by_address(&tx.account).ok_or_else(|| {
+            TxFailure::new(TxFailureCode::AccountNotFound, "Account does not exist")
+        })?;
         ensure!(
             tx.eth_signature.is_none() || tx.verify_eth_signature() == Some(account.address),
-            "ChangePubKey Ethereum signature is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectSignature,
+                "ChangePubKey Ethereum signature is incorrect"
+            )
         );
         ensure!(
             tx.verify_signature() == Some(tx.new_pk_hash),
-            "ChangePubKey zkSync signature is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectSignature,
+                "ChangePubKey zkSync signature is incorrect"
+            )
         );
         ensure!(
             account_id == tx.account_id,
-            "ChangePubKey account id is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "ChangePubKey account id is incorrect"
+            )
         );
         ensure!(
             account_id <= params::max_account_id(),
-            "ChangePubKey account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "ChangePubKey account id is bigger than max supported"
+            )
         );
         let change_pk_op = ChangePubKeyOp { tx, account_id };
 
@@ -65,14 +77,20 @@ impl TxHandler<ChangePubKey> for ZkSyncState {
         let old_nonce = account.nonce;
 
         // Update nonce.
-        ensure!(op.tx.nonce == account.nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == account.nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         *account.nonce += 1;
 
         // Update pubkey hash.
         account.pub_key_hash = op.tx.new_pk_hash;
 
         // Subract fees.
-        ensure!(old_balance >= op.tx.fee, "Not enough balance");
+        ensure!(
+            old_balance >= op.tx.fee,
+            TxFailure::new(TxFailureCode::NotEnoughBalance, "Not enough balance")
+        );
         account.sub_balance(op.tx.fee_token, &op.tx.fee);
 
         let new_pub_key_hash = account.pub_key_hash;
@@ -1,7 +1,10 @@
 use anyhow::{bail, ensure};
 use num::BigUint;
 use zksync_crypto::params::{self, max_account_id};
-use zksync_types::{AccountUpdate, AccountUpdates, Close, CloseOp, TokenId};
+use zksync_types::{
+    tx::{TxFailure, TxFailureCode},
+    AccountUpdate, AccountUpdates, Close, CloseOp, TokenId,
+};
 
 use crate::{
     handler::TxHandler,
@@ -25,7 +28,10 @@ impl TxHandler<Close> for ZkSyncState {
     ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
         ensure!(
             op.account_id <= max_account_id(),
-            "Close account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Close account id is bigger than max supported"
+            )
         );
 
         let mut updates = Vec::new();
@@ -37,7 +43,10 @@ impl TxHandler<Close> for ZkSyncState {
             }
         }
 
-        ensure!(op.tx.nonce == account.nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == account.nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
 
         self.remove_account(op.account_id);
 
@@ -1,7 +1,10 @@
-use anyhow::{ensure, format_err};
+use anyhow::ensure;
 use std::time::Instant;
 use zksync_crypto::params;
-use zksync_types::{AccountUpdate, AccountUpdates, ForcedExit, ForcedExitOp, PubKeyHash, ZkSyncOp};
+use zksync_types::{
+    tx::{TxFailure, TxFailureCode},
+    AccountUpdate, AccountUpdates, ForcedExit, ForcedExitOp, PubKeyHash, ZkSyncOp,
+};
 use zksync_utils::BigUintSerdeWrapper;
 
 use crate::{
@@ -14,27 +17,40 @@ impl TxHandler<ForcedExit> for ZkSyncState {
 
     fn create_op(&self, tx: ForcedExit) -> Result<Self::Op, anyhow::Error> {
         // Check the tx signature.
-        let initiator_account = self
-            .get_account(tx.initiator_account_id)
-            .ok_or_else(|| format_err!("Initiator account does not exist"))?;
+        let initiator_account = self.get_account(tx.initiator_account_id).ok_or_else(|| {
+            TxFailure::new(
+                TxFailureCode::AccountNotFound,
+                "Initiator account does not exist",
+            )
+        })?;
         ensure!(
             tx.verify_signature() == Some(initiator_account.pub_key_hash),
-            "ForcedExit signature is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectSignature,
+                "ForcedExit signature is incorrect"
+            )
         );
 
         // Check the token ID correctness.
         ensure!(
             tx.token <= params::max_token_id(),
-            "Token id is not supported"
+            TxFailure::new(TxFailureCode::UnsupportedToken, "Token id is not supported")
         );
 
         // Check that target account does not have an account ID set.
-        let (target_account_id, account) = self
-            .get_account_by_address(&tx.target)
-            .ok_or_else(|| format_err!("Target account does not exist"))?;
+        let (target_account_id, account) =
+            self.get_account_by_address(&tx.target).ok_or_else(|| {
+                TxFailure::new(
+                    TxFailureCode::AccountNotFound,
+                    "Target account does not exist",
+                )
+            })?;
         ensure!(
             account.pub_key_hash == PubKeyHash::default(),
-            "Target account is not locked; forced exit is forbidden"
+            TxFailure::new(
+                TxFailureCode::ForcedExitForbidden,
+                "Target account is not locked; forced exit is forbidden"
+            )
         );
 
         // Obtain the token balance to be withdrawn.
@@ -71,7 +87,10 @@ impl TxHandler<ForcedExit> for ZkSyncState {
         let start = Instant::now();
         ensure!(
             op.tx.initiator_account_id <= params::max_account_id(),
-            "Incorrect initiator account ID"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Incorrect initiator account ID"
+            )
         );
 
         let initiator_account_id = op.tx.initiator_account_id;
@@ -92,10 +111,16 @@ impl TxHandler<ForcedExit> for ZkSyncState {
         let initiator_old_balance = initiator_account.get_balance(op.tx.token);
         let initiator_old_nonce = initiator_account.nonce;
 
-        ensure!(op.tx.nonce == initiator_old_nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == initiator_old_nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         ensure!(
             initiator_old_balance >= op.tx.fee,
-            "Initiator account: Not enough balance to cover fees"
+            TxFailure::new(
+                TxFailureCode::NotEnoughBalance,
+                "Initiator account: Not enough balance to cover fees"
+            )
         );
 
         // Check that target account has required amount of tokens to withdraw.
@@ -104,7 +129,10 @@ impl TxHandler<ForcedExit> for ZkSyncState {
         let target_old_balance = target_account.get_balance(op.tx.token);
         ensure!(
             target_old_balance == amount,
-            "Target account: Target account balance is not equal to the withdrawal amount"
+            TxFailure::new(
+                TxFailureCode::ForcedExitForbidden,
+                "Target account: Target account balance is not equal to the withdrawal amount"
+            )
         );
 
         // Take fees from the initiator account (and update initiator account nonce).
@@ -1,7 +1,8 @@
-use anyhow::{ensure, format_err};
+use anyhow::ensure;
 use std::time::Instant;
 use zksync_crypto::params::{self, max_account_id};
 use zksync_types::{
+    tx::{TxFailure, TxFailureCode},
     Account, AccountUpdate, AccountUpdates, Address, PubKeyHash, Transfer, TransferOp,
     TransferToNewOp,
 };
@@ -17,24 +18,36 @@ impl TxHandler<Transfer> for ZkSyncState {
     fn create_op(&self, tx: Transfer) -> Result<Self::Op, anyhow::Error> {
         ensure!(
             tx.token <= params::max_token_id(),
-            "Token id is not supported"
+            TxFailure::new(TxFailureCode::UnsupportedToken, "Token id is not supported")
         );
         ensure!(
             tx.to != Address::zero(),
             "Transfer to Account with address 0 is not allowed"
         );
-        let (from, from_account) = self
-            .get_account_by_address(&tx.from)
-            .ok_or_else(|| format_err!("From account does not exist"))?;
+        let (from, from_account) = self.get_account_by_address(&tx.from).ok_or_else(|| {
+            TxFailure::new(
+                TxFailureCode::AccountNotFound,
+                "From account does not exist",
+            )
+        })?;
         ensure!(
             from_account.pub_key_hash != PubKeyHash::default(),
-            "Account is locked"
+            TxFailure::new(TxFailureCode::AccountLocked, "Account is locked")
         );
         ensure!(
             tx.verify_signature() == Some(from_account.pub_key_hash),
-            "Transfer signature is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectSignature,
+                "Transfer signature is incorrect"
+            )
+        );
+        ensure!(
+            from == tx.account_id,
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Transfer account id is incorrect"
+            )
         );
-        ensure!(from == tx.account_id, "Transfer account id is incorrect");
 
         let outcome = if let Some((to, _)) = self.get_account_by_address(&tx.to) {
             let transfer_op = TransferOp { tx, from, to };
@@ -82,11 +95,17 @@ impl ZkSyncState {
         let start = Instant::now();
         ensure!(
             op.from <= max_account_id(),
-            "Transfer from account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Transfer from account id is bigger than max supported"
+            )
         );
         ensure!(
             op.to <= max_account_id(),
-            "Transfer to account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Transfer to account id is bigger than max supported"
+            )
         );
 
         if op.from == op.to {
@@ -100,10 +119,13 @@ impl ZkSyncState {
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
 
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == from_old_nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         ensure!(
             from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
+            TxFailure::new(TxFailureCode::NotEnoughBalance, "Not enough balance")
         );
 
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
@@ -156,7 +178,10 @@ impl ZkSyncState {
         let start = Instant::now();
         ensure!(
             op.from <= max_account_id(),
-            "Transfer to self from account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Transfer to self from account id is bigger than max supported"
+            )
         );
         ensure!(
             op.from == op.to,
@@ -169,10 +194,13 @@ impl ZkSyncState {
         let old_balance = account.get_balance(op.tx.token);
         let old_nonce = account.nonce;
 
-        ensure!(op.tx.nonce == old_nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == old_nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         ensure!(
             old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
+            TxFailure::new(TxFailureCode::NotEnoughBalance, "Not enough balance")
         );
 
         account.sub_balance(op.tx.token, &op.tx.fee);
@@ -210,11 +238,17 @@ impl ZkSyncState {
 
         ensure!(
             op.from <= max_account_id(),
-            "TransferToNew from account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "TransferToNew from account id is bigger than max supported"
+            )
         );
         ensure!(
             op.to <= max_account_id(),
-            "TransferToNew to account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "TransferToNew to account id is bigger than max supported"
+            )
         );
 
         if let Some(account) = self.get_account(op.to) {
@@ -235,10 +269,13 @@ impl ZkSyncState {
         let mut from_account = self.get_account(op.from).unwrap();
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == from_old_nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         ensure!(
             from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
+            TxFailure::new(TxFailureCode::NotEnoughBalance, "Not enough balance")
         );
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
         *from_account.nonce += 1;
@@ -1,7 +1,10 @@
-use anyhow::{ensure, format_err};
+use anyhow::ensure;
 use std::time::Instant;
 use zksync_crypto::params::{self, max_account_id};
-use zksync_types::{AccountUpdate, AccountUpdates, PubKeyHash, Withdraw, WithdrawOp, ZkSyncOp};
+use zksync_types::{
+    tx::{TxFailure, TxFailureCode},
+    AccountUpdate, AccountUpdates, PubKeyHash, Withdraw, WithdrawOp, ZkSyncOp,
+};
 
 use crate::{
     handler::TxHandler,
@@ -14,22 +17,28 @@ impl TxHandler<Withdraw> for ZkSyncState {
     fn create_op(&self, tx: Withdraw) -> Result<Self::Op, anyhow::Error> {
         ensure!(
             tx.token <= params::max_token_id(),
-            "Token id is not supported"
+            TxFailure::new(TxFailureCode::UnsupportedToken, "Token id is not supported")
         );
-        let (account_id, account) = self
-            .get_account_by_address(&tx.from)
-            .ok_or_else(|| format_err!("Account does not exist"))?;
+        let (account_id, account) = self.get_account_by_address(&tx.from).ok_or_else(|| {
+            TxFailure::new(TxFailureCode::AccountNotFound, "Account does not exist")
+        })?;
         ensure!(
             account.pub_key_hash != PubKeyHash::default(),
-            "Account is locked"
+            TxFailure::new(TxFailureCode::AccountLocked, "Account is locked")
         );
         ensure!(
             tx.verify_signature() == Some(account.pub_key_hash),
-            "withdraw signature is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectSignature,
+                "withdraw signature is incorrect"
+            )
         );
         ensure!(
             account_id == tx.account_id,
-            "Withdraw account id is incorrect"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Withdraw account id is incorrect"
+            )
         );
         let withdraw_op = WithdrawOp { tx, account_id };
 
@@ -54,7 +63,10 @@ impl TxHandler<Withdraw> for ZkSyncState {
         let start = Instant::now();
         ensure!(
             op.account_id <= max_account_id(),
-            "Withdraw account id is bigger than max supported"
+            TxFailure::new(
+                TxFailureCode::IncorrectAccountId,
+                "Withdraw account id is bigger than max supported"
+            )
         );
 
         let mut updates = Vec::new();
@@ -63,10 +75,13 @@ impl TxHandler<Withdraw> for ZkSyncState {
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
 
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
+        ensure!(
+            op.tx.nonce == from_old_nonce,
+            TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch")
+        );
         ensure!(
             from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
+            TxFailure::new(TxFailureCode::NotEnoughBalance, "Not enough balance")
         );
 
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
@@ -1,5 +1,6 @@
 pub mod handler;
 pub mod state;
+pub mod tree;
 
 #[cfg(test)]
 pub mod tests;
@@ -1,15 +1,16 @@
 use anyhow::Error;
 use num::BigUint;
-use std::collections::{HashMap, HashSet};
-use zksync_crypto::{params, Fr};
+use std::collections::HashSet;
+use zksync_crypto::Fr;
 use zksync_types::{
     helpers::reverse_updates,
     operations::{TransferOp, TransferToNewOp, ZkSyncOp},
-    Account, AccountId, AccountMap, AccountTree, AccountUpdate, AccountUpdates, Address,
-    BlockNumber, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx,
+    tx::{TxFailure, TxFailureCode},
+    Account, AccountId, AccountMap, AccountUpdate, AccountUpdates, Address, BlockNumber,
+    SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx,
 };
 
-use crate::handler::TxHandler;
+use crate::{handler::TxHandler, tree::StateTree};
 
 #[derive(Debug)]
 pub struct OpSuccess {
@@ -21,9 +22,7 @@ pub struct OpSuccess {
 #[derive(Debug, Clone)]
 pub struct ZkSyncState {
     /// Accounts stored in a sparse Merkle tree
-    balance_tree: AccountTree,
-
-    account_id_by_address: HashMap<Address, AccountId>,
+    balance_tree: StateTree,
 
     /// Current block number
     pub block_number: BlockNumber,
@@ -53,12 +52,9 @@ impl TransferOutcome {
 
 impl ZkSyncState {
     pub fn empty() -> Self {
-        let tree_depth = params::account_tree_depth();
-        let balance_tree = AccountTree::new(tree_depth);
         Self {
-            balance_tree,
+            balance_tree: StateTree::default(),
             block_number: BlockNumber(0),
-            account_id_by_address: HashMap::new(),
         }
     }
 
@@ -71,23 +67,18 @@ impl ZkSyncState {
         empty
     }
 
-    pub fn new(
-        balance_tree: AccountTree,
-        account_id_by_address: HashMap<Address, AccountId>,
-        current_block: BlockNumber,
-    ) -> Self {
+    pub fn new(balance_tree: StateTree, current_block: BlockNumber) -> Self {
         Self {
             balance_tree,
             block_number: current_block,
-            account_id_by_address,
         }
     }
 
     pub fn get_accounts(&self) -> Vec<(u32, Account)> {
         self.balance_tree
-            .items
-            .iter()
-            .map(|a| (*a.0 as u32, a.1.clone()))
+            .account_ids()
+            .into_iter()
+            .filter_map(|id| Some((id, self.balance_tree.get(id)?)))
             .collect()
     }
 
@@ -98,10 +89,16 @@ impl ZkSyncState {
         hash
     }
 
+    /// Writes the changes of the account tree to RocksDB if the tree is stored there,
+    /// marking the stored tree with the current block number.
+    pub fn flush_tree(&mut self) -> anyhow::Result<()> {
+        self.balance_tree.flush(self.block_number)
+    }
+
     pub fn get_account(&self, account_id: AccountId) -> Option<Account> {
         let start = std::time::Instant::now();
 
-        let account = self.balance_tree.get(*account_id).cloned();
+        let account = self.balance_tree.get(*account_id);
 
         vlog::trace!(
             "Get account (id {}) execution time: {}ms",
@@ -243,9 +240,11 @@ impl ZkSyncState {
                         error
                     );
 
-                    // Create the same error for each transaction.
+                    // Create the same error for each transaction, keeping the code
+                    // of the failed one.
+                    let code = TxFailureCode::of(&error);
                     let errors = (0..txs.len())
-                        .map(|_| Err(anyhow::format_err!("{}", error_msg)))
+                        .map(|_| Err(TxFailure::new(code, error_msg.clone()).into()))
                         .collect();
 
                     // Stop execution and return an error.
@@ -268,7 +267,7 @@ impl ZkSyncState {
     }
 
     pub(crate) fn get_free_account_id(&self) -> AccountId {
-        let mut account_id = AccountId(self.balance_tree.items.len() as u32);
+        let mut account_id = AccountId(self.balance_tree.accounts_count() as u32);
 
         // In the production database it somehow appeared that one account ID in the database got missing,
         // meaning that it was never assigned, but the next one was inserted.
@@ -318,7 +317,7 @@ impl ZkSyncState {
     }
 
     pub fn get_account_by_address(&self, address: &Address) -> Option<(AccountId, Account)> {
-        let account_id = *self.account_id_by_address.get(address)?;
+        let account_id = self.balance_tree.account_id_by_address(address)?;
         Some((
             account_id,
             self.get_account(account_id)
@@ -328,16 +327,12 @@ impl ZkSyncState {
 
     #[doc(hidden)] // Public for benches.
     pub fn insert_account(&mut self, id: AccountId, account: Account) {
-        self.account_id_by_address.insert(account.address, id);
         self.balance_tree.insert(*id, account);
     }
 
     #[allow(dead_code)]
     pub(crate) fn remove_account(&mut self, id: AccountId) {
-        if let Some(account) = self.get_account(id) {
-            self.account_id_by_address.remove(&account.address);
-            self.balance_tree.remove(*id);
-        }
+        self.balance_tree.remove(*id);
     }
 
     /// Converts the `ZkSyncTx` object to a `ZkSyncOp`, without applying it.
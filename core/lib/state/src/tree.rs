@@ -0,0 +1,599 @@
+//! Account tree of the zkSync state.
+//!
+//! The tree is either kept in memory entirely, or stored in RocksDB (with the `rocksdb` feature
+//! enabled), in which case only the changes since the last flush and the hashes of the nodes close
+//! to the root are kept in memory. The stored tree is marked with the number of the block it
+//! corresponds to, so the server can catch up with the committed state after the restart instead
+//! of building the whole tree again.
+//!
+//! Both trees keep the index of the account IDs by their addresses, the stored tree keeps it
+//! in RocksDB as well.
+
+use std::collections::HashMap;
+
+use zksync_crypto::{
+    circuit::account::CircuitAccount,
+    merkle_tree::{
+        persistent_smt::{MemoryMerkleTreeStore, PersistentMerkleTree},
+        RescueHasher,
+    },
+    params, Engine, Fr,
+};
+use zksync_types::{
+    Account, AccountId, AccountMap, AccountTree, AccountUpdates, Address, BlockNumber,
+};
+
+#[cfg(feature = "rocksdb")]
+pub use self::rocksdb_store::{PersistentAccountTree, RocksDbAccountStore, StoredAccountTree};
+
+/// Part of the account tree extracted to build the witness of the block,
+/// see [`StateTree::extract_circuit_tree`].
+pub type PartialCircuitAccountTree = PersistentMerkleTree<
+    CircuitAccount<Engine>,
+    Fr,
+    RescueHasher<Engine>,
+    MemoryMerkleTreeStore<CircuitAccount<Engine>, Fr>,
+>;
+
+/// Account tree of the state.
+#[derive(Debug, Clone)]
+pub enum StateTree {
+    /// Tree kept in memory along with the account IDs by their addresses.
+    Memory(AccountTree, HashMap<Address, AccountId>),
+    /// Tree stored in RocksDB.
+    #[cfg(feature = "rocksdb")]
+    RocksDb(Box<StoredAccountTree>),
+}
+
+impl Default for StateTree {
+    fn default() -> Self {
+        Self::Memory(
+            AccountTree::new(params::account_tree_depth()),
+            HashMap::new(),
+        )
+    }
+}
+
+impl From<AccountTree> for StateTree {
+    fn from(tree: AccountTree) -> Self {
+        let account_ids = tree
+            .items
+            .iter()
+            .map(|(id, account)| (account.address, AccountId(*id as u32)))
+            .collect();
+        Self::Memory(tree, account_ids)
+    }
+}
+
+impl StateTree {
+    /// Opens the tree stored in RocksDB at the given path, or creates an in-memory tree
+    /// if the path is not set.
+    pub fn open(path: Option<&str>) -> anyhow::Result<Self> {
+        match path {
+            #[cfg(feature = "rocksdb")]
+            Some(path) => Ok(Self::RocksDb(Box::new(StoredAccountTree::open(path)?))),
+            #[cfg(not(feature = "rocksdb"))]
+            Some(_) => anyhow::bail!(
+                "RocksDB account tree is not supported by this build, enable the `rocksdb` feature"
+            ),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Returns the number of the block the stored tree corresponds to as of the last flush,
+    /// or `None` for the in-memory tree.
+    pub fn stored_block(&self) -> Option<BlockNumber> {
+        match self {
+            Self::Memory(..) => None,
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => Some(BlockNumber(tree.version() as u32)),
+        }
+    }
+
+    pub fn get(&self, id: u32) -> Option<Account> {
+        match self {
+            Self::Memory(tree, _) => tree.get(id).cloned(),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .get(id)
+                .expect("Failed to load the account from RocksDB"),
+        }
+    }
+
+    /// Returns the ID of the account with the given address.
+    pub fn account_id_by_address(&self, address: &Address) -> Option<AccountId> {
+        match self {
+            Self::Memory(_, account_ids) => account_ids.get(address).copied(),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .account_id(address)
+                .expect("Failed to load the account ID from RocksDB"),
+        }
+    }
+
+    /// Returns the number of the accounts with the known addresses.
+    pub fn accounts_count(&self) -> usize {
+        match self {
+            Self::Memory(_, account_ids) => account_ids.len(),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree.accounts_count() as usize,
+        }
+    }
+
+    /// Returns the IDs of all the accounts of the tree. Only the IDs are loaded, so the stored tree
+    /// doesn't have to fit into memory, but all the accounts are still visited.
+    pub fn account_ids(&self) -> Vec<u32> {
+        match self {
+            Self::Memory(tree, _) => tree.items.keys().map(|id| *id as u32).collect(),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .account_ids()
+                .expect("Failed to load the account IDs from RocksDB"),
+        }
+    }
+
+    pub fn insert(&mut self, id: u32, account: Account) {
+        match self {
+            Self::Memory(tree, account_ids) => {
+                account_ids.insert(account.address, AccountId(id));
+                tree.insert(id, account)
+            }
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .insert(id, account)
+                .expect("Failed to update the account tree in RocksDB"),
+        }
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<Account> {
+        match self {
+            Self::Memory(tree, account_ids) => {
+                let account = tree.remove(id)?;
+                account_ids.remove(&account.address);
+                Some(account)
+            }
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .remove(id)
+                .expect("Failed to update the account tree in RocksDB"),
+        }
+    }
+
+    pub fn root_hash(&self) -> Fr {
+        match self {
+            Self::Memory(tree, _) => tree.root_hash(),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree
+                .root_hash()
+                .expect("Failed to load the account tree from RocksDB"),
+        }
+    }
+
+    /// Extracts the part of the stored tree required to build the witness changing the given
+    /// accounts, so the witness is built without loading the whole tree. Returns `None` for
+    /// the in-memory tree.
+    pub fn extract_circuit_tree(
+        &self,
+        ids: &[u32],
+    ) -> anyhow::Result<Option<PartialCircuitAccountTree>> {
+        match self {
+            Self::Memory(..) => Ok(None),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree.extract_circuit_tree(ids).map(Some),
+        }
+    }
+
+    /// Applies the account updates, e.g. the ones loaded from the database.
+    pub fn apply_updates(&mut self, updates: AccountUpdates) {
+        for (id, update) in updates {
+            match Account::apply_update(self.get(*id), update) {
+                Some(account) => self.insert(*id, account),
+                None => {
+                    self.remove(*id);
+                }
+            }
+        }
+    }
+
+    /// Updates the tree to contain exactly the given accounts. Only the accounts that differ
+    /// are updated, so the tree which is mostly up to date is reconciled quickly.
+    pub fn reconcile(&mut self, accounts: &AccountMap) {
+        for id in self.account_ids() {
+            if !accounts.contains_key(&AccountId(id)) {
+                self.remove(id);
+            }
+        }
+        for (id, account) in accounts {
+            if self.get(**id).as_ref() != Some(account) {
+                self.insert(**id, account.clone());
+            }
+        }
+    }
+
+    /// Writes the changes of the stored tree to RocksDB, marking it with the given block number.
+    /// Does nothing for the in-memory tree.
+    pub fn flush(&mut self, block_number: BlockNumber) -> anyhow::Result<()> {
+        match self {
+            Self::Memory(..) => Ok(()),
+            #[cfg(feature = "rocksdb")]
+            Self::RocksDb(tree) => tree.flush(u64::from(*block_number)),
+        }
+    }
+}
+
+#[cfg(feature = "rocksdb")]
+mod rocksdb_store {
+    use std::{collections::HashMap, fmt, path::Path, sync::Arc};
+
+    use rocksdb::{ColumnFamily, IteratorMode, Options, WriteBatch, DB};
+    use zksync_crypto::{
+        circuit::account::CircuitAccount,
+        convert::FeConvert,
+        merkle_tree::persistent_smt::{MerkleTreeBatch, MerkleTreeStore, PersistentMerkleTree},
+        params, Engine, Fr,
+    };
+    use zksync_types::{Account, AccountId, Address, RescueHasher};
+
+    use super::PartialCircuitAccountTree;
+
+    /// Account tree stored in RocksDB.
+    pub type PersistentAccountTree =
+        PersistentMerkleTree<Account, Fr, RescueHasher<Engine>, RocksDbAccountStore>;
+
+    const ITEMS_CF: &str = "items";
+    const HASHES_CF: &str = "hashes";
+    const ADDRESSES_CF: &str = "addresses";
+    const VERSION_KEY: &[u8] = b"version";
+    const ACCOUNTS_COUNT_KEY: &[u8] = b"accounts_count";
+
+    /// RocksDB store of the account tree. Accounts, node hashes and account IDs by the account
+    /// addresses are kept in separate column families keyed by the big-endian indices and the
+    /// addresses respectively. The version of the tree and the number of the accounts are kept
+    /// in the default column family.
+    ///
+    /// The store is shared by its clones.
+    #[derive(Clone)]
+    pub struct RocksDbAccountStore {
+        db: Arc<DB>,
+    }
+
+    impl fmt::Debug for RocksDbAccountStore {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("RocksDbAccountStore")
+                .field("path", &self.db.path())
+                .finish()
+        }
+    }
+
+    impl RocksDbAccountStore {
+        /// Opens the store at the given path, creating it if it doesn't exist.
+        pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+            let mut options = Options::default();
+            options.create_if_missing(true);
+            options.create_missing_column_families(true);
+            let db = DB::open_cf(&options, path, &[ITEMS_CF, HASHES_CF, ADDRESSES_CF])?;
+
+            let store = Self { db: Arc::new(db) };
+            // The tree stored without the address index is indexed once.
+            if store.load_version()? != 0 && store.db.get(ACCOUNTS_COUNT_KEY)?.is_none() {
+                store.build_address_index()?;
+            }
+            Ok(store)
+        }
+
+        fn build_address_index(&self) -> anyhow::Result<()> {
+            let mut write_batch = WriteBatch::default();
+            let mut accounts_count = 0u64;
+            for (key, value) in self.db.iterator_cf(self.cf(ITEMS_CF), IteratorMode::Start) {
+                let account: Account = serde_json::from_slice(&value)?;
+                write_batch.put_cf(self.cf(ADDRESSES_CF), account.address.as_bytes(), key);
+                accounts_count += 1;
+            }
+            write_batch.put(ACCOUNTS_COUNT_KEY, accounts_count.to_be_bytes());
+
+            self.db.write(write_batch)?;
+            Ok(())
+        }
+
+        /// Loads the ID of the account with the given address as of the last write.
+        pub fn load_account_id(&self, address: &Address) -> anyhow::Result<Option<AccountId>> {
+            match self.db.get_cf(self.cf(ADDRESSES_CF), address.as_bytes())? {
+                Some(value) => Ok(Some(AccountId(u64_from_bytes(&value) as u32))),
+                None => Ok(None),
+            }
+        }
+
+        /// Loads the number of the accounts as of the last write.
+        pub fn load_accounts_count(&self) -> anyhow::Result<u64> {
+            match self.db.get(ACCOUNTS_COUNT_KEY)? {
+                Some(value) => Ok(u64_from_bytes(&value)),
+                None => Ok(0),
+            }
+        }
+
+        fn cf(&self, name: &str) -> &ColumnFamily {
+            self.db
+                .cf_handle(name)
+                .expect("Column family is created on open")
+        }
+    }
+
+    impl MerkleTreeStore<Account, Fr> for RocksDbAccountStore {
+        fn load_item(&self, index: u64) -> anyhow::Result<Option<Account>> {
+            match self.db.get_cf(self.cf(ITEMS_CF), index.to_be_bytes())? {
+                Some(value) => Ok(Some(serde_json::from_slice(&value)?)),
+                None => Ok(None),
+            }
+        }
+
+        fn load_hash(&self, index: u64) -> anyhow::Result<Option<Fr>> {
+            match self.db.get_cf(self.cf(HASHES_CF), index.to_be_bytes())? {
+                Some(value) => Ok(Some(Fr::from_bytes(&value)?)),
+                None => Ok(None),
+            }
+        }
+
+        fn load_indices(&self) -> anyhow::Result<Vec<u64>> {
+            Ok(self
+                .db
+                .iterator_cf(self.cf(ITEMS_CF), IteratorMode::Start)
+                .map(|(key, _)| u64_from_bytes(&key))
+                .collect())
+        }
+
+        fn load_version(&self) -> anyhow::Result<u64> {
+            match self.db.get(VERSION_KEY)? {
+                Some(value) => Ok(u64_from_bytes(&value)),
+                None => Ok(0),
+            }
+        }
+
+        fn write_batch(&mut self, batch: MerkleTreeBatch<Account, Fr>) -> anyhow::Result<()> {
+            let mut write_batch = WriteBatch::default();
+            let mut accounts_count = self.load_accounts_count()?;
+
+            // Addresses of the replaced accounts are removed before the new addresses are added,
+            // so the address moved to another account within the batch stays in the index.
+            for (index, item) in &batch.items {
+                match (self.load_item(*index)?, item) {
+                    (Some(old_item), new_item) => {
+                        write_batch.delete_cf(self.cf(ADDRESSES_CF), old_item.address.as_bytes());
+                        if new_item.is_none() {
+                            accounts_count -= 1;
+                        }
+                    }
+                    (None, Some(_)) => accounts_count += 1,
+                    (None, None) => {}
+                }
+            }
+            for (index, item) in batch.items {
+                match item {
+                    Some(item) => {
+                        write_batch.put_cf(
+                            self.cf(ADDRESSES_CF),
+                            item.address.as_bytes(),
+                            index.to_be_bytes(),
+                        );
+                        write_batch.put_cf(
+                            self.cf(ITEMS_CF),
+                            index.to_be_bytes(),
+                            serde_json::to_vec(&item)?,
+                        );
+                    }
+                    None => write_batch.delete_cf(self.cf(ITEMS_CF), index.to_be_bytes()),
+                }
+            }
+            for (index, hash) in batch.hashes {
+                write_batch.put_cf(self.cf(HASHES_CF), index.to_be_bytes(), hash.to_bytes());
+            }
+            write_batch.put(VERSION_KEY, batch.version.to_be_bytes());
+            write_batch.put(ACCOUNTS_COUNT_KEY, accounts_count.to_be_bytes());
+
+            self.db.write(write_batch)?;
+            Ok(())
+        }
+    }
+
+    fn u64_from_bytes(bytes: &[u8]) -> u64 {
+        let mut value = [0u8; 8];
+        value.copy_from_slice(bytes);
+        u64::from_be_bytes(value)
+    }
+
+    /// Account tree stored in RocksDB along with the account IDs by their addresses.
+    #[derive(Debug, Clone)]
+    pub struct StoredAccountTree {
+        tree: PersistentAccountTree,
+        /// Changes of the account IDs by addresses since the last flush,
+        /// `None` means that the address was removed.
+        dirty_ids: HashMap<Address, Option<AccountId>>,
+        /// Number of the accounts, including the changes since the last flush.
+        accounts_count: u64,
+    }
+
+    impl StoredAccountTree {
+        /// Opens the tree stored at the given path, creating an empty one if it doesn't exist.
+        pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+            let store = RocksDbAccountStore::open(path)?;
+            let accounts_count = store.load_accounts_count()?;
+            let tree = PersistentAccountTree::new(store, params::account_tree_depth())?;
+
+            Ok(Self {
+                tree,
+                dirty_ids: HashMap::new(),
+                accounts_count,
+            })
+        }
+
+        /// Returns the version of the tree as of the last flush.
+        pub fn version(&self) -> u64 {
+            self.tree.version()
+        }
+
+        pub fn get(&self, id: u32) -> anyhow::Result<Option<Account>> {
+            self.tree.get(id)
+        }
+
+        pub fn account_id(&self, address: &Address) -> anyhow::Result<Option<AccountId>> {
+            match self.dirty_ids.get(address) {
+                Some(id) => Ok(*id),
+                None => self.tree.store().load_account_id(address),
+            }
+        }
+
+        pub fn accounts_count(&self) -> u64 {
+            self.accounts_count
+        }
+
+        pub fn account_ids(&self) -> anyhow::Result<Vec<u32>> {
+            self.tree.indices()
+        }
+
+        pub fn insert(&mut self, id: u32, account: Account) -> anyhow::Result<()> {
+            match self.tree.get(id)? {
+                Some(old_account) if old_account.address != account.address => {
+                    self.dirty_ids.insert(old_account.address, None);
+                }
+                Some(_) => {}
+                None => self.accounts_count += 1,
+            }
+            self.dirty_ids.insert(account.address, Some(AccountId(id)));
+            self.tree.insert(id, account)
+        }
+
+        pub fn remove(&mut self, id: u32) -> anyhow::Result<Option<Account>> {
+            let old_account = self.tree.remove(id)?;
+            if let Some(old_account) = &old_account {
+                self.dirty_ids.insert(old_account.address, None);
+                self.accounts_count -= 1;
+            }
+            Ok(old_account)
+        }
+
+        pub fn root_hash(&self) -> anyhow::Result<Fr> {
+            self.tree.root_hash()
+        }
+
+        pub fn extract_circuit_tree(
+            &self,
+            ids: &[u32],
+        ) -> anyhow::Result<PartialCircuitAccountTree> {
+            let store = self.tree.extract(ids)?.map_items(CircuitAccount::from);
+            PartialCircuitAccountTree::new(store, params::account_tree_depth())
+        }
+
+        pub fn flush(&mut self, version: u64) -> anyhow::Result<()> {
+            self.tree.flush(version)?;
+            self.dirty_ids.clear();
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rocksdb"))]
+mod tests {
+    use super::*;
+
+    /// Checks that the tree stored in RocksDB matches the in-memory one, and is restored
+    /// as of the last flush once reopened.
+    #[test]
+    fn rocksdb_tree_matches_memory_tree() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().to_str().unwrap();
+
+        let mut memory_tree = StateTree::default();
+        let mut stored_tree = StateTree::open(Some(path))?;
+        assert_eq!(stored_tree.stored_block(), Some(BlockNumber(0)));
+
+        let accounts = (0..5u32)
+            .map(|id| Account::create_account(AccountId(id), Address::random()).0)
+            .collect::<Vec<_>>();
+        for (id, account) in accounts.iter().enumerate() {
+            memory_tree.insert(id as u32, account.clone());
+            stored_tree.insert(id as u32, account.clone());
+        }
+        assert_eq!(stored_tree.root_hash(), memory_tree.root_hash());
+        stored_tree.flush(BlockNumber(1))?;
+
+        assert_eq!(
+            stored_tree.account_id_by_address(&accounts[1].address),
+            Some(AccountId(1))
+        );
+
+        // Changes made after the flush are lost on reopen.
+        stored_tree.remove(0);
+        assert_eq!(
+            stored_tree.account_id_by_address(&accounts[0].address),
+            None
+        );
+        assert_eq!(stored_tree.accounts_count(), accounts.len() - 1);
+        drop(stored_tree);
+
+        let stored_tree = StateTree::open(Some(path))?;
+        assert_eq!(stored_tree.stored_block(), Some(BlockNumber(1)));
+        assert_eq!(stored_tree.root_hash(), memory_tree.root_hash());
+        assert_eq!(stored_tree.get(0), Some(accounts[0].clone()));
+        assert_eq!(stored_tree.account_ids().len(), accounts.len());
+        assert_eq!(stored_tree.accounts_count(), accounts.len());
+        assert_eq!(
+            stored_tree.account_id_by_address(&accounts[0].address),
+            Some(AccountId(0))
+        );
+
+        Ok(())
+    }
+
+    /// Checks that the address index follows the accounts moved and removed between the flushes.
+    #[test]
+    fn rocksdb_tree_address_index() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().to_str().unwrap();
+
+        let mut stored_tree = StateTree::open(Some(path))?;
+        let (account, _) = Account::create_account(AccountId(1), Address::random());
+        stored_tree.insert(1, account.clone());
+        stored_tree.flush(BlockNumber(1))?;
+
+        // The account is moved to another ID within the same flush.
+        stored_tree.remove(1);
+        stored_tree.insert(2, account.clone());
+        stored_tree.flush(BlockNumber(2))?;
+        drop(stored_tree);
+
+        let stored_tree = StateTree::open(Some(path))?;
+        assert_eq!(
+            stored_tree.account_id_by_address(&account.address),
+            Some(AccountId(2))
+        );
+        assert_eq!(stored_tree.accounts_count(), 1);
+
+        Ok(())
+    }
+
+    /// Checks that the part of the tree extracted for the accounts has the same root hash,
+    /// and is updated in the same way as the whole tree.
+    #[test]
+    fn extracted_circuit_tree() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().to_str().unwrap();
+
+        let mut stored_tree = StateTree::open(Some(path))?;
+        for id in 0..5u32 {
+            let (account, _) = Account::create_account(AccountId(id), Address::random());
+            stored_tree.insert(id, account);
+        }
+        stored_tree.flush(BlockNumber(1))?;
+
+        let mut circuit_tree = stored_tree
+            .extract_circuit_tree(&[1, 7])?
+            .expect("Tree is stored in RocksDB");
+        assert_eq!(circuit_tree.root_hash()?, stored_tree.root_hash());
+
+        let (account, _) = Account::create_account(AccountId(7), Address::random());
+        circuit_tree.insert(7, account.clone().into())?;
+        stored_tree.insert(7, account);
+        assert_eq!(circuit_tree.root_hash()?, stored_tree.root_hash());
+
+        Ok(())
+    }
+}
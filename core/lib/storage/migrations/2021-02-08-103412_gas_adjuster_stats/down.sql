@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eth_gas_adjuster_stats;
@@ -0,0 +1,15 @@
+CREATE TABLE eth_gas_adjuster_stats (
+    -- enforce single record
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    -- Gas price suggested for the last sent transaction
+    last_suggested_price BIGINT,
+    -- Recently observed gas prices
+    samples BIGINT[] NOT NULL,
+    -- Average of the recently observed gas prices
+    average_price BIGINT,
+    -- Current gas price limit
+    price_limit BIGINT NOT NULL,
+    -- Factor applied to the average gas price to obtain the limit
+    scale_factor DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE block_witness DROP COLUMN IF EXISTS commitment;
+DROP TABLE IF EXISTS proof_cache;
@@ -0,0 +1,9 @@
+-- Proofs indexed by the public data commitment of the proven block,
+-- so the same block doesn't have to be proven twice.
+CREATE TABLE proof_cache (
+    commitment BYTEA PRIMARY KEY,
+    proof jsonb NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+ALTER TABLE block_witness ADD COLUMN commitment BYTEA;
@@ -0,0 +1,9 @@
+-- Blocks the witness is being generated for at the moment.
+-- Jobs that are not updated for too long are considered abandoned
+-- and are taken by another witness generator.
+CREATE TABLE witness_generation_jobs (
+    block_number BIGINT PRIMARY KEY,
+    worker TEXT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,9 @@
+-- Append-only journal of all the account updates committed to the state.
+CREATE TABLE account_updates_journal (
+    sequence_number BIGSERIAL PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    account_id BIGINT NOT NULL,
+    update_order_id INT NOT NULL,
+    account_update jsonb NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1,9 @@
+-- Proofs and witnesses of the verified blocks moved from the database to the object store.
+-- `kind` is either `proof` or `witness`.
+CREATE TABLE cold_storage_objects (
+    block_number BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    object_key TEXT NOT NULL,
+    archived_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY (block_number, kind)
+);
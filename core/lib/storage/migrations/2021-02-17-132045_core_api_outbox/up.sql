@@ -0,0 +1,12 @@
+-- Requests forwarded by the API server to the Core.
+-- Every request is stored before being sent, and the Core records the result for it,
+-- so a request retried after a network failure is never applied twice.
+CREATE TABLE core_api_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    request jsonb NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    -- Set by the Core once it starts processing the request.
+    claimed_at TIMESTAMP with time zone,
+    -- Result returned by the Core, set once the request is processed.
+    result jsonb
+);
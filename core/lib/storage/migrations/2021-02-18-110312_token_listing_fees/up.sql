@@ -0,0 +1,21 @@
+-- Fees paid in the L1 for the permissionless token listings.
+-- Rows are never deleted, so the table serves as the financial record of the listing flow.
+CREATE TABLE token_listing_fees (
+    id BIGSERIAL PRIMARY KEY,
+    -- Address of the token requested to be listed.
+    token_address TEXT NOT NULL,
+    -- Address of the account that paid the fee.
+    payer TEXT NOT NULL,
+    -- Paid amount in wei.
+    amount NUMERIC NOT NULL,
+    -- Hash of the L1 transaction containing the payment.
+    eth_tx_hash bytea NOT NULL UNIQUE,
+    -- One of `pending`, `listed` or `refunded`.
+    status TEXT NOT NULL,
+    -- Token ID assigned once the token is listed.
+    token_id INTEGER REFERENCES tokens(id),
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX token_listing_fees_status_idx ON token_listing_fees (status);
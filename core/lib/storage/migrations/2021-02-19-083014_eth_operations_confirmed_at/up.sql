@@ -0,0 +1,2 @@
+-- Time when the Ethereum transaction of the operation was confirmed.
+ALTER TABLE eth_operations ADD COLUMN confirmed_at TIMESTAMP with time zone;
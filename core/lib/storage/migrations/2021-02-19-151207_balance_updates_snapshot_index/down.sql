@@ -0,0 +1 @@
+DROP INDEX IF EXISTS account_balance_updates_coin_account_block_idx;
@@ -0,0 +1,4 @@
+-- Speeds up restoring the balances of all the accounts for a token as of a certain block.
+-- Updates of each account are ordered from the latest one, matching the `DISTINCT ON` query.
+CREATE INDEX IF NOT EXISTS account_balance_updates_coin_account_block_idx
+    ON account_balance_updates (coin_id, account_id, block_number DESC, update_order_id DESC);
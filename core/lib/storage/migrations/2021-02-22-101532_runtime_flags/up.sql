@@ -0,0 +1,7 @@
+-- Flags switched by the operator in runtime, e.g. to disable fast withdrawals.
+-- Absent flag means its default value is used.
+CREATE TABLE runtime_flags (
+    name TEXT NOT NULL PRIMARY KEY,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
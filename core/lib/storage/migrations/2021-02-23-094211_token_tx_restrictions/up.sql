@@ -0,0 +1,8 @@
+-- Transaction types allowed by the operator for the particular tokens.
+-- Tokens without a row allow all the transaction types.
+CREATE TABLE token_tx_restrictions (
+    token_id INTEGER NOT NULL PRIMARY KEY REFERENCES tokens(id),
+    -- Names of the allowed transaction types, e.g. `Withdraw`.
+    allowed_tx_types TEXT[] NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1,2 @@
+-- Reason of the last flag switch provided by the operator, e.g. shown to the users while the server is paused.
+ALTER TABLE runtime_flags ADD COLUMN reason TEXT;
@@ -0,0 +1 @@
+ALTER TABLE runtime_flags DROP COLUMN IF EXISTS until;
@@ -0,0 +1,2 @@
+-- Time until which the flag is expected to stay switched, as announced by the operator.
+ALTER TABLE runtime_flags ADD COLUMN until TIMESTAMP with time zone;
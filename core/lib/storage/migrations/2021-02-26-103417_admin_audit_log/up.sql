@@ -0,0 +1,9 @@
+-- Actions performed by the operator through the admin API, e.g. eviction of transactions from the mempool.
+CREATE TABLE admin_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    action TEXT NOT NULL,
+    -- Subject of the authentication token used to perform the action.
+    actor TEXT NOT NULL,
+    details JSONB NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
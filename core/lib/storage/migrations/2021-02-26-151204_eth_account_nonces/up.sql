@@ -0,0 +1,6 @@
+-- Nonces of the additional operator accounts, e.g. the ones sending `verify` operations.
+-- Nonce of the main operator account is stored in the `eth_parameters` table.
+CREATE TABLE eth_account_nonces (
+    address BYTEA NOT NULL PRIMARY KEY,
+    nonce BIGINT NOT NULL
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS account_updates_journal_block_number_idx;
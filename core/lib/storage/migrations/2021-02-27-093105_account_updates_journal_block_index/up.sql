@@ -0,0 +1,3 @@
+-- Speeds up locating the position of a block in the journal for the standby state keeper.
+CREATE INDEX IF NOT EXISTS account_updates_journal_block_number_idx
+    ON account_updates_journal (block_number);
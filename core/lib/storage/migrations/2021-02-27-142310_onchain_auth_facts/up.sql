@@ -0,0 +1,7 @@
+-- Public key hashes authorized onchain for `ChangePubKey` operations, as emitted by the `FactAuth` events.
+CREATE TABLE onchain_auth_facts (
+    address BYTEA NOT NULL,
+    nonce BIGINT NOT NULL,
+    pubkey_hash BYTEA NOT NULL,
+    PRIMARY KEY (address, nonce)
+);
@@ -0,0 +1,10 @@
+-- Progress of the background migrations backfilling the data of the online schema migrations.
+CREATE TABLE background_migrations (
+    name TEXT NOT NULL PRIMARY KEY,
+    -- Key of the last processed row, the backfill continues after it.
+    last_processed_key BIGINT,
+    processed_rows BIGINT NOT NULL DEFAULT 0,
+    started_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    finished_at TIMESTAMP with time zone
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS witness_debug_bundles;
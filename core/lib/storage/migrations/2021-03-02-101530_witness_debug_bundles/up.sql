@@ -0,0 +1,14 @@
+-- Debugging artifacts of the blocks the provers failed to prove.
+-- Failure is reported by the prover, and the bundle is built by the `witness_debug_bundle` tool afterwards.
+CREATE TABLE witness_debug_bundles (
+    block_number BIGINT NOT NULL PRIMARY KEY,
+    -- Error reported by the prover.
+    failure TEXT NOT NULL,
+    reported_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    -- Path of the first unsatisfied constraint, if any.
+    unsatisfied_constraint TEXT,
+    num_constraints BIGINT,
+    -- Circuit inputs of the failed operation, `NULL` until the bundle is built.
+    bundle jsonb,
+    created_at TIMESTAMP with time zone
+);
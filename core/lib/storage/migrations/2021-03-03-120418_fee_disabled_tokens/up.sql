@@ -0,0 +1,5 @@
+-- Tokens the operator doesn't accept to pay fees in, regardless of their market volume.
+CREATE TABLE fee_disabled_tokens (
+    token_id INTEGER NOT NULL PRIMARY KEY REFERENCES tokens(id),
+    disabled_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS txs_batches_hashes;
@@ -0,0 +1,10 @@
+-- Hashes and the transactions of the batches accepted to the mempool.
+CREATE TABLE txs_batches_hashes (
+    batch_id BIGINT PRIMARY KEY,
+    batch_hash BYTEA NOT NULL,
+    tx_hashes BYTEA[] NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX txs_batches_hashes_batch_hash_idx ON txs_batches_hashes (batch_hash);
+-- Old batches are pruned by the creation time.
+CREATE INDEX txs_batches_hashes_created_at_idx ON txs_batches_hashes (created_at);
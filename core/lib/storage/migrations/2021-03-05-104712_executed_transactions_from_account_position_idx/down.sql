@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS executed_transactions_from_account_position_idx;
@@ -0,0 +1,6 @@
+-- Indices of the account transactions history sorted by the transaction position,
+-- so the pages of the history are loaded without scanning all the account transactions.
+-- Indices are built concurrently to not lock the tables, which requires running every
+-- statement in a separate migration outside of the transaction.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS executed_transactions_from_account_position_idx
+    ON executed_transactions (from_account, block_number, (COALESCE(block_index, -1)), tx_hash);
@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS executed_transactions_to_account_position_idx;
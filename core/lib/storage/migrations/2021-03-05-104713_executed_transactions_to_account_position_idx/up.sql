@@ -0,0 +1,2 @@
+CREATE INDEX CONCURRENTLY IF NOT EXISTS executed_transactions_to_account_position_idx
+    ON executed_transactions (to_account, block_number, (COALESCE(block_index, -1)), tx_hash);
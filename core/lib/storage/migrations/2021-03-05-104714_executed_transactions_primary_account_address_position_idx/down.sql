@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS executed_transactions_primary_account_address_position_idx;
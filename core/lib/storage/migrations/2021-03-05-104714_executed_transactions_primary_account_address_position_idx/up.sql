@@ -0,0 +1,2 @@
+CREATE INDEX CONCURRENTLY IF NOT EXISTS executed_transactions_primary_account_address_position_idx
+    ON executed_transactions (primary_account_address, block_number, (COALESCE(block_index, -1)), tx_hash);
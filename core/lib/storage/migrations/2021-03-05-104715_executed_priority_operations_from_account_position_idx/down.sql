@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS executed_priority_operations_from_account_position_idx;
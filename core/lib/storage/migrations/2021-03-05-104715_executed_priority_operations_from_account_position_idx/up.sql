@@ -0,0 +1,2 @@
+CREATE INDEX CONCURRENTLY IF NOT EXISTS executed_priority_operations_from_account_position_idx
+    ON executed_priority_operations (from_account, block_number, block_index, eth_hash);
@@ -0,0 +1,2 @@
+CREATE INDEX CONCURRENTLY IF NOT EXISTS executed_priority_operations_to_account_position_idx
+    ON executed_priority_operations (to_account, block_number, block_index, eth_hash);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS network_stats_daily;
+DROP TABLE IF EXISTS network_stats_daily_fees;
+DROP TABLE IF EXISTS network_stats_tvl;
@@ -0,0 +1,27 @@
+-- Network statistics precomputed by the stats aggregator of the server,
+-- so the API doesn't have to scan the history tables on every request.
+
+-- Executed transactions and priority operations per day (in UTC).
+CREATE TABLE network_stats_daily (
+    day DATE PRIMARY KEY,
+    tx_count BIGINT NOT NULL,
+    -- Number of the distinct addresses which have initiated a transaction.
+    active_accounts BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+-- Average fees of the transactions per day and the fee token.
+CREATE TABLE network_stats_daily_fees (
+    day DATE NOT NULL,
+    token_id INTEGER NOT NULL,
+    tx_count BIGINT NOT NULL,
+    average_fee NUMERIC NOT NULL,
+    PRIMARY KEY (day, token_id)
+);
+
+-- Total value locked in the verified state per token.
+CREATE TABLE network_stats_tvl (
+    token_id INTEGER PRIMARY KEY,
+    amount NUMERIC NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
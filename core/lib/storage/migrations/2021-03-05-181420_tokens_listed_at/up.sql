@@ -0,0 +1,4 @@
+-- Time the token was listed at. It's unknown for the tokens listed before
+-- the column was added, so only the new tokens get the timestamp.
+ALTER TABLE tokens ADD COLUMN listed_at TIMESTAMP with time zone;
+ALTER TABLE tokens ALTER COLUMN listed_at SET DEFAULT now();
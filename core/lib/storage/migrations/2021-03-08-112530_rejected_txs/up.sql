@@ -0,0 +1,17 @@
+-- Transactions rejected by the API server on submission, so it's possible to find out
+-- why the transaction never made it to the mempool.
+CREATE TABLE rejected_txs (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash BYTEA NOT NULL,
+    -- Address of the account which has initiated the transaction.
+    address BYTEA NOT NULL,
+    tx JSONB NOT NULL,
+    -- Stable API error code the submission was rejected with.
+    error_code INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX rejected_txs_tx_hash_idx ON rejected_txs (tx_hash);
+CREATE INDEX rejected_txs_address_idx ON rejected_txs (address, id);
+CREATE INDEX rejected_txs_created_at_idx ON rejected_txs (created_at);
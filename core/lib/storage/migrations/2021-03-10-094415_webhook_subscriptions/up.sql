@@ -0,0 +1,14 @@
+-- Webhooks registered by the integrators to be notified about the activity of the addresses.
+CREATE TABLE webhook_subscriptions (
+    id BIGSERIAL PRIMARY KEY,
+    -- Name of the integrator the subscription belongs to, as set in the API keys config.
+    client TEXT NOT NULL,
+    address BYTEA NOT NULL,
+    url TEXT NOT NULL,
+    -- Key of the HMAC signatures of the delivered events.
+    secret TEXT NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX webhook_subscriptions_address_idx ON webhook_subscriptions (address);
+CREATE INDEX webhook_subscriptions_client_idx ON webhook_subscriptions (client, id);
@@ -0,0 +1,10 @@
+-- Co-signers of the accounts opted in to the 2-Factor authentication of their transactions.
+CREATE TABLE account_cosigners (
+    address BYTEA PRIMARY KEY,
+    -- Address of the co-signer key, NULL if the account has opted out.
+    cosigner BYTEA,
+    -- Number of the co-signer registrations of the account. Every registration is signed along
+    -- with the next number, so the signed registrations can't be replayed.
+    registration_nonce BIGINT NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS core_api_outbox_pending_idx;
@@ -0,0 +1,2 @@
+-- Outbox requests without the result are polled by the Core to process the ones left unprocessed.
+CREATE INDEX core_api_outbox_pending_idx ON core_api_outbox (id) WHERE result IS NULL;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS governance_paused_tokens;
+DROP TABLE IF EXISTS governance_network_governor;
+DROP TABLE IF EXISTS governance_validators;
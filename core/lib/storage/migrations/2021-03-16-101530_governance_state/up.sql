@@ -0,0 +1,18 @@
+-- Validators set in the governance contract, as observed by `eth_watch`.
+CREATE TABLE governance_validators (
+    address BYTEA PRIMARY KEY,
+    is_active BOOLEAN NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+-- Network governor set in the governance contract, the table has at most one row.
+CREATE TABLE governance_network_governor (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    address BYTEA NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+-- Tokens paused in the governance contract, the deposits of the paused tokens are rejected on L1.
+CREATE TABLE governance_paused_tokens (
+    address BYTEA PRIMARY KEY,
+    paused BOOLEAN NOT NULL,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
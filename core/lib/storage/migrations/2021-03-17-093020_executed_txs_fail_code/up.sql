@@ -0,0 +1,2 @@
+-- Machine-readable class of the transaction failure, set by the state along with `fail_reason`.
+ALTER TABLE executed_transactions ADD COLUMN fail_code TEXT;
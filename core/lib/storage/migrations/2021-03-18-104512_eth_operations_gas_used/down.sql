@@ -0,0 +1 @@
+ALTER TABLE eth_operations DROP COLUMN gas_used;
@@ -0,0 +1,2 @@
+-- Amount of gas used by the accepted transaction, stored once the operation is confirmed.
+ALTER TABLE eth_operations ADD COLUMN gas_used BIGINT;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS webhook_notifier_state;
+DROP TABLE IF EXISTS webhook_deliveries;
@@ -0,0 +1,22 @@
+-- Events pending delivery to the webhooks. Events are stored once the block event is handled
+-- and removed once delivered or after the last failed attempt, so the retries survive restarts.
+CREATE TABLE webhook_deliveries (
+    id BIGSERIAL PRIMARY KEY,
+    subscription_id BIGINT NOT NULL REFERENCES webhook_subscriptions (id) ON DELETE CASCADE,
+    event jsonb NOT NULL,
+    -- Number of the failed delivery attempts.
+    attempts INT NOT NULL DEFAULT 0,
+    -- Set forward when the delivery is claimed, so the delivery in progress isn't claimed again.
+    next_attempt_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX webhook_deliveries_next_attempt_idx ON webhook_deliveries (next_attempt_at);
+
+-- Last blocks the webhook events were created for, so the events of the blocks missed while
+-- the notifier was lagging behind or stopped are created from the stored blocks.
+CREATE TABLE webhook_notifier_state (
+    id BOOLEAN PRIMARY KEY DEFAULT true CHECK (id),
+    last_committed_block BIGINT NOT NULL,
+    last_verified_block BIGINT NOT NULL
+);
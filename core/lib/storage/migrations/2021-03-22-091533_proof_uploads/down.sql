@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS proof_upload_chunks;
+DROP TABLE IF EXISTS proof_uploads;
@@ -0,0 +1,22 @@
+-- Unfinished proof uploads of the prover runs. Uploaded chunks are stored in the database instead
+-- of the server memory, so the upload is resumed after the restart of the server.
+CREATE TABLE proof_uploads (
+    block_number BIGINT NOT NULL,
+    prover_run_id INT NOT NULL,
+    -- Size of the whole serialized proof in bytes.
+    total_size BIGINT NOT NULL,
+    -- Amount of the proof bytes received so far.
+    uploaded BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP with time zone NOT NULL DEFAULT now(),
+    PRIMARY KEY (block_number, prover_run_id)
+);
+
+CREATE TABLE proof_upload_chunks (
+    block_number BIGINT NOT NULL,
+    prover_run_id INT NOT NULL,
+    chunk_offset BIGINT NOT NULL,
+    data bytea NOT NULL,
+    PRIMARY KEY (block_number, prover_run_id, chunk_offset),
+    FOREIGN KEY (block_number, prover_run_id)
+        REFERENCES proof_uploads (block_number, prover_run_id) ON DELETE CASCADE
+);
@@ -0,0 +1,53 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use self::records::StoredAuditEntry;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Admin audit schema keeps the log of the actions performed by the operator through
+/// the admin API, so it's possible to find out who did what and when.
+#[derive(Debug)]
+pub struct AdminAuditSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> AdminAuditSchema<'a, 'c> {
+    /// Records the action performed by the operator, returns the ID of the entry.
+    pub async fn record(
+        &mut self,
+        action: &str,
+        actor: &str,
+        details: serde_json::Value,
+    ) -> QueryResult<i64> {
+        let start = Instant::now();
+        let id = sqlx::query!(
+            "INSERT INTO admin_audit_log (action, actor, details) VALUES ($1, $2, $3) RETURNING id",
+            action,
+            actor,
+            details
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        report_query!("sql.admin_audit.record", start);
+        Ok(id)
+    }
+
+    /// Loads the latest entries of the log, starting with the most recent one.
+    pub async fn load_latest(&mut self, limit: u32) -> QueryResult<Vec<StoredAuditEntry>> {
+        let start = Instant::now();
+        let entries = sqlx::query_as!(
+            StoredAuditEntry,
+            "SELECT * FROM admin_audit_log ORDER BY id DESC LIMIT $1",
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.admin_audit.load_latest", start);
+        Ok(entries)
+    }
+}
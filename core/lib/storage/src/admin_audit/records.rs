@@ -0,0 +1,16 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Action performed by the operator through the admin API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredAuditEntry {
+    pub id: i64,
+    pub action: String,
+    pub actor: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,218 @@
+//! Background migrations backfilling the data of the online schema migrations.
+//!
+//! Schema changes of the large tables are performed in the expand/contract style,
+//! so none of the steps blocks the tables for long:
+//!
+//! 1. Expand: the regular migration adds the new schema (e.g. a nullable column or a new table),
+//!    and the server starts to maintain both the old and the new schema for the new rows.
+//! 2. Backfill: the background migration fills the new schema for the existing rows in small
+//!    batches, while the server keeps running. The progress is stored in the database, so the
+//!    backfill continues from the last processed row after the restart.
+//! 3. Contract: once the backfill is finished (see [`BackgroundMigrationsSchema::is_finished`]),
+//!    the next release switches to the new schema, and the regular migration drops the old one.
+//!
+//! [`BackgroundMigrationsSchema::is_finished`]: struct.BackgroundMigrationsSchema.html#method.is_finished
+
+// Built-in deps
+use std::time::Instant;
+// External imports
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use self::witness_commitments::WitnessCommitments;
+use crate::{QueryResult, StorageProcessor};
+
+mod witness_commitments;
+
+/// Rows processed by one batch of the background migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillBatch {
+    /// Key of the last processed row.
+    pub last_key: i64,
+    /// Amount of the processed rows.
+    pub rows: u32,
+}
+
+/// Backfill job of the online schema migration.
+#[async_trait]
+pub trait BackgroundMigration: Send + Sync {
+    /// Unique name of the migration, used to track its progress.
+    fn name(&self) -> &'static str;
+
+    /// Processes up to `limit` rows with the keys greater than `after` (or from the first row,
+    /// if `after` is `None`) in the ascending order of the keys.
+    /// Returns `None` if there are no rows left to process.
+    ///
+    /// The batch is processed in the same database transaction as the progress is stored.
+    async fn backfill(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        after: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Option<BackfillBatch>>;
+}
+
+/// Returns the background migrations run by the server in the given order.
+///
+/// Migrations are never removed from the list until their contract step is released,
+/// so the servers that haven't finished the backfill yet can complete it.
+pub fn background_migrations() -> Vec<Box<dyn BackgroundMigration>> {
+    vec![Box::new(WitnessCommitments)]
+}
+
+/// Progress of the background migration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackgroundMigrationProgress {
+    pub name: String,
+    /// Key of the last processed row, the backfill continues after it.
+    pub last_processed_key: Option<i64>,
+    pub processed_rows: i64,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl BackgroundMigrationProgress {
+    pub fn is_finished(&self) -> bool {
+        self.finished_at.is_some()
+    }
+}
+
+/// Background migrations schema tracks the progress of the background migrations.
+#[derive(Debug)]
+pub struct BackgroundMigrationsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> BackgroundMigrationsSchema<'a, 'c> {
+    /// Loads the progress of the migration, returns `None` if the migration hasn't started yet.
+    pub async fn load_progress(
+        &mut self,
+        name: &str,
+    ) -> QueryResult<Option<BackgroundMigrationProgress>> {
+        let start = Instant::now();
+        let progress = sqlx::query!(
+            "SELECT name, last_processed_key, processed_rows, started_at, updated_at, finished_at
+            FROM background_migrations WHERE name = $1",
+            name
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| BackgroundMigrationProgress {
+            name: record.name,
+            last_processed_key: record.last_processed_key,
+            processed_rows: record.processed_rows,
+            started_at: record.started_at,
+            updated_at: record.updated_at,
+            finished_at: record.finished_at,
+        });
+
+        report_query!("sql.background_migrations.load_progress", start);
+        Ok(progress)
+    }
+
+    /// Loads the progress of all the started migrations.
+    pub async fn load_all_progress(&mut self) -> QueryResult<Vec<BackgroundMigrationProgress>> {
+        let start = Instant::now();
+        let progress = sqlx::query!(
+            "SELECT name, last_processed_key, processed_rows, started_at, updated_at, finished_at
+            FROM background_migrations ORDER BY started_at"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| BackgroundMigrationProgress {
+            name: record.name,
+            last_processed_key: record.last_processed_key,
+            processed_rows: record.processed_rows,
+            started_at: record.started_at,
+            updated_at: record.updated_at,
+            finished_at: record.finished_at,
+        })
+        .collect();
+
+        report_query!("sql.background_migrations.load_all_progress", start);
+        Ok(progress)
+    }
+
+    /// Stores the batch processed by the migration.
+    pub async fn save_progress(&mut self, name: &str, batch: BackfillBatch) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO background_migrations (name, last_processed_key, processed_rows)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET last_processed_key = $2,
+                processed_rows = background_migrations.processed_rows + $3,
+                updated_at = now()",
+            name,
+            batch.last_key,
+            i64::from(batch.rows)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.background_migrations.save_progress", start);
+        Ok(())
+    }
+
+    /// Marks the migration as finished once there are no rows left to process.
+    pub async fn mark_finished(&mut self, name: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO background_migrations (name, finished_at)
+            VALUES ($1, now())
+            ON CONFLICT (name) DO UPDATE
+            SET finished_at = now(), updated_at = now()",
+            name
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.background_migrations.mark_finished", start);
+        Ok(())
+    }
+
+    /// Processes the next batch of the migration in one transaction with its progress.
+    /// Returns `None` if there are no rows left to process and the migration is finished.
+    pub async fn process_batch(
+        &mut self,
+        migration: &dyn BackgroundMigration,
+        limit: u32,
+    ) -> QueryResult<Option<BackfillBatch>> {
+        let mut transaction = self.0.start_transaction().await?;
+
+        let progress = BackgroundMigrationsSchema(&mut transaction)
+            .load_progress(migration.name())
+            .await?;
+        if progress.as_ref().map(|progress| progress.is_finished()) == Some(true) {
+            return Ok(None);
+        }
+        let after = progress.and_then(|progress| progress.last_processed_key);
+
+        let batch = migration.backfill(&mut transaction, after, limit).await?;
+        match batch {
+            Some(batch) => {
+                BackgroundMigrationsSchema(&mut transaction)
+                    .save_progress(migration.name(), batch)
+                    .await?
+            }
+            None => {
+                BackgroundMigrationsSchema(&mut transaction)
+                    .mark_finished(migration.name())
+                    .await?
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(batch)
+    }
+
+    /// Returns `true` if the backfill of the migration is finished, so its contract step can be performed.
+    pub async fn is_finished(&mut self, name: &str) -> QueryResult<bool> {
+        Ok(self
+            .load_progress(name)
+            .await?
+            .map(|progress| progress.is_finished())
+            .unwrap_or(false))
+    }
+}
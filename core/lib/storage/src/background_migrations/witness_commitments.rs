@@ -0,0 +1,63 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use async_trait::async_trait;
+// Workspace imports
+// Local imports
+use super::{BackfillBatch, BackgroundMigration};
+use crate::{QueryResult, StorageProcessor};
+
+/// Fills the commitments of the witnesses stored before the commitment column was added
+/// to `block_witness`, so the proofs of these blocks can be looked up in the proof cache.
+///
+/// The commitment is taken from the public data commitment of the witness itself,
+/// which is stored as the big-endian hex string.
+#[derive(Debug)]
+pub struct WitnessCommitments;
+
+#[async_trait]
+impl BackgroundMigration for WitnessCommitments {
+    fn name(&self) -> &'static str {
+        "witness_commitments"
+    }
+
+    async fn backfill(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        after: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Option<BackfillBatch>> {
+        let start = Instant::now();
+        let blocks: Vec<i64> = sqlx::query!(
+            "SELECT block FROM block_witness WHERE block > $1 ORDER BY block LIMIT $2",
+            after.unwrap_or(i64::MIN),
+            i64::from(limit)
+        )
+        .fetch_all(storage.conn())
+        .await?
+        .into_iter()
+        .map(|row| row.block)
+        .collect();
+
+        let last_key = match blocks.last() {
+            Some(block) => *block,
+            None => return Ok(None),
+        };
+
+        let rows = sqlx::query!(
+            "UPDATE block_witness
+            SET commitment = decode(witness::jsonb->>'public_data_commitment', 'hex')
+            WHERE block = ANY($1) AND commitment IS NULL",
+            &blocks
+        )
+        .execute(storage.conn())
+        .await?
+        .rows_affected();
+
+        report_query!("sql.background_migrations.witness_commitments", start);
+        Ok(Some(BackfillBatch {
+            last_key,
+            rows: rows as u32,
+        }))
+    }
+}
@@ -3,7 +3,9 @@ use std::time::Instant;
 // External imports
 use sqlx::Acquire;
 // Workspace imports
-use zksync_types::{Account, AccountId, AccountUpdates, Address};
+use zksync_types::{
+    Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+};
 // Local imports
 use self::records::*;
 use crate::diff::StorageAccountDiff;
@@ -39,7 +41,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             .await?
             .map(|a| (account_id, a));
 
-        metrics::histogram!("sql.chain.account.account_state_by_id", start.elapsed());
+        report_query!("sql.chain.account.account_state_by_id", start);
         Ok(StoredAccountState {
             committed,
             verified,
@@ -62,10 +64,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
             })
         };
 
-        metrics::histogram!(
-            "sql.chain.account.account_state_by_address",
-            start.elapsed()
-        );
+        report_query!("sql.chain.account.account_state_by_address", start);
         account_state
     }
 
@@ -155,10 +154,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.chain.account.last_committed_state_for_account",
-            start.elapsed()
-        );
+        report_query!("sql.chain.account.last_committed_state_for_account", start);
         Ok(account_state)
     }
 
@@ -170,10 +166,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
     ) -> QueryResult<Option<Account>> {
         let start = Instant::now();
         let (_, account) = self.account_and_last_block(account_id).await?;
-        metrics::histogram!(
-            "sql.chain.account.last_verified_state_for_account",
-            start.elapsed()
-        );
+        report_query!("sql.chain.account.last_verified_state_for_account", start);
         Ok(account)
     }
 
@@ -218,10 +211,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!(
-            "sql.chain.account.get_account_and_last_block",
-            start.elapsed()
-        );
+        report_query!("sql.chain.account.get_account_and_last_block", start);
         result
     }
 
@@ -245,7 +235,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let account_id = result.map(|record| AccountId(record.account_id as u32));
-        metrics::histogram!("sql.chain.account.account_id_by_address", start.elapsed());
+        report_query!("sql.chain.account.account_id_by_address", start);
         Ok(account_id)
     }
 
@@ -263,7 +253,100 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         .await?;
 
         let address = result.map(|record| Address::from_slice(&record.address));
-        metrics::histogram!("sql.chain.account.account_address_by_id", start.elapsed());
+        report_query!("sql.chain.account.account_address_by_id", start);
         Ok(address)
     }
+
+    /// Loads the non-zero balances of the given token as of the given block,
+    /// ordered by the account ID, starting from the account following `after`.
+    ///
+    /// Balances are restored from the balance updates history, so the block must be
+    /// at least committed for the snapshot to be meaningful.
+    pub async fn load_balances_snapshot(
+        &mut self,
+        token: TokenId,
+        block: BlockNumber,
+        after: Option<AccountId>,
+        limit: u32,
+    ) -> QueryResult<Vec<StorageBalanceSnapshotEntry>> {
+        let start = Instant::now();
+        let after = after.map(|id| i64::from(*id)).unwrap_or(-1);
+        let balances = sqlx::query_as!(
+            StorageBalanceSnapshotEntry,
+            r#"
+            WITH latest_balances AS (
+                SELECT DISTINCT ON (account_id) account_id, new_balance
+                FROM account_balance_updates
+                WHERE coin_id = $1 AND block_number <= $2 AND account_id > $3
+                ORDER BY account_id, block_number DESC, update_order_id DESC
+            )
+            SELECT
+                latest_balances.account_id AS "account_id!",
+                account_creates.address AS "address!",
+                latest_balances.new_balance AS "balance!"
+            FROM latest_balances
+            INNER JOIN account_creates ON
+                account_creates.account_id = latest_balances.account_id AND account_creates.is_create = true
+            WHERE latest_balances.new_balance > 0
+            ORDER BY latest_balances.account_id ASC
+            LIMIT $4
+            "#,
+            i32::from(*token),
+            i64::from(*block),
+            after,
+            i64::from(limit),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.account.load_balances_snapshot", start);
+        Ok(balances)
+    }
+
+    /// Stores the public key hash authorized onchain by the account for the `ChangePubKey`
+    /// operation with the given nonce. Authorization facts can't be changed once set, so
+    /// the repeated facts are ignored.
+    pub async fn store_auth_fact(
+        &mut self,
+        address: Address,
+        nonce: Nonce,
+        pub_key_hash: &PubKeyHash,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO onchain_auth_facts (address, nonce, pubkey_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (address, nonce) DO NOTHING",
+            address.as_bytes(),
+            i64::from(*nonce),
+            &pub_key_hash.data[..]
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.account.store_auth_fact", start);
+        Ok(())
+    }
+
+    /// Loads the public key hash authorized onchain by the account for the given nonce.
+    /// Returns `None` if there is no such authorization fact known.
+    pub async fn load_auth_fact(
+        &mut self,
+        address: Address,
+        nonce: Nonce,
+    ) -> QueryResult<Option<PubKeyHash>> {
+        let start = Instant::now();
+        let pub_key_hash = sqlx::query!(
+            "SELECT pubkey_hash FROM onchain_auth_facts WHERE address = $1 AND nonce = $2",
+            address.as_bytes(),
+            i64::from(*nonce)
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|fact| PubKeyHash::from_bytes(&fact.pubkey_hash))
+        .transpose()?;
+
+        report_query!("sql.chain.account.load_auth_fact", start);
+        Ok(pub_key_hash)
+    }
 }
@@ -1,4 +1,5 @@
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::{types::BigDecimal, FromRow};
 
 #[derive(Debug, FromRow)]
@@ -45,9 +46,27 @@ pub struct StorageAccountPubkeyUpdate {
     pub new_nonce: i64,
 }
 
+#[derive(Debug, FromRow)]
+pub struct StorageAccountUpdateJournalEntry {
+    pub sequence_number: i64,
+    pub block_number: i64,
+    pub account_id: i64,
+    pub update_order_id: i32,
+    pub account_update: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, FromRow, Clone)]
 pub struct StorageBalance {
     pub account_id: i64,
     pub coin_id: i32,
     pub balance: BigDecimal,
 }
+
+/// Balance of the account in the token balances snapshot.
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct StorageBalanceSnapshotEntry {
+    pub account_id: i64,
+    pub address: Vec<u8>,
+    pub balance: BigDecimal,
+}
@@ -64,6 +64,7 @@ impl StoredExecutedTransaction {
             success: self.success,
             op: franklin_op,
             fail_reason: self.fail_reason,
+            fail_code: self.fail_code.as_deref().and_then(|code| code.parse().ok()),
             block_index: self
                 .block_index
                 .map(|val| u32::try_from(val).expect("Invalid block index")),
@@ -186,6 +187,7 @@ impl NewExecutedTransaction {
             created_at: exec_tx.created_at,
             eth_sign_data,
             batch_id: exec_tx.batch_id,
+            fail_code: exec_tx.fail_code.map(|code| code.as_str().to_owned()),
         }
     }
 }
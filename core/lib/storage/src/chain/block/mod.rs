@@ -3,7 +3,7 @@ use std::time::Instant;
 // External imports
 use zksync_basic_types::U256;
 // Workspace imports
-use zksync_crypto::convert::FeConvert;
+use zksync_crypto::{convert::FeConvert, Fr};
 use zksync_types::{block::PendingBlock, Action, ActionType, Operation};
 use zksync_types::{
     block::{Block, ExecutedOperations},
@@ -11,7 +11,8 @@ use zksync_types::{
 };
 // Local imports
 use self::records::{
-    AccountTreeCache, BlockDetails, BlockTransactionItem, StorageBlock, StoragePendingBlock,
+    AccountTreeCache, BlockDetails, BlockStageDetails, BlockTransactionItem, StorageBlock,
+    StoragePendingBlock,
 };
 use crate::{
     chain::operations::{
@@ -74,7 +75,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = stored.into_op(&mut transaction).await;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.execute_operation", start.elapsed());
+        report_query!("sql.chain.block.execute_operation", start);
         result
     }
 
@@ -104,7 +105,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 }
             }
         }
-        metrics::histogram!("sql.chain.block.save_block_transactions", start.elapsed());
+        report_query!("sql.chain.block.save_block_transactions", start);
         Ok(())
     }
 
@@ -119,7 +120,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_storage_block", start.elapsed());
+        report_query!("sql.chain.block.get_storage_block", start);
 
         Ok(block)
     }
@@ -157,11 +158,23 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             U256::from(stored_block.verify_gas_limit as u64),
         ));
 
-        metrics::histogram!("sql.chain.block.get_block", start.elapsed());
+        report_query!("sql.chain.block.get_block", start);
 
         Ok(result)
     }
 
+    /// Returns the root hash of the state after the given block,
+    /// or `None` if the block does not exist yet.
+    pub async fn get_block_root_hash(&mut self, block: BlockNumber) -> QueryResult<Option<Fr>> {
+        let start = Instant::now();
+        let root_hash = self.get_storage_block(block).await?.map(|stored_block| {
+            FeConvert::from_bytes(&stored_block.root_hash).expect("Unparsable root hash")
+        });
+
+        report_query!("sql.chain.block.get_block_root_hash", start);
+        Ok(root_hash)
+    }
+
     /// Same as `get_block_executed_ops`, but returns a vector of `ZkSyncOp` instead
     /// of `ExecutedOperations`.
     pub async fn get_block_operations(&mut self, block: BlockNumber) -> QueryResult<Vec<ZkSyncOp>> {
@@ -174,7 +187,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 ExecutedOperations::PriorityOp(priorop) => Some(priorop.op),
             })
             .collect();
-        metrics::histogram!("sql.chain.block.get_block_operations", start.elapsed());
+        report_query!("sql.chain.block.get_block_operations", start);
         Ok(result)
     }
 
@@ -227,7 +240,62 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_block_transactions", start.elapsed());
+        report_query!("sql.chain.block.get_block_transactions", start);
+        Ok(block_txs)
+    }
+
+    /// Loads the transactions and priority operations of the given blocks in a single query,
+    /// ordered by the block number and then in the same way as in `get_block_transactions`.
+    pub async fn get_blocks_transactions(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<Vec<BlockTransactionItem>> {
+        let start = Instant::now();
+        let block_numbers: Vec<_> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let block_txs = sqlx::query_as!(
+            BlockTransactionItem,
+            r#"
+                WITH transactions AS (
+                    SELECT
+                        '0x' || encode(tx_hash, 'hex') as tx_hash,
+                        tx as op,
+                        block_number,
+                        success,
+                        fail_reason,
+                        created_at
+                    FROM executed_transactions
+                    WHERE block_number = ANY($1)
+                ), priority_ops AS (
+                    SELECT
+                        '0x' || encode(eth_hash, 'hex') as tx_hash,
+                        operation as op,
+                        block_number,
+                        true as success,
+                        Null as fail_reason,
+                        created_at
+                    FROM executed_priority_operations
+                    WHERE block_number = ANY($1)
+                ), everything AS (
+                    SELECT * FROM transactions
+                    UNION ALL
+                    SELECT * FROM priority_ops
+                )
+                SELECT
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number!",
+                    op as "op!",
+                    success as "success?",
+                    fail_reason as "fail_reason?",
+                    created_at as "created_at!"
+                FROM everything
+                ORDER BY block_number, created_at DESC
+            "#,
+            &block_numbers
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.block.get_blocks_transactions", start);
         Ok(block_txs)
     }
 
@@ -290,7 +358,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             }
         });
 
-        metrics::histogram!("sql.chain.block.get_block_executed_ops", start.elapsed());
+        report_query!("sql.chain.block.get_block_executed_ops", start);
         Ok(executed_operations)
     }
 
@@ -346,10 +414,42 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         ).fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.load_block_range", start.elapsed());
+        report_query!("sql.chain.block.load_block_range", start);
         Ok(details)
     }
 
+    /// Loads the processing stages (commit and verify) of the block in the order
+    /// of their creation, together with the Ethereum operations sending them to L1.
+    pub async fn load_block_stages(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Vec<BlockStageDetails>> {
+        let start = Instant::now();
+        let stages = sqlx::query_as!(
+            BlockStageDetails,
+            r#"
+            SELECT
+                operations.action_type as "action_type!: StorageActionType",
+                operations.created_at,
+                eth_operations.id AS "eth_op_id?",
+                eth_operations.final_hash AS "final_hash?",
+                eth_operations.confirmed_at AS "confirmed_at?",
+                eth_operations.gas_used AS "gas_used?"
+            FROM operations
+                LEFT JOIN eth_ops_binding ON eth_ops_binding.op_id = operations.id
+                LEFT JOIN eth_operations ON eth_operations.id = eth_ops_binding.eth_op_id
+            WHERE operations.block_number = $1
+            ORDER BY operations.id ASC
+            "#,
+            i64::from(*block_number)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.block.load_block_stages", start);
+        Ok(stages)
+    }
+
     /// Helper method for `find_block_by_height_or_hash`. It checks whether
     /// provided string can be interpreted like a hash, and if so, returns the
     /// hexadecimal string without prefix.
@@ -454,10 +554,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .ok()
             .flatten();
 
-        metrics::histogram!(
-            "sql.chain.block.find_block_by_height_or_hash",
-            start.elapsed()
-        );
+        report_query!("sql.chain.block.find_block_by_height_or_hash", start);
         result
     }
 
@@ -471,14 +568,14 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         } else {
             None
         };
-        metrics::histogram!("sql.chain.block.load_commit_op", start.elapsed());
+        report_query!("sql.chain.block.load_commit_op", start);
         result
     }
 
     pub async fn load_committed_block(&mut self, block_number: BlockNumber) -> Option<Block> {
         let start = Instant::now();
         let op = self.load_commit_op(block_number).await;
-        metrics::histogram!("sql.chain.block.load_committed_block", start.elapsed());
+        report_query!("sql.chain.block.load_committed_block", start);
         op.map(|op| op.block)
     }
 
@@ -488,7 +585,17 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_action(ActionType::COMMIT, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_committed_block", start.elapsed());
+        report_query!("sql.chain.block.get_last_committed_block", start);
+        result
+    }
+
+    /// Returns the number of last block for which commit transaction has been confirmed on Ethereum.
+    pub async fn get_last_committed_confirmed_block(&mut self) -> QueryResult<BlockNumber> {
+        let start = Instant::now();
+        let result = OperationsSchema(self.0)
+            .get_last_block_by_action(ActionType::COMMIT, Some(true))
+            .await;
+        report_query!("sql.chain.block.get_last_committed_confirmed_block", start);
         result
     }
 
@@ -502,7 +609,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_action(ActionType::VERIFY, None)
             .await;
-        metrics::histogram!("sql.chain.block.get_last_verified_block", start.elapsed());
+        report_query!("sql.chain.block.get_last_verified_block", start);
         result
     }
 
@@ -513,10 +620,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let result = OperationsSchema(self.0)
             .get_last_block_by_action(ActionType::VERIFY, Some(true))
             .await;
-        metrics::histogram!(
-            "sql.chain.block.get_last_verified_confirmed_block",
-            start.elapsed()
-        );
+        report_query!("sql.chain.block.get_last_verified_confirmed_block", start);
         result
     }
 
@@ -531,10 +635,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         )
         .fetch_optional(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.block.load_storage_pending_block",
-            start.elapsed()
-        );
+        report_query!("sql.chain.block.load_storage_pending_block", start);
 
         Ok(maybe_block)
     }
@@ -577,7 +678,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.load_pending_block", start.elapsed());
+        report_query!("sql.chain.block.load_pending_block", start);
         Ok(Some(result))
     }
 
@@ -586,10 +687,24 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let start = Instant::now();
         let result = self.load_storage_pending_block().await?.is_some();
 
-        metrics::histogram!("sql.chain.block.pending_block_exists", start.elapsed());
+        report_query!("sql.chain.block.pending_block_exists", start);
         Ok(result)
     }
 
+    /// Removes stored pending blocks with numbers greater than `last_block`.
+    pub async fn remove_pending_blocks(&mut self, last_block: BlockNumber) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM pending_block WHERE number > $1",
+            i64::from(*last_block)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.block.remove_pending_blocks", start);
+        Ok(())
+    }
+
     /// Stores given pending block into the database.
     pub async fn save_pending_block(&mut self, pending_block: PendingBlock) -> QueryResult<()> {
         let start = Instant::now();
@@ -630,7 +745,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             .await?;
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.block.load_pending_block", start.elapsed());
+        report_query!("sql.chain.block.load_pending_block", start);
 
         Ok(())
     }
@@ -651,7 +766,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.chain.block.count_operations", start.elapsed());
+        report_query!("sql.chain.block.count_operations", start);
         Ok(count)
     }
 
@@ -705,7 +820,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.block.save_block", start.elapsed());
+        report_query!("sql.chain.block.save_block", start);
         Ok(())
     }
 
@@ -733,7 +848,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.store_account_tree_cache", start.elapsed());
+        report_query!("sql.chain.block.store_account_tree_cache", start);
         Ok(())
     }
 
@@ -753,7 +868,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.block.get_account_tree_cache", start.elapsed());
+        report_query!("sql.chain.block.get_account_tree_cache", start);
         Ok(account_tree_cache.map(|w| {
             (
                 BlockNumber(w.block as u32),
@@ -780,10 +895,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.block.get_account_tree_cache_block",
-            start.elapsed()
-        );
+        report_query!("sql.chain.block.get_account_tree_cache_block", start);
         Ok(account_tree_cache.map(|w| {
             serde_json::from_str(&w.tree_cache).expect("Failed to deserialize Account Tree Cache")
         }))
@@ -6,6 +6,7 @@ use sqlx::FromRow;
 // Workspace imports
 use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, SyncBlockPrefix, ZeroxPrefix};
 // Local imports
+use crate::StorageActionType;
 
 #[derive(Debug, FromRow)]
 pub struct StorageBlock {
@@ -47,6 +48,21 @@ pub struct BlockDetails {
     pub verified_at: Option<DateTime<Utc>>,
 }
 
+/// Stage of the block processing (commit or verify) together with
+/// the Ethereum operation sending it to L1.
+#[derive(Debug, FromRow, PartialEq, Clone)]
+pub struct BlockStageDetails {
+    pub action_type: StorageActionType,
+    /// Time when the operation was created by the server.
+    pub created_at: DateTime<Utc>,
+    pub eth_op_id: Option<i64>,
+    /// Hash of the Ethereum transaction accepted by the network.
+    pub final_hash: Option<Vec<u8>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Amount of gas used by the accepted Ethereum transaction.
+    pub gas_used: Option<i64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct BlockTransactionItem {
     pub tx_hash: String,
@@ -6,14 +6,17 @@ use itertools::Itertools;
 use zksync_types::{
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    SignedZkSyncTx,
+    Address, BlockNumber, SignedZkSyncTx,
 };
 // Local imports
-use self::records::MempoolTx;
-use crate::{QueryResult, StorageProcessor};
+use self::records::{MempoolTx, StoredBatchInfo};
+use crate::{chain::operations::records::StoredExecutedTransaction, QueryResult, StorageProcessor};
 
 pub mod records;
 
+/// Time the hashes of the accepted batches are kept for.
+const BATCHES_HASHES_RETENTION_DAYS: i64 = 30;
+
 /// Schema for persisting transactions awaiting for the execution.
 ///
 /// This schema holds the transactions that are received by the `mempool` module, but not yet have
@@ -119,7 +122,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
                 .nonce(),
         });
 
-        metrics::histogram!("sql.chain.mempool.load_txs", start.elapsed());
+        report_query!("sql.chain.mempool.load_txs", start);
         Ok(txs.into())
     }
 
@@ -217,10 +220,59 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .await?;
         }
 
-        metrics::histogram!("sql.chain.mempool.insert_batch", start.elapsed());
+        // Batch is identified by its hash, so the clients could track it as a whole.
+        let batch_tx_hashes: Vec<TxHash> = txs.iter().map(|tx_data| tx_data.hash()).collect();
+        let batch_hash = TxHash::batch_hash(&batch_tx_hashes);
+        let batch_tx_hashes: Vec<Vec<u8>> = batch_tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect();
+        sqlx::query!(
+            "INSERT INTO txs_batches_hashes (batch_id, batch_hash, tx_hashes, created_at)
+            VALUES ($1, $2, $3, $4)",
+            batch_id,
+            batch_hash.as_ref(),
+            &batch_tx_hashes,
+            chrono::Utc::now(),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        // Batches are kept after the execution, so the old ones are pruned along the way.
+        sqlx::query!(
+            "DELETE FROM txs_batches_hashes WHERE created_at < $1",
+            chrono::Utc::now() - chrono::Duration::days(BATCHES_HASHES_RETENTION_DAYS),
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.mempool.insert_batch", start);
         Ok(batch_id)
     }
 
+    /// Loads the batch with the given hash. Batches are kept after the execution,
+    /// so the batch is found if it was accepted to the mempool during the retention period.
+    pub async fn get_batch_info(
+        &mut self,
+        batch_hash: TxHash,
+    ) -> QueryResult<Option<StoredBatchInfo>> {
+        let start = Instant::now();
+
+        let batch = sqlx::query_as!(
+            StoredBatchInfo,
+            "SELECT * FROM txs_batches_hashes
+            WHERE batch_hash = $1
+            ORDER BY batch_id DESC
+            LIMIT 1",
+            batch_hash.as_ref()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.mempool.get_batch_info", start);
+        Ok(batch)
+    }
+
     /// Adds a new transaction to the mempool schema.
     pub async fn insert_tx(&mut self, tx_data: &SignedZkSyncTx) -> QueryResult<()> {
         let start = Instant::now();
@@ -245,7 +297,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.insert_tx", start.elapsed());
+        report_query!("sql.chain.mempool.insert_tx", start);
         Ok(())
     }
 
@@ -261,7 +313,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_tx", start.elapsed());
+        report_query!("sql.chain.mempool.remove_tx", start);
         Ok(())
     }
 
@@ -277,10 +329,87 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain.mempool.remove_txs", start.elapsed());
+        report_query!("sql.chain.mempool.remove_txs", start);
         Ok(())
     }
 
+    /// Moves transactions executed in blocks with numbers greater than `last_block` back to the
+    /// mempool schema, so they can be executed again.
+    ///
+    /// This method is expected to be invoked when blocks after `last_block` (including the pending one)
+    /// are discarded. Transactions are returned in the order of their execution along with their
+    /// Ethereum signature data and batch IDs, so users don't have to re-sign them.
+    ///
+    /// Failed transactions are not returned, since they were already reported as failed to the users,
+    /// and their records are kept as is.
+    /// Transactions which are already in the mempool (e.g. resent by the users) are not duplicated.
+    ///
+    /// Returns the number of transactions returned to the mempool.
+    pub async fn return_executed_txs_to_mempool(
+        &mut self,
+        last_block: BlockNumber,
+    ) -> QueryResult<usize> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let executed_txs = sqlx::query_as!(
+            StoredExecutedTransaction,
+            "SELECT * FROM executed_transactions
+            WHERE block_number > $1 AND success = true
+            ORDER BY block_number, block_index",
+            i64::from(*last_block)
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut tx_hashes = Vec::with_capacity(executed_txs.len());
+        let mut tx_values = Vec::with_capacity(executed_txs.len());
+        let mut txs_sign_data = Vec::with_capacity(executed_txs.len());
+        let mut created_at = Vec::with_capacity(executed_txs.len());
+        let mut batch_ids = Vec::with_capacity(executed_txs.len());
+
+        for tx in executed_txs {
+            tx_hashes.push(hex::encode(&tx.tx_hash));
+            tx_values.push(tx.tx);
+            txs_sign_data.push(tx.eth_sign_data.unwrap_or_default());
+            created_at.push(tx.created_at);
+            // Special case: batch_id == 0 <==> transaction is not a part of some batch
+            batch_ids.push(tx.batch_id.unwrap_or_default());
+        }
+
+        let returned_txs = sqlx::query!(
+            "INSERT INTO mempool_txs (tx_hash, tx, eth_sign_data, created_at, batch_id)
+            SELECT u.tx_hash, u.tx, u.eth_sign_data, u.created_at, u.batch_id
+                FROM UNNEST ($1::text[], $2::jsonb[], $3::jsonb[], $4::timestamptz[], $5::bigint[])
+                AS u(tx_hash, tx, eth_sign_data, created_at, batch_id)
+            WHERE NOT EXISTS (
+                SELECT 1 FROM mempool_txs WHERE mempool_txs.tx_hash = u.tx_hash
+            )",
+            &tx_hashes,
+            &tx_values,
+            &txs_sign_data,
+            &created_at,
+            &batch_ids,
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+
+        // History of the failed transactions is kept, they are not re-executed.
+        sqlx::query!(
+            "DELETE FROM executed_transactions
+            WHERE block_number > $1 AND success = true",
+            i64::from(*last_block)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        report_query!("sql.chain.mempool.return_executed_txs_to_mempool", start);
+        Ok(returned_txs as usize)
+    }
+
     /// Checks if the memory pool contains transaction with the given hash.
     pub async fn contains_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
         let start = Instant::now();
@@ -298,12 +427,22 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         let contains = row.filter(|&counter| counter > 0).is_some();
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "contains_tx");
+        report_query!("sql.chain.mempool.contains_tx", start);
         Ok(contains)
     }
 
     /// Returns zkSync transaction with thr given hash.
     pub async fn get_tx(&mut self, tx_hash: TxHash) -> QueryResult<Option<SignedZkSyncTx>> {
+        self.get_mempool_tx(tx_hash)
+            .await?
+            .map(SignedZkSyncTx::try_from)
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Returns the stored memory pool entry of the transaction with the given hash.
+    /// The ID of the entry reflects the order in which the transactions were accepted.
+    pub async fn get_mempool_tx(&mut self, tx_hash: TxHash) -> QueryResult<Option<MempoolTx>> {
         let start = Instant::now();
 
         let tx_hash = hex::encode(tx_hash.as_ref());
@@ -317,11 +456,39 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
-        mempool_tx
-            .map(SignedZkSyncTx::try_from)
-            .transpose()
-            .map_err(anyhow::Error::from)
+        report_query!("sql.chain.mempool.get_mempool_tx", start);
+        Ok(mempool_tx)
+    }
+
+    /// Loads the transactions initiated by or sent to the given addresses, in the order
+    /// in which they were accepted.
+    pub async fn load_address_txs(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+        // Addresses are stored in the transactions as the `0x`-prefixed lowercase hex strings.
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| format!("{:?}", address))
+            .collect();
+
+        let txs = sqlx::query_as!(
+            MempoolTx,
+            "SELECT * FROM mempool_txs
+            WHERE tx->>'from' = ANY($1) OR tx->>'to' = ANY($1)
+                OR tx->>'account' = ANY($1) OR tx->>'target' = ANY($1)
+            ORDER BY id",
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(SignedZkSyncTx::try_from)
+        .collect::<Result<_, _>>()?;
+
+        report_query!("sql.chain.mempool.load_address_txs", start);
+        Ok(txs)
     }
 
     /// Removes transactions that are already committed.
@@ -370,7 +537,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         self.remove_txs(&tx_hashes_to_remove).await?;
 
-        metrics::histogram!("sql.chain.mempool.collect_garbage", start.elapsed());
+        report_query!("sql.chain.mempool.collect_garbage", start);
         Ok(())
     }
 }
@@ -20,6 +20,16 @@ pub struct MempoolTx {
     pub batch_id: i64,
 }
 
+/// Stored information about the transactions batch accepted to the mempool.
+#[derive(Debug, FromRow, PartialEq)]
+pub struct StoredBatchInfo {
+    pub batch_id: i64,
+    pub batch_hash: Vec<u8>,
+    /// Raw hashes of the batch transactions in the batch order.
+    pub tx_hashes: Vec<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+}
+
 impl TryFrom<MempoolTx> for SignedZkSyncTx {
     type Error = serde_json::Error;
 
@@ -39,10 +39,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .max
         .unwrap_or(0);
 
-        metrics::histogram!(
-            "sql.chain.operations.get_last_block_by_action",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.get_last_block_by_action", start);
         Ok(BlockNumber(max_block as u32))
     }
 
@@ -70,7 +67,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .ok()
         .flatten();
 
-        metrics::histogram!("sql.chain.operations.get_operation", start.elapsed());
+        report_query!("sql.chain.operations.get_operation", start);
         result
     }
 
@@ -88,10 +85,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations.get_executed_operation",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.get_executed_operation", start);
         Ok(op)
     }
 
@@ -109,13 +103,28 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations.get_executed_priority_operation",
-            start.elapsed()
+            start
         );
         Ok(op)
     }
 
+    /// Returns the serial ID of the first priority operation that is not executed yet.
+    /// Operations of the pending block are considered executed.
+    pub async fn next_priority_op_serial_id(&mut self) -> QueryResult<u64> {
+        let start = Instant::now();
+        let max_serial_id = sqlx::query!(
+            "SELECT MAX(priority_op_serialid) AS max_serial_id FROM executed_priority_operations"
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max_serial_id;
+
+        report_query!("sql.chain.operations.next_priority_op_serial_id", start);
+        Ok(max_serial_id.map_or(0, |serial_id| serial_id as u64 + 1))
+    }
+
     /// Retrieves priority operation from the database given its hash.
     pub async fn get_executed_priority_operation_by_hash(
         &mut self,
@@ -130,9 +139,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations.get_executed_priority_operation_by_hash",
-            start.elapsed()
+            start
         );
         Ok(op)
     }
@@ -155,7 +164,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .fetch_one(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.operations.store_operation", start.elapsed());
+        report_query!("sql.chain.operations.store_operation", start);
         Ok(op)
     }
 
@@ -175,7 +184,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!("sql.chain.operations.confirm_operation", start.elapsed());
+        report_query!("sql.chain.operations.confirm_operation", start);
         Ok(())
     }
 
@@ -199,11 +208,11 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             // sent the same transfer again.
 
             sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, fail_code)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
                 ON CONFLICT (tx_hash)
                 DO UPDATE
-                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14",
+                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14, fail_code = $15",
                 operation.block_number,
                 operation.block_index,
                 operation.tx,
@@ -218,14 +227,15 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.fail_code,
             )
             .execute(transaction.conn())
             .await?;
         } else {
             // If transaction failed, we do nothing on conflict.
             sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, fail_code)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
                 ON CONFLICT (tx_hash)
                 DO NOTHING",
                 operation.block_number,
@@ -242,13 +252,14 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.fail_code,
             )
             .execute(transaction.conn())
             .await?;
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.operations.store_executed_tx", start.elapsed());
+        report_query!("sql.chain.operations.store_executed_tx", start);
         Ok(())
     }
 
@@ -280,10 +291,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.store_executed_priority_op",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.store_executed_priority_op", start);
         Ok(())
     }
 
@@ -318,10 +326,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!(
-            "sql.chain.operations.add_pending_withdrawal",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.add_pending_withdrawal", start);
         Ok(())
     }
 
@@ -342,9 +347,9 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations.add_complete_withdrawals_transaction",
-            start.elapsed()
+            start
         );
         Ok(())
     }
@@ -358,10 +363,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 .await?
                 .count;
 
-        metrics::histogram!(
-            "sql.chain.operations.no_stored_pending_withdrawals",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.no_stored_pending_withdrawals", start);
         Ok(stored_pending_withdrawals == 0)
     }
 
@@ -403,10 +405,7 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             None => None,
         };
 
-        metrics::histogram!(
-            "sql.chain.operations.eth_tx_for_withdrawal",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations.eth_tx_for_withdrawal", start);
         Ok(res)
     }
 }
@@ -45,6 +45,7 @@ pub struct StoredExecutedTransaction {
     pub created_at: DateTime<Utc>,
     pub eth_sign_data: Option<serde_json::Value>,
     pub batch_id: Option<i64>,
+    pub fail_code: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +84,7 @@ pub struct NewExecutedTransaction {
     pub created_at: DateTime<Utc>,
     pub eth_sign_data: Option<serde_json::Value>,
     pub batch_id: Option<i64>,
+    pub fail_code: Option<String>,
 }
 
 #[derive(Debug, Clone)]
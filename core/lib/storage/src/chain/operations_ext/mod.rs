@@ -1,17 +1,22 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 
 // External imports
 use chrono::{DateTime, Utc};
+use serde_json::Value;
 
 // Workspace imports
 use zksync_types::ActionType;
-use zksync_types::{Address, BlockNumber, TokenId};
+use zksync_types::{
+    tx::{ChangePubKey, TxHash},
+    Address, BlockNumber, Nonce, Token, TokenId, ZkSyncTx,
+};
 
 // Local imports
 use self::records::{
     AccountCreatedAt, AccountOpReceiptResponse, AccountTxReceiptResponse,
-    PriorityOpReceiptResponse, TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse,
+    PriorityOpReceiptResponse, TransactionsHistoryItem, TransactionsHistoryPageItem,
+    TxByHashResponse, TxReceiptResponse, TxStatusResponse,
 };
 use crate::{
     chain::operations::{records::StoredExecutedPriorityOperation, OperationsSchema},
@@ -31,6 +36,50 @@ pub enum SearchDirection {
     Newer,
 }
 
+/// Side of the transaction the account participates on.
+/// Transactions of the account to itself (e.g. `Close` or `FullExit`) match both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxDirection {
+    /// Transactions sent by the account, including the deposits from its Ethereum address.
+    Outgoing,
+    /// Transactions received by the account.
+    Incoming,
+}
+
+/// Filter of the account transactions history. Every set field narrows down the result.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxHistoryFilter {
+    /// Transactions with the token, either the transferred one or the fee one.
+    pub token: Option<TokenId>,
+    /// Transactions of the type, e.g. `Transfer` or `Deposit`.
+    pub tx_type: Option<String>,
+    pub direction: Option<TxDirection>,
+    /// Transactions created at this time or later.
+    pub from_time: Option<DateTime<Utc>>,
+    /// Transactions created before this time.
+    pub to_time: Option<DateTime<Utc>>,
+}
+
+/// Position of the transaction in the account history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxHistoryCursor {
+    pub block_number: BlockNumber,
+    /// Transaction index in block, absent for rejected transactions.
+    pub block_index: Option<u32>,
+    /// Raw hash bytes of the transaction, orders the rejected transactions of the same block.
+    pub hash: Vec<u8>,
+}
+
+impl From<&TransactionsHistoryPageItem> for TxHistoryCursor {
+    fn from(item: &TransactionsHistoryPageItem) -> Self {
+        Self {
+            block_number: BlockNumber(item.block_number as u32),
+            block_index: item.block_index.map(|index| index as u32),
+            hash: item.raw_hash.clone(),
+        }
+    }
+}
+
 /// `OperationsExt` schema is a logical extension for an `Operations` schema,
 /// which provides more getters for transactions.
 /// While `Operations` getters are very basic, `OperationsExt` schema can transform
@@ -64,16 +113,67 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 success: tx.success,
                 verified,
                 fail_reason: tx.fail_reason,
+                fail_code: tx.fail_code,
                 prover_run,
             }))
         } else {
             Ok(None)
         };
 
-        metrics::histogram!("sql.chain.operations_ext.tx_receipt", start.elapsed());
+        report_query!("sql.chain.operations_ext.tx_receipt", start);
         result
     }
 
+    /// Loads the statuses of the given transactions with a single query.
+    ///
+    /// Exactly one entry is returned for every distinct hash, so the unknown transactions
+    /// can be told apart from the executed and pending ones.
+    pub async fn get_txs_statuses(
+        &mut self,
+        hashes: &[TxHash],
+    ) -> QueryResult<Vec<TxStatusResponse>> {
+        let start = Instant::now();
+        let hashes: Vec<Vec<u8>> = hashes.iter().map(|hash| hash.as_ref().to_vec()).collect();
+
+        let statuses = sqlx::query_as!(
+            TxStatusResponse,
+            r#"
+            WITH hashes AS (
+                SELECT DISTINCT unnest($1::bytea[]) AS tx_hash
+            )
+            SELECT DISTINCT ON (hashes.tx_hash)
+                hashes.tx_hash as "tx_hash!",
+                executed_transactions.block_number as "block_number?",
+                executed_transactions.success as "success?",
+                executed_transactions.fail_reason as "fail_reason?",
+                executed_transactions.fail_code as "fail_code?",
+                EXISTS (
+                    SELECT 1 FROM operations
+                    WHERE operations.block_number = executed_transactions.block_number
+                        AND operations.action_type = 'COMMIT' AND operations.confirmed = true
+                ) as "committed!",
+                EXISTS (
+                    SELECT 1 FROM operations
+                    WHERE operations.block_number = executed_transactions.block_number
+                        AND operations.action_type = 'VERIFY' AND operations.confirmed = true
+                ) as "verified!",
+                EXISTS (
+                    SELECT 1 FROM mempool_txs
+                    WHERE mempool_txs.tx_hash = encode(hashes.tx_hash, 'hex')
+                ) as "pending!"
+            FROM hashes
+            LEFT JOIN executed_transactions ON executed_transactions.tx_hash = hashes.tx_hash
+            ORDER BY hashes.tx_hash, executed_transactions.success DESC NULLS LAST
+            "#,
+            &hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.operations_ext.get_txs_statuses", start);
+        Ok(statuses)
+    }
+
     pub async fn get_priority_op_receipt(
         &mut self,
         op_id: u32,
@@ -111,10 +211,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             }),
         };
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.get_priority_op_receipt",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations_ext.get_priority_op_receipt", start);
         result
     }
 
@@ -129,7 +226,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             self.find_priority_op_by_hash(hash).await?
         };
 
-        metrics::histogram!("sql.chain.operations_ext.get_tx_by_hash", start.elapsed());
+        report_query!("sql.chain.operations_ext.get_tx_by_hash", start);
         Ok(result)
     }
 
@@ -226,7 +323,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!("sql.chain.operations_ext.find_tx_by_hash", start.elapsed());
+        report_query!("sql.chain.operations_ext.find_tx_by_hash", start);
         Ok(result)
     }
 
@@ -306,10 +403,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             None
         };
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.find_priority_op_by_hash",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations_ext.find_priority_op_by_hash", start);
         Ok(result)
     }
 
@@ -358,13 +452,61 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.chain.operations_ext.account_created_on",
-            start.elapsed()
-        );
+        report_query!("sql.chain.operations_ext.account_created_on", start);
         Ok(first_history_entry.map(|entry| entry.created_at))
     }
 
+    /// Loads the last successfully executed `ChangePubKey` transaction of the account.
+    /// Returns `None` if the account has never set its signing key.
+    pub async fn last_change_pubkey_tx(
+        &mut self,
+        address: &Address,
+    ) -> QueryResult<Option<ChangePubKey>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT tx FROM executed_transactions
+            WHERE primary_account_address = $1 AND success = true AND tx->>'type' = 'ChangePubKey'
+            ORDER BY block_number DESC, block_index DESC
+            LIMIT 1
+            "#,
+            address.as_bytes(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let tx = match record {
+            Some(record) => match serde_json::from_value(record.tx)? {
+                ZkSyncTx::ChangePubKey(tx) => Some(*tx),
+                _ => unreachable!("Transaction type is checked in the query"),
+            },
+            None => None,
+        };
+
+        report_query!("sql.chain.operations_ext.last_change_pubkey_tx", start);
+        Ok(tx)
+    }
+
+    /// Loads the nonce of the last successfully executed transaction of the account,
+    /// including the transactions of the pending block.
+    /// Returns `None` if the account has no executed transactions.
+    pub async fn last_executed_nonce(&mut self, address: &Address) -> QueryResult<Option<Nonce>> {
+        let start = Instant::now();
+        let nonce = sqlx::query!(
+            r#"
+            SELECT MAX(nonce) as "nonce" FROM executed_transactions
+            WHERE primary_account_address = $1 AND success = true
+            "#,
+            address.as_bytes(),
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .nonce;
+
+        report_query!("sql.chain.operations_ext.last_executed_nonce", start);
+        Ok(nonce.map(|nonce| Nonce(nonce as u32)))
+    }
+
     /// Loads the range of the transactions applied to the account starting
     /// from the block with number $(offset) up to $(offset + limit).
     pub async fn get_account_transactions_history(
@@ -464,39 +606,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         if !tx_history.is_empty() {
             let tokens = TokensSchema(self.0).load_tokens().await?;
             for tx_item in &mut tx_history {
-                let tx_info = match tx_item.tx["type"].as_str().unwrap_or("NONE") {
-                    "NONE" => {
-                        vlog::warn!("Tx history item type not found, tx: {:?}", tx_item);
-                        continue;
-                    }
-                    "Deposit" | "FullExit" => tx_item.tx.get_mut("priority_op"),
-                    _ => Some(&mut tx_item.tx),
-                };
-
-                let tx_info = if let Some(tx_info) = tx_info {
-                    tx_info
-                } else {
-                    vlog::warn!("tx_info not found for tx: {:?}", tx_item);
-                    continue;
-                };
-
-                if let Some(tok_val) = tx_info.get_mut("token") {
-                    if let Some(token_id) = tok_val.as_u64() {
-                        let token_id = TokenId(token_id as u16);
-                        let token_symbol = tokens
-                            .get(&token_id)
-                            .map(|t| t.symbol.clone())
-                            .unwrap_or_else(|| "UNKNOWN".to_string());
-                        *tok_val =
-                            serde_json::to_value(token_symbol).expect("json string to value");
-                    };
-                };
+                set_token_symbol(&mut tx_item.tx, &tokens);
             }
         }
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations_ext.get_account_transactions_history",
-            start.elapsed()
+            start
         );
         Ok(tx_history)
     }
@@ -635,39 +751,223 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         if !tx_history.is_empty() {
             let tokens = TokensSchema(self.0).load_tokens().await?;
             for tx_item in &mut tx_history {
-                let tx_info = match tx_item.tx["type"].as_str().unwrap_or("NONE") {
-                    "NONE" => {
-                        vlog::warn!("Tx history item type not found, tx: {:?}", tx_item);
-                        continue;
-                    }
-                    "Deposit" | "FullExit" => tx_item.tx.get_mut("priority_op"),
-                    _ => Some(&mut tx_item.tx),
-                };
-
-                let tx_info = if let Some(tx_info) = tx_info {
-                    tx_info
-                } else {
-                    vlog::warn!("tx_info not found for tx: {:?}", tx_item);
-                    continue;
-                };
-
-                if let Some(tok_val) = tx_info.get_mut("token") {
-                    if let Some(token_id) = tok_val.as_u64() {
-                        let token_id = TokenId(token_id as u16);
-                        let token_symbol = tokens
-                            .get(&token_id)
-                            .map(|t| t.symbol.clone())
-                            .unwrap_or_else(|| "UNKNOWN".to_string());
-                        *tok_val =
-                            serde_json::to_value(token_symbol).expect("json string to value");
-                    };
-                };
+                set_token_symbol(&mut tx_item.tx, &tokens);
             }
         }
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations_ext.get_account_transactions_history_from",
-            start.elapsed()
+            start
+        );
+        Ok(tx_history)
+    }
+
+    /// Loads the page of the transactions applied to the account which satisfy the filter.
+    /// Transactions are sorted from the newest to the oldest ones, and the page starts right
+    /// after the `cursor`, or from the newest transaction if the cursor is not set.
+    ///
+    /// Unlike `get_account_transactions_history`, the page is located by the transaction
+    /// position instead of the offset, so loading the deep pages of the large accounts history
+    /// is as fast as loading the first one.
+    pub async fn get_account_transactions_history_page(
+        &mut self,
+        address: &Address,
+        filter: &TxHistoryFilter,
+        cursor: Option<&TxHistoryCursor>,
+        limit: u64,
+    ) -> QueryResult<Vec<TransactionsHistoryPageItem>> {
+        let start = Instant::now();
+
+        // Without the cursor every transaction precedes the starting position.
+        let (block_number, block_index, hash) = match cursor {
+            Some(cursor) => (
+                *cursor.block_number as i64,
+                cursor.block_index.map_or(-1, |index| index as i32),
+                cursor.hash.clone(),
+            ),
+            None => (i64::MAX, -1, Vec::new()),
+        };
+        let (outgoing, incoming) = match filter.direction {
+            Some(TxDirection::Outgoing) => (true, false),
+            Some(TxDirection::Incoming) => (false, true),
+            None => (true, true),
+        };
+        let token = filter.token.map(|token| *token as i32);
+
+        // This query does the following:
+        // - selects the page of the account transactions from both the `executed_transactions`
+        //   and the `executed_priority_operations` tables. Every column referring to the account
+        //   is queried separately, so each subquery is an ordered scan of the matching position
+        //   index limited by the page size. Subqueries exclude the rows selected by the previous
+        //   ones, so the transactions touching the account in several ways are not duplicated.
+        //   Rejected transactions have no index in the block, so they are placed before
+        //   the executed ones of the same block, and the hash is used to order them;
+        // - checks whether the blocks of the selected transactions are verified.
+        let mut tx_history = sqlx::query_as!(
+            TransactionsHistoryPageItem,
+            r#"
+            WITH transactions AS (
+                (
+                    SELECT
+                        'sync-tx:' || encode(tx_hash, 'hex') AS hash,
+                        tx_hash AS raw_hash,
+                        block_number,
+                        block_index,
+                        null::bigint AS pq_id,
+                        null::bigint AS eth_block,
+                        tx,
+                        success,
+                        fail_reason,
+                        created_at
+                    FROM executed_transactions
+                    WHERE $8 AND from_account = $1
+                        AND (block_number, COALESCE(block_index, -1), tx_hash) < ($2, $3, $4)
+                        AND ($6::integer IS NULL OR (tx->>'token')::integer = $6 OR (tx->>'feeToken')::integer = $6)
+                        AND ($7::text IS NULL OR tx->>'type' = $7)
+                        AND ($10::timestamptz IS NULL OR created_at >= $10)
+                        AND ($11::timestamptz IS NULL OR created_at < $11)
+                    ORDER BY block_number DESC, COALESCE(block_index, -1) DESC, tx_hash DESC
+                    LIMIT $5
+                )
+                UNION ALL
+                (
+                    SELECT
+                        'sync-tx:' || encode(tx_hash, 'hex') AS hash,
+                        tx_hash AS raw_hash,
+                        block_number,
+                        block_index,
+                        null::bigint AS pq_id,
+                        null::bigint AS eth_block,
+                        tx,
+                        success,
+                        fail_reason,
+                        created_at
+                    FROM executed_transactions
+                    WHERE $8 AND primary_account_address = $1 AND from_account <> $1
+                        AND (block_number, COALESCE(block_index, -1), tx_hash) < ($2, $3, $4)
+                        AND ($6::integer IS NULL OR (tx->>'token')::integer = $6 OR (tx->>'feeToken')::integer = $6)
+                        AND ($7::text IS NULL OR tx->>'type' = $7)
+                        AND ($10::timestamptz IS NULL OR created_at >= $10)
+                        AND ($11::timestamptz IS NULL OR created_at < $11)
+                    ORDER BY block_number DESC, COALESCE(block_index, -1) DESC, tx_hash DESC
+                    LIMIT $5
+                )
+                UNION ALL
+                (
+                    SELECT
+                        'sync-tx:' || encode(tx_hash, 'hex') AS hash,
+                        tx_hash AS raw_hash,
+                        block_number,
+                        block_index,
+                        null::bigint AS pq_id,
+                        null::bigint AS eth_block,
+                        tx,
+                        success,
+                        fail_reason,
+                        created_at
+                    FROM executed_transactions
+                    WHERE $9 AND to_account = $1
+                        AND NOT ($8 AND (from_account = $1 OR primary_account_address = $1))
+                        AND (block_number, COALESCE(block_index, -1), tx_hash) < ($2, $3, $4)
+                        AND ($6::integer IS NULL OR (tx->>'token')::integer = $6 OR (tx->>'feeToken')::integer = $6)
+                        AND ($7::text IS NULL OR tx->>'type' = $7)
+                        AND ($10::timestamptz IS NULL OR created_at >= $10)
+                        AND ($11::timestamptz IS NULL OR created_at < $11)
+                    ORDER BY block_number DESC, COALESCE(block_index, -1) DESC, tx_hash DESC
+                    LIMIT $5
+                )
+                UNION ALL
+                (
+                    SELECT
+                        '0x' || encode(eth_hash, 'hex') AS hash,
+                        eth_hash AS raw_hash,
+                        block_number,
+                        block_index,
+                        priority_op_serialid AS pq_id,
+                        eth_block,
+                        operation AS tx,
+                        true AS success,
+                        null AS fail_reason,
+                        created_at
+                    FROM executed_priority_operations
+                    WHERE $8 AND from_account = $1
+                        AND (block_number, block_index, eth_hash) < ($2, $3, $4)
+                        AND ($6::integer IS NULL OR (operation->'priority_op'->>'token')::integer = $6)
+                        AND ($7::text IS NULL OR operation->>'type' = $7)
+                        AND ($10::timestamptz IS NULL OR created_at >= $10)
+                        AND ($11::timestamptz IS NULL OR created_at < $11)
+                    ORDER BY block_number DESC, block_index DESC, eth_hash DESC
+                    LIMIT $5
+                )
+                UNION ALL
+                (
+                    SELECT
+                        '0x' || encode(eth_hash, 'hex') AS hash,
+                        eth_hash AS raw_hash,
+                        block_number,
+                        block_index,
+                        priority_op_serialid AS pq_id,
+                        eth_block,
+                        operation AS tx,
+                        true AS success,
+                        null AS fail_reason,
+                        created_at
+                    FROM executed_priority_operations
+                    WHERE $9 AND to_account = $1 AND NOT ($8 AND from_account = $1)
+                        AND (block_number, block_index, eth_hash) < ($2, $3, $4)
+                        AND ($6::integer IS NULL OR (operation->'priority_op'->>'token')::integer = $6)
+                        AND ($7::text IS NULL OR operation->>'type' = $7)
+                        AND ($10::timestamptz IS NULL OR created_at >= $10)
+                        AND ($11::timestamptz IS NULL OR created_at < $11)
+                    ORDER BY block_number DESC, block_index DESC, eth_hash DESC
+                    LIMIT $5
+                )
+            )
+            SELECT
+                hash AS "hash!",
+                raw_hash AS "raw_hash!",
+                block_number AS "block_number!",
+                block_index AS "block_index?",
+                pq_id AS "pq_id?",
+                eth_block AS "eth_block?",
+                tx AS "tx!",
+                success AS "success!",
+                fail_reason AS "fail_reason?",
+                EXISTS (
+                    SELECT 1 FROM operations
+                    WHERE operations.block_number = transactions.block_number
+                        AND action_type = 'VERIFY' AND confirmed = true
+                ) AS "verified!",
+                created_at AS "created_at!"
+            FROM transactions
+            ORDER BY block_number DESC, COALESCE(block_index, -1) DESC, raw_hash DESC
+            LIMIT $5
+            "#,
+            address.as_bytes(),
+            block_number,
+            block_index,
+            hash,
+            limit as i64,
+            token,
+            filter.tx_type.as_deref(),
+            outgoing,
+            incoming,
+            filter.from_time,
+            filter.to_time,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        if !tx_history.is_empty() {
+            let tokens = TokensSchema(self.0).load_tokens().await?;
+            for tx_item in &mut tx_history {
+                set_token_symbol(&mut tx_item.tx, &tokens);
+            }
+        }
+
+        report_query!(
+            "sql.chain.operations_ext.get_account_transactions_history_page",
+            start
         );
         Ok(tx_history)
     }
@@ -726,8 +1026,10 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         tx_hash,
                         success,
                         fail_reason as "fail_reason?",
+                        fail_code as "fail_code?",
                         details.commit_tx_hash as "commit_tx_hash?",
-                        details.verify_tx_hash as "verify_tx_hash?"
+                        details.verify_tx_hash as "verify_tx_hash?",
+                        tx->>'memo' as "memo?"
                     FROM executed_transactions
                     LEFT JOIN block_details details ON details.details_block_number = executed_transactions.block_number
                     WHERE (
@@ -784,8 +1086,10 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         tx_hash,
                         success,
                         fail_reason as "fail_reason?",
+                        fail_code as "fail_code?",
                         details.commit_tx_hash as "commit_tx_hash?",
-                        details.verify_tx_hash as "verify_tx_hash?"
+                        details.verify_tx_hash as "verify_tx_hash?",
+                        tx->>'memo' as "memo?"
                     FROM executed_transactions
                     LEFT JOIN block_details details ON details.details_block_number = executed_transactions.block_number
                     WHERE (
@@ -810,9 +1114,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations_ext.get_account_transactions_receipts",
-            start.elapsed()
+            start
         );
         Ok(receipts)
     }
@@ -951,10 +1255,40 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!(
+        report_query!(
             "sql.chain.operations_ext.get_account_operations_receipts",
-            start.elapsed()
+            start
         );
         Ok(receipts)
     }
 }
+
+/// Replaces the token ID in the history item transaction with the token symbol.
+fn set_token_symbol(tx: &mut Value, tokens: &HashMap<TokenId, Token>) {
+    let tx_info = match tx["type"].as_str().unwrap_or("NONE") {
+        "NONE" => {
+            vlog::warn!("Tx history item type not found, tx: {:?}", tx);
+            return;
+        }
+        "Deposit" | "FullExit" => tx.get_mut("priority_op"),
+        _ => Some(&mut *tx),
+    };
+
+    let tx_info = if let Some(tx_info) = tx_info {
+        tx_info
+    } else {
+        vlog::warn!("tx_info not found for tx: {:?}", tx);
+        return;
+    };
+
+    if let Some(tok_val) = tx_info.get_mut("token") {
+        if let Some(token_id) = tok_val.as_u64() {
+            let token_id = TokenId(token_id as u16);
+            let token_symbol = tokens
+                .get(&token_id)
+                .map(|t| t.symbol.clone())
+                .unwrap_or_else(|| "UNKNOWN".to_string());
+            *tok_val = serde_json::to_value(token_symbol).expect("json string to value");
+        };
+    };
+}
@@ -36,6 +36,33 @@ pub struct TransactionsHistoryItem {
     pub created_at: DateTime<Utc>,
 }
 
+/// A single entry from the raw response of the [`get_account_transactions_history_page`] query.
+///
+/// [`get_account_transactions_history_page`]: super::OperationsExtSchema::get_account_transactions_history_page()
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct TransactionsHistoryPageItem {
+    /// Transaction hash with the `sync-tx:` prefix, or the Ethereum hash of the priority operation.
+    pub hash: String,
+    /// Raw transaction hash bytes.
+    pub raw_hash: Vec<u8>,
+    /// The block containing the transaction.
+    pub block_number: i64,
+    /// Transaction index in block.
+    ///
+    /// Absent for rejected transactions.
+    pub block_index: Option<i32>,
+    /// Serial ID of the priority operation, absent for transactions.
+    pub pq_id: Option<i64>,
+    /// Ethereum block of the priority operation, absent for transactions.
+    pub eth_block: Option<i64>,
+    pub tx: Value,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    /// Whether the verify of the transaction block is confirmed in the L1.
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Stored information resulted from executing the transaction.
 /// Obtained from the operations schema.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,9 +72,33 @@ pub struct TxReceiptResponse {
     pub success: bool,
     pub verified: bool,
     pub fail_reason: Option<String>,
+    pub fail_code: Option<String>,
     pub prover_run: Option<ProverRun>,
 }
 
+/// Raw response of the [`get_txs_statuses`] query.
+///
+/// [`get_txs_statuses`]: super::OperationsExtSchema::get_txs_statuses()
+#[derive(Debug, FromRow, PartialEq)]
+pub struct TxStatusResponse {
+    /// Raw transaction hash bytes.
+    pub tx_hash: Vec<u8>,
+    /// The block containing the transaction, absent if the transaction wasn't executed.
+    pub block_number: Option<i64>,
+    /// Execution status, absent if the transaction wasn't executed.
+    pub success: Option<bool>,
+    /// Reason why transaction has been rejected.
+    pub fail_reason: Option<String>,
+    /// Machine-readable class of the rejection.
+    pub fail_code: Option<String>,
+    /// Whether the commit of the transaction block is confirmed in the L1.
+    pub committed: bool,
+    /// Whether the verify of the transaction block is confirmed in the L1.
+    pub verified: bool,
+    /// Whether the transaction is waiting for execution in the mempool.
+    pub pending: bool,
+}
+
 /// Stored information resulted from executing the priority operation.
 /// Obtained from the operations schema.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -111,6 +162,10 @@ pub struct AccountTxReceiptResponse {
     ///
     /// May only exists for unsuccessful transactions.
     pub fail_reason: Option<String>,
+    /// Machine-readable class of the rejection.
+    ///
+    /// May only exists for unsuccessful transactions.
+    pub fail_code: Option<String>,
     /// The raw hash bytes of the corresponding "COMMIT" Ethereum operation for block with
     /// given transaction.
     ///
@@ -121,6 +176,8 @@ pub struct AccountTxReceiptResponse {
     ///
     /// May only exists for successful transactions.
     pub verify_tx_hash: Option<Vec<u8>>,
+    /// Memo attached to the `Transfer` transaction by the sender.
+    pub memo: Option<String>,
 }
 
 /// Raw response of the [`get_account_operations_receipts`] query.
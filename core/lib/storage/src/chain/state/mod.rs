@@ -6,14 +6,15 @@ use sqlx::types::BigDecimal;
 // Workspace imports
 use zksync_types::{
     helpers::{apply_updates, reverse_updates},
-    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, PubKeyHash,
+    AccountId, AccountMap, AccountUpdate, AccountUpdateJournalEntry, AccountUpdates, BlockNumber,
+    PubKeyHash,
 };
 // Local imports
 use crate::chain::{
     account::{
         records::{
             StorageAccount, StorageAccountCreation, StorageAccountPubkeyUpdate,
-            StorageAccountUpdate, StorageBalance,
+            StorageAccountUpdate, StorageAccountUpdateJournalEntry, StorageBalance,
         },
         restore_account,
     },
@@ -157,11 +158,24 @@ impl<'a, 'c> StateSchema<'a, 'c> {
                     .await?;
                 }
             }
+
+            // Every update is also appended to the journal, so the consumers
+            // are able to stream the state changes in the order they were made.
+            let update = serde_json::to_value(upd).expect("Unable to serialize AccountUpdate");
+            sqlx::query!(
+                r#"
+                INSERT INTO account_updates_journal ( block_number, account_id, update_order_id, account_update )
+                VALUES ( $1, $2, $3, $4 )
+                "#,
+                i64::from(*block_number), i64::from(**id), update_order_id as i32, update
+            )
+            .execute(transaction.conn())
+            .await?;
         }
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.commit_state_update", start.elapsed());
+        report_query!("sql.chain.state.commit_state_update", start);
         Ok(())
     }
 
@@ -311,7 +325,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.apply_state_update", start.elapsed());
+        report_query!("sql.chain.state.apply_state_update", start);
         Ok(())
     }
 
@@ -349,7 +363,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.chain.state.load_committed_state", start.elapsed());
+        report_query!("sql.chain.state.load_committed_state", start);
         result
     }
 
@@ -399,7 +413,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         }
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_verified_state", start.elapsed());
+        report_query!("sql.chain.state.load_verified_state", start);
         Ok((last_block, account_map))
     }
 
@@ -530,7 +544,7 @@ impl<'a, 'c> StateSchema<'a, 'c> {
         };
 
         transaction.commit().await?;
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        report_query!("sql.chain.state.load_state_diff", start);
 
         // We don't want to return an empty list to avoid the confusion, so return
         // `None` if there are no changes.
@@ -552,7 +566,60 @@ impl<'a, 'c> StateSchema<'a, 'c> {
             .await
             .map(|diff| diff.unwrap_or_default().1);
 
-        metrics::histogram!("sql.chain.state.load_state_diff", start.elapsed());
+        report_query!("sql.chain.state.load_state_diff", start);
         result
     }
+
+    /// Loads up to `limit` entries of the account updates journal starting from
+    /// the entry with the given sequence number (inclusive), in the order they were committed.
+    pub async fn load_account_updates_journal(
+        &mut self,
+        from_sequence_number: u64,
+        limit: u32,
+    ) -> QueryResult<Vec<AccountUpdateJournalEntry>> {
+        let start = Instant::now();
+        let entries = sqlx::query_as!(
+            StorageAccountUpdateJournalEntry,
+            "SELECT * FROM account_updates_journal
+            WHERE sequence_number >= $1
+            ORDER BY sequence_number ASC
+            LIMIT $2",
+            from_sequence_number as i64,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|entry| AccountUpdateJournalEntry {
+            sequence_number: entry.sequence_number as u64,
+            block_number: BlockNumber(entry.block_number as u32),
+            account_id: AccountId(entry.account_id as u32),
+            update: serde_json::from_value(entry.account_update)
+                .expect("Unparsable AccountUpdate in db"),
+        })
+        .collect();
+
+        report_query!("sql.chain.state.load_account_updates_journal", start);
+        Ok(entries)
+    }
+
+    /// Returns the sequence number following the last journal entry of the given block
+    /// or any of the preceding ones, i.e. the position to stream the updates of the
+    /// subsequent blocks from.
+    pub async fn account_updates_journal_position(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let last_sequence_number = sqlx::query!(
+            "SELECT MAX(sequence_number) FROM account_updates_journal WHERE block_number <= $1",
+            i64::from(*block_number)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        report_query!("sql.chain.state.account_updates_journal_position", start);
+        Ok(last_sequence_number.map_or(0, |number| number as u64 + 1))
+    }
 }
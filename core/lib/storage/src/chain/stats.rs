@@ -1,12 +1,54 @@
 // Built-in deps
 use std::time::Instant;
 // External imports
-
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::types::BigDecimal;
 // Workspace imports
 use zksync_types::BlockNumber;
 // Local imports
 use crate::{QueryResult, StorageProcessor};
 
+/// Timeline of the block processing: creation of the commit and verify operations
+/// by the server and their confirmation on Ethereum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockTimings {
+    pub block_number: i64,
+    pub committed_at: DateTime<Utc>,
+    pub commit_confirmed_at: Option<DateTime<Utc>>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub verify_confirmed_at: Option<DateTime<Utc>>,
+}
+
+/// Transactions statistics of a single day (in UTC).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    /// Amount of the executed transactions and priority operations.
+    pub tx_count: i64,
+    /// Amount of the distinct addresses which have initiated a transaction.
+    pub active_accounts: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Average fee of the transactions paid in the token during a single day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyFeeStats {
+    pub day: NaiveDate,
+    pub token_id: i32,
+    /// Amount of the transactions which have paid the fee in the token.
+    pub tx_count: i64,
+    /// Average fee rounded to the token units.
+    pub average_fee: BigDecimal,
+}
+
+/// Total value locked in the token, i.e. the sum of the verified balances of all the accounts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenTvl {
+    pub token_id: i32,
+    pub amount: BigDecimal,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Auxiliary schema encapsulating the stats counting logic for the storage tables.
 #[derive(Debug)]
 pub struct StatsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
@@ -24,10 +66,83 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
         .count
         .unwrap_or(0);
 
-        metrics::histogram!("sql.chain.stats.count_outstanding_proofs", start.elapsed());
+        report_query!("sql.chain.stats.count_outstanding_proofs", start);
+        Ok(count as u32)
+    }
+
+    /// Loads the processing timelines of up to `limit` committed blocks ending with `max_block`
+    /// in the descending order of the block numbers.
+    pub async fn load_block_timings(
+        &mut self,
+        max_block: BlockNumber,
+        limit: u32,
+    ) -> QueryResult<Vec<BlockTimings>> {
+        let start = Instant::now();
+        let timings = sqlx::query_as!(
+            BlockTimings,
+            r#"
+            SELECT
+                commit_op.block_number,
+                commit_op.created_at AS committed_at,
+                commit_eth.confirmed_at AS "commit_confirmed_at?",
+                verify_op.created_at AS "verified_at?",
+                verify_eth.confirmed_at AS "verify_confirmed_at?"
+            FROM operations commit_op
+                LEFT JOIN eth_ops_binding commit_binding ON commit_binding.op_id = commit_op.id
+                LEFT JOIN eth_operations commit_eth ON commit_eth.id = commit_binding.eth_op_id
+                LEFT JOIN operations verify_op ON
+                    verify_op.block_number = commit_op.block_number AND verify_op.action_type = 'VERIFY'
+                LEFT JOIN eth_ops_binding verify_binding ON verify_binding.op_id = verify_op.id
+                LEFT JOIN eth_operations verify_eth ON verify_eth.id = verify_binding.eth_op_id
+            WHERE commit_op.action_type = 'COMMIT' AND commit_op.block_number <= $1
+            ORDER BY commit_op.block_number DESC
+            LIMIT $2
+            "#,
+            i64::from(*max_block),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.stats.load_block_timings", start);
+        Ok(timings)
+    }
+
+    /// Returns the amount of operations sent by `eth_sender` and not confirmed on Ethereum yet.
+    pub async fn count_unconfirmed_eth_operations(&mut self) -> QueryResult<u32> {
+        let start = Instant::now();
+        let count = sqlx::query!("SELECT COUNT(*) FROM eth_operations WHERE confirmed = false")
+            .fetch_one(self.0.conn())
+            .await?
+            .count
+            .unwrap_or(0);
+
+        report_query!("sql.chain.stats.count_unconfirmed_eth_operations", start);
         Ok(count as u32)
     }
 
+    /// Returns the amount of transactions waiting in the mempool for every transaction type
+    /// (e.g. `Transfer`), ordered by the type.
+    pub async fn count_mempool_txs_by_type(&mut self) -> QueryResult<Vec<(String, u32)>> {
+        let start = Instant::now();
+        let counts = sqlx::query!(
+            r#"
+            SELECT tx->>'type' AS "tx_type!", COUNT(*) AS "count!"
+            FROM mempool_txs
+            GROUP BY tx->>'type'
+            ORDER BY tx->>'type'
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| (row.tx_type, row.count as u32))
+        .collect();
+
+        report_query!("sql.chain.stats.count_mempool_txs_by_type", start);
+        Ok(counts)
+    }
+
     /// Returns the amount of executed transactions (both usual and priority).
     pub async fn count_total_transactions(&mut self) -> QueryResult<u32> {
         let start = Instant::now();
@@ -44,7 +159,143 @@ impl<'a, 'c> StatsSchema<'a, 'c> {
             .count
             .unwrap_or(0);
 
-        metrics::histogram!("sql.chain.stats.count_total_transactions", start.elapsed());
+        report_query!("sql.chain.stats.count_total_transactions", start);
         Ok((count_tx + prior_ops) as u32)
     }
+
+    /// Returns the latest day which has the aggregated statistics.
+    pub async fn last_aggregated_day(&mut self) -> QueryResult<Option<NaiveDate>> {
+        let start = Instant::now();
+        let day = sqlx::query!(r#"SELECT MAX(day) AS "day" FROM network_stats_daily"#)
+            .fetch_one(self.0.conn())
+            .await?
+            .day;
+
+        report_query!("sql.chain.stats.last_aggregated_day", start);
+        Ok(day)
+    }
+
+    /// Recomputes the daily statistics of the transactions executed since the start of the `since` day.
+    pub async fn aggregate_daily_stats(&mut self, since: NaiveDate) -> QueryResult<()> {
+        let start = Instant::now();
+        let since = DateTime::<Utc>::from_utc(since.and_hms(0, 0, 0), Utc);
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!(
+            r#"
+            WITH txs AS (
+                SELECT (created_at AT TIME ZONE 'UTC')::date AS day, primary_account_address AS account
+                FROM executed_transactions
+                WHERE success = true AND created_at >= $1
+                UNION ALL
+                SELECT (created_at AT TIME ZONE 'UTC')::date AS day, from_account AS account
+                FROM executed_priority_operations
+                WHERE created_at >= $1
+            )
+            INSERT INTO network_stats_daily (day, tx_count, active_accounts, updated_at)
+            SELECT day, COUNT(*), COUNT(DISTINCT account), now()
+            FROM txs
+            GROUP BY day
+            ON CONFLICT (day) DO UPDATE
+            SET tx_count = EXCLUDED.tx_count,
+                active_accounts = EXCLUDED.active_accounts,
+                updated_at = EXCLUDED.updated_at
+            "#,
+            since
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        // The fee token is `feeToken` for `ChangePubKey` and `token` for the other transactions.
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_daily_fees (day, token_id, tx_count, average_fee)
+            SELECT
+                (created_at AT TIME ZONE 'UTC')::date,
+                COALESCE(tx->>'feeToken', tx->>'token')::integer,
+                COUNT(*),
+                ROUND(AVG((tx->>'fee')::numeric))
+            FROM executed_transactions
+            WHERE success = true AND created_at >= $1 AND tx->>'fee' IS NOT NULL
+            GROUP BY 1, 2
+            ON CONFLICT (day, token_id) DO UPDATE
+            SET tx_count = EXCLUDED.tx_count,
+                average_fee = EXCLUDED.average_fee
+            "#,
+            since
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        report_query!("sql.chain.stats.aggregate_daily_stats", start);
+        Ok(())
+    }
+
+    /// Recomputes the total value locked in every token.
+    pub async fn aggregate_tvl(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO network_stats_tvl (token_id, amount, updated_at)
+            SELECT coin_id, SUM(balance), now()
+            FROM balances
+            GROUP BY coin_id
+            ON CONFLICT (token_id) DO UPDATE
+            SET amount = EXCLUDED.amount,
+                updated_at = EXCLUDED.updated_at
+            "#
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.stats.aggregate_tvl", start);
+        Ok(())
+    }
+
+    /// Loads the daily statistics starting from the `since` day, in the ascending order of the days.
+    pub async fn load_daily_stats(&mut self, since: NaiveDate) -> QueryResult<Vec<DailyStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            DailyStats,
+            "SELECT * FROM network_stats_daily WHERE day >= $1 ORDER BY day",
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.stats.load_daily_stats", start);
+        Ok(stats)
+    }
+
+    /// Loads the daily fees statistics starting from the `since` day, in the ascending order
+    /// of the days and the token IDs.
+    pub async fn load_daily_fees(&mut self, since: NaiveDate) -> QueryResult<Vec<DailyFeeStats>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            DailyFeeStats,
+            "SELECT * FROM network_stats_daily_fees WHERE day >= $1 ORDER BY day, token_id",
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.stats.load_daily_fees", start);
+        Ok(fees)
+    }
+
+    /// Loads the total value locked in every token, in the ascending order of the token IDs.
+    pub async fn load_tvl(&mut self) -> QueryResult<Vec<TokenTvl>> {
+        let start = Instant::now();
+        let tvl = sqlx::query_as!(
+            TokenTvl,
+            "SELECT * FROM network_stats_tvl ORDER BY token_id"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.chain.stats.load_tvl", start);
+        Ok(tvl)
+    }
 }
@@ -25,7 +25,7 @@ impl<'a, 'c> ConfigSchema<'a, 'c> {
             .fetch_one(self.0.conn())
             .await?;
 
-        metrics::histogram!("sql.load_config", start.elapsed());
+        report_query!("sql.load_config", start);
         Ok(config)
     }
 }
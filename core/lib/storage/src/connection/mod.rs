@@ -1,12 +1,22 @@
 // Built-in deps
-use std::{env, fmt, time::Instant};
+use std::{
+    env, fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
-use sqlx::{Connection, Error as SqlxError, PgConnection};
+use log::LevelFilter;
+use sqlx::{
+    postgres::PgConnectOptions, ConnectOptions, Connection, Error as SqlxError, PgConnection,
+};
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
-use crate::StorageProcessor;
+use crate::{
+    slow_query::{set_slow_query_threshold, DEFAULT_SLOW_QUERY_THRESHOLD},
+    StorageProcessor,
+};
 use zksync_utils::parse_env;
 
 pub mod holder;
@@ -18,22 +28,32 @@ pub type PooledConnection = deadpool::managed::Object<PgConnection, SqlxError>;
 #[derive(Clone)]
 struct DbPool {
     url: String,
+    /// Execution time after which the statement is logged as slow.
+    slow_query_threshold: Duration,
 }
 
 impl DbPool {
-    fn create(url: impl Into<String>, max_size: usize) -> Pool {
+    fn create(url: impl Into<String>, max_size: usize, slow_query_threshold: Duration) -> Pool {
         let pool_config = PoolConfig {
             max_size,
             timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
         };
-        Pool::from_config(DbPool { url: url.into() }, pool_config)
+        Pool::from_config(
+            DbPool {
+                url: url.into(),
+                slow_query_threshold,
+            },
+            pool_config,
+        )
     }
 }
 
 #[async_trait]
 impl Manager<PgConnection, SqlxError> for DbPool {
     async fn create(&self) -> Result<PgConnection, SqlxError> {
-        PgConnection::connect(&self.url).await
+        let mut options = PgConnectOptions::from_str(&self.url)?;
+        options.log_slow_statements(LevelFilter::Warn, self.slow_query_threshold);
+        PgConnection::connect_with(&options).await
     }
     async fn recycle(&self, obj: &mut PgConnection) -> RecycleResult<SqlxError> {
         Ok(obj.ping().await?)
@@ -45,9 +65,14 @@ impl Manager<PgConnection, SqlxError> for DbPool {
 ///
 /// The size of the pool and the database URL are configured via environment
 /// variables `DB_POOL_SIZE` and `DATABASE_URL` respectively.
+///
+/// Server subsystems use dedicated pools (see `ConnectionPool::dedicated`),
+/// so the load on one of them can't exhaust the connections of another.
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    /// Name of the subsystem the pool is dedicated to, used in metrics.
+    name: &'static str,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -64,9 +89,33 @@ impl ConnectionPool {
         let database_url = Self::get_database_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DB_POOL_SIZE"));
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        let pool = DbPool::create(
+            database_url,
+            max_size as usize,
+            DEFAULT_SLOW_QUERY_THRESHOLD,
+        );
+
+        Self {
+            pool,
+            name: "shared",
+        }
+    }
+
+    /// Creates a pool of the given size dedicated to one of the server subsystems.
+    /// Storage methods and statements taking longer than `slow_query_threshold` are reported as slow.
+    pub fn dedicated(
+        name: &'static str,
+        pool_max_size: usize,
+        slow_query_threshold: Duration,
+    ) -> Self {
+        set_slow_query_threshold(slow_query_threshold);
+        let pool = DbPool::create(
+            Self::get_database_url(),
+            pool_max_size,
+            slow_query_threshold,
+        );
 
-        Self { pool }
+        Self { pool, name }
     }
 
     /// Creates a `StorageProcessor` entity over a recoverable connection.
@@ -80,7 +129,7 @@ impl ConnectionPool {
     pub async fn access_storage(&self) -> Result<StorageProcessor<'_>, SqlxError> {
         let start = Instant::now();
         let connection = self.pool.get().await.unwrap();
-        metrics::histogram!("sql.connection_acquire", start.elapsed());
+        metrics::histogram!("sql.connection_acquire", start.elapsed(), "pool" => self.name);
 
         Ok(StorageProcessor::from_pool(connection))
     }
@@ -0,0 +1,105 @@
+// Built-in deps
+use std::{collections::HashMap, time::Instant};
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use self::records::StoredAccountCosigner;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Cosigners schema keeps the co-signer keys registered by the accounts, the transactions of
+/// such accounts are accepted only if they're signed by the co-signing service as well.
+#[derive(Debug)]
+pub struct CosignersSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> CosignersSchema<'a, 'c> {
+    /// Loads the last co-signer registration of the account.
+    pub async fn load_registration(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Option<StoredAccountCosigner>> {
+        let start = Instant::now();
+        let registration = sqlx::query_as!(
+            StoredAccountCosigner,
+            "SELECT * FROM account_cosigners WHERE address = $1",
+            address.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.cosigners.load_registration", start);
+        Ok(registration)
+    }
+
+    /// Loads the co-signers of the accounts opted in to the co-signing, keyed by the accounts.
+    /// Accounts without the co-signers are omitted.
+    pub async fn load_cosigners(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashMap<Address, Address>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let cosigners = sqlx::query!(
+            "SELECT address, cosigner FROM account_cosigners
+            WHERE address = ANY($1) AND cosigner IS NOT NULL",
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            let cosigner = row.cosigner?;
+            Some((
+                Address::from_slice(&row.address),
+                Address::from_slice(&cosigner),
+            ))
+        })
+        .collect();
+
+        report_query!("sql.cosigners.load_cosigners", start);
+        Ok(cosigners)
+    }
+
+    /// Sets the co-signer of the account, `None` opts the account out of the co-signing.
+    /// The registration is only saved if its nonce follows the nonce of the previous one,
+    /// returns `false` otherwise.
+    pub async fn set_cosigner(
+        &mut self,
+        address: Address,
+        cosigner: Option<Address>,
+        registration_nonce: i64,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let cosigner = cosigner.map(|cosigner| cosigner.as_bytes().to_vec());
+        let result = if registration_nonce == 1 {
+            sqlx::query!(
+                "INSERT INTO account_cosigners (address, cosigner, registration_nonce)
+                VALUES ($1, $2, 1)
+                ON CONFLICT (address) DO NOTHING",
+                address.as_bytes(),
+                cosigner
+            )
+            .execute(self.0.conn())
+            .await?
+        } else {
+            sqlx::query!(
+                "UPDATE account_cosigners
+                SET cosigner = $2, registration_nonce = $3, updated_at = now()
+                WHERE address = $1 AND registration_nonce = $3 - 1",
+                address.as_bytes(),
+                cosigner,
+                registration_nonce
+            )
+            .execute(self.0.conn())
+            .await?
+        };
+
+        report_query!("sql.cosigners.set_cosigner", start);
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,15 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Co-signer registered by the account for the 2-Factor authentication of its transactions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredAccountCosigner {
+    pub address: Vec<u8>,
+    pub cosigner: Option<Vec<u8>>,
+    pub registration_nonce: i64,
+    pub updated_at: DateTime<Utc>,
+}
@@ -56,7 +56,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_block_operations", start.elapsed());
+        report_query!("sql.data_restore.save_block_operations", start);
         Ok(())
     }
 
@@ -73,7 +73,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .apply_state_update(BlockNumber(0))
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_genesis_state", start.elapsed());
+        report_query!("sql.data_restore.save_genesis_state", start);
         Ok(())
     }
 
@@ -110,7 +110,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
                 }
             })
             .collect();
-        metrics::histogram!("sql.data_restore.load_rollup_ops_blocks", start.elapsed());
+        report_query!("sql.data_restore.load_rollup_ops_blocks", start);
         Ok(ops_blocks)
     }
 
@@ -133,10 +133,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!(
-            "sql.data_restore.update_last_watched_block_number",
-            start.elapsed()
-        );
+        report_query!("sql.data_restore.update_last_watched_block_number", start);
         Ok(())
     }
 
@@ -152,10 +149,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!(
-            "sql.data_restore.load_last_watched_block_number",
-            start.elapsed()
-        );
+        report_query!("sql.data_restore.load_last_watched_block_number", start);
         Ok(stored)
     }
 
@@ -195,7 +189,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.save_events_state", start.elapsed());
+        report_query!("sql.data_restore.save_events_state", start);
         Ok(())
     }
 
@@ -223,7 +217,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .update_storage_state(new_state)
             .await?;
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.save_rollup_ops", start.elapsed());
+        report_query!("sql.data_restore.save_rollup_ops", start);
         Ok(())
     }
 
@@ -249,7 +243,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.initialize_eth_stats", start.elapsed());
+        report_query!("sql.data_restore.initialize_eth_stats", start);
         Ok(())
     }
 
@@ -265,7 +259,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_all(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_events_state", start.elapsed());
+        report_query!("sql.data_restore.load_events_state", start);
         Ok(events)
     }
 
@@ -287,7 +281,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.data_restore.load_storage_state", start.elapsed());
+        report_query!("sql.data_restore.load_storage_state", start);
         Ok(state)
     }
 
@@ -306,7 +300,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
         .await?;
         transaction.commit().await?;
 
-        metrics::histogram!("sql.data_restore.update_storage_state", start.elapsed());
+        report_query!("sql.data_restore.update_storage_state", start);
         Ok(())
     }
 
@@ -329,7 +323,7 @@ impl<'a, 'c> DataRestoreSchema<'a, 'c> {
             .await?;
         }
         transaction.commit().await?;
-        metrics::histogram!("sql.data_restore.update_block_events", start.elapsed());
+        report_query!("sql.data_restore.update_block_events", start);
         Ok(())
     }
 }
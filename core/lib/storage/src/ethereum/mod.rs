@@ -1,16 +1,24 @@
 // Built-in deps
-use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    str::FromStr,
+    time::Instant,
+};
 // External imports
 use num::{BigInt, BigUint};
 use sqlx::types::BigDecimal;
-use zksync_basic_types::{H256, U256};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace imports
 use zksync_types::{
-    ethereum::{ETHOperation, InsertedOperationResponse, OperationType},
-    Operation,
+    ethereum::{
+        ETHOperation, ETHOperationSummary, EthOpId, GasAdjusterStats, InsertedOperationResponse,
+        OperationType,
+    },
+    BlockNumber, Operation,
 };
 // Local imports
-use self::records::{ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
+use self::records::{ETHParams, ETHStats, ETHTxHash, StorageETHOperation, StorageGasAdjusterStats};
 use crate::chain::operations::records::StoredOperation;
 use crate::{QueryResult, StorageActionType, StorageProcessor};
 
@@ -121,7 +129,82 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.load_unconfirmed_operations", start.elapsed());
+        report_query!("sql.ethereum.load_unconfirmed_operations", start);
+        Ok(ops)
+    }
+
+    /// Loads the brief information about the Ethereum operations in the descending order
+    /// of their IDs, starting from the operation preceding the `before` one (or the latest
+    /// operation, if `before` is not set).
+    pub async fn load_operations_summary(
+        &mut self,
+        before: Option<EthOpId>,
+        limit: u32,
+    ) -> QueryResult<Vec<ETHOperationSummary>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eth_ops = sqlx::query!(
+            r#"
+            SELECT eth_operations.id, eth_operations.op_type, eth_operations.nonce,
+                eth_operations.last_used_gas_price, eth_operations.confirmed, eth_operations.final_hash,
+                eth_operations.gas_used, operations.block_number as "block_number?"
+            FROM eth_operations
+            LEFT JOIN eth_ops_binding ON eth_ops_binding.eth_op_id = eth_operations.id
+            LEFT JOIN operations ON operations.id = eth_ops_binding.op_id
+            WHERE eth_operations.id < $1
+            ORDER BY eth_operations.id DESC
+            LIMIT $2
+            "#,
+            before.unwrap_or(EthOpId::MAX),
+            i64::from(limit),
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        // Load the stored txs hashes of all the operations at once, ordered by their ID,
+        // so the latest added hash will be the last one in the list of its operation.
+        let eth_op_ids: Vec<i64> = eth_ops.iter().map(|eth_op| eth_op.id).collect();
+        let eth_tx_hashes: Vec<ETHTxHash> = sqlx::query_as!(
+            ETHTxHash,
+            "SELECT * FROM eth_tx_hashes
+            WHERE eth_op_id = ANY($1)
+            ORDER BY id ASC",
+            &eth_op_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut used_tx_hashes: HashMap<i64, Vec<H256>> = HashMap::new();
+        for entry in eth_tx_hashes {
+            used_tx_hashes
+                .entry(entry.eth_op_id)
+                .or_default()
+                .push(H256::from_slice(&entry.tx_hash));
+        }
+
+        let mut ops = Vec::with_capacity(eth_ops.len());
+        for eth_op in eth_ops {
+            ops.push(ETHOperationSummary {
+                id: eth_op.id,
+                op_type: OperationType::from_str(eth_op.op_type.as_ref())
+                    .expect("Stored operation type must have a valid value"),
+                block_number: eth_op
+                    .block_number
+                    .map(|block_number| BlockNumber(block_number as u32)),
+                nonce: eth_op.nonce.into(),
+                last_used_gas_price: U256::from_str(&eth_op.last_used_gas_price.to_string())
+                    .unwrap(),
+                used_tx_hashes: used_tx_hashes.remove(&eth_op.id).unwrap_or_default(),
+                confirmed: eth_op.confirmed,
+                final_hash: eth_op.final_hash.map(|hash| H256::from_slice(&hash)),
+                gas_used: eth_op.gas_used.map(|gas_used| U256::from(gas_used as u64)),
+            });
+        }
+
+        transaction.commit().await?;
+
+        report_query!("sql.ethereum.load_operations_summary", start);
         Ok(ops)
     }
 
@@ -161,7 +244,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.load_unprocessed_operations", start.elapsed());
+        report_query!("sql.ethereum.load_unprocessed_operations", start);
         Ok(operations)
     }
 
@@ -174,13 +257,42 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         last_deadline_block: i64,
         last_used_gas_price: BigUint,
         raw_tx: Vec<u8>,
+    ) -> QueryResult<InsertedOperationResponse> {
+        self.save_new_eth_tx_from_account(
+            None,
+            op_type,
+            op_id,
+            last_deadline_block,
+            last_used_gas_price,
+            raw_tx,
+        )
+        .await
+    }
+
+    /// Same as `save_new_eth_tx`, but the nonce is assigned from the sequence of the provided
+    /// additional operator account. If `None` is provided, the main operator account is used.
+    pub async fn save_new_eth_tx_from_account(
+        &mut self,
+        account: Option<Address>,
+        op_type: OperationType,
+        op_id: Option<i64>,
+        last_deadline_block: i64,
+        last_used_gas_price: BigUint,
+        raw_tx: Vec<u8>,
     ) -> QueryResult<InsertedOperationResponse> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
         // It's important to assign nonce within the same db transaction
         // as saving the operation to avoid the state divergence.
-        let nonce = EthereumSchema(&mut transaction).get_next_nonce().await?;
+        let nonce = match account {
+            Some(address) => {
+                EthereumSchema(&mut transaction)
+                    .get_next_account_nonce(address)
+                    .await?
+            }
+            None => EthereumSchema(&mut transaction).get_next_nonce().await?,
+        };
 
         // Create and insert the operation.
 
@@ -222,7 +334,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.save_new_eth_tx", start.elapsed());
+        report_query!("sql.ethereum.save_new_eth_tx", start);
         Ok(response)
     }
 
@@ -237,7 +349,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.get_eth_op_id", start.elapsed());
+        report_query!("sql.ethereum.get_eth_op_id", start);
         Ok(hash_entry.eth_op_id)
     }
 
@@ -252,7 +364,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         )
         .execute(self.0.conn())
         .await?;
-        metrics::histogram!("sql.ethereum.add_hash_entry", start.elapsed());
+        report_query!("sql.ethereum.add_hash_entry", start);
         Ok(())
     }
 
@@ -278,7 +390,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_eth_tx", start.elapsed());
+        report_query!("sql.ethereum.update_eth_tx", start);
         Ok(())
     }
 
@@ -323,7 +435,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.report_created_operation", start.elapsed());
+        report_query!("sql.ethereum.report_created_operation", start);
         Ok(())
     }
 
@@ -353,7 +465,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.ethereum.update_gas_price", start.elapsed());
+        report_query!("sql.ethereum.update_gas_price", start);
         Ok(())
     }
 
@@ -364,7 +476,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let gas_price_limit =
             U256::try_from(params.gas_price_limit).expect("Negative gas limit value stored in DB");
 
-        metrics::histogram!("sql.ethereum.load_gas_price_limit", start.elapsed());
+        report_query!("sql.ethereum.load_gas_price_limit", start);
         Ok(gas_price_limit)
     }
 
@@ -376,16 +488,70 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .average_gas_price
             .map(|price| U256::try_from(price).expect("Negative average gas price stored in DB"));
 
-        metrics::histogram!("sql.ethereum.load_average_gas_price", start.elapsed());
+        report_query!("sql.ethereum.load_average_gas_price", start);
         Ok(average_gas_price)
     }
 
+    /// Stores the snapshot of the `GasAdjuster` state, replacing the previous one.
+    pub async fn update_gas_adjuster_stats(&mut self, stats: &GasAdjusterStats) -> QueryResult<()> {
+        let start = Instant::now();
+        let to_i64 = |price: U256| i64::try_from(price).expect("Can't convert U256 to i64");
+
+        let last_suggested_price = stats.last_suggested_price.map(to_i64);
+        let samples: Vec<i64> = stats.samples.iter().copied().map(to_i64).collect();
+        let average_price = stats.average_price.map(to_i64);
+        let price_limit = to_i64(stats.price_limit);
+
+        sqlx::query!(
+            "INSERT INTO eth_gas_adjuster_stats (id, last_suggested_price, samples, average_price, price_limit, scale_factor, updated_at)
+            VALUES (true, $1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id)
+            DO UPDATE
+            SET last_suggested_price = $1, samples = $2, average_price = $3, price_limit = $4, scale_factor = $5, updated_at = $6",
+            last_suggested_price,
+            &samples,
+            average_price,
+            price_limit,
+            stats.scale_factor,
+            stats.updated_at,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.ethereum.update_gas_adjuster_stats", start);
+        Ok(())
+    }
+
+    /// Loads the last stored snapshot of the `GasAdjuster` state, if any.
+    pub async fn load_gas_adjuster_stats(&mut self) -> QueryResult<Option<GasAdjusterStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query_as!(
+            StorageGasAdjusterStats,
+            "SELECT * FROM eth_gas_adjuster_stats WHERE id = true",
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let to_u256 = |price: i64| U256::try_from(price).expect("Negative gas price stored in DB");
+        let stats = stats.map(|stats| GasAdjusterStats {
+            last_suggested_price: stats.last_suggested_price.map(to_u256),
+            samples: stats.samples.into_iter().map(to_u256).collect(),
+            average_price: stats.average_price.map(to_u256),
+            price_limit: to_u256(stats.price_limit),
+            scale_factor: stats.scale_factor,
+            updated_at: stats.updated_at,
+        });
+
+        report_query!("sql.ethereum.load_gas_adjuster_stats", start);
+        Ok(stats)
+    }
+
     /// Loads the stored Ethereum operations stats.
     pub async fn load_stats(&mut self) -> QueryResult<ETHStats> {
         let start = Instant::now();
         let params = self.load_eth_params().await?;
 
-        metrics::histogram!("sql.ethereum.load_stats", start.elapsed());
+        report_query!("sql.ethereum.load_stats", start);
         Ok(params.into())
     }
 
@@ -394,27 +560,28 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         let params = sqlx::query_as!(ETHParams, "SELECT * FROM eth_parameters WHERE id = true",)
             .fetch_one(self.0.conn())
             .await?;
-        metrics::histogram!("sql.ethereum.load_eth_params", start.elapsed());
+        report_query!("sql.ethereum.load_eth_params", start);
         Ok(params)
     }
 
     /// Marks the stored Ethereum transaction as confirmed (and thus the associated `Operation`
-    /// is marked as confirmed as well).
-    pub async fn confirm_eth_tx(&mut self, hash: &H256) -> QueryResult<()> {
+    /// is marked as confirmed as well), storing the amount of gas used by the transaction.
+    pub async fn confirm_eth_tx(&mut self, hash: &H256, gas_used: Option<U256>) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
 
         let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
 
-        // Set the `confirmed` and `final_hash` field of the entry.
+        // Set the `confirmed`, `final_hash` and `gas_used` fields of the entry.
         let eth_op_id: i64 = sqlx::query!(
             "UPDATE eth_operations
-                SET confirmed = $1, final_hash = $2
+                SET confirmed = $1, final_hash = $2, confirmed_at = now(), gas_used = $4
                 WHERE id = $3
                 RETURNING id",
             true,
             hash.as_bytes(),
-            eth_op_id
+            eth_op_id,
+            gas_used.map(|gas_used| gas_used.as_u64() as i64)
         )
         .fetch_one(transaction.conn())
         .await?
@@ -434,7 +601,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.confirm_eth_tx", start.elapsed());
+        report_query!("sql.ethereum.confirm_eth_tx", start);
         Ok(())
     }
 
@@ -465,10 +632,51 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.ethereum.get_next_nonce", start.elapsed());
+        report_query!("sql.ethereum.get_next_nonce", start);
         Ok(old_nonce_value)
     }
 
+    /// Starts tracking the nonce of the additional operator account, unless it's tracked already.
+    ///
+    /// The provided nonce is expected to be the pending nonce of the account, so it must be
+    /// initialized before any transaction is sent from this account.
+    pub async fn init_account_nonce(&mut self, address: Address, nonce: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO eth_account_nonces (address, nonce) VALUES ($1, $2)
+            ON CONFLICT (address) DO NOTHING",
+            address.as_bytes(),
+            nonce
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.ethereum.init_account_nonce", start);
+        Ok(())
+    }
+
+    /// Same as `get_next_nonce`, but for the additional operator account.
+    /// The nonce of the account must be initialized with `init_account_nonce` beforehand.
+    pub(crate) async fn get_next_account_nonce(&mut self, address: Address) -> QueryResult<i64> {
+        let start = Instant::now();
+        let new_nonce = sqlx::query!(
+            "UPDATE eth_account_nonces
+            SET nonce = nonce + 1
+            WHERE address = $1
+            RETURNING nonce",
+            address.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .ok_or_else(|| {
+            anyhow::format_err!("Nonce of the account {:?} is not initialized", address)
+        })?
+        .nonce;
+
+        report_query!("sql.ethereum.get_next_account_nonce", start);
+        Ok(new_nonce - 1)
+    }
+
     /// Method that internally initializes the `eth_parameters` table.
     /// Since in db tests the database is empty, we must provide a possibility
     /// to initialize required db fields.
@@ -507,7 +715,7 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
             .await?;
         }
 
-        metrics::histogram!("sql.ethereum.initialize_eth_data", start.elapsed());
+        report_query!("sql.ethereum.initialize_eth_data", start);
         Ok(())
     }
 }
@@ -1,4 +1,5 @@
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 // Local imports
@@ -13,6 +14,8 @@ pub struct StorageETHOperation {
     pub final_hash: Option<Vec<u8>>,
     pub last_deadline_block: i64,
     pub last_used_gas_price: BigDecimal,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub gas_used: Option<i64>,
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -57,3 +60,14 @@ impl From<ETHParams> for ETHStats {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageGasAdjusterStats {
+    pub id: bool,
+    pub last_suggested_price: Option<i64>,
+    pub samples: Vec<i64>,
+    pub average_price: Option<i64>,
+    pub price_limit: i64,
+    pub scale_factor: f64,
+    pub updated_at: DateTime<Utc>,
+}
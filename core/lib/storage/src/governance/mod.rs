@@ -0,0 +1,113 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Governance schema keeps the state of the governance contract which isn't tied to the tokens
+/// (those are kept by the tokens schema): the validators set, the network governor and the
+/// paused tokens. The state is updated by `eth_watch` as the contract events get confirmed.
+#[derive(Debug)]
+pub struct GovernanceSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> GovernanceSchema<'a, 'c> {
+    /// Activates or deactivates the validator.
+    pub async fn set_validator_status(
+        &mut self,
+        address: Address,
+        is_active: bool,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO governance_validators (address, is_active)
+            VALUES ($1, $2)
+            ON CONFLICT (address) DO UPDATE SET is_active = $2, updated_at = now()",
+            address.as_bytes(),
+            is_active
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.governance.set_validator_status", start);
+        Ok(())
+    }
+
+    /// Loads the addresses of the active validators.
+    pub async fn load_active_validators(&mut self) -> QueryResult<Vec<Address>> {
+        let start = Instant::now();
+        let validators = sqlx::query!(
+            "SELECT address FROM governance_validators WHERE is_active ORDER BY address"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| Address::from_slice(&row.address))
+        .collect();
+
+        report_query!("sql.governance.load_active_validators", start);
+        Ok(validators)
+    }
+
+    /// Stores the new network governor.
+    pub async fn set_network_governor(&mut self, address: Address) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO governance_network_governor (address)
+            VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET address = $1, updated_at = now()",
+            address.as_bytes()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.governance.set_network_governor", start);
+        Ok(())
+    }
+
+    /// Loads the network governor, `None` if it hasn't been changed since the deployment.
+    pub async fn load_network_governor(&mut self) -> QueryResult<Option<Address>> {
+        let start = Instant::now();
+        let governor = sqlx::query!("SELECT address FROM governance_network_governor")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|row| Address::from_slice(&row.address));
+
+        report_query!("sql.governance.load_network_governor", start);
+        Ok(governor)
+    }
+
+    /// Pauses or unpauses the deposits of the token with the given address.
+    pub async fn set_token_paused(&mut self, address: Address, paused: bool) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO governance_paused_tokens (address, paused)
+            VALUES ($1, $2)
+            ON CONFLICT (address) DO UPDATE SET paused = $2, updated_at = now()",
+            address.as_bytes(),
+            paused
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.governance.set_token_paused", start);
+        Ok(())
+    }
+
+    /// Loads the addresses of the tokens whose deposits are paused.
+    pub async fn load_paused_tokens(&mut self) -> QueryResult<Vec<Address>> {
+        let start = Instant::now();
+        let tokens = sqlx::query!(
+            "SELECT address FROM governance_paused_tokens WHERE paused ORDER BY address"
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| Address::from_slice(&row.address))
+        .collect();
+
+        report_query!("sql.governance.load_paused_tokens", start);
+        Ok(tokens)
+    }
+}
@@ -0,0 +1,51 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Key of the Postgres advisory lock held by the active Core instance.
+const CORE_LEADER_LOCK_KEY: i64 = 0x7a6b_5379_6e63;
+
+/// Leader lock schema provides the lock ensuring that only one Core instance
+/// (the leader) creates blocks at a time, while the others stay in the standby mode.
+///
+/// The lock is bound to the database session rather than to a transaction, so it's
+/// held for as long as the connection which acquired it is alive, and is released
+/// by the database once the leader terminates or loses its connection.
+#[derive(Debug)]
+pub struct LeaderLockSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> LeaderLockSchema<'a, 'c> {
+    /// Attempts to acquire the leader lock without waiting.
+    /// Returns `true` if the lock is held by the current connection afterwards.
+    pub async fn try_acquire(&mut self) -> QueryResult<bool> {
+        let start = Instant::now();
+        let acquired = sqlx::query!(
+            "SELECT pg_try_advisory_lock($1) AS acquired",
+            CORE_LEADER_LOCK_KEY
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .acquired
+        .unwrap_or(false);
+
+        report_query!("sql.leader_lock.try_acquire", start);
+        Ok(acquired)
+    }
+
+    /// Releases the leader lock held by the current connection.
+    pub async fn release(&mut self) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "SELECT pg_advisory_unlock($1) AS released",
+            CORE_LEADER_LOCK_KEY
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        report_query!("sql.leader_lock.release", start);
+        Ok(())
+    }
+}
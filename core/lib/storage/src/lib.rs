@@ -31,10 +31,14 @@
 //! There are the following sets of schemas:
 //!
 //! - config, for the server config.
+//! - cosigners, for the co-signers of the accounts opted in to the 2-Factor authentication.
 //! - data_restore, for the data_restore crate.
 //! - ethereum, for the data associated with the Ethereum blockchain.
+//! - governance, for the validators, the network governor and the paused tokens of the governance contract.
 //! - prover, for the data on prover jobs, proofs, etc.
+//! - rejected_txs, for the transactions rejected by the API server on submission.
 //! - tokens, for storing and loading known tokens.
+//! - webhooks, for the webhooks notified about the activity of the addresses.
 //! - chain - the biggest one, which includes several schemas for the ZKSync sidechain itself.
 //!
 //! The chain module includes the following schemas:
@@ -82,19 +86,39 @@ use zksync_types::ActionType;
 // Local imports
 use crate::connection::{holder::ConnectionHolder, PooledConnection};
 
+/// Reports the execution time of the storage method to the metrics.
+/// Methods exceeding the slow query threshold are also reported to the log.
+macro_rules! report_query {
+    ($method:literal, $start:expr) => {{
+        let elapsed = $start.elapsed();
+        metrics::histogram!($method, elapsed);
+        $crate::slow_query::trace_slow_query($method, elapsed);
+    }};
+}
+
 // mod schema;
 #[cfg(test)]
 mod tests;
 
+pub mod admin_audit;
+pub mod background_migrations;
 pub mod chain;
 pub mod config;
 pub mod connection;
+pub mod cosigners;
 pub mod data_restore;
 pub mod diff;
 pub mod ethereum;
+pub mod governance;
+pub mod leader_lock;
+pub mod outbox;
 pub mod prover;
+pub mod rejected_txs;
+pub mod runtime_flags;
+mod slow_query;
 pub mod test_data;
 pub mod tokens;
+pub mod webhooks;
 
 pub use crate::connection::ConnectionPool;
 pub type QueryResult<T> = Result<T, anyhow::Error>;
@@ -182,6 +206,18 @@ impl<'a> StorageProcessor<'a> {
         }
     }
 
+    /// Gains access to the `AdminAudit` schema.
+    pub fn admin_audit_schema(&mut self) -> admin_audit::AdminAuditSchema<'_, 'a> {
+        admin_audit::AdminAuditSchema(self)
+    }
+
+    /// Gains access to the `BackgroundMigrations` schema.
+    pub fn background_migrations_schema(
+        &mut self,
+    ) -> background_migrations::BackgroundMigrationsSchema<'_, 'a> {
+        background_migrations::BackgroundMigrationsSchema(self)
+    }
+
     /// Gains access to the `Chain` schemas.
     pub fn chain(&mut self) -> chain::ChainIntermediator<'_, 'a> {
         chain::ChainIntermediator(self)
@@ -192,6 +228,11 @@ impl<'a> StorageProcessor<'a> {
         config::ConfigSchema(self)
     }
 
+    /// Gains access to the `Cosigners` schema.
+    pub fn cosigners_schema(&mut self) -> cosigners::CosignersSchema<'_, 'a> {
+        cosigners::CosignersSchema(self)
+    }
+
     /// Gains access to the `DataRestore` schema.
     pub fn data_restore_schema(&mut self) -> data_restore::DataRestoreSchema<'_, 'a> {
         data_restore::DataRestoreSchema(self)
@@ -202,16 +243,46 @@ impl<'a> StorageProcessor<'a> {
         ethereum::EthereumSchema(self)
     }
 
+    /// Gains access to the `Governance` schema.
+    pub fn governance_schema(&mut self) -> governance::GovernanceSchema<'_, 'a> {
+        governance::GovernanceSchema(self)
+    }
+
+    /// Gains access to the `LeaderLock` schema.
+    pub fn leader_lock_schema(&mut self) -> leader_lock::LeaderLockSchema<'_, 'a> {
+        leader_lock::LeaderLockSchema(self)
+    }
+
+    /// Gains access to the `Outbox` schema.
+    pub fn outbox_schema(&mut self) -> outbox::OutboxSchema<'_, 'a> {
+        outbox::OutboxSchema(self)
+    }
+
     /// Gains access to the `Prover` schema.
     pub fn prover_schema(&mut self) -> prover::ProverSchema<'_, 'a> {
         prover::ProverSchema(self)
     }
 
+    /// Gains access to the `RejectedTxs` schema.
+    pub fn rejected_txs_schema(&mut self) -> rejected_txs::RejectedTxsSchema<'_, 'a> {
+        rejected_txs::RejectedTxsSchema(self)
+    }
+
+    /// Gains access to the `RuntimeFlags` schema.
+    pub fn runtime_flags_schema(&mut self) -> runtime_flags::RuntimeFlagsSchema<'_, 'a> {
+        runtime_flags::RuntimeFlagsSchema(self)
+    }
+
     /// Gains access to the `Tokens` schema.
     pub fn tokens_schema(&mut self) -> tokens::TokensSchema<'_, 'a> {
         tokens::TokensSchema(self)
     }
 
+    /// Gains access to the `Webhooks` schema.
+    pub fn webhooks_schema(&mut self) -> webhooks::WebhooksSchema<'_, 'a> {
+        webhooks::WebhooksSchema(self)
+    }
+
     fn conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
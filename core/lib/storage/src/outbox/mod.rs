@@ -0,0 +1,197 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Done;
+// Workspace imports
+use zksync_types::{tx::TxEthSignature, SignedZkSyncTx};
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Request forwarded by the API server to the Core mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OutboxRequest {
+    Tx(Box<SignedZkSyncTx>),
+    TxsBatch(Vec<SignedZkSyncTx>, Option<TxEthSignature>),
+}
+
+/// Outcome of the attempt to claim the outbox request for processing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutboxClaim {
+    /// Request was claimed and should be processed.
+    Claimed,
+    /// Request is being processed right now.
+    InProgress,
+    /// Request was processed already, the result is returned.
+    Processed(serde_json::Value),
+    /// Request doesn't exist: either it was abandoned by the sender or removed long after
+    /// being processed.
+    Unknown,
+}
+
+/// Outbox schema keeps the requests forwarded by the API server to the Core,
+/// providing exactly-once processing for them.
+///
+/// Requests are stored by the sender prior to sending them. The Core claims the request
+/// before processing it and stores the result afterwards, so a repeated delivery of the
+/// same request returns the stored result instead of processing the request again.
+/// If the sender gives up on the request, it's abandoned, so it won't be processed
+/// if delivered later.
+///
+/// Requests which were never delivered (e.g. the sender has failed before abandoning them),
+/// and the ones whose processing was interrupted by the Core restart, are picked up from
+/// the outbox by the Core itself. Processed requests are kept for a while, so their repeated
+/// deliveries still get the result, and removed afterwards.
+#[derive(Debug)]
+pub struct OutboxSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> OutboxSchema<'a, 'c> {
+    /// Stores a new request, returns its ID.
+    pub async fn add_request(&mut self, request: &OutboxRequest) -> QueryResult<i64> {
+        let start = Instant::now();
+        let request = serde_json::to_value(request)?;
+        let id = sqlx::query!(
+            "INSERT INTO core_api_outbox (request) VALUES ($1) RETURNING id",
+            request
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .id;
+
+        report_query!("sql.outbox.add_request", start);
+        Ok(id)
+    }
+
+    /// Claims the request for processing.
+    pub async fn claim_request(&mut self, id: i64) -> QueryResult<OutboxClaim> {
+        let start = Instant::now();
+        // The request is claimed and its state is loaded by the same statement. The outer query
+        // sees the row as it was before the update, which doesn't change the result.
+        let request = sqlx::query!(
+            r#"WITH claimed AS (
+                UPDATE core_api_outbox SET claimed_at = now()
+                WHERE id = $1 AND claimed_at IS NULL
+                RETURNING id
+            )
+            SELECT EXISTS (SELECT 1 FROM claimed) AS "claimed!", result
+            FROM core_api_outbox WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let claim = match request {
+            Some(request) if request.claimed => OutboxClaim::Claimed,
+            Some(request) => match request.result {
+                Some(result) => OutboxClaim::Processed(result),
+                None => OutboxClaim::InProgress,
+            },
+            None => OutboxClaim::Unknown,
+        };
+
+        report_query!("sql.outbox.claim_request", start);
+        Ok(claim)
+    }
+
+    /// Claims the requests left unprocessed: the ones which weren't claimed until `created_before`
+    /// and the ones claimed before `claimed_before`. Returns at most `limit` requests.
+    pub async fn claim_pending_requests(
+        &mut self,
+        created_before: DateTime<Utc>,
+        claimed_before: DateTime<Utc>,
+        limit: i64,
+    ) -> QueryResult<Vec<(i64, OutboxRequest)>> {
+        let start = Instant::now();
+        let requests = sqlx::query!(
+            "UPDATE core_api_outbox SET claimed_at = now()
+            WHERE id IN (
+                SELECT id FROM core_api_outbox
+                WHERE result IS NULL AND (
+                    (claimed_at IS NULL AND created_at < $1) OR claimed_at < $2
+                )
+                ORDER BY id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, request",
+            created_before,
+            claimed_before,
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|request| -> QueryResult<_> {
+            Ok((request.id, serde_json::from_value(request.request)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        report_query!("sql.outbox.claim_pending_requests", start);
+        Ok(requests)
+    }
+
+    /// Stores the result of the processed request.
+    pub async fn store_result(&mut self, id: i64, result: serde_json::Value) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE core_api_outbox SET result = $2 WHERE id = $1",
+            id,
+            result
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.outbox.store_result", start);
+        Ok(())
+    }
+
+    /// Loads the result of the request, if it was processed.
+    pub async fn load_result(&mut self, id: i64) -> QueryResult<Option<serde_json::Value>> {
+        let start = Instant::now();
+        let result = sqlx::query!("SELECT result FROM core_api_outbox WHERE id = $1", id)
+            .fetch_optional(self.0.conn())
+            .await?
+            .and_then(|request| request.result);
+
+        report_query!("sql.outbox.load_result", start);
+        Ok(result)
+    }
+
+    /// Abandons the request unless it was claimed by the Core already.
+    /// Returns `true` if the request was abandoned and thus will never be processed.
+    pub async fn abandon_request(&mut self, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let abandoned = sqlx::query!(
+            "DELETE FROM core_api_outbox WHERE id = $1 AND claimed_at IS NULL",
+            id
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            == 1;
+
+        report_query!("sql.outbox.abandon_request", start);
+        Ok(abandoned)
+    }
+
+    /// Removes the requests processed before `claimed_before`. Returns the number of the removed
+    /// requests.
+    pub async fn remove_processed_requests(
+        &mut self,
+        claimed_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let removed = sqlx::query!(
+            "DELETE FROM core_api_outbox WHERE result IS NOT NULL AND claimed_at < $1",
+            claimed_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        report_query!("sql.outbox.remove_processed_requests", start);
+        Ok(removed)
+    }
+}
@@ -7,9 +7,9 @@ use std::{
 use sqlx::{postgres::types::PgInterval, Done};
 // Workspace imports
 use zksync_crypto::proof::EncodedProofPlonk;
-use zksync_types::BlockNumber;
+use zksync_types::{BlockNumber, U256};
 // Local imports
-use self::records::{ActiveProver, ProverRun, StoredProof};
+use self::records::{ActiveProver, ProverRun, StoredProof, WitnessDebugBundle};
 use crate::prover::records::StorageBlockWitness;
 use crate::{chain::block::BlockSchema, QueryResult, StorageProcessor};
 
@@ -62,7 +62,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         let result = *last_committed_block - (*last_verified_block + num_ongoing_jobs);
 
         transaction.commit().await?;
-        metrics::histogram!("sql.prover.unstarted_jobs_count", start.elapsed());
+        report_query!("sql.prover.unstarted_jobs_count", start);
         Ok(result)
     }
 
@@ -85,7 +85,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .integer_value
             .unwrap_or(0) as u64;
 
-        metrics::histogram!("sql.prover.pending_jobs_count", start.elapsed());
+        report_query!("sql.prover.pending_jobs_count", start);
         Ok(block_without_proofs as u32)
     }
 
@@ -103,7 +103,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.get_existing_prover_run", start.elapsed());
+        report_query!("sql.prover.get_existing_prover_run", start);
         Ok(prover_run)
     }
 
@@ -124,6 +124,26 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
             .execute(transaction.conn())
             .await?;
 
+        // Reuse the cached proofs for the blocks with the already proven commitments,
+        // so these blocks won't be assigned to the provers.
+        let cached_proofs = sqlx::query!(
+            r#"
+                INSERT INTO proofs (block_number, proof)
+                SELECT block_witness.block, proof_cache.proof FROM block_witness
+                INNER JOIN proof_cache ON proof_cache.commitment = block_witness.commitment
+                WHERE block_witness.block >
+                    (SELECT COALESCE(max(block_number),0) FROM operations WHERE action_type = 'VERIFY')
+                ON CONFLICT (block_number) DO NOTHING
+            "#
+        )
+        .execute(transaction.conn())
+        .await?
+        .rows_affected();
+        if cached_proofs > 0 {
+            vlog::info!("Reused {} cached proofs", cached_proofs);
+            metrics::counter!("sql.prover.cached_proofs", cached_proofs);
+        }
+
         // Find the block that satisfies the following criteria:
         // - Block number is greater than the index of last verified block.
         // - There is no proof for block.
@@ -184,7 +204,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
 
         transaction.commit().await?;
 
-        metrics::histogram!("sql.prover.prover_run_for_next_commit", start.elapsed());
+        report_query!("sql.prover.prover_run_for_next_commit", start);
         Ok(result)
     }
 
@@ -200,7 +220,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.record_prover_is_working", start.elapsed());
+        report_query!("sql.prover.record_prover_is_working", start);
         Ok(())
     }
 
@@ -218,7 +238,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .id;
 
-        metrics::histogram!("sql.prover.register_prover", start.elapsed());
+        report_query!("sql.prover.register_prover", start);
         Ok(inserted_id)
     }
 
@@ -233,7 +253,7 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_one(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.prover_by_id", start.elapsed());
+        report_query!("sql.prover.prover_by_id", start);
         Ok(prover)
     }
 
@@ -252,28 +272,47 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.record_prover_stop", start.elapsed());
+        report_query!("sql.prover.record_prover_stop", start);
         Ok(())
     }
 
     /// Stores the proof for a block.
+    ///
+    /// The proof is also added to the proof cache, so it can be reused for a block with the same commitment.
     pub async fn store_proof(
         &mut self,
         block_number: BlockNumber,
         proof: &EncodedProofPlonk,
     ) -> QueryResult<usize> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let proof_value = serde_json::to_value(proof).unwrap();
         let updated_rows = sqlx::query!(
             "INSERT INTO proofs (block_number, proof)
             VALUES ($1, $2)",
             i64::from(*block_number),
-            serde_json::to_value(proof).unwrap()
+            proof_value
         )
-        .execute(self.0.conn())
+        .execute(transaction.conn())
         .await?
         .rows_affected() as usize;
 
-        metrics::histogram!("sql.prover.store_proof", start.elapsed());
+        // The only public input of the block proof is the block commitment.
+        if let Some(commitment) = proof.inputs.first() {
+            sqlx::query!(
+                "INSERT INTO proof_cache (commitment, proof)
+                VALUES ($1, $2)
+                ON CONFLICT (commitment) DO NOTHING",
+                commitment_bytes(*commitment),
+                proof_value
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+        report_query!("sql.prover.store_proof", start);
         Ok(updated_rows)
     }
 
@@ -292,33 +331,349 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .await?
         .map(|stored| serde_json::from_value(stored.proof).unwrap());
 
-        metrics::histogram!("sql.prover.load_proof", start.elapsed());
+        report_query!("sql.prover.load_proof", start);
         Ok(proof)
     }
 
-    /// Stores witness for a block
+    /// Stores witness for a block along with the block commitment.
+    /// Witness generation job for the block is considered completed.
     pub async fn store_witness(
         &mut self,
         block: BlockNumber,
+        commitment: U256,
         witness: serde_json::Value,
     ) -> QueryResult<()> {
         let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
         let witness_str = serde_json::to_string(&witness).expect("Failed to serialize witness");
         sqlx::query!(
-            "INSERT INTO block_witness (block, witness)
-            VALUES ($1, $2)
+            "INSERT INTO block_witness (block, witness, commitment)
+            VALUES ($1, $2, $3)
             ON CONFLICT (block)
             DO NOTHING",
             i64::from(*block),
-            witness_str
+            witness_str,
+            commitment_bytes(commitment)
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM witness_generation_jobs WHERE block_number = $1",
+            i64::from(*block),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        report_query!("sql.prover.store_witness", start);
+        Ok(())
+    }
+
+    /// Chooses the next block to generate witness for and assigns it to the witness generator.
+    /// Returns `None` if all the blocks either have witness or are assigned to other witness generators.
+    ///
+    /// Jobs that weren't updated for `job_timeout` are considered abandoned (e.g. because
+    /// the witness generator crashed) and can be assigned again.
+    pub async fn witness_job_for_next_block(
+        &mut self,
+        worker_: &str,
+        job_timeout: time::Duration,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!("LOCK TABLE witness_generation_jobs IN EXCLUSIVE MODE")
+            .execute(transaction.conn())
+            .await?;
+
+        let block_number = sqlx::query!(
+            r#"
+                SELECT min(number) FROM blocks
+                WHERE number >
+                        (SELECT COALESCE(max(block_number),0) FROM operations WHERE action_type = 'VERIFY')
+                    AND NOT EXISTS
+                        (SELECT * FROM block_witness WHERE block = blocks.number)
+                    AND NOT EXISTS
+                        (SELECT * FROM witness_generation_jobs
+                            WHERE block_number = blocks.number AND (now() - updated_at) < $1::interval)
+            "#,
+            PgInterval::try_from(job_timeout).expect("Cannot convert Duration to PgInterval"),
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .min;
+
+        if let Some(block_number) = block_number {
+            sqlx::query!(
+                "INSERT INTO witness_generation_jobs (block_number, worker)
+                VALUES ($1, $2)
+                ON CONFLICT (block_number)
+                DO UPDATE SET worker = $2, created_at = now(), updated_at = now()",
+                block_number,
+                worker_,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+        report_query!("sql.prover.witness_job_for_next_block", start);
+        Ok(block_number.map(|block_number| BlockNumber(block_number as u32)))
+    }
+
+    /// Records the progress of the witness generation job, so it won't be considered abandoned.
+    /// Returns `false` if the job is not assigned to the witness generator anymore, e.g. because
+    /// it was considered abandoned and taken by another witness generator.
+    pub async fn record_witness_job_progress(
+        &mut self,
+        block: BlockNumber,
+        worker_: &str,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let updated = sqlx::query!(
+            "UPDATE witness_generation_jobs SET updated_at = now()
+            WHERE block_number = $1 AND worker = $2",
+            i64::from(*block),
+            worker_,
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        report_query!("sql.prover.record_witness_job_progress", start);
+        Ok(updated > 0)
+    }
+
+    /// Removes the witness generation job for the block, so it can be assigned again right away.
+    /// The job is kept if it was already taken by another witness generator.
+    pub async fn release_witness_job(
+        &mut self,
+        block: BlockNumber,
+        worker_: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM witness_generation_jobs WHERE block_number = $1 AND worker = $2",
+            i64::from(*block),
+            worker_,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.prover.release_witness_job", start);
+        Ok(())
+    }
+
+    /// Records the failure of the prover to prove the block, so the debug bundle
+    /// can be built for it. The bundle built for the earlier failure is kept,
+    /// since it's built from the same witness.
+    pub async fn report_proving_failure(
+        &mut self,
+        block: BlockNumber,
+        failure: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO witness_debug_bundles (block_number, failure)
+            VALUES ($1, $2)
+            ON CONFLICT (block_number) DO UPDATE
+            SET failure = $2, reported_at = now()",
+            i64::from(*block),
+            failure
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.prover.report_proving_failure", start);
+        Ok(())
+    }
+
+    /// Returns the earliest block the proving failure was reported for,
+    /// but the debug bundle is not built yet.
+    pub async fn debug_bundle_job(&mut self) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let block = sqlx::query!(
+            "SELECT block_number FROM witness_debug_bundles
+            WHERE bundle IS NULL
+            ORDER BY block_number ASC
+            LIMIT 1"
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| BlockNumber(record.block_number as u32));
+
+        report_query!("sql.prover.debug_bundle_job", start);
+        Ok(block)
+    }
+
+    /// Stores the debug bundle built for the block.
+    pub async fn store_debug_bundle(
+        &mut self,
+        block: BlockNumber,
+        unsatisfied_constraint: Option<&str>,
+        num_constraints: Option<u64>,
+        bundle: serde_json::Value,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "UPDATE witness_debug_bundles
+            SET unsatisfied_constraint = $2, num_constraints = $3, bundle = $4, created_at = now()
+            WHERE block_number = $1",
+            i64::from(*block),
+            unsatisfied_constraint,
+            num_constraints.map(|num| num as i64),
+            bundle
         )
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.store_witness", start.elapsed());
+        report_query!("sql.prover.store_debug_bundle", start);
+        Ok(())
+    }
+
+    /// Loads the debug bundle of the block, if the proving failure was reported for it.
+    pub async fn load_debug_bundle(
+        &mut self,
+        block: BlockNumber,
+    ) -> QueryResult<Option<WitnessDebugBundle>> {
+        let start = Instant::now();
+        let bundle = sqlx::query_as!(
+            WitnessDebugBundle,
+            "SELECT * FROM witness_debug_bundles WHERE block_number = $1",
+            i64::from(*block)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.prover.load_debug_bundle", start);
+        Ok(bundle)
+    }
+
+    /// Returns up to `limit` verified blocks that still have the blob of the given kind
+    /// stored in the database, and were verified more than `age` ago.
+    pub async fn blocks_to_archive(
+        &mut self,
+        blob: ColdStorageBlob,
+        age: time::Duration,
+        limit: u32,
+    ) -> QueryResult<Vec<BlockNumber>> {
+        let start = Instant::now();
+        let age = PgInterval::try_from(age).expect("Cannot convert Duration to PgInterval");
+        let blocks = match blob {
+            ColdStorageBlob::Proof => {
+                sqlx::query!(
+                    "SELECT block_number FROM proofs
+                    WHERE block_number IN (
+                        SELECT block_number FROM operations
+                        WHERE action_type = 'VERIFY' AND confirmed = true AND created_at <= now() - $1::interval
+                    )
+                    ORDER BY block_number ASC
+                    LIMIT $2",
+                    age,
+                    i64::from(limit),
+                )
+                .fetch_all(self.0.conn())
+                .await?
+                .into_iter()
+                .map(|row| row.block_number)
+                .collect::<Vec<_>>()
+            }
+            ColdStorageBlob::Witness => {
+                sqlx::query!(
+                    "SELECT block FROM block_witness
+                    WHERE block IN (
+                        SELECT block_number FROM operations
+                        WHERE action_type = 'VERIFY' AND confirmed = true AND created_at <= now() - $1::interval
+                    )
+                    ORDER BY block ASC
+                    LIMIT $2",
+                    age,
+                    i64::from(limit),
+                )
+                .fetch_all(self.0.conn())
+                .await?
+                .into_iter()
+                .map(|row| row.block)
+                .collect()
+            }
+        };
+
+        report_query!("sql.prover.blocks_to_archive", start);
+        Ok(blocks
+            .into_iter()
+            .map(|block| BlockNumber(block as u32))
+            .collect())
+    }
+
+    /// Records that the blob of the block was uploaded to the cold storage under the
+    /// given key and removes it from the database.
+    pub async fn archive_blob(
+        &mut self,
+        blob: ColdStorageBlob,
+        block: BlockNumber,
+        object_key: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!(
+            "INSERT INTO cold_storage_objects (block_number, kind, object_key)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (block_number, kind)
+            DO UPDATE SET object_key = $3, archived_at = now()",
+            i64::from(*block),
+            blob.as_str(),
+            object_key,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        match blob {
+            ColdStorageBlob::Proof => {
+                sqlx::query!(
+                    "DELETE FROM proofs WHERE block_number = $1",
+                    i64::from(*block),
+                )
+                .execute(transaction.conn())
+                .await?;
+            }
+            ColdStorageBlob::Witness => {
+                sqlx::query!(
+                    "DELETE FROM block_witness WHERE block = $1",
+                    i64::from(*block),
+                )
+                .execute(transaction.conn())
+                .await?;
+            }
+        }
+
+        transaction.commit().await?;
+        report_query!("sql.prover.archive_blob", start);
         Ok(())
     }
 
+    /// Returns the key of the blob in the cold storage, if the blob was archived.
+    pub async fn cold_storage_key(
+        &mut self,
+        blob: ColdStorageBlob,
+        block: BlockNumber,
+    ) -> QueryResult<Option<String>> {
+        let start = Instant::now();
+        let object_key = sqlx::query!(
+            "SELECT object_key FROM cold_storage_objects
+            WHERE block_number = $1 AND kind = $2",
+            i64::from(*block),
+            blob.as_str(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| row.object_key);
+
+        report_query!("sql.prover.cold_storage_key", start);
+        Ok(object_key)
+    }
+
     /// Gets stored witness for a block
     pub async fn get_witness(
         &mut self,
@@ -333,8 +688,168 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.prover.get_witness", start.elapsed());
+        report_query!("sql.prover.get_witness", start);
         Ok(block_witness
             .map(|w| serde_json::from_str(&w.witness).expect("Failed to deserialize witness")))
     }
+
+    /// Stores the part of the proof uploaded by the prover run and returns the amount of the proof
+    /// bytes received so far, so the prover continues the upload from this offset.
+    ///
+    /// Chunks that don't continue the already received data are ignored. Uploads that didn't receive
+    /// a chunk for `upload_timeout` are removed. The new upload isn't started (and `None` is returned)
+    /// if the unfinished uploads together with it would exceed `max_pending_size` bytes.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn store_proof_chunk(
+        &mut self,
+        block: BlockNumber,
+        prover_run_id: i32,
+        total_size: u64,
+        offset: u64,
+        chunk: &[u8],
+        upload_timeout: time::Duration,
+        max_pending_size: u64,
+    ) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        sqlx::query!("LOCK TABLE proof_uploads IN EXCLUSIVE MODE")
+            .execute(transaction.conn())
+            .await?;
+
+        sqlx::query!(
+            "DELETE FROM proof_uploads WHERE (now() - updated_at) >= $1::interval",
+            PgInterval::try_from(upload_timeout).expect("Cannot convert Duration to PgInterval"),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        let upload = sqlx::query!(
+            "SELECT total_size, uploaded FROM proof_uploads
+            WHERE block_number = $1 AND prover_run_id = $2",
+            i64::from(*block),
+            prover_run_id,
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+
+        let mut uploaded = match upload {
+            Some(upload) if upload.total_size as u64 == total_size => upload.uploaded as u64,
+            _ => {
+                // Another proof is uploaded by the prover run, start over.
+                sqlx::query!(
+                    "DELETE FROM proof_uploads WHERE block_number = $1 AND prover_run_id = $2",
+                    i64::from(*block),
+                    prover_run_id,
+                )
+                .execute(transaction.conn())
+                .await?;
+
+                let pending_size = sqlx::query!(
+                    r#"SELECT COALESCE(sum(total_size), 0)::BIGINT as "pending_size!" FROM proof_uploads"#
+                )
+                .fetch_one(transaction.conn())
+                .await?
+                .pending_size as u64;
+                if pending_size + total_size > max_pending_size {
+                    transaction.commit().await?;
+                    return Ok(None);
+                }
+
+                sqlx::query!(
+                    "INSERT INTO proof_uploads (block_number, prover_run_id, total_size)
+                    VALUES ($1, $2, $3)",
+                    i64::from(*block),
+                    prover_run_id,
+                    total_size as i64,
+                )
+                .execute(transaction.conn())
+                .await?;
+                0
+            }
+        };
+
+        if offset == uploaded && offset + chunk.len() as u64 <= total_size {
+            sqlx::query!(
+                "INSERT INTO proof_upload_chunks (block_number, prover_run_id, chunk_offset, data)
+                VALUES ($1, $2, $3, $4)",
+                i64::from(*block),
+                prover_run_id,
+                offset as i64,
+                chunk,
+            )
+            .execute(transaction.conn())
+            .await?;
+            uploaded += chunk.len() as u64;
+        }
+
+        sqlx::query!(
+            "UPDATE proof_uploads SET uploaded = $3, updated_at = now()
+            WHERE block_number = $1 AND prover_run_id = $2",
+            i64::from(*block),
+            prover_run_id,
+            uploaded as i64,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        report_query!("sql.prover.store_proof_chunk", start);
+        Ok(Some(uploaded))
+    }
+
+    /// Loads the proof uploaded by the prover run and removes all the uploads of the block,
+    /// since the proofs uploaded by the other prover runs are not needed anymore.
+    pub async fn finish_proof_upload(
+        &mut self,
+        block: BlockNumber,
+        prover_run_id: i32,
+    ) -> QueryResult<Vec<u8>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let chunks = sqlx::query!(
+            "SELECT data FROM proof_upload_chunks
+            WHERE block_number = $1 AND prover_run_id = $2
+            ORDER BY chunk_offset",
+            i64::from(*block),
+            prover_run_id,
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM proof_uploads WHERE block_number = $1",
+            i64::from(*block),
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+        report_query!("sql.prover.finish_proof_upload", start);
+        Ok(chunks.into_iter().flat_map(|chunk| chunk.data).collect())
+    }
+}
+
+/// Kind of the block data that can be moved to the cold storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColdStorageBlob {
+    Proof,
+    Witness,
+}
+
+impl ColdStorageBlob {
+    /// Returns the name of the blob kind as it's stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColdStorageBlob::Proof => "proof",
+            ColdStorageBlob::Witness => "witness",
+        }
+    }
+}
+
+fn commitment_bytes(commitment: U256) -> Vec<u8> {
+    let mut bytes = vec![0u8; 32];
+    commitment.to_big_endian(&mut bytes);
+    bytes
 }
@@ -46,4 +46,20 @@ pub struct IntegerNumber {
 pub struct StorageBlockWitness {
     pub block: i64,
     pub witness: String,
+    pub commitment: Option<Vec<u8>>,
+}
+
+/// Debugging artifacts of the block the prover failed to prove.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, PartialEq)]
+pub struct WitnessDebugBundle {
+    pub block_number: i64,
+    /// Error reported by the prover.
+    pub failure: String,
+    pub reported_at: DateTime<Utc>,
+    /// Path of the first unsatisfied constraint of the circuit, if any.
+    pub unsatisfied_constraint: Option<String>,
+    pub num_constraints: Option<i64>,
+    /// Circuit inputs of the block, `None` until the bundle is built by the witness generator.
+    pub bundle: Option<serde_json::Value>,
+    pub created_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,123 @@
+// Built-in deps
+use std::time::Instant;
+// External imports
+// Workspace imports
+use zksync_types::{Address, ZkSyncTx};
+// Local imports
+use self::records::StoredRejectedTx;
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Time the rejected transactions are kept for.
+const REJECTED_TXS_RETENTION_DAYS: i64 = 7;
+/// Maximum amount of the rejected transactions kept, the oldest ones are pruned first.
+const MAX_REJECTED_TXS: i64 = 1_000_000;
+
+/// Rejected transactions schema keeps the transactions rejected by the API server on submission
+/// along with the reasons, so it's possible to find out why the transaction was never executed.
+#[derive(Debug)]
+pub struct RejectedTxsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> RejectedTxsSchema<'a, 'c> {
+    /// Records the transactions rejected with the same error, e.g. all the transactions of the batch.
+    /// Rejections older than the retention period or exceeding the size cap are removed.
+    pub async fn record(
+        &mut self,
+        txs: &[ZkSyncTx],
+        error_code: u32,
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut tx_hashes = Vec::with_capacity(txs.len());
+        let mut addresses = Vec::with_capacity(txs.len());
+        let mut tx_values = Vec::with_capacity(txs.len());
+        for tx in txs {
+            tx_hashes.push(tx.hash().as_ref().to_vec());
+            addresses.push(tx.account().as_bytes().to_vec());
+            tx_values.push(
+                serde_json::to_value(tx).expect("Unserializable TX provided to the database"),
+            );
+        }
+
+        sqlx::query!(
+            "INSERT INTO rejected_txs (tx_hash, address, tx, error_code, reason)
+            SELECT u.tx_hash, u.address, u.tx, $4, $5
+                FROM UNNEST ($1::bytea[], $2::bytea[], $3::jsonb[])
+                AS u(tx_hash, address, tx)",
+            &tx_hashes,
+            &addresses,
+            &tx_values,
+            error_code as i32,
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        // Old rejections are pruned along the way, and the size of the table is capped
+        // by the identifiers, since they grow monotonically.
+        sqlx::query!(
+            "DELETE FROM rejected_txs
+            WHERE created_at < $1 OR id <= (SELECT MAX(id) FROM rejected_txs) - $2",
+            chrono::Utc::now() - chrono::Duration::days(REJECTED_TXS_RETENTION_DAYS),
+            MAX_REJECTED_TXS
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.rejected_txs.record", start);
+        Ok(())
+    }
+
+    /// Loads up to `limit` rejections of the transaction with the given hash, starting
+    /// with the most recent one and preceding the `before_id` rejection if it's set.
+    pub async fn load_by_hash(
+        &mut self,
+        tx_hash: &[u8],
+        before_id: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredRejectedTx>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            StoredRejectedTx,
+            "SELECT * FROM rejected_txs
+            WHERE tx_hash = $1 AND ($2::bigint IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3",
+            tx_hash,
+            before_id,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.rejected_txs.load_by_hash", start);
+        Ok(txs)
+    }
+
+    /// Loads up to `limit` rejected transactions initiated by the account, starting
+    /// with the most recent one and preceding the `before_id` rejection if it's set.
+    pub async fn load_by_address(
+        &mut self,
+        address: Address,
+        before_id: Option<i64>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredRejectedTx>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            StoredRejectedTx,
+            "SELECT * FROM rejected_txs
+            WHERE address = $1 AND ($2::bigint IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3",
+            address.as_bytes(),
+            before_id,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.rejected_txs.load_by_address", start);
+        Ok(txs)
+    }
+}
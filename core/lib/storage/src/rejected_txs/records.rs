@@ -0,0 +1,18 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Transaction rejected by the API server on submission.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredRejectedTx {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub address: Vec<u8>,
+    pub tx: serde_json::Value,
+    pub error_code: i32,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
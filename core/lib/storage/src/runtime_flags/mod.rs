@@ -0,0 +1,197 @@
+// Built-in deps
+use std::{collections::HashMap, time::Instant};
+// External imports
+use chrono::{DateTime, Utc};
+// Workspace imports
+// Local imports
+use crate::{QueryResult, StorageProcessor};
+
+/// Flag switched by the operator in runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeFlag {
+    /// Fast processing of withdrawals. Once disabled, the API server rejects new fast withdrawals
+    /// and the already accepted ones are processed as the regular withdrawals.
+    FastProcessing,
+    /// Emergency pause. While enabled, the API server rejects new transactions and the block
+    /// proposer stops creating new blocks, the already created ones are still sent to Ethereum.
+    Paused,
+    /// Scheduled maintenance. While enabled, the API server rejects new transactions with
+    /// the maintenance error, so the clients may retry them once the maintenance is over.
+    Maintenance,
+}
+
+impl RuntimeFlag {
+    /// Name of the flag in the database.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FastProcessing => "fast_processing",
+            Self::Paused => "paused",
+            Self::Maintenance => "maintenance",
+        }
+    }
+
+    /// Value of the flag until it's set by the operator.
+    pub fn default_value(self) -> bool {
+        match self {
+            Self::FastProcessing => true,
+            Self::Paused => false,
+            Self::Maintenance => false,
+        }
+    }
+}
+
+/// Value of the flag along with the details provided by the operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeFlagState {
+    pub enabled: bool,
+    /// Reason of the last flag switch.
+    pub reason: Option<String>,
+    /// Time until which the flag is expected to stay switched.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl RuntimeFlagState {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            reason: None,
+            until: None,
+        }
+    }
+}
+
+/// Runtime flags schema stores the flags switched by the operator while the server is running,
+/// so all the server components observe the same values.
+#[derive(Debug)]
+pub struct RuntimeFlagsSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> RuntimeFlagsSchema<'a, 'c> {
+    /// Loads the value of the flag, falling back to the default one if the flag was never set.
+    pub async fn is_enabled(&mut self, flag: RuntimeFlag) -> QueryResult<bool> {
+        let start = Instant::now();
+        let enabled = sqlx::query!(
+            "SELECT enabled FROM runtime_flags WHERE name = $1",
+            flag.name()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.enabled)
+        .unwrap_or_else(|| flag.default_value());
+
+        report_query!("sql.runtime_flags.is_enabled", start);
+        Ok(enabled)
+    }
+
+    /// Loads the reason provided by the operator the last time the flag was switched.
+    pub async fn reason(&mut self, flag: RuntimeFlag) -> QueryResult<Option<String>> {
+        let start = Instant::now();
+        let reason = sqlx::query!(
+            "SELECT reason FROM runtime_flags WHERE name = $1",
+            flag.name()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|record| record.reason);
+
+        report_query!("sql.runtime_flags.reason", start);
+        Ok(reason)
+    }
+
+    /// Loads the value of the flag along with the details provided by the operator.
+    pub async fn load_state(&mut self, flag: RuntimeFlag) -> QueryResult<RuntimeFlagState> {
+        let start = Instant::now();
+        let state = sqlx::query!(
+            "SELECT enabled, reason, until FROM runtime_flags WHERE name = $1",
+            flag.name()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| RuntimeFlagState {
+            enabled: record.enabled,
+            reason: record.reason,
+            until: record.until,
+        })
+        .unwrap_or_else(|| RuntimeFlagState::new(flag.default_value()));
+
+        report_query!("sql.runtime_flags.load_state", start);
+        Ok(state)
+    }
+
+    /// Loads the states of the given flags at once, in the same order as the flags.
+    pub async fn load_states(
+        &mut self,
+        flags: &[RuntimeFlag],
+    ) -> QueryResult<Vec<RuntimeFlagState>> {
+        let start = Instant::now();
+        let names: Vec<_> = flags.iter().map(|flag| flag.name().to_string()).collect();
+        let mut stored: HashMap<_, _> = sqlx::query!(
+            "SELECT name, enabled, reason, until FROM runtime_flags WHERE name = ANY($1)",
+            &names
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            let state = RuntimeFlagState {
+                enabled: record.enabled,
+                reason: record.reason,
+                until: record.until,
+            };
+            (record.name, state)
+        })
+        .collect();
+        let states = flags
+            .iter()
+            .map(|flag| {
+                stored
+                    .remove(flag.name())
+                    .unwrap_or_else(|| RuntimeFlagState::new(flag.default_value()))
+            })
+            .collect();
+
+        report_query!("sql.runtime_flags.load_states", start);
+        Ok(states)
+    }
+
+    /// Sets the value of the flag.
+    pub async fn set_enabled(&mut self, flag: RuntimeFlag, enabled: bool) -> QueryResult<()> {
+        self.set_enabled_with_reason(flag, enabled, None).await
+    }
+
+    /// Sets the value of the flag along with the reason of the switch.
+    pub async fn set_enabled_with_reason(
+        &mut self,
+        flag: RuntimeFlag,
+        enabled: bool,
+        reason: Option<&str>,
+    ) -> QueryResult<()> {
+        let state = RuntimeFlagState {
+            enabled,
+            reason: reason.map(String::from),
+            until: None,
+        };
+        self.set_state(flag, &state).await
+    }
+
+    /// Sets the value of the flag along with the details of the switch.
+    pub async fn set_state(
+        &mut self,
+        flag: RuntimeFlag,
+        state: &RuntimeFlagState,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO runtime_flags (name, enabled, reason, until) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (name) DO UPDATE SET enabled = $2, reason = $3, until = $4, updated_at = now()",
+            flag.name(),
+            state.enabled,
+            state.reason,
+            state.until
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.runtime_flags.set_state", start);
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+//! Tracing of the slow storage queries.
+//!
+//! Aggregated latencies are reported per storage method to the metrics, but it's not enough
+//! to find out which code path causes the database to slow down. Methods exceeding the
+//! `DB_SLOW_QUERY_THRESHOLD` are additionally counted per method and reported to the log.
+//! Individual slow statements are logged by `sqlx` with the same threshold (see `ConnectionPool`).
+//!
+//! Query parameters may contain user data, so they are never logged: methods are reported by
+//! their names only, and statements are logged with the parameter placeholders.
+
+// Built-in deps
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Slow query threshold of the pools created without the database config (e.g. by the tools).
+pub(crate) const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Execution time after which the storage method is considered slow, in milliseconds.
+/// Set by the dedicated connection pools from the `DB_SLOW_QUERY_THRESHOLD` of the database config.
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 =
+    AtomicU64::new(DEFAULT_SLOW_QUERY_THRESHOLD.as_millis() as u64);
+
+/// Sets the threshold the storage methods are reported as slow after.
+pub(crate) fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_THRESHOLD_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Reports the storage method execution if it took longer than the slow query threshold.
+pub(crate) fn trace_slow_query(method: &'static str, elapsed: Duration) {
+    if elapsed < Duration::from_millis(SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed)) {
+        return;
+    }
+
+    metrics::counter!("sql.slow_queries", 1, "method" => method);
+    vlog::warn!(
+        method,
+        elapsed_ms = elapsed.as_millis() as u64,
+        "Slow storage query"
+    );
+}
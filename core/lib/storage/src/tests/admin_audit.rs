@@ -0,0 +1,33 @@
+// External imports
+use serde_json::json;
+// Local imports
+use crate::{admin_audit::AdminAuditSchema, tests::db_test, QueryResult, StorageProcessor};
+
+/// Checks that the recorded actions are loaded starting with the most recent one.
+#[db_test]
+async fn admin_audit_log(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(AdminAuditSchema(&mut storage)
+        .load_latest(10)
+        .await?
+        .is_empty());
+
+    let first = AdminAuditSchema(&mut storage)
+        .record("evict_txs", "operator", json!({ "evicted": [] }))
+        .await?;
+    let second = AdminAuditSchema(&mut storage)
+        .record("evict_txs", "operator", json!({ "evicted": ["0x01"] }))
+        .await?;
+
+    let entries = AdminAuditSchema(&mut storage).load_latest(10).await?;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, second);
+    assert_eq!(entries[0].details, json!({ "evicted": ["0x01"] }));
+    assert_eq!(entries[1].id, first);
+    assert_eq!(entries[1].actor, "operator");
+
+    let entries = AdminAuditSchema(&mut storage).load_latest(1).await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, second);
+
+    Ok(())
+}
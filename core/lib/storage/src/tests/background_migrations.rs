@@ -0,0 +1,166 @@
+// Workspace imports
+use zksync_types::{Action, BlockNumber, U256};
+// Local imports
+use crate::{
+    background_migrations::{background_migrations, BackfillBatch, BackgroundMigrationsSchema},
+    test_data::gen_operation,
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+/// Checks that the progress of the background migration is accumulated between the batches
+/// and the migration is reported as finished only once it's marked so.
+#[db_test]
+async fn background_migration_progress(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let name = "test_migration";
+    assert_eq!(
+        BackgroundMigrationsSchema(&mut storage)
+            .load_progress(name)
+            .await?,
+        None
+    );
+    assert!(
+        !BackgroundMigrationsSchema(&mut storage)
+            .is_finished(name)
+            .await?
+    );
+
+    BackgroundMigrationsSchema(&mut storage)
+        .save_progress(
+            name,
+            BackfillBatch {
+                last_key: 100,
+                rows: 50,
+            },
+        )
+        .await?;
+    BackgroundMigrationsSchema(&mut storage)
+        .save_progress(
+            name,
+            BackfillBatch {
+                last_key: 250,
+                rows: 70,
+            },
+        )
+        .await?;
+
+    let progress = BackgroundMigrationsSchema(&mut storage)
+        .load_progress(name)
+        .await?
+        .expect("Progress is not stored");
+    assert_eq!(progress.last_processed_key, Some(250));
+    assert_eq!(progress.processed_rows, 120);
+    assert!(!progress.is_finished());
+
+    BackgroundMigrationsSchema(&mut storage)
+        .mark_finished(name)
+        .await?;
+    assert!(
+        BackgroundMigrationsSchema(&mut storage)
+            .is_finished(name)
+            .await?
+    );
+
+    let all_progress = BackgroundMigrationsSchema(&mut storage)
+        .load_all_progress()
+        .await?;
+    assert_eq!(all_progress.len(), 1);
+    assert_eq!(all_progress[0].last_processed_key, Some(250));
+    assert_eq!(all_progress[0].processed_rows, 120);
+
+    Ok(())
+}
+
+/// Checks that the commitments of the witnesses stored before the commitment column was added
+/// are filled from the witnesses in batches, and the migration is finished once all of them are processed.
+#[db_test]
+async fn witness_commitments_migration(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const BLOCKS: u32 = 5;
+    for block in 1..=BLOCKS {
+        storage
+            .chain()
+            .block_schema()
+            .execute_operation(gen_operation(BlockNumber(block), Action::Commit, 100))
+            .await?;
+        let witness = serde_json::json!({
+            "public_data_commitment": format!("{:064x}", block),
+        });
+        storage
+            .prover_schema()
+            .store_witness(BlockNumber(block), U256::from(block), witness)
+            .await?;
+    }
+    // Witnesses stored by the previous releases have no commitment.
+    sqlx::query("UPDATE block_witness SET commitment = NULL WHERE block < 4")
+        .execute(storage.conn())
+        .await?;
+
+    let migrations = background_migrations();
+    let migration = migrations
+        .iter()
+        .find(|migration| migration.name() == "witness_commitments")
+        .expect("Migration is not registered");
+
+    let batch = BackgroundMigrationsSchema(&mut storage)
+        .process_batch(migration.as_ref(), 2)
+        .await?;
+    assert_eq!(
+        batch,
+        Some(BackfillBatch {
+            last_key: 2,
+            rows: 2
+        })
+    );
+    // Witnesses which already have the commitment are left as is.
+    let batch = BackgroundMigrationsSchema(&mut storage)
+        .process_batch(migration.as_ref(), 2)
+        .await?;
+    assert_eq!(
+        batch,
+        Some(BackfillBatch {
+            last_key: 4,
+            rows: 1
+        })
+    );
+    let batch = BackgroundMigrationsSchema(&mut storage)
+        .process_batch(migration.as_ref(), 2)
+        .await?;
+    assert_eq!(
+        batch,
+        Some(BackfillBatch {
+            last_key: 5,
+            rows: 0
+        })
+    );
+    let batch = BackgroundMigrationsSchema(&mut storage)
+        .process_batch(migration.as_ref(), 2)
+        .await?;
+    assert_eq!(batch, None);
+
+    let progress = BackgroundMigrationsSchema(&mut storage)
+        .load_progress(migration.name())
+        .await?
+        .expect("Progress is not stored");
+    assert_eq!(progress.last_processed_key, Some(5));
+    assert_eq!(progress.processed_rows, 3);
+    assert!(progress.is_finished());
+
+    let commitments: Vec<(i64, Option<Vec<u8>>)> =
+        sqlx::query_as("SELECT block, commitment FROM block_witness ORDER BY block")
+            .fetch_all(storage.conn())
+            .await?;
+    assert_eq!(commitments.len(), BLOCKS as usize);
+    for (block, commitment) in commitments {
+        let mut expected = vec![0u8; 32];
+        U256::from(block).to_big_endian(&mut expected);
+        assert_eq!(commitment, Some(expected), "Block {}", block);
+    }
+
+    // Finished migration is not run again.
+    let batch = BackgroundMigrationsSchema(&mut storage)
+        .process_batch(migration.as_ref(), 2)
+        .await?;
+    assert_eq!(batch, None);
+
+    Ok(())
+}
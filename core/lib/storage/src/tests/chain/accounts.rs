@@ -1,12 +1,18 @@
+// Built-in imports
+use std::collections::BTreeMap;
 // External imports
+use num::{BigInt, BigUint, ToPrimitive, Zero};
+use sqlx::types::BigDecimal;
 // Workspace imports
-use zksync_types::{AccountMap, Action, BlockNumber};
+use zksync_types::{
+    AccountId, AccountMap, Action, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+};
 // Local imports
 use super::block::apply_random_updates;
 use crate::tests::{create_rng, db_test};
 use crate::{chain::state::StateSchema, test_data::gen_operation};
 use crate::{
-    chain::{account::AccountSchema, block::BlockSchema},
+    chain::{account::AccountSchema, block::BlockSchema, stats::StatsSchema},
     prover::ProverSchema,
     QueryResult, StorageProcessor,
 };
@@ -128,3 +134,173 @@ async fn stored_accounts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that the token balances snapshot is restored correctly for the past blocks
+/// and can be loaded page by page.
+#[db_test]
+async fn balances_snapshot(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    /// Builds the expected snapshot of the `ETH` balances from the account map.
+    fn expected_snapshot(accounts: &AccountMap) -> Vec<(i64, Vec<u8>, BigDecimal)> {
+        let mut snapshot: Vec<_> = accounts
+            .iter()
+            .filter(|(_, account)| account.get_balance(TokenId(0)) > 0u32.into())
+            .map(|(id, account)| {
+                (
+                    i64::from(**id),
+                    account.address.as_bytes().to_vec(),
+                    BigDecimal::from(BigInt::from(account.get_balance(TokenId(0)))),
+                )
+            })
+            .collect();
+        snapshot.sort_by_key(|(id, _, _)| *id);
+        snapshot
+    }
+
+    let mut rng = create_rng();
+
+    // Create accounts in two blocks, so the snapshot of the first one doesn't
+    // contain the accounts of the second one.
+    let (accounts_block_1, updates) = apply_random_updates(AccountMap::default(), &mut rng);
+    BlockSchema(&mut storage)
+        .execute_operation(gen_operation(BlockNumber(1), Action::Commit, 100))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let (accounts_block_2, updates) = apply_random_updates(accounts_block_1.clone(), &mut rng);
+    BlockSchema(&mut storage)
+        .execute_operation(gen_operation(BlockNumber(2), Action::Commit, 100))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates, 0)
+        .await?;
+
+    for (block, accounts) in &[
+        (BlockNumber(1), accounts_block_1),
+        (BlockNumber(2), accounts_block_2),
+    ] {
+        let snapshot = AccountSchema(&mut storage)
+            .load_balances_snapshot(TokenId(0), *block, None, 100)
+            .await?
+            .into_iter()
+            .map(|entry| (entry.account_id, entry.address, entry.balance))
+            .collect::<Vec<_>>();
+        assert_eq!(snapshot, expected_snapshot(accounts));
+    }
+
+    // Load the snapshot page by page.
+    let mut after = None;
+    let mut snapshot = Vec::new();
+    loop {
+        let page = AccountSchema(&mut storage)
+            .load_balances_snapshot(TokenId(0), BlockNumber(2), after, 2)
+            .await?;
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.len() <= 2);
+
+        after = page
+            .last()
+            .map(|entry| AccountId(entry.account_id.to_u32().unwrap()));
+        snapshot.extend(page);
+    }
+    let full_snapshot = AccountSchema(&mut storage)
+        .load_balances_snapshot(TokenId(0), BlockNumber(2), None, 100)
+        .await?;
+    assert_eq!(snapshot, full_snapshot);
+
+    Ok(())
+}
+
+/// Checks that the total value locked is aggregated from the verified balances.
+#[db_test]
+async fn aggregate_tvl(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let (accounts, updates) = apply_random_updates(AccountMap::default(), &mut rng);
+    BlockSchema(&mut storage)
+        .execute_operation(gen_operation(BlockNumber(1), Action::Commit, 100))
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    // Committed balances are not counted.
+    StatsSchema(&mut storage).aggregate_tvl().await?;
+    assert!(StatsSchema(&mut storage).load_tvl().await?.is_empty());
+
+    StateSchema(&mut storage)
+        .apply_state_update(BlockNumber(1))
+        .await?;
+    StatsSchema(&mut storage).aggregate_tvl().await?;
+
+    let mut expected_tvl = BTreeMap::new();
+    for account in accounts.values() {
+        for (token_id, balance) in account.get_nonzero_balances() {
+            *expected_tvl
+                .entry(i32::from(*token_id))
+                .or_insert_with(BigUint::zero) += balance.0;
+        }
+    }
+    let expected_tvl: BTreeMap<_, _> = expected_tvl
+        .into_iter()
+        .map(|(token_id, amount)| (token_id, BigDecimal::from(BigInt::from(amount))))
+        .collect();
+
+    let tvl: BTreeMap<_, _> = StatsSchema(&mut storage)
+        .load_tvl()
+        .await?
+        .into_iter()
+        .filter(|tvl| !tvl.amount.is_zero())
+        .map(|tvl| (tvl.token_id, tvl.amount))
+        .collect();
+    assert_eq!(tvl, expected_tvl);
+
+    Ok(())
+}
+
+/// Checks that the onchain authorization facts are stored and can't be overwritten.
+#[db_test]
+async fn onchain_auth_facts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x11);
+    let pub_key_hash = PubKeyHash::from_bytes(&[0xAA; 20])?;
+
+    assert_eq!(
+        AccountSchema(&mut storage)
+            .load_auth_fact(address, Nonce(0))
+            .await?,
+        None
+    );
+
+    AccountSchema(&mut storage)
+        .store_auth_fact(address, Nonce(0), &pub_key_hash)
+        .await?;
+    // Repeated fact for the same nonce is ignored.
+    AccountSchema(&mut storage)
+        .store_auth_fact(address, Nonce(0), &PubKeyHash::zero())
+        .await?;
+
+    assert_eq!(
+        AccountSchema(&mut storage)
+            .load_auth_fact(address, Nonce(0))
+            .await?,
+        Some(pub_key_hash)
+    );
+    // Facts are bound to both the address and the nonce.
+    assert_eq!(
+        AccountSchema(&mut storage)
+            .load_auth_fact(address, Nonce(1))
+            .await?,
+        None
+    );
+    assert_eq!(
+        AccountSchema(&mut storage)
+            .load_auth_fact(Address::repeat_byte(0x22), Nonce(0))
+            .await?,
+        None
+    );
+
+    Ok(())
+}
@@ -4,7 +4,7 @@
 use zksync_crypto::{convert::FeConvert, rand::XorShiftRng};
 use zksync_types::{
     ethereum::OperationType, helpers::apply_updates, AccountId, AccountMap, AccountUpdate,
-    AccountUpdates, Action, ActionType, BlockNumber, TokenId,
+    AccountUpdates, Action, ActionType, BlockNumber, TokenId, U256,
 };
 // Local imports
 use crate::test_data::{gen_operation, gen_operation_with_txs};
@@ -266,7 +266,7 @@ async fn find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) -> Quer
             .add_hash_entry(response.id, &eth_tx_hash)
             .await?;
         EthereumSchema(&mut storage)
-            .confirm_eth_tx(&eth_tx_hash)
+            .confirm_eth_tx(&eth_tx_hash, None)
             .await?;
 
         // Initialize reference sample fields.
@@ -308,7 +308,7 @@ async fn find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) -> Quer
                     .add_hash_entry(response.id, &eth_tx_hash)
                     .await?;
                 EthereumSchema(&mut storage)
-                    .confirm_eth_tx(&eth_tx_hash)
+                    .confirm_eth_tx(&eth_tx_hash, None)
                     .await?;
                 current_block_detail.verify_tx_hash = Some(eth_tx_hash.as_ref().to_vec());
             }
@@ -415,7 +415,7 @@ async fn block_range(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
             .add_hash_entry(response.id, &eth_tx_hash)
             .await?;
         EthereumSchema(&mut storage)
-            .confirm_eth_tx(&eth_tx_hash)
+            .confirm_eth_tx(&eth_tx_hash, None)
             .await?;
 
         // Add verification for the block if required.
@@ -447,7 +447,7 @@ async fn block_range(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
                 .add_hash_entry(response.id, &eth_tx_hash)
                 .await?;
             EthereumSchema(&mut storage)
-                .confirm_eth_tx(&eth_tx_hash)
+                .confirm_eth_tx(&eth_tx_hash, None)
                 .await?;
         }
     }
@@ -525,7 +525,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
 
         if *block_number <= n_commited_confirmed {
             EthereumSchema(&mut storage)
-                .confirm_eth_tx(&eth_tx_hash)
+                .confirm_eth_tx(&eth_tx_hash, None)
                 .await?;
         }
 
@@ -558,7 +558,7 @@ async fn unconfirmed_transaction(mut storage: StorageProcessor<'_>) -> QueryResu
                 .add_hash_entry(response.id, &eth_tx_hash)
                 .await?;
             EthereumSchema(&mut storage)
-                .confirm_eth_tx(&eth_tx_hash)
+                .confirm_eth_tx(&eth_tx_hash, Some(150_000.into()))
                 .await?;
         }
     }
@@ -624,6 +624,7 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
             success: true,
             op: Some(change_pubkey_op),
             fail_reason: None,
+            fail_code: None,
             block_index: None,
             created_at: chrono::Utc::now(),
             batch_id: None,
@@ -658,6 +659,7 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
             success: true,
             op: Some(transfer_to_new_op),
             fail_reason: None,
+            fail_code: None,
             block_index: None,
             created_at: chrono::Utc::now(),
             batch_id: None,
@@ -821,7 +823,7 @@ async fn test_unproven_block_query(mut storage: StorageProcessor<'_>) -> QueryRe
         ))
         .await?;
     ProverSchema(&mut storage)
-        .store_witness(BlockNumber(1), serde_json::json!(null))
+        .store_witness(BlockNumber(1), U256::from(1), serde_json::json!(null))
         .await?;
     assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 1);
     BlockSchema(&mut storage)
@@ -832,7 +834,7 @@ async fn test_unproven_block_query(mut storage: StorageProcessor<'_>) -> QueryRe
         ))
         .await?;
     ProverSchema(&mut storage)
-        .store_witness(BlockNumber(2), serde_json::json!(null))
+        .store_witness(BlockNumber(2), U256::from(2), serde_json::json!(null))
         .await?;
     assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 2);
     BlockSchema(&mut storage)
@@ -843,7 +845,7 @@ async fn test_unproven_block_query(mut storage: StorageProcessor<'_>) -> QueryRe
         ))
         .await?;
     ProverSchema(&mut storage)
-        .store_witness(BlockNumber(3), serde_json::json!(null))
+        .store_witness(BlockNumber(3), U256::from(3), serde_json::json!(null))
         .await?;
     assert_eq!(ProverSchema(&mut storage).pending_jobs_count().await?, 3);
 
@@ -990,3 +992,77 @@ async fn test_operations_counter(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the block stages are loaded together with the Ethereum operations:
+///
+/// - Confirmed commit stage has the final hash and confirmation time.
+/// - Verify stage without an Ethereum operation has only the creation time.
+#[db_test]
+async fn block_stages(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Required since we use `EthereumSchema` in this test.
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let block_number = BlockNumber(1);
+    assert!(BlockSchema(&mut storage)
+        .load_block_stages(block_number)
+        .await?
+        .is_empty());
+
+    let operation = BlockSchema(&mut storage)
+        .execute_operation(gen_unique_operation(
+            block_number,
+            Action::Commit,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let ethereum_op_id = operation.id.unwrap() as i64;
+    let eth_tx_hash = dummy_ethereum_tx_hash(ethereum_op_id);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            OperationType::Commit,
+            Some(ethereum_op_id),
+            100,
+            100u32.into(),
+            Default::default(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &eth_tx_hash)
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&eth_tx_hash, None)
+        .await?;
+
+    ProverSchema(&mut storage)
+        .store_proof(block_number, &Default::default())
+        .await?;
+    BlockSchema(&mut storage)
+        .execute_operation(gen_unique_operation(
+            block_number,
+            Action::Verify {
+                proof: Default::default(),
+            },
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+
+    let stages = BlockSchema(&mut storage)
+        .load_block_stages(block_number)
+        .await?;
+    assert_eq!(stages.len(), 2);
+
+    let commit = &stages[0];
+    assert_eq!(commit.action_type, StorageActionType::COMMIT);
+    assert_eq!(commit.eth_op_id, Some(response.id));
+    assert_eq!(commit.final_hash, Some(eth_tx_hash.as_bytes().to_vec()));
+    assert!(commit.confirmed_at.is_some());
+    assert_eq!(commit.gas_used, Some(150_000));
+
+    let verify = &stages[1];
+    assert_eq!(verify.action_type, StorageActionType::VERIFY);
+    assert_eq!(verify.eth_op_id, None);
+    assert_eq!(verify.final_hash, None);
+    assert_eq!(verify.confirmed_at, None);
+
+    Ok(())
+}
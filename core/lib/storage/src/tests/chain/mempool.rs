@@ -3,8 +3,8 @@ use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 // Workspace imports
 use zksync_types::{
     mempool::SignedTxVariant,
-    tx::{ChangePubKey, Transfer, Withdraw},
-    AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
+    tx::{ChangePubKey, Transfer, TxHash, Withdraw},
+    AccountId, Address, BlockNumber, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx,
 };
 // Local imports
 use crate::test_data::gen_eth_sign_data;
@@ -150,6 +150,35 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the transactions are loaded by the addresses of their senders and recipients.
+#[db_test]
+async fn load_address_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = franklin_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let (transfer, change_pubkey) = match (&txs[0].tx, &txs[3].tx) {
+        (ZkSyncTx::Transfer(transfer), ZkSyncTx::ChangePubKey(change_pubkey)) => {
+            (transfer, change_pubkey)
+        }
+        _ => unreachable!("Unexpected transaction types"),
+    };
+
+    let loaded = MempoolSchema(&mut storage)
+        .load_address_txs(&[transfer.to, change_pubkey.account])
+        .await?;
+    let hashes: Vec<_> = loaded.iter().map(|tx| tx.hash()).collect();
+    assert_eq!(hashes, vec![txs[0].hash(), txs[3].hash()]);
+
+    let loaded = MempoolSchema(&mut storage)
+        .load_address_txs(&[Address::random()])
+        .await?;
+    assert!(loaded.is_empty());
+
+    Ok(())
+}
+
 /// Checks the save&load routine for mempool schema.
 #[db_test]
 async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -205,6 +234,43 @@ async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the batches can be found by their hashes.
+#[db_test]
+async fn get_batch_info(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let batch_id = MempoolSchema(&mut storage).insert_batch(&txs, None).await?;
+
+    let tx_hashes: Vec<TxHash> = txs.iter().map(|tx| tx.hash()).collect();
+    let batch_hash = TxHash::batch_hash(&tx_hashes);
+    let batch_info = MempoolSchema(&mut storage)
+        .get_batch_info(batch_hash)
+        .await?
+        .expect("batch should be stored");
+    assert_eq!(batch_info.batch_id, batch_id);
+    assert_eq!(batch_info.batch_hash, batch_hash.as_ref().to_vec());
+    assert_eq!(
+        batch_info.tx_hashes,
+        tx_hashes
+            .iter()
+            .map(|tx_hash| tx_hash.as_ref().to_vec())
+            .collect::<Vec<_>>()
+    );
+
+    // Batch is kept after its transactions leave the mempool.
+    MempoolSchema(&mut storage).remove_txs(&tx_hashes).await?;
+    assert!(MempoolSchema(&mut storage)
+        .get_batch_info(batch_hash)
+        .await?
+        .is_some());
+    // Hash of a single transaction doesn't identify the batch.
+    assert!(MempoolSchema(&mut storage)
+        .get_batch_info(tx_hashes[0])
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
 /// Checks that removed txs won't appear on the next load.
 #[db_test]
 async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -266,6 +332,7 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        fail_code: None,
     };
     OperationsSchema(&mut storage)
         .store_executed_tx(executed_tx)
@@ -287,6 +354,84 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that transactions executed after the given block are returned to the mempool schema,
+/// except for the failed ones and the ones that are already in the mempool.
+/// Records of the failed transactions are kept.
+#[db_test]
+async fn return_executed_txs_to_mempool(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(6);
+
+    // Store transactions as executed in blocks 1 and 2.
+    for (idx, tx) in txs.iter().enumerate() {
+        let executed_tx = NewExecutedTransaction {
+            block_number: if idx < 2 { 1 } else { 2 },
+            tx_hash: tx.hash().as_ref().to_vec(),
+            tx: serde_json::to_value(&tx.tx).unwrap(),
+            operation: Default::default(),
+            from_account: Default::default(),
+            to_account: None,
+            success: idx != 4,
+            fail_reason: if idx != 4 {
+                None
+            } else {
+                Some("Not enough balance".to_string())
+            },
+            block_index: Some(idx as i32),
+            primary_account_address: Default::default(),
+            nonce: Default::default(),
+            created_at: chrono::Utc::now(),
+            eth_sign_data: tx
+                .eth_sign_data
+                .as_ref()
+                .map(|sign_data| serde_json::to_value(sign_data).unwrap()),
+            batch_id: None,
+            fail_code: None,
+        };
+        OperationsSchema(&mut storage)
+            .store_executed_tx(executed_tx)
+            .await?;
+    }
+
+    // The last transaction is resent by the user after being executed.
+    MempoolSchema(&mut storage).insert_tx(&txs[5]).await?;
+
+    // Revert the second block: its successful transactions should be returned to the mempool
+    // along with the Ethereum signature data.
+    let returned = MempoolSchema(&mut storage)
+        .return_executed_txs_to_mempool(BlockNumber(1))
+        .await?;
+    assert_eq!(returned, 2);
+
+    let txs_from_db = MempoolSchema(&mut storage).load_txs().await?;
+    assert_eq!(txs_from_db.len(), 3);
+    let expected_txs = [&txs[2], &txs[3], &txs[5]];
+    for (expected_tx, tx_from_db) in expected_txs.iter().zip(txs_from_db) {
+        let tx_from_db = unwrap_tx(tx_from_db);
+        assert_eq!(expected_tx.hash(), tx_from_db.hash());
+        assert_eq!(expected_tx.eth_sign_data, tx_from_db.eth_sign_data);
+    }
+
+    // Returned transactions should not be considered executed anymore.
+    for tx in [&txs[2], &txs[3], &txs[5]].iter() {
+        assert!(OperationsSchema(&mut storage)
+            .get_executed_operation(tx.hash().as_ref())
+            .await?
+            .is_none());
+    }
+    // The failed transaction is still reported as failed.
+    let failed_tx = OperationsSchema(&mut storage)
+        .get_executed_operation(txs[4].hash().as_ref())
+        .await?
+        .expect("Failed transaction should be kept");
+    assert!(!failed_tx.success);
+    assert!(OperationsSchema(&mut storage)
+        .get_executed_operation(txs[0].hash().as_ref())
+        .await?
+        .is_some());
+
+    Ok(())
+}
+
 /// Checks that memory pool contains previously inserted transaction.
 #[db_test]
 async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -316,9 +461,18 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
     }
 
     // Make sure that the memory pool now responds that these transactions exist.
+    let mut last_id = 0;
     for tx in &txs {
         let tx_hash = tx.hash();
 
+        // Entries are ordered as the transactions were inserted.
+        let entry = MempoolSchema(&mut storage)
+            .get_mempool_tx(tx_hash)
+            .await?
+            .unwrap();
+        assert!(entry.id > last_id);
+        last_id = entry.id;
+
         assert_eq!(
             MempoolSchema(&mut storage).contains_tx(tx_hash).await?,
             true
@@ -336,3 +490,32 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+/// Checks that the mempool transactions are counted by their types.
+#[db_test]
+async fn count_txs_by_type(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(storage
+        .chain()
+        .stats_schema()
+        .count_mempool_txs_by_type()
+        .await?
+        .is_empty());
+
+    for tx in &franklin_txs() {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    assert_eq!(
+        storage
+            .chain()
+            .stats_schema()
+            .count_mempool_txs_by_type()
+            .await?,
+        vec![
+            ("ChangePubKey".to_string(), 1),
+            ("Transfer".to_string(), 2),
+            ("Withdraw".to_string(), 1),
+        ]
+    );
+
+    Ok(())
+}
@@ -59,6 +59,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: Some(10),
+        fail_code: None,
     };
 
     OperationsSchema(&mut storage)
@@ -78,6 +79,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
     assert_eq!(stored_operation.to_account, executed_tx.to_account);
     assert_eq!(stored_operation.success, executed_tx.success);
     assert_eq!(stored_operation.fail_reason, executed_tx.fail_reason);
+    assert_eq!(stored_operation.fail_code, executed_tx.fail_code);
     assert_eq!(stored_operation.block_index, executed_tx.block_index);
     assert_eq!(stored_operation.nonce, executed_tx.nonce);
     assert_eq!(
@@ -92,6 +94,13 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
 /// Checks the save&load routine for executed priority operations.
 #[db_test]
 async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(
+        OperationsSchema(&mut storage)
+            .next_priority_op_serial_id()
+            .await?,
+        0
+    );
+
     let executed_tx = NewExecutedPriorityOperation {
         block_number: 1,
         block_index: 1,
@@ -124,6 +133,12 @@ async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> Quer
     );
     assert_eq!(stored_operation.deadline_block, executed_tx.deadline_block);
     assert_eq!(stored_operation.eth_hash, executed_tx.eth_hash);
+    assert_eq!(
+        OperationsSchema(&mut storage)
+            .next_priority_op_serial_id()
+            .await?,
+        1
+    );
 
     Ok(())
 }
@@ -148,6 +163,7 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        fail_code: None,
     };
 
     let executed_priority_op = NewExecutedPriorityOperation {
@@ -194,6 +210,12 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
 
     assert_eq!(block_txs.len(), 2);
 
+    // The same transactions are loaded for several blocks at once.
+    let blocks_txs = BlockSchema(&mut storage)
+        .get_blocks_transactions(&[BlockNumber(BLOCK_NUMBER as u32), BlockNumber(100)])
+        .await?;
+    assert_eq!(blocks_txs.len(), 2);
+
     Ok(())
 }
 
@@ -217,6 +239,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        fail_code: None,
     };
 
     // Save the failed operation.
@@ -1,15 +1,19 @@
 // Built-in imports
 use std::collections::HashMap;
 // External imports
+use chrono::{DateTime, NaiveDate, Utc};
 // Workspace imports
 // Local imports
-use zksync_types::{ethereum::OperationType, Action, BlockNumber};
+use zksync_types::{
+    block::ExecutedOperations, ethereum::OperationType, tx::TxHash, Action, BlockNumber, Nonce,
+    ZkSyncTx,
+};
 
 use self::setup::TransactionsHistoryTestSetup;
 use crate::{
     chain::operations_ext::{
         records::{AccountOpReceiptResponse, AccountTxReceiptResponse},
-        SearchDirection,
+        SearchDirection, TxDirection, TxHistoryCursor, TxHistoryFilter,
     },
     test_data::{dummy_ethereum_tx_hash, gen_unique_operation, BLOCK_SIZE_CHUNKS},
     tests::db_test,
@@ -60,7 +64,7 @@ async fn confirm_eth_op(
         .await?;
     storage
         .ethereum_schema()
-        .confirm_eth_tx(&eth_tx_hash)
+        .confirm_eth_tx(&eth_tx_hash, None)
         .await?;
 
     Ok(())
@@ -310,6 +314,217 @@ async fn get_account_transactions_history_from(
     Ok(())
 }
 
+/// Checks that the account transactions history can be walked through with the
+/// `get_account_transactions_history_page` method, and that the filters are applied.
+#[db_test]
+async fn get_account_transactions_history_page(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+    setup.add_block(3);
+
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let from = setup.from_zksync_account.address;
+    let to = setup.to_zksync_account.address;
+
+    for (address, limit) in vec![(from, 3), (to, 5)] {
+        let history = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_page(&address, &TxHistoryFilter::default(), None, 100)
+            .await?;
+
+        // The history contains the same transactions as the offset-based one.
+        let mut hashes: Vec<_> = history.iter().map(|item| item.hash.clone()).collect();
+        let mut expected_hashes: Vec<_> = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history(&address, 0, 100)
+            .await?
+            .into_iter()
+            .map(|item| item.hash.unwrap())
+            .collect();
+        hashes.sort();
+        expected_hashes.sort();
+        assert_eq!(hashes, expected_hashes);
+
+        // Walk through the history using the cursors.
+        let mut cursor = None;
+        let mut pages = Vec::new();
+        loop {
+            let page = storage
+                .chain()
+                .operations_ext_schema()
+                .get_account_transactions_history_page(
+                    &address,
+                    &TxHistoryFilter::default(),
+                    cursor.as_ref(),
+                    limit,
+                )
+                .await?;
+            assert!(page.len() <= limit as usize);
+            if page.is_empty() {
+                break;
+            }
+
+            cursor = page.last().map(TxHistoryCursor::from);
+            pages.extend(page);
+        }
+        assert_eq!(pages, history);
+    }
+
+    // Every block contains the same set of transactions, and the rejected
+    // transfer of the second block is reported too.
+    let test_vector = vec![
+        (
+            from,
+            TxHistoryFilter {
+                tx_type: Some("Transfer".to_owned()),
+                ..TxHistoryFilter::default()
+            },
+            6,
+        ),
+        (
+            from,
+            TxHistoryFilter {
+                token: Some(setup.tokens[2].id),
+                ..TxHistoryFilter::default()
+            },
+            6,
+        ),
+        (
+            from,
+            TxHistoryFilter {
+                direction: Some(TxDirection::Incoming),
+                ..TxHistoryFilter::default()
+            },
+            6,
+        ),
+        (
+            to,
+            TxHistoryFilter {
+                direction: Some(TxDirection::Incoming),
+                ..TxHistoryFilter::default()
+            },
+            12,
+        ),
+        (
+            to,
+            TxHistoryFilter {
+                direction: Some(TxDirection::Outgoing),
+                ..TxHistoryFilter::default()
+            },
+            0,
+        ),
+        (
+            to,
+            TxHistoryFilter {
+                tx_type: Some("Deposit".to_owned()),
+                direction: Some(TxDirection::Incoming),
+                ..TxHistoryFilter::default()
+            },
+            3,
+        ),
+    ];
+    for (address, filter, expected_count) in test_vector {
+        let history = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_history_page(&address, &filter, None, 100)
+            .await?;
+        assert_eq!(history.len(), expected_count, "Filter: {:?}", filter);
+    }
+
+    // Time range includes the transactions of the second block only.
+    let history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_page(&from, &TxHistoryFilter::default(), None, 100)
+        .await?;
+    let block_start_time = |block_number| {
+        history
+            .iter()
+            .filter(|item| item.block_number == block_number)
+            .map(|item| item.created_at)
+            .min()
+    };
+    let filter = TxHistoryFilter {
+        from_time: block_start_time(2),
+        to_time: block_start_time(3),
+        ..TxHistoryFilter::default()
+    };
+    let block_history = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_transactions_history_page(&from, &filter, None, 100)
+        .await?;
+    assert_eq!(block_history.len(), 7);
+    assert!(block_history.iter().all(|item| item.block_number == 2));
+
+    Ok(())
+}
+
+/// Checks that the daily statistics are aggregated from the executed transactions.
+#[db_test]
+async fn aggregate_daily_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let day = NaiveDate::from_ymd(2021, 3, 5);
+
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.next_tx_time = DateTime::from_utc(day.and_hms(12, 0, 0), Utc);
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+
+    commit_schema_data(&mut storage, &setup).await?;
+
+    assert_eq!(
+        storage.chain().stats_schema().last_aggregated_day().await?,
+        None
+    );
+    // Aggregation of the same day can be repeated.
+    for _ in 0..2 {
+        storage
+            .chain()
+            .stats_schema()
+            .aggregate_daily_stats(day)
+            .await?;
+    }
+    assert_eq!(
+        storage.chain().stats_schema().last_aggregated_day().await?,
+        Some(day)
+    );
+
+    // All the transactions are sent by the same account, and the rejected one is not counted.
+    let daily_stats = storage.chain().stats_schema().load_daily_stats(day).await?;
+    assert_eq!(daily_stats.len(), 1);
+    assert_eq!(daily_stats[0].day, day);
+    assert_eq!(daily_stats[0].tx_count, 13);
+    assert_eq!(daily_stats[0].active_accounts, 1);
+
+    let daily_fees: Vec<_> = storage
+        .chain()
+        .stats_schema()
+        .load_daily_fees(day)
+        .await?
+        .into_iter()
+        .map(|fees| (fees.day, fees.token_id, fees.tx_count))
+        .collect();
+    assert_eq!(daily_fees, vec![(day, 0, 2), (day, 1, 3), (day, 2, 2)]);
+
+    // Nothing is loaded for the days without the transactions.
+    let next_day = day.succ();
+    assert!(storage
+        .chain()
+        .stats_schema()
+        .load_daily_stats(next_day)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
 /// Checks that all the transaction receipts related to account address can be loaded
 /// with the `get_account_transactions_receipts` method and the result will be
 /// same as expected.
@@ -859,3 +1074,140 @@ async fn get_account_operations_receipts(mut storage: StorageProcessor<'_>) -> Q
 
     Ok(())
 }
+
+/// Checks that the statuses of several transactions can be loaded at once, including
+/// the rejected and unknown ones.
+#[db_test]
+async fn get_txs_statuses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+
+    commit_schema_data(&mut storage, &setup).await?;
+    // Make first block committed and verified
+    update_blocks_status(&mut storage).await?;
+
+    let txs: Vec<_> = setup
+        .blocks
+        .iter()
+        .flat_map(|block| block.block_transactions.iter())
+        .filter_map(|op| match op {
+            ExecutedOperations::Tx(tx) => Some((tx.signed_tx.hash(), tx.success)),
+            _ => None,
+        })
+        .collect();
+    let unknown_hash = TxHash::from_slice(&[0xff; 32]).unwrap();
+
+    // Duplicate hashes are returned once.
+    let mut hashes: Vec<_> = txs.iter().map(|(hash, _)| *hash).collect();
+    hashes.push(txs[0].0);
+    hashes.push(unknown_hash);
+
+    let statuses: HashMap<_, _> = storage
+        .chain()
+        .operations_ext_schema()
+        .get_txs_statuses(&hashes)
+        .await?
+        .into_iter()
+        .map(|status| (TxHash::from_slice(&status.tx_hash).unwrap(), status))
+        .collect();
+    assert_eq!(statuses.len(), txs.len() + 1);
+
+    for (hash, success) in txs {
+        let status = &statuses[&hash];
+        assert_eq!(status.success, Some(success));
+        assert!(!status.pending);
+
+        let in_first_block = status.block_number == Some(1);
+        assert_eq!(status.committed, in_first_block);
+        assert_eq!(status.verified, in_first_block);
+    }
+
+    let unknown = &statuses[&unknown_hash];
+    assert_eq!(unknown.block_number, None);
+    assert_eq!(unknown.success, None);
+    assert!(!unknown.committed && !unknown.verified && !unknown.pending);
+
+    Ok(())
+}
+
+/// Checks that the last executed `ChangePubKey` transaction of the account is loaded.
+#[db_test]
+async fn last_change_pubkey_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let expected_hash = setup.blocks[1]
+        .block_transactions
+        .iter()
+        .find_map(|op| match op {
+            ExecutedOperations::Tx(tx) if matches!(tx.signed_tx.tx, ZkSyncTx::ChangePubKey(_)) => {
+                Some(tx.signed_tx.hash())
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    let tx = storage
+        .chain()
+        .operations_ext_schema()
+        .last_change_pubkey_tx(&setup.from_zksync_account.address)
+        .await?
+        .expect("ChangePubKey transaction should be found");
+    assert_eq!(ZkSyncTx::from(tx.clone()).hash(), expected_hash);
+    assert!(tx.eth_signature.is_some());
+
+    // Account without the `ChangePubKey` transactions.
+    let tx = storage
+        .chain()
+        .operations_ext_schema()
+        .last_change_pubkey_tx(&setup.to_zksync_account.address)
+        .await?;
+    assert!(tx.is_none());
+
+    Ok(())
+}
+
+/// Checks that the nonce of the last executed transaction of the account is loaded.
+#[db_test]
+async fn last_executed_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block_with_rejected_op(2);
+
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let from_address = setup.from_zksync_account.address;
+    let expected_nonce = setup
+        .blocks
+        .iter()
+        .flat_map(|block| block.block_transactions.iter())
+        .filter_map(|op| match op {
+            ExecutedOperations::Tx(tx) if tx.success && tx.signed_tx.account() == from_address => {
+                Some(tx.signed_tx.nonce())
+            }
+            _ => None,
+        })
+        .max();
+    assert!(expected_nonce.is_some());
+
+    let nonce = storage
+        .chain()
+        .operations_ext_schema()
+        .last_executed_nonce(&from_address)
+        .await?;
+    assert_eq!(nonce, expected_nonce);
+
+    // Account without the executed transactions.
+    let nonce: Option<Nonce> = storage
+        .chain()
+        .operations_ext_schema()
+        .last_executed_nonce(&setup.to_zksync_account.address)
+        .await?;
+    assert_eq!(nonce, None);
+
+    Ok(())
+}
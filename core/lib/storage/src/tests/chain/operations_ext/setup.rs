@@ -218,6 +218,7 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(transfer_to_new_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
@@ -249,6 +250,7 @@ impl TransactionsHistoryTestSetup {
             success: block_index.is_some(),
             op: Some(transfer_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
@@ -279,6 +281,7 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(withdraw_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
@@ -298,6 +301,7 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(close_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
@@ -323,6 +327,7 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(change_pubkey_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
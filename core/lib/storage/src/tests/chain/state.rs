@@ -205,3 +205,97 @@ async fn state_diff(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks that all the committed account updates are appended to the journal:
+/// - Commit updates for two blocks.
+/// - Load the whole journal and check that it contains all the updates in the commit order.
+/// - Load the journal starting from the middle and check that the limit is respected.
+#[db_test]
+async fn account_updates_journal(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    let (accounts_block_1, updates_block_1) = apply_random_updates(AccountMap::default(), &mut rng);
+    let (_, updates_block_2) = apply_random_updates(accounts_block_1, &mut rng);
+
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates_block_1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates_block_2, 0)
+        .await?;
+
+    let journal = StateSchema(&mut storage)
+        .load_account_updates_journal(0, u32::MAX)
+        .await?;
+    let expected_updates = updates_block_1
+        .iter()
+        .map(|update| (BlockNumber(1), update))
+        .chain(
+            updates_block_2
+                .iter()
+                .map(|update| (BlockNumber(2), update)),
+        );
+    assert_eq!(journal.len(), updates_block_1.len() + updates_block_2.len());
+    for (entry, (block_number, (account_id, update))) in journal.iter().zip(expected_updates) {
+        assert_eq!(entry.block_number, block_number);
+        assert_eq!(entry.account_id, *account_id);
+        assert_eq!(&entry.update, update);
+    }
+    // Sequence numbers are strictly increasing.
+    for pair in journal.windows(2) {
+        assert!(pair[0].sequence_number < pair[1].sequence_number);
+    }
+
+    // Stream the journal starting from the middle.
+    let from = journal[updates_block_1.len()].sequence_number;
+    let tail = StateSchema(&mut storage)
+        .load_account_updates_journal(from, 2)
+        .await?;
+    assert_eq!(
+        tail.as_slice(),
+        &journal[updates_block_1.len()..updates_block_1.len() + 2]
+    );
+
+    Ok(())
+}
+
+/// Checks that the position of the block in the journal follows its last update.
+#[db_test]
+async fn account_updates_journal_position(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+
+    assert_eq!(
+        StateSchema(&mut storage)
+            .account_updates_journal_position(BlockNumber(1))
+            .await?,
+        0
+    );
+
+    let (accounts_block_1, updates_block_1) = apply_random_updates(AccountMap::default(), &mut rng);
+    let (_, updates_block_2) = apply_random_updates(accounts_block_1, &mut rng);
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(1), &updates_block_1, 0)
+        .await?;
+    StateSchema(&mut storage)
+        .commit_state_update(BlockNumber(2), &updates_block_2, 0)
+        .await?;
+
+    let journal = StateSchema(&mut storage)
+        .load_account_updates_journal(0, u32::MAX)
+        .await?;
+    let position = StateSchema(&mut storage)
+        .account_updates_journal_position(BlockNumber(1))
+        .await?;
+    // Streaming from the position yields exactly the updates of the second block.
+    let tail = StateSchema(&mut storage)
+        .load_account_updates_journal(position, u32::MAX)
+        .await?;
+    assert_eq!(tail.as_slice(), &journal[updates_block_1.len()..]);
+
+    let position = StateSchema(&mut storage)
+        .account_updates_journal_position(BlockNumber(2))
+        .await?;
+    assert_eq!(position, journal.last().unwrap().sequence_number + 1);
+
+    Ok(())
+}
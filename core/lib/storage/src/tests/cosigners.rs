@@ -0,0 +1,63 @@
+// Built-in deps
+use std::collections::HashMap;
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::{cosigners::CosignersSchema, tests::db_test, QueryResult, StorageProcessor};
+
+/// Checks that the co-signer registrations are only saved in the order of their nonces.
+#[db_test]
+async fn cosigner_registrations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let account = Address::random();
+    let cosigner = Address::random();
+
+    assert_eq!(
+        CosignersSchema(&mut storage)
+            .load_cosigners(&[account])
+            .await?,
+        HashMap::new()
+    );
+    // Registrations must start with the first nonce.
+    assert!(
+        !CosignersSchema(&mut storage)
+            .set_cosigner(account, Some(cosigner), 2)
+            .await?
+    );
+    assert!(
+        CosignersSchema(&mut storage)
+            .set_cosigner(account, Some(cosigner), 1)
+            .await?
+    );
+    assert_eq!(
+        CosignersSchema(&mut storage)
+            .load_cosigners(&[account, Address::random()])
+            .await?,
+        vec![(account, cosigner)].into_iter().collect()
+    );
+
+    // Registration can't be replayed.
+    assert!(
+        !CosignersSchema(&mut storage)
+            .set_cosigner(account, Some(Address::random()), 1)
+            .await?
+    );
+    assert!(
+        CosignersSchema(&mut storage)
+            .set_cosigner(account, None, 2)
+            .await?
+    );
+    assert_eq!(
+        CosignersSchema(&mut storage)
+            .load_cosigners(&[account])
+            .await?,
+        HashMap::new()
+    );
+    let registration = CosignersSchema(&mut storage)
+        .load_registration(account)
+        .await?
+        .expect("registration is saved");
+    assert_eq!(registration.registration_nonce, 2);
+
+    Ok(())
+}
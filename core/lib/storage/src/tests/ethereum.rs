@@ -1,11 +1,12 @@
 // Built-in deps
 use std::str::FromStr;
 // External imports
-use zksync_basic_types::{H256, U256};
+use chrono::{TimeZone, Utc};
+use zksync_basic_types::{Address, H256, U256};
 // Workspace imports
 use zksync_crypto::Fr;
 use zksync_types::{
-    ethereum::{ETHOperation, OperationType},
+    ethereum::{ETHOperation, GasAdjusterStats, OperationType},
     Action, Operation,
     {block::Block, AccountId, BlockNumber},
 };
@@ -198,7 +199,7 @@ async fn ethereum_storage(mut storage: StorageProcessor<'_>) -> QueryResult<()>
 
     // Make the transaction as completed.
     EthereumSchema(&mut storage)
-        .confirm_eth_tx(&params_2.hash)
+        .confirm_eth_tx(&params_2.hash, None)
         .await?;
 
     // Now there should be only one unconfirmed operation.
@@ -231,6 +232,48 @@ async fn eth_nonce(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the nonces of the additional operator accounts are tracked independently
+/// from each other and from the main operator account.
+#[db_test]
+async fn eth_account_nonces(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+    let first_account = Address::repeat_byte(1);
+    let second_account = Address::repeat_byte(2);
+
+    // Nonce must be initialized before use.
+    assert!(EthereumSchema(&mut storage)
+        .get_next_account_nonce(first_account)
+        .await
+        .is_err());
+
+    EthereumSchema(&mut storage)
+        .init_account_nonce(first_account, 10)
+        .await?;
+    EthereumSchema(&mut storage)
+        .init_account_nonce(second_account, 0)
+        .await?;
+    // Repeated initialization doesn't affect the tracked nonce.
+    EthereumSchema(&mut storage)
+        .init_account_nonce(first_account, 0)
+        .await?;
+
+    for expected_next_nonce in 10..13 {
+        let actual_next_nonce = EthereumSchema(&mut storage)
+            .get_next_account_nonce(first_account)
+            .await?;
+        assert_eq!(actual_next_nonce, expected_next_nonce);
+    }
+    assert_eq!(
+        EthereumSchema(&mut storage)
+            .get_next_account_nonce(second_account)
+            .await?,
+        0
+    );
+    assert_eq!(EthereumSchema(&mut storage).get_next_nonce().await?, 0);
+
+    Ok(())
+}
+
 /// Here we check `unprocessed` and `unconfirmed` operations getting.
 /// If there is no `ETHOperation` for `Operation`, it must be returend by `load_unprocessed_operations`.
 /// It must **not** be returned by `load_unconfirmed_operations`.
@@ -336,7 +379,7 @@ async fn ethereum_unprocessed(mut storage: StorageProcessor<'_>) -> QueryResult<
 
     // Confirm first tx and check that it isn't returned by `unconfirmed` method anymore.
     EthereumSchema(&mut storage)
-        .confirm_eth_tx(&params.hash)
+        .confirm_eth_tx(&params.hash, None)
         .await?;
 
     let unconfirmed_operations = EthereumSchema(&mut storage)
@@ -369,3 +412,118 @@ async fn ethereum_gas_update(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+/// Checks the store/load routine for the gas adjuster stats.
+#[db_test]
+async fn ethereum_gas_adjuster_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // There are no stats until the gas adjuster stores them.
+    assert!(storage
+        .ethereum_schema()
+        .load_gas_adjuster_stats()
+        .await?
+        .is_none());
+
+    let mut stats = GasAdjusterStats {
+        last_suggested_price: None,
+        samples: vec![10.into(), 20.into()],
+        average_price: None,
+        price_limit: 100.into(),
+        scale_factor: 1.5,
+        updated_at: Utc.timestamp(1_600_000_000, 0),
+    };
+    storage
+        .ethereum_schema()
+        .update_gas_adjuster_stats(&stats)
+        .await?;
+    assert_eq!(
+        storage.ethereum_schema().load_gas_adjuster_stats().await?,
+        Some(stats.clone())
+    );
+
+    // New stats should replace the old ones.
+    stats.last_suggested_price = Some(15.into());
+    stats.average_price = Some(15.into());
+    stats.updated_at = Utc.timestamp(1_600_000_060, 0);
+    storage
+        .ethereum_schema()
+        .update_gas_adjuster_stats(&stats)
+        .await?;
+    assert_eq!(
+        storage.ethereum_schema().load_gas_adjuster_stats().await?,
+        Some(stats)
+    );
+
+    Ok(())
+}
+
+/// Checks that the brief information about the stored Ethereum operations is loaded
+/// in the descending order, and that the `before` parameter is respected.
+#[db_test]
+async fn ethereum_operations_summary(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    // Store two commit operations along with the Ethereum transactions.
+    let mut params = Vec::new();
+    for block_number in 1..=2 {
+        let operation = BlockSchema(&mut storage)
+            .execute_operation(get_commit_operation(BlockNumber(block_number)))
+            .await?;
+
+        let op_params = EthereumTxParams::new("commit".into(), operation);
+        let response = EthereumSchema(&mut storage)
+            .save_new_eth_tx(
+                OperationType::Commit,
+                Some(op_params.op.id.unwrap()),
+                op_params.deadline_block as i64,
+                op_params.gas_price.clone(),
+                op_params.raw_tx.clone(),
+            )
+            .await?;
+        EthereumSchema(&mut storage)
+            .add_hash_entry(response.id, &op_params.hash)
+            .await?;
+
+        params.push(op_params);
+    }
+    // The latest operation has the transaction replaced.
+    let replacement_hash = H256::from_low_u64_be(0xbeef);
+    let summary = EthereumSchema(&mut storage)
+        .load_operations_summary(None, 1)
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(summary[0].id, &replacement_hash)
+        .await?;
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params[0].hash, Some(150_000.into()))
+        .await?;
+
+    let summary = EthereumSchema(&mut storage)
+        .load_operations_summary(None, 10)
+        .await?;
+    assert_eq!(summary.len(), 2);
+
+    // The latest operation goes first.
+    assert_eq!(summary[0].block_number, Some(BlockNumber(2)));
+    assert_eq!(summary[0].op_type, OperationType::Commit);
+    assert_eq!(
+        summary[0].used_tx_hashes,
+        vec![params[1].hash, replacement_hash]
+    );
+    assert!(!summary[0].confirmed);
+    assert_eq!(summary[0].final_hash, None);
+    assert_eq!(summary[0].gas_used, None);
+
+    assert_eq!(summary[1].block_number, Some(BlockNumber(1)));
+    assert_eq!(summary[1].used_tx_hashes, vec![params[0].hash]);
+    assert!(summary[1].confirmed);
+    assert_eq!(summary[1].final_hash, Some(params[0].hash));
+    assert_eq!(summary[1].gas_used, Some(150_000.into()));
+
+    // Load operations preceding the latest one.
+    let summary_before = EthereumSchema(&mut storage)
+        .load_operations_summary(Some(summary[0].id), 10)
+        .await?;
+    assert_eq!(summary_before, summary[1..].to_vec());
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+// Built-in deps
+// External imports
+// Workspace imports
+use zksync_types::Address;
+// Local imports
+use crate::{governance::GovernanceSchema, tests::db_test, QueryResult, StorageProcessor};
+
+/// Checks that the governance state is updated by the latest event.
+#[db_test]
+async fn governance_state(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let validator = Address::random();
+    let token = Address::random();
+
+    GovernanceSchema(&mut storage)
+        .set_validator_status(validator, true)
+        .await?;
+    assert_eq!(
+        GovernanceSchema(&mut storage)
+            .load_active_validators()
+            .await?,
+        vec![validator]
+    );
+    GovernanceSchema(&mut storage)
+        .set_validator_status(validator, false)
+        .await?;
+    assert!(GovernanceSchema(&mut storage)
+        .load_active_validators()
+        .await?
+        .is_empty());
+
+    assert_eq!(
+        GovernanceSchema(&mut storage)
+            .load_network_governor()
+            .await?,
+        None
+    );
+    for governor in &[Address::random(), Address::random()] {
+        GovernanceSchema(&mut storage)
+            .set_network_governor(*governor)
+            .await?;
+        assert_eq!(
+            GovernanceSchema(&mut storage)
+                .load_network_governor()
+                .await?,
+            Some(*governor)
+        );
+    }
+
+    GovernanceSchema(&mut storage)
+        .set_token_paused(token, true)
+        .await?;
+    assert_eq!(
+        GovernanceSchema(&mut storage).load_paused_tokens().await?,
+        vec![token]
+    );
+    GovernanceSchema(&mut storage)
+        .set_token_paused(token, false)
+        .await?;
+    assert!(GovernanceSchema(&mut storage)
+        .load_paused_tokens()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// External imports
+// Local imports
+use crate::{leader_lock::LeaderLockSchema, tests::db_test, QueryResult, StorageProcessor};
+
+/// Checks that the leader lock can be held by only one connection at a time
+/// and becomes available to the other connections once released.
+#[db_test]
+async fn leader_lock(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut other_storage = StorageProcessor::establish_connection().await?;
+
+    assert!(LeaderLockSchema(&mut storage).try_acquire().await?);
+    // The lock is reentrant for the connection holding it.
+    assert!(LeaderLockSchema(&mut storage).try_acquire().await?);
+    assert!(!LeaderLockSchema(&mut other_storage).try_acquire().await?);
+
+    // The lock was acquired twice, so it must be released twice as well.
+    LeaderLockSchema(&mut storage).release().await?;
+    assert!(!LeaderLockSchema(&mut other_storage).try_acquire().await?);
+    LeaderLockSchema(&mut storage).release().await?;
+    assert!(LeaderLockSchema(&mut other_storage).try_acquire().await?);
+    assert!(!LeaderLockSchema(&mut storage).try_acquire().await?);
+
+    LeaderLockSchema(&mut other_storage).release().await?;
+    Ok(())
+}
@@ -30,12 +30,21 @@
 use zksync_crypto::rand::{SeedableRng, XorShiftRng};
 // use diesel::Connection;
 
+mod admin_audit;
+mod background_migrations;
 pub(crate) mod chain;
 mod config;
+mod cosigners;
 mod data_restore;
 mod ethereum;
+mod governance;
+mod leader_lock;
+mod outbox;
 mod prover;
+mod rejected_txs;
+mod runtime_flags;
 mod tokens;
+mod webhooks;
 
 pub use db_test_macro::test as db_test;
 
@@ -0,0 +1,155 @@
+// External imports
+use chrono::{Duration, Utc};
+use serde_json::json;
+// Workspace imports
+use zksync_types::{tx::Transfer, AccountId, Address, Nonce, SignedZkSyncTx, TokenId, ZkSyncTx};
+// Local imports
+use crate::{
+    outbox::{OutboxClaim, OutboxRequest, OutboxSchema},
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+fn signed_tx() -> SignedZkSyncTx {
+    let transfer = Transfer::new(
+        AccountId(1),
+        Address::random(),
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(0),
+        None,
+    );
+    SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(transfer)),
+        eth_sign_data: None,
+    }
+}
+
+fn outbox_request() -> OutboxRequest {
+    OutboxRequest::Tx(Box::new(signed_tx()))
+}
+
+/// Checks the lifecycle of the processed outbox request:
+/// - Request is claimed once, repeated claims report it as being in progress.
+/// - Once the result is stored, it's returned for the repeated claims.
+/// - Processed request can't be abandoned.
+/// - Processed request is removed after a while.
+#[db_test]
+async fn outbox_processed_request(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let id = OutboxSchema(&mut storage)
+        .add_request(&outbox_request())
+        .await?;
+
+    assert_eq!(
+        OutboxSchema(&mut storage).claim_request(id).await?,
+        OutboxClaim::Claimed
+    );
+    assert_eq!(
+        OutboxSchema(&mut storage).claim_request(id).await?,
+        OutboxClaim::InProgress
+    );
+    assert_eq!(OutboxSchema(&mut storage).load_result(id).await?, None);
+
+    let result = json!({ "Ok": null });
+    OutboxSchema(&mut storage)
+        .store_result(id, result.clone())
+        .await?;
+    assert_eq!(
+        OutboxSchema(&mut storage).claim_request(id).await?,
+        OutboxClaim::Processed(result.clone())
+    );
+    assert_eq!(
+        OutboxSchema(&mut storage).load_result(id).await?,
+        Some(result)
+    );
+    assert!(!OutboxSchema(&mut storage).abandon_request(id).await?);
+
+    let an_hour_ago = Utc::now() - Duration::hours(1);
+    assert_eq!(
+        OutboxSchema(&mut storage)
+            .remove_processed_requests(an_hour_ago)
+            .await?,
+        0
+    );
+    assert_eq!(
+        OutboxSchema(&mut storage)
+            .remove_processed_requests(Utc::now() + Duration::seconds(1))
+            .await?,
+        1
+    );
+    assert_eq!(
+        OutboxSchema(&mut storage).claim_request(id).await?,
+        OutboxClaim::Unknown
+    );
+
+    Ok(())
+}
+
+/// Checks that the abandoned request can't be claimed.
+#[db_test]
+async fn outbox_abandoned_request(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let id = OutboxSchema(&mut storage)
+        .add_request(&outbox_request())
+        .await?;
+
+    assert!(OutboxSchema(&mut storage).abandon_request(id).await?);
+    assert_eq!(
+        OutboxSchema(&mut storage).claim_request(id).await?,
+        OutboxClaim::Unknown
+    );
+
+    Ok(())
+}
+
+/// Checks that the requests left unprocessed are claimed along with their payload:
+/// - Recent unclaimed requests are left to their sender.
+/// - Requests claimed before the given moment are claimed again.
+/// - Processed requests are never claimed.
+#[db_test]
+async fn outbox_pending_requests(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let tx = signed_tx();
+    let request = OutboxRequest::Tx(Box::new(tx.clone()));
+    let unclaimed_id = OutboxSchema(&mut storage).add_request(&request).await?;
+    let claimed_id = OutboxSchema(&mut storage).add_request(&request).await?;
+    let processed_id = OutboxSchema(&mut storage).add_request(&request).await?;
+    for id in &[claimed_id, processed_id] {
+        OutboxSchema(&mut storage).claim_request(*id).await?;
+    }
+    OutboxSchema(&mut storage)
+        .store_result(processed_id, json!({ "Ok": null }))
+        .await?;
+
+    let an_hour_ago = Utc::now() - Duration::hours(1);
+    let pending = OutboxSchema(&mut storage)
+        .claim_pending_requests(an_hour_ago, an_hour_ago, 10)
+        .await?;
+    assert!(pending.is_empty());
+
+    let later = Utc::now() + Duration::seconds(1);
+    let pending = OutboxSchema(&mut storage)
+        .claim_pending_requests(an_hour_ago, later, 10)
+        .await?;
+    let ids: Vec<_> = pending.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![claimed_id]);
+    match &pending[0].1 {
+        OutboxRequest::Tx(pending_tx) => assert_eq!(pending_tx.tx.hash(), tx.tx.hash()),
+        request => panic!("Unexpected request: {:?}", request),
+    }
+
+    // Claimed requests are not returned until they're left unprocessed again.
+    let pending = OutboxSchema(&mut storage)
+        .claim_pending_requests(later, an_hour_ago, 10)
+        .await?;
+    let ids: Vec<_> = pending.iter().map(|(id, _)| *id).collect();
+    assert_eq!(ids, vec![unclaimed_id]);
+    assert_eq!(
+        OutboxSchema(&mut storage)
+            .claim_request(unclaimed_id)
+            .await?,
+        OutboxClaim::InProgress
+    );
+
+    Ok(())
+}
@@ -2,9 +2,13 @@ use std::time::Duration;
 // Workspace imports
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::proof::EncodedProofPlonk;
-use zksync_types::{block::PendingBlock, Action, BlockNumber};
+use zksync_types::{block::PendingBlock, Action, ActionType, BlockNumber, U256};
 // Local imports
-use crate::{chain::block::BlockSchema, prover::ProverSchema, QueryResult, StorageProcessor};
+use crate::{
+    chain::{block::BlockSchema, operations::OperationsSchema},
+    prover::{ColdStorageBlob, ProverSchema},
+    QueryResult, StorageProcessor,
+};
 use crate::{test_data::gen_operation, tests::db_test};
 
 /// Returns the smallest supported block size.
@@ -73,7 +77,7 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
     let witness = serde_json::to_value(expected.clone()).unwrap();
     storage
         .prover_schema()
-        .store_witness(BLOCK_NUMBER, witness)
+        .store_witness(BLOCK_NUMBER, U256::from(1), witness)
         .await?;
 
     // Now load it.
@@ -89,7 +93,7 @@ async fn test_store_witness(mut storage: StorageProcessor<'_>) -> QueryResult<()
     let witness = serde_json::to_value(expected.clone()).unwrap();
     storage
         .prover_schema()
-        .store_witness(BLOCK_NUMBER, witness)
+        .store_witness(BLOCK_NUMBER, U256::from(1), witness)
         .await?;
 
     let loaded = storage
@@ -322,3 +326,334 @@ async fn unstarted_prover_jobs_count(mut storage: StorageProcessor<'_>) -> Query
 
     Ok(())
 }
+
+/// Checks that the cached proof is reused for a block with the already proven commitment
+/// instead of creating a prover run for it.
+#[db_test]
+async fn proof_cache(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let prover_name = "prover_10";
+    let block_size = smallest_block_size();
+    let timeout = prover_gone_timeout();
+    let commitment = U256::from(0xdead_u64);
+    let proof = EncodedProofPlonk {
+        inputs: vec![commitment],
+        ..Default::default()
+    };
+
+    for block_number in 1..=3 {
+        BlockSchema(&mut storage)
+            .execute_operation(gen_operation(
+                BlockNumber(block_number),
+                Action::Commit,
+                block_size,
+            ))
+            .await?;
+    }
+    // The first two blocks have the same commitment.
+    for (block_number, commitment) in vec![(1, commitment), (2, commitment), (3, U256::one())] {
+        ProverSchema(&mut storage)
+            .store_witness(
+                BlockNumber(block_number),
+                commitment,
+                serde_json::json!(null),
+            )
+            .await?;
+    }
+
+    ProverSchema(&mut storage)
+        .store_proof(BlockNumber(1), &proof)
+        .await?;
+
+    // The second block should get the cached proof, so the next job is for the third block.
+    let run = ProverSchema(&mut storage)
+        .prover_run_for_next_commit(prover_name, timeout, block_size)
+        .await?
+        .expect("Can't get a prover run with a block committed");
+    assert_eq!(run.block_number, 3);
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .load_proof(BlockNumber(2))
+            .await?,
+        Some(proof)
+    );
+
+    Ok(())
+}
+
+/// Checks the workflow of the witness generation jobs assignment.
+/// - Obtain a job for the committed block without witness.
+/// - Check that the assigned block isn't given to another witness generator.
+/// - Check that the abandoned job is reassigned.
+/// - Check that the reassigned job can't be refreshed or released by its previous witness generator.
+/// - Check that the released job is reassigned right away.
+/// - Check that there are no jobs once the witness is stored.
+#[db_test]
+async fn witness_generation_jobs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let block_size = smallest_block_size();
+    let timeout = Duration::from_secs(60);
+
+    // No blocks, no jobs.
+    assert!(ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_1", timeout)
+        .await?
+        .is_none());
+
+    for block_number in 1..=2 {
+        BlockSchema(&mut storage)
+            .execute_operation(gen_operation(
+                BlockNumber(block_number),
+                Action::Commit,
+                block_size,
+            ))
+            .await?;
+    }
+
+    let job = ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_1", timeout)
+        .await?;
+    assert_eq!(job, Some(BlockNumber(1)));
+    assert!(
+        ProverSchema(&mut storage)
+            .record_witness_job_progress(BlockNumber(1), "generator_1")
+            .await?
+    );
+
+    // The first block is assigned already.
+    let job = ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_2", timeout)
+        .await?;
+    assert_eq!(job, Some(BlockNumber(2)));
+    assert!(ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_2", timeout)
+        .await?
+        .is_none());
+
+    // With the zero timeout all the jobs are considered abandoned.
+    let job = ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_2", Duration::from_secs(0))
+        .await?;
+    assert_eq!(job, Some(BlockNumber(1)));
+
+    // The generator the job was taken from can neither refresh nor release it.
+    assert!(
+        !ProverSchema(&mut storage)
+            .record_witness_job_progress(BlockNumber(1), "generator_1")
+            .await?
+    );
+    ProverSchema(&mut storage)
+        .release_witness_job(BlockNumber(1), "generator_1")
+        .await?;
+    assert!(ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_3", timeout)
+        .await?
+        .is_none());
+
+    // Released job can be taken again.
+    ProverSchema(&mut storage)
+        .release_witness_job(BlockNumber(2), "generator_2")
+        .await?;
+    let job = ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_3", timeout)
+        .await?;
+    assert_eq!(job, Some(BlockNumber(2)));
+
+    // Blocks with witness are not assigned.
+    for block_number in 1..=2 {
+        ProverSchema(&mut storage)
+            .store_witness(
+                BlockNumber(block_number),
+                U256::from(block_number),
+                serde_json::json!(null),
+            )
+            .await?;
+    }
+    assert!(ProverSchema(&mut storage)
+        .witness_job_for_next_block("generator_1", Duration::from_secs(0))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+/// Checks the cold storage bookkeeping:
+/// - Blobs of the blocks that are not verified are never archived.
+/// - Blobs of the verified blocks are archived once they are old enough.
+/// - Archived blobs are removed from the database and their keys are stored.
+#[db_test]
+async fn cold_storage_archiving(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let block_size = smallest_block_size();
+
+    for block_number in 1..=2 {
+        let block_number = BlockNumber(block_number);
+        BlockSchema(&mut storage)
+            .execute_operation(gen_operation(block_number, Action::Commit, block_size))
+            .await?;
+        ProverSchema(&mut storage)
+            .store_witness(
+                block_number,
+                U256::from(*block_number),
+                serde_json::json!(null),
+            )
+            .await?;
+        ProverSchema(&mut storage)
+            .store_proof(block_number, &Default::default())
+            .await?;
+    }
+    // Only the first block is verified.
+    BlockSchema(&mut storage)
+        .execute_operation(gen_operation(
+            BlockNumber(1),
+            Action::Verify {
+                proof: Default::default(),
+            },
+            block_size,
+        ))
+        .await?;
+    OperationsSchema(&mut storage)
+        .confirm_operation(BlockNumber(1), ActionType::VERIFY)
+        .await?;
+
+    for &blob in &[ColdStorageBlob::Proof, ColdStorageBlob::Witness] {
+        // The block was verified just now.
+        assert!(ProverSchema(&mut storage)
+            .blocks_to_archive(blob, Duration::from_secs(3600), 10)
+            .await?
+            .is_empty());
+
+        let blocks = ProverSchema(&mut storage)
+            .blocks_to_archive(blob, Duration::from_secs(0), 10)
+            .await?;
+        assert_eq!(blocks, vec![BlockNumber(1)]);
+
+        assert!(ProverSchema(&mut storage)
+            .cold_storage_key(blob, BlockNumber(1))
+            .await?
+            .is_none());
+        ProverSchema(&mut storage)
+            .archive_blob(blob, BlockNumber(1), "blob_key")
+            .await?;
+        assert_eq!(
+            ProverSchema(&mut storage)
+                .cold_storage_key(blob, BlockNumber(1))
+                .await?,
+            Some("blob_key".to_string())
+        );
+
+        // Archived blob is not archived again.
+        assert!(ProverSchema(&mut storage)
+            .blocks_to_archive(blob, Duration::from_secs(0), 10)
+            .await?
+            .is_empty());
+    }
+
+    // Archived blobs are removed from the database.
+    assert!(ProverSchema(&mut storage)
+        .load_proof(BlockNumber(1))
+        .await?
+        .is_none());
+    assert!(ProverSchema(&mut storage)
+        .get_witness(BlockNumber(1))
+        .await?
+        .is_none());
+    assert!(ProverSchema(&mut storage)
+        .load_proof(BlockNumber(2))
+        .await?
+        .is_some());
+
+    Ok(())
+}
+
+/// Checks that the debug bundle is requested by the reported proving failure,
+/// and isn't requested again once it's built.
+#[db_test]
+async fn witness_debug_bundle(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert_eq!(ProverSchema(&mut storage).debug_bundle_job().await?, None);
+    assert!(ProverSchema(&mut storage)
+        .load_debug_bundle(BlockNumber(1))
+        .await?
+        .is_none());
+
+    ProverSchema(&mut storage)
+        .report_proving_failure(BlockNumber(1), "proof verification failed")
+        .await?;
+    assert_eq!(
+        ProverSchema(&mut storage).debug_bundle_job().await?,
+        Some(BlockNumber(1))
+    );
+
+    let bundle = serde_json::json!({ "operations": [] });
+    ProverSchema(&mut storage)
+        .store_debug_bundle(
+            BlockNumber(1),
+            Some("rescue/enforce"),
+            Some(100),
+            bundle.clone(),
+        )
+        .await?;
+    assert_eq!(ProverSchema(&mut storage).debug_bundle_job().await?, None);
+
+    // Repeated failure doesn't discard the built bundle.
+    ProverSchema(&mut storage)
+        .report_proving_failure(BlockNumber(1), "unsatisfied constraint")
+        .await?;
+    assert_eq!(ProverSchema(&mut storage).debug_bundle_job().await?, None);
+
+    let loaded = ProverSchema(&mut storage)
+        .load_debug_bundle(BlockNumber(1))
+        .await?
+        .expect("Debug bundle is not stored");
+    assert_eq!(loaded.failure, "unsatisfied constraint");
+    assert_eq!(
+        loaded.unsatisfied_constraint.as_deref(),
+        Some("rescue/enforce")
+    );
+    assert_eq!(loaded.num_constraints, Some(100));
+    assert_eq!(loaded.bundle, Some(bundle));
+
+    Ok(())
+}
+
+/// Checks that the proof chunks are stored in order, the unfinished uploads are limited
+/// by the total size, and the finished upload returns the whole proof.
+#[db_test]
+async fn proof_uploads(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let timeout = Duration::from_secs(60);
+    let proof = b"serialized proof".to_vec();
+    let total_size = proof.len() as u64;
+
+    let uploaded = ProverSchema(&mut storage)
+        .store_proof_chunk(BlockNumber(1), 1, total_size, 0, &proof[..8], timeout, 40)
+        .await?;
+    assert_eq!(uploaded, Some(8));
+
+    // Chunk that doesn't continue the received data is ignored.
+    let uploaded = ProverSchema(&mut storage)
+        .store_proof_chunk(BlockNumber(1), 1, total_size, 4, &proof[4..], timeout, 40)
+        .await?;
+    assert_eq!(uploaded, Some(8));
+
+    // Another upload doesn't fit into the limit along with the unfinished one.
+    let uploaded = ProverSchema(&mut storage)
+        .store_proof_chunk(BlockNumber(1), 2, 32, 0, &proof[..8], timeout, 40)
+        .await?;
+    assert_eq!(uploaded, None);
+
+    let uploaded = ProverSchema(&mut storage)
+        .store_proof_chunk(BlockNumber(1), 1, total_size, 8, &proof[8..], timeout, 40)
+        .await?;
+    assert_eq!(uploaded, Some(total_size));
+    assert_eq!(
+        ProverSchema(&mut storage)
+            .finish_proof_upload(BlockNumber(1), 1)
+            .await?,
+        proof
+    );
+
+    // Finished upload is removed, so it doesn't count towards the limit.
+    let uploaded = ProverSchema(&mut storage)
+        .store_proof_chunk(BlockNumber(2), 2, 32, 0, &proof[..8], timeout, 40)
+        .await?;
+    assert_eq!(uploaded, Some(8));
+
+    Ok(())
+}
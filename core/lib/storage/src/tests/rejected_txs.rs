@@ -0,0 +1,96 @@
+// External imports
+// Workspace imports
+use zksync_types::{tx::Transfer, AccountId, Address, Nonce, TokenId, ZkSyncTx};
+// Local imports
+use crate::{rejected_txs::RejectedTxsSchema, tests::db_test, QueryResult, StorageProcessor};
+
+fn transfer(from: Address, nonce: u32) -> ZkSyncTx {
+    let transfer = Transfer::new(
+        AccountId(1),
+        from,
+        Address::random(),
+        TokenId(0),
+        100u32.into(),
+        10u32.into(),
+        Nonce(nonce),
+        None,
+    );
+    ZkSyncTx::Transfer(Box::new(transfer))
+}
+
+/// Checks that the rejected transactions are loaded by the hash and by the account
+/// starting with the most recent one.
+#[db_test]
+async fn rejected_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::random();
+    let first = transfer(address, 1);
+    let batch = vec![transfer(address, 2), transfer(Address::random(), 3)];
+
+    RejectedTxsSchema(&mut storage)
+        .record(
+            std::slice::from_ref(&first),
+            104,
+            "Transaction fee is too low",
+        )
+        .await?;
+    RejectedTxsSchema(&mut storage)
+        .record(&batch, 106, "Transactions batch summary fee is too low")
+        .await?;
+    // The same transaction may be rejected several times.
+    RejectedTxsSchema(&mut storage)
+        .record(std::slice::from_ref(&first), 101, "Tx nonce is too low")
+        .await?;
+
+    let rejections = RejectedTxsSchema(&mut storage)
+        .load_by_hash(first.hash().as_ref(), None, 10)
+        .await?;
+    assert_eq!(rejections.len(), 2);
+    assert_eq!(rejections[0].error_code, 101);
+    assert_eq!(rejections[1].error_code, 104);
+    assert_eq!(rejections[1].reason, "Transaction fee is too low");
+    assert_eq!(rejections[1].tx, serde_json::to_value(&first)?);
+
+    let account_txs = RejectedTxsSchema(&mut storage)
+        .load_by_address(address, None, 2)
+        .await?;
+    assert_eq!(account_txs.len(), 2);
+    assert_eq!(account_txs[0].tx_hash, first.hash().as_ref());
+    assert_eq!(account_txs[1].tx_hash, batch[0].hash().as_ref());
+    assert_eq!(account_txs[1].address, address.as_bytes());
+
+    let next_page = RejectedTxsSchema(&mut storage)
+        .load_by_address(address, Some(account_txs[1].id), 2)
+        .await?;
+    assert_eq!(next_page.len(), 1);
+    assert_eq!(next_page[0].id, rejections[1].id);
+
+    Ok(())
+}
+
+/// Checks that the rejections older than the retention period are pruned.
+#[db_test]
+async fn rejected_txs_retention(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let old = transfer(Address::random(), 1);
+    RejectedTxsSchema(&mut storage)
+        .record(std::slice::from_ref(&old), 101, "Tx nonce is too low")
+        .await?;
+    sqlx::query("UPDATE rejected_txs SET created_at = now() - interval '8 days'")
+        .execute(storage.conn())
+        .await?;
+
+    let recent = transfer(Address::random(), 2);
+    RejectedTxsSchema(&mut storage)
+        .record(std::slice::from_ref(&recent), 101, "Tx nonce is too low")
+        .await?;
+
+    let rejections = RejectedTxsSchema(&mut storage)
+        .load_by_hash(old.hash().as_ref(), None, 10)
+        .await?;
+    assert!(rejections.is_empty());
+    let rejections = RejectedTxsSchema(&mut storage)
+        .load_by_hash(recent.hash().as_ref(), None, 10)
+        .await?;
+    assert_eq!(rejections.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// External imports
+use chrono::{TimeZone, Utc};
+// Local imports
+use crate::{
+    runtime_flags::{RuntimeFlag, RuntimeFlagState, RuntimeFlagsSchema},
+    tests::db_test,
+    QueryResult, StorageProcessor,
+};
+
+/// Checks that the flag has the default value until set and can be switched afterwards.
+#[db_test]
+async fn runtime_flags(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let flag = RuntimeFlag::FastProcessing;
+    assert_eq!(
+        RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?,
+        flag.default_value()
+    );
+
+    RuntimeFlagsSchema(&mut storage)
+        .set_enabled(flag, false)
+        .await?;
+    assert!(!RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+
+    RuntimeFlagsSchema(&mut storage)
+        .set_enabled(flag, true)
+        .await?;
+    assert!(RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+
+    Ok(())
+}
+
+/// Checks that the reason of the flag switch is stored and reset by the subsequent switches.
+#[db_test]
+async fn runtime_flag_reason(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let flag = RuntimeFlag::Paused;
+    assert!(!RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+    assert_eq!(RuntimeFlagsSchema(&mut storage).reason(flag).await?, None);
+
+    RuntimeFlagsSchema(&mut storage)
+        .set_enabled_with_reason(flag, true, Some("Incident investigation"))
+        .await?;
+    assert!(RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+    assert_eq!(
+        RuntimeFlagsSchema(&mut storage).reason(flag).await?,
+        Some("Incident investigation".to_owned())
+    );
+
+    RuntimeFlagsSchema(&mut storage)
+        .set_enabled(flag, false)
+        .await?;
+    assert!(!RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+    assert_eq!(RuntimeFlagsSchema(&mut storage).reason(flag).await?, None);
+
+    Ok(())
+}
+
+/// Checks that the flag state is loaded along with the details provided by the operator.
+#[db_test]
+async fn runtime_flag_state(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let flag = RuntimeFlag::Maintenance;
+    assert_eq!(
+        RuntimeFlagsSchema(&mut storage).load_state(flag).await?,
+        RuntimeFlagState::new(false)
+    );
+
+    let state = RuntimeFlagState {
+        enabled: true,
+        reason: Some("Database migration".to_owned()),
+        until: Some(Utc.ymd(2021, 2, 25).and_hms(12, 0, 0)),
+    };
+    RuntimeFlagsSchema(&mut storage)
+        .set_state(flag, &state)
+        .await?;
+    assert_eq!(
+        RuntimeFlagsSchema(&mut storage).load_state(flag).await?,
+        state
+    );
+    assert!(RuntimeFlagsSchema(&mut storage).is_enabled(flag).await?);
+
+    Ok(())
+}
+
+/// Checks that the states of several flags are loaded at once, falling back to the defaults.
+#[db_test]
+async fn runtime_flag_states(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let flags = [
+        RuntimeFlag::FastProcessing,
+        RuntimeFlag::Paused,
+        RuntimeFlag::Maintenance,
+    ];
+    let paused = RuntimeFlagState {
+        enabled: true,
+        reason: Some("Incident investigation".to_owned()),
+        until: None,
+    };
+    RuntimeFlagsSchema(&mut storage)
+        .set_state(RuntimeFlag::Paused, &paused)
+        .await?;
+
+    assert_eq!(
+        RuntimeFlagsSchema(&mut storage).load_states(&flags).await?,
+        vec![
+            RuntimeFlagState::new(true),
+            paused,
+            RuntimeFlagState::new(false)
+        ]
+    );
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 // External imports
 use num::{rational::Ratio, BigUint};
 // Workspace imports
-use zksync_types::{tokens::TokenMarketVolume, Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{
+    tokens::{TokenListingFeeStatus, TokenMarketVolume, TokenTxRestriction, TokenTxType},
+    Address, Token, TokenId, TokenLike, TokenPrice, H256,
+};
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
 use crate::tests::db_test;
@@ -193,3 +196,218 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks the accounting of the token listing fees:
+///
+/// - Paid fees are stored as pending, every L1 payment only once.
+/// - Pending fees can be resolved as listed or refunded.
+/// - Resolved fees can't be changed anymore.
+#[db_test]
+async fn token_listing_fees(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token_address = Address::random();
+    let payer = Address::random();
+
+    let eth_tx_hash = H256::random();
+    let listed_fee = storage
+        .tokens_schema()
+        .store_listing_fee(token_address, payer, BigUint::from(100u32), eth_tx_hash)
+        .await?
+        .expect("New payment should be stored");
+    let refunded_fee = storage
+        .tokens_schema()
+        .store_listing_fee(token_address, payer, BigUint::from(200u32), H256::random())
+        .await?
+        .expect("New payment should be stored");
+    let duplicate_fee = storage
+        .tokens_schema()
+        .store_listing_fee(token_address, payer, BigUint::from(100u32), eth_tx_hash)
+        .await?;
+    assert!(duplicate_fee.is_none());
+    assert_eq!(listed_fee.status, TokenListingFeeStatus::Pending);
+    assert_eq!(listed_fee.token_address, token_address);
+    assert_eq!(listed_fee.payer, payer);
+    assert_eq!(listed_fee.amount, BigUint::from(100u32));
+    assert_eq!(listed_fee.token_id, None);
+
+    let pending = storage
+        .tokens_schema()
+        .load_listing_fees(Some(TokenListingFeeStatus::Pending))
+        .await?;
+    assert_eq!(pending, vec![listed_fee.clone(), refunded_fee.clone()]);
+
+    // List the token for the first fee and refund the second one.
+    let token = Token::new(TokenId(1), token_address, "ABC", 18);
+    storage.tokens_schema().store_token(token).await?;
+    let listed_fee = storage
+        .tokens_schema()
+        .resolve_listing_fee(
+            listed_fee.id,
+            TokenListingFeeStatus::Listed,
+            Some(TokenId(1)),
+        )
+        .await?
+        .expect("Pending fee should be resolved");
+    assert_eq!(listed_fee.status, TokenListingFeeStatus::Listed);
+    assert_eq!(listed_fee.token_id, Some(TokenId(1)));
+
+    let refunded_fee = storage
+        .tokens_schema()
+        .resolve_listing_fee(refunded_fee.id, TokenListingFeeStatus::Refunded, None)
+        .await?
+        .expect("Pending fee should be resolved");
+    assert_eq!(refunded_fee.status, TokenListingFeeStatus::Refunded);
+
+    // Resolved fees are immutable.
+    let resolved_again = storage
+        .tokens_schema()
+        .resolve_listing_fee(listed_fee.id, TokenListingFeeStatus::Refunded, None)
+        .await?;
+    assert!(resolved_again.is_none());
+
+    assert!(storage
+        .tokens_schema()
+        .load_listing_fees(Some(TokenListingFeeStatus::Pending))
+        .await?
+        .is_empty());
+    assert_eq!(
+        storage.tokens_schema().load_listing_fees(None).await?,
+        vec![listed_fee, refunded_fee]
+    );
+
+    Ok(())
+}
+
+/// Checks that the transaction restrictions of the tokens can be set, updated and removed.
+#[db_test]
+async fn token_tx_restrictions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(TokenId(1), Address::random(), "ABC", 18);
+    storage.tokens_schema().store_token(token).await?;
+
+    // Tokens are not restricted by default.
+    assert!(storage
+        .tokens_schema()
+        .load_tx_restriction(TokenId(1))
+        .await?
+        .is_none());
+
+    let restriction = TokenTxRestriction::new(
+        TokenId(1),
+        vec![TokenTxType::Withdraw, TokenTxType::ForcedExit],
+    );
+    storage
+        .tokens_schema()
+        .store_tx_restriction(&restriction)
+        .await?;
+    assert_eq!(
+        storage
+            .tokens_schema()
+            .load_tx_restriction(TokenId(1))
+            .await?,
+        Some(restriction)
+    );
+
+    // New restriction replaces the old one.
+    let restriction = TokenTxRestriction::new(TokenId(1), vec![TokenTxType::Withdraw]);
+    storage
+        .tokens_schema()
+        .store_tx_restriction(&restriction)
+        .await?;
+    assert_eq!(
+        storage.tokens_schema().load_tx_restrictions().await?,
+        vec![restriction]
+    );
+
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_tx_restriction(TokenId(1))
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_tx_restriction(TokenId(1))
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_tx_restrictions()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that paying fees in the tokens can be disabled and enabled again.
+#[db_test]
+async fn fee_disabled_tokens(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(TokenId(1), Address::random(), "ABC", 18);
+    storage.tokens_schema().store_token(token).await?;
+
+    assert!(storage
+        .tokens_schema()
+        .load_fee_disabled_tokens()
+        .await?
+        .is_empty());
+
+    assert!(
+        storage
+            .tokens_schema()
+            .store_fee_disabled_token(TokenId(1))
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .store_fee_disabled_token(TokenId(1))
+            .await?
+    );
+    assert_eq!(
+        storage.tokens_schema().load_fee_disabled_tokens().await?,
+        vec![TokenId(1)]
+    );
+
+    assert!(
+        storage
+            .tokens_schema()
+            .remove_fee_disabled_token(TokenId(1))
+            .await?
+    );
+    assert!(
+        !storage
+            .tokens_schema()
+            .remove_fee_disabled_token(TokenId(1))
+            .await?
+    );
+    assert!(storage
+        .tokens_schema()
+        .load_fee_disabled_tokens()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the listing time is stored for the new tokens.
+#[db_test]
+async fn listed_tokens(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(TokenId(1), Address::repeat_byte(0x11), "ABC", 18);
+    storage.tokens_schema().store_token(token.clone()).await?;
+
+    let tokens = storage.tokens_schema().load_listed_tokens().await?;
+    assert_eq!(tokens.len(), 2);
+    // Default `ETH` token is listed before the listing time was stored.
+    assert_eq!(tokens[0].token.id, TokenId(0));
+    assert_eq!(tokens[0].listed_at, None);
+    assert_eq!(tokens[1].token, token);
+    assert!(tokens[1].listed_at.is_some());
+
+    // Updating the token keeps its listing time.
+    let renamed = Token::new(token.id, token.address, "XYZ", 18);
+    storage.tokens_schema().store_token(renamed.clone()).await?;
+    let updated = storage.tokens_schema().load_listed_tokens().await?;
+    assert_eq!(updated[1].token, renamed);
+    assert_eq!(updated[1].listed_at, tokens[1].listed_at);
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+// Built-in imports
+use std::time::Duration;
+// External imports
+// Workspace imports
+use zksync_types::{Address, BlockNumber};
+// Local imports
+use crate::{tests::db_test, webhooks::WebhooksSchema, QueryResult, StorageProcessor};
+
+/// Checks that the webhooks are loaded by the integrator and by the subscribed addresses,
+/// and only the integrator can remove its webhook.
+#[db_test]
+async fn webhook_subscriptions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let first_address = Address::random();
+    let second_address = Address::random();
+
+    let first = WebhooksSchema(&mut storage)
+        .add_subscription("wallet", first_address, "https://wallet.io/hook", "secret")
+        .await?;
+    let second = WebhooksSchema(&mut storage)
+        .add_subscription("wallet", second_address, "https://wallet.io/hook", "secret")
+        .await?;
+    let other = WebhooksSchema(&mut storage)
+        .add_subscription("exchange", first_address, "https://exchange.io/hook", "key")
+        .await?;
+    assert_eq!(first.client, "wallet");
+    assert_eq!(first.address, first_address.as_bytes());
+
+    let subscriptions = WebhooksSchema(&mut storage)
+        .load_client_subscriptions("wallet")
+        .await?;
+    assert_eq!(subscriptions, vec![first.clone(), second.clone()]);
+
+    let subscriptions = WebhooksSchema(&mut storage)
+        .load_address_subscriptions(&[first_address, Address::random()])
+        .await?;
+    assert_eq!(subscriptions, vec![first.clone(), other.clone()]);
+
+    // Webhook of another integrator can't be removed.
+    assert!(
+        !WebhooksSchema(&mut storage)
+            .remove_subscription("wallet", other.id)
+            .await?
+    );
+    assert!(
+        WebhooksSchema(&mut storage)
+            .remove_subscription("wallet", first.id)
+            .await?
+    );
+    let subscriptions = WebhooksSchema(&mut storage)
+        .load_address_subscriptions(&[first_address, second_address])
+        .await?;
+    assert_eq!(subscriptions, vec![second, other]);
+
+    Ok(())
+}
+
+/// Checks the lifecycle of the webhook deliveries:
+/// - Claimed deliveries are not claimed again until the lease passes.
+/// - Rescheduled delivery is claimed again with the failed attempt recorded.
+/// - Deliveries are removed along with their webhook.
+#[db_test]
+async fn webhook_deliveries(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let lease = Duration::from_secs(60);
+    let subscription = WebhooksSchema(&mut storage)
+        .add_subscription(
+            "wallet",
+            Address::random(),
+            "https://wallet.io/hook",
+            "secret",
+        )
+        .await?;
+
+    let events = vec![
+        (subscription.id, serde_json::json!({ "event": 1 })),
+        (subscription.id, serde_json::json!({ "event": 2 })),
+    ];
+    WebhooksSchema(&mut storage).add_deliveries(&events).await?;
+
+    let deliveries = WebhooksSchema(&mut storage)
+        .claim_deliveries(lease, 10)
+        .await?;
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0].event, events[0].1);
+    assert_eq!(deliveries[0].url, subscription.url);
+    assert_eq!(deliveries[0].secret, subscription.secret);
+    assert_eq!(deliveries[0].attempts, 0);
+    assert!(WebhooksSchema(&mut storage)
+        .claim_deliveries(lease, 10)
+        .await?
+        .is_empty());
+
+    WebhooksSchema(&mut storage)
+        .reschedule_delivery(deliveries[0].id, Duration::from_secs(0))
+        .await?;
+    WebhooksSchema(&mut storage)
+        .remove_delivery(deliveries[1].id)
+        .await?;
+    let rescheduled = WebhooksSchema(&mut storage)
+        .claim_deliveries(Duration::from_secs(0), 10)
+        .await?;
+    assert_eq!(rescheduled.len(), 1);
+    assert_eq!(rescheduled[0].id, deliveries[0].id);
+    assert_eq!(rescheduled[0].attempts, 1);
+
+    WebhooksSchema(&mut storage)
+        .remove_subscription("wallet", subscription.id)
+        .await?;
+    assert!(WebhooksSchema(&mut storage)
+        .claim_deliveries(Duration::from_secs(0), 10)
+        .await?
+        .is_empty());
+
+    // Blocks the events were created for.
+    assert_eq!(
+        WebhooksSchema(&mut storage).load_notified_blocks().await?,
+        None
+    );
+    for &(committed, verified) in &[(2, 1), (3, 2)] {
+        WebhooksSchema(&mut storage)
+            .store_notified_blocks(BlockNumber(committed), BlockNumber(verified))
+            .await?;
+        assert_eq!(
+            WebhooksSchema(&mut storage).load_notified_blocks().await?,
+            Some((BlockNumber(committed), BlockNumber(verified)))
+        );
+    }
+
+    Ok(())
+}
@@ -2,12 +2,16 @@
 use std::collections::HashMap;
 use std::time::Instant;
 // External imports
-use num::{rational::Ratio, BigUint};
+use num::{rational::Ratio, BigInt, BigUint};
+use sqlx::types::BigDecimal;
 // Workspace imports
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::tokens::{TokenListingFee, TokenListingFeeStatus, TokenTxRestriction};
+use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice, H256};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken};
+use self::records::{
+    DBMarketVolume, DbTickerPrice, DbToken, DbTokenListingFee, DbTokenTxRestriction, ListedToken,
+};
 use crate::tokens::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
@@ -43,7 +47,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .execute(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.store_token", start.elapsed());
+        report_query!("sql.token.store_token", start);
         Ok(())
     }
 
@@ -70,10 +74,27 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             })
             .collect());
 
-        metrics::histogram!("sql.token.load_tokens", start.elapsed());
+        report_query!("sql.token.load_tokens", start);
         result
     }
 
+    /// Loads all the stored tokens along with their listing time, in the ascending order of the IDs.
+    pub async fn load_listed_tokens(&mut self) -> QueryResult<Vec<ListedToken>> {
+        let start = Instant::now();
+        let tokens = sqlx::query_as!(
+            DbToken,
+            r#"
+            SELECT * FROM tokens
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.load_listed_tokens", start);
+        Ok(tokens.into_iter().map(ListedToken::from).collect())
+    }
+
     /// Loads all the stored tokens, which have market_volume (ticker_market_volume table)
     /// not less than parameter (min_market_volume)
     pub async fn load_tokens_by_market_volume(
@@ -84,7 +105,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         let tokens = sqlx::query_as!(
             DbToken,
             r#"
-            SELECT id, address, symbol, decimals
+            SELECT id, address, symbol, decimals, listed_at
             FROM tokens
             INNER JOIN ticker_market_volume
             ON tokens.id = ticker_market_volume.token_id
@@ -104,7 +125,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             })
             .collect());
 
-        metrics::histogram!("sql.token.load_tokens_by_market_volume", start.elapsed());
+        report_query!("sql.token.load_tokens_by_market_volume", start);
         result
     }
 
@@ -120,7 +141,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .await?
         .count;
 
-        metrics::histogram!("sql.token.get_count", start.elapsed());
+        report_query!("sql.token.get_count", start);
         Ok(tokens_count)
     }
 
@@ -169,7 +190,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
             }
         };
 
-        metrics::histogram!("sql.token.get_token", start.elapsed());
+        report_query!("sql.token.get_token", start);
         Ok(db_token.map(|t| t.into()))
     }
 
@@ -190,7 +211,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_market_volume", start.elapsed());
+        report_query!("sql.token.get_market_volume", start);
         Ok(db_market_volume.map(|p| p.into()))
     }
 
@@ -217,7 +238,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_market_volume", start.elapsed());
+        report_query!("sql.token.update_market_volume", start);
         Ok(())
     }
     /// Given token id, returns its price in USD and a timestamp of the last update.
@@ -238,7 +259,7 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.get_historical_ticker_price", start.elapsed());
+        report_query!("sql.token.get_historical_ticker_price", start);
         Ok(db_price.map(|p| p.into()))
     }
 
@@ -268,7 +289,210 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         .fetch_optional(self.0.conn())
         .await?;
 
-        metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
+        report_query!("sql.token.update_historical_ticker_price", start);
         Ok(())
     }
+
+    /// Records the fee paid in the L1 for listing the token.
+    /// The payment is stored in the `pending` status.
+    ///
+    /// Every L1 transaction can be recorded only once, so `None` is returned
+    /// if the payment is recorded already.
+    pub async fn store_listing_fee(
+        &mut self,
+        token_address: Address,
+        payer: Address,
+        amount: BigUint,
+        eth_tx_hash: H256,
+    ) -> QueryResult<Option<TokenListingFee>> {
+        let start = Instant::now();
+        let fee = sqlx::query_as!(
+            DbTokenListingFee,
+            r#"
+            INSERT INTO token_listing_fees ( token_address, payer, amount, eth_tx_hash, status )
+            VALUES ( $1, $2, $3, $4, $5 )
+            ON CONFLICT (eth_tx_hash) DO NOTHING
+            RETURNING *
+            "#,
+            address_to_stored_string(&token_address),
+            address_to_stored_string(&payer),
+            BigDecimal::from(BigInt::from(amount)),
+            eth_tx_hash.as_bytes(),
+            TokenListingFeeStatus::Pending.as_str(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.store_listing_fee", start);
+        Ok(fee.map(Into::into))
+    }
+
+    /// Loads the recorded listing fees, optionally filtered by status, in the order of recording.
+    pub async fn load_listing_fees(
+        &mut self,
+        status: Option<TokenListingFeeStatus>,
+    ) -> QueryResult<Vec<TokenListingFee>> {
+        let start = Instant::now();
+        let fees = sqlx::query_as!(
+            DbTokenListingFee,
+            r#"
+            SELECT * FROM token_listing_fees
+            WHERE $1::text IS NULL OR status = $1
+            ORDER BY id ASC
+            "#,
+            status.map(TokenListingFeeStatus::as_str),
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.load_listing_fees", start);
+        Ok(fees.into_iter().map(Into::into).collect())
+    }
+
+    /// Resolves the `pending` listing fee with the given status, binding it to the listed token
+    /// if it's provided.
+    ///
+    /// Resolved fees are immutable, so `None` is returned if there is no pending fee
+    /// with the given ID.
+    pub async fn resolve_listing_fee(
+        &mut self,
+        id: i64,
+        status: TokenListingFeeStatus,
+        token_id: Option<TokenId>,
+    ) -> QueryResult<Option<TokenListingFee>> {
+        let start = Instant::now();
+        let fee = sqlx::query_as!(
+            DbTokenListingFee,
+            r#"
+            UPDATE token_listing_fees
+            SET status = $2, token_id = $3, updated_at = now()
+            WHERE id = $1 AND status = $4
+            RETURNING *
+            "#,
+            id,
+            status.as_str(),
+            token_id.map(|id| i32::from(*id)),
+            TokenListingFeeStatus::Pending.as_str(),
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.resolve_listing_fee", start);
+        Ok(fee.map(Into::into))
+    }
+
+    /// Loads the transaction types allowed for the token.
+    /// `None` is returned if the token has no restrictions.
+    pub async fn load_tx_restriction(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<TokenTxRestriction>> {
+        let start = Instant::now();
+        let restriction = sqlx::query_as!(
+            DbTokenTxRestriction,
+            "SELECT * FROM token_tx_restrictions WHERE token_id = $1",
+            i32::from(*token_id)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.load_tx_restriction", start);
+        Ok(restriction.map(Into::into))
+    }
+
+    /// Loads the restrictions of all the restricted tokens.
+    pub async fn load_tx_restrictions(&mut self) -> QueryResult<Vec<TokenTxRestriction>> {
+        let start = Instant::now();
+        let restrictions = sqlx::query_as!(
+            DbTokenTxRestriction,
+            "SELECT * FROM token_tx_restrictions ORDER BY token_id ASC"
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.load_tx_restrictions", start);
+        Ok(restrictions.into_iter().map(Into::into).collect())
+    }
+
+    /// Sets the transaction types allowed for the token, replacing the previous restriction.
+    pub async fn store_tx_restriction(
+        &mut self,
+        restriction: &TokenTxRestriction,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let allowed_tx_types: Vec<String> = restriction
+            .allowed_tx_types
+            .iter()
+            .map(|tx_type| tx_type.as_str().to_string())
+            .collect();
+        sqlx::query!(
+            "INSERT INTO token_tx_restrictions (token_id, allowed_tx_types) VALUES ($1, $2)
+            ON CONFLICT (token_id) DO UPDATE SET allowed_tx_types = $2, updated_at = now()",
+            i32::from(*restriction.token_id),
+            &allowed_tx_types
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.store_tx_restriction", start);
+        Ok(())
+    }
+
+    /// Removes the restriction, so all the transaction types are allowed for the token again.
+    /// Returns `false` if the token had no restriction.
+    pub async fn remove_tx_restriction(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM token_tx_restrictions WHERE token_id = $1",
+            i32::from(*token_id)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.remove_tx_restriction", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads the tokens not accepted by the operator to pay fees in.
+    pub async fn load_fee_disabled_tokens(&mut self) -> QueryResult<Vec<TokenId>> {
+        let start = Instant::now();
+        let tokens = sqlx::query!("SELECT token_id FROM fee_disabled_tokens ORDER BY token_id ASC")
+            .fetch_all(self.0.conn())
+            .await?;
+
+        report_query!("sql.token.load_fee_disabled_tokens", start);
+        Ok(tokens
+            .into_iter()
+            .map(|row| TokenId(row.token_id as u16))
+            .collect())
+    }
+
+    /// Disables paying fees in the token. Returns `false` if the fees were already disabled.
+    pub async fn store_fee_disabled_token(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "INSERT INTO fee_disabled_tokens (token_id) VALUES ($1) ON CONFLICT (token_id) DO NOTHING",
+            i32::from(*token_id)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.store_fee_disabled_token", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Allows paying fees in the token again, if it's suitable for the fee ticker.
+    /// Returns `false` if the fees weren't disabled.
+    pub async fn remove_fee_disabled_token(&mut self, token_id: TokenId) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM fee_disabled_tokens WHERE token_id = $1",
+            i32::from(*token_id)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.token.remove_fee_disabled_token", start);
+        Ok(result.rows_affected() > 0)
+    }
 }
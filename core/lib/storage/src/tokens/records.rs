@@ -5,8 +5,9 @@ use sqlx::{types::BigDecimal, FromRow};
 // Local imports
 use crate::tokens::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
-use zksync_types::tokens::{TokenMarketVolume, TokenPrice};
-use zksync_types::{Token, TokenId};
+use num::bigint::ToBigInt;
+use zksync_types::tokens::{TokenListingFee, TokenMarketVolume, TokenPrice, TokenTxRestriction};
+use zksync_types::{Token, TokenId, H256};
 use zksync_utils::big_decimal_to_ratio;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
@@ -15,6 +16,7 @@ pub struct DbToken {
     pub address: String,
     pub symbol: String,
     pub decimals: i16,
+    pub listed_at: Option<DateTime<Utc>>,
 }
 
 impl From<Token> for DbToken {
@@ -24,6 +26,7 @@ impl From<Token> for DbToken {
             address: address_to_stored_string(&token.address),
             symbol: token.symbol,
             decimals: token.decimals as i16,
+            listed_at: None,
         }
     }
 }
@@ -39,6 +42,24 @@ impl Into<Token> for DbToken {
     }
 }
 
+/// Token along with the time it was listed at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedToken {
+    pub token: Token,
+    /// Absent for the tokens listed before the listing time was stored.
+    pub listed_at: Option<DateTime<Utc>>,
+}
+
+impl From<DbToken> for ListedToken {
+    fn from(token: DbToken) -> Self {
+        let listed_at = token.listed_at;
+        Self {
+            token: token.into(),
+            listed_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct DbTickerPrice {
     pub token_id: i32,
@@ -71,3 +92,63 @@ impl Into<TokenMarketVolume> for DBMarketVolume {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbTokenListingFee {
+    pub id: i64,
+    pub token_address: String,
+    pub payer: String,
+    pub amount: BigDecimal,
+    pub eth_tx_hash: Vec<u8>,
+    pub status: String,
+    pub token_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Into<TokenListingFee> for DbTokenListingFee {
+    fn into(self) -> TokenListingFee {
+        TokenListingFee {
+            id: self.id,
+            token_address: stored_str_address_to_address(&self.token_address),
+            payer: stored_str_address_to_address(&self.payer),
+            amount: self
+                .amount
+                .to_bigint()
+                .and_then(|amount| amount.to_biguint())
+                .expect("Listing fee amount could not be negative"),
+            eth_tx_hash: H256::from_slice(&self.eth_tx_hash),
+            status: self
+                .status
+                .parse()
+                .expect("Invalid token listing fee status stored in the database"),
+            token_id: self.token_id.map(|id| TokenId(id as u16)),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct DbTokenTxRestriction {
+    pub token_id: i32,
+    pub allowed_tx_types: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Into<TokenTxRestriction> for DbTokenTxRestriction {
+    fn into(self) -> TokenTxRestriction {
+        TokenTxRestriction {
+            token_id: TokenId(self.token_id as u16),
+            allowed_tx_types: self
+                .allowed_tx_types
+                .iter()
+                .map(|tx_type| {
+                    tx_type
+                        .parse()
+                        .expect("Invalid token transaction type stored in the database")
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Built-in deps
+use std::{
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+// External imports
+use sqlx::postgres::types::PgInterval;
+// Workspace imports
+use zksync_types::{Address, BlockNumber};
+// Local imports
+use self::records::{StoredWebhookDelivery, StoredWebhookSubscription};
+use crate::{QueryResult, StorageProcessor};
+
+pub mod records;
+
+/// Webhooks schema keeps the webhooks registered by the integrators, which are notified
+/// by the server about the transactions and the blocks touching the subscribed addresses,
+/// and the events pending delivery to them.
+#[derive(Debug)]
+pub struct WebhooksSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
+
+impl<'a, 'c> WebhooksSchema<'a, 'c> {
+    /// Registers the webhook of the integrator for the address.
+    pub async fn add_subscription(
+        &mut self,
+        client: &str,
+        address: Address,
+        url: &str,
+        secret: &str,
+    ) -> QueryResult<StoredWebhookSubscription> {
+        let start = Instant::now();
+        let subscription = sqlx::query_as!(
+            StoredWebhookSubscription,
+            "INSERT INTO webhook_subscriptions (client, address, url, secret)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *",
+            client,
+            address.as_bytes(),
+            url,
+            secret
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.add_subscription", start);
+        Ok(subscription)
+    }
+
+    /// Removes the webhook, returns `false` if the integrator has no webhook with the given ID.
+    pub async fn remove_subscription(&mut self, client: &str, id: i64) -> QueryResult<bool> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            "DELETE FROM webhook_subscriptions WHERE id = $1 AND client = $2",
+            id,
+            client
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.remove_subscription", start);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Loads all the webhooks of the integrator in the order of the registration.
+    pub async fn load_client_subscriptions(
+        &mut self,
+        client: &str,
+    ) -> QueryResult<Vec<StoredWebhookSubscription>> {
+        let start = Instant::now();
+        let subscriptions = sqlx::query_as!(
+            StoredWebhookSubscription,
+            "SELECT * FROM webhook_subscriptions WHERE client = $1 ORDER BY id",
+            client
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.load_client_subscriptions", start);
+        Ok(subscriptions)
+    }
+
+    /// Loads the webhooks subscribed to any of the addresses.
+    pub async fn load_address_subscriptions(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<Vec<StoredWebhookSubscription>> {
+        let start = Instant::now();
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let subscriptions = sqlx::query_as!(
+            StoredWebhookSubscription,
+            "SELECT * FROM webhook_subscriptions WHERE address = ANY($1) ORDER BY id",
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.load_address_subscriptions", start);
+        Ok(subscriptions)
+    }
+
+    /// Stores the events to be delivered to the webhooks, given as pairs of the webhook ID and the event.
+    pub async fn add_deliveries(
+        &mut self,
+        deliveries: &[(i64, serde_json::Value)],
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        for (subscription_id, event) in deliveries {
+            sqlx::query!(
+                "INSERT INTO webhook_deliveries (subscription_id, event) VALUES ($1, $2)",
+                subscription_id,
+                event
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+        transaction.commit().await?;
+
+        report_query!("sql.webhooks.add_deliveries", start);
+        Ok(())
+    }
+
+    /// Claims at most `limit` deliveries due for the next attempt. Claimed deliveries are not
+    /// claimed again until `lease` passes, so the ones interrupted by the restart are retried.
+    pub async fn claim_deliveries(
+        &mut self,
+        lease: Duration,
+        limit: i64,
+    ) -> QueryResult<Vec<StoredWebhookDelivery>> {
+        let start = Instant::now();
+        let lease = PgInterval::try_from(lease).expect("Cannot convert Duration to PgInterval");
+        let deliveries = sqlx::query_as!(
+            StoredWebhookDelivery,
+            "UPDATE webhook_deliveries SET next_attempt_at = now() + $1::interval
+            FROM webhook_subscriptions
+            WHERE webhook_deliveries.id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE next_attempt_at <= now()
+                ORDER BY id
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            ) AND webhook_subscriptions.id = webhook_deliveries.subscription_id
+            RETURNING webhook_deliveries.id, webhook_deliveries.subscription_id, webhook_deliveries.event,
+                webhook_deliveries.attempts, webhook_subscriptions.url, webhook_subscriptions.secret",
+            lease,
+            limit
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.claim_deliveries", start);
+        Ok(deliveries)
+    }
+
+    /// Removes the delivery, either delivered or failed for the last time.
+    pub async fn remove_delivery(&mut self, id: i64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!("DELETE FROM webhook_deliveries WHERE id = $1", id)
+            .execute(self.0.conn())
+            .await?;
+
+        report_query!("sql.webhooks.remove_delivery", start);
+        Ok(())
+    }
+
+    /// Records the failed attempt of the delivery, so it's retried once `retry_interval` passes.
+    pub async fn reschedule_delivery(
+        &mut self,
+        id: i64,
+        retry_interval: Duration,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let retry_interval =
+            PgInterval::try_from(retry_interval).expect("Cannot convert Duration to PgInterval");
+        sqlx::query!(
+            "UPDATE webhook_deliveries
+            SET attempts = attempts + 1, next_attempt_at = now() + $2::interval
+            WHERE id = $1",
+            id,
+            retry_interval
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.reschedule_delivery", start);
+        Ok(())
+    }
+
+    /// Stores the last committed and verified blocks the events were created for.
+    pub async fn store_notified_blocks(
+        &mut self,
+        last_committed_block: BlockNumber,
+        last_verified_block: BlockNumber,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO webhook_notifier_state (last_committed_block, last_verified_block)
+            VALUES ($1, $2)
+            ON CONFLICT (id) DO UPDATE SET last_committed_block = $1, last_verified_block = $2",
+            i64::from(*last_committed_block),
+            i64::from(*last_verified_block)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        report_query!("sql.webhooks.store_notified_blocks", start);
+        Ok(())
+    }
+
+    /// Loads the last committed and verified blocks the events were created for,
+    /// `None` if the events were never created.
+    pub async fn load_notified_blocks(
+        &mut self,
+    ) -> QueryResult<Option<(BlockNumber, BlockNumber)>> {
+        let start = Instant::now();
+        let blocks = sqlx::query!(
+            "SELECT last_committed_block, last_verified_block FROM webhook_notifier_state"
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|row| {
+            (
+                BlockNumber(row.last_committed_block as u32),
+                BlockNumber(row.last_verified_block as u32),
+            )
+        });
+
+        report_query!("sql.webhooks.load_notified_blocks", start);
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,29 @@
+// External imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+// Workspace imports
+// Local imports
+
+/// Webhook registered by the integrator to be notified about the activity of the address.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, FromRow)]
+pub struct StoredWebhookSubscription {
+    pub id: i64,
+    pub client: String,
+    pub address: Vec<u8>,
+    pub url: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Event pending delivery to the webhook, along with the webhook it's delivered to.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredWebhookDelivery {
+    pub id: i64,
+    pub subscription_id: i64,
+    pub event: serde_json::Value,
+    /// Number of the failed delivery attempts.
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
@@ -1,8 +1,8 @@
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
-use super::{Nonce, TokenId};
-use zksync_basic_types::Address;
+use super::{AccountId, Nonce, TokenId};
+use zksync_basic_types::{Address, BlockNumber};
 
 use super::PubKeyHash;
 
@@ -31,6 +31,17 @@ pub enum AccountUpdate {
     },
 }
 
+/// Account update stored in the journal of the committed account updates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateJournalEntry {
+    /// Position of the update in the journal, increases monotonically.
+    pub sequence_number: u64,
+    pub block_number: BlockNumber,
+    pub account_id: AccountId,
+    pub update: AccountUpdate,
+}
+
 impl AccountUpdate {
     /// Generates an account update to revert current update.
     pub fn reversed_update(&self) -> Self {
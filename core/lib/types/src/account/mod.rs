@@ -13,7 +13,10 @@ use zksync_basic_types::Address;
 use zksync_crypto::circuit::account::{Balance, CircuitAccount};
 use zksync_crypto::circuit::utils::eth_address_to_fr;
 
-pub use self::{account_update::AccountUpdate, pubkey_hash::PubKeyHash};
+pub use self::{
+    account_update::{AccountUpdate, AccountUpdateJournalEntry},
+    pubkey_hash::PubKeyHash,
+};
 
 mod account_update;
 mod pubkey_hash;
@@ -3,7 +3,7 @@
 use super::PriorityOp;
 use super::ZkSyncOp;
 use super::{AccountId, BlockNumber, Fr};
-use crate::SignedZkSyncTx;
+use crate::{tx::TxFailureCode, SignedZkSyncTx};
 use chrono::DateTime;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -41,6 +41,9 @@ pub struct ExecutedTx {
     pub success: bool,
     pub op: Option<ZkSyncOp>,
     pub fail_reason: Option<String>,
+    /// Machine-readable class of the failure, absent for the successful transactions.
+    #[serde(default)]
+    pub fail_code: Option<TxFailureCode>,
     pub block_index: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub batch_id: Option<i64>,
@@ -2,17 +2,19 @@
 // Built-in deps
 use std::{convert::TryFrom, fmt, str::FromStr};
 // External uses
+use chrono::{DateTime, Utc};
 use ethabi::{decode, ParamType};
 use serde::{Deserialize, Serialize};
 // Local uses
-use crate::{Action, Operation};
-use zksync_basic_types::{Log, H256, U256};
+use crate::{Action, Nonce, Operation, PubKeyHash};
+use zksync_basic_types::{Address, BlockNumber, Log, TokenId, H256, U256};
 
 /// Numerical identifier of the Ethereum operation.
 pub type EthOpId = i64;
 
 /// Type of the transactions sent to the Ethereum network.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum OperationType {
     /// Commit action (`commitBlock` method of the smart contract).
     Commit,
@@ -115,6 +117,31 @@ impl PartialEq for ETHOperation {
     }
 }
 
+/// Brief information about the stored Ethereum operation, which doesn't include
+/// the encoded transaction data and the contents of the associated zkSync operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ETHOperationSummary {
+    // Numeric ID of the operation.
+    pub id: EthOpId,
+    /// Type of the operation.
+    pub op_type: OperationType,
+    /// Number of the zkSync block processed by the operation, if any.
+    pub block_number: Option<BlockNumber>,
+    /// Used nonce (fixed for all the sent transactions).
+    pub nonce: U256,
+    /// Gas price used in the last sent transaction.
+    pub last_used_gas_price: U256,
+    /// Hashes of all the sent transactions.
+    pub used_tx_hashes: Vec<H256>,
+    /// Whether the operation is confirmed on Ethereum.
+    pub confirmed: bool,
+    /// Hash of the accepted Ethereum transaction (if operation is confirmed).
+    pub final_hash: Option<H256>,
+    /// Amount of gas used by the accepted Ethereum transaction, if it was reported
+    /// when the operation was confirmed.
+    pub gas_used: Option<U256>,
+}
+
 /// Structure representing the result of the insertion of the Ethereum
 /// operation into the database.
 /// Contains the assigned nonce and ID for the operation.
@@ -126,6 +153,25 @@ pub struct InsertedOperationResponse {
     pub nonce: U256,
 }
 
+/// Snapshot of the gas adjuster state, which is used by the Ethereum sender
+/// to choose the gas price for the sent transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasAdjusterStats {
+    /// Gas price suggested for the last sent transaction.
+    pub last_suggested_price: Option<U256>,
+    /// Recently observed gas prices.
+    pub samples: Vec<U256>,
+    /// Average of the recently observed gas prices, if there are enough samples.
+    pub average_price: Option<U256>,
+    /// Current upper limit for the gas price.
+    pub price_limit: U256,
+    /// Factor applied to the average gas price to obtain the price limit.
+    pub scale_factor: f64,
+    /// Time when the snapshot was taken.
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompleteWithdrawalsTx {
     pub tx_hash: H256,
@@ -165,3 +211,156 @@ impl TryFrom<Log> for CompleteWithdrawalsTx {
         })
     }
 }
+
+/// `NewToken` event emitted by the governance contract when
+/// a new token is allowed to be used in the zkSync network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewTokenEvent {
+    pub address: Address,
+    pub id: TokenId,
+}
+
+impl TryFrom<Log> for NewTokenEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: Log) -> Result<NewTokenEvent, anyhow::Error> {
+        if event.topics.len() != 3 {
+            anyhow::bail!("Failed to parse NewTokenEvent: {:#?}", event);
+        }
+
+        let id = U256::from_big_endian(&event.topics[2].as_fixed_bytes()[..]);
+        let id = u16::try_from(id)
+            .map_err(|e| anyhow::format_err!("Token id {} conversion failed: {}", id, e))?;
+
+        Ok(NewTokenEvent {
+            address: Address::from_slice(&event.topics[1].as_fixed_bytes()[12..]),
+            id: TokenId(id),
+        })
+    }
+}
+
+/// `ValidatorStatusUpdate` event emitted by the governance contract when
+/// the validator is either activated or deactivated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorStatusUpdateEvent {
+    pub address: Address,
+    pub is_active: bool,
+}
+
+impl TryFrom<Log> for ValidatorStatusUpdateEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: Log) -> Result<ValidatorStatusUpdateEvent, anyhow::Error> {
+        if event.topics.len() != 2 {
+            anyhow::bail!("Failed to parse ValidatorStatusUpdateEvent: {:#?}", event);
+        }
+
+        let is_active = decode(&[ParamType::Bool], &event.data.0)
+            .map_err(|e| anyhow::format_err!("Event data decode: {:?}", e))?
+            .remove(0)
+            .to_bool()
+            .ok_or_else(|| anyhow::format_err!("isActive value conversion failed"))?;
+
+        Ok(ValidatorStatusUpdateEvent {
+            address: Address::from_slice(&event.topics[1].as_fixed_bytes()[12..]),
+            is_active,
+        })
+    }
+}
+
+/// `NewGovernor` event emitted by the governance contract when
+/// the network governor is changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewGovernorEvent {
+    pub address: Address,
+}
+
+impl TryFrom<Log> for NewGovernorEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: Log) -> Result<NewGovernorEvent, anyhow::Error> {
+        let address = decode(&[ParamType::Address], &event.data.0)
+            .map_err(|e| anyhow::format_err!("Event data decode: {:?}", e))?
+            .remove(0)
+            .to_address()
+            .ok_or_else(|| anyhow::format_err!("newGovernor value conversion failed"))?;
+
+        Ok(NewGovernorEvent { address })
+    }
+}
+
+/// Event of the governance contract that has to be applied to the
+/// zkSync server state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GovernanceEvent {
+    NewToken(NewTokenEvent),
+    ValidatorStatusUpdate(ValidatorStatusUpdateEvent),
+    NewGovernor(NewGovernorEvent),
+}
+
+/// `FactAuth` event emitted by the main contract when the account authorizes
+/// the new public key hash for the onchain `ChangePubKey` operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactAuthEvent {
+    pub address: Address,
+    pub nonce: Nonce,
+    pub pub_key_hash: PubKeyHash,
+}
+
+impl TryFrom<Log> for FactAuthEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(event: Log) -> Result<FactAuthEvent, anyhow::Error> {
+        if event.topics.len() != 2 {
+            anyhow::bail!("Failed to parse FactAuthEvent: {:#?}", event);
+        }
+
+        let mut decoded = decode(&[ParamType::Uint(32), ParamType::Bytes], &event.data.0)
+            .map_err(|e| anyhow::format_err!("Event data decode: {:?}", e))?;
+        let fact = decoded
+            .remove(1)
+            .to_bytes()
+            .ok_or_else(|| anyhow::format_err!("fact value conversion failed"))?;
+        let nonce = decoded
+            .remove(0)
+            .to_uint()
+            .ok_or_else(|| anyhow::format_err!("nonce value conversion failed"))?;
+
+        Ok(FactAuthEvent {
+            address: Address::from_slice(&event.topics[1].as_fixed_bytes()[12..]),
+            nonce: Nonce(nonce.as_u32()),
+            pub_key_hash: PubKeyHash::from_bytes(&fact)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_token_log(token_id: U256) -> Log {
+        let mut id_topic = [0u8; 32];
+        token_id.to_big_endian(&mut id_topic);
+        Log {
+            address: Address::zero(),
+            topics: vec![H256::zero(), H256::from(Address::random()), id_topic.into()],
+            data: Default::default(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn new_token_event_id_overflow() {
+        let event = NewTokenEvent::try_from(new_token_log(u16::MAX.into())).unwrap();
+        assert_eq!(event.id, TokenId(u16::MAX));
+
+        NewTokenEvent::try_from(new_token_log(U256::from(u16::MAX) + 1)).unwrap_err();
+    }
+}
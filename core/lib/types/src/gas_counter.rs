@@ -2,6 +2,9 @@
 //! the transactions that server sends to the Ethereum network.
 //! Server uses this module to ensure that generated transactions
 //! won't run out of the gas and won't trespass the block gas limit.
+//!
+//! Cost constants can be checked against the actual gas usage of the recent
+//! blocks with the `gas_calibrator` tool.
 // Workspace deps
 use zksync_basic_types::*;
 // Local deps
@@ -143,6 +146,11 @@ impl GasCounter {
     pub const COMPLETE_WITHDRAWALS_COST: u64 = 41_641;
     /// Some ERС20 tokens may require a lot of gas to withdrawals.
     pub const COMPLETE_WITHDRAWALS_ERC20_COST: u64 = 200_000;
+    /// Actual gas spent on the commit and verify of a block regardless of the operations it contains.
+    pub const BLOCK_OVERHEAD_COST: u64 = 140_000 + 530_000;
+    /// Share of the block overhead charged in fees for every chunk of the operation.
+    /// Corresponds to the overhead of the largest blocks, which are created when the load is high.
+    pub const BLOCK_OVERHEAD_COST_PER_CHUNK: u64 = 1_000;
 
     pub fn new() -> Self {
         Self::default()
@@ -54,14 +54,14 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
-pub use self::account::{Account, AccountUpdate, PubKeyHash};
+pub use self::account::{Account, AccountUpdate, AccountUpdateJournalEntry, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
 pub use self::fee::{BatchFee, Fee, OutputFeeType};
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, TransferOp, TransferToNewOp, WithdrawOp,
     ZkSyncOp,
 };
-pub use self::priority_ops::{Deposit, FullExit, PriorityOp, ZkSyncPriorityOp};
+pub use self::priority_ops::{Deposit, FullExit, PriorityOp, QueuedPriorityOp, ZkSyncPriorityOp};
 pub use self::tokens::{Token, TokenGenesisListItem, TokenLike, TokenPrice, TxFeeTypes};
 pub use self::tx::{ForcedExit, SignedZkSyncTx, Transfer, Withdraw, ZkSyncTx};
 
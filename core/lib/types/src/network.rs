@@ -22,6 +22,10 @@ pub enum Network {
     Rinkeby,
     /// Ethereum Ropsten testnet.
     Ropsten,
+    /// Ethereum Goerli testnet.
+    Goerli,
+    /// Ethereum Kovan testnet.
+    Kovan,
     /// Self-hosted Ethereum & zkSync networks.
     Localhost,
     /// Unknown network type.
@@ -38,6 +42,8 @@ impl FromStr for Network {
             "mainnet" => Self::Mainnet,
             "rinkeby" => Self::Rinkeby,
             "ropsten" => Self::Ropsten,
+            "goerli" => Self::Goerli,
+            "kovan" => Self::Kovan,
             "localhost" => Self::Localhost,
             "test" => Self::Test,
             another => return Err(another.to_owned()),
@@ -51,6 +57,8 @@ impl fmt::Display for Network {
             Self::Mainnet => write!(f, "mainnet"),
             Self::Rinkeby => write!(f, "rinkeby"),
             Self::Ropsten => write!(f, "ropsten"),
+            Self::Goerli => write!(f, "goerli"),
+            Self::Kovan => write!(f, "kovan"),
             Self::Localhost => write!(f, "localhost"),
             Self::Unknown => write!(f, "unknown"),
             Self::Test => write!(f, "test"),
@@ -60,14 +68,60 @@ impl fmt::Display for Network {
 
 impl Network {
     /// Returns the network chain ID on the Ethereum side.
-    pub fn chain_id(self) -> u8 {
+    pub fn chain_id(self) -> u64 {
         match self {
             Network::Mainnet => 1,
             Network::Ropsten => 3,
             Network::Rinkeby => 4,
+            Network::Goerli => 5,
             Network::Localhost => 9,
+            Network::Kovan => 42,
             Network::Unknown => panic!("Unknown chain ID"),
             Network::Test => panic!("Test chain ID"),
         }
     }
+
+    /// Returns the network with the provided chain ID on the Ethereum side.
+    /// If the chain ID doesn't correspond to any known network, `Unknown` is returned.
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        match chain_id {
+            1 => Network::Mainnet,
+            3 => Network::Ropsten,
+            4 => Network::Rinkeby,
+            5 => Network::Goerli,
+            9 => Network::Localhost,
+            42 => Network::Kovan,
+            _ => Network::Unknown,
+        }
+    }
+
+    /// Returns `true` if the network has a predefined chain ID.
+    pub fn has_known_chain_id(self) -> bool {
+        !matches!(self, Network::Unknown | Network::Test)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_id_presets() {
+        let networks = [
+            Network::Mainnet,
+            Network::Rinkeby,
+            Network::Ropsten,
+            Network::Goerli,
+            Network::Kovan,
+            Network::Localhost,
+        ];
+        for &network in &networks {
+            assert!(network.has_known_chain_id());
+            assert_eq!(Network::from_chain_id(network.chain_id()), network);
+            assert_eq!(network.to_string().parse::<Network>(), Ok(network));
+        }
+
+        assert!(!Network::Test.has_known_chain_id());
+        assert_eq!(Network::from_chain_id(100), Network::Unknown);
+    }
 }
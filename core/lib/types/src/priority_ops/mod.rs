@@ -6,7 +6,7 @@ use ethabi::{decode, ParamType};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
-use zksync_basic_types::{Address, Log, H256, U256};
+use zksync_basic_types::{Address, BlockNumber, Log, H256, U256};
 use zksync_crypto::{
     params::{
         ACCOUNT_ID_BIT_WIDTH, BALANCE_BIT_WIDTH, ETH_ADDRESS_BIT_WIDTH, FR_ADDRESS_LEN,
@@ -227,3 +227,24 @@ impl TryFrom<Log> for PriorityOp {
         })
     }
 }
+
+/// Priority operation observed by the Ethereum watcher, but not yet included into the zkSync block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedPriorityOp {
+    /// Priority operation itself.
+    pub op: PriorityOp,
+    /// Whether the operation got enough confirmations to be processed by zkSync.
+    pub confirmed: bool,
+    /// Ethereum block starting from which operation is considered confirmed.
+    pub confirmation_block: u64,
+    /// Number of Ethereum blocks mined after the block with the operation.
+    pub confirmations: u64,
+    /// Position of the operation in the queue, operations are processed starting from the zero position.
+    pub queue_position: usize,
+    /// zkSync block the operation is expected to be included into, estimated from the operations
+    /// ahead of it in the queue and the chunks the block proposer takes for the priority operations
+    /// of a single block.
+    pub expected_block: BlockNumber,
+    /// Position of the operation among the priority operations of the expected block.
+    pub processing_position: usize,
+}
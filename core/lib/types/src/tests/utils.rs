@@ -45,6 +45,7 @@ pub fn create_withdraw_tx() -> ExecutedOperations {
         success: true,
         op: Some(withdraw_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
@@ -73,6 +74,7 @@ pub fn create_change_pubkey_tx() -> ExecutedOperations {
         success: true,
         op: Some(change_pubkey_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
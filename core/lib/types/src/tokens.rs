@@ -1,10 +1,10 @@
-use crate::{Address, TokenId};
+use crate::{Address, TokenId, H256};
 use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
 use std::{fmt, fs::read_to_string, path::PathBuf, str::FromStr};
 use zksync_utils::parse_env;
-use zksync_utils::UnsignedRatioSerializeAsDecimal;
+use zksync_utils::{BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
 
 // Order of the fields is important (from more specific types to less specific types)
 /// Set of values that can be interpreted as a token descriptor.
@@ -138,6 +138,140 @@ pub struct TokenMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Status of the fee paid for the permissionless token listing.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenListingFeeStatus {
+    /// The fee is paid, but the token is not listed yet.
+    Pending,
+    /// The token was listed for the paid fee.
+    Listed,
+    /// The listing was rejected and the fee was returned to the payer.
+    Refunded,
+}
+
+impl TokenListingFeeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Listed => "listed",
+            Self::Refunded => "refunded",
+        }
+    }
+}
+
+impl FromStr for TokenListingFeeStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "listed" => Ok(Self::Listed),
+            "refunded" => Ok(Self::Refunded),
+            other => Err(anyhow::anyhow!(
+                "Unknown token listing fee status: {}",
+                other
+            )),
+        }
+    }
+}
+
+/// Fee paid in the L1 for the permissionless token listing.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenListingFee {
+    pub id: i64,
+    /// Address of the token requested to be listed.
+    pub token_address: Address,
+    /// Address of the account that paid the fee.
+    pub payer: Address,
+    /// Paid amount in wei.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+    /// Hash of the L1 transaction containing the payment.
+    pub eth_tx_hash: H256,
+    pub status: TokenListingFeeStatus,
+    /// Token ID assigned once the token is listed.
+    pub token_id: Option<TokenId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Type of the transaction that can be restricted for the particular token.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
+pub enum TokenTxType {
+    /// `Transfer` of the token.
+    Transfer,
+    /// `Withdraw` of the token.
+    Withdraw,
+    /// `ForcedExit` of the token.
+    ForcedExit,
+    /// `ChangePubKey` paying fee in the token.
+    ChangePubKey,
+}
+
+impl TokenTxType {
+    /// All the transaction types, allowed for the tokens without restrictions.
+    pub const ALL: [TokenTxType; 4] = [
+        Self::Transfer,
+        Self::Withdraw,
+        Self::ForcedExit,
+        Self::ChangePubKey,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::Withdraw => "Withdraw",
+            Self::ForcedExit => "ForcedExit",
+            Self::ChangePubKey => "ChangePubKey",
+        }
+    }
+}
+
+impl FromStr for TokenTxType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|tx_type| tx_type.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Unknown token transaction type: {}", s))
+    }
+}
+
+impl fmt::Display for TokenTxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Set of the transaction types allowed for the token by the operator,
+/// e.g. only withdrawals for a deprecated token.
+///
+/// Tokens without a restriction allow all the transaction types.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTxRestriction {
+    pub token_id: TokenId,
+    pub allowed_tx_types: Vec<TokenTxType>,
+}
+
+impl TokenTxRestriction {
+    pub fn new(token_id: TokenId, allowed_tx_types: impl Into<Vec<TokenTxType>>) -> Self {
+        Self {
+            token_id,
+            allowed_tx_types: allowed_tx_types.into(),
+        }
+    }
+
+    /// Checks whether the transaction type is allowed for the token.
+    pub fn allows(&self, tx_type: TokenTxType) -> bool {
+        self.allowed_tx_types.contains(&tx_type)
+    }
+}
+
 /// Type of transaction fees that exist in the zkSync network.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum TxFeeTypes {
@@ -158,6 +292,18 @@ pub enum TxFeeTypes {
 mod tests {
     use super::*;
 
+    #[test]
+    fn token_tx_type_str_roundtrip() {
+        for tx_type in TokenTxType::ALL.iter() {
+            assert_eq!(tx_type.as_str().parse::<TokenTxType>().unwrap(), *tx_type);
+        }
+        assert!("Close".parse::<TokenTxType>().is_err());
+
+        let restriction = TokenTxRestriction::new(TokenId(1), vec![TokenTxType::Withdraw]);
+        assert!(restriction.allows(TokenTxType::Withdraw));
+        assert!(!restriction.allows(TokenTxType::Transfer));
+    }
+
     #[test]
     fn tx_fee_type_deserialize() {
         let deserialized: TxFeeTypes =
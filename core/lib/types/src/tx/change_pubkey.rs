@@ -5,6 +5,7 @@ use crate::{
 
 use crate::account::PubKeyHash;
 use anyhow::ensure;
+use ethabi::Token;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::{Address, TokenId};
@@ -14,7 +15,10 @@ use zksync_crypto::{
 };
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{PackedEthSignature, TxSignature, VerifiedSignatureCache};
+use super::{
+    eip712::{amount_token, EIP712Struct},
+    PackedEthSignature, TxSignature, VerifiedSignatureCache,
+};
 
 /// `ChangePubKey` transaction is used to set the owner's public key hash
 /// associated with the account.
@@ -199,3 +203,21 @@ impl ChangePubKey {
             && is_fee_amount_packable(&self.fee)
     }
 }
+
+/// Typed data of `ChangePubKey` is only signed for the 2-Factor authentication. The new public key
+/// is still authorized by `eth_signature` over the text message or onchain, since the contract
+/// verifies the text message.
+impl EIP712Struct for ChangePubKey {
+    const TYPE: &'static str = "ChangePubKey(uint32 accountId,address account,bytes20 pubKeyHash,uint16 feeToken,uint256 fee,uint32 nonce)";
+
+    fn encode_fields(&self) -> Vec<Token> {
+        vec![
+            Token::Uint((*self.account_id).into()),
+            Token::Address(self.account),
+            Token::FixedBytes(self.new_pk_hash.data.to_vec()),
+            Token::Uint((*self.fee_token).into()),
+            amount_token(&self.fee),
+            Token::Uint((*self.nonce).into()),
+        ]
+    }
+}
@@ -0,0 +1,89 @@
+//! EIP-712 structured data signing of the zkSync transactions.
+//!
+//! Unlike the text messages signed via `personal_sign`, the typed data is displayed by the
+//! hardware wallets field by field. The domain binds the signature to the network, i.e. to
+//! the L1 chain ID and the address of the zkSync contract.
+
+use ethabi::{encode, Token, Uint};
+use num::BigUint;
+use parity_crypto::Keccak256;
+use zksync_basic_types::{Address, H256};
+
+/// Domain of the typed data signed by the zkSync accounts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EIP712Domain {
+    pub chain_id: u64,
+    /// Address of the zkSync contract.
+    pub verifying_contract: Address,
+}
+
+impl EIP712Domain {
+    pub const NAME: &'static str = "zkSync";
+    pub const VERSION: &'static str = "1";
+    pub const TYPE: &'static str =
+        "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+    pub fn new(chain_id: u64, verifying_contract: Address) -> Self {
+        Self {
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// Returns the domain separator, i.e. the hash of the encoded domain.
+    pub fn separator(&self) -> H256 {
+        hash_struct(
+            Self::TYPE,
+            &[
+                string_token(Self::NAME),
+                string_token(Self::VERSION),
+                Token::Uint(self.chain_id.into()),
+                Token::Address(self.verifying_contract),
+            ],
+        )
+    }
+
+    /// Returns the hash signed by the Ethereum wallet for the typed data:
+    /// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(data))`.
+    pub fn sign_hash(&self, data: &impl EIP712Struct) -> H256 {
+        let mut bytes = Vec::with_capacity(2 + 32 + 32);
+        bytes.extend_from_slice(b"\x19\x01");
+        bytes.extend_from_slice(self.separator().as_bytes());
+        bytes.extend_from_slice(data.struct_hash().as_bytes());
+        bytes.keccak256().into()
+    }
+}
+
+/// Structure signed as the EIP-712 typed data.
+pub trait EIP712Struct {
+    /// Encoded type of the structure, e.g. `Mail(address from,address to,string contents)`.
+    const TYPE: &'static str;
+
+    /// Encodes the structure fields in the order of their declaration in the `TYPE`.
+    fn encode_fields(&self) -> Vec<Token>;
+
+    /// Returns `hashStruct` of the structure as defined by EIP-712.
+    fn struct_hash(&self) -> H256 {
+        hash_struct(Self::TYPE, &self.encode_fields())
+    }
+}
+
+/// Encodes the amount as `uint256`. Amounts that don't fit into 256 bits are truncated,
+/// such transactions are rejected anyway since their amounts aren't packable.
+pub(crate) fn amount_token(amount: &BigUint) -> Token {
+    let bytes = amount.to_bytes_be();
+    Token::Uint(Uint::from_big_endian(
+        &bytes[bytes.len().saturating_sub(32)..],
+    ))
+}
+
+/// Encodes the dynamic `string` value, i.e. as the hash of its contents.
+pub(crate) fn string_token(value: &str) -> Token {
+    Token::FixedBytes(value.as_bytes().keccak256().to_vec())
+}
+
+fn hash_struct(type_: &str, fields: &[Token]) -> H256 {
+    let mut bytes = type_.as_bytes().keccak256().to_vec();
+    bytes.extend_from_slice(&encode(fields));
+    bytes.keccak256().into()
+}
@@ -0,0 +1,100 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable class of the L2 transaction failure.
+///
+/// Code is set by the state along with the human-readable reason of the failure, and is stored
+/// with the failed transaction. For the transactions of a failed batch the code corresponds
+/// to the failure of the transaction that caused the whole batch to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxFailureCode {
+    /// Balance of the account is not enough to cover the amount and the fee.
+    NotEnoughBalance,
+    /// Transaction nonce doesn't match the account nonce, e.g. because the transactions
+    /// of the account were reordered.
+    NonceMismatch,
+    /// Account involved in the transaction does not exist.
+    AccountNotFound,
+    /// Account is locked and can't send transactions.
+    AccountLocked,
+    /// Account ID specified in the transaction is incorrect.
+    IncorrectAccountId,
+    /// Token is not supported by the operation.
+    UnsupportedToken,
+    /// Transaction signature is incorrect.
+    IncorrectSignature,
+    /// Conditions of the forced exit are not met by the target account.
+    ForcedExitForbidden,
+    /// Any other failure.
+    Other,
+}
+
+impl TxFailureCode {
+    /// Returns the name of the code, the same as the serialized one.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotEnoughBalance => "notEnoughBalance",
+            Self::NonceMismatch => "nonceMismatch",
+            Self::AccountNotFound => "accountNotFound",
+            Self::AccountLocked => "accountLocked",
+            Self::IncorrectAccountId => "incorrectAccountId",
+            Self::UnsupportedToken => "unsupportedToken",
+            Self::IncorrectSignature => "incorrectSignature",
+            Self::ForcedExitForbidden => "forcedExitForbidden",
+            Self::Other => "other",
+        }
+    }
+
+    /// Returns the code of the transaction failure, or `Other` if the error
+    /// is not a `TxFailure`.
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .downcast_ref::<TxFailure>()
+            .map_or(Self::Other, |failure| failure.code)
+    }
+}
+
+impl FromStr for TxFailureCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "notEnoughBalance" => Self::NotEnoughBalance,
+            "nonceMismatch" => Self::NonceMismatch,
+            "accountNotFound" => Self::AccountNotFound,
+            "accountLocked" => Self::AccountLocked,
+            "incorrectAccountId" => Self::IncorrectAccountId,
+            "unsupportedToken" => Self::UnsupportedToken,
+            "incorrectSignature" => Self::IncorrectSignature,
+            "forcedExitForbidden" => Self::ForcedExitForbidden,
+            "other" => Self::Other,
+            _ => anyhow::bail!("Unknown transaction failure code: {}", s),
+        })
+    }
+}
+
+/// Error returned by the state for the transaction that can't be executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxFailure {
+    pub code: TxFailureCode,
+    pub message: String,
+}
+
+impl TxFailure {
+    pub fn new(code: TxFailureCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TxFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for TxFailure {}
@@ -2,8 +2,11 @@
 
 mod change_pubkey;
 mod close;
+mod eip712;
+mod failure;
 mod forced_exit;
 mod primitives;
+mod sign_message;
 mod transfer;
 mod withdraw;
 mod zksync_tx;
@@ -16,7 +19,10 @@ mod tests;
 pub use self::close::Close;
 pub use self::{
     change_pubkey::ChangePubKey,
+    eip712::{EIP712Domain, EIP712Struct},
+    failure::{TxFailure, TxFailureCode},
     forced_exit::ForcedExit,
+    sign_message::{EthSignMessageFields, EthSignMessageTemplate},
     transfer::Transfer,
     withdraw::Withdraw,
     zksync_tx::{EthSignData, SignedZkSyncTx, ZkSyncTx},
@@ -71,6 +71,20 @@ impl PackedEthSignature {
         Ok(public_to_address(&public_key))
     }
 
+    /// Signs the 32-byte hash as is, e.g. the hash of the EIP-712 typed data
+    /// signed via `eth_signTypedData`.
+    pub fn sign_hash(private_key: &H256, hash: H256) -> Result<PackedEthSignature, anyhow::Error> {
+        let secret_key = (*private_key).into();
+        let signature = sign(&secret_key, &hash)?;
+        Ok(PackedEthSignature(signature))
+    }
+
+    /// Checks signature of the hash made by `sign_hash` and returns ethereum address of the signer.
+    pub fn signature_recover_signer_from_hash(&self, hash: H256) -> Result<Address, anyhow::Error> {
+        let public_key = recover(&self.0, &hash)?;
+        Ok(public_to_address(&public_key))
+    }
+
     /// Get Ethereum address from private key.
     pub fn address_from_private_key(private_key: &H256) -> Result<Address, anyhow::Error> {
         Ok(KeyPair::from_secret((*private_key).into())?.address())
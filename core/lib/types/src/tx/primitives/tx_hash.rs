@@ -1,8 +1,10 @@
+use parity_crypto::digest::sha256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::TryInto, str::FromStr};
 
 /// Transaction hash.
 /// Essentially, a SHA-256 hash of transaction bytes encoded according to the zkSync protocol.
+/// The memo of the transfer, which is not a part of the protocol encoding, is appended to the hashed bytes.
 #[derive(Debug, Copy, Clone, PartialEq, Default, Eq, Hash, PartialOrd, Ord)]
 pub struct TxHash {
     pub(crate) data: [u8; 32],
@@ -22,6 +24,19 @@ impl TxHash {
             Some(out)
         }
     }
+
+    /// Returns the hash identifying the transactions batch.
+    /// Essentially, a SHA-256 hash of the concatenated hashes of the batch transactions.
+    pub fn batch_hash(tx_hashes: &[TxHash]) -> TxHash {
+        let bytes: Vec<u8> = tx_hashes
+            .iter()
+            .flat_map(|tx_hash| tx_hash.data.iter().copied())
+            .collect();
+
+        let mut out = TxHash { data: [0_u8; 32] };
+        out.data.copy_from_slice(&sha256(&bytes));
+        out
+    }
 }
 
 impl AsRef<[u8]> for TxHash {
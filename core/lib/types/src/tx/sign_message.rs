@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AccountId, Address, Nonce};
+
+/// Template of the human-readable message signed by the Ethereum key of the account
+/// for the 2-Factor authentication of `Transfer` and `Withdraw` transactions.
+///
+/// The template may contain the following placeholders:
+/// `{tx_type}`, `{amount}`, `{token}`, `{to}`, `{nonce}`, `{fee}`, `{account_id}` and `{network}`.
+/// Lines containing the `{network}` placeholder are omitted if no network name is set.
+/// Unknown placeholders are left as is.
+///
+/// Templates are versioned, so the server is able to accept messages in both the old
+/// and the new format while the clients migrate to the new one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthSignMessageTemplate {
+    pub version: u32,
+    pub template: String,
+    pub network: Option<String>,
+}
+
+/// Values of the transaction fields substituted into the sign message template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthSignMessageFields<'a> {
+    pub tx_type: &'a str,
+    pub amount: String,
+    pub token: &'a str,
+    pub to: Address,
+    pub nonce: Nonce,
+    pub fee: String,
+    pub account_id: AccountId,
+}
+
+impl EthSignMessageTemplate {
+    /// Version of the original sign message format.
+    pub const DEFAULT_VERSION: u32 = 1;
+    /// The original sign message format.
+    pub const DEFAULT_TEMPLATE: &'static str = "{tx_type} {amount} {token}\n\
+        To: {to}\n\
+        Nonce: {nonce}\n\
+        Fee: {fee} {token}\n\
+        Account Id: {account_id}";
+
+    pub fn new(version: u32, template: impl Into<String>, network: Option<String>) -> Self {
+        Self {
+            version,
+            template: template.into(),
+            network,
+        }
+    }
+
+    /// Builds the message for the given transaction fields.
+    pub fn render(&self, fields: &EthSignMessageFields<'_>) -> String {
+        self.template
+            .split('\n')
+            .filter_map(|line| self.render_line(line, fields))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_line(&self, line: &str, fields: &EthSignMessageFields<'_>) -> Option<String> {
+        let mut result = String::with_capacity(line.len());
+        let mut rest = line;
+
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = match rest.find('}') {
+                Some(end) => end,
+                None => break,
+            };
+            let value = match &rest[1..end] {
+                "tx_type" => fields.tx_type.to_string(),
+                "amount" => fields.amount.clone(),
+                "token" => fields.token.to_string(),
+                "to" => format!("{:?}", fields.to),
+                "nonce" => fields.nonce.to_string(),
+                "fee" => fields.fee.clone(),
+                "account_id" => fields.account_id.to_string(),
+                // The whole line is omitted if the optional field is not set.
+                "network" => self.network.clone()?,
+                _ => rest[..=end].to_string(),
+            };
+            result.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+
+        Some(result)
+    }
+}
+
+impl Default for EthSignMessageTemplate {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_VERSION, Self::DEFAULT_TEMPLATE, None)
+    }
+}
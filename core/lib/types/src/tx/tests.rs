@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use super::*;
 use crate::{
     helpers::{pack_fee_amount, pack_token_amount},
-    AccountId, Engine, Nonce, TokenId,
+    AccountId, Engine, Nonce, PubKeyHash, TokenId,
 };
 
 fn gen_pk_and_msg() -> (PrivateKey<Engine>, Vec<Vec<u8>>) {
@@ -294,3 +294,212 @@ fn test_check_signature() {
 
     assert_eq!(hex::encode(signature), "4e3298ac8cc13868dbbc94ad6fb41085ffe05b3c2eee22f88b05e69b7a5126aea723d7a3e7282ef5a32d9479c9c8dde52b3e3c462dd445dcd8158ebb6edb6000");
 }
+
+/// Checks that the default sign message template produces the original message format
+/// and that custom templates are rendered correctly.
+#[test]
+fn eth_sign_message_templates() {
+    let transfer = Transfer::new(
+        AccountId(12),
+        Address::repeat_byte(0x11),
+        Address::repeat_byte(0x22),
+        TokenId(0),
+        BigUint::from(1_500_000_000_000_000_000u64),
+        BigUint::from(1_000_000_000_000_000u64),
+        Nonce(3),
+        None,
+    );
+
+    assert_eq!(
+        transfer.get_ethereum_sign_message("ETH", 18),
+        format!(
+            "Transfer 1.5 ETH\n\
+            To: {:?}\n\
+            Nonce: 3\n\
+            Fee: 0.001 ETH\n\
+            Account Id: 12",
+            transfer.to
+        )
+    );
+
+    let template = "{tx_type} of {amount} {token} at Acme Pay\nNetwork: {network}\nNonce: {nonce}";
+    let without_network = EthSignMessageTemplate::new(2, template, None);
+    assert_eq!(
+        transfer.get_ethereum_sign_message_with_template(&without_network, "ETH", 18),
+        "Transfer of 1.5 ETH at Acme Pay\nNonce: 3"
+    );
+
+    let with_network = EthSignMessageTemplate::new(2, template, Some("mainnet".into()));
+    assert_eq!(
+        transfer.get_ethereum_sign_message_with_template(&with_network, "ETH", 18),
+        "Transfer of 1.5 ETH at Acme Pay\nNetwork: mainnet\nNonce: 3"
+    );
+
+    // Unknown placeholders are left as is.
+    let unknown = EthSignMessageTemplate::new(2, "{tx_type} {unknown} {", None);
+    assert_eq!(
+        transfer.get_ethereum_sign_message_with_template(&unknown, "ETH", 18),
+        "Transfer {unknown} {"
+    );
+}
+
+/// Checks that the transfer memo is kept in the serialized transaction, but doesn't
+/// affect the transaction bytes, and that too long memos are rejected.
+#[test]
+fn transfer_memo() {
+    let (pk, _) = gen_pk_and_msg();
+    let mut transfer = Transfer::new_signed(
+        AccountId(12),
+        Address::repeat_byte(0x11),
+        Address::repeat_byte(0x22),
+        TokenId(0),
+        BigUint::from(1_000u32),
+        BigUint::from(10u32),
+        Nonce(3),
+        &pk,
+    )
+    .unwrap();
+    let bytes = transfer.get_bytes();
+    let hash = ZkSyncTx::from(transfer.clone()).hash();
+    let message = transfer.get_ethereum_sign_message("ETH", 18);
+
+    // Transfers without memo are serialized and hashed as before.
+    let value = serde_json::to_value(&transfer).unwrap();
+    assert!(value.get("memo").is_none());
+    assert_eq!(transfer.get_signed_bytes(), bytes);
+
+    // Memo isn't processed by the circuit, but it's covered by the hash and the Ethereum message.
+    transfer.memo = Some("order #42".to_string());
+    assert_eq!(transfer.get_bytes(), bytes);
+    assert!(transfer.verify_signature().is_some());
+    assert!(transfer.check_correctness());
+    assert_ne!(ZkSyncTx::from(transfer.clone()).hash(), hash);
+    assert_eq!(
+        transfer.get_ethereum_sign_message("ETH", 18),
+        format!("{}\nMemo: order #42", message)
+    );
+
+    let deserialized: Transfer =
+        serde_json::from_value(serde_json::to_value(&transfer).unwrap()).unwrap();
+    assert_eq!(deserialized.memo, transfer.memo);
+
+    transfer.memo = Some("x".repeat(Transfer::MAX_MEMO_LENGTH + 1));
+    assert!(!transfer.check_correctness());
+}
+
+/// Checks that the failure code is kept by the error and survives its conversions.
+#[test]
+fn tx_failure_codes() {
+    let error: anyhow::Error =
+        TxFailure::new(TxFailureCode::NonceMismatch, "Nonce mismatch").into();
+    assert_eq!(TxFailureCode::of(&error), TxFailureCode::NonceMismatch);
+    assert_eq!(error.to_string(), "Nonce mismatch");
+
+    let error = anyhow::format_err!("Nonce mismatch");
+    assert_eq!(TxFailureCode::of(&error), TxFailureCode::Other);
+
+    for code in [
+        TxFailureCode::NotEnoughBalance,
+        TxFailureCode::NonceMismatch,
+        TxFailureCode::AccountNotFound,
+        TxFailureCode::AccountLocked,
+        TxFailureCode::IncorrectAccountId,
+        TxFailureCode::UnsupportedToken,
+        TxFailureCode::IncorrectSignature,
+        TxFailureCode::ForcedExitForbidden,
+        TxFailureCode::Other,
+    ]
+    .iter()
+    {
+        assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        assert_eq!(code.as_str().parse::<TxFailureCode>().unwrap(), *code);
+    }
+    assert!("unknown".parse::<TxFailureCode>().is_err());
+}
+
+#[test]
+fn batch_hash_depends_on_txs_order() {
+    let first = TxHash::from_slice(&[1; 32]).unwrap();
+    let second = TxHash::from_slice(&[2; 32]).unwrap();
+
+    let batch_hash = TxHash::batch_hash(&[first, second]);
+    assert_eq!(batch_hash, TxHash::batch_hash(&[first, second]));
+    assert_ne!(batch_hash, TxHash::batch_hash(&[second, first]));
+    assert_ne!(batch_hash, TxHash::batch_hash(&[first]));
+}
+
+/// Checks the EIP-712 typed data of the transactions against the reference values
+/// and the recovery of the typed data signer.
+#[test]
+fn eip712_typed_data() {
+    let domain = EIP712Domain::new(
+        9,
+        "70a0F165d6f8054d0d0CF8dFd4DD2005f0AF6B55".parse().unwrap(),
+    );
+    assert_eq!(
+        hex::encode(domain.separator()),
+        "be8506bf9b09576354606a4d0b32340225fcceefdd32e69b7e8e4105e68afb57"
+    );
+
+    let transfer = Transfer::new(
+        AccountId(12),
+        Address::repeat_byte(0x11),
+        Address::repeat_byte(0x22),
+        TokenId(3),
+        BigUint::from(10u64.pow(18)),
+        BigUint::from(10u64.pow(13)),
+        Nonce(5),
+        None,
+    );
+    let sign_hash = ZkSyncTx::from(transfer.clone())
+        .eip712_sign_hash(&domain)
+        .expect("transfer has typed data");
+    assert_eq!(
+        hex::encode(sign_hash),
+        "1ba7c329c48c454dd76eb39cbbece0f60fbc3a730b326862bcded666533152c6"
+    );
+
+    let mut transfer_with_memo = transfer.clone();
+    transfer_with_memo.memo = Some("order #42".to_string());
+    assert_eq!(
+        hex::encode(
+            ZkSyncTx::from(transfer_with_memo)
+                .eip712_sign_hash(&domain)
+                .expect("transfer has typed data")
+        ),
+        "737950f79de0e7e55b625a6bfc9c219a556c11ec4aa9d173e46950128e53cb8f"
+    );
+
+    let change_pubkey = ChangePubKey::new(
+        AccountId(12),
+        Address::repeat_byte(0x11),
+        PubKeyHash::from_bytes(&[0x33; 20]).unwrap(),
+        TokenId(3),
+        BigUint::from(10u64.pow(13)),
+        Nonce(5),
+        None,
+        None,
+    );
+    let change_pubkey_sign_hash = ZkSyncTx::from(change_pubkey)
+        .eip712_sign_hash(&domain)
+        .expect("ChangePubKey has typed data");
+    assert_eq!(
+        hex::encode(change_pubkey_sign_hash),
+        "4bfd0fd531438a87d5133f7411b9590d4fe31ed9bb07e86af201aa370d19562f"
+    );
+
+    // Typed data of the same transaction differs between the networks.
+    let other_domain = EIP712Domain::new(1, domain.verifying_contract);
+    assert_ne!(domain.separator(), other_domain.separator());
+
+    let private_key = "0b43c0f5b5a13a7047408d1f8c8ad32ba5879902ea6212184e0a5d1157281d76"
+        .parse()
+        .unwrap();
+    let signature = PackedEthSignature::sign_hash(&private_key, sign_hash).unwrap();
+    assert_eq!(
+        signature
+            .signature_recover_signer_from_hash(sign_hash)
+            .unwrap(),
+        PackedEthSignature::address_from_private_key(&private_key).unwrap()
+    );
+}
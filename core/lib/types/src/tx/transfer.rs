@@ -8,6 +8,7 @@ use num::BigUint;
 
 use crate::account::PubKeyHash;
 use crate::Engine;
+use ethabi::Token;
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::Address;
 use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
@@ -15,7 +16,10 @@ use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::format_units;
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{
+    eip712::{amount_token, string_token, EIP712Struct},
+    EthSignMessageFields, EthSignMessageTemplate, TxSignature, VerifiedSignatureCache,
+};
 
 /// `Transfer` transaction performs a move of funds from one zkSync account to another.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,11 +45,19 @@ pub struct Transfer {
     pub signature: TxSignature,
     #[serde(skip)]
     cached_signer: VerifiedSignatureCache,
+    /// Optional payment reference attached by the sender, e.g. to let an exchange match
+    /// the transfer with the user account.
+    /// Memo is not processed by the circuit, so it's not covered by the zkSync signature.
+    /// Instead, it's a part of the transaction hash and the messages signed by the Ethereum key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 impl Transfer {
     /// Unique identifier of the transaction type in zkSync network.
     pub const TX_TYPE: u8 = 5;
+    /// Maximum length of the memo in bytes.
+    pub const MAX_MEMO_LENGTH: usize = 256;
 
     /// Creates transaction from all the required fields.
     ///
@@ -72,6 +84,7 @@ impl Transfer {
             nonce,
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
+            memo: None,
         };
         if signature.is_some() {
             tx.cached_signer = VerifiedSignatureCache::Cached(tx.verify_signature());
@@ -114,6 +127,17 @@ impl Transfer {
         out
     }
 
+    /// Encodes the transaction data covered by the transaction hash and the Ethereum signatures:
+    /// the protocol encoding followed by the length-prefixed memo, if it's set.
+    pub fn get_signed_bytes(&self) -> Vec<u8> {
+        let mut out = self.get_bytes();
+        if let Some(memo) = &self.memo {
+            out.extend_from_slice(&(memo.len() as u32).to_be_bytes());
+            out.extend_from_slice(memo.as_bytes());
+        }
+        out
+    }
+
     /// Verifies the transaction correctness:
     ///
     /// - `account_id` field must be within supported range.
@@ -121,6 +145,7 @@ impl Transfer {
     /// - `amount` field must represent a packable value.
     /// - `fee` field must represent a packable value.
     /// - transfer recipient must not be `Adddress::zero()`.
+    /// - `memo` must not be longer than `MAX_MEMO_LENGTH` bytes.
     /// - zkSync signature must correspond to the PubKeyHash of the account.
     pub fn check_correctness(&mut self) -> bool {
         let mut valid = self.amount <= BigUint::from(u128::max_value())
@@ -129,7 +154,11 @@ impl Transfer {
             && is_fee_amount_packable(&self.fee)
             && self.account_id <= max_account_id()
             && self.token <= max_token_id()
-            && self.to != Address::zero();
+            && self.to != Address::zero()
+            && self
+                .memo
+                .as_ref()
+                .map_or(true, |memo| memo.len() <= Self::MAX_MEMO_LENGTH);
         if valid {
             let signer = self.verify_signature();
             valid = valid && signer.is_some();
@@ -151,18 +180,51 @@ impl Transfer {
 
     /// Gets message that should be signed by Ethereum keys of the account for 2-Factor authentication.
     pub fn get_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
-        format!(
-            "Transfer {amount} {token}\n\
-            To: {to:?}\n\
-            Nonce: {nonce}\n\
-            Fee: {fee} {token}\n\
-            Account Id: {account_id}",
-            amount = format_units(&self.amount, decimals),
-            token = token_symbol,
-            to = self.to,
-            nonce = *self.nonce,
-            fee = format_units(&self.fee, decimals),
-            account_id = *self.account_id,
+        self.get_ethereum_sign_message_with_template(
+            &EthSignMessageTemplate::default(),
+            token_symbol,
+            decimals,
         )
     }
+
+    /// Same as `get_ethereum_sign_message`, but builds the message using the given template.
+    /// The memo, if set, is appended as the last line regardless of the template.
+    pub fn get_ethereum_sign_message_with_template(
+        &self,
+        template: &EthSignMessageTemplate,
+        token_symbol: &str,
+        decimals: u8,
+    ) -> String {
+        let mut message = template.render(&EthSignMessageFields {
+            tx_type: "Transfer",
+            amount: format_units(&self.amount, decimals),
+            token: token_symbol,
+            to: self.to,
+            nonce: self.nonce,
+            fee: format_units(&self.fee, decimals),
+            account_id: self.account_id,
+        });
+        if let Some(memo) = &self.memo {
+            message.push_str("\nMemo: ");
+            message.push_str(memo);
+        }
+        message
+    }
+}
+
+impl EIP712Struct for Transfer {
+    const TYPE: &'static str = "Transfer(uint32 accountId,address from,address to,uint16 token,uint256 amount,uint256 fee,uint32 nonce,string memo)";
+
+    fn encode_fields(&self) -> Vec<Token> {
+        vec![
+            Token::Uint((*self.account_id).into()),
+            Token::Address(self.from),
+            Token::Address(self.to),
+            Token::Uint((*self.token).into()),
+            amount_token(&self.amount),
+            amount_token(&self.fee),
+            Token::Uint((*self.nonce).into()),
+            string_token(self.memo.as_deref().unwrap_or_default()),
+        ]
+    }
 }
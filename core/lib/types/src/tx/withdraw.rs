@@ -6,6 +6,7 @@ use num::{BigUint, ToPrimitive};
 
 use crate::account::PubKeyHash;
 use crate::Engine;
+use ethabi::Token;
 use serde::{Deserialize, Serialize};
 use zksync_basic_types::Address;
 use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
@@ -13,7 +14,10 @@ use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::format_units;
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{
+    eip712::{amount_token, EIP712Struct},
+    EthSignMessageFields, EthSignMessageTemplate, TxSignature, VerifiedSignatureCache,
+};
 
 /// `Withdraw` transaction performs a withdrawal of funds from zkSync account to L1 account.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -153,18 +157,44 @@ impl Withdraw {
 
     /// Get message that should be signed by Ethereum keys of the account for 2-Factor authentication.
     pub fn get_ethereum_sign_message(&self, token_symbol: &str, decimals: u8) -> String {
-        format!(
-            "Withdraw {amount} {token}\n\
-            To: {to:?}\n\
-            Nonce: {nonce}\n\
-            Fee: {fee} {token}\n\
-            Account Id: {account_id}",
-            amount = format_units(&self.amount, decimals),
-            token = token_symbol,
-            to = self.to,
-            nonce = *self.nonce,
-            fee = format_units(&self.fee, decimals),
-            account_id = *self.account_id,
+        self.get_ethereum_sign_message_with_template(
+            &EthSignMessageTemplate::default(),
+            token_symbol,
+            decimals,
         )
     }
+
+    /// Same as `get_ethereum_sign_message`, but builds the message using the given template.
+    pub fn get_ethereum_sign_message_with_template(
+        &self,
+        template: &EthSignMessageTemplate,
+        token_symbol: &str,
+        decimals: u8,
+    ) -> String {
+        template.render(&EthSignMessageFields {
+            tx_type: "Withdraw",
+            amount: format_units(&self.amount, decimals),
+            token: token_symbol,
+            to: self.to,
+            nonce: self.nonce,
+            fee: format_units(&self.fee, decimals),
+            account_id: self.account_id,
+        })
+    }
+}
+
+impl EIP712Struct for Withdraw {
+    const TYPE: &'static str = "Withdraw(uint32 accountId,address from,address to,uint16 token,uint256 amount,uint256 fee,uint32 nonce)";
+
+    fn encode_fields(&self) -> Vec<Token> {
+        vec![
+            Token::Uint((*self.account_id).into()),
+            Token::Address(self.from),
+            Token::Address(self.to),
+            Token::Uint((*self.token).into()),
+            amount_token(&self.amount),
+            amount_token(&self.fee),
+            Token::Uint((*self.nonce).into()),
+        ]
+    }
 }
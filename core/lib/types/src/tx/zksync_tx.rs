@@ -2,13 +2,16 @@ use num::BigUint;
 use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
 
-use zksync_basic_types::Address;
+use zksync_basic_types::{Address, H256};
 
 use crate::{
     operations::ChangePubKeyOp,
-    tx::{ChangePubKey, Close, ForcedExit, Transfer, TxEthSignature, TxHash, Withdraw},
+    tokens::TokenTxType,
+    tx::{
+        ChangePubKey, Close, EIP712Domain, ForcedExit, Transfer, TxEthSignature, TxHash, Withdraw,
+    },
     utils::deserialize_eth_message,
-    CloseOp, ForcedExitOp, Nonce, TokenLike, TransferOp, TxFeeTypes, WithdrawOp,
+    CloseOp, ForcedExitOp, Nonce, TokenId, TokenLike, TransferOp, TxFeeTypes, WithdrawOp,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -91,15 +94,7 @@ impl std::ops::Deref for SignedZkSyncTx {
 impl ZkSyncTx {
     /// Returns the hash of the transaction.
     pub fn hash(&self) -> TxHash {
-        let bytes = match self {
-            ZkSyncTx::Transfer(tx) => tx.get_bytes(),
-            ZkSyncTx::Withdraw(tx) => tx.get_bytes(),
-            ZkSyncTx::Close(tx) => tx.get_bytes(),
-            ZkSyncTx::ChangePubKey(tx) => tx.get_bytes(),
-            ZkSyncTx::ForcedExit(tx) => tx.get_bytes(),
-        };
-
-        let hash = sha256(&bytes);
+        let hash = sha256(&self.get_signed_bytes());
         let mut out = [0u8; 32];
         out.copy_from_slice(&hash);
         TxHash { data: out }
@@ -152,6 +147,27 @@ impl ZkSyncTx {
         }
     }
 
+    /// Encodes the transaction data covered by the transaction hash and the Ethereum signatures.
+    /// Unlike the protocol encoding, it includes the memo of the transfer.
+    pub fn get_signed_bytes(&self) -> Vec<u8> {
+        match self {
+            ZkSyncTx::Transfer(tx) => tx.get_signed_bytes(),
+            tx => tx.get_bytes(),
+        }
+    }
+
+    /// Returns the hash of the EIP-712 typed data signed by the Ethereum key of the account
+    /// for the 2-Factor authentication. Transactions which don't require the 2-Factor
+    /// authentication have no typed data.
+    pub fn eip712_sign_hash(&self, domain: &EIP712Domain) -> Option<H256> {
+        match self {
+            ZkSyncTx::Transfer(tx) => Some(domain.sign_hash(&**tx)),
+            ZkSyncTx::Withdraw(tx) => Some(domain.sign_hash(&**tx)),
+            ZkSyncTx::ChangePubKey(tx) => Some(domain.sign_hash(&**tx)),
+            _ => None,
+        }
+    }
+
     /// Returns the minimum amount of block chunks required for this operation.
     /// Maximum amount of chunks in block is a part of  the server and provers configuration,
     /// and this value determines the block capacity.
@@ -176,6 +192,20 @@ impl ZkSyncTx {
         matches!(self, ZkSyncTx::Close(_))
     }
 
+    /// Returns the token used by the transaction along with the transaction type,
+    /// so the operator restrictions for this token can be checked.
+    ///
+    /// For `ChangePubKey` the fee token is returned.
+    pub fn token_tx_type(&self) -> Option<(TokenId, TokenTxType)> {
+        match self {
+            ZkSyncTx::Transfer(tx) => Some((tx.token, TokenTxType::Transfer)),
+            ZkSyncTx::Withdraw(tx) => Some((tx.token, TokenTxType::Withdraw)),
+            ZkSyncTx::ForcedExit(tx) => Some((tx.token, TokenTxType::ForcedExit)),
+            ZkSyncTx::ChangePubKey(tx) => Some((tx.fee_token, TokenTxType::ChangePubKey)),
+            ZkSyncTx::Close(_) => None,
+        }
+    }
+
     /// Returns the data required to calculate fee for the transaction.
     ///
     /// Response includes the following items:
@@ -0,0 +1,252 @@
+//! File sink with the size and time based rotation.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Default amount of the rotated files kept along with the current one.
+const DEFAULT_MAX_FILES: usize = 10;
+
+/// Settings of the log file rotation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rotation {
+    /// The file is rotated once it exceeds this size in bytes.
+    pub max_size: Option<u64>,
+    /// The file is rotated once it was written for longer than this period.
+    pub period: Option<Duration>,
+    /// Amount of the rotated files kept on disk, the older ones are removed.
+    pub max_files: usize,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            period: None,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
+}
+
+impl Rotation {
+    /// Loads the rotation settings from the `MISC_LOG_FILE_*` env variables.
+    pub fn from_env() -> Self {
+        let max_size = std::env::var("MISC_LOG_FILE_MAX_SIZE_MB").ok().map(|size| {
+            let size: u64 = size
+                .parse()
+                .expect("MISC_LOG_FILE_MAX_SIZE_MB should be a number");
+            size * 1024 * 1024
+        });
+        let period = std::env::var("MISC_LOG_FILE_ROTATION")
+            .ok()
+            .and_then(|period| match period.as_str() {
+                "hourly" => Some(Duration::from_secs(60 * 60)),
+                "daily" => Some(Duration::from_secs(24 * 60 * 60)),
+                "never" => None,
+                _ => panic!("MISC_LOG_FILE_ROTATION has an unexpected value {}", period),
+            });
+        let max_files = std::env::var("MISC_LOG_FILE_MAX_FILES")
+            .map(|max_files| {
+                max_files
+                    .parse()
+                    .expect("MISC_LOG_FILE_MAX_FILES should be a number")
+            })
+            .unwrap_or(DEFAULT_MAX_FILES);
+
+        Self {
+            max_size,
+            period,
+            max_files,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RotatingFile {
+    path: PathBuf,
+    rotation: Rotation,
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf, rotation: Rotation) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            rotation,
+            file,
+            size,
+            opened_at: SystemTime::now(),
+        })
+    }
+
+    fn should_rotate(&self, incoming: usize) -> bool {
+        let size_exceeded = self
+            .rotation
+            .max_size
+            .map(|max_size| self.size > 0 && self.size + incoming as u64 > max_size)
+            .unwrap_or(false);
+        let period_elapsed = self
+            .rotation
+            .period
+            .map(|period| self.opened_at.elapsed().unwrap_or_default() >= period)
+            .unwrap_or(false);
+
+        size_exceeded || period_elapsed
+    }
+
+    /// Renames the current file to `<name>.<unix timestamp in ms>` and starts a new one.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        fs::rename(&self.path, rotated_path(&self.path, timestamp))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        self.opened_at = SystemTime::now();
+
+        self.remove_old_files()
+    }
+
+    fn remove_old_files(&self) -> io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if dir != Path::new("") => dir,
+            _ => Path::new("."),
+        };
+        let prefix = format!("{}.", file_name(&self.path));
+
+        let mut rotated: Vec<(u128, PathBuf)> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let timestamp = name.strip_prefix(&prefix)?.parse().ok()?;
+                Some((timestamp, entry.path()))
+            })
+            .collect();
+        if rotated.len() <= self.rotation.max_files {
+            return Ok(());
+        }
+
+        rotated.sort();
+        let outdated = rotated.len() - self.rotation.max_files;
+        for (_, path) in rotated.into_iter().take(outdated) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn rotated_path(path: &Path, timestamp: u128) -> PathBuf {
+    path.with_file_name(format!("{}.{}", file_name(path), timestamp))
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.should_rotate(buf.len()) {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writer of the log file, which is rotated according to the provided settings.
+#[derive(Debug, Clone)]
+pub struct RotatingFileWriter {
+    inner: Arc<Mutex<RotatingFile>>,
+}
+
+impl RotatingFileWriter {
+    pub fn new(path: impl Into<PathBuf>, rotation: Rotation) -> io::Result<Self> {
+        let file = RotatingFile::open(path.into(), rotation)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .lock()
+            .expect("log file lock is poisoned")
+            .write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner
+            .lock()
+            .expect("log file lock is poisoned")
+            .flush()
+    }
+}
+
+impl MakeWriter for RotatingFileWriter {
+    type Writer = Self;
+
+    fn make_writer(&self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the file is rotated once it exceeds the size limit and that
+    /// only the configured amount of the rotated files is kept.
+    #[test]
+    fn size_rotation() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("vlog-test-{}", std::process::id()));
+        let path = dir.join("server.log");
+        let rotation = Rotation {
+            max_size: Some(10),
+            period: None,
+            max_files: 2,
+        };
+
+        let mut writer = RotatingFileWriter::new(&path, rotation)?;
+        for line in &["first\n", "second\n", "third\n", "fourth\n"] {
+            writer.write_all(line.as_bytes())?;
+            // Rotated files are named by the timestamp in milliseconds.
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        writer.flush()?;
+
+        assert_eq!(fs::read_to_string(&path)?, "fourth\n");
+        let rotated = fs::read_dir(&dir)?.count() - 1;
+        assert_eq!(rotated, 2);
+
+        fs::remove_dir_all(dir)
+    }
+}
@@ -6,11 +6,38 @@
 //!
 //! The format of the logs in stdout can be `plain` or` json` and is set by the `MISC_LOG_FORMAT` env variable.
 //!
+//! Besides stdout, logs can be written into a rotating file set by the `MISC_LOG_FILE_PATH` env variable,
+//! see `file::Rotation::from_env` for the rotation settings. Each sink can additionally be limited
+//! to the level set by the `MISC_LOG_STDOUT_LEVEL` and `MISC_LOG_FILE_LEVEL` env variables,
+//! on top of the filter set by `RUST_LOG`.
+//!
+//! Messages sent through the channels between the actors can be wrapped into `Traced`,
+//! so the receiver continues the trace of the sender. The requests sent to another process carry
+//! the `TraceContext` instead.
+//!
+//! Spans are exported to Jaeger if the agent endpoint is set by the `MISC_JAEGER_AGENT_ENDPOINT`
+//! env variable, e.g. `localhost:6831`.
+//!
 //! Full documentation for the `tracing` crate here https://docs.rs/tracing/
 
 pub use tracing as __tracing;
 pub use tracing::{debug, info, log, trace};
 
+use tracing::{span, Event, Subscriber};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    layer::{Context, Layer},
+    prelude::*,
+    EnvFilter,
+};
+
+use self::file::{RotatingFileWriter, Rotation};
+pub use self::{trace_context::TraceContext, traced::Traced};
+
+pub mod file;
+mod trace_context;
+mod traced;
+
 #[macro_export]
 macro_rules! warn {
     ($($arg:tt)*) => {
@@ -35,14 +62,103 @@ macro_rules! error {
     };
 }
 
+/// Layer passing to the inner layer only the events not more verbose than the sink level.
+/// Unlike the regular filters, it doesn't affect the rest of the sinks.
+struct SinkFilter<L> {
+    inner: L,
+    level: LevelFilter,
+}
+
+impl<L> SinkFilter<L> {
+    fn new(inner: L, level_var: &str) -> Self {
+        let level = std::env::var(level_var)
+            .map(|level| {
+                level
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{} has an unexpected value {}", level_var, level))
+            })
+            .unwrap_or(LevelFilter::TRACE);
+        Self { inner, level }
+    }
+}
+
+impl<S: Subscriber, L: Layer<S>> Layer<S> for SinkFilter<L> {
+    fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.new_span(attrs, id, ctx)
+    }
+
+    fn on_record(&self, span: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        self.inner.on_record(span, values, ctx)
+    }
+
+    fn on_follows_from(&self, span: &span::Id, follows: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_follows_from(span, follows, ctx)
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() <= self.level {
+            self.inner.on_event(event, ctx)
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_enter(id, ctx)
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_exit(id, ctx)
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        self.inner.on_close(id, ctx)
+    }
+
+    fn on_id_change(&self, old: &span::Id, new: &span::Id, ctx: Context<'_, S>) {
+        self.inner.on_id_change(old, new, ctx)
+    }
+}
+
+fn file_writer() -> Option<RotatingFileWriter> {
+    let path = std::env::var("MISC_LOG_FILE_PATH").ok()?;
+    let writer = RotatingFileWriter::new(&path, Rotation::from_env())
+        .unwrap_or_else(|err| panic!("Unable to open the log file {}: {}", path, err));
+    Some(writer)
+}
+
 pub fn init() {
     let log_format = std::env::var("MISC_LOG_FORMAT").unwrap_or_else(|_| "plain".to_string());
+    let registry = tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(trace_context::jaeger_layer());
+    let file_writer = file_writer();
+
     match log_format.as_str() {
-        "plain" => tracing_subscriber::fmt::init(),
+        "plain" => {
+            let file_layer = file_writer.map(|writer| {
+                let layer = tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(writer);
+                SinkFilter::new(layer, "MISC_LOG_FILE_LEVEL")
+            });
+            registry
+                .with(SinkFilter::new(
+                    tracing_subscriber::fmt::layer(),
+                    "MISC_LOG_STDOUT_LEVEL",
+                ))
+                .with(file_layer)
+                .init();
+        }
         "json" => {
-            tracing_subscriber::fmt::Subscriber::builder()
-                .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-                .json()
+            let file_layer = file_writer.map(|writer| {
+                let layer = tracing_subscriber::fmt::layer().json().with_writer(writer);
+                SinkFilter::new(layer, "MISC_LOG_FILE_LEVEL")
+            });
+            registry
+                .with(SinkFilter::new(
+                    tracing_subscriber::fmt::layer().json(),
+                    "MISC_LOG_STDOUT_LEVEL",
+                ))
+                .with(file_layer)
                 .init();
         }
         _ => panic!("MISC_LOG_FORMAT has an unexpected value {}", log_format),
@@ -0,0 +1,94 @@
+//! Export of the traces and their propagation between the processes.
+
+use std::collections::HashMap;
+
+use opentelemetry::{global, sdk::propagation::TraceContextPropagator, sdk::trace::Tracer};
+use tracing::{Span, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Context of the trace sent to another process along with the request, so the spans
+/// of the receiver continue the trace of the sender.
+///
+/// The context is empty unless the traces are exported, see `jaeger_layer`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraceContext {
+    fields: HashMap<String, String>,
+}
+
+impl TraceContext {
+    /// Returns the context of the current span.
+    pub fn current() -> Self {
+        Self::of(&Span::current())
+    }
+
+    /// Returns the context of the provided span.
+    pub fn of(span: &Span) -> Self {
+        let mut fields = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&span.context(), &mut fields)
+        });
+        Self { fields }
+    }
+
+    /// Restores the context from the headers of the received request, `header` returns
+    /// the value of the header with the given name.
+    pub fn from_headers(header: impl Fn(&str) -> Option<String>) -> Self {
+        let fields = global::get_text_map_propagator(|propagator| {
+            propagator
+                .fields()
+                .filter_map(|name| Some((name.to_owned(), header(name)?)))
+                .collect()
+        });
+        Self { fields }
+    }
+
+    /// Returns the headers to be sent along with the request.
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Makes the span a child of the span this context was taken from.
+    pub fn attach_to(&self, span: &Span) {
+        if self.is_empty() {
+            return;
+        }
+        let context =
+            global::get_text_map_propagator(|propagator| propagator.extract(&self.fields));
+        span.set_parent(context);
+    }
+}
+
+/// Returns the layer exporting the spans to the Jaeger agent set by the
+/// `MISC_JAEGER_AGENT_ENDPOINT` env variable, if any.
+///
+/// Spans are exported one by one as they are closed, since the batch export requires
+/// a newer version of `tokio` than the one the server runs on.
+pub(crate) fn jaeger_layer<S>() -> Option<OpenTelemetryLayer<S, Tracer>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let endpoint = std::env::var("MISC_JAEGER_AGENT_ENDPOINT").ok()?;
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let tracer = opentelemetry_jaeger::new_pipeline()
+        .with_agent_endpoint(&endpoint)
+        .with_service_name(service_name())
+        .install_simple()
+        .unwrap_or_else(|err| panic!("Unable to export the traces to {}: {}", endpoint, err));
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Name of the service the spans are reported by, i.e. the name of the executable.
+fn service_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+        .unwrap_or_else(|| "zksync".to_owned())
+}
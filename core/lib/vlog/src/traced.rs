@@ -0,0 +1,46 @@
+//! Propagation of the tracing spans through the actor channels.
+
+use tracing::Span;
+
+/// Message sent through a channel along with the span of the sender.
+///
+/// Spans are bound to the task executing them, so without the explicit propagation every
+/// channel between the actors starts a new trace. The receiver is expected to handle the
+/// message within the received span, e.g. with `tracing::Instrument::instrument`.
+#[derive(Debug)]
+pub struct Traced<T> {
+    span: Span,
+    message: T,
+}
+
+impl<T> Traced<T> {
+    /// Wraps the message into the current span.
+    pub fn new(message: T) -> Self {
+        Self {
+            span: Span::current(),
+            message,
+        }
+    }
+
+    /// Wraps the message into the provided span.
+    pub fn with_span(span: Span, message: T) -> Self {
+        Self { span, message }
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn message(&self) -> &T {
+        &self.message
+    }
+
+    pub fn into_parts(self) -> (Span, T) {
+        (self.span, self.message)
+    }
+
+    /// Drops the span, returning the message.
+    pub fn into_inner(self) -> T {
+        self.message
+    }
+}
@@ -4,6 +4,7 @@ use futures::{
 };
 use std::thread::JoinHandle;
 use tokio::runtime::Runtime;
+use vlog::Traced;
 use zksync_core::committer::CommitRequest;
 use zksync_core::state_keeper::{start_state_keeper, StateKeeperRequest, ZkSyncStateKeeper};
 use zksync_types::{
@@ -26,7 +27,7 @@ pub async fn state_keeper_get_account(
 
 pub struct StateKeeperChannels {
     pub(crate) requests: mpsc::Sender<StateKeeperRequest>,
-    pub(crate) new_blocks: mpsc::Receiver<CommitRequest>,
+    pub(crate) new_blocks: mpsc::Receiver<Traced<CommitRequest>>,
 }
 
 // Thread join handle and stop channel sender.
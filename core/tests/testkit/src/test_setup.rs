@@ -4,6 +4,7 @@ use anyhow::bail;
 use futures::{channel::mpsc, SinkExt, StreamExt};
 use num::BigUint;
 use std::collections::HashMap;
+use vlog::Traced;
 use zksync_core::committer::{BlockCommitRequest, CommitRequest};
 use zksync_core::mempool::ProposedBlock;
 use zksync_core::state_keeper::StateKeeperRequest;
@@ -32,7 +33,7 @@ use crate::types::*;
 /// using `execute_incorrect_tx`
 pub struct TestSetup {
     pub state_keeper_request_sender: mpsc::Sender<StateKeeperRequest>,
-    pub proposed_blocks_receiver: mpsc::Receiver<CommitRequest>,
+    pub proposed_blocks_receiver: mpsc::Receiver<Traced<CommitRequest>>,
 
     pub accounts: AccountSet,
     pub tokens: HashMap<TokenId, Address>,
@@ -569,7 +570,7 @@ impl TestSetup {
     /// the pending blocks.
     async fn await_for_block_commit_request(&mut self) -> BlockCommitRequest {
         while let Some(new_block_event) = self.proposed_blocks_receiver.next().await {
-            match new_block_event {
+            match new_block_event.into_inner() {
                 CommitRequest::Block((new_block, _)) => {
                     return new_block;
                 }
@@ -589,7 +590,7 @@ impl TestSetup {
             .next()
             .await
             .expect("StateKeeper sender dropped");
-        match new_block_event {
+        match new_block_event.into_inner() {
             CommitRequest::Block((new_block, _)) => {
                 panic!(
                     "Expected pending block, got full block proposed. Block: {:?}",
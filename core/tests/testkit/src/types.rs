@@ -7,7 +7,7 @@ use zksync_types::TokenId;
 
 #[derive(Debug)]
 pub struct TestkitConfig {
-    pub chain_id: u8,
+    pub chain_id: u64,
     pub gas_price_factor: f64,
     pub web3_url: String,
     pub available_block_chunk_sizes: Vec<usize>,
@@ -20,7 +20,7 @@ impl TestkitConfig {
             chain_id: config.eth_client.chain_id,
             gas_price_factor: config.eth_client.gas_price_factor,
             web3_url: config.eth_client.web3_url(),
-            available_block_chunk_sizes: config.chain.state_keeper.block_chunk_sizes,
+            available_block_chunk_sizes: config.chain.block_chunk_sizes(),
         }
     }
 }
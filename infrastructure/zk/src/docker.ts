@@ -4,6 +4,10 @@ import * as contract from './contract';
 
 const IMAGES = [
     'server',
+    'core',
+    'api',
+    'eth-sender',
+    'witness-generator',
     'prover',
     'nginx',
     'geth',
@@ -16,10 +20,15 @@ const IMAGES = [
     'ci-integration-test'
 ];
 
+// Images built from the Rust workspace: the combined server, its components and the prover.
+const RUST_IMAGES = ['server', 'core', 'api', 'eth-sender', 'witness-generator', 'prover'];
+const TAGGED_IMAGES = ['nginx', ...RUST_IMAGES];
+
 async function dockerCommand(command: 'push' | 'build', image: string) {
     if (image == 'rust') {
-        await dockerCommand(command, 'server');
-        await dockerCommand(command, 'prover');
+        for (const rustImage of RUST_IMAGES) {
+            await dockerCommand(command, rustImage);
+        }
         return;
     }
     if (!IMAGES.includes(image)) {
@@ -39,19 +48,19 @@ async function _build(image: string) {
     if (image == 'nginx') {
         await utils.spawn('yarn explorer build');
     }
-    if (image == 'server' || image == 'prover') {
+    if (RUST_IMAGES.includes(image)) {
         await contract.build();
         await contract.buildDev();
     }
     const { stdout: imageTag } = await utils.exec('git rev-parse --short HEAD');
     const latestImage = `-t matterlabs/${image}:latest`;
-    const taggedImage = ['nginx', 'server', 'prover'].includes(image) ? `-t matterlabs/${image}:${imageTag}` : '';
+    const taggedImage = TAGGED_IMAGES.includes(image) ? `-t matterlabs/${image}:${imageTag}` : '';
     await utils.spawn(`DOCKER_BUILDKIT=1 docker build ${latestImage} ${taggedImage} -f ./docker/${image}/Dockerfile .`);
 }
 
 async function _push(image: string) {
     await utils.spawn(`docker push matterlabs/${image}:latest`);
-    if (['nginx', 'server', 'prover'].includes(image)) {
+    if (TAGGED_IMAGES.includes(image)) {
         const { stdout: imageTag } = await utils.exec('git rev-parse --short HEAD');
         await utils.spawn(`docker push matterlabs/${image}:${imageTag}`);
     }
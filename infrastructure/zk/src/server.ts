@@ -4,14 +4,16 @@ import * as env from './env';
 import fs from 'fs';
 import * as db from './db/db';
 
-export async function server() {
-    await utils.spawn('cargo run --bin zksync_server --release');
+export async function server(components?: string) {
+    const options = components ? ` -- --components=${components}` : '';
+    await utils.spawn(`cargo run --bin zksync_server --release${options}`);
 }
 
-export async function genesis() {
+export async function genesis(spec?: string) {
     await db.reset();
     await utils.confirmAction();
-    await utils.spawn('cargo run --bin zksync_server --release -- --genesis | tee genesis.log');
+    const specOption = spec ? ` --genesis-spec ${spec}` : '';
+    await utils.spawn(`cargo run --bin zksync_server --release -- --genesis${specOption} | tee genesis.log`);
     const genesisRoot = fs.readFileSync('genesis.log').toString();
     const date = new Date();
     const [year, month, day, hour, minute, second] = [
@@ -32,10 +34,12 @@ export async function genesis() {
 export const command = new Command('server')
     .description('start zksync server')
     .option('--genesis', 'generate genesis data via server')
+    .option('--genesis-spec <path>', 'genesis spec file with the pre-funded accounts, tokens and governance parameters')
+    .option('--components <components>', 'comma-separated list of the server components to run')
     .action(async (cmd: Command) => {
         if (cmd.genesis) {
-            await genesis();
+            await genesis(cmd.genesisSpec);
         } else {
-            await server();
+            await server(cmd.components);
         }
     });
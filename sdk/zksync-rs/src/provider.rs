@@ -26,6 +26,9 @@ pub fn get_rpc_addr(network: Network) -> &'static str {
         Network::Rinkeby => "https://rinkeby-api.zksync.io/jsrpc",
         Network::Ropsten => "https://ropsten-api.zksync.io/jsrpc",
         Network::Localhost => "http://127.0.0.1:3030",
+        Network::Goerli | Network::Kovan => {
+            panic!("Attempt to create a provider from a network without zkSync deployment")
+        }
         Network::Unknown => panic!("Attempt to create a provider from an unknown network"),
         Network::Test => panic!("Attempt to create a provider from an test network"),
     }
@@ -138,6 +138,12 @@ export class Provider {
         return await this.transport.request('get_eth_tx_for_withdrawal', [withdrawal_hash]);
     }
 
+    // get the message that has to be signed with the Ethereum key to submit the transaction,
+    // `null` is returned if the transaction doesn't require the Ethereum signature
+    async getTxMessageToSign(tx: any): Promise<string | null> {
+        return await this.transport.request('get_tx_message_to_sign', [tx]);
+    }
+
     async notifyPriorityOp(serialId: number, action: 'COMMIT' | 'VERIFY'): Promise<PriorityOperationReceipt> {
         if (this.transport.subscriptionsSupported()) {
             return await new Promise((resolve) => {
@@ -57,7 +57,7 @@ export type EthSignerType = {
 };
 
 export interface TxEthSignature {
-    type: 'EthereumSignature' | 'EIP1271Signature';
+    type: 'EthereumSignature' | 'EIP1271Signature' | 'EIP712Signature';
     signature: string;
 }
 